@@ -0,0 +1,25 @@
+#import bevy_pbr::vertex_displacement::{morph_vertex, vertex_output, time}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::prepass_io::{Vertex, VertexOutput}
+#else
+#import bevy_pbr::forward_io::{Vertex, VertexOutput}
+#endif
+
+struct WaveExtension {
+    amplitude: f32,
+    frequency: f32,
+}
+
+@group(2) @binding(100)
+var<uniform> wave: WaveExtension;
+
+// Used by the main pass and by the prepass, which renders the shadows, so both see the same displacement.
+@vertex
+fn vertex(vertex_in: Vertex) -> VertexOutput {
+    var vertex = morph_vertex(vertex_in);
+    let offset = sin(time() * 2.0 + vertex.position.y * wave.frequency) * wave.amplitude;
+    vertex.position.x += offset;
+    vertex.position.z += offset;
+    return vertex_output(vertex, vertex_in.instance_index);
+}
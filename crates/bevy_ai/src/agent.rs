@@ -0,0 +1,89 @@
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+use crate::{BehaviorStatus, BehaviorTree};
+
+/// Ticks a [`BehaviorTree`] every frame for this entity.
+///
+/// Conditions of the tree are checked against this entity, and actions receive it in their
+/// [`ActionInput`](crate::ActionInput).
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct BehaviorTreeAgent {
+    /// The ticked tree.
+    pub tree: Handle<BehaviorTree>,
+    /// Whether ticking is paused.
+    pub paused: bool,
+    /// The status of each node of the tree on the last tick, in depth-first pre-order, or `None`
+    /// for the nodes that weren't ticked.
+    statuses: Vec<Option<BehaviorStatus>>,
+}
+
+impl BehaviorTreeAgent {
+    /// Creates an agent ticking `tree`.
+    pub fn new(tree: Handle<BehaviorTree>) -> Self {
+        Self {
+            tree,
+            ..Default::default()
+        }
+    }
+
+    /// The status of the root of the tree on the last tick, or `None` if the tree wasn't ticked
+    /// yet.
+    pub fn status(&self) -> Option<BehaviorStatus> {
+        self.node_status(0)
+    }
+
+    /// The status of the node at `index` in depth-first pre-order on the last tick, or `None` if
+    /// it wasn't ticked.
+    pub fn node_status(&self, index: usize) -> Option<BehaviorStatus> {
+        self.statuses.get(index).copied().flatten()
+    }
+
+    /// The status of each node of the tree on the last tick, in depth-first pre-order.
+    pub fn statuses(&self) -> &[Option<BehaviorStatus>] {
+        &self.statuses
+    }
+
+    /// Forgets the last tick, so that running actions start over on the next tick.
+    pub fn reset(&mut self) {
+        self.statuses.clear();
+    }
+}
+
+/// Ticks the [`BehaviorTree`] of every [`BehaviorTreeAgent`].
+///
+/// Actions are one-shot systems, so this system is exclusive and ticks agents one at a time.
+pub fn tick_behavior_trees(
+    world: &mut World,
+    agents: &mut QueryState<(Entity, &BehaviorTreeAgent)>,
+    mut ticked: Local<Vec<(Entity, Handle<BehaviorTree>)>>,
+) {
+    ticked.extend(
+        agents
+            .iter(world)
+            .filter(|(_, agent)| !agent.paused)
+            .map(|(entity, agent)| (entity, agent.tree.clone_weak())),
+    );
+
+    for (entity, handle) in ticked.drain(..) {
+        let Some(tree) = world
+            .resource::<Assets<BehaviorTree>>()
+            .get(&handle)
+            .cloned()
+        else {
+            continue;
+        };
+        let Some(mut agent) = world.get_mut::<BehaviorTreeAgent>(entity) else {
+            continue;
+        };
+        let previous = std::mem::take(&mut agent.statuses);
+        let mut statuses = vec![None; tree.len()];
+        tree.tick(entity, world, &previous, &mut statuses);
+        // Actions may have despawned the agent, or removed its tree.
+        if let Some(mut agent) = world.get_mut::<BehaviorTreeAgent>(entity) {
+            agent.statuses = statuses;
+        }
+    }
+}
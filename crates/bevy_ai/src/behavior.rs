@@ -0,0 +1,231 @@
+use bevy_app::App;
+use bevy_ecs::{
+    entity::Entity,
+    query::{QueryFilter, QueryItem, ReadOnlyQueryData},
+    system::{IntoSystem, Resource, SystemId},
+    world::World,
+};
+use bevy_reflect::{FromType, GetTypeRegistration, Reflect, TypePath};
+use bevy_utils::tracing::warn;
+
+/// The result of ticking a node of a [`BehaviorTree`](crate::BehaviorTree).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum BehaviorStatus {
+    /// The node succeeded.
+    Success,
+    /// The node failed.
+    Failure,
+    /// The node hasn't finished yet, and should be ticked again.
+    Running,
+}
+
+/// A condition of a [`BehaviorTree`](crate::BehaviorTree), checked against the agent ticking the
+/// tree with a query.
+///
+/// The condition succeeds if the agent matches the query and [`check`](Self::check) returns
+/// `true` for its item. Conditions only depending on the components of the agent can leave
+/// `check` returning `true` and rely on [`Filter`](Self::Filter):
+///
+/// ```
+/// # use bevy_ai::BehaviorCondition;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::QueryItem;
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component)]
+/// struct Hunger(f32);
+///
+/// #[derive(Component)]
+/// struct Sleeping;
+///
+/// /// Whether the agent is awake and hungrier than `threshold`.
+/// #[derive(Reflect)]
+/// struct IsHungry {
+///     threshold: f32,
+/// }
+///
+/// impl BehaviorCondition for IsHungry {
+///     type Data = &'static Hunger;
+///     type Filter = Without<Sleeping>;
+///
+///     fn check(&self, hunger: QueryItem<&Hunger>) -> bool {
+///         hunger.0 > self.threshold
+///     }
+/// }
+/// ```
+///
+/// Conditions must be registered with
+/// [`BehaviorAppExt::register_behavior_condition`] before trees use them.
+pub trait BehaviorCondition: Reflect + TypePath {
+    /// The data read from the agent.
+    type Data: ReadOnlyQueryData + 'static;
+    /// The filter the agent must match.
+    type Filter: QueryFilter + 'static;
+
+    /// Returns `true` if the condition holds for the data of the agent.
+    fn check(&self, item: QueryItem<Self::Data>) -> bool;
+}
+
+/// Type data evaluating a reflected [`BehaviorCondition`], inserted by
+/// [`BehaviorAppExt::register_behavior_condition`].
+#[derive(Clone)]
+pub struct ReflectBehaviorCondition {
+    evaluate: fn(&dyn Reflect, Entity, &mut World) -> bool,
+}
+
+impl ReflectBehaviorCondition {
+    /// Returns `true` if `condition` holds for `entity`.
+    ///
+    /// Returns `false` if `condition` isn't of the type this data was created for.
+    pub fn evaluate(&self, condition: &dyn Reflect, entity: Entity, world: &mut World) -> bool {
+        (self.evaluate)(condition, entity, world)
+    }
+}
+
+impl<C: BehaviorCondition> FromType<C> for ReflectBehaviorCondition {
+    fn from_type() -> Self {
+        ReflectBehaviorCondition {
+            evaluate: |condition, entity, world| {
+                let Some(condition) = condition.downcast_ref::<C>() else {
+                    return false;
+                };
+                world
+                    .cached_query::<C::Data, C::Filter>()
+                    .get(entity)
+                    .is_ok_and(|item| condition.check(item))
+            },
+        }
+    }
+}
+
+/// The input of the one-shot system running an action of a
+/// [`BehaviorTree`](crate::BehaviorTree), registered with
+/// [`BehaviorAppExt::register_behavior_action`].
+#[derive(Clone, Debug)]
+pub struct ActionInput<A> {
+    /// The agent ticking the tree.
+    pub entity: Entity,
+    /// The action, as written in the tree.
+    pub action: A,
+    /// Whether the action returned [`BehaviorStatus::Running`] on the previous tick, as opposed
+    /// to starting.
+    pub resumed: bool,
+}
+
+/// The one-shot system running the action `A`.
+#[derive(Resource)]
+struct ActionSystem<A: 'static>(SystemId<ActionInput<A>, BehaviorStatus>);
+
+/// Type data running a reflected action, inserted by
+/// [`BehaviorAppExt::register_behavior_action`].
+#[derive(Clone)]
+pub struct ReflectBehaviorAction {
+    run: fn(&dyn Reflect, Entity, bool, &mut World) -> BehaviorStatus,
+}
+
+impl ReflectBehaviorAction {
+    /// Runs the system of `action` for `entity`, returning its status.
+    ///
+    /// `resumed` tells the system if the action was running on the previous tick. Fails if
+    /// `action` isn't of the type this data was created for, or if its system wasn't registered.
+    pub fn run(
+        &self,
+        action: &dyn Reflect,
+        entity: Entity,
+        resumed: bool,
+        world: &mut World,
+    ) -> BehaviorStatus {
+        (self.run)(action, entity, resumed, world)
+    }
+}
+
+impl<A: Reflect + TypePath + Clone> FromType<A> for ReflectBehaviorAction {
+    fn from_type() -> Self {
+        ReflectBehaviorAction {
+            run: |action, entity, resumed, world| {
+                let Some(action) = action.downcast_ref::<A>() else {
+                    return BehaviorStatus::Failure;
+                };
+                let Some(&ActionSystem(id)) = world.get_resource::<ActionSystem<A>>() else {
+                    warn!(
+                        "No system was registered for the behavior action `{}`",
+                        A::type_path()
+                    );
+                    return BehaviorStatus::Failure;
+                };
+                let input = ActionInput {
+                    entity,
+                    action: action.clone(),
+                    resumed,
+                };
+                world
+                    .run_system_with_input(id, input)
+                    .unwrap_or(BehaviorStatus::Failure)
+            },
+        }
+    }
+}
+
+/// Adds methods registering conditions and actions of behavior trees to [`App`].
+pub trait BehaviorAppExt {
+    /// Registers the condition `C`, so that behavior trees can check it.
+    fn register_behavior_condition<C: BehaviorCondition + GetTypeRegistration>(
+        &mut self,
+    ) -> &mut Self;
+
+    /// Registers the action `A`, run by the one-shot `system` when behavior trees tick it.
+    ///
+    /// The system takes an [`ActionInput<A>`] and returns the [`BehaviorStatus`] of the action:
+    ///
+    /// ```
+    /// # use bevy_ai::{ActionInput, BehaviorAppExt, BehaviorStatus};
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component)]
+    /// struct Hunger(f32);
+    ///
+    /// /// Eats `amount` of food.
+    /// #[derive(Reflect, Clone)]
+    /// struct Eat {
+    ///     amount: f32,
+    /// }
+    ///
+    /// fn eat(In(input): In<ActionInput<Eat>>, mut hungers: Query<&mut Hunger>) -> BehaviorStatus {
+    ///     let Ok(mut hunger) = hungers.get_mut(input.entity) else {
+    ///         return BehaviorStatus::Failure;
+    ///     };
+    ///     hunger.0 = (hunger.0 - input.action.amount).max(0.0);
+    ///     BehaviorStatus::Success
+    /// }
+    ///
+    /// App::new().register_behavior_action(eat);
+    /// ```
+    fn register_behavior_action<A, M>(
+        &mut self,
+        system: impl IntoSystem<ActionInput<A>, BehaviorStatus, M> + 'static,
+    ) -> &mut Self
+    where
+        A: Reflect + TypePath + GetTypeRegistration + Clone;
+}
+
+impl BehaviorAppExt for App {
+    fn register_behavior_condition<C: BehaviorCondition + GetTypeRegistration>(
+        &mut self,
+    ) -> &mut Self {
+        self.register_type::<C>()
+            .register_type_data::<C, ReflectBehaviorCondition>()
+    }
+
+    fn register_behavior_action<A, M>(
+        &mut self,
+        system: impl IntoSystem<ActionInput<A>, BehaviorStatus, M> + 'static,
+    ) -> &mut Self
+    where
+        A: Reflect + TypePath + GetTypeRegistration + Clone,
+    {
+        let id = self.world_mut().register_system(system);
+        self.insert_resource(ActionSystem(id))
+            .register_type::<A>()
+            .register_type_data::<A, ReflectBehaviorAction>()
+    }
+}
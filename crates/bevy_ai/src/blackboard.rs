@@ -0,0 +1,274 @@
+use std::fmt;
+
+use bevy_ecs::{component::Component, query::QueryItem};
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+
+use crate::BehaviorCondition;
+
+/// A key-value store of reflected values, shared by the systems deciding what an entity does.
+///
+/// Behavior trees, scripts and animation state machines read the same blackboard, so that a value
+/// written by one of them, like a target or a threat level, drives the others without a dedicated
+/// component per value.
+///
+/// Values are accessed either with their concrete type, with [`get`](Blackboard::get) and
+/// [`insert`](Blackboard::insert), or through reflection, with
+/// [`get_reflect`](Blackboard::get_reflect) and [`insert_reflect`](Blackboard::insert_reflect).
+///
+/// # Change detection
+///
+/// Besides the change detection of the component itself, which tells that *some* key changed,
+/// every change to the blackboard increments its [`revision`](Blackboard::revision), and each key
+/// records the revision it last changed at. Readers store the revision they last saw, and check
+/// [`changed_since`](Blackboard::changed_since) for the keys they care about.
+///
+/// ```
+/// # use bevy_ai::Blackboard;
+/// let mut blackboard = Blackboard::new().with("hunger", 0.5_f32);
+/// let seen = blackboard.revision();
+///
+/// blackboard.insert("thirst", 0.25_f32);
+/// assert!(blackboard.changed_since("thirst", seen));
+/// assert!(!blackboard.changed_since("hunger", seen));
+/// assert_eq!(blackboard.get::<f32>("hunger"), Some(&0.5));
+/// ```
+#[derive(Component, Default)]
+pub struct Blackboard {
+    entries: HashMap<String, BlackboardEntry>,
+    revision: u64,
+}
+
+struct BlackboardEntry {
+    value: Box<dyn Reflect>,
+    changed: u64,
+}
+
+impl Blackboard {
+    /// Creates an empty blackboard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the blackboard with `value` inserted at `key`.
+    pub fn with<T: Reflect>(mut self, key: impl Into<String>, value: T) -> Self {
+        self.insert(key, value);
+        self
+    }
+
+    /// Inserts `value` at `key`, returning the previous value if there was one.
+    pub fn insert<T: Reflect>(
+        &mut self,
+        key: impl Into<String>,
+        value: T,
+    ) -> Option<Box<dyn Reflect>> {
+        self.insert_reflect(key, Box::new(value))
+    }
+
+    /// Inserts the reflected `value` at `key`, returning the previous value if there was one.
+    pub fn insert_reflect(
+        &mut self,
+        key: impl Into<String>,
+        value: Box<dyn Reflect>,
+    ) -> Option<Box<dyn Reflect>> {
+        self.revision += 1;
+        let entry = BlackboardEntry {
+            value,
+            changed: self.revision,
+        };
+        self.entries
+            .insert(key.into(), entry)
+            .map(|entry| entry.value)
+    }
+
+    /// Removes the value at `key`, returning it if there was one.
+    pub fn remove(&mut self, key: &str) -> Option<Box<dyn Reflect>> {
+        let entry = self.entries.remove(key)?;
+        self.revision += 1;
+        Some(entry.value)
+    }
+
+    /// Returns the value at `key`, or `None` if there is no value there or if it isn't a `T`.
+    pub fn get<T: Reflect>(&self, key: &str) -> Option<&T> {
+        self.get_reflect(key)?.downcast_ref()
+    }
+
+    /// Returns the value at `key` mutably, or `None` if there is no value there or if it isn't
+    /// a `T`.
+    ///
+    /// The key is marked as changed if a value is returned.
+    pub fn get_mut<T: Reflect>(&mut self, key: &str) -> Option<&mut T> {
+        if !self.get_reflect(key)?.is::<T>() {
+            return None;
+        }
+        self.get_reflect_mut(key)?.downcast_mut()
+    }
+
+    /// Returns the numeric or boolean value at `key` as an `f64`, booleans counting as `0` and
+    /// `1`, or `None` if there is no such value there.
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        as_f64(self.get_reflect(key)?)
+    }
+
+    /// Returns the reflected value at `key`.
+    pub fn get_reflect(&self, key: &str) -> Option<&dyn Reflect> {
+        self.entries.get(key).map(|entry| &*entry.value)
+    }
+
+    /// Returns the reflected value at `key` mutably.
+    ///
+    /// The key is marked as changed if a value is returned.
+    pub fn get_reflect_mut(&mut self, key: &str) -> Option<&mut dyn Reflect> {
+        let entry = self.entries.get_mut(key)?;
+        self.revision += 1;
+        entry.changed = self.revision;
+        Some(&mut *entry.value)
+    }
+
+    /// Returns `true` if there is a value at `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// The number of values in the blackboard.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the blackboard has no values.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the keys and reflected values of the blackboard, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &dyn Reflect)> {
+        self.entries
+            .iter()
+            .map(|(key, entry)| (key.as_str(), &*entry.value))
+    }
+
+    /// The revision of the blackboard, incremented by every change.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// The revision at which the value at `key` was last inserted or accessed mutably, or `None`
+    /// if there is no value there.
+    pub fn last_changed(&self, key: &str) -> Option<u64> {
+        self.entries.get(key).map(|entry| entry.changed)
+    }
+
+    /// Returns `true` if the value at `key` changed after `revision`.
+    ///
+    /// Removed keys aren't reported as changed, check [`contains_key`](Self::contains_key) to
+    /// detect them.
+    pub fn changed_since(&self, key: &str, revision: u64) -> bool {
+        self.last_changed(key)
+            .is_some_and(|changed| changed > revision)
+    }
+}
+
+impl fmt::Debug for Blackboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Reads a numeric or boolean reflected value as an `f64`.
+fn as_f64(value: &dyn Reflect) -> Option<f64> {
+    let any = value.as_any();
+    Some(if let Some(v) = any.downcast_ref::<f64>() {
+        *v
+    } else if let Some(v) = any.downcast_ref::<f32>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<i32>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<u32>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<i64>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<u64>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<usize>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<bool>() {
+        if *v {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        return None;
+    })
+}
+
+/// A [`BehaviorCondition`] holding if the [`Blackboard`] of the agent has a value at `key`.
+#[derive(Reflect, Clone, Debug)]
+pub struct BlackboardIsSet {
+    /// The checked key.
+    pub key: String,
+}
+
+impl BehaviorCondition for BlackboardIsSet {
+    type Data = &'static Blackboard;
+    type Filter = ();
+
+    fn check(&self, blackboard: QueryItem<&Blackboard>) -> bool {
+        blackboard.contains_key(&self.key)
+    }
+}
+
+/// How a [`BlackboardCompare`] condition compares a value of the blackboard to its operand.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// The value is less than the operand.
+    Less,
+    /// The value is less than or equal to the operand.
+    LessOrEqual,
+    /// The value is equal to the operand.
+    Equal,
+    /// The value isn't equal to the operand.
+    NotEqual,
+    /// The value is greater than or equal to the operand.
+    GreaterOrEqual,
+    /// The value is greater than the operand.
+    Greater,
+}
+
+/// A [`BehaviorCondition`] comparing the numeric value at `key` in the [`Blackboard`] of the agent
+/// to `value`.
+///
+/// Booleans count as `0` and `1`. The condition doesn't hold if there is no numeric value at
+/// `key`.
+///
+/// ```ron
+/// Condition({"bevy_ai::blackboard::BlackboardCompare": (key: "hunger", comparison: Greater, value: 0.5)})
+/// ```
+#[derive(Reflect, Clone, Debug)]
+pub struct BlackboardCompare {
+    /// The key of the compared value.
+    pub key: String,
+    /// How the value is compared to the operand.
+    pub comparison: Comparison,
+    /// The operand.
+    pub value: f64,
+}
+
+impl BehaviorCondition for BlackboardCompare {
+    type Data = &'static Blackboard;
+    type Filter = ();
+
+    fn check(&self, blackboard: QueryItem<&Blackboard>) -> bool {
+        let Some(value) = blackboard.get_f64(&self.key) else {
+            return false;
+        };
+        match self.comparison {
+            Comparison::Less => value < self.value,
+            Comparison::LessOrEqual => value <= self.value,
+            Comparison::Equal => value == self.value,
+            Comparison::NotEqual => value != self.value,
+            Comparison::GreaterOrEqual => value >= self.value,
+            Comparison::Greater => value > self.value,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Logging of the active branches of behavior trees.
+
+use bevy_app::prelude::*;
+use bevy_asset::Assets;
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_reflect::Reflect;
+use bevy_utils::tracing::info;
+
+use crate::{BehaviorTree, BehaviorTreeAgent, BehaviorTreeSystem};
+
+/// Logs the [active branches](BehaviorTree::active_branches) of the agents with a
+/// [`DebugBehaviorTree`] whenever they change.
+#[derive(Default)]
+pub struct BehaviorTreeDebugPlugin;
+
+impl Plugin for BehaviorTreeDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<DebugBehaviorTree>()
+            .add_systems(Update, log_active_branches.after(BehaviorTreeSystem));
+    }
+}
+
+/// Marks a [`BehaviorTreeAgent`] whose active branches are logged by the
+/// [`BehaviorTreeDebugPlugin`].
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct DebugBehaviorTree;
+
+/// Logs the active branches of the agents with a [`DebugBehaviorTree`] whenever they change.
+pub fn log_active_branches(
+    agents: Query<
+        (Entity, &BehaviorTreeAgent),
+        (With<DebugBehaviorTree>, Changed<BehaviorTreeAgent>),
+    >,
+    trees: Res<Assets<BehaviorTree>>,
+    mut logged: Local<EntityHashMap<String>>,
+    mut removed: RemovedComponents<DebugBehaviorTree>,
+) {
+    for entity in removed.read() {
+        logged.remove(&entity);
+    }
+    for (entity, agent) in &agents {
+        let Some(tree) = trees.get(&agent.tree) else {
+            continue;
+        };
+        let branches = tree.active_branches(agent).to_string();
+        if logged.get(&entity) != Some(&branches) {
+            info!("Behavior tree of {entity:?}:\n{branches}");
+            logged.insert(entity, branches);
+        }
+    }
+}
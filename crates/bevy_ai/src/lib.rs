@@ -0,0 +1,365 @@
+#![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! Data-driven behavior trees for Bevy.
+//!
+//! A [`BehaviorTree`] asset combines conditions and actions with composite nodes, like
+//! [`BehaviorNode::Sequence`] and [`BehaviorNode::Selector`]. Entities with a
+//! [`BehaviorTreeAgent`] tick their tree every frame:
+//! - conditions are [`BehaviorCondition`]s, queries checked against the agent,
+//! - actions are one-shot systems taking an [`ActionInput`] and returning a [`BehaviorStatus`].
+//!
+//! Both are reflected types, registered with [`BehaviorAppExt`], so that trees can be loaded
+//! from `.bt.ron` files naming them by their type path.
+//!
+//! A [`Blackboard`] stores the values shared by the behavior tree of an entity and its other
+//! decision makers, like scripts, which the [`BlackboardIsSet`] and [`BlackboardCompare`]
+//! conditions check.
+//!
+//! [`BehaviorTreeDebugPlugin`](debug::BehaviorTreeDebugPlugin) logs the branches of the trees
+//! ticked by agents, to follow their decisions.
+
+mod agent;
+mod behavior;
+mod blackboard;
+pub mod debug;
+mod serde;
+mod tree;
+
+pub use agent::*;
+pub use behavior::*;
+pub use blackboard::*;
+pub use serde::*;
+pub use tree::*;
+
+/// The behavior tree prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        ActionInput, BehaviorAppExt, BehaviorCondition, BehaviorNode, BehaviorStatus, BehaviorTree,
+        BehaviorTreeAgent, BehaviorTreePlugin, Blackboard,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+use bevy_ecs::schedule::{IntoSystemConfigs, SystemSet};
+
+/// Adds the [`BehaviorTree`] asset and ticks the trees of [`BehaviorTreeAgent`]s in
+/// [`Update`], in the [`BehaviorTreeSystem`] set.
+///
+/// The [`BlackboardIsSet`] and [`BlackboardCompare`] conditions are registered.
+#[derive(Default)]
+pub struct BehaviorTreePlugin;
+
+impl Plugin for BehaviorTreePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<BehaviorTree>()
+            .init_asset_loader::<BehaviorTreeLoader>()
+            .register_type::<BehaviorTreeAgent>()
+            .register_type::<BehaviorStatus>()
+            .register_behavior_condition::<BlackboardIsSet>()
+            .register_behavior_condition::<BlackboardCompare>()
+            .add_systems(Update, tick_behavior_trees.in_set(BehaviorTreeSystem));
+    }
+}
+
+/// The system set ticking behavior trees, in [`Update`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BehaviorTreeSystem;
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::prelude::*;
+    use bevy_asset::{ron, Assets};
+    use bevy_ecs::{prelude::*, query::QueryItem};
+    use bevy_reflect::Reflect;
+    use serde::de::DeserializeSeed;
+
+    use crate::prelude::*;
+    use crate::{
+        tick_behavior_trees, BehaviorTreeDeserializer, BlackboardCompare, BlackboardIsSet,
+    };
+
+    #[derive(Component)]
+    struct Hunger(f32);
+
+    #[derive(Component)]
+    struct Sleeping;
+
+    #[derive(Component, Default)]
+    struct Steps(u32);
+
+    #[derive(Reflect)]
+    struct IsHungry {
+        threshold: f32,
+    }
+
+    impl BehaviorCondition for IsHungry {
+        type Data = &'static Hunger;
+        type Filter = Without<Sleeping>;
+
+        fn check(&self, hunger: QueryItem<&Hunger>) -> bool {
+            hunger.0 > self.threshold
+        }
+    }
+
+    #[derive(Reflect, Clone)]
+    struct Eat {
+        amount: f32,
+    }
+
+    fn eat(In(input): In<ActionInput<Eat>>, mut hungers: Query<&mut Hunger>) -> BehaviorStatus {
+        let Ok(mut hunger) = hungers.get_mut(input.entity) else {
+            return BehaviorStatus::Failure;
+        };
+        hunger.0 -= input.action.amount;
+        BehaviorStatus::Success
+    }
+
+    /// Runs for `steps` ticks, restarting when interrupted.
+    #[derive(Reflect, Clone)]
+    struct Wander {
+        steps: u32,
+    }
+
+    fn wander(In(input): In<ActionInput<Wander>>, mut steps: Query<&mut Steps>) -> BehaviorStatus {
+        let mut steps = steps.get_mut(input.entity).unwrap();
+        if !input.resumed {
+            steps.0 = 0;
+        }
+        steps.0 += 1;
+        if steps.0 < input.action.steps {
+            BehaviorStatus::Running
+        } else {
+            BehaviorStatus::Success
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<Assets<BehaviorTree>>()
+            .register_behavior_condition::<IsHungry>()
+            .register_behavior_action(eat)
+            .register_behavior_action(wander)
+            .add_systems(Update, tick_behavior_trees);
+        app
+    }
+
+    fn spawn_agent(app: &mut App, tree: BehaviorTree, hunger: f32) -> Entity {
+        let tree = app
+            .world_mut()
+            .resource_mut::<Assets<BehaviorTree>>()
+            .add(tree);
+        app.world_mut()
+            .spawn((BehaviorTreeAgent::new(tree), Hunger(hunger), Steps(0)))
+            .id()
+    }
+
+    fn eat_or_wander() -> BehaviorTree {
+        BehaviorTree::new(BehaviorNode::Selector(vec![
+            BehaviorNode::Sequence(vec![
+                BehaviorNode::condition(IsHungry { threshold: 0.5 }),
+                BehaviorNode::action(Eat { amount: 0.25 }),
+            ]),
+            BehaviorNode::action(Wander { steps: 3 }),
+        ]))
+    }
+
+    #[test]
+    fn conditions_select_actions() {
+        let mut app = app();
+        let agent = spawn_agent(&mut app, eat_or_wander(), 1.0);
+
+        app.update();
+        let world = app.world();
+        assert_eq!(world.get::<Hunger>(agent).unwrap().0, 0.75);
+        let status = world.get::<BehaviorTreeAgent>(agent).unwrap();
+        assert_eq!(status.status(), Some(BehaviorStatus::Success));
+        // The wandering action wasn't ticked.
+        assert_eq!(status.node_status(4), None);
+
+        app.update();
+        app.update();
+        let world = app.world();
+        assert_eq!(world.get::<Hunger>(agent).unwrap().0, 0.5);
+        let status = world.get::<BehaviorTreeAgent>(agent).unwrap();
+        assert_eq!(status.status(), Some(BehaviorStatus::Running));
+        assert_eq!(status.node_status(2), Some(BehaviorStatus::Failure));
+        assert_eq!(world.get::<Steps>(agent).unwrap().0, 1);
+    }
+
+    #[test]
+    fn running_actions_resume_until_interrupted() {
+        let mut app = app();
+        let agent = spawn_agent(&mut app, eat_or_wander(), 0.0);
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().get::<Steps>(agent).unwrap().0, 2);
+
+        // Becoming hungry interrupts wandering, which starts over afterwards.
+        app.world_mut().get_mut::<Hunger>(agent).unwrap().0 = 0.6;
+        app.update();
+        app.update();
+        assert_eq!(app.world().get::<Steps>(agent).unwrap().0, 1);
+        app.update();
+        app.update();
+        let world = app.world();
+        assert_eq!(world.get::<Steps>(agent).unwrap().0, 3);
+        let status = world.get::<BehaviorTreeAgent>(agent).unwrap();
+        assert_eq!(status.status(), Some(BehaviorStatus::Success));
+    }
+
+    #[test]
+    fn query_filters_conditions() {
+        let mut app = app();
+        let agent = spawn_agent(&mut app, eat_or_wander(), 1.0);
+        app.world_mut().entity_mut(agent).insert(Sleeping);
+
+        app.update();
+        assert_eq!(app.world().get::<Hunger>(agent).unwrap().0, 1.0);
+        assert_eq!(app.world().get::<Steps>(agent).unwrap().0, 1);
+    }
+
+    #[test]
+    fn decorators_and_parallel() {
+        let mut app = app();
+        let tree = BehaviorTree::new(BehaviorNode::Parallel(vec![
+            BehaviorNode::Succeed(Box::new(BehaviorNode::Invert(Box::new(
+                BehaviorNode::condition(IsHungry { threshold: 0.5 }),
+            )))),
+            BehaviorNode::action(Wander { steps: 2 }),
+        ]));
+        let agent = spawn_agent(&mut app, tree, 1.0);
+
+        app.update();
+        let status = app.world().get::<BehaviorTreeAgent>(agent).unwrap();
+        assert_eq!(status.status(), Some(BehaviorStatus::Running));
+        assert_eq!(status.node_status(1), Some(BehaviorStatus::Success));
+        assert_eq!(status.node_status(2), Some(BehaviorStatus::Failure));
+
+        app.update();
+        let status = app.world().get::<BehaviorTreeAgent>(agent).unwrap();
+        assert_eq!(status.status(), Some(BehaviorStatus::Success));
+    }
+
+    #[test]
+    fn deserialize_and_display_tree() {
+        let app = app();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let input = r#"Selector([
+            Sequence([
+                Condition({"bevy_ai::tests::IsHungry": (threshold: 0.5)}),
+                Action({"bevy_ai::tests::Eat": (amount: 0.25)}),
+            ]),
+            Action({"bevy_ai::tests::Wander": (steps: 3)}),
+        ])"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let tree = BehaviorTreeDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        drop(registry);
+        assert_eq!(tree.len(), 5);
+
+        let mut app = app;
+        let agent = spawn_agent(&mut app, tree, 0.0);
+        app.update();
+        let world = app.world();
+        let agent = world.get::<BehaviorTreeAgent>(agent).unwrap();
+        let tree = world
+            .resource::<Assets<BehaviorTree>>()
+            .get(&agent.tree)
+            .unwrap();
+        assert_eq!(
+            tree.active_branches(agent).to_string(),
+            "Selector: Running\n  Sequence: Failure\n    IsHungry: Failure\n  Wander: Running\n"
+        );
+    }
+
+    #[test]
+    fn blackboard_conditions() {
+        let mut app = app();
+        app.register_behavior_condition::<BlackboardIsSet>()
+            .register_behavior_condition::<BlackboardCompare>();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let input = r#"Selector([
+            Sequence([
+                Condition({"bevy_ai::blackboard::BlackboardIsSet": (key: "food")}),
+                Condition({"bevy_ai::blackboard::BlackboardCompare": (key: "hunger", comparison: Greater, value: 0.5)}),
+                Action({"bevy_ai::tests::Eat": (amount: 0.25)}),
+            ]),
+            Action({"bevy_ai::tests::Wander": (steps: 3)}),
+        ])"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let tree = BehaviorTreeDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        drop(registry);
+
+        let agent = spawn_agent(&mut app, tree, 1.0);
+        app.world_mut()
+            .entity_mut(agent)
+            .insert(Blackboard::new().with("hunger", 1_u32));
+        app.update();
+        assert_eq!(app.world().get::<Steps>(agent).unwrap().0, 1);
+
+        let mut blackboard = app.world_mut().get_mut::<Blackboard>(agent).unwrap();
+        blackboard.insert("food", ());
+        app.update();
+        assert_eq!(app.world().get::<Hunger>(agent).unwrap().0, 0.75);
+
+        let mut blackboard = app.world_mut().get_mut::<Blackboard>(agent).unwrap();
+        *blackboard.get_mut::<u32>("hunger").unwrap() = 0;
+        app.update();
+        assert_eq!(app.world().get::<Steps>(agent).unwrap().0, 1);
+    }
+
+    #[test]
+    fn blackboard_change_detection() {
+        let mut blackboard = Blackboard::new().with("a", 1.0_f32).with("b", 2_i32);
+        let seen = blackboard.revision();
+        assert!(!blackboard.changed_since("a", seen));
+
+        // Reading doesn't change keys, writing does.
+        assert_eq!(blackboard.get::<f32>("a"), Some(&1.0));
+        assert_eq!(blackboard.get::<i32>("a"), None);
+        assert!(blackboard.get_mut::<i32>("a").is_none());
+        assert_eq!(blackboard.revision(), seen);
+        *blackboard.get_mut::<i32>("b").unwrap() += 1;
+        assert!(blackboard.changed_since("b", seen));
+        assert!(!blackboard.changed_since("a", seen));
+
+        let seen = blackboard.revision();
+        assert!(blackboard.remove("a").is_some());
+        assert!(blackboard.revision() > seen);
+        assert!(!blackboard.changed_since("a", seen));
+        assert_eq!(blackboard.len(), 1);
+    }
+
+    #[test]
+    fn unregistered_types_fail_to_deserialize() {
+        let app = app();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let input = r#"Action({"bevy_ai::tests::IsHungry": (threshold: 0.5)})"#;
+        let mut deserializer = ron::de::Deserializer::from_str(input).unwrap();
+        let error = BehaviorTreeDeserializer {
+            type_registry: &registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("isn't registered as a behavior action"));
+    }
+}
@@ -0,0 +1,248 @@
+use std::fmt::{self, Formatter};
+
+use bevy_asset::{io::Reader, ron, AssetLoader, AsyncReadExt, LoadContext};
+use bevy_ecs::{
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_reflect::{
+    serde::ReflectDeserializer, Reflect, ReflectFromReflect, TypeData, TypeRegistry,
+    TypeRegistryArc,
+};
+use serde::{
+    de::{DeserializeSeed, EnumAccess, Error, SeqAccess, VariantAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use thiserror::Error;
+
+use crate::{
+    ActionNode, BehaviorNode, BehaviorTree, ConditionNode, ReflectBehaviorAction,
+    ReflectBehaviorCondition,
+};
+
+/// Deserializes a [`BehaviorTree`] from its root node, resolving the conditions and actions
+/// with the type data registered in `type_registry`.
+pub struct BehaviorTreeDeserializer<'a> {
+    /// Type registry in which the conditions and actions of the tree are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BehaviorTreeDeserializer<'a> {
+    type Value = BehaviorTree;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let root = BehaviorNodeDeserializer {
+            type_registry: self.type_registry,
+        }
+        .deserialize(deserializer)?;
+        Ok(BehaviorTree::new(root))
+    }
+}
+
+#[derive(Deserialize)]
+enum NodeVariant {
+    Sequence,
+    Selector,
+    Parallel,
+    Invert,
+    Succeed,
+    Condition,
+    Action,
+}
+
+const NODE_VARIANTS: &[&str] = &[
+    "Sequence",
+    "Selector",
+    "Parallel",
+    "Invert",
+    "Succeed",
+    "Condition",
+    "Action",
+];
+
+/// Handles deserialization of a [`BehaviorNode`] and its children.
+pub struct BehaviorNodeDeserializer<'a> {
+    /// Type registry in which the conditions and actions of the node are registered.
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BehaviorNodeDeserializer<'a> {
+    type Value = BehaviorNode;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_enum(
+            "BehaviorNode",
+            NODE_VARIANTS,
+            BehaviorNodeVisitor {
+                type_registry: self.type_registry,
+            },
+        )
+    }
+}
+
+struct BehaviorNodeVisitor<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BehaviorNodeVisitor<'a> {
+    type Value = BehaviorNode;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("behavior tree node")
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: EnumAccess<'de>,
+    {
+        let type_registry = self.type_registry;
+        let node = BehaviorNodeDeserializer { type_registry };
+        let children = BehaviorNodesDeserializer { type_registry };
+        let (variant, access) = data.variant::<NodeVariant>()?;
+        Ok(match variant {
+            NodeVariant::Sequence => BehaviorNode::Sequence(access.newtype_variant_seed(children)?),
+            NodeVariant::Selector => BehaviorNode::Selector(access.newtype_variant_seed(children)?),
+            NodeVariant::Parallel => BehaviorNode::Parallel(access.newtype_variant_seed(children)?),
+            NodeVariant::Invert => {
+                BehaviorNode::Invert(Box::new(access.newtype_variant_seed(node)?))
+            }
+            NodeVariant::Succeed => {
+                BehaviorNode::Succeed(Box::new(access.newtype_variant_seed(node)?))
+            }
+            NodeVariant::Condition => {
+                let condition =
+                    access.newtype_variant_seed(ReflectDeserializer::new(type_registry))?;
+                let (condition, evaluate) =
+                    resolve::<ReflectBehaviorCondition>(condition, type_registry, "condition")
+                        .map_err(Error::custom)?;
+                BehaviorNode::Condition(ConditionNode::from_reflect(condition, evaluate))
+            }
+            NodeVariant::Action => {
+                let action =
+                    access.newtype_variant_seed(ReflectDeserializer::new(type_registry))?;
+                let (action, run) =
+                    resolve::<ReflectBehaviorAction>(action, type_registry, "action")
+                        .map_err(Error::custom)?;
+                BehaviorNode::Action(ActionNode::from_reflect(action, run))
+            }
+        })
+    }
+}
+
+/// Resolves a deserialized condition or action into its concrete type and its type data `T`.
+fn resolve<T: TypeData + Clone>(
+    value: Box<dyn Reflect>,
+    type_registry: &TypeRegistry,
+    kind: &str,
+) -> Result<(Box<dyn Reflect>, T), String> {
+    let type_path = value
+        .get_represented_type_info()
+        .map_or_else(|| value.reflect_type_path(), |info| info.type_path());
+    let registration = type_registry
+        .get_with_type_path(type_path)
+        .ok_or_else(|| format!("no registration found for `{type_path}`"))?;
+    let data = registration
+        .data::<T>()
+        .ok_or_else(|| format!("`{type_path}` isn't registered as a behavior {kind}"))?;
+    let value = registration
+        .data::<ReflectFromReflect>()
+        .and_then(|from_reflect| from_reflect.from_reflect(&*value))
+        .ok_or_else(|| format!("`{type_path}` couldn't be created from its reflected value"))?;
+    Ok((value, data.clone()))
+}
+
+struct BehaviorNodesDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BehaviorNodesDeserializer<'a> {
+    type Value = Vec<BehaviorNode>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'a, 'de> Visitor<'de> for BehaviorNodesDeserializer<'a> {
+    type Value = Vec<BehaviorNode>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("list of behavior tree nodes")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut nodes = Vec::new();
+        while let Some(node) = seq.next_element_seed(BehaviorNodeDeserializer {
+            type_registry: self.type_registry,
+        })? {
+            nodes.push(node);
+        }
+        Ok(nodes)
+    }
+}
+
+/// Asset loader for a [`BehaviorTree`] (`.bt.ron`).
+#[derive(Debug)]
+pub struct BehaviorTreeLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BehaviorTreeLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BehaviorTreeLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`BehaviorTreeLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BehaviorTreeLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the behavior tree file: {0}")]
+    Io(#[from] std::io::Error),
+    /// A [RON Error](ron::error::SpannedError)
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] ron::error::SpannedError),
+}
+
+impl AssetLoader for BehaviorTreeLoader {
+    type Asset = BehaviorTree;
+    type Settings = ();
+    type Error = BehaviorTreeLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut deserializer = ron::de::Deserializer::from_bytes(&bytes)?;
+        let tree_deserializer = BehaviorTreeDeserializer {
+            type_registry: &self.type_registry.read(),
+        };
+        Ok(tree_deserializer
+            .deserialize(&mut deserializer)
+            .map_err(|e| deserializer.span_error(e))?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bt.ron"]
+    }
+}
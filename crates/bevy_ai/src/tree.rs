@@ -0,0 +1,346 @@
+use std::{fmt, sync::Arc};
+
+use bevy_asset::Asset;
+use bevy_ecs::{entity::Entity, world::World};
+use bevy_reflect::{FromType, Reflect, TypePath};
+
+use crate::{
+    BehaviorCondition, BehaviorStatus, BehaviorTreeAgent, ReflectBehaviorAction,
+    ReflectBehaviorCondition,
+};
+
+/// A node of a behavior tree, used to build a [`BehaviorTree`].
+pub enum BehaviorNode {
+    /// Ticks its children in order until one doesn't succeed, returning its status. Succeeds if
+    /// every child succeeds.
+    Sequence(Vec<BehaviorNode>),
+    /// Ticks its children in order until one doesn't fail, returning its status. Fails if every
+    /// child fails.
+    Selector(Vec<BehaviorNode>),
+    /// Ticks all its children. Fails if a child fails, otherwise runs while a child runs, and
+    /// succeeds once every child succeeds.
+    Parallel(Vec<BehaviorNode>),
+    /// Turns the success of its child into a failure and the other way around.
+    Invert(Box<BehaviorNode>),
+    /// Succeeds once its child finishes, whether it succeeded or not.
+    Succeed(Box<BehaviorNode>),
+    /// Checks a [`BehaviorCondition`], succeeding if it holds.
+    Condition(ConditionNode),
+    /// Runs an action registered with
+    /// [`BehaviorAppExt::register_behavior_action`](crate::BehaviorAppExt::register_behavior_action).
+    Action(ActionNode),
+}
+
+impl BehaviorNode {
+    /// Creates a node checking `condition`.
+    pub fn condition<C: BehaviorCondition>(condition: C) -> Self {
+        Self::Condition(ConditionNode::new(condition))
+    }
+
+    /// Creates a node running `action`.
+    pub fn action<A: Reflect + TypePath + Clone>(action: A) -> Self {
+        Self::Action(ActionNode::new(action))
+    }
+}
+
+/// A [`BehaviorCondition`] in a [`BehaviorTree`], with the type data evaluating it.
+pub struct ConditionNode {
+    condition: Box<dyn Reflect>,
+    evaluate: ReflectBehaviorCondition,
+}
+
+impl ConditionNode {
+    /// Creates a node checking `condition`.
+    pub fn new<C: BehaviorCondition>(condition: C) -> Self {
+        Self::from_reflect(
+            Box::new(condition),
+            <ReflectBehaviorCondition as FromType<C>>::from_type(),
+        )
+    }
+
+    /// Creates a node checking the reflected `condition` with `evaluate`, which must have been
+    /// created for the type of `condition`.
+    pub fn from_reflect(condition: Box<dyn Reflect>, evaluate: ReflectBehaviorCondition) -> Self {
+        Self {
+            condition,
+            evaluate,
+        }
+    }
+
+    /// The checked condition.
+    pub fn condition(&self) -> &dyn Reflect {
+        &*self.condition
+    }
+}
+
+/// An action in a [`BehaviorTree`], with the type data running it.
+pub struct ActionNode {
+    action: Box<dyn Reflect>,
+    run: ReflectBehaviorAction,
+}
+
+impl ActionNode {
+    /// Creates a node running `action`.
+    pub fn new<A: Reflect + TypePath + Clone>(action: A) -> Self {
+        Self::from_reflect(
+            Box::new(action),
+            <ReflectBehaviorAction as FromType<A>>::from_type(),
+        )
+    }
+
+    /// Creates a node running the reflected `action` with `run`, which must have been created
+    /// for the type of `action`.
+    pub fn from_reflect(action: Box<dyn Reflect>, run: ReflectBehaviorAction) -> Self {
+        Self { action, run }
+    }
+
+    /// The run action.
+    pub fn action(&self) -> &dyn Reflect {
+        &*self.action
+    }
+}
+
+/// A node flattened into the nodes of a [`BehaviorTree`], with the indices of its children.
+enum FlatNode {
+    Sequence(Vec<usize>),
+    Selector(Vec<usize>),
+    Parallel(Vec<usize>),
+    Invert(usize),
+    Succeed(usize),
+    Condition(ConditionNode),
+    Action(ActionNode),
+}
+
+impl FlatNode {
+    fn label(&self) -> &str {
+        match self {
+            FlatNode::Sequence(_) => "Sequence",
+            FlatNode::Selector(_) => "Selector",
+            FlatNode::Parallel(_) => "Parallel",
+            FlatNode::Invert(_) => "Invert",
+            FlatNode::Succeed(_) => "Succeed",
+            FlatNode::Condition(node) => node.condition.reflect_short_type_path(),
+            FlatNode::Action(node) => node.action.reflect_short_type_path(),
+        }
+    }
+
+    fn children(&self) -> &[usize] {
+        match self {
+            FlatNode::Sequence(children)
+            | FlatNode::Selector(children)
+            | FlatNode::Parallel(children) => children,
+            FlatNode::Invert(child) | FlatNode::Succeed(child) => std::slice::from_ref(child),
+            FlatNode::Condition(_) | FlatNode::Action(_) => &[],
+        }
+    }
+}
+
+/// A behavior tree, ticked by the entities with a [`BehaviorTreeAgent`].
+///
+/// Trees are built from a root [`BehaviorNode`] in code, or loaded from RON files with the
+/// `.bt.ron` extension, naming conditions and actions by their type path:
+///
+/// ```ron
+/// Selector([
+///     Sequence([
+///         Condition({"my_game::IsHungry": (threshold: 0.5)}),
+///         Action({"my_game::Eat": (amount: 1.0)}),
+///     ]),
+///     Action({"my_game::Wander": ()}),
+/// ])
+/// ```
+///
+/// Trees are reactive: each tick walks the tree from the root, so a running action is
+/// interrupted as soon as a condition before it fails.
+///
+/// The tree is shared behind an [`Arc`], so cloning it is cheap.
+#[derive(Asset, TypePath, Clone)]
+pub struct BehaviorTree {
+    /// The nodes of the tree, in depth-first pre-order.
+    nodes: Arc<[FlatNode]>,
+}
+
+impl BehaviorTree {
+    /// Creates a tree from its `root` node.
+    pub fn new(root: BehaviorNode) -> Self {
+        fn flatten(node: BehaviorNode, nodes: &mut Vec<FlatNode>) -> usize {
+            let index = nodes.len();
+            // Reserve the slot of the node, so that it comes before its children.
+            nodes.push(FlatNode::Sequence(Vec::new()));
+            let flattened = match node {
+                BehaviorNode::Sequence(children) => FlatNode::Sequence(
+                    children
+                        .into_iter()
+                        .map(|child| flatten(child, nodes))
+                        .collect(),
+                ),
+                BehaviorNode::Selector(children) => FlatNode::Selector(
+                    children
+                        .into_iter()
+                        .map(|child| flatten(child, nodes))
+                        .collect(),
+                ),
+                BehaviorNode::Parallel(children) => FlatNode::Parallel(
+                    children
+                        .into_iter()
+                        .map(|child| flatten(child, nodes))
+                        .collect(),
+                ),
+                BehaviorNode::Invert(child) => FlatNode::Invert(flatten(*child, nodes)),
+                BehaviorNode::Succeed(child) => FlatNode::Succeed(flatten(*child, nodes)),
+                BehaviorNode::Condition(node) => FlatNode::Condition(node),
+                BehaviorNode::Action(node) => FlatNode::Action(node),
+            };
+            nodes[index] = flattened;
+            index
+        }
+
+        let mut nodes = Vec::new();
+        flatten(root, &mut nodes);
+        Self {
+            nodes: nodes.into(),
+        }
+    }
+
+    /// The number of nodes in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the tree has no nodes, which never happens for a tree built with
+    /// [`BehaviorTree::new`].
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Ticks the tree for `entity`, returning the status of the root.
+    ///
+    /// `previous` holds the status of each node on the previous tick, and `statuses` receives the
+    /// status of each node ticked now. Both are indexed in depth-first pre-order.
+    pub(crate) fn tick(
+        &self,
+        entity: Entity,
+        world: &mut World,
+        previous: &[Option<BehaviorStatus>],
+        statuses: &mut [Option<BehaviorStatus>],
+    ) -> BehaviorStatus {
+        self.tick_node(0, entity, world, previous, statuses)
+    }
+
+    fn tick_node(
+        &self,
+        index: usize,
+        entity: Entity,
+        world: &mut World,
+        previous: &[Option<BehaviorStatus>],
+        statuses: &mut [Option<BehaviorStatus>],
+    ) -> BehaviorStatus {
+        let mut tick = |child| self.tick_node(child, entity, world, previous, statuses);
+        let status = match &self.nodes[index] {
+            FlatNode::Sequence(children) => children
+                .iter()
+                .map(|&child| tick(child))
+                .find(|&status| status != BehaviorStatus::Success)
+                .unwrap_or(BehaviorStatus::Success),
+            FlatNode::Selector(children) => children
+                .iter()
+                .map(|&child| tick(child))
+                .find(|&status| status != BehaviorStatus::Failure)
+                .unwrap_or(BehaviorStatus::Failure),
+            FlatNode::Parallel(children) => {
+                let mut status = BehaviorStatus::Success;
+                for &child in children {
+                    match tick(child) {
+                        BehaviorStatus::Failure => status = BehaviorStatus::Failure,
+                        BehaviorStatus::Running if status == BehaviorStatus::Success => {
+                            status = BehaviorStatus::Running;
+                        }
+                        _ => {}
+                    }
+                }
+                status
+            }
+            FlatNode::Invert(child) => match tick(*child) {
+                BehaviorStatus::Success => BehaviorStatus::Failure,
+                BehaviorStatus::Failure => BehaviorStatus::Success,
+                BehaviorStatus::Running => BehaviorStatus::Running,
+            },
+            FlatNode::Succeed(child) => match tick(*child) {
+                BehaviorStatus::Running => BehaviorStatus::Running,
+                _ => BehaviorStatus::Success,
+            },
+            FlatNode::Condition(node) => {
+                if node.evaluate.evaluate(&*node.condition, entity, world) {
+                    BehaviorStatus::Success
+                } else {
+                    BehaviorStatus::Failure
+                }
+            }
+            FlatNode::Action(node) => {
+                let resumed = previous.get(index) == Some(&Some(BehaviorStatus::Running));
+                node.run.run(&*node.action, entity, resumed, world)
+            }
+        };
+        statuses[index] = Some(status);
+        status
+    }
+
+    /// Returns a [`Display`](fmt::Display)able view of the branches of the tree ticked by `agent`
+    /// on its last tick, with the status of each node.
+    ///
+    /// ```text
+    /// Selector: Running
+    ///   Sequence: Failure
+    ///     IsHungry: Failure
+    ///   Wander: Running
+    /// ```
+    pub fn active_branches<'a>(&'a self, agent: &'a BehaviorTreeAgent) -> ActiveBranches<'a> {
+        ActiveBranches {
+            tree: self,
+            statuses: agent.statuses(),
+        }
+    }
+}
+
+impl fmt::Debug for BehaviorTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BehaviorTree")
+            .field("len", &self.nodes.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// The branches of a [`BehaviorTree`] ticked by an agent, returned by
+/// [`BehaviorTree::active_branches`].
+pub struct ActiveBranches<'a> {
+    tree: &'a BehaviorTree,
+    statuses: &'a [Option<BehaviorStatus>],
+}
+
+impl ActiveBranches<'_> {
+    fn fmt_node(&self, f: &mut fmt::Formatter<'_>, index: usize, depth: usize) -> fmt::Result {
+        let Some(Some(status)) = self.statuses.get(index) else {
+            return Ok(());
+        };
+        writeln!(
+            f,
+            "{:indent$}{}: {status:?}",
+            "",
+            self.tree.nodes[index].label(),
+            indent = depth * 2
+        )?;
+        for &child in self.tree.nodes[index].children() {
+            self.fmt_node(f, child, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ActiveBranches<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tree.is_empty() {
+            return Ok(());
+        }
+        self.fmt_node(f, 0, 0)
+    }
+}
@@ -0,0 +1,205 @@
+//! Parenting entities, like weapons or props, to the bones of animated skeletons.
+
+use bevy_core::Name;
+use bevy_ecs::{
+    entity::{EntityMapper, MapEntities},
+    prelude::*,
+    reflect::ReflectMapEntities,
+};
+use bevy_hierarchy::{BuildChildren, Children, Parent};
+use bevy_reflect::Reflect;
+use bevy_transform::{commands::BuildChildrenTransformExt, prelude::Transform};
+
+/// Attaches the entity to the bone named `bone_name` in the hierarchy of `skeleton`.
+///
+/// The bone is looked up by [`Name`] among `skeleton` and its descendants, once the skeleton has
+/// been animated for the frame, and the entity is made a child of the bone with `offset` as its
+/// [`Transform`]. It then follows the bone as it is animated. The bone is looked up again whenever
+/// the component changes, or when the bone is despawned or renamed, and every frame until it is
+/// found, so the component can be added before the skeleton, for instance a glTF scene, is
+/// spawned.
+///
+/// Removing the component detaches the entity from the bone, keeping its current global
+/// transform.
+///
+/// ```
+/// # use bevy_animation::attachment::AttachToBone;
+/// # use bevy_core::Name;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_transform::prelude::Transform;
+/// # let mut world = World::new();
+/// # let character = world.spawn_empty().id();
+/// world.spawn((
+///     Transform::default(),
+///     AttachToBone {
+///         skeleton: character,
+///         bone_name: Name::new("mixamorig:RightHand"),
+///         offset: Transform::from_xyz(0.0, 0.1, 0.0),
+///     },
+/// ));
+/// ```
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, MapEntities)]
+pub struct AttachToBone {
+    /// The root of the skeleton, usually the entity with the
+    /// [`AnimationPlayer`](crate::AnimationPlayer).
+    pub skeleton: Entity,
+    /// The name of the bone.
+    pub bone_name: Name,
+    /// The transform of the entity relative to the bone.
+    pub offset: Transform,
+}
+
+impl AttachToBone {
+    /// Attaches the entity to the bone named `bone_name` in `skeleton`, without offset.
+    pub fn new(skeleton: Entity, bone_name: impl Into<Name>) -> Self {
+        Self {
+            skeleton,
+            bone_name: bone_name.into(),
+            offset: Transform::IDENTITY,
+        }
+    }
+
+    /// Returns the attachment with `offset` as the transform relative to the bone.
+    pub fn with_offset(mut self, offset: Transform) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl MapEntities for AttachToBone {
+    fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+        self.skeleton = entity_mapper.map_entity(self.skeleton);
+    }
+}
+
+/// The bone an entity with [`AttachToBone`] is attached to, once it was found.
+///
+/// This is inserted and kept up to date by [`attach_to_bones`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AttachedBone(Entity);
+
+impl AttachedBone {
+    /// The bone entity.
+    pub fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Returns the entity named `name` among `root` and its descendants, searching depth-first.
+pub fn find_bone(
+    root: Entity,
+    name: &Name,
+    children: &Query<&Children>,
+    names: &Query<&Name>,
+) -> Option<Entity> {
+    let mut stack = vec![root];
+    while let Some(entity) = stack.pop() {
+        if names.get(entity).is_ok_and(|bone_name| bone_name == name) {
+            return Some(entity);
+        }
+        if let Ok(entity_children) = children.get(entity) {
+            stack.extend(entity_children.iter().rev());
+        }
+    }
+    None
+}
+
+/// A system resolving the bones of the [`AttachToBone`] components, and parenting their entities
+/// to them.
+pub fn attach_to_bones(
+    mut commands: Commands,
+    mut attachments: Query<(
+        Entity,
+        Ref<AttachToBone>,
+        Option<&AttachedBone>,
+        Option<&Parent>,
+        &mut Transform,
+    )>,
+    mut detached: RemovedComponents<AttachToBone>,
+    attached: Query<(), With<AttachedBone>>,
+    children: Query<&Children>,
+    names: Query<&Name>,
+) {
+    for entity in detached.read() {
+        if attached.contains(entity) {
+            commands
+                .entity(entity)
+                .remove::<AttachedBone>()
+                .remove_parent_in_place();
+        }
+    }
+
+    for (entity, attachment, attached_bone, parent, mut transform) in &mut attachments {
+        let previous = attached_bone.map(AttachedBone::get);
+        let bone = match previous {
+            Some(bone)
+                if !attachment.is_changed()
+                    && names
+                        .get(bone)
+                        .is_ok_and(|name| *name == attachment.bone_name) =>
+            {
+                bone
+            }
+            _ => {
+                let Some(bone) = find_bone(
+                    attachment.skeleton,
+                    &attachment.bone_name,
+                    &children,
+                    &names,
+                ) else {
+                    continue;
+                };
+                if previous != Some(bone) {
+                    commands.entity(entity).insert(AttachedBone(bone));
+                }
+                bone
+            }
+        };
+
+        if parent.map(Parent::get) != Some(bone) {
+            commands.entity(entity).set_parent(bone);
+        }
+        if attachment.is_changed() || previous != Some(bone) {
+            *transform = attachment.offset;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_hierarchy::BuildWorldChildren;
+
+    #[test]
+    fn attaches_to_named_bone() {
+        let mut world = World::new();
+        let hand = world.spawn(Name::new("hand")).id();
+        let arm = world.spawn(Name::new("arm")).add_child(hand).id();
+        let skeleton = world.spawn(Name::new("root")).add_child(arm).id();
+        let sword = world
+            .spawn((
+                Transform::default(),
+                AttachToBone::new(skeleton, "hand").with_offset(Transform::from_xyz(0.0, 1.0, 0.0)),
+            ))
+            .id();
+
+        world.run_system_once(attach_to_bones);
+        assert_eq!(world.get::<Parent>(sword).map(Parent::get), Some(hand));
+        assert_eq!(world.get::<AttachedBone>(sword), Some(&AttachedBone(hand)));
+        assert_eq!(
+            world.get::<Transform>(sword),
+            Some(&Transform::from_xyz(0.0, 1.0, 0.0))
+        );
+
+        world.get_mut::<AttachToBone>(sword).unwrap().bone_name = Name::new("arm");
+        world.run_system_once(attach_to_bones);
+        assert_eq!(world.get::<Parent>(sword).map(Parent::get), Some(arm));
+
+        world.entity_mut(sword).remove::<AttachToBone>();
+        world.run_system_once(attach_to_bones);
+        assert!(world.get::<Parent>(sword).is_none());
+        assert!(world.get::<AttachedBone>(sword).is_none());
+    }
+}
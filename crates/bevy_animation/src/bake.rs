@@ -0,0 +1,349 @@
+//! Baking animation clips into animation textures, to skin crowds of characters on the GPU.
+//!
+//! Animating a character through an [`AnimationPlayer`](crate::AnimationPlayer) costs a joint
+//! entity per bone, sampled and propagated every frame, which doesn't scale to thousands of
+//! characters. Instead, an [`AnimationBaker`] samples clips once, at a fixed frame rate, into a
+//! texture holding the skinning matrices of every frame, laid out as described by
+//! [`AnimationTextureClip`]. Instances then only store which clip they play and when they
+//! started it, and are skinned from the texture.
+
+use bevy_asset::Assets;
+use bevy_ecs::prelude::*;
+use bevy_hierarchy::Parent;
+use bevy_math::{Affine3A, Mat4, Vec4};
+use bevy_render::{
+    mesh::skinning::{AnimationTextureClip, SkinnedMesh, SkinnedMeshInverseBindposes},
+    render_asset::RenderAssetUsages,
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    texture::Image,
+};
+use bevy_transform::prelude::{GlobalTransform, Transform};
+
+use crate::{AnimationClip, AnimationTarget, AnimationTargetContext, AnimationTargetId};
+
+/// Samples animation clips on the skeleton of a [`SkinnedMesh`] into a [`BakedAnimation`].
+///
+/// The baker is created from a spawned reference character, whose joints provide the hierarchy,
+/// the rest pose and the [`AnimationTargetId`] of each bone. The character must have been
+/// spawned for at least a frame, so that the [`GlobalTransform`]s of its joints are up to date.
+///
+/// ```no_run
+/// # use bevy_animation::{bake::AnimationBaker, AnimationClip};
+/// # use bevy_asset::{Assets, Handle};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::texture::Image;
+/// # fn bake(world: &mut World, character_mesh: Entity, walk: Handle<AnimationClip>) {
+/// let baker = AnimationBaker::from_skinned_mesh(world, character_mesh)
+///     .unwrap()
+///     .with_frame_rate(30.0);
+/// let clips = world.resource::<Assets<AnimationClip>>();
+/// let baked = baker.bake([clips.get(&walk).unwrap()]);
+/// let walk_clip = baked.clips()[0];
+/// let texture = world.resource_mut::<Assets<Image>>().add(baked.to_image());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct AnimationBaker {
+    joints: Vec<BakedJoint>,
+    /// The indices of the joints, parents first.
+    order: Vec<usize>,
+    frame_rate: f32,
+}
+
+#[derive(Clone, Debug)]
+struct BakedJoint {
+    target: Option<AnimationTargetId>,
+    parent: Option<usize>,
+    /// The transform of the joint before animation.
+    rest: Transform,
+    /// The transform of the parent of a root joint, relative to the mesh.
+    base: Affine3A,
+    inverse_bindpose: Mat4,
+}
+
+impl AnimationBaker {
+    /// The frame rate used unless [`with_frame_rate`](Self::with_frame_rate) is called.
+    pub const DEFAULT_FRAME_RATE: f32 = 30.0;
+
+    /// Creates a baker for the skeleton of the [`SkinnedMesh`] of `entity`.
+    ///
+    /// Returns `None` if the entity has no skinned mesh, if its inverse bindposes aren't loaded,
+    /// or if one of its joints has no [`Transform`].
+    pub fn from_skinned_mesh(world: &World, entity: Entity) -> Option<Self> {
+        let skinned_mesh = world.get::<SkinnedMesh>(entity)?;
+        let inverse_bindposes = world
+            .resource::<Assets<SkinnedMeshInverseBindposes>>()
+            .get(&skinned_mesh.inverse_bindposes)?;
+        let mesh_from_world = world
+            .get::<GlobalTransform>(entity)
+            .map_or(Affine3A::IDENTITY, |transform| transform.affine().inverse());
+
+        let mut joints = Vec::with_capacity(skinned_mesh.joints.len());
+        for (&joint, &inverse_bindpose) in skinned_mesh.joints.iter().zip(inverse_bindposes.iter())
+        {
+            let parent_entity = world.get::<Parent>(joint).map(Parent::get);
+            let parent = parent_entity
+                .and_then(|parent| skinned_mesh.joints.iter().position(|&j| j == parent));
+            let base = match (parent, parent_entity) {
+                (None, Some(parent)) => {
+                    let parent_transform = world.get::<GlobalTransform>(parent)?;
+                    mesh_from_world * parent_transform.affine()
+                }
+                _ => mesh_from_world,
+            };
+            joints.push(BakedJoint {
+                target: world.get::<AnimationTarget>(joint).map(|target| target.id),
+                parent,
+                rest: *world.get::<Transform>(joint)?,
+                base,
+                inverse_bindpose,
+            });
+        }
+
+        Some(Self {
+            order: parents_first(&joints),
+            joints,
+            frame_rate: Self::DEFAULT_FRAME_RATE,
+        })
+    }
+
+    /// Returns the baker sampling clips at `frame_rate` frames per second.
+    pub fn with_frame_rate(mut self, frame_rate: f32) -> Self {
+        assert!(frame_rate > 0.0, "The frame rate must be positive.");
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    /// The number of joints of the skeleton.
+    pub fn joint_count(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// The number of frames per second clips are sampled at.
+    pub fn frame_rate(&self) -> f32 {
+        self.frame_rate
+    }
+
+    /// Samples `clips` one after the other, returning their frames and where they are.
+    pub fn bake<'a>(&self, clips: impl IntoIterator<Item = &'a AnimationClip>) -> BakedAnimation {
+        let mut baked = BakedAnimation {
+            joint_count: self.joints.len(),
+            clips: Vec::new(),
+            matrices: Vec::new(),
+        };
+        let mut poses = vec![Affine3A::IDENTITY; self.joints.len()];
+
+        for clip in clips {
+            let frame_count = ((clip.duration() * self.frame_rate).round() as u32).max(1);
+            baked.clips.push(AnimationTextureClip {
+                first_frame: baked.frame_count(),
+                frame_count,
+                frame_rate: self.frame_rate,
+            });
+
+            for frame in 0..frame_count {
+                let seek_time = frame as f32 / self.frame_rate;
+                for &index in &self.order {
+                    let joint = &self.joints[index];
+                    let local = self.sample(joint, clip, seek_time);
+                    let parent = joint.parent.map_or(joint.base, |parent| poses[parent]);
+                    poses[index] = parent * local.compute_affine();
+                }
+                baked
+                    .matrices
+                    .extend(self.joints.iter().zip(&poses).map(|(joint, pose)| {
+                        let skinning = Mat4::from(*pose) * joint.inverse_bindpose;
+                        [skinning.row(0), skinning.row(1), skinning.row(2)]
+                    }));
+            }
+        }
+        baked
+    }
+
+    /// Returns the transform of `joint` in `clip` at `seek_time`.
+    fn sample(&self, joint: &BakedJoint, clip: &AnimationClip, seek_time: f32) -> Transform {
+        let mut transform = joint.rest;
+        let Some((id, curves)) = joint
+            .target
+            .and_then(|id| Some((id, clip.curves_for_target(id)?)))
+        else {
+            return transform;
+        };
+        let target = AnimationTarget {
+            id,
+            player: Entity::PLACEHOLDER,
+        };
+        let (mut added, mut changed) = Default::default();
+        let mut context = AnimationTargetContext {
+            entity: Entity::PLACEHOLDER,
+            target: &target,
+            name: None,
+            transform: Some(Mut::new(
+                &mut transform,
+                &mut added,
+                &mut changed,
+                Default::default(),
+                Default::default(),
+            )),
+            morph_weights: None,
+        };
+        context.apply(curves, 1.0, seek_time);
+        transform
+    }
+}
+
+/// Returns the indices of `joints`, each joint coming after its parent.
+fn parents_first(joints: &[BakedJoint]) -> Vec<usize> {
+    let mut order = Vec::with_capacity(joints.len());
+    let mut visited = vec![false; joints.len()];
+    for index in 0..joints.len() {
+        let mut chain = Vec::new();
+        let mut current = Some(index);
+        while let Some(joint) = current.filter(|&joint| !visited[joint]) {
+            visited[joint] = true;
+            chain.push(joint);
+            current = joints[joint].parent;
+        }
+        order.extend(chain.into_iter().rev());
+    }
+    order
+}
+
+/// The skinning matrices of animation clips sampled by an [`AnimationBaker`].
+#[derive(Clone, Debug, Default)]
+pub struct BakedAnimation {
+    joint_count: usize,
+    clips: Vec<AnimationTextureClip>,
+    /// The rows of the skinning matrix of each joint, frame after frame.
+    matrices: Vec<[Vec4; 3]>,
+}
+
+impl BakedAnimation {
+    /// The number of joints in each frame.
+    pub fn joint_count(&self) -> usize {
+        self.joint_count
+    }
+
+    /// The total number of frames of the clips.
+    pub fn frame_count(&self) -> u32 {
+        (self.matrices.len() / self.joint_count.max(1)) as u32
+    }
+
+    /// Where each baked clip is in the animation texture, in the order the clips were given.
+    pub fn clips(&self) -> &[AnimationTextureClip] {
+        &self.clips
+    }
+
+    /// The rows of the affine skinning matrices of the joints at `frame`.
+    pub fn frame(&self, frame: u32) -> &[[Vec4; 3]] {
+        let start = frame as usize * self.joint_count;
+        &self.matrices[start..start + self.joint_count]
+    }
+
+    /// Creates the animation texture, with a row per frame and three texels per joint.
+    ///
+    /// The texture is `3 * joint_count` texels wide and [`frame_count`](Self::frame_count)
+    /// texels high, which must both be within the texture size limits of the GPU, often 8192
+    /// or only 2048 on WebGL2.
+    pub fn to_image(&self) -> Image {
+        let mut data: Vec<u8> = self
+            .matrices
+            .iter()
+            .flatten()
+            .flat_map(|row| row.to_array())
+            .flat_map(f32::to_le_bytes)
+            .collect();
+        // An empty skeleton still needs a texel.
+        if data.is_empty() {
+            data = vec![0; 16];
+        }
+        Image::new(
+            Extent3d {
+                width: (self.joint_count * 3).max(1) as u32,
+                height: self.frame_count().max(1),
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba32Float,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Interpolation, Keyframes, VariableCurve};
+    use bevy_core::Name;
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_math::Vec3;
+
+    #[test]
+    fn bakes_joint_hierarchy() {
+        let mut world = World::new();
+        let mut inverse_bindposes = Assets::<SkinnedMeshInverseBindposes>::default();
+        let inverse_bindposes = inverse_bindposes.add(vec![Mat4::IDENTITY; 2]);
+
+        let hand_id = AnimationTargetId::from_name(&Name::new("hand"));
+        let hand = world
+            .spawn((
+                Transform::default(),
+                GlobalTransform::default(),
+                AnimationTarget {
+                    id: hand_id,
+                    player: Entity::PLACEHOLDER,
+                },
+            ))
+            .id();
+        let arm = world
+            .spawn((
+                Transform::from_xyz(1.0, 0.0, 0.0),
+                GlobalTransform::default(),
+            ))
+            .add_child(hand)
+            .id();
+        // Joints listed children first, to check that parents are sampled first.
+        let mesh = world
+            .spawn(SkinnedMesh {
+                inverse_bindposes,
+                joints: vec![hand, arm],
+            })
+            .id();
+
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            hand_id,
+            VariableCurve {
+                keyframe_timestamps: vec![0.0, 1.0],
+                keyframes: Keyframes::Translation(vec![Vec3::ZERO, Vec3::new(0.0, 2.0, 0.0)]),
+                interpolation: Interpolation::Linear,
+            },
+        );
+
+        let baker = AnimationBaker::from_skinned_mesh(&world, mesh)
+            .unwrap()
+            .with_frame_rate(2.0);
+        let baked = baker.bake([&clip]);
+        assert_eq!(
+            baked.clips(),
+            [AnimationTextureClip {
+                first_frame: 0,
+                frame_count: 2,
+                frame_rate: 2.0,
+            }]
+        );
+        assert_eq!(baked.frame_count(), 2);
+
+        let [hand_rows, arm_rows] = baked.frame(1) else {
+            panic!("expected two joints");
+        };
+        assert_eq!(arm_rows[0], Vec4::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(hand_rows[0], Vec4::new(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(hand_rows[1], Vec4::new(0.0, 1.0, 0.0, 1.0));
+
+        let image = baked.to_image();
+        assert_eq!(image.width(), 6);
+        assert_eq!(image.height(), 2);
+    }
+}
@@ -0,0 +1,501 @@
+//! Blend spaces, which weight the children of an animation graph node
+//! according to a runtime parameter.
+
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+use petgraph::graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::graph::AnimationNodeIndex;
+
+/// Weights the clips under an animation graph node according to a parameter
+/// of the [`AnimationPlayer`](crate::AnimationPlayer), such as the speed or
+/// the direction of a character.
+///
+/// Each clip of the blend space is a *sample* placed at a position. Every
+/// frame, the samples closest to the parameter are blended together, with
+/// weights that smoothly change as the parameter moves between them, and all
+/// other samples get a weight of zero.
+///
+/// A *one-dimensional* blend space places its samples on a line, and blends
+/// the two samples surrounding the parameter. For example, idle, walk, and run
+/// clips placed at speeds 0, 1.5, and 5.
+///
+/// A *two-dimensional* blend space places its samples on a plane, and blends
+/// the three samples of the triangle containing the parameter. The triangles
+/// are computed automatically from the positions of the samples with a
+/// Delaunay triangulation. Outside of the triangles, the parameter is moved to
+/// the closest point of the triangles. For example, forward, backward and
+/// strafing clips placed at their velocities.
+///
+/// The samples are usually added with
+/// [`AnimationGraph::add_blend_space_clip`](crate::graph::AnimationGraph::add_blend_space_clip),
+/// and must all be playing to contribute to the pose, see
+/// [`AnimationPlayer::play_blend_space`](crate::AnimationPlayer::play_blend_space).
+#[derive(Clone, Debug, Reflect)]
+pub struct BlendSpace {
+    kind: BlendSpaceKind,
+    samples: Vec<BlendSpaceSample>,
+    /// The triangles of a two-dimensional blend space, as indices into
+    /// `samples`.
+    #[reflect(ignore)]
+    triangles: Vec<[usize; 3]>,
+}
+
+/// The number of dimensions of a [`BlendSpace`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum BlendSpaceKind {
+    /// Samples are placed on a line, and only the `x` coordinate of positions
+    /// and parameters is used.
+    OneDimensional,
+    /// Samples are placed on a plane.
+    TwoDimensional,
+}
+
+/// A node of a [`BlendSpace`], and its position.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub struct BlendSpaceSample {
+    /// The weighted node, usually a clip node.
+    pub node: AnimationNodeIndex,
+    /// The position of the sample. For one-dimensional blend spaces, `y` is
+    /// ignored.
+    pub position: Vec2,
+}
+
+/// A version of [`BlendSpace`] suitable for serializing as an asset.
+///
+/// The triangles of two-dimensional blend spaces aren't serialized, but
+/// computed again on load.
+#[derive(Serialize, Deserialize)]
+pub struct SerializedBlendSpace {
+    /// Corresponds to the kind of the [`BlendSpace`].
+    pub kind: BlendSpaceKind,
+    /// The node and position of each sample.
+    pub samples: Vec<(NodeIndex, [f32; 2])>,
+}
+
+impl BlendSpace {
+    /// Creates an empty blend space of the given kind.
+    pub fn new(kind: BlendSpaceKind) -> Self {
+        Self {
+            kind,
+            samples: Vec::new(),
+            triangles: Vec::new(),
+        }
+    }
+
+    /// Creates a one-dimensional blend space with the given nodes and
+    /// positions.
+    pub fn new_1d(samples: impl IntoIterator<Item = (AnimationNodeIndex, f32)>) -> Self {
+        let mut blend_space = Self::new(BlendSpaceKind::OneDimensional);
+        blend_space
+            .samples
+            .extend(samples.into_iter().map(|(node, x)| BlendSpaceSample {
+                node,
+                position: Vec2::new(x, 0.0),
+            }));
+        blend_space
+    }
+
+    /// Creates a two-dimensional blend space with the given nodes and
+    /// positions.
+    pub fn new_2d(samples: impl IntoIterator<Item = (AnimationNodeIndex, Vec2)>) -> Self {
+        let mut blend_space = Self::new(BlendSpaceKind::TwoDimensional);
+        blend_space.samples.extend(
+            samples
+                .into_iter()
+                .map(|(node, position)| BlendSpaceSample { node, position }),
+        );
+        blend_space.triangulate();
+        blend_space
+    }
+
+    /// Returns the number of dimensions of this blend space.
+    pub fn kind(&self) -> BlendSpaceKind {
+        self.kind
+    }
+
+    /// Returns the samples of this blend space, in the order they were added.
+    pub fn samples(&self) -> &[BlendSpaceSample] {
+        &self.samples
+    }
+
+    /// Returns the triangles of a two-dimensional blend space, as indices
+    /// into [`samples`](Self::samples).
+    ///
+    /// This is empty for one-dimensional blend spaces, and for two-dimensional
+    /// ones with less than three samples or with all samples on a line.
+    pub fn triangles(&self) -> &[[usize; 3]] {
+        &self.triangles
+    }
+
+    /// Adds a sample for `node` at `position`.
+    pub fn add_sample(&mut self, node: AnimationNodeIndex, position: Vec2) {
+        self.samples.push(BlendSpaceSample { node, position });
+        if self.kind == BlendSpaceKind::TwoDimensional {
+            self.triangulate();
+        }
+    }
+
+    /// Removes the samples of `node`, returning true if there was any.
+    pub fn remove_sample(&mut self, node: AnimationNodeIndex) -> bool {
+        let len = self.samples.len();
+        self.samples.retain(|sample| sample.node != node);
+        if self.kind == BlendSpaceKind::TwoDimensional {
+            self.triangulate();
+        }
+        self.samples.len() != len
+    }
+
+    /// Calls `f` with the node and weight of every sample for the given
+    /// parameter.
+    ///
+    /// The weights are between 0 and 1 and sum up to 1, unless the blend space
+    /// is empty.
+    pub fn for_each_weight(&self, parameter: Vec2, mut f: impl FnMut(AnimationNodeIndex, f32)) {
+        let mut weights = [(0, 0.0); 3];
+        let count = match self.kind {
+            BlendSpaceKind::OneDimensional => {
+                self.weights_on_line(parameter.x, |position| position.x, &mut weights)
+            }
+            BlendSpaceKind::TwoDimensional => self.weights_2d(parameter, &mut weights),
+        };
+        for (index, sample) in self.samples.iter().enumerate() {
+            let weight = weights[..count]
+                .iter()
+                .filter(|(weighted, _)| *weighted == index)
+                .map(|(_, weight)| weight)
+                .sum();
+            f(sample.node, weight);
+        }
+    }
+
+    /// Returns the weight of the samples of `node` for the given parameter.
+    pub fn weight(&self, node: AnimationNodeIndex, parameter: Vec2) -> f32 {
+        let mut total = 0.0;
+        self.for_each_weight(parameter, |weighted, weight| {
+            if weighted == node {
+                total += weight;
+            }
+        });
+        total
+    }
+
+    /// Blends the two samples surrounding `x` on a line, where `coordinate`
+    /// gives the position of samples on the line, writing their indices and
+    /// weights to `out` and returning how many were written.
+    fn weights_on_line(
+        &self,
+        x: f32,
+        coordinate: impl Fn(Vec2) -> f32,
+        out: &mut [(usize, f32); 3],
+    ) -> usize {
+        // The closest samples below and above `x`.
+        let mut below: Option<(usize, f32)> = None;
+        let mut above: Option<(usize, f32)> = None;
+        for (index, sample) in self.samples.iter().enumerate() {
+            let position = coordinate(sample.position);
+            if position <= x && !matches!(below, Some((_, best)) if best >= position) {
+                below = Some((index, position));
+            }
+            if position >= x && !matches!(above, Some((_, best)) if best <= position) {
+                above = Some((index, position));
+            }
+        }
+
+        match (below, above) {
+            (Some((below, below_x)), Some((above, above_x))) if below != above => {
+                let t = (x - below_x) / (above_x - below_x);
+                out[0] = (below, 1.0 - t);
+                out[1] = (above, t);
+                2
+            }
+            (Some((index, _)), _) | (None, Some((index, _))) => {
+                out[0] = (index, 1.0);
+                1
+            }
+            (None, None) => 0,
+        }
+    }
+
+    /// Blends the three samples of the triangle closest to `parameter`,
+    /// writing their indices and weights to `out` and returning how many were
+    /// written.
+    fn weights_2d(&self, parameter: Vec2, out: &mut [(usize, f32); 3]) -> usize {
+        match self.samples.len() {
+            0 => return 0,
+            1 => {
+                out[0] = (0, 1.0);
+                return 1;
+            }
+            _ => {}
+        }
+
+        let position = |index: usize| self.samples[index].position;
+
+        let mut best_distance = f32::INFINITY;
+        for &[a, b, c] in &self.triangles {
+            let (weights, distance) =
+                closest_in_triangle(position(a), position(b), position(c), parameter);
+            if distance < best_distance {
+                best_distance = distance;
+                *out = [(a, weights[0]), (b, weights[1]), (c, weights[2])];
+            }
+        }
+        if best_distance.is_finite() {
+            return 3;
+        }
+
+        // Without triangles, all samples are on a line: blend the two samples
+        // surrounding the projection of the parameter on that line.
+        let origin = position(0);
+        let end = self
+            .samples
+            .iter()
+            .map(|sample| sample.position)
+            .max_by(|a, b| {
+                a.distance_squared(origin)
+                    .total_cmp(&b.distance_squared(origin))
+            })
+            .unwrap_or(origin);
+        let direction = (end - origin).normalize_or_zero();
+        self.weights_on_line(
+            (parameter - origin).dot(direction),
+            |position| (position - origin).dot(direction),
+            out,
+        )
+    }
+
+    /// Computes the Delaunay triangulation of the samples, with the
+    /// Bowyer-Watson algorithm.
+    fn triangulate(&mut self) {
+        self.triangles.clear();
+        let count = self.samples.len();
+        if count < 3 {
+            return;
+        }
+
+        // Start with a triangle containing all samples, whose vertices are
+        // appended after the samples.
+        let mut points: Vec<Vec2> = self.samples.iter().map(|sample| sample.position).collect();
+        let (min, max) = points
+            .iter()
+            .fold((points[0], points[0]), |(min, max), &point| {
+                (min.min(point), max.max(point))
+            });
+        let center = (min + max) * 0.5;
+        let size = (max - min).max_element().max(1.0);
+        points.push(center + Vec2::new(-20.0 * size, -size));
+        points.push(center + Vec2::new(0.0, 20.0 * size));
+        points.push(center + Vec2::new(20.0 * size, -size));
+        let mut triangles = vec![[count, count + 1, count + 2]];
+
+        let mut edges = Vec::new();
+        for index in 0..count {
+            let point = points[index];
+
+            // Remove the triangles whose circumcircle contains the point, and
+            // keep the boundary of the hole they leave.
+            edges.clear();
+            triangles.retain(|&[a, b, c]| {
+                if !in_circumcircle(points[a], points[b], points[c], point) {
+                    return true;
+                }
+                edges.extend([[a, b], [b, c], [c, a]]);
+                false
+            });
+            let boundary = edges.iter().filter(|&&[a, b]| {
+                edges
+                    .iter()
+                    .filter(|&&[c, d]| (a, b) == (c, d) || (a, b) == (d, c))
+                    .count()
+                    == 1
+            });
+
+            // Fill the hole with triangles connecting its boundary to the
+            // point.
+            triangles.extend(boundary.map(|&[a, b]| [a, b, index]));
+        }
+
+        // Drop the triangles touching the enclosing triangle, and the flat
+        // ones left by samples on a line.
+        triangles.retain(|&[a, b, c]| {
+            a < count
+                && b < count
+                && c < count
+                && (points[b] - points[a])
+                    .perp_dot(points[c] - points[a])
+                    .abs()
+                    > f32::EPSILON
+        });
+        self.triangles = triangles;
+    }
+}
+
+impl From<&BlendSpace> for SerializedBlendSpace {
+    fn from(blend_space: &BlendSpace) -> Self {
+        Self {
+            kind: blend_space.kind,
+            samples: blend_space
+                .samples
+                .iter()
+                .map(|sample| (sample.node, sample.position.to_array()))
+                .collect(),
+        }
+    }
+}
+
+impl From<&SerializedBlendSpace> for BlendSpace {
+    fn from(serialized: &SerializedBlendSpace) -> Self {
+        let mut blend_space = Self::new(serialized.kind);
+        blend_space
+            .samples
+            .extend(
+                serialized
+                    .samples
+                    .iter()
+                    .map(|&(node, position)| BlendSpaceSample {
+                        node,
+                        position: Vec2::from_array(position),
+                    }),
+            );
+        if blend_space.kind == BlendSpaceKind::TwoDimensional {
+            blend_space.triangulate();
+        }
+        blend_space
+    }
+}
+
+/// Returns true if `point` is strictly inside the circumcircle of the
+/// triangle `a`, `b`, `c`.
+fn in_circumcircle(a: Vec2, b: Vec2, c: Vec2, point: Vec2) -> bool {
+    let (a, b, c) = (a - point, b - point, c - point);
+    let determinant = a.length_squared() * b.perp_dot(c) - b.length_squared() * a.perp_dot(c)
+        + c.length_squared() * a.perp_dot(b);
+    if (b - a).perp_dot(c - a) > 0.0 {
+        determinant > 0.0
+    } else {
+        determinant < 0.0
+    }
+}
+
+/// Returns the position of the point of the segment `a`, `b` closest to
+/// `point`, between 0 at `a` and 1 at `b`, and its squared distance to
+/// `point`.
+fn closest_on_segment(a: Vec2, b: Vec2, point: Vec2) -> (f32, f32) {
+    let segment = b - a;
+    let length_squared = segment.length_squared();
+    let t = if length_squared > 0.0 {
+        ((point - a).dot(segment) / length_squared).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (t, (a + segment * t).distance_squared(point))
+}
+
+/// Returns the barycentric coordinates of the point of the triangle `a`, `b`,
+/// `c` closest to `point`, and its squared distance to `point`.
+fn closest_in_triangle(a: Vec2, b: Vec2, c: Vec2, point: Vec2) -> ([f32; 3], f32) {
+    let area = (b - a).perp_dot(c - a);
+    let wa = (c - b).perp_dot(point - b) / area;
+    let wb = (a - c).perp_dot(point - c) / area;
+    let wc = 1.0 - wa - wb;
+    if wa >= 0.0 && wb >= 0.0 && wc >= 0.0 {
+        return ([wa, wb, wc], 0.0);
+    }
+
+    let (t_ab, d_ab) = closest_on_segment(a, b, point);
+    let (t_bc, d_bc) = closest_on_segment(b, c, point);
+    let (t_ca, d_ca) = closest_on_segment(c, a, point);
+    if d_ab <= d_bc && d_ab <= d_ca {
+        ([1.0 - t_ab, t_ab, 0.0], d_ab)
+    } else if d_bc <= d_ca {
+        ([0.0, 1.0 - t_bc, t_bc], d_bc)
+    } else {
+        ([t_ca, 0.0, 1.0 - t_ca], d_ca)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::Vec2;
+
+    use super::BlendSpace;
+    use crate::graph::AnimationNodeIndex;
+
+    fn node(index: u32) -> AnimationNodeIndex {
+        AnimationNodeIndex::new(index as usize)
+    }
+
+    #[test]
+    fn one_dimensional_weights() {
+        let blend_space = BlendSpace::new_1d([(node(1), 0.0), (node(2), 5.0), (node(3), 1.5)]);
+
+        let weights = |x: f32| {
+            let mut weights = Vec::new();
+            blend_space.for_each_weight(Vec2::new(x, 0.0), |_, weight| weights.push(weight));
+            weights
+        };
+        assert_eq!(weights(0.75), [0.5, 0.0, 0.5]);
+        assert_eq!(weights(1.5), [0.0, 0.0, 1.0]);
+        assert_eq!(weights(5.0), [0.0, 1.0, 0.0]);
+        // Parameters outside of the samples are clamped.
+        assert_eq!(weights(-1.0), [1.0, 0.0, 0.0]);
+        assert_eq!(weights(8.0), [0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn two_dimensional_weights() {
+        // A square of directions around an idle sample.
+        let blend_space = BlendSpace::new_2d([
+            (node(0), Vec2::ZERO),
+            (node(1), Vec2::X),
+            (node(2), Vec2::Y),
+            (node(3), Vec2::NEG_X),
+            (node(4), Vec2::NEG_Y),
+        ]);
+        assert_eq!(blend_space.triangles().len(), 4);
+
+        let total = |parameter: Vec2| {
+            let mut total = 0.0;
+            blend_space.for_each_weight(parameter, |_, weight| total += weight);
+            total
+        };
+
+        // On a sample, only that sample is weighted.
+        assert_eq!(blend_space.weight(node(2), Vec2::Y), 1.0);
+        assert_eq!(blend_space.weight(node(0), Vec2::Y), 0.0);
+
+        // Inside a triangle, its three samples are blended.
+        let parameter = Vec2::new(0.25, 0.25);
+        assert!((blend_space.weight(node(0), parameter) - 0.5).abs() < 1e-5);
+        assert!((blend_space.weight(node(1), parameter) - 0.25).abs() < 1e-5);
+        assert!((blend_space.weight(node(2), parameter) - 0.25).abs() < 1e-5);
+        assert_eq!(blend_space.weight(node(3), parameter), 0.0);
+        assert!((total(parameter) - 1.0).abs() < 1e-5);
+
+        // Outside of the triangles, the closest edge is blended.
+        let parameter = Vec2::new(1.0, 1.0);
+        assert!((blend_space.weight(node(1), parameter) - 0.5).abs() < 1e-5);
+        assert!((blend_space.weight(node(2), parameter) - 0.5).abs() < 1e-5);
+        assert!((total(parameter) - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn two_dimensional_degenerate() {
+        let mut blend_space = BlendSpace::new_2d([(node(0), Vec2::ZERO)]);
+        assert_eq!(blend_space.weight(node(0), Vec2::new(3.0, 4.0)), 1.0);
+
+        // Samples on a line have no triangles, the closest segment is blended.
+        blend_space.add_sample(node(1), Vec2::X);
+        blend_space.add_sample(node(2), Vec2::X * 2.0);
+        assert!(blend_space.triangles().is_empty());
+        let parameter = Vec2::new(1.5, 1.0);
+        assert!((blend_space.weight(node(1), parameter) - 0.5).abs() < 1e-5);
+        assert!((blend_space.weight(node(2), parameter) - 0.5).abs() < 1e-5);
+
+        assert!(blend_space.remove_sample(node(1)));
+        assert!(!blend_space.remove_sample(node(1)));
+        assert_eq!(blend_space.samples().len(), 2);
+    }
+}
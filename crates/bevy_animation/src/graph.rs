@@ -5,12 +5,14 @@ use std::ops::{Index, IndexMut};
 
 use bevy_asset::io::Reader;
 use bevy_asset::{Asset, AssetId, AssetLoader, AssetPath, AsyncReadExt as _, Handle, LoadContext};
+use bevy_math::Vec2;
 use bevy_reflect::{Reflect, ReflectSerialize};
 use petgraph::graph::{DiGraph, NodeIndex};
 use ron::de::SpannedError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::blend_space::{BlendSpace, BlendSpaceKind, SerializedBlendSpace};
 use crate::AnimationClip;
 
 /// A graph structure that describes how animation clips are to be blended
@@ -30,6 +32,10 @@ use crate::AnimationClip;
 /// specify an animation clip to play. When a graph is created, it starts with
 /// only a single blend node, the root node.
 ///
+/// Blend nodes can also be *blend spaces*, which additionally weight their
+/// children according to a parameter of the [`crate::AnimationPlayer`]. See
+/// [`BlendSpace`] for more information.
+///
 /// For example, consider the following graph:
 ///
 /// ```text
@@ -90,7 +96,8 @@ pub type AnimationNodeIndex = NodeIndex<u32>;
 ///
 /// If `clip` is present, this is a *clip node*. Otherwise, it's a *blend node*.
 /// Both clip and blend nodes can have weights, and those weights are propagated
-/// down to descendants.
+/// down to descendants. Blend nodes with a `blend_space` are *blend space
+/// nodes*.
 #[derive(Clone, Reflect, Debug)]
 pub struct AnimationGraphNode {
     /// The animation clip associated with this node, if any.
@@ -105,6 +112,12 @@ pub struct AnimationGraphNode {
     /// has weight 0.3 and its parent blend node has weight 0.6, the computed
     /// weight of the animation clip is 0.18.
     pub weight: f32,
+
+    /// The blend space weighting the children of this node, if any.
+    ///
+    /// The weight of each child is multiplied by its weight in the blend
+    /// space. Children that aren't samples of the blend space are unaffected.
+    pub blend_space: Option<BlendSpace>,
 }
 
 /// An [`AssetLoader`] that can load [`AnimationGraph`]s as assets.
@@ -156,6 +169,9 @@ pub struct SerializedAnimationGraphNode {
     pub clip: Option<SerializedAnimationClip>,
     /// Corresponds to the `weight` field on [`AnimationGraphNode`].
     pub weight: f32,
+    /// Corresponds to the `blend_space` field on [`AnimationGraphNode`].
+    #[serde(default)]
+    pub blend_space: Option<SerializedBlendSpace>,
 }
 
 /// A version of `Handle<AnimationClip>` suitable for serializing as an asset.
@@ -205,6 +221,7 @@ impl AnimationGraph {
         let node_index = self.graph.add_node(AnimationGraphNode {
             clip: Some(clip),
             weight,
+            blend_space: None,
         });
         self.graph.add_edge(parent, node_index, ());
         node_index
@@ -239,13 +256,59 @@ impl AnimationGraph {
     /// animation evaluation, the descendants of this blend node will have their
     /// weights multiplied by the weight of the blend.
     pub fn add_blend(&mut self, weight: f32, parent: AnimationNodeIndex) -> AnimationNodeIndex {
-        let node_index = self
-            .graph
-            .add_node(AnimationGraphNode { clip: None, weight });
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            clip: None,
+            weight,
+            blend_space: None,
+        });
         self.graph.add_edge(parent, node_index, ());
         node_index
     }
 
+    /// Adds an empty blend space node of the given kind to the animation graph
+    /// with the given weight and returns its index.
+    ///
+    /// The blend space node will be placed under the supplied `parent` node.
+    /// Add samples to it with [`AnimationGraph::add_blend_space_clip`].
+    pub fn add_blend_space(
+        &mut self,
+        kind: BlendSpaceKind,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            clip: None,
+            weight,
+            blend_space: Some(BlendSpace::new(kind)),
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds an [`AnimationClip`] to the animation graph as a sample of the
+    /// given blend space node, at the given position, and returns its index.
+    ///
+    /// The clip node has a weight of 1.0. For one-dimensional blend spaces,
+    /// the `y` coordinate of `position` is ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `blend_space` isn't a blend space node.
+    pub fn add_blend_space_clip(
+        &mut self,
+        clip: Handle<AnimationClip>,
+        position: Vec2,
+        blend_space: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.add_clip(clip, 1.0, blend_space);
+        self[blend_space]
+            .blend_space
+            .as_mut()
+            .expect("the parent of a blend space clip should be a blend space node")
+            .add_sample(node_index, position);
+        node_index
+    }
+
     /// Adds an edge from the edge `from` to `to`, making `to` a child of
     /// `from`.
     ///
@@ -318,6 +381,7 @@ impl Default for AnimationGraphNode {
         Self {
             clip: None,
             weight: 1.0,
+            blend_space: None,
         }
     }
 }
@@ -362,6 +426,7 @@ impl AssetLoader for AnimationGraphAssetLoader {
                         }
                     }),
                     weight: serialized_node.weight,
+                    blend_space: serialized_node.blend_space.as_ref().map(BlendSpace::from),
                 },
                 |_, _| (),
             ),
@@ -383,6 +448,7 @@ impl From<AnimationGraph> for SerializedAnimationGraph {
             graph: animation_graph.graph.map(
                 |_, node| SerializedAnimationGraphNode {
                     weight: node.weight,
+                    blend_space: node.blend_space.as_ref().map(SerializedBlendSpace::from),
                     clip: node.clip.as_ref().map(|clip| match clip.path() {
                         Some(path) => SerializedAnimationClip::AssetPath(path.clone()),
                         None => SerializedAnimationClip::AssetId(clip.id()),
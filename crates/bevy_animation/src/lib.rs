@@ -8,7 +8,12 @@
 //! Animation for the game engine Bevy
 
 mod animatable;
+pub mod attachment;
+pub mod bake;
+pub mod blend_space;
 mod graph;
+pub mod path;
+pub mod state_machine;
 mod transition;
 mod util;
 
@@ -24,7 +29,7 @@ use bevy_core::Name;
 use bevy_ecs::entity::MapEntities;
 use bevy_ecs::prelude::*;
 use bevy_ecs::reflect::ReflectMapEntities;
-use bevy_math::{FloatExt, Quat, Vec3};
+use bevy_math::{FloatExt, Quat, Vec2, Vec3};
 use bevy_reflect::Reflect;
 use bevy_render::mesh::morph::MorphWeights;
 use bevy_time::Time;
@@ -46,11 +51,27 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, graph::*, transition::*, AnimationClip, AnimationPlayer, AnimationPlugin,
-        Interpolation, Keyframes, VariableCurve,
+        animatable::*,
+        attachment::AttachToBone,
+        blend_space::{BlendSpace, BlendSpaceKind},
+        graph::*,
+        path::{
+            CurvePath, PathCompleted, PathFollower, PathLoopMode, SpeedProfile, WaypointReached,
+        },
+        state_machine::{
+            Animator, AnimatorController, AnimatorParameter, AnimatorState, AnimatorStateChanged,
+            AnimatorTransition, ConditionTest, TransitionCondition,
+        },
+        transition::*,
+        AnimationClip, AnimationPlayer, AnimationPlugin, Interpolation, Keyframes, VariableCurve,
     };
 }
 
+use crate::attachment::{attach_to_bones, AttachToBone};
+use crate::path::{follow_paths, CurvePath, PathCompleted, PathFollower, WaypointReached};
+use crate::state_machine::{
+    update_animators, Animator, AnimatorController, AnimatorControllerLoader, AnimatorStateChanged,
+};
 use crate::transition::{advance_transitions, expire_completed_transitions};
 
 /// The [UUID namespace] of animation targets (e.g. bones).
@@ -513,6 +534,7 @@ pub struct AnimationPlayer {
     /// ordering when applying the animations.
     active_animations: BTreeMap<AnimationNodeIndex, ActiveAnimation>,
     blend_weights: HashMap<AnimationNodeIndex, f32>,
+    blend_space_parameters: HashMap<AnimationNodeIndex, Vec2>,
 }
 
 /// The components that we might need to read or write during animation of each
@@ -535,6 +557,9 @@ pub struct AnimationGraphEvaluator {
     dfs_visited: FixedBitSet,
     /// Accumulated weights for each node.
     weights: Vec<f32>,
+    /// The weights given to each node by the blend space of its parent, if
+    /// any.
+    blend_space_weights: Vec<f32>,
 }
 
 thread_local! {
@@ -680,6 +705,49 @@ impl AnimationPlayer {
     pub fn animation_is_playing(&self, animation: AnimationNodeIndex) -> bool {
         self.active_animations.contains_key(&animation)
     }
+
+    /// Starts playing every sample of the given blend space node on repeat,
+    /// unless they are already playing.
+    ///
+    /// Does nothing if `blend_space` isn't a blend space node of `graph`.
+    pub fn play_blend_space(
+        &mut self,
+        graph: &AnimationGraph,
+        blend_space: AnimationNodeIndex,
+    ) -> &mut Self {
+        let Some(blend_space) = graph
+            .get(blend_space)
+            .and_then(|node| node.blend_space.as_ref())
+        else {
+            return self;
+        };
+        for sample in blend_space.samples() {
+            self.play(sample.node).repeat();
+        }
+        self
+    }
+
+    /// Returns the parameter of the given blend space node, which is zero
+    /// unless set with [`AnimationPlayer::set_blend_space_parameter`].
+    pub fn blend_space_parameter(&self, blend_space: AnimationNodeIndex) -> Vec2 {
+        self.blend_space_parameters
+            .get(&blend_space)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets the parameter of the given blend space node, such as the speed or
+    /// the direction of a character, which weights its samples.
+    ///
+    /// For one-dimensional blend spaces, the `y` coordinate is ignored.
+    pub fn set_blend_space_parameter(
+        &mut self,
+        blend_space: AnimationNodeIndex,
+        parameter: Vec2,
+    ) -> &mut Self {
+        self.blend_space_parameters.insert(blend_space, parameter);
+        self
+    }
 }
 
 /// A system that advances the time for all playing animations.
@@ -707,7 +775,7 @@ pub fn advance_animations(
             let AnimationPlayer {
                 ref mut active_animations,
                 ref blend_weights,
-                ..
+                ref blend_space_parameters,
             } = *player;
 
             // Reset our state.
@@ -729,6 +797,7 @@ pub fn advance_animations(
                 {
                     weight *= animation_graph[parent_index].weight;
                 }
+                weight *= evaluator.blend_space_weights[node_index.index()];
                 evaluator.weights[node_index.index()] = weight;
 
                 if let Some(active_animation) = active_animations.get_mut(&node_index) {
@@ -751,6 +820,20 @@ pub fn advance_animations(
                     active_animation.computed_weight = weight;
                 }
 
+                // Weight the samples of blend spaces for the current parameter.
+                if let Some(ref blend_space) = node.blend_space {
+                    let parameter = blend_space_parameters
+                        .get(&node_index)
+                        .copied()
+                        .unwrap_or_default();
+                    let blend_space_weights = &mut evaluator.blend_space_weights;
+                    blend_space.for_each_weight(parameter, |sample, weight| {
+                        if let Some(sample_weight) = blend_space_weights.get_mut(sample.index()) {
+                            *sample_weight = weight;
+                        }
+                    });
+                }
+
                 // Push children.
                 evaluator.dfs_stack.extend(
                     animation_graph
@@ -827,7 +910,9 @@ pub fn animate_targets(
             for (&animation_graph_node_index, active_animation) in
                 animation_player.active_animations.iter()
             {
-                if active_animation.weight == 0.0 {
+                // Animations weighted out, for instance by a blend space,
+                // contribute nothing.
+                if active_animation.weight == 0.0 || active_animation.computed_weight == 0.0 {
                     continue;
                 }
 
@@ -1155,16 +1240,30 @@ impl Plugin for AnimationPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset::<AnimationClip>()
             .init_asset::<AnimationGraph>()
+            .init_asset::<CurvePath>()
+            .init_asset::<AnimatorController>()
             .init_asset_loader::<AnimationGraphAssetLoader>()
+            .init_asset_loader::<AnimatorControllerLoader>()
             .register_asset_reflect::<AnimationClip>()
             .register_asset_reflect::<AnimationGraph>()
             .register_type::<AnimationPlayer>()
             .register_type::<AnimationTarget>()
             .register_type::<AnimationTransitions>()
             .register_type::<NodeIndex>()
+            .register_type::<PathFollower>()
+            .register_type::<Animator>()
+            .register_type::<AttachToBone>()
+            .add_event::<WaypointReached>()
+            .add_event::<PathCompleted>()
+            .add_event::<AnimatorStateChanged>()
+            .add_systems(
+                PostUpdate,
+                follow_paths.before(TransformSystem::TransformPropagate),
+            )
             .add_systems(
                 PostUpdate,
                 (
+                    update_animators,
                     advance_transitions,
                     advance_animations,
                     animate_targets,
@@ -1172,6 +1271,12 @@ impl Plugin for AnimationPlugin {
                 )
                     .chain()
                     .before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                PostUpdate,
+                attach_to_bones
+                    .after(animate_targets)
+                    .before(TransformSystem::TransformPropagate),
             );
     }
 }
@@ -1220,6 +1325,10 @@ impl AnimationGraphEvaluator {
 
         self.weights.clear();
         self.weights.extend(iter::repeat(0.0).take(node_count));
+
+        self.blend_space_weights.clear();
+        self.blend_space_weights
+            .extend(iter::repeat(1.0).take(node_count));
     }
 }
 
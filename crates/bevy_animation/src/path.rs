@@ -0,0 +1,495 @@
+//! Moving entities along curves at a controlled speed, for cutscenes, patrols and rail cameras.
+
+use bevy_asset::{Asset, Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_math::{cubic_splines::CubicCurve, Dir3, Vec3};
+use bevy_reflect::{Reflect, TypePath};
+use bevy_time::Time;
+use bevy_transform::prelude::Transform;
+
+/// The number of arc-length samples taken on each segment of a [`CurvePath`].
+const SAMPLES_PER_SEGMENT: usize = 32;
+
+/// A curve that entities with a [`PathFollower`] move along.
+///
+/// The curve is parameterized by distance: positions are looked up by the distance travelled
+/// from the start of the curve, so that followers move at the speed of their [`SpeedProfile`]
+/// however the control points of the curve are spaced.
+///
+/// The joints between the segments of the curve are its waypoints. A [`WaypointReached`] event is
+/// sent whenever a follower passes one of them.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct CurvePath {
+    curve: CubicCurve<Vec3>,
+    /// The distance travelled at each sample, sampled uniformly in `t`.
+    distances: Vec<f32>,
+    waypoints: Vec<f32>,
+}
+
+impl CurvePath {
+    /// Creates a path following `curve`.
+    pub fn new(curve: CubicCurve<Vec3>) -> Self {
+        let subdivisions = curve.segments().len() * SAMPLES_PER_SEGMENT;
+        let mut distances = Vec::with_capacity(subdivisions + 1);
+        let mut travelled = 0.0;
+        let mut previous = None;
+        for position in curve.iter_positions(subdivisions) {
+            if let Some(previous) = previous {
+                travelled += Vec3::distance(previous, position);
+            }
+            distances.push(travelled);
+            previous = Some(position);
+        }
+        let waypoints = distances
+            .iter()
+            .step_by(SAMPLES_PER_SEGMENT)
+            .copied()
+            .collect();
+        Self {
+            curve,
+            distances,
+            waypoints,
+        }
+    }
+
+    /// The curve of the path.
+    pub fn curve(&self) -> &CubicCurve<Vec3> {
+        &self.curve
+    }
+
+    /// The length of the path.
+    pub fn length(&self) -> f32 {
+        self.distances.last().copied().unwrap_or(0.0)
+    }
+
+    /// The distances from the start of the path to each of its waypoints, including the start
+    /// and the end of the path.
+    pub fn waypoints(&self) -> &[f32] {
+        &self.waypoints
+    }
+
+    /// Returns the parameter `t` of the curve at `distance` from the start of the path.
+    ///
+    /// `distance` is clamped to the length of the path.
+    pub fn t_at_distance(&self, distance: f32) -> f32 {
+        if self.distances.len() < 2 {
+            return 0.0;
+        }
+        let distance = distance.clamp(0.0, self.length());
+        let index = self
+            .distances
+            .partition_point(|&sample| sample <= distance)
+            .clamp(1, self.distances.len() - 1);
+        let (start, end) = (self.distances[index - 1], self.distances[index]);
+        let fraction = if end > start {
+            (distance - start) / (end - start)
+        } else {
+            0.0
+        };
+        (index - 1) as f32 / SAMPLES_PER_SEGMENT as f32 + fraction / SAMPLES_PER_SEGMENT as f32
+    }
+
+    /// Returns the position at `distance` from the start of the path.
+    pub fn position_at_distance(&self, distance: f32) -> Vec3 {
+        if self.curve.segments().is_empty() {
+            return Vec3::ZERO;
+        }
+        self.curve.position(self.t_at_distance(distance))
+    }
+
+    /// Returns the direction of the path at `distance` from its start, if it isn't degenerate.
+    pub fn direction_at_distance(&self, distance: f32) -> Option<Dir3> {
+        if self.curve.segments().is_empty() {
+            return None;
+        }
+        Dir3::new(self.curve.velocity(self.t_at_distance(distance))).ok()
+    }
+}
+
+/// How the speed of a [`PathFollower`] changes along its path.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+pub enum SpeedProfile {
+    /// Moves at a constant speed, in units per second.
+    Constant(f32),
+    /// Accelerates from `min_speed` to `max_speed` over `ramp_distance` at the start of the path,
+    /// and slows down symmetrically before its end.
+    Ramp {
+        /// The speed at the ends of the path. Must be greater than zero for the follower to
+        /// leave the start of the path.
+        min_speed: f32,
+        /// The speed in the middle of the path.
+        max_speed: f32,
+        /// The distance over which the follower speeds up and slows down.
+        ramp_distance: f32,
+    },
+    /// Interpolates linearly between speeds given at distances from the start of the path,
+    /// sorted by distance.
+    Keyframes(Vec<(f32, f32)>),
+}
+
+impl Default for SpeedProfile {
+    fn default() -> Self {
+        SpeedProfile::Constant(1.0)
+    }
+}
+
+impl SpeedProfile {
+    /// Returns the speed at `distance` from the start of a path of the given `length`.
+    pub fn speed_at(&self, distance: f32, length: f32) -> f32 {
+        match self {
+            SpeedProfile::Constant(speed) => *speed,
+            SpeedProfile::Ramp {
+                min_speed,
+                max_speed,
+                ramp_distance,
+            } => {
+                let from_end = distance.min(length - distance).max(0.0);
+                let ramp = if *ramp_distance > 0.0 {
+                    (from_end / ramp_distance).min(1.0)
+                } else {
+                    1.0
+                };
+                min_speed + (max_speed - min_speed) * ramp
+            }
+            SpeedProfile::Keyframes(keyframes) => {
+                let index = keyframes.partition_point(|&(key, _)| key <= distance);
+                match (
+                    index.checked_sub(1).map(|i| keyframes[i]),
+                    keyframes.get(index),
+                ) {
+                    (Some((start, from)), Some(&(end, to))) if end > start => {
+                        from + (to - from) * (distance - start) / (end - start)
+                    }
+                    (Some((_, speed)), _) | (None, Some(&(_, speed))) => speed,
+                    (None, None) => 0.0,
+                }
+            }
+        }
+    }
+}
+
+/// What a [`PathFollower`] does when it reaches the end of its path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum PathLoopMode {
+    /// Stops at the end of the path.
+    #[default]
+    Once,
+    /// Jumps back to the start of the path. Best used with closed curves.
+    Loop,
+    /// Turns around and moves back towards the start of the path, and so on.
+    PingPong,
+}
+
+/// Moves the [`Transform`] of its entity along a [`CurvePath`].
+///
+/// The translation of the entity is set to the position on the path every frame. When
+/// [`look_ahead`](Self::look_ahead) is set, the entity is also rotated to look towards a point
+/// further along the path, which smooths the orientation of rail cameras.
+///
+/// Followers send [`WaypointReached`] and [`PathCompleted`] events as they move.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component)]
+pub struct PathFollower {
+    /// The path to follow.
+    pub path: Handle<CurvePath>,
+    /// The distance travelled from the start of the path.
+    pub distance: f32,
+    /// The speed of the follower along the path.
+    pub speed: SpeedProfile,
+    /// What happens at the end of the path.
+    pub mode: PathLoopMode,
+    /// The distance ahead on the path the entity looks at, or `None` to leave the rotation of
+    /// the entity untouched. Zero looks along the direction of the path.
+    pub look_ahead: Option<f32>,
+    /// The up direction used to orient the entity.
+    pub up: Dir3,
+    /// Whether the follower is paused.
+    pub paused: bool,
+    reversed: bool,
+    finished: bool,
+}
+
+impl Default for PathFollower {
+    fn default() -> Self {
+        Self {
+            path: Handle::default(),
+            distance: 0.0,
+            speed: SpeedProfile::default(),
+            mode: PathLoopMode::default(),
+            look_ahead: None,
+            up: Dir3::Y,
+            paused: false,
+            reversed: false,
+            finished: false,
+        }
+    }
+}
+
+impl PathFollower {
+    /// Creates a follower moving along `path` at a constant speed of one unit per second.
+    pub fn new(path: Handle<CurvePath>) -> Self {
+        Self {
+            path,
+            ..Default::default()
+        }
+    }
+
+    /// Returns this follower with the given speed profile.
+    pub fn with_speed(mut self, speed: SpeedProfile) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Returns this follower with the given loop mode.
+    pub fn with_mode(mut self, mode: PathLoopMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Returns this follower, looking `distance` ahead on the path.
+    pub fn with_look_ahead(mut self, distance: f32) -> Self {
+        self.look_ahead = Some(distance);
+        self
+    }
+
+    /// Returns `true` if the follower is moving back towards the start of the path, in
+    /// [`PathLoopMode::PingPong`].
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Returns `true` if the follower has reached the end of the path in [`PathLoopMode::Once`].
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Moves the follower back to the start of its path.
+    pub fn restart(&mut self) {
+        self.distance = 0.0;
+        self.reversed = false;
+        self.finished = false;
+    }
+
+    /// Moves the follower `step` along `path`, calling `on_waypoint` with the index of every
+    /// waypoint passed and `on_completed` every time an end of the path is reached.
+    fn advance(
+        &mut self,
+        path: &CurvePath,
+        mut step: f32,
+        mut on_waypoint: impl FnMut(usize),
+        mut on_completed: impl FnMut(),
+    ) {
+        let length = path.length();
+        if self.finished || length <= 0.0 {
+            return;
+        }
+        // Followers stepping over the whole path several times in a frame only report the
+        // first few laps.
+        for _ in 0..8 {
+            let (target, end) = if self.reversed {
+                (self.distance - step, 0.0)
+            } else {
+                (self.distance + step, length)
+            };
+            let overshoot = if self.reversed {
+                end - target
+            } else {
+                target - end
+            };
+            let next = if overshoot >= 0.0 { end } else { target };
+
+            let passed = |waypoint: f32| {
+                if self.reversed {
+                    waypoint < self.distance && waypoint >= next
+                } else {
+                    waypoint > self.distance && waypoint <= next
+                }
+            };
+            let waypoints = path.waypoints();
+            if self.reversed {
+                (0..waypoints.len())
+                    .rev()
+                    .filter(|&i| passed(waypoints[i]))
+                    .for_each(&mut on_waypoint);
+            } else {
+                (0..waypoints.len())
+                    .filter(|&i| passed(waypoints[i]))
+                    .for_each(&mut on_waypoint);
+            }
+            self.distance = next;
+
+            if overshoot < 0.0 {
+                return;
+            }
+            on_completed();
+            step = overshoot;
+            match self.mode {
+                PathLoopMode::Once => {
+                    self.finished = true;
+                    return;
+                }
+                PathLoopMode::Loop => self.distance = 0.0,
+                PathLoopMode::PingPong => self.reversed = !self.reversed,
+            }
+            if step == 0.0 {
+                return;
+            }
+        }
+    }
+}
+
+/// Sent when a [`PathFollower`] passes one of the waypoints of its [`CurvePath`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WaypointReached {
+    /// The entity of the follower.
+    pub entity: Entity,
+    /// The index of the waypoint in [`CurvePath::waypoints`].
+    pub waypoint: usize,
+}
+
+/// Sent when a [`PathFollower`] reaches an end of its [`CurvePath`]: the end of the path, or
+/// the start of the path when moving back in [`PathLoopMode::PingPong`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathCompleted {
+    /// The entity of the follower.
+    pub entity: Entity,
+}
+
+/// Moves the entities with a [`PathFollower`] along their path.
+pub fn follow_paths(
+    time: Res<Time>,
+    paths: Res<Assets<CurvePath>>,
+    mut followers: Query<(Entity, &mut PathFollower, &mut Transform)>,
+    mut waypoint_events: EventWriter<WaypointReached>,
+    mut completed_events: EventWriter<PathCompleted>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut follower, mut transform) in &mut followers {
+        let Some(path) = paths.get(&follower.path) else {
+            continue;
+        };
+        if !follower.paused {
+            let step = follower.speed.speed_at(follower.distance, path.length()) * delta;
+            follower.advance(
+                path,
+                step,
+                |waypoint| {
+                    waypoint_events.send(WaypointReached { entity, waypoint });
+                },
+                || {
+                    completed_events.send(PathCompleted { entity });
+                },
+            );
+        }
+
+        let position = path.position_at_distance(follower.distance);
+        transform.translation = position;
+        let Some(look_ahead) = follower.look_ahead else {
+            continue;
+        };
+        let direction = if look_ahead > 0.0 {
+            let ahead = if follower.reversed {
+                follower.distance - look_ahead
+            } else {
+                follower.distance + look_ahead
+            };
+            let ahead = match follower.mode {
+                PathLoopMode::Loop => ahead.rem_euclid(path.length()),
+                _ => ahead,
+            };
+            Dir3::new(path.position_at_distance(ahead) - position).ok()
+        } else {
+            None
+        };
+        // Near the ends of the path there is nothing to look at, so the follower looks along
+        // the path instead.
+        let direction = direction.or_else(|| {
+            let direction = path.direction_at_distance(follower.distance)?;
+            Some(if follower.reversed {
+                -direction
+            } else {
+                direction
+            })
+        });
+        if let Some(direction) = direction {
+            transform.look_to(direction, follower.up);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::cubic_splines::{CubicGenerator, LinearSpline};
+
+    fn straight_path() -> CurvePath {
+        // Three segments of length 2 along the x axis.
+        CurvePath::new(
+            LinearSpline::new([Vec3::ZERO, Vec3::X * 2.0, Vec3::X * 4.0, Vec3::X * 6.0]).to_curve(),
+        )
+    }
+
+    #[test]
+    fn path_is_parameterized_by_distance() {
+        let path = straight_path();
+        assert!((path.length() - 6.0).abs() < 1e-4);
+        assert_eq!(path.waypoints().len(), 4);
+        for distance in [0.0, 0.5, 2.0, 3.7, 6.0] {
+            let position = path.position_at_distance(distance);
+            assert!(
+                (position.x - distance).abs() < 1e-4,
+                "{distance}: {position}"
+            );
+        }
+        assert!(path.position_at_distance(10.0).distance(Vec3::X * 6.0) < 1e-4);
+        assert_eq!(path.direction_at_distance(1.0), Some(Dir3::X));
+    }
+
+    #[test]
+    fn speed_profiles() {
+        assert_eq!(SpeedProfile::Constant(2.0).speed_at(3.0, 10.0), 2.0);
+        let ramp = SpeedProfile::Ramp {
+            min_speed: 1.0,
+            max_speed: 3.0,
+            ramp_distance: 2.0,
+        };
+        assert_eq!(ramp.speed_at(0.0, 10.0), 1.0);
+        assert_eq!(ramp.speed_at(1.0, 10.0), 2.0);
+        assert_eq!(ramp.speed_at(5.0, 10.0), 3.0);
+        assert_eq!(ramp.speed_at(9.0, 10.0), 2.0);
+        let keyframes = SpeedProfile::Keyframes(vec![(1.0, 2.0), (3.0, 4.0)]);
+        assert_eq!(keyframes.speed_at(0.0, 10.0), 2.0);
+        assert_eq!(keyframes.speed_at(2.0, 10.0), 3.0);
+        assert_eq!(keyframes.speed_at(5.0, 10.0), 4.0);
+    }
+
+    fn advance(follower: &mut PathFollower, path: &CurvePath, step: f32) -> (Vec<usize>, usize) {
+        let mut waypoints = Vec::new();
+        let mut completions = 0;
+        follower.advance(path, step, |i| waypoints.push(i), || completions += 1);
+        (waypoints, completions)
+    }
+
+    #[test]
+    fn followers_report_waypoints_and_completion() {
+        let path = straight_path();
+        let mut follower = PathFollower::new(Handle::default());
+
+        assert_eq!(advance(&mut follower, &path, 2.5), (vec![1], 0));
+        assert_eq!(advance(&mut follower, &path, 5.0), (vec![2, 3], 1));
+        assert_eq!(follower.distance, 6.0);
+        assert!(follower.is_finished());
+        assert_eq!(advance(&mut follower, &path, 1.0), (vec![], 0));
+
+        let mut follower = PathFollower::new(Handle::default()).with_mode(PathLoopMode::Loop);
+        follower.distance = 5.0;
+        assert_eq!(advance(&mut follower, &path, 3.0), (vec![3, 1], 1));
+        assert!((follower.distance - 2.0).abs() < 1e-4);
+
+        let mut follower = PathFollower::new(Handle::default()).with_mode(PathLoopMode::PingPong);
+        follower.distance = 5.0;
+        assert_eq!(advance(&mut follower, &path, 3.0), (vec![3, 2], 1));
+        assert!(follower.is_reversed());
+        assert!((follower.distance - 4.0).abs() < 1e-4);
+    }
+}
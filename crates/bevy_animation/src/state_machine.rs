@@ -0,0 +1,666 @@
+//! Animation state machines, playing the nodes of an [`AnimationGraph`](crate::graph::AnimationGraph)
+//! as states and switching between them as parameters change.
+
+use std::io;
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, Assets, AsyncReadExt as _, Handle, LoadContext};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_utils::{Duration, HashMap};
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{graph::AnimationNodeIndex, AnimationPlayer, AnimationTransitions};
+
+/// The index of a state in an [`AnimatorController`].
+pub type AnimatorStateIndex = usize;
+
+/// A state machine driving the [`AnimationPlayer`] of the entities with an [`Animator`].
+///
+/// Each [`AnimatorState`] plays a node of the [`AnimationGraph`](crate::graph::AnimationGraph) of
+/// the entity, either a clip or a blend node. [`AnimatorTransition`]s switch between states when
+/// their [`TransitionCondition`]s hold, cross-fading the animations of the states with
+/// [`AnimationTransitions`].
+///
+/// Conditions check the parameters of the [`Animator`], which default to the
+/// [`parameters`](Self::parameters) of the controller, or, with the `bevy_ai` feature, the values
+/// of the `Blackboard` of the entity.
+///
+/// Controllers are assets and can be loaded from [RON] files, with an `.animctrl.ron` extension.
+///
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Asset, Reflect, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AnimatorController {
+    /// The states of the state machine.
+    pub states: Vec<AnimatorState>,
+    /// The transitions between states, checked in order: the first one that can happen does.
+    #[serde(default)]
+    pub transitions: Vec<AnimatorTransition>,
+    /// The state entered first.
+    #[serde(default)]
+    pub initial_state: AnimatorStateIndex,
+    /// The default values of the parameters, used until they are set on the [`Animator`].
+    #[serde(default)]
+    pub parameters: HashMap<String, AnimatorParameter>,
+}
+
+impl AnimatorController {
+    /// Creates a controller without states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a state, returning its index. The first state added is the initial state.
+    pub fn add_state(&mut self, state: AnimatorState) -> AnimatorStateIndex {
+        self.states.push(state);
+        self.states.len() - 1
+    }
+
+    /// Adds a transition, checked after the transitions already added.
+    pub fn add_transition(&mut self, transition: AnimatorTransition) -> &mut Self {
+        self.transitions.push(transition);
+        self
+    }
+
+    /// Sets the default value of the parameter `name`.
+    pub fn add_parameter(
+        &mut self,
+        name: impl Into<String>,
+        value: AnimatorParameter,
+    ) -> &mut Self {
+        self.parameters.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the state at `index`.
+    pub fn state(&self, index: AnimatorStateIndex) -> Option<&AnimatorState> {
+        self.states.get(index)
+    }
+
+    /// Returns the index of the first state named `name`.
+    pub fn state_by_name(&self, name: &str) -> Option<AnimatorStateIndex> {
+        self.states.iter().position(|state| state.name == name)
+    }
+}
+
+/// A state of an [`AnimatorController`].
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
+pub struct AnimatorState {
+    /// The name of the state.
+    pub name: String,
+    /// The node of the animation graph played in this state.
+    pub node: AnimationNodeIndex,
+    /// The playback speed of the node.
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    /// Whether the node repeats while in this state.
+    #[serde(default = "default_repeat")]
+    pub repeat: bool,
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+fn default_repeat() -> bool {
+    true
+}
+
+impl AnimatorState {
+    /// Creates a state named `name`, repeating `node` at normal speed.
+    pub fn new(name: impl Into<String>, node: AnimationNodeIndex) -> Self {
+        Self {
+            name: name.into(),
+            node,
+            speed: default_speed(),
+            repeat: default_repeat(),
+        }
+    }
+
+    /// Returns the state with its playback `speed` set.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Returns the state with `repeat` set.
+    pub fn with_repeat(mut self, repeat: bool) -> Self {
+        self.repeat = repeat;
+        self
+    }
+}
+
+/// A transition between two states of an [`AnimatorController`].
+#[derive(Reflect, Clone, Debug, Serialize, Deserialize)]
+pub struct AnimatorTransition {
+    /// The state the transition leaves, or `None` to leave any other state.
+    #[serde(default)]
+    pub from: Option<AnimatorStateIndex>,
+    /// The state the transition enters.
+    pub to: AnimatorStateIndex,
+    /// The conditions that must all hold for the transition to happen.
+    #[serde(default)]
+    pub conditions: Vec<TransitionCondition>,
+    /// The time, in seconds, the state left must have been active before the transition can
+    /// happen.
+    #[serde(default)]
+    pub exit_time: f32,
+    /// The duration, in seconds, of the cross-fade between the states.
+    #[serde(default)]
+    pub duration: f32,
+}
+
+impl AnimatorTransition {
+    /// Creates a transition from the state `from` to the state `to`, happening immediately.
+    pub fn new(from: AnimatorStateIndex, to: AnimatorStateIndex) -> Self {
+        Self {
+            from: Some(from),
+            to,
+            conditions: Vec::new(),
+            exit_time: 0.0,
+            duration: 0.0,
+        }
+    }
+
+    /// Creates a transition from any other state to the state `to`, happening immediately.
+    pub fn from_any_state(to: AnimatorStateIndex) -> Self {
+        Self {
+            from: None,
+            ..Self::new(0, to)
+        }
+    }
+
+    /// Returns the transition with `condition` added.
+    pub fn with_condition(mut self, condition: TransitionCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Returns the transition with its `exit_time` set, in seconds.
+    pub fn with_exit_time(mut self, exit_time: f32) -> Self {
+        self.exit_time = exit_time;
+        self
+    }
+
+    /// Returns the transition with the `duration` of its cross-fade set, in seconds.
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    fn leaves(&self, state: AnimatorStateIndex) -> bool {
+        match self.from {
+            Some(from) => from == state,
+            None => self.to != state,
+        }
+    }
+}
+
+/// A condition of an [`AnimatorTransition`], testing a value.
+#[derive(Reflect, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransitionCondition {
+    /// Where the tested value is read from.
+    pub source: ConditionSource,
+    /// The test the value must pass.
+    pub test: ConditionTest,
+}
+
+impl TransitionCondition {
+    /// Creates a condition testing the parameter `name` of the [`Animator`].
+    pub fn parameter(name: impl Into<String>, test: ConditionTest) -> Self {
+        Self {
+            source: ConditionSource::Parameter(name.into()),
+            test,
+        }
+    }
+
+    /// Creates a condition testing the numeric or boolean value at `key` in the `Blackboard` of
+    /// the entity.
+    pub fn blackboard(key: impl Into<String>, test: ConditionTest) -> Self {
+        Self {
+            source: ConditionSource::Blackboard(key.into()),
+            test,
+        }
+    }
+}
+
+/// Where a [`TransitionCondition`] reads its value from.
+#[derive(Reflect, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConditionSource {
+    /// A parameter of the [`Animator`].
+    Parameter(String),
+    /// A numeric or boolean value of the `Blackboard` of the entity.
+    ///
+    /// Blackboards are only read with the `bevy_ai` feature, otherwise the value never exists.
+    Blackboard(String),
+}
+
+/// The test a [`TransitionCondition`] applies to its value.
+///
+/// Booleans and triggers count as `0` and `1`. Tests fail when the value doesn't exist.
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ConditionTest {
+    /// The value isn't zero.
+    IsTrue,
+    /// The value is zero.
+    IsFalse,
+    /// The value is greater than the operand.
+    Greater(f32),
+    /// The value is less than the operand.
+    Less(f32),
+    /// The value is equal to the operand.
+    Equal(f32),
+    /// The value isn't equal to the operand.
+    NotEqual(f32),
+}
+
+impl ConditionTest {
+    fn passes(self, value: f32) -> bool {
+        match self {
+            ConditionTest::IsTrue => value != 0.0,
+            ConditionTest::IsFalse => value == 0.0,
+            ConditionTest::Greater(operand) => value > operand,
+            ConditionTest::Less(operand) => value < operand,
+            ConditionTest::Equal(operand) => value == operand,
+            ConditionTest::NotEqual(operand) => value != operand,
+        }
+    }
+}
+
+/// The value of a parameter of an [`Animator`].
+#[derive(Reflect, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum AnimatorParameter {
+    /// A number.
+    Float(f32),
+    /// An integer.
+    Int(i32),
+    /// A boolean.
+    Bool(bool),
+    /// A boolean reset once a transition testing it happens.
+    Trigger(bool),
+}
+
+impl AnimatorParameter {
+    /// The value of the parameter as a number, booleans and triggers counting as `0` and `1`.
+    pub fn as_f32(self) -> f32 {
+        match self {
+            AnimatorParameter::Float(value) => value,
+            AnimatorParameter::Int(value) => value as f32,
+            AnimatorParameter::Bool(value) | AnimatorParameter::Trigger(value) => {
+                if value {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Plays an [`AnimatorController`] on the [`AnimationPlayer`] of this entity.
+///
+/// The entity also needs the [`Handle<AnimationGraph>`](crate::graph::AnimationGraph) whose nodes
+/// the states play, and [`AnimationTransitions`] to cross-fade between states. Animations should
+/// only be played through the animator, see [`AnimationTransitions`].
+///
+/// An [`AnimatorStateChanged`] event is sent whenever the animator enters a state.
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct Animator {
+    /// The played state machine.
+    pub controller: Handle<AnimatorController>,
+    parameters: HashMap<String, AnimatorParameter>,
+    state: Option<AnimatorStateIndex>,
+    state_time: f32,
+}
+
+/// The `Blackboard` of the entity of an [`Animator`], if any.
+#[cfg(feature = "bevy_ai")]
+type BlackboardRef<'a> = Option<&'a bevy_ai::Blackboard>;
+#[cfg(not(feature = "bevy_ai"))]
+type BlackboardRef<'a> = ();
+
+#[cfg(feature = "bevy_ai")]
+fn blackboard_value(blackboard: BlackboardRef, key: &str) -> Option<f32> {
+    blackboard
+        .and_then(|blackboard| blackboard.get_f64(key))
+        .map(|value| value as f32)
+}
+
+#[cfg(not(feature = "bevy_ai"))]
+fn blackboard_value(_blackboard: BlackboardRef, _key: &str) -> Option<f32> {
+    None
+}
+
+/// A change of state of an [`Animator`], returned by [`Animator::advance`].
+struct StateChange {
+    from: Option<AnimatorStateIndex>,
+    to: AnimatorStateIndex,
+    duration: f32,
+}
+
+impl Animator {
+    /// Creates an animator playing `controller`, starting in its initial state.
+    pub fn new(controller: Handle<AnimatorController>) -> Self {
+        Self {
+            controller,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the number parameter `name`.
+    pub fn set_float(&mut self, name: impl Into<String>, value: f32) -> &mut Self {
+        self.set_parameter(name, AnimatorParameter::Float(value))
+    }
+
+    /// Sets the integer parameter `name`.
+    pub fn set_int(&mut self, name: impl Into<String>, value: i32) -> &mut Self {
+        self.set_parameter(name, AnimatorParameter::Int(value))
+    }
+
+    /// Sets the boolean parameter `name`.
+    pub fn set_bool(&mut self, name: impl Into<String>, value: bool) -> &mut Self {
+        self.set_parameter(name, AnimatorParameter::Bool(value))
+    }
+
+    /// Sets the trigger `name`, which stays set until a transition testing it happens.
+    pub fn set_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.set_parameter(name, AnimatorParameter::Trigger(true))
+    }
+
+    /// Resets the trigger `name`.
+    pub fn reset_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.set_parameter(name, AnimatorParameter::Trigger(false))
+    }
+
+    /// Sets the parameter `name`.
+    pub fn set_parameter(
+        &mut self,
+        name: impl Into<String>,
+        value: AnimatorParameter,
+    ) -> &mut Self {
+        self.parameters.insert(name.into(), value);
+        self
+    }
+
+    /// Returns the parameter `name` set on this animator, not including the defaults of the
+    /// controller.
+    pub fn parameter(&self, name: &str) -> Option<AnimatorParameter> {
+        self.parameters.get(name).copied()
+    }
+
+    /// The current state, or `None` if the animator didn't enter its initial state yet.
+    pub fn state(&self) -> Option<AnimatorStateIndex> {
+        self.state
+    }
+
+    /// The time, in seconds, since the current state was entered.
+    pub fn state_time(&self) -> f32 {
+        self.state_time
+    }
+
+    /// Advances the animator by `delta` seconds, returning the change of state that happens, if
+    /// any.
+    ///
+    /// At most one transition happens per call.
+    fn advance(
+        &mut self,
+        controller: &AnimatorController,
+        delta: f32,
+        blackboard: BlackboardRef,
+    ) -> Option<StateChange> {
+        let Some(state) = self.state.filter(|&state| state < controller.states.len()) else {
+            // Enter the initial state, or start over if the controller lost the current state.
+            if controller.initial_state >= controller.states.len() {
+                return None;
+            }
+            return Some(self.enter(None, controller.initial_state, 0.0));
+        };
+
+        let value = |source: &ConditionSource| match source {
+            ConditionSource::Parameter(name) => self
+                .parameters
+                .get(name)
+                .or_else(|| controller.parameters.get(name))
+                .map(|parameter| parameter.as_f32()),
+            ConditionSource::Blackboard(key) => blackboard_value(blackboard, key),
+        };
+        let Some(transition) = controller.transitions.iter().find(|transition| {
+            transition.leaves(state)
+                && transition.to < controller.states.len()
+                && self.state_time >= transition.exit_time
+                && transition.conditions.iter().all(|condition| {
+                    value(&condition.source).is_some_and(|value| condition.test.passes(value))
+                })
+        }) else {
+            self.state_time += delta;
+            return None;
+        };
+
+        // Consume the triggers the transition tested.
+        for condition in &transition.conditions {
+            let ConditionSource::Parameter(name) = &condition.source else {
+                continue;
+            };
+            if let Some(AnimatorParameter::Trigger(set)) = self.parameters.get_mut(name) {
+                *set = false;
+            }
+        }
+        Some(self.enter(Some(state), transition.to, transition.duration))
+    }
+
+    fn enter(
+        &mut self,
+        from: Option<AnimatorStateIndex>,
+        to: AnimatorStateIndex,
+        duration: f32,
+    ) -> StateChange {
+        self.state = Some(to);
+        self.state_time = 0.0;
+        StateChange { from, to, duration }
+    }
+}
+
+/// Sent when an [`Animator`] enters a state.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnimatorStateChanged {
+    /// The entity of the animator.
+    pub entity: Entity,
+    /// The state left, or `None` when entering the initial state.
+    pub from: Option<AnimatorStateIndex>,
+    /// The state entered.
+    pub to: AnimatorStateIndex,
+}
+
+/// The `Blackboard` queried along with [`Animator`]s.
+#[cfg(feature = "bevy_ai")]
+type BlackboardData = Option<&'static bevy_ai::Blackboard>;
+#[cfg(not(feature = "bevy_ai"))]
+type BlackboardData = ();
+
+/// Advances every [`Animator`], playing the states they enter.
+pub fn update_animators(
+    mut animators: Query<(
+        Entity,
+        &mut Animator,
+        &mut AnimationPlayer,
+        &mut AnimationTransitions,
+        BlackboardData,
+    )>,
+    controllers: Res<Assets<AnimatorController>>,
+    time: Res<Time>,
+    mut state_changed: EventWriter<AnimatorStateChanged>,
+) {
+    for (entity, mut animator, mut player, mut transitions, blackboard) in &mut animators {
+        let Some(controller) = controllers.get(&animator.controller) else {
+            continue;
+        };
+        let Some(change) = animator.advance(controller, time.delta_seconds(), blackboard) else {
+            continue;
+        };
+
+        let state = &controller.states[change.to];
+        let animation = transitions.play(
+            &mut player,
+            state.node,
+            Duration::from_secs_f32(change.duration.max(0.0)),
+        );
+        animation.replay();
+        animation.set_speed(state.speed);
+        if state.repeat {
+            animation.repeat();
+        }
+        state_changed.send(AnimatorStateChanged {
+            entity,
+            from: change.from,
+            to: change.to,
+        });
+    }
+}
+
+/// An [`AssetLoader`] that can load [`AnimatorController`]s as assets.
+///
+/// The canonical extension for [`AnimatorController`]s is `.animctrl.ron`. Plain `.animctrl` is
+/// supported as well.
+#[derive(Default)]
+pub struct AnimatorControllerLoader;
+
+/// Various errors that can occur when loading animator controllers.
+#[derive(Error, Debug)]
+pub enum AnimatorControllerLoadError {
+    /// An I/O error occurred.
+    #[error("I/O")]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization, and the location of the error is supplied.
+    #[error("RON serialization")]
+    SpannedRon(#[from] SpannedError),
+}
+
+impl AssetLoader for AnimatorControllerLoader {
+    type Asset = AnimatorController;
+
+    type Settings = ();
+
+    type Error = AnimatorControllerLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _: &'a Self::Settings,
+        _: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["animctrl", "animctrl.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller() -> AnimatorController {
+        let mut controller = AnimatorController::new();
+        let idle = controller.add_state(AnimatorState::new("idle", AnimationNodeIndex::new(1)));
+        let walk = controller.add_state(AnimatorState::new("walk", AnimationNodeIndex::new(2)));
+        let jump = controller
+            .add_state(AnimatorState::new("jump", AnimationNodeIndex::new(3)).with_repeat(false));
+        controller
+            .add_parameter("speed", AnimatorParameter::Float(0.0))
+            .add_transition(
+                AnimatorTransition::new(idle, walk)
+                    .with_condition(TransitionCondition::parameter(
+                        "speed",
+                        ConditionTest::Greater(0.1),
+                    ))
+                    .with_duration(0.25),
+            )
+            .add_transition(AnimatorTransition::new(walk, idle).with_condition(
+                TransitionCondition::parameter("speed", ConditionTest::Less(0.1)),
+            ))
+            .add_transition(AnimatorTransition::from_any_state(jump).with_condition(
+                TransitionCondition::parameter("jump", ConditionTest::IsTrue),
+            ))
+            .add_transition(AnimatorTransition::new(jump, idle).with_exit_time(0.45));
+        controller
+    }
+
+    fn advance(animator: &mut Animator, controller: &AnimatorController) -> Option<usize> {
+        #[cfg(feature = "bevy_ai")]
+        let change = animator.advance(controller, 0.1, None);
+        #[cfg(not(feature = "bevy_ai"))]
+        let change = animator.advance(controller, 0.1, ());
+        change.map(|change| change.to)
+    }
+
+    #[test]
+    fn parameters_drive_transitions() {
+        let controller = controller();
+        let mut animator = Animator::default();
+
+        assert_eq!(advance(&mut animator, &controller), Some(0));
+        assert_eq!(advance(&mut animator, &controller), None);
+        animator.set_float("speed", 1.0);
+        assert_eq!(advance(&mut animator, &controller), Some(1));
+        assert_eq!(advance(&mut animator, &controller), None);
+        animator.set_float("speed", 0.0);
+        assert_eq!(advance(&mut animator, &controller), Some(0));
+    }
+
+    #[test]
+    fn triggers_are_consumed() {
+        let controller = controller();
+        let mut animator = Animator::default();
+        advance(&mut animator, &controller);
+
+        animator.set_trigger("jump");
+        assert_eq!(advance(&mut animator, &controller), Some(2));
+        assert_eq!(
+            animator.parameter("jump"),
+            Some(AnimatorParameter::Trigger(false))
+        );
+
+        // The jump lasts until its exit time.
+        for _ in 0..5 {
+            assert_eq!(advance(&mut animator, &controller), None);
+        }
+        assert_eq!(advance(&mut animator, &controller), Some(0));
+    }
+
+    #[test]
+    fn deserialize_controller() {
+        let controller: AnimatorController = ron::de::from_str(
+            r#"(
+                states: [
+                    (name: "idle", node: 1),
+                    (name: "walk", node: 2, speed: 1.5),
+                ],
+                transitions: [
+                    (
+                        from: Some(0),
+                        to: 1,
+                        conditions: [(source: Parameter("moving"), test: IsTrue)],
+                        duration: 0.2,
+                    ),
+                ],
+                parameters: {"moving": Bool(false)},
+            )"#,
+        )
+        .unwrap();
+        assert_eq!(controller.state_by_name("walk"), Some(1));
+        assert_eq!(controller.states[1].speed, 1.5);
+        assert!(controller.states[0].repeat);
+
+        let mut animator = Animator::default();
+        assert_eq!(advance(&mut animator, &controller), Some(0));
+        assert_eq!(advance(&mut animator, &controller), None);
+        animator.set_bool("moving", true);
+        assert_eq!(advance(&mut animator, &controller), Some(1));
+    }
+}
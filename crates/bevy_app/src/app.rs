@@ -1,20 +1,21 @@
 use crate::{
-    First, Main, MainSchedulePlugin, PlaceholderPlugin, Plugin, Plugins, PluginsState, SubApp,
-    SubApps,
+    AppRestart, BootProfile, First, Main, MainSchedulePlugin, PlaceholderPlugin, Plugin,
+    PluginTiming, Plugins, PluginsState, SubApp, SubApps,
 };
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
-    event::{event_update_system, ManualEventReader},
+    event::{event_update_system, ManualEventReader, OverflowPolicy},
     intern::Interned,
     prelude::*,
-    schedule::{ScheduleBuildSettings, ScheduleLabel},
+    query::ArchetypeFilter,
+    schedule::{AmbiguitySet, ScheduleBuildSettings, ScheduleLabel},
     system::SystemId,
 };
 #[cfg(feature = "bevy_state")]
 use bevy_state::{prelude::*, state::FreelyMutableState};
 #[cfg(feature = "trace")]
 use bevy_utils::tracing::info_span;
-use bevy_utils::{tracing::debug, HashMap};
+use bevy_utils::{tracing::debug, HashMap, Instant};
 use std::{
     fmt::Debug,
     process::{ExitCode, Termination},
@@ -106,6 +107,7 @@ impl Default for App {
                 .run_if(bevy_ecs::event::event_update_condition),
         );
         app.add_event::<AppExit>();
+        app.add_event::<AppRestart>();
 
         app
     }
@@ -220,6 +222,14 @@ impl App {
                     }
                 }
                 self.main_mut().plugin_registry = plugins;
+                // poll every task, so that their timings are accurate
+                let mut tasks_completed = true;
+                for task in &mut self.main_mut().finish_tasks {
+                    tasks_completed &= task.poll();
+                }
+                if !tasks_completed {
+                    state = PluginsState::Adding;
+                }
                 state
             }
             state => state,
@@ -236,10 +246,17 @@ impl App {
     /// Runs [`Plugin::finish`] for each plugin. This is usually called by the event loop once all
     /// plugins are ready, but can be useful for situations where you want to use [`App::update`].
     pub fn finish(&mut self) {
+        for task in std::mem::take(&mut self.main_mut().finish_tasks) {
+            let timing = task.apply(self);
+            self.main_mut().boot_profile.finish_tasks.push(timing);
+        }
+
         // plugins installed to main should see all sub-apps
         let plugins = std::mem::take(&mut self.main_mut().plugin_registry);
-        for plugin in &plugins {
+        for (index, plugin) in plugins.iter().enumerate() {
+            let start = Instant::now();
             plugin.finish(self);
+            self.main_mut().boot_profile.plugins[index].finish = start.elapsed();
         }
         let main = self.main_mut();
         main.plugin_registry = plugins;
@@ -252,12 +269,19 @@ impl App {
     pub fn cleanup(&mut self) {
         // plugins installed to main should see all sub-apps
         let plugins = std::mem::take(&mut self.main_mut().plugin_registry);
-        for plugin in &plugins {
+        for (index, plugin) in plugins.iter().enumerate() {
+            let start = Instant::now();
             plugin.cleanup(self);
+            self.main_mut().boot_profile.plugins[index].cleanup = start.elapsed();
         }
         let main = self.main_mut();
         main.plugin_registry = plugins;
         main.plugins_state = PluginsState::Cleaned;
+        let timings = std::mem::take(&mut main.boot_profile);
+        if let Some(mut profile) = main.world_mut().get_resource_mut::<BootProfile>() {
+            profile.plugins = timings.plugins;
+            profile.finish_tasks = timings.finish_tasks;
+        }
         self.sub_apps.iter_mut().skip(1).for_each(|s| s.cleanup());
     }
 
@@ -397,6 +421,78 @@ impl App {
         self
     }
 
+    /// Initializes `T` event handling like [`add_event`](Self::add_event), and inserts a
+    /// [`BoundedEvents<T>`] resource holding at most `capacity` pending events, forwarded to
+    /// [`Events<T>`] in [`First`].
+    ///
+    /// Events sent through the [`BoundedEvents<T>`] are subject to its [`OverflowPolicy`], while
+    /// events sent directly to [`Events<T>`] are not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::event::{BoundedEvents, OverflowPolicy};
+    /// #
+    /// # #[derive(Event)]
+    /// # struct NetworkMessage;
+    /// # let mut app = App::new();
+    /// #
+    /// app.add_bounded_event::<NetworkMessage>(256, OverflowPolicy::DropOldest);
+    ///
+    /// let sender = app.world().resource::<BoundedEvents<NetworkMessage>>().sender();
+    /// std::thread::spawn(move || {
+    ///     let _ = sender.send(NetworkMessage);
+    /// });
+    /// ```
+    ///
+    /// [`BoundedEvents<T>`]: bevy_ecs::event::BoundedEvents
+    pub fn add_bounded_event<T>(&mut self, capacity: usize, policy: OverflowPolicy) -> &mut Self
+    where
+        T: Event,
+    {
+        self.main_mut().add_bounded_event::<T>(capacity, policy);
+        self
+    }
+
+    /// Sends [`OnQueryMatch<F>`] and [`OnQueryUnmatch<F>`] events when entities start or stop
+    /// matching the archetypal filter `F`.
+    ///
+    /// Changes are detected by [`track_query_matches`] in [`Last`](crate::Last), so systems
+    /// reading the events react on the following frame.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::query_match::OnQueryMatch;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// # #[derive(Component)]
+    /// # struct Frozen;
+    /// # let mut app = App::new();
+    /// #
+    /// fn shatter(mut frozen: EventReader<OnQueryMatch<(With<Enemy>, With<Frozen>)>>) {
+    ///     for event in frozen.read() {
+    ///         // ...
+    ///     }
+    /// }
+    ///
+    /// app.add_query_match_events::<(With<Enemy>, With<Frozen>)>()
+    ///     .add_systems(Update, shatter);
+    /// ```
+    ///
+    /// [`OnQueryMatch<F>`]: bevy_ecs::query_match::OnQueryMatch
+    /// [`OnQueryUnmatch<F>`]: bevy_ecs::query_match::OnQueryUnmatch
+    /// [`track_query_matches`]: bevy_ecs::query_match::track_query_matches
+    pub fn add_query_match_events<F: ArchetypeFilter + 'static>(&mut self) -> &mut Self {
+        self.main_mut().add_query_match_events::<F>();
+        self
+    }
+
     /// Inserts the [`Resource`] into the app, overwriting any existing resource of the same type.
     ///
     /// There is also an [`init_resource`](Self::init_resource) for resources that have
@@ -508,10 +604,16 @@ impl App {
         self.main_mut()
             .plugin_registry
             .push(Box::new(PlaceholderPlugin));
+        self.main_mut().boot_profile.plugins.push(PluginTiming {
+            name: plugin.name().to_string(),
+            ..Default::default()
+        });
 
+        let start = Instant::now();
         self.main_mut().plugin_build_depth += 1;
         let result = catch_unwind(AssertUnwindSafe(|| plugin.build(self)));
         self.main_mut().plugin_build_depth -= 1;
+        self.main_mut().boot_profile.plugins[index].build = start.elapsed();
 
         if let Err(payload) = result {
             resume_unwind(payload);
@@ -724,6 +826,19 @@ impl App {
         self
     }
 
+    /// Adds the systems and system sets of the `child` schedule to the `parent` schedule, where
+    /// they run in parallel with the other systems instead of from an exclusive system.
+    ///
+    /// The child is ordered and gated as a unit by configuring its
+    /// [`ChildScheduleSet`](bevy_ecs::schedule::ChildScheduleSet) in the parent schedule. See
+    /// [`Schedule::add_child`] for more details.
+    ///
+    /// **Note:** This will create the parent schedule if it does not already exist.
+    pub fn add_child_schedule(&mut self, parent: impl ScheduleLabel, child: Schedule) -> &mut Self {
+        self.main_mut().add_child_schedule(parent, child);
+        self
+    }
+
     /// Initializes an empty `schedule` under the provided `label`, if it does not exist.
     ///
     /// See [`add_schedule`](Self::add_schedule) to insert an existing schedule.
@@ -840,6 +955,34 @@ impl App {
         self
     }
 
+    /// When doing [ambiguity checking](ScheduleBuildSettings) this ignores the pairs of systems in
+    /// `ambiguities`, typically [loaded](AmbiguitySet::load) from a file of reviewed ambiguities.
+    ///
+    /// New ambiguities are still reported, so strict ambiguity checking can be enabled in CI on
+    /// projects that don't resolve all of their ambiguities yet. See [`AmbiguitySet`] for how to
+    /// export the current ambiguities.
+    ///
+    /// This settings only applies to the main world. To apply this to other worlds call the
+    /// [corresponding method](World::suppress_ambiguities) on World
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use bevy_app::prelude::*;
+    /// # use bevy_ecs::schedule::{AmbiguitySet, LogLevel, ScheduleBuildSettings};
+    /// # use bevy_utils::default;
+    /// let mut app = App::new();
+    /// app.configure_schedules(ScheduleBuildSettings {
+    ///     ambiguity_detection: LogLevel::Error,
+    ///     ..default()
+    /// });
+    /// app.suppress_ambiguities(AmbiguitySet::load("ambiguities.txt").unwrap());
+    /// ```
+    pub fn suppress_ambiguities(&mut self, ambiguities: AmbiguitySet) -> &mut Self {
+        self.main_mut().suppress_ambiguities(ambiguities);
+        self
+    }
+
     /// Suppress warnings and errors that would result from systems in these sets having ambiguities
     /// (conflicting access but indeterminate order) with systems in `set`.
     ///
@@ -0,0 +1,312 @@
+use crate::{app::App, plugin::Plugin};
+use bevy_ecs::{
+    prelude::*,
+    schedule::{InternedScheduleLabel, SystemTiming},
+};
+use bevy_tasks::{AsyncComputeTaskPool, TaskPool};
+use bevy_utils::{tracing::info, Duration, Instant};
+use std::{
+    fmt,
+    future::Future,
+    sync::mpsc::{channel, Receiver, TryRecvError},
+};
+
+/// A [`Plugin`] that records how long each phase of the startup of the app takes in the
+/// [`BootProfile`] resource.
+///
+/// The timings of the plugins are always recorded, but the startup systems only run one at a
+/// time on the main thread while this plugin is added, so that their timings don't overlap.
+#[derive(Default)]
+pub struct BootProfilePlugin {
+    /// Whether to log the [`BootProfile`] once the startup schedules have run.
+    pub log: bool,
+}
+
+impl Plugin for BootProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(BootProfile {
+            log: self.log,
+            ..Default::default()
+        });
+    }
+}
+
+/// How long a plugin took to build, finish and clean up, recorded in the [`BootProfile`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginTiming {
+    /// The [name](Plugin::name) of the plugin.
+    pub name: String,
+    /// How long [`Plugin::build`] took, including the plugins it added.
+    pub build: Duration,
+    /// How long [`Plugin::finish`] took.
+    pub finish: Duration,
+    /// How long [`Plugin::cleanup`] took.
+    pub cleanup: Duration,
+}
+
+impl PluginTiming {
+    /// Returns the total time spent in this plugin.
+    pub fn total(&self) -> Duration {
+        self.build + self.finish + self.cleanup
+    }
+}
+
+/// How long a task added with [`App::add_finish_task`] took to complete, recorded in the
+/// [`BootProfile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinishTaskTiming {
+    /// The name of the task.
+    pub name: String,
+    /// The time between the task being added and its completion.
+    pub duration: Duration,
+}
+
+/// How long a startup system took to run, recorded in the [`BootProfile`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StartupSystemTiming {
+    /// The startup schedule of the system.
+    pub schedule: InternedScheduleLabel,
+    /// The name of the system.
+    pub name: String,
+    /// How long the system took to run.
+    pub duration: Duration,
+}
+
+/// The timings of the startup of the app, recorded by the [`BootProfilePlugin`].
+///
+/// The plugin timings are filled in once all plugins are cleaned up, and the startup systems
+/// once the startup schedules ran for the first time.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BootProfile {
+    /// The timings of the plugins of the main app, in the order they were added.
+    pub plugins: Vec<PluginTiming>,
+    /// The timings of the tasks added with [`App::add_finish_task`].
+    pub finish_tasks: Vec<FinishTaskTiming>,
+    /// The timings of the startup systems, in the order they ran.
+    pub startup_systems: Vec<StartupSystemTiming>,
+    log: bool,
+    startup_profiled: bool,
+}
+
+impl BootProfile {
+    /// Returns the total time spent in the plugins.
+    pub fn plugins_total(&self) -> Duration {
+        self.plugins.iter().map(PluginTiming::total).sum()
+    }
+
+    /// Returns the total time spent in the startup systems.
+    pub fn startup_systems_total(&self) -> Duration {
+        self.startup_systems
+            .iter()
+            .map(|timing| timing.duration)
+            .sum()
+    }
+
+    /// Returns `true` if the startup systems should be timed on this run of the startup schedules.
+    pub(crate) fn should_profile_startup(world: &World) -> bool {
+        world
+            .get_resource::<BootProfile>()
+            .is_some_and(|profile| !profile.startup_profiled)
+    }
+
+    /// Records the timings of a run of a startup schedule.
+    pub(crate) fn record_startup_schedule(
+        &mut self,
+        schedule: InternedScheduleLabel,
+        timings: Vec<SystemTiming>,
+    ) {
+        self.startup_systems
+            .extend(timings.into_iter().map(|timing| StartupSystemTiming {
+                schedule,
+                name: timing.name.into_owned(),
+                duration: timing.duration,
+            }));
+    }
+
+    /// Marks the startup as profiled, and logs the profile if requested.
+    pub(crate) fn finish_startup(&mut self) {
+        self.startup_profiled = true;
+        if self.log {
+            info!("{self}");
+        }
+    }
+}
+
+impl fmt::Display for BootProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SLOWEST: usize = 10;
+
+        let mut plugins: Vec<_> = self.plugins.iter().collect();
+        plugins.sort_by_key(|timing| std::cmp::Reverse(timing.total()));
+        writeln!(f, "plugins: {:?}", self.plugins_total())?;
+        for timing in plugins.iter().take(SLOWEST) {
+            writeln!(
+                f,
+                "  {:?} {} (build {:?}, finish {:?}, cleanup {:?})",
+                timing.total(),
+                timing.name,
+                timing.build,
+                timing.finish,
+                timing.cleanup
+            )?;
+        }
+
+        if !self.finish_tasks.is_empty() {
+            writeln!(f, "finish tasks:")?;
+            for timing in &self.finish_tasks {
+                writeln!(f, "  {:?} {}", timing.duration, timing.name)?;
+            }
+        }
+
+        let mut systems: Vec<_> = self.startup_systems.iter().collect();
+        systems.sort_by_key(|timing| std::cmp::Reverse(timing.duration));
+        write!(f, "startup systems: {:?}", self.startup_systems_total())?;
+        for timing in systems.iter().take(SLOWEST) {
+            write!(
+                f,
+                "\n  {:?} {} ({:?})",
+                timing.duration, timing.name, timing.schedule
+            )?;
+        }
+        Ok(())
+    }
+}
+
+type ApplyFinishTask = Box<dyn FnOnce(&mut App) + Send>;
+
+/// A task added with [`App::add_finish_task`].
+pub(crate) struct FinishTask {
+    name: String,
+    receiver: Receiver<(ApplyFinishTask, Duration)>,
+    output: Option<(ApplyFinishTask, Duration)>,
+}
+
+impl FinishTask {
+    /// Returns `true` once the task completed.
+    pub(crate) fn poll(&mut self) -> bool {
+        if self.output.is_none() {
+            match self.receiver.try_recv() {
+                Ok(output) => self.output = Some(output),
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => {
+                    panic!("The finish task `{}` panicked", self.name)
+                }
+            }
+        }
+        true
+    }
+
+    /// Waits for the task to complete, and applies its output to the `app`.
+    pub(crate) fn apply(self, app: &mut App) -> FinishTaskTiming {
+        let name = self.name;
+        let (apply, duration) = self.output.unwrap_or_else(|| {
+            self.receiver
+                .recv()
+                .unwrap_or_else(|_| panic!("The finish task `{name}` panicked"))
+        });
+        apply(app);
+        FinishTaskTiming { name, duration }
+    }
+}
+
+impl App {
+    /// Runs `task` on the [`AsyncComputeTaskPool`] while the app keeps building, and applies the
+    /// function it returns to the app before [`Plugin::finish`] runs.
+    ///
+    /// This lets plugins with slow, asynchronous setup, like the initialization of a renderer,
+    /// start it from [`Plugin::build`] without blocking the other plugins. The plugins aren't
+    /// [ready](crate::PluginsState::Ready) until all tasks complete, and the tasks are applied in
+    /// the order they were added. Calling [`App::finish`] directly blocks until they complete.
+    ///
+    /// ```
+    /// # use bevy_app::App;
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource)]
+    /// struct Device(String);
+    ///
+    /// async fn request_device() -> Device {
+    ///     Device("gpu".into())
+    /// }
+    ///
+    /// App::new().add_finish_task("request device", async {
+    ///     let device = request_device().await;
+    ///     move |app: &mut App| {
+    ///         app.insert_resource(device);
+    ///     }
+    /// });
+    /// ```
+    pub fn add_finish_task<F, Apply>(&mut self, name: impl Into<String>, task: F) -> &mut Self
+    where
+        F: Future<Output = Apply> + Send + 'static,
+        Apply: FnOnce(&mut App) + Send + 'static,
+    {
+        let (sender, receiver) = channel();
+        let start = Instant::now();
+        AsyncComputeTaskPool::get_or_init(TaskPool::default)
+            .spawn(async move {
+                let apply: ApplyFinishTask = Box::new(task.await);
+                // the app was dropped if this fails, so nobody waits for the output
+                let _ = sender.send((apply, start.elapsed()));
+            })
+            .detach();
+        self.main_mut().finish_tasks.push(FinishTask {
+            name: name.into(),
+            receiver,
+            output: None,
+        });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PluginsState, Startup};
+
+    #[derive(Resource)]
+    struct Device;
+
+    #[test]
+    fn boot_profile() {
+        fn slow_startup() {
+            std::thread::sleep(Duration::from_millis(2));
+        }
+
+        let mut app = App::new();
+        app.add_plugins(BootProfilePlugin::default())
+            .add_plugins(|app: &mut App| {
+                app.add_finish_task("device", async {
+                    |app: &mut App| {
+                        app.insert_resource(Device);
+                    }
+                });
+            })
+            .add_systems(Startup, slow_startup);
+
+        while app.plugins_state() == PluginsState::Adding {
+            std::thread::yield_now();
+        }
+        app.finish();
+        assert!(app.world().contains_resource::<Device>());
+        app.cleanup();
+        app.update();
+
+        let profile = app.world().resource::<BootProfile>();
+        assert_eq!(profile.plugins.len(), 3);
+        assert!(profile.plugins[0].name.ends_with("MainSchedulePlugin"));
+        assert!(profile.plugins[1].name.ends_with("BootProfilePlugin"));
+        assert_eq!(profile.finish_tasks.len(), 1);
+        assert_eq!(profile.finish_tasks[0].name, "device");
+        assert_eq!(profile.startup_systems.len(), 1);
+        assert!(profile.startup_systems[0].name.ends_with("slow_startup"));
+        assert!(profile.startup_systems_total() >= Duration::from_millis(2));
+
+        // the startup systems are only profiled once
+        app.world_mut().send_event(crate::AppRestart::default());
+        app.update();
+        assert_eq!(
+            app.world().resource::<BootProfile>().startup_systems.len(),
+            1
+        );
+    }
+}
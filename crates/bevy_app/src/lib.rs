@@ -8,20 +8,26 @@
 //! This crate is about everything concerning the highest-level, application layer of a Bevy app.
 
 mod app;
+mod boot_profile;
 mod main_schedule;
 mod panic_handler;
 mod plugin;
 mod plugin_group;
+mod restart;
 mod schedule_runner;
+mod storage_maintenance;
 mod sub_app;
 
 pub use app::*;
 pub use bevy_derive::DynamicPlugin;
+pub use boot_profile::*;
 pub use main_schedule::*;
 pub use panic_handler::*;
 pub use plugin::*;
 pub use plugin_group::*;
+pub use restart::*;
 pub use schedule_runner::*;
+pub use storage_maintenance::*;
 pub use sub_app::*;
 
 #[allow(missing_docs)]
@@ -33,6 +39,7 @@ pub mod prelude {
             First, FixedFirst, FixedLast, FixedPostUpdate, FixedPreUpdate, FixedUpdate, Last, Main,
             PostStartup, PostUpdate, PreStartup, PreUpdate, SpawnScene, Startup, Update,
         },
+        restart::{AppRestart, KeepOnRestart},
         sub_app::SubApp,
         DynamicPlugin, Plugin, PluginGroup,
     };
@@ -1,11 +1,14 @@
-use crate::{App, Plugin};
+use crate::{App, BootProfile, Plugin, RestartState};
 use bevy_ecs::{
-    schedule::{ExecutorKind, InternedScheduleLabel, Schedule, ScheduleLabel},
-    system::{Local, Resource},
+    schedule::{
+        BoxedCondition, Condition, ExecutorKind, InternedScheduleLabel, Schedule, ScheduleLabel,
+    },
+    system::{IntoSystem, Local, Resource, System},
     world::{Mut, World},
 };
 #[cfg(feature = "bevy_state")]
 use bevy_state::state::StateTransition;
+use bevy_utils::HashMap;
 
 /// The schedule that contains the app logic that is evaluated each tick of [`App::update()`].
 ///
@@ -161,12 +164,48 @@ pub struct Last;
 
 /// Defines the schedules to be run for the [`Main`] schedule, including
 /// their order.
-#[derive(Resource, Debug)]
+///
+/// Plugins can define their own top-level phases by inserting schedule labels relative to the
+/// existing ones, and make them conditional with [`run_if`](Self::run_if).
+///
+/// ```
+/// # use bevy_app::{App, MainScheduleOrder, Update};
+/// # use bevy_ecs::{prelude::*, schedule::ScheduleLabel};
+/// #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Simulation;
+///
+/// #[derive(Resource)]
+/// struct Paused(bool);
+///
+/// let mut app = App::new();
+/// app.init_schedule(Simulation).insert_resource(Paused(false));
+/// let mut order = app.world_mut().resource_mut::<MainScheduleOrder>();
+/// order.insert_before(Update, Simulation);
+/// order.run_if(Simulation, |paused: Res<Paused>| !paused.0);
+/// ```
+#[derive(Resource)]
 pub struct MainScheduleOrder {
     /// The labels to run for the main phase of the [`Main`] schedule (in the order they will be run).
     pub labels: Vec<InternedScheduleLabel>,
     /// The labels to run for the startup phase of the [`Main`] schedule (in the order they will be run).
     pub startup_labels: Vec<InternedScheduleLabel>,
+    conditions: HashMap<InternedScheduleLabel, Vec<PhaseCondition>>,
+}
+
+/// A run condition of a schedule in the [`MainScheduleOrder`], initialized on its first run.
+struct PhaseCondition {
+    condition: BoxedCondition,
+    initialized: bool,
+}
+
+impl std::fmt::Debug for MainScheduleOrder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MainScheduleOrder")
+            .field("labels", &self.labels)
+            .field("startup_labels", &self.startup_labels)
+            .field("conditional_labels", &self.conditions.keys())
+            .finish()
+    }
 }
 
 impl Default for MainScheduleOrder {
@@ -183,6 +222,7 @@ impl Default for MainScheduleOrder {
                 Last.intern(),
             ],
             startup_labels: vec![PreStartup.intern(), Startup.intern(), PostStartup.intern()],
+            conditions: HashMap::default(),
         }
     }
 }
@@ -190,44 +230,184 @@ impl Default for MainScheduleOrder {
 impl MainScheduleOrder {
     /// Adds the given `schedule` after the `after` schedule in the main list of schedules.
     pub fn insert_after(&mut self, after: impl ScheduleLabel, schedule: impl ScheduleLabel) {
-        let index = self
-            .labels
-            .iter()
-            .position(|current| (**current).eq(&after))
-            .unwrap_or_else(|| panic!("Expected {after:?} to exist"));
+        let index = position(&self.labels, &after);
         self.labels.insert(index + 1, schedule.intern());
     }
 
+    /// Adds the given `schedule` before the `before` schedule in the main list of schedules.
+    pub fn insert_before(&mut self, before: impl ScheduleLabel, schedule: impl ScheduleLabel) {
+        let index = position(&self.labels, &before);
+        self.labels.insert(index, schedule.intern());
+    }
+
     /// Adds the given `schedule` after the `after` schedule in the list of startup schedules.
     pub fn insert_startup_after(
         &mut self,
         after: impl ScheduleLabel,
         schedule: impl ScheduleLabel,
     ) {
-        let index = self
-            .startup_labels
-            .iter()
-            .position(|current| (**current).eq(&after))
-            .unwrap_or_else(|| panic!("Expected {after:?} to exist"));
+        let index = position(&self.startup_labels, &after);
         self.startup_labels.insert(index + 1, schedule.intern());
     }
+
+    /// Adds the given `schedule` before the `before` schedule in the list of startup schedules.
+    pub fn insert_startup_before(
+        &mut self,
+        before: impl ScheduleLabel,
+        schedule: impl ScheduleLabel,
+    ) {
+        let index = position(&self.startup_labels, &before);
+        self.startup_labels.insert(index, schedule.intern());
+    }
+
+    /// Removes the given `schedule` from the main and startup lists of schedules, along with its
+    /// run conditions.
+    ///
+    /// Returns `true` if the schedule was in one of the lists.
+    pub fn remove(&mut self, schedule: impl ScheduleLabel) -> bool {
+        let label = schedule.intern();
+        let len = self.labels.len() + self.startup_labels.len();
+        self.labels.retain(|&current| current != label);
+        self.startup_labels.retain(|&current| current != label);
+        self.conditions.remove(&label);
+        len != self.labels.len() + self.startup_labels.len()
+    }
+
+    /// Replaces the `old` schedule with `new` in the main and startup lists of schedules.
+    /// The run conditions of `old` now apply to `new`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `old` is in neither list.
+    pub fn replace(&mut self, old: impl ScheduleLabel, new: impl ScheduleLabel) {
+        let (old, new) = (old.intern(), new.intern());
+        let mut found = false;
+        for current in self.labels.iter_mut().chain(&mut self.startup_labels) {
+            if *current == old {
+                *current = new;
+                found = true;
+            }
+        }
+        assert!(found, "Expected {old:?} to exist");
+        if let Some(conditions) = self.conditions.remove(&old) {
+            self.conditions.entry(new).or_default().extend(conditions);
+        }
+    }
+
+    /// Only runs the given `schedule` when `condition` returns `true`, checked each time the
+    /// [`Main`] schedule reaches it.
+    ///
+    /// Multiple conditions can be added to the same schedule: it only runs if all of them are met.
+    pub fn run_if<M>(&mut self, schedule: impl ScheduleLabel, condition: impl Condition<M>) {
+        let condition = IntoSystem::into_system(condition);
+        assert!(
+            condition.is_send(),
+            "Condition `{}` accesses `NonSend` resources. This is not currently supported.",
+            condition.name()
+        );
+        self.conditions
+            .entry(schedule.intern())
+            .or_default()
+            .push(PhaseCondition {
+                condition: Box::new(condition),
+                initialized: false,
+            });
+    }
+
+    /// Removes the run conditions of the given `schedule`, so that it runs unconditionally.
+    pub fn clear_run_conditions(&mut self, schedule: impl ScheduleLabel) {
+        self.conditions.remove(&schedule.intern());
+    }
+
+    /// Runs the given `schedule` if its run conditions are met.
+    fn run_schedule(&mut self, world: &mut World, label: InternedScheduleLabel) {
+        if self.should_run(world, label) {
+            let _ = world.try_run_schedule(label);
+        }
+    }
+
+    /// Runs the given `schedule` with [`Schedule::run_timed`] if its run conditions are met, and
+    /// records its timings in the [`BootProfile`].
+    fn run_schedule_timed(&mut self, world: &mut World, label: InternedScheduleLabel) {
+        if !self.should_run(world, label) {
+            return;
+        }
+        if let Ok(timings) =
+            world.try_schedule_scope(label, |world, schedule| schedule.run_timed(world))
+        {
+            world
+                .resource_mut::<BootProfile>()
+                .record_startup_schedule(label, timings);
+        }
+    }
+
+    /// Returns `true` if the run conditions of the given `schedule` are met.
+    fn should_run(&mut self, world: &mut World, label: InternedScheduleLabel) -> bool {
+        if let Some(conditions) = self.conditions.get_mut(&label) {
+            // not short-circuiting is intentional, like the conditions of systems
+            #[allow(clippy::unnecessary_fold)]
+            let should_run = conditions
+                .iter_mut()
+                .map(|phase| {
+                    if !phase.initialized {
+                        phase.condition.initialize(world);
+                        phase.initialized = true;
+                    }
+                    phase.condition.run((), world)
+                })
+                .fold(true, |acc, res| acc && res);
+            if !should_run {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+fn position(labels: &[InternedScheduleLabel], label: &impl ScheduleLabel) -> usize {
+    labels
+        .iter()
+        .position(|current| (**current).eq(label))
+        .unwrap_or_else(|| panic!("Expected {label:?} to exist"))
 }
 
 impl Main {
     /// A system that runs the "main schedule"
-    pub fn run_main(world: &mut World, mut run_at_least_once: Local<bool>) {
+    ///
+    /// The startup schedules run again after the app handles an [`AppRestart`](crate::AppRestart).
+    pub fn run_main(
+        world: &mut World,
+        mut run_at_least_once: Local<bool>,
+        mut restart: Local<RestartState>,
+    ) {
+        if !*run_at_least_once {
+            restart.record_built_entities(world);
+        } else if restart.restart_if_requested(world) {
+            *run_at_least_once = false;
+        }
+
         if !*run_at_least_once {
-            world.resource_scope(|world, order: Mut<MainScheduleOrder>| {
-                for &label in &order.startup_labels {
-                    let _ = world.try_run_schedule(label);
+            let profile = BootProfile::should_profile_startup(world);
+            world.resource_scope(|world, mut order: Mut<MainScheduleOrder>| {
+                for index in 0..order.startup_labels.len() {
+                    let label = order.startup_labels[index];
+                    if profile {
+                        order.run_schedule_timed(world, label);
+                    } else {
+                        order.run_schedule(world, label);
+                    }
                 }
             });
+            if profile {
+                world.resource_mut::<BootProfile>().finish_startup();
+            }
             *run_at_least_once = true;
         }
 
-        world.resource_scope(|world, order: Mut<MainScheduleOrder>| {
-            for &label in &order.labels {
-                let _ = world.try_run_schedule(label);
+        world.resource_scope(|world, mut order: Mut<MainScheduleOrder>| {
+            for index in 0..order.labels.len() {
+                let label = order.labels[index];
+                order.run_schedule(world, label);
             }
         });
     }
@@ -256,8 +436,9 @@ impl Plugin for MainSchedulePlugin {
 
         #[cfg(feature = "bevy_debug_stepping")]
         {
-            use bevy_ecs::schedule::{IntoSystemConfigs, Stepping};
-            app.add_systems(Main, Stepping::begin_frame.before(Main::run_main));
+            use bevy_ecs::schedule::{IntoSystemConfigs, Stepping, SteppingDiagnostics};
+            app.init_resource::<SteppingDiagnostics>()
+                .add_systems(Main, Stepping::begin_frame.before(Main::run_main));
         }
     }
 }
@@ -287,13 +468,23 @@ impl Default for FixedMainScheduleOrder {
 impl FixedMainScheduleOrder {
     /// Adds the given `schedule` after the `after` schedule
     pub fn insert_after(&mut self, after: impl ScheduleLabel, schedule: impl ScheduleLabel) {
-        let index = self
-            .labels
-            .iter()
-            .position(|current| (**current).eq(&after))
-            .unwrap_or_else(|| panic!("Expected {after:?} to exist"));
+        let index = position(&self.labels, &after);
         self.labels.insert(index + 1, schedule.intern());
     }
+
+    /// Adds the given `schedule` before the `before` schedule
+    pub fn insert_before(&mut self, before: impl ScheduleLabel, schedule: impl ScheduleLabel) {
+        let index = position(&self.labels, &before);
+        self.labels.insert(index, schedule.intern());
+    }
+
+    /// Removes the given `schedule`, returning `true` if it was in the list.
+    pub fn remove(&mut self, schedule: impl ScheduleLabel) -> bool {
+        let label = schedule.intern();
+        let len = self.labels.len();
+        self.labels.retain(|&current| current != label);
+        len != self.labels.len()
+    }
 }
 
 impl FixedMain {
@@ -306,3 +497,51 @@ impl FixedMain {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::system::{Res, ResMut};
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Simulation;
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct Replay;
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    #[derive(Resource)]
+    struct Enabled(bool);
+
+    #[test]
+    fn custom_phases() {
+        let mut app = App::new();
+        app.init_resource::<Log>()
+            .insert_resource(Enabled(true))
+            .add_systems(Simulation, |mut log: ResMut<Log>| log.0.push("simulation"))
+            .add_systems(Replay, |mut log: ResMut<Log>| log.0.push("replay"))
+            .add_systems(Update, |mut log: ResMut<Log>| log.0.push("update"));
+
+        let mut order = app.world_mut().resource_mut::<MainScheduleOrder>();
+        order.insert_before(Update, Simulation);
+        order.run_if(Simulation, |enabled: Res<Enabled>| enabled.0);
+        app.update();
+        assert_eq!(app.world().resource::<Log>().0, ["simulation", "update"]);
+
+        app.world_mut().resource_mut::<Enabled>().0 = false;
+        app.world_mut().resource_mut::<Log>().0.clear();
+        app.update();
+        assert_eq!(app.world().resource::<Log>().0, ["update"]);
+
+        let mut order = app.world_mut().resource_mut::<MainScheduleOrder>();
+        order.replace(Simulation, Replay);
+        assert!(order.remove(Update));
+        assert!(!order.remove(Update));
+        app.world_mut().resource_mut::<Enabled>().0 = true;
+        app.world_mut().resource_mut::<Log>().0.clear();
+        app.update();
+        assert_eq!(app.world().resource::<Log>().0, ["replay"]);
+    }
+}
@@ -62,6 +62,8 @@ pub trait Plugin: Downcast + Any + Send + Sync {
     /// Has the plugin finished its setup? This can be useful for plugins that need something
     /// asynchronous to happen before they can finish their setup, like the initialization of a renderer.
     /// Once the plugin is ready, [`finish`](Plugin::finish) should be called.
+    ///
+    /// Asynchronous setup can also be run on the task pool with [`App::add_finish_task`].
     fn ready(&self, _app: &App) -> bool {
         true
     }
@@ -0,0 +1,207 @@
+use crate::app::App;
+use bevy_ecs::{
+    entity::EntityHashSet, event::ManualEventReader, prelude::*, schedule::ScheduleLabel,
+};
+use bevy_utils::tracing::info;
+
+/// An [`Event`] that restarts the [`App`] at the start of the next update, without leaving the
+/// runner.
+///
+/// Restarting tears down the world according to the request, runs the [`Restart`] schedule, and
+/// then runs the startup schedules again, as if the app was just built. This is a supported way
+/// to return to the main menu from an unrecoverable state, or to apply settings that require a
+/// restart.
+///
+/// Entities spawned while building the app, like the primary window or the one-shot systems
+/// registered by plugins, and entities with a [`KeepOnRestart`] component are never despawned.
+/// Resources are kept as they are, unless they were registered with
+/// [`App::reset_on_restart`].
+///
+/// ```
+/// # use bevy_app::{App, AppRestart, Startup, Update};
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Resource, Default)]
+/// struct Score(u32);
+///
+/// fn give_up(mut restarts: EventWriter<AppRestart>) {
+///     restarts.send(AppRestart::default());
+/// }
+///
+/// App::new()
+///     .init_resource::<Score>()
+///     .reset_on_restart::<Score>()
+///     .add_systems(Update, give_up);
+/// ```
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AppRestart {
+    /// Whether to despawn the entities spawned since the app was built.
+    pub despawn_entities: bool,
+    /// Whether to close the windows and open them again, for settings that can't be changed on an
+    /// open window.
+    ///
+    /// The windows spawned while building the app are recreated by the window plugin, and the
+    /// others by the startup systems that spawned them.
+    pub recreate_windows: bool,
+}
+
+impl Default for AppRestart {
+    /// Despawns the entities spawned since the app was built, and keeps the windows open.
+    fn default() -> Self {
+        Self {
+            despawn_entities: true,
+            recreate_windows: false,
+        }
+    }
+}
+
+impl AppRestart {
+    /// Only resets the resources registered with [`App::reset_on_restart`] and runs the startup
+    /// schedules again, keeping all entities.
+    pub const SOFT: Self = Self {
+        despawn_entities: false,
+        recreate_windows: false,
+    };
+
+    /// Despawns the entities spawned since the app was built, and recreates the windows.
+    pub const FULL: Self = Self {
+        despawn_entities: true,
+        recreate_windows: true,
+    };
+
+    fn merge(self, other: Self) -> Self {
+        Self {
+            despawn_entities: self.despawn_entities || other.despawn_entities,
+            recreate_windows: self.recreate_windows || other.recreate_windows,
+        }
+    }
+}
+
+/// A marker component for the entities that survive an [`AppRestart`].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct KeepOnRestart;
+
+/// The schedule that runs when the app handles an [`AppRestart`], after the world is torn down
+/// and before the startup schedules run again.
+///
+/// Plugins add their own teardown to this schedule, and can read the [`AppRestart`] events to
+/// know what was requested.
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Restart;
+
+impl App {
+    /// Resets the resource `R` to its [`FromWorld`] value when the app handles an
+    /// [`AppRestart`].
+    pub fn reset_on_restart<R: Resource + FromWorld>(&mut self) -> &mut Self {
+        self.add_systems(Restart, reset_resource::<R>)
+    }
+}
+
+fn reset_resource<R: Resource + FromWorld>(world: &mut World) {
+    let resource = R::from_world(world);
+    world.insert_resource(resource);
+}
+
+/// The state of [`Main::run_main`](crate::Main::run_main) used to handle [`AppRestart`]s.
+#[derive(Default)]
+pub struct RestartState {
+    reader: ManualEventReader<AppRestart>,
+    built_entities: EntityHashSet,
+}
+
+impl RestartState {
+    /// Records the entities spawned while building the app, before the first startup.
+    pub(crate) fn record_built_entities(&mut self, world: &World) {
+        self.built_entities = world.iter_entities().map(|entity| entity.id()).collect();
+    }
+
+    /// Tears down the world if an [`AppRestart`] was sent since the last update, and returns
+    /// whether the startup schedules should run again.
+    pub(crate) fn restart_if_requested(&mut self, world: &mut World) -> bool {
+        let Some(events) = world.get_resource::<Events<AppRestart>>() else {
+            return false;
+        };
+        let Some(restart) = self.reader.read(events).copied().reduce(AppRestart::merge) else {
+            return false;
+        };
+        info!("restarting the app: {restart:?}");
+
+        if restart.despawn_entities {
+            let keep = world.init_component::<KeepOnRestart>();
+            let entities: Vec<Entity> = world
+                .iter_entities()
+                .filter(|entity| {
+                    !self.built_entities.contains(&entity.id()) && !entity.contains_id(keep)
+                })
+                .map(|entity| entity.id())
+                .collect();
+            for entity in entities {
+                world.despawn(entity);
+            }
+        }
+        self.built_entities
+            .retain(|&entity| world.get_entity(entity).is_some());
+
+        let _ = world.try_run_schedule(Restart);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Startup, Update};
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    #[derive(Resource, Default)]
+    struct StartupRuns(u32);
+
+    #[derive(Component)]
+    struct Level;
+
+    #[test]
+    fn restart_reruns_startup() {
+        let mut app = App::new();
+        let built = app.world_mut().spawn_empty().id();
+        app.init_resource::<Counter>()
+            .init_resource::<StartupRuns>()
+            .reset_on_restart::<Counter>()
+            .add_systems(
+                Startup,
+                |mut commands: Commands, mut runs: ResMut<StartupRuns>| {
+                    commands.spawn(Level);
+                    commands.spawn((Level, KeepOnRestart));
+                    runs.0 += 1;
+                },
+            )
+            .add_systems(Update, |mut counter: ResMut<Counter>| counter.0 += 1);
+
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Counter>().0, 2);
+
+        app.world_mut().send_event(AppRestart::default());
+        app.update();
+        assert_eq!(app.world().resource::<StartupRuns>().0, 2);
+        assert_eq!(app.world().resource::<Counter>().0, 1);
+        assert!(app.world().get_entity(built).is_some());
+        let levels = app
+            .world_mut()
+            .query_filtered::<(), With<Level>>()
+            .iter(app.world())
+            .count();
+        // one kept from the first startup, two spawned again
+        assert_eq!(levels, 3);
+
+        app.world_mut().send_event(AppRestart::SOFT);
+        app.update();
+        assert_eq!(app.world().resource::<StartupRuns>().0, 3);
+        let levels = app
+            .world_mut()
+            .query_filtered::<(), With<Level>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(levels, 5);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{app::App, main_schedule::Last, plugin::Plugin};
+use bevy_ecs::{prelude::*, world::CompactionReport};
+use bevy_utils::tracing::debug;
+
+/// Periodically compacts the storage of the main [`World`] with [`World::compact`], to return the
+/// memory left behind by despawned entities and to keep table rows in entity order.
+///
+/// Compaction runs in the [`Last`] schedule, every [`StorageMaintenance::interval`] frames or on
+/// demand with [`StorageMaintenance::request_compaction`].
+pub struct StorageMaintenancePlugin {
+    /// The number of frames between two compactions, or `0` to only compact on demand.
+    pub interval: u32,
+}
+
+impl Default for StorageMaintenancePlugin {
+    fn default() -> Self {
+        Self { interval: 600 }
+    }
+}
+
+impl Plugin for StorageMaintenancePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(StorageMaintenance {
+            interval: self.interval,
+            ..Default::default()
+        })
+        .add_systems(Last, compact_storage);
+    }
+}
+
+/// Controls and reports the compactions of the [`StorageMaintenancePlugin`].
+#[derive(Resource, Debug, Default)]
+pub struct StorageMaintenance {
+    /// The number of frames between two compactions, or `0` to only compact on demand.
+    pub interval: u32,
+    frames: u32,
+    requested: bool,
+    last_report: Option<CompactionReport>,
+    total_bytes_reclaimed: usize,
+}
+
+impl StorageMaintenance {
+    /// Compacts the world at the end of the current frame, regardless of the interval.
+    pub fn request_compaction(&mut self) {
+        self.requested = true;
+    }
+
+    /// Returns the report of the last compaction, if any.
+    pub fn last_report(&self) -> Option<&CompactionReport> {
+        self.last_report.as_ref()
+    }
+
+    /// Returns the number of bytes reclaimed by all compactions so far.
+    pub fn total_bytes_reclaimed(&self) -> usize {
+        self.total_bytes_reclaimed
+    }
+}
+
+fn compact_storage(world: &mut World) {
+    let mut maintenance = world.resource_mut::<StorageMaintenance>();
+    let maintenance = maintenance.bypass_change_detection();
+    maintenance.frames += 1;
+    let due = maintenance.interval > 0 && maintenance.frames >= maintenance.interval;
+    if !due && !maintenance.requested {
+        return;
+    }
+    maintenance.frames = 0;
+    maintenance.requested = false;
+
+    let report = world.compact();
+    debug!(
+        "compacted world storage: {} bytes reclaimed, {} rows moved",
+        report.bytes_reclaimed, report.rows_moved
+    );
+    let mut maintenance = world.resource_mut::<StorageMaintenance>();
+    maintenance.total_bytes_reclaimed += report.bytes_reclaimed;
+    maintenance.last_report = Some(report);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{StorageMaintenance, StorageMaintenancePlugin};
+    use crate::App;
+    use bevy_ecs::prelude::*;
+
+    #[derive(Component)]
+    struct A(#[allow(dead_code)] [u8; 16]);
+
+    #[test]
+    fn compacts_on_interval_and_request() {
+        let mut app = App::new();
+        app.add_plugins(StorageMaintenancePlugin { interval: 2 });
+        let entities: Vec<Entity> = (0..32)
+            .map(|_| app.world_mut().spawn(A([0; 16])).id())
+            .collect();
+        for entity in entities {
+            app.world_mut().despawn(entity);
+        }
+
+        app.update();
+        assert!(app
+            .world()
+            .resource::<StorageMaintenance>()
+            .last_report()
+            .is_none());
+        app.update();
+        let maintenance = app.world().resource::<StorageMaintenance>();
+        assert!(maintenance.last_report().unwrap().bytes_reclaimed > 0);
+        let reclaimed = maintenance.total_bytes_reclaimed();
+
+        app.world_mut()
+            .resource_mut::<StorageMaintenance>()
+            .request_compaction();
+        app.update();
+        let maintenance = app.world().resource::<StorageMaintenance>();
+        assert_eq!(maintenance.last_report().unwrap().bytes_reclaimed, 0);
+        assert_eq!(maintenance.total_bytes_reclaimed(), reclaimed);
+    }
+}
@@ -1,8 +1,13 @@
-use crate::{App, InternedAppLabel, Plugin, Plugins, PluginsState, Startup};
+use crate::{
+    boot_profile::FinishTask, App, BootProfile, First, InternedAppLabel, Last, Plugin, Plugins,
+    PluginsState, Startup,
+};
 use bevy_ecs::{
-    event::EventRegistry,
+    event::{forward_bounded_events, BoundedEvents, EventRegistry, EventUpdates, OverflowPolicy},
     prelude::*,
-    schedule::{InternedScheduleLabel, ScheduleBuildSettings, ScheduleLabel},
+    query::ArchetypeFilter,
+    query_match::{track_query_matches, OnQueryMatch, OnQueryUnmatch},
+    schedule::{AmbiguitySet, InternedScheduleLabel, ScheduleBuildSettings, ScheduleLabel},
     system::SystemId,
 };
 #[cfg(feature = "bevy_state")]
@@ -72,6 +77,10 @@ pub struct SubApp {
     /// Panics if an update is attempted while plugins are building.
     pub(crate) plugin_build_depth: usize,
     pub(crate) plugins_state: PluginsState,
+    /// The timings of the plugins, moved to the [`BootProfile`] resource if there is one.
+    pub(crate) boot_profile: BootProfile,
+    /// The tasks added with [`App::add_finish_task`].
+    pub(crate) finish_tasks: Vec<FinishTask>,
     /// The schedule that will be run by [`update`](Self::update).
     pub update_schedule: Option<InternedScheduleLabel>,
     /// A function that gives mutable access to two app worlds. This is primarily
@@ -95,6 +104,8 @@ impl Default for SubApp {
             plugin_names: HashSet::default(),
             plugin_build_depth: 0,
             plugins_state: PluginsState::Adding,
+            boot_profile: BootProfile::default(),
+            finish_tasks: Vec::new(),
             update_schedule: None,
             extract: None,
         }
@@ -213,6 +224,14 @@ impl SubApp {
         self
     }
 
+    /// See [`App::add_child_schedule`].
+    pub fn add_child_schedule(&mut self, parent: impl ScheduleLabel, child: Schedule) -> &mut Self {
+        let mut child = Some(child);
+        self.edit_schedule(parent, |schedule| {
+            schedule.add_child(child.take().unwrap());
+        })
+    }
+
     /// See [`App::init_schedule`].
     pub fn init_schedule(&mut self, label: impl ScheduleLabel) -> &mut Self {
         let label = label.intern();
@@ -278,6 +297,12 @@ impl SubApp {
         self
     }
 
+    /// See [`App::suppress_ambiguities`].
+    pub fn suppress_ambiguities(&mut self, ambiguities: AmbiguitySet) -> &mut Self {
+        self.world_mut().suppress_ambiguities(ambiguities);
+        self
+    }
+
     /// See [`App::ignore_ambiguity`].
     #[track_caller]
     pub fn ignore_ambiguity<M1, M2, S1, S2>(
@@ -374,6 +399,31 @@ impl SubApp {
         self
     }
 
+    /// See [`App::add_bounded_event`].
+    pub fn add_bounded_event<T>(&mut self, capacity: usize, policy: OverflowPolicy) -> &mut Self
+    where
+        T: Event,
+    {
+        if !self.world.contains_resource::<BoundedEvents<T>>() {
+            self.add_event::<T>()
+                .insert_resource(BoundedEvents::<T>::new(capacity, policy))
+                .add_systems(First, forward_bounded_events::<T>.after(EventUpdates));
+        }
+
+        self
+    }
+
+    /// See [`App::add_query_match_events`].
+    pub fn add_query_match_events<F: ArchetypeFilter + 'static>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<Events<OnQueryMatch<F>>>() {
+            self.add_event::<OnQueryMatch<F>>()
+                .add_event::<OnQueryUnmatch<F>>()
+                .add_systems(Last, track_query_matches::<F>);
+        }
+
+        self
+    }
+
     /// See [`App::add_plugins`].
     pub fn add_plugins<M>(&mut self, plugins: impl Plugins<M>) -> &mut Self {
         self.run_as_app(|app| plugins.add_to_app(app));
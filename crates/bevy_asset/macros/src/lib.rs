@@ -123,3 +123,91 @@ fn derive_dependency_visitor_internal(
         }
     })
 }
+
+const ASSET_ATTRIBUTE: &str = "asset";
+
+#[proc_macro_derive(AssetCollection, attributes(asset))]
+pub fn derive_asset_collection(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let bevy_asset_path: Path = bevy_asset_path();
+    match derive_asset_collection_internal(&ast, &bevy_asset_path) {
+        Ok(asset_collection) => TokenStream::from(asset_collection),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn derive_asset_collection_internal(
+    ast: &DeriveInput,
+    bevy_asset_path: &Path,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    let Data::Struct(data_struct) = &ast.data else {
+        return Err(syn::Error::new(
+            Span::call_site().into(),
+            "AssetCollection derive only works on structs",
+        ));
+    };
+
+    let mut field_loaders = Vec::new();
+    let mut field_visitors = Vec::new();
+    for (i, field) in data_struct.fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(i)),
+        };
+        let mut loader = None;
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident(ASSET_ATTRIBUTE))
+        {
+            attr.parse_nested_meta(|meta| {
+                let method = if meta.path.is_ident("path") {
+                    quote!(load)
+                } else if meta.path.is_ident("folder") {
+                    quote!(load_folder)
+                } else {
+                    return Err(meta.error("expected `path` or `folder`"));
+                };
+                if loader.is_some() {
+                    return Err(meta.error("an asset field can only be loaded from one path"));
+                }
+                let path: syn::LitStr = meta.value()?.parse()?;
+                loader = Some(quote!(asset_server.#method(#path)));
+                Ok(())
+            })?;
+        }
+        match loader {
+            Some(loader) => {
+                field_loaders.push(quote!(#member: #loader));
+                field_visitors.push(quote!(
+                    #bevy_asset_path::VisitAssetDependencies::visit_dependencies(&self.#member, visit);
+                ));
+            }
+            None => field_loaders.push(quote!(#member: ::core::default::Default::default())),
+        }
+    }
+
+    // prevent unused variable warnings in case there are no assets
+    let (asset_server, visit) = if field_visitors.is_empty() {
+        (quote!(_asset_server), quote!(_visit))
+    } else {
+        (quote!(asset_server), quote!(visit))
+    };
+
+    Ok(quote! {
+        impl #impl_generics #bevy_asset_path::AssetCollection for #struct_name #type_generics #where_clause {
+            fn load(#asset_server: &#bevy_asset_path::AssetServer) -> Self {
+                Self { #(#field_loaders,)* }
+            }
+        }
+
+        impl #impl_generics #bevy_asset_path::VisitAssetDependencies for #struct_name #type_generics #where_clause {
+            fn visit_dependencies(&self, #visit: &mut impl FnMut(#bevy_asset_path::UntypedAssetId)) {
+                #(#field_visitors)*
+            }
+        }
+    })
+}
@@ -1,13 +1,14 @@
 use crate::{self as bevy_asset};
 use crate::{
-    Asset, AssetEvent, AssetHandleProvider, AssetId, AssetServer, Handle, LoadState, UntypedHandle,
+    retention::RetainedAsset, Asset, AssetEvent, AssetHandleProvider, AssetId, AssetRetention,
+    AssetServer, AssetUnloading, Handle, LoadState, UntypedHandle,
 };
 use bevy_ecs::{
     prelude::EventWriter,
     system::{Res, ResMut, Resource},
 };
 use bevy_reflect::{Reflect, TypePath};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, Instant};
 use crossbeam_channel::{Receiver, Sender};
 use serde::{Deserialize, Serialize};
 use std::{
@@ -297,6 +298,15 @@ pub struct Assets<A: Asset> {
     /// Assets managed by the `Assets` struct with live strong `Handle`s
     /// originating from `get_strong_handle`.
     duplicate_handles: HashMap<AssetId<A>, u16>,
+    retention: AssetRetention,
+    /// Unused assets kept alive by the `retention` policy.
+    retained: HashMap<AssetId<A>, RetainedAsset<A>>,
+    queued_unloading_events: Vec<AssetUnloading<A>>,
+    /// The number of times `track_assets` ran, used to delay requested unloads.
+    track_passes: u32,
+    /// Assets loaded with the same content as another asset, which share its value.
+    /// See [`AssetApp::deduplicate_assets`](crate::AssetApp::deduplicate_assets).
+    aliases: HashMap<AssetId<A>, Handle<A>>,
 }
 
 impl<A: Asset> Default for Assets<A> {
@@ -310,6 +320,11 @@ impl<A: Asset> Default for Assets<A> {
             hash_map: Default::default(),
             queued_events: Default::default(),
             duplicate_handles: Default::default(),
+            retention: Default::default(),
+            retained: Default::default(),
+            queued_unloading_events: Default::default(),
+            track_passes: 0,
+            aliases: Default::default(),
         }
     }
 }
@@ -328,7 +343,9 @@ impl<A: Asset> Assets<A> {
 
     /// Inserts the given `asset`, identified by the given `id`. If an asset already exists for `id`, it will be replaced.
     pub fn insert(&mut self, id: impl Into<AssetId<A>>, asset: A) {
-        match id.into() {
+        let id: AssetId<A> = id.into();
+        self.aliases.remove(&id);
+        match id {
             AssetId::Index { index, .. } => {
                 self.insert_with_index(index, asset).unwrap();
             }
@@ -352,9 +369,33 @@ impl<A: Asset> Assets<A> {
         self.get_mut(id).unwrap()
     }
 
+    /// Makes `id` share the value of the asset `original`, which was loaded with the same content.
+    pub(crate) fn insert_alias(&mut self, id: AssetId<A>, original: Handle<A>) {
+        let replaced = match id {
+            AssetId::Index { index, .. } => self.dense_storage.remove_still_alive(index),
+            AssetId::Uuid { uuid } => self.hash_map.remove(&uuid),
+        };
+        let realiased = self.aliases.insert(id, original).is_some();
+        if replaced.is_some() || realiased {
+            self.queued_events.push(AssetEvent::Modified { id });
+        } else {
+            self.queued_events.push(AssetEvent::Added { id });
+        }
+    }
+
+    /// Returns the id of the asset whose value is stored for `id`, which differs from `id` if it
+    /// was deduplicated.
+    #[inline]
+    fn resolve(&self, id: AssetId<A>) -> AssetId<A> {
+        if self.aliases.is_empty() {
+            return id;
+        }
+        self.aliases.get(&id).map_or(id, Handle::id)
+    }
+
     /// Returns `true` if the `id` exists in this collection. Otherwise it returns `false`.
     pub fn contains(&self, id: impl Into<AssetId<A>>) -> bool {
-        match id.into() {
+        match self.resolve(id.into()) {
             AssetId::Index { index, .. } => self.dense_storage.get(index).is_some(),
             AssetId::Uuid { uuid } => self.hash_map.contains_key(&uuid),
         }
@@ -421,7 +462,7 @@ impl<A: Asset> Assets<A> {
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
     #[inline]
     pub fn get(&self, id: impl Into<AssetId<A>>) -> Option<&A> {
-        match id.into() {
+        match self.resolve(id.into()) {
             AssetId::Index { index, .. } => self.dense_storage.get(index),
             AssetId::Uuid { uuid } => self.hash_map.get(&uuid),
         }
@@ -430,8 +471,11 @@ impl<A: Asset> Assets<A> {
     /// Retrieves a mutable reference to the [`Asset`] with the given `id`, if it exists.
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
     #[inline]
+    ///
+    /// If the asset was deduplicated, this returns the value it shares with the original asset, and
+    /// the [`AssetEvent::Modified`] event is sent for the original asset.
     pub fn get_mut(&mut self, id: impl Into<AssetId<A>>) -> Option<&mut A> {
-        let id: AssetId<A> = id.into();
+        let id = self.resolve(id.into());
         let result = match id {
             AssetId::Index { index, .. } => self.dense_storage.get_mut(index),
             AssetId::Uuid { uuid } => self.hash_map.get_mut(&uuid),
@@ -457,7 +501,9 @@ impl<A: Asset> Assets<A> {
     /// Note that this supports anything that implements `Into<AssetId<A>>`, which includes [`Handle`] and [`AssetId`].
     pub fn remove_untracked(&mut self, id: impl Into<AssetId<A>>) -> Option<A> {
         let id: AssetId<A> = id.into();
+        self.retained.remove(&id);
         self.duplicate_handles.remove(&id);
+        self.aliases.remove(&id);
         match id {
             AssetId::Index { index, .. } => self.dense_storage.remove_still_alive(index),
             AssetId::Uuid { uuid } => self.hash_map.remove(&uuid),
@@ -473,11 +519,12 @@ impl<A: Asset> Assets<A> {
                 return;
             }
         }
+        let aliased = self.aliases.remove(&id).is_some();
         let existed = match id {
             AssetId::Index { index, .. } => self.dense_storage.remove_dropped(index).is_some(),
             AssetId::Uuid { uuid } => self.hash_map.remove(&uuid).is_some(),
         };
-        if existed {
+        if existed || aliased {
             self.queued_events.push(AssetEvent::Removed { id });
         }
     }
@@ -536,6 +583,117 @@ impl<A: Asset> Assets<A> {
         }
     }
 
+    /// Returns the [`AssetRetention`] policy of this collection.
+    pub fn retention(&self) -> AssetRetention {
+        self.retention
+    }
+
+    /// Sets when the assets of this collection are unloaded once they are no longer used.
+    ///
+    /// Unused assets already kept alive by the previous policy follow the new one from now on.
+    pub fn set_retention(&mut self, retention: AssetRetention) {
+        self.retention = retention;
+    }
+
+    /// Returns `true` if the asset is unused, and only kept alive by the [`AssetRetention`]
+    /// policy.
+    pub fn is_retained(&self, id: impl Into<AssetId<A>>) -> bool {
+        let id = id.into();
+        self.retained
+            .get(&id)
+            .is_some_and(|retained| !retained.is_shared() && !self.has_duplicate_handles(id))
+    }
+
+    /// Requests to unload an asset kept alive by the [`AssetRetention`] policy, sending an
+    /// [`AssetUnloading`] event. The asset is unloaded two updates later, unless the unload is
+    /// cancelled with [`Assets::cancel_unload`] or the asset is used again in the meantime.
+    ///
+    /// Returns `false` if the asset isn't kept alive by the policy.
+    pub fn unload(&mut self, id: impl Into<AssetId<A>>) -> bool {
+        let id = id.into();
+        let Some(retained) = self.retained.get_mut(&id) else {
+            return false;
+        };
+        if retained.unloading_at.is_none() {
+            retained.unloading_at = Some(self.track_passes);
+            self.queued_unloading_events.push(AssetUnloading { id });
+        }
+        true
+    }
+
+    /// Cancels the unload of an asset announced by an [`AssetUnloading`] event.
+    ///
+    /// With [`AssetRetention::Timeout`], the asset has to be unused for the whole timeout again
+    /// before the next unload. Returns `false` if no unload was requested for the asset.
+    pub fn cancel_unload(&mut self, id: impl Into<AssetId<A>>) -> bool {
+        match self.retained.get_mut(&id.into()) {
+            Some(retained) if retained.unloading_at.is_some() => {
+                retained.mark_used();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn has_duplicate_handles(&self, id: AssetId<A>) -> bool {
+        self.duplicate_handles
+            .get(&id)
+            .is_some_and(|count| *count > 0)
+    }
+
+    /// Returns `true` if the asset should be kept alive by the retention policy now that its last
+    /// strong handle was dropped.
+    fn should_retain(&self, id: AssetId<A>) -> bool {
+        self.retention != AssetRetention::Immediate
+            && !self.retained.contains_key(&id)
+            && self.contains(id)
+    }
+
+    fn retain(&mut self, id: AssetId<A>, handle: Handle<A>) {
+        self.retained.insert(id, RetainedAsset::new(handle));
+    }
+
+    /// Applies the retention policy to the retained assets, dropping the handles of the assets
+    /// that should be unloaded and returning their ids.
+    fn update_retained(&mut self) -> Vec<AssetId<A>> {
+        self.track_passes = self.track_passes.wrapping_add(1);
+        let mut released = Vec::new();
+        if self.retained.is_empty() {
+            return released;
+        }
+        let now = Instant::now();
+        for (id, retained) in &mut self.retained {
+            let duplicated = self
+                .duplicate_handles
+                .get(id)
+                .is_some_and(|count| *count > 0);
+            if retained.is_shared() || duplicated {
+                retained.mark_used();
+                continue;
+            }
+            match retained.unloading_at {
+                // leave a whole update to the systems reading the unloading event to cancel it
+                Some(pass) if self.track_passes.wrapping_sub(pass) >= 2 => released.push(*id),
+                Some(_) => {}
+                None => match self.retention {
+                    AssetRetention::Immediate => released.push(*id),
+                    AssetRetention::Timeout(timeout) if retained.unused_for(now) >= timeout => {
+                        retained.unloading_at = Some(self.track_passes);
+                        self.queued_unloading_events
+                            .push(AssetUnloading { id: *id });
+                    }
+                    AssetRetention::Timeout(_)
+                    | AssetRetention::Manual
+                    | AssetRetention::Forever => {}
+                },
+            }
+        }
+        for id in &released {
+            self.retained.remove(id);
+        }
+        released
+    }
+
     /// A system that synchronizes the state of assets in this collection with the [`AssetServer`]. This manages
     /// [`Handle`] drop events.
     pub fn track_assets(mut assets: ResMut<Self>, asset_server: Res<AssetServer>) {
@@ -545,6 +703,8 @@ impl<A: Asset> Assets<A> {
         // re-loads are kicked off appropriately. This function must be "transactional" relative
         // to other asset info operations
         let mut infos = asset_server.data.infos.write();
+        // the drops of the handles of released assets are processed below
+        let released = assets.update_retained();
         let mut not_ready = Vec::new();
         while let Ok(drop_event) = assets.handle_provider.drop_receiver.try_recv() {
             let id = drop_event.id.typed();
@@ -558,11 +718,27 @@ impl<A: Asset> Assets<A> {
                     }
                 }
 
+                if !released.contains(&id) && assets.should_retain(id) {
+                    if let Some(handle) = infos.revive_handle(untyped_id) {
+                        assets.retain(id, handle.typed_debug_checked());
+                        continue;
+                    }
+                }
+
                 // the process_handle_drop call checks whether new handles have been created since the drop event was fired, before removing the asset
                 if !infos.process_handle_drop(untyped_id) {
                     // a new handle has been created, or the asset doesn't exist
                     continue;
                 }
+            } else if !released.contains(&id)
+                && assets.should_retain(id)
+                && !assets.has_duplicate_handles(id)
+            {
+                let handle = assets
+                    .handle_provider
+                    .get_handle(drop_event.id, false, None, None);
+                assets.retain(id, Handle::Strong(handle));
+                continue;
             }
 
             assets.queued_events.push(AssetEvent::Unused { id });
@@ -579,8 +755,13 @@ impl<A: Asset> Assets<A> {
     /// A system that applies accumulated asset change events to the [`Events`] resource.
     ///
     /// [`Events`]: bevy_ecs::event::Events
-    pub fn asset_events(mut assets: ResMut<Self>, mut events: EventWriter<AssetEvent<A>>) {
+    pub fn asset_events(
+        mut assets: ResMut<Self>,
+        mut events: EventWriter<AssetEvent<A>>,
+        mut unloading_events: EventWriter<AssetUnloading<A>>,
+    ) {
         events.send_batch(assets.queued_events.drain(..));
+        unloading_events.send_batch(assets.queued_unloading_events.drain(..));
     }
 
     /// A run condition for [`asset_events`]. The system will not run if there are no events to
@@ -588,7 +769,7 @@ impl<A: Asset> Assets<A> {
     ///
     /// [`asset_events`]: Self::asset_events
     pub(crate) fn asset_events_condition(assets: Res<Self>) -> bool {
-        !assets.queued_events.is_empty()
+        !assets.queued_events.is_empty() || !assets.queued_unloading_events.is_empty()
     }
 }
 
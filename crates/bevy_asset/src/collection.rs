@@ -0,0 +1,114 @@
+use crate::{
+    AssetServer, LoadState, RecursiveDependencyLoadState, UntypedAssetId, VisitAssetDependencies,
+};
+use bevy_ecs::prelude::*;
+
+/// A [`Resource`] holding the handles of a group of assets that are loaded together, like the
+/// assets of a level or of a menu.
+///
+/// Deriving this trait loads the fields annotated with `#[asset(path = "...")]` with
+/// [`AssetServer::load`], and the ones annotated with `#[asset(folder = "...")]` with
+/// [`AssetServer::load_folder`]. Fields without an `#[asset]` attribute are initialized with
+/// [`Default`].
+///
+/// The collection is inserted as soon as its assets start loading, so systems can clone the
+/// handles right away. Use the [`asset_collection_loaded`] run condition to wait until all of
+/// them and their dependencies are loaded, for example to leave a loading state.
+///
+/// ```
+/// # use bevy_app::{App, Update};
+/// # use bevy_asset::{prelude::*, AssetCollection, LoadedFolder, asset_collection_loaded};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::TypePath;
+/// # #[derive(Asset, TypePath)]
+/// # struct Image;
+/// #[derive(Resource, AssetCollection)]
+/// struct LevelAssets {
+///     #[asset(path = "levels/forest.png")]
+///     background: Handle<Image>,
+///     #[asset(folder = "levels/forest")]
+///     props: Handle<LoadedFolder>,
+/// }
+///
+/// fn start_level(assets: Res<LevelAssets>) {
+///     // all assets of the collection are loaded
+/// }
+///
+/// fn setup(app: &mut App) {
+///     app.init_asset_collection::<LevelAssets>().add_systems(
+///         Update,
+///         start_level.run_if(asset_collection_loaded::<LevelAssets>),
+///     );
+/// }
+/// ```
+pub trait AssetCollection: Resource + VisitAssetDependencies {
+    /// Starts loading the assets of the collection, and returns their handles.
+    fn load(asset_server: &AssetServer) -> Self;
+
+    /// Returns how many assets of the collection are loaded with their dependencies.
+    fn progress(&self, asset_server: &AssetServer) -> AssetCollectionProgress {
+        let mut progress = AssetCollectionProgress::default();
+        self.visit_dependencies(&mut |id| progress.add(asset_server, id));
+        progress
+    }
+}
+
+/// The loading progress of an [`AssetCollection`], returned by [`AssetCollection::progress`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AssetCollectionProgress {
+    /// The number of assets that are loaded with their dependencies.
+    pub loaded: usize,
+    /// The number of assets that failed to load, or that have a dependency that failed to load.
+    pub failed: usize,
+    /// The number of assets in the collection.
+    pub total: usize,
+}
+
+impl AssetCollectionProgress {
+    fn add(&mut self, asset_server: &AssetServer, id: UntypedAssetId) {
+        self.total += 1;
+        match asset_server.get_load_states(id) {
+            Some((LoadState::Loaded, _, RecursiveDependencyLoadState::Loaded)) => self.loaded += 1,
+            Some((LoadState::Failed(_), _, _) | (_, _, RecursiveDependencyLoadState::Failed)) => {
+                self.failed += 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns `true` if all assets of the collection are loaded with their dependencies.
+    pub fn is_loaded(&self) -> bool {
+        self.loaded == self.total
+    }
+
+    /// Returns `true` if any asset of the collection failed to load.
+    pub fn is_failed(&self) -> bool {
+        self.failed > 0
+    }
+}
+
+/// A run condition that returns `true` once the [`AssetCollection`] `C` is inserted and all of its
+/// assets are loaded with their dependencies.
+pub fn asset_collection_loaded<C: AssetCollection>(
+    collection: Option<Res<C>>,
+    asset_server: Res<AssetServer>,
+) -> bool {
+    collection.is_some_and(|collection| collection.progress(&asset_server).is_loaded())
+}
+
+/// A run condition that returns `true` if any asset of the [`AssetCollection`] `C` failed to load.
+pub fn asset_collection_failed<C: AssetCollection>(
+    collection: Option<Res<C>>,
+    asset_server: Res<AssetServer>,
+) -> bool {
+    collection.is_some_and(|collection| collection.progress(&asset_server).is_failed())
+}
+
+/// A system that starts loading the [`AssetCollection`] `C` and inserts it, replacing the previous
+/// one.
+pub fn load_asset_collection<C: AssetCollection>(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(C::load(&asset_server));
+}
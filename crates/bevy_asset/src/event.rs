@@ -14,6 +14,20 @@ pub struct AssetLoadFailedEvent<A: Asset> {
     pub error: AssetLoadError,
 }
 
+/// An event sent before an unused asset kept alive by its [`AssetRetention`] policy is
+/// unloaded.
+///
+/// The asset is unloaded two updates after this event is sent, unless the unload is cancelled
+/// with [`Assets::cancel_unload`] or the asset gets a new strong [`Handle`] in the meantime.
+///
+/// [`AssetRetention`]: crate::AssetRetention
+/// [`Assets::cancel_unload`]: crate::Assets::cancel_unload
+/// [`Handle`]: crate::Handle
+#[derive(Event, Clone, Debug)]
+pub struct AssetUnloading<A: Asset> {
+    pub id: AssetId<A>,
+}
+
 impl<A: Asset> AssetLoadFailedEvent<A> {
     /// Converts this to an "untyped" / "generic-less" asset error event that stores the type information.
     pub fn untyped(&self) -> UntypedAssetLoadFailedEvent {
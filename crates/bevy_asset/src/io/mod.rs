@@ -270,6 +270,38 @@ pub trait AssetWriter: Send + Sync + 'static {
             Ok(())
         }
     }
+    /// Writes the asset `bytes` to the given `path`, replacing the previous asset at once so that
+    /// readers never observe a partially written asset.
+    ///
+    /// By default, the bytes are written to a temporary path next to the asset, which is then
+    /// [renamed](AssetWriter::rename) to `path`.
+    fn write_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> impl ConditionalSendFuture<Output = Result<(), AssetWriterError>> {
+        async move {
+            let temp_path = get_temp_path(path);
+            self.write_bytes(&temp_path, bytes).await?;
+            self.rename(&temp_path, path).await
+        }
+    }
+    /// Writes the asset meta `bytes` to the given `path`, replacing the previous meta at once so
+    /// that readers never observe a partially written meta.
+    ///
+    /// By default, the bytes are written to a temporary path next to the meta, which is then
+    /// [renamed](AssetWriter::rename_meta) to `path`.
+    fn write_meta_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> impl ConditionalSendFuture<Output = Result<(), AssetWriterError>> {
+        async move {
+            let temp_path = get_temp_path(path);
+            self.write_meta_bytes(&temp_path, bytes).await?;
+            self.rename_meta(&temp_path, path).await
+        }
+    }
 }
 
 /// Equivalent to an [`AssetWriter`] but using boxed futures, necessary eg. when using a `dyn AssetWriter`,
@@ -326,6 +358,18 @@ pub trait ErasedAssetWriter: Send + Sync + 'static {
         path: &'a Path,
         bytes: &'a [u8],
     ) -> BoxedFuture<Result<(), AssetWriterError>>;
+    /// Writes the asset `bytes` to the given `path`, replacing the previous asset at once.
+    fn write_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<Result<(), AssetWriterError>>;
+    /// Writes the asset meta `bytes` to the given `path`, replacing the previous meta at once.
+    fn write_meta_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<Result<(), AssetWriterError>>;
 }
 
 impl<T: AssetWriter> ErasedAssetWriter for T {
@@ -387,6 +431,20 @@ impl<T: AssetWriter> ErasedAssetWriter for T {
     ) -> BoxedFuture<Result<(), AssetWriterError>> {
         Box::pin(Self::write_meta_bytes(self, path, bytes))
     }
+    fn write_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<Result<(), AssetWriterError>> {
+        Box::pin(Self::write_bytes_atomic(self, path, bytes))
+    }
+    fn write_meta_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> BoxedFuture<Result<(), AssetWriterError>> {
+        Box::pin(Self::write_meta_bytes_atomic(self, path, bytes))
+    }
 }
 
 /// An "asset source change event" that occurs whenever asset (or asset metadata) is created/added/removed
@@ -574,6 +632,13 @@ pub(crate) fn get_meta_path(path: &Path) -> PathBuf {
     meta_path
 }
 
+/// Returns the temporary path an asset is written to before being renamed to `path`.
+pub(crate) fn get_temp_path(path: &Path) -> PathBuf {
+    let mut temp_path = path.as_os_str().to_owned();
+    temp_path.push(".tmp");
+    temp_path.into()
+}
+
 /// A [`PathBuf`] [`Stream`] implementation that immediately returns nothing.
 struct EmptyPathStream;
 
@@ -1,12 +1,22 @@
 use crate::io::{
-    get_meta_path, AssetReader, AssetReaderError, EmptyPathStream, PathStream, Reader, VecReader,
+    get_meta_path, AssetReader, AssetReaderError, AssetWriter, AssetWriterError, EmptyPathStream,
+    PathStream, Reader, VecReader, Writer,
 };
 use bevy_utils::tracing::error;
-use js_sys::{Uint8Array, JSON};
-use std::path::{Path, PathBuf};
+use futures_io::AsyncWrite;
+use js_sys::{Array, Uint8Array, JSON};
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+};
 use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
-use wasm_bindgen_futures::JsFuture;
-use web_sys::Response;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    Blob, DomException, File, FileSystemDirectoryHandle, FileSystemFileHandle,
+    FileSystemGetDirectoryOptions, FileSystemGetFileOptions, FileSystemRemoveOptions,
+    FileSystemWritableFileStream, HtmlAnchorElement, Response, StorageManager, Url,
+};
 
 /// Represents the global object in the JavaScript context
 #[wasm_bindgen]
@@ -114,3 +124,490 @@ impl AssetReader for HttpWasmAssetReader {
         Ok(false)
     }
 }
+
+/// A [`Writer`] that buffers the written bytes, and commits them whenever it is flushed or closed
+/// after being written to.
+struct BufferedWasmWriter {
+    bytes: Vec<u8>,
+    dirty: bool,
+    commit: Box<dyn Fn(Vec<u8>) -> std::io::Result<()> + Send + Sync>,
+}
+
+impl BufferedWasmWriter {
+    fn new(commit: impl Fn(Vec<u8>) -> std::io::Result<()> + Send + Sync + 'static) -> Self {
+        Self {
+            bytes: Vec::new(),
+            dirty: false,
+            commit: Box::new(commit),
+        }
+    }
+}
+
+impl AsyncWrite for BufferedWasmWriter {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        this.bytes.extend_from_slice(buf);
+        this.dirty = true;
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        if !this.dirty {
+            return Poll::Ready(Ok(()));
+        }
+        this.dirty = false;
+        Poll::Ready((this.commit)(this.bytes.clone()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+fn unsupported(operation: &str) -> AssetWriterError {
+    let error = std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("{operation} is not supported by the DownloadWasmAssetWriter"),
+    );
+    AssetWriterError::Io(error)
+}
+
+/// Offers `bytes` to the user as a browser download named after the file name of `path`.
+fn download(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let window = web_sys::window().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "Downloads are only supported in a window context",
+        )
+    })?;
+    let document = window
+        .document()
+        .ok_or_else(|| js_value_to_err("get the document")(JsValue::NULL))?;
+    let parts = Array::of1(&Uint8Array::from(bytes));
+    let blob = Blob::new_with_u8_array_sequence(&parts).map_err(js_value_to_err("create blob"))?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(js_value_to_err("create url"))?;
+    let anchor: HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(js_value_to_err("create anchor"))?
+        .unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(&path.file_name().unwrap_or_default().to_string_lossy());
+    anchor.click();
+    Url::revoke_object_url(&url).map_err(js_value_to_err("revoke url"))?;
+    Ok(())
+}
+
+/// Writer implementation that offers each written asset to the user as a browser download.
+///
+/// This lets web builds export user-created content, such as levels made in an in-game editor.
+/// The downloads are named after the file name of the asset path, and each one is complete, so
+/// [`write_bytes_atomic`](AssetWriter::write_bytes_atomic) doesn't need a temporary file. Removing
+/// and renaming assets isn't supported.
+///
+/// This only works on the main thread, as downloads require a window.
+#[derive(Default)]
+pub struct DownloadWasmAssetWriter;
+
+impl AssetWriter for DownloadWasmAssetWriter {
+    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        let path = path.to_owned();
+        let writer = BufferedWasmWriter::new(move |bytes| download(&path, &bytes));
+        Ok(Box::new(writer))
+    }
+
+    async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        self.write(&get_meta_path(path)).await
+    }
+
+    async fn remove<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+        Err(unsupported("Removing assets"))
+    }
+
+    async fn remove_meta<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+        Err(unsupported("Removing asset metas"))
+    }
+
+    async fn rename<'a>(
+        &'a self,
+        _old_path: &'a Path,
+        _new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        Err(unsupported("Renaming assets"))
+    }
+
+    async fn rename_meta<'a>(
+        &'a self,
+        _old_path: &'a Path,
+        _new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        Err(unsupported("Renaming asset metas"))
+    }
+
+    async fn remove_directory<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+        Err(unsupported("Removing directories"))
+    }
+
+    async fn remove_empty_directory<'a>(&'a self, _path: &'a Path) -> Result<(), AssetWriterError> {
+        Err(unsupported("Removing directories"))
+    }
+
+    async fn remove_assets_in_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        Err(unsupported("Removing directories"))
+    }
+
+    async fn write_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        Ok(download(path, bytes)?)
+    }
+
+    async fn write_meta_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        Ok(download(&get_meta_path(path), bytes)?)
+    }
+
+    async fn write_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        self.write_bytes(path, bytes).await
+    }
+
+    async fn write_meta_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        self.write_meta_bytes(path, bytes).await
+    }
+}
+
+/// Returns `true` if `error` is a `DOMException` with the given `name`.
+fn is_dom_exception(error: &JsValue, name: &str) -> bool {
+    error
+        .dyn_ref::<DomException>()
+        .is_some_and(|exception| exception.name() == name)
+}
+
+fn storage_manager() -> Result<StorageManager, JsValue> {
+    let global: Global = js_sys::global().unchecked_into();
+    if !global.window().is_undefined() {
+        let window: web_sys::Window = global.unchecked_into();
+        Ok(window.navigator().storage())
+    } else if !global.worker().is_undefined() {
+        let worker: web_sys::WorkerGlobalScope = global.unchecked_into();
+        Ok(worker.navigator().storage())
+    } else {
+        Err(JsValue::from_str("Unsupported JavaScript global context"))
+    }
+}
+
+/// Returns the handle of the directory at `path` in the origin private file system.
+async fn opfs_directory(path: &Path, create: bool) -> Result<FileSystemDirectoryHandle, JsValue> {
+    let mut directory: FileSystemDirectoryHandle =
+        JsFuture::from(storage_manager()?.get_directory())
+            .await?
+            .unchecked_into();
+    let options = FileSystemGetDirectoryOptions::new();
+    options.set_create(create);
+    for component in path.components() {
+        let name = component.as_os_str().to_string_lossy();
+        directory = JsFuture::from(directory.get_directory_handle_with_options(&name, &options))
+            .await?
+            .unchecked_into();
+    }
+    Ok(directory)
+}
+
+/// Returns the handle of the file at `path` in the origin private file system.
+async fn opfs_file(path: &Path, create: bool) -> Result<FileSystemFileHandle, JsValue> {
+    let directory = opfs_directory(path.parent().unwrap_or(Path::new("")), create).await?;
+    let options = FileSystemGetFileOptions::new();
+    options.set_create(create);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    Ok(
+        JsFuture::from(directory.get_file_handle_with_options(&name, &options))
+            .await?
+            .unchecked_into(),
+    )
+}
+
+async fn opfs_read(path: &Path) -> Result<Vec<u8>, JsValue> {
+    let file: File = JsFuture::from(opfs_file(path, false).await?.get_file())
+        .await?
+        .unchecked_into();
+    let buffer = JsFuture::from(file.array_buffer()).await?;
+    Ok(Uint8Array::new(&buffer).to_vec())
+}
+
+/// Replaces the content of the file at `path`. The browser writes to a swap file and only replaces
+/// the file once the stream is closed, so readers never observe a partially written file.
+async fn opfs_write(path: &Path, bytes: &[u8]) -> Result<(), JsValue> {
+    let stream: FileSystemWritableFileStream =
+        JsFuture::from(opfs_file(path, true).await?.create_writable())
+            .await?
+            .unchecked_into();
+    JsFuture::from(stream.write_with_u8_array(bytes)?).await?;
+    JsFuture::from(stream.close()).await?;
+    Ok(())
+}
+
+async fn opfs_remove(path: &Path, recursive: bool) -> Result<(), JsValue> {
+    let directory = opfs_directory(path.parent().unwrap_or(Path::new("")), false).await?;
+    let options = FileSystemRemoveOptions::new();
+    options.set_recursive(recursive);
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    JsFuture::from(directory.remove_entry_with_options(&name, &options)).await?;
+    Ok(())
+}
+
+fn opfs_reader_err(path: &Path) -> impl FnOnce(JsValue) -> AssetReaderError + '_ {
+    move |error| {
+        if is_dom_exception(&error, "NotFoundError") {
+            AssetReaderError::NotFound(path.to_owned())
+        } else {
+            AssetReaderError::Io(
+                js_value_to_err("read from the origin private file system")(error).into(),
+            )
+        }
+    }
+}
+
+fn opfs_writer_err(error: JsValue) -> AssetWriterError {
+    AssetWriterError::Io(js_value_to_err("write to the origin private file system")(
+        error,
+    ))
+}
+
+/// Reader implementation for loading assets from the
+/// [origin private file system](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system)
+/// of the browser, such as the ones saved with an [`OpfsAssetWriter`].
+pub struct OpfsAssetReader {
+    root_path: PathBuf,
+}
+
+impl OpfsAssetReader {
+    /// Creates a new `OpfsAssetReader`, reading assets from the `path` directory of the origin
+    /// private file system.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            root_path: path.as_ref().to_owned(),
+        }
+    }
+}
+
+impl AssetReader for OpfsAssetReader {
+    async fn read<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let bytes = opfs_read(&self.root_path.join(path))
+            .await
+            .map_err(opfs_reader_err(path))?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+
+    async fn read_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Reader<'a>>, AssetReaderError> {
+        let meta_path = get_meta_path(path);
+        let bytes = opfs_read(&self.root_path.join(&meta_path))
+            .await
+            .map_err(opfs_reader_err(&meta_path))?;
+        let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+        Ok(reader)
+    }
+
+    async fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<Box<PathStream>, AssetReaderError> {
+        let directory = opfs_directory(&self.root_path.join(path), false)
+            .await
+            .map_err(opfs_reader_err(path))?;
+        let names = directory.keys();
+        let mut paths = Vec::new();
+        loop {
+            let next = names.next().map_err(opfs_reader_err(path))?;
+            let next: js_sys::IteratorNext = JsFuture::from(next)
+                .await
+                .map_err(opfs_reader_err(path))?
+                .unchecked_into();
+            if next.done() {
+                break;
+            }
+            if let Some(name) = next.value().as_string() {
+                if !name.ends_with(".meta") {
+                    paths.push(path.join(name));
+                }
+            }
+        }
+        let stream: Box<PathStream> = Box::new(futures_lite::stream::iter(paths));
+        Ok(stream)
+    }
+
+    async fn is_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> std::result::Result<bool, AssetReaderError> {
+        match opfs_directory(&self.root_path.join(path), false).await {
+            Ok(_) => Ok(true),
+            Err(error) if is_dom_exception(&error, "TypeMismatchError") => Ok(false),
+            Err(error) => Err(opfs_reader_err(path)(error)),
+        }
+    }
+}
+
+/// Writer implementation for saving assets to the
+/// [origin private file system](https://developer.mozilla.org/en-US/docs/Web/API/File_System_API/Origin_private_file_system)
+/// of the browser, which persists across page loads.
+///
+/// Register it along with an [`OpfsAssetReader`] of the same path as an asset source, to save
+/// user-created content with [`AssetServer::save`](crate::AssetServer::save) and load it back:
+///
+/// ```ignore
+/// app.register_asset_source(
+///     "saves",
+///     AssetSource::build()
+///         .with_reader(|| Box::new(OpfsAssetReader::new("saves")))
+///         .with_writer(|_| Some(Box::new(OpfsAssetWriter::new("saves")))),
+/// );
+/// ```
+///
+/// The browser replaces files only once they are completely written, so
+/// [`write_bytes_atomic`](AssetWriter::write_bytes_atomic) doesn't need a temporary file. The
+/// writers returned by [`write`](AssetWriter::write) commit their content in the background when
+/// flushed, while [`write_bytes`](AssetWriter::write_bytes) waits for it to be written.
+pub struct OpfsAssetWriter {
+    root_path: PathBuf,
+}
+
+impl OpfsAssetWriter {
+    /// Creates a new `OpfsAssetWriter`, writing assets to the `path` directory of the origin
+    /// private file system.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            root_path: path.as_ref().to_owned(),
+        }
+    }
+
+    async fn rename_file(&self, old_path: &Path, new_path: &Path) -> Result<(), JsValue> {
+        let bytes = opfs_read(&self.root_path.join(old_path)).await?;
+        opfs_write(&self.root_path.join(new_path), &bytes).await?;
+        opfs_remove(&self.root_path.join(old_path), false).await
+    }
+}
+
+impl AssetWriter for OpfsAssetWriter {
+    async fn write<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        let path = self.root_path.join(path);
+        let writer = BufferedWasmWriter::new(move |bytes| {
+            let path = path.clone();
+            spawn_local(async move {
+                if let Err(error) = opfs_write(&path, &bytes).await {
+                    error!("{}", opfs_writer_err(error));
+                }
+            });
+            Ok(())
+        });
+        Ok(Box::new(writer))
+    }
+
+    async fn write_meta<'a>(&'a self, path: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+        self.write(&get_meta_path(path)).await
+    }
+
+    async fn remove<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        opfs_remove(&self.root_path.join(path), false)
+            .await
+            .map_err(opfs_writer_err)
+    }
+
+    async fn remove_meta<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.remove(&get_meta_path(path)).await
+    }
+
+    async fn rename<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.rename_file(old_path, new_path)
+            .await
+            .map_err(opfs_writer_err)
+    }
+
+    async fn rename_meta<'a>(
+        &'a self,
+        old_path: &'a Path,
+        new_path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.rename(&get_meta_path(old_path), &get_meta_path(new_path))
+            .await
+    }
+
+    async fn remove_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        opfs_remove(&self.root_path.join(path), true)
+            .await
+            .map_err(opfs_writer_err)
+    }
+
+    async fn remove_empty_directory<'a>(&'a self, path: &'a Path) -> Result<(), AssetWriterError> {
+        self.remove(path).await
+    }
+
+    async fn remove_assets_in_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> Result<(), AssetWriterError> {
+        self.remove_directory(path).await?;
+        opfs_directory(&self.root_path.join(path), true)
+            .await
+            .map_err(opfs_writer_err)?;
+        Ok(())
+    }
+
+    async fn write_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        opfs_write(&self.root_path.join(path), bytes)
+            .await
+            .map_err(opfs_writer_err)
+    }
+
+    async fn write_meta_bytes<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        self.write_bytes(&get_meta_path(path), bytes).await
+    }
+
+    async fn write_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        self.write_bytes(path, bytes).await
+    }
+
+    async fn write_meta_bytes_atomic<'a>(
+        &'a self,
+        path: &'a Path,
+        bytes: &'a [u8],
+    ) -> Result<(), AssetWriterError> {
+        self.write_meta_bytes(path, bytes).await
+    }
+}
@@ -21,6 +21,7 @@ pub mod prelude {
 }
 
 mod assets;
+mod collection;
 mod direct_access_ext;
 mod event;
 mod folder;
@@ -29,10 +30,12 @@ mod id;
 mod loader;
 mod path;
 mod reflect;
+mod retention;
 mod server;
 
 pub use assets::*;
-pub use bevy_asset_macros::Asset;
+pub use bevy_asset_macros::{Asset, AssetCollection};
+pub use collection::*;
 pub use direct_access_ext::DirectAssetAccessExt;
 pub use event::*;
 pub use folder::*;
@@ -42,6 +45,7 @@ pub use id::*;
 pub use loader::*;
 pub use path::*;
 pub use reflect::*;
+pub use retention::AssetRetention;
 pub use server::*;
 
 /// Rusty Object Notation, a crate used to serialize and deserialize bevy assets.
@@ -50,11 +54,12 @@ pub use ron;
 use crate::{
     io::{embedded::EmbeddedAssetRegistry, AssetSourceBuilder, AssetSourceBuilders, AssetSourceId},
     processor::{AssetProcessor, Process},
+    saver::AssetSaver,
 };
 use bevy_app::{App, Last, Plugin, PreUpdate};
 use bevy_ecs::{
     reflect::AppTypeRegistry,
-    schedule::{IntoSystemConfigs, IntoSystemSetConfigs, SystemSet},
+    schedule::{IntoSystemConfigs, IntoSystemSetConfigs, ScheduleLabel, SystemSet},
     world::FromWorld,
 };
 use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect, TypePath};
@@ -219,6 +224,7 @@ impl Plugin for AssetPlugin {
             .add_event::<UntypedAssetLoadFailedEvent>()
             .configure_sets(PreUpdate, TrackAssets.after(handle_internal_asset_events))
             .add_systems(PreUpdate, handle_internal_asset_events)
+            .add_systems(Last, save_requested_assets)
             .register_type::<AssetPath>();
     }
 }
@@ -277,6 +283,9 @@ impl VisitAssetDependencies for Vec<UntypedHandle> {
 pub trait AssetApp {
     /// Registers the given `loader` in the [`App`]'s [`AssetServer`].
     fn register_asset_loader<L: AssetLoader>(&mut self, loader: L) -> &mut Self;
+    /// Registers the given `saver` in the [`App`]'s [`AssetServer`], to save assets with
+    /// [`AssetServer::save`].
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self;
     /// Registers the given `processor` in the [`App`]'s [`AssetProcessor`].
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self;
     /// Registers the given [`AssetSourceBuilder`] with the given `id`.
@@ -310,6 +319,29 @@ pub trait AssetApp {
     /// Preregisters a loader for the given extensions, that will block asset loads until a real loader
     /// is registered.
     fn preregister_asset_loader<L: AssetLoader>(&mut self, extensions: &[&str]) -> &mut Self;
+    /// Sets when the assets of type `A` are unloaded once they are no longer used.
+    ///
+    /// See [`AssetRetention`] for the available policies.
+    fn set_asset_retention<A: Asset>(&mut self, retention: AssetRetention) -> &mut Self;
+    /// Makes assets of type `A` loaded from identical bytes with identical settings share the
+    /// same value, even if they are loaded from different paths.
+    ///
+    /// This saves memory in projects with copy-pasted files, at the cost of hashing the content
+    /// of every loaded asset of this type. An asset is only deduplicated once the first asset
+    /// with the same content finished loading. Use [`AssetServer::duplicate_assets`] to list the
+    /// deduplicated assets.
+    fn deduplicate_assets<A: Asset>(&mut self) -> &mut Self;
+    /// Starts loading the [`AssetCollection`] `C` and inserts it as a resource.
+    fn init_asset_collection<C: AssetCollection>(&mut self) -> &mut Self;
+    /// Starts loading the [`AssetCollection`] `C` and inserts it as a resource every time the
+    /// `schedule` runs.
+    ///
+    /// This ties the collection to a state when passed its `OnEnter` schedule. Removing the
+    /// resource, for example in `OnExit`, releases the handles so that the assets can be unloaded.
+    fn load_asset_collection_in<C: AssetCollection>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+    ) -> &mut Self;
 }
 
 impl AssetApp for App {
@@ -320,6 +352,11 @@ impl AssetApp for App {
         self
     }
 
+    fn register_asset_saver<S: AssetSaver>(&mut self, saver: S) -> &mut Self {
+        self.world().resource::<AssetServer>().register_saver(saver);
+        self
+    }
+
     fn register_asset_processor<P: Process>(&mut self, processor: P) -> &mut Self {
         if let Some(asset_processor) = self.world().get_resource::<AssetProcessor>() {
             asset_processor.register_processor(processor);
@@ -380,6 +417,7 @@ impl AssetApp for App {
             .allow_ambiguous_resource::<Assets<A>>()
             .add_event::<AssetEvent<A>>()
             .add_event::<AssetLoadFailedEvent<A>>()
+            .add_event::<AssetUnloading<A>>()
             .register_type::<Handle<A>>()
             .add_systems(
                 Last,
@@ -413,6 +451,32 @@ impl AssetApp for App {
             .preregister_loader::<L>(extensions);
         self
     }
+
+    fn set_asset_retention<A: Asset>(&mut self, retention: AssetRetention) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<Assets<A>>()
+            .set_retention(retention);
+        self
+    }
+
+    fn deduplicate_assets<A: Asset>(&mut self) -> &mut Self {
+        self.world()
+            .resource::<AssetServer>()
+            .deduplicate_assets::<A>();
+        self
+    }
+
+    fn init_asset_collection<C: AssetCollection>(&mut self) -> &mut Self {
+        let collection = C::load(self.world().resource::<AssetServer>());
+        self.insert_resource(collection)
+    }
+
+    fn load_asset_collection_in<C: AssetCollection>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+    ) -> &mut Self {
+        self.add_systems(schedule, load_asset_collection::<C>)
+    }
 }
 
 /// A system set that holds all "track asset" operations.
@@ -428,32 +492,41 @@ pub struct AssetEvents;
 #[cfg(test)]
 mod tests {
     use crate::{
-        self as bevy_asset,
+        self as bevy_asset, asset_collection_failed, asset_collection_loaded,
         folder::LoadedFolder,
         handle::Handle,
         io::{
             gated::{GateOpener, GatedReader},
+            get_meta_path,
             memory::{Dir, MemoryAssetReader},
-            AssetReader, AssetReaderError, AssetSource, AssetSourceId, Reader,
+            AssetReader, AssetReaderError, AssetSource, AssetSourceId, AssetWriter,
+            AssetWriterError, Reader, Writer,
         },
         loader::{AssetLoader, LoadContext},
-        Asset, AssetApp, AssetEvent, AssetId, AssetLoadError, AssetLoadFailedEvent, AssetPath,
-        AssetPlugin, AssetServer, Assets, DependencyLoadState, LoadState,
-        RecursiveDependencyLoadState,
+        saver::{AssetSaver, SavedAsset},
+        Asset, AssetApp, AssetCollection, AssetCollectionProgress, AssetEvent, AssetId,
+        AssetLoadError, AssetLoadFailedEvent, AssetPath, AssetPlugin, AssetRetention, AssetServer,
+        AssetUnloading, Assets, DependencyLoadState, DirectAssetAccessExt, LoadState,
+        RecursiveDependencyLoadState, SaveAssetError,
     };
-    use bevy_app::{App, Update};
+    use bevy_app::{App, Startup, Update};
     use bevy_core::TaskPoolPlugin;
     use bevy_ecs::prelude::*;
     use bevy_ecs::{
         event::ManualEventReader,
         schedule::{LogLevel, ScheduleBuildSettings},
+        system::RunSystemOnce,
     };
     use bevy_log::LogPlugin;
     use bevy_reflect::TypePath;
+    use bevy_tasks::block_on;
     use bevy_utils::{Duration, HashMap};
-    use futures_lite::AsyncReadExt;
+    use futures_lite::{future::poll_once, AsyncReadExt, AsyncWriteExt};
     use serde::{Deserialize, Serialize};
-    use std::{path::Path, sync::Arc};
+    use std::{
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex},
+    };
     use thiserror::Error;
 
     #[derive(Asset, TypePath, Debug, Default)]
@@ -1479,6 +1552,389 @@ mod tests {
         app.world_mut().run_schedule(Update);
     }
 
+    /// An [`AssetWriter`] storing the written bytes, which only supports what saving assets needs.
+    #[derive(Clone, Default)]
+    struct SavedFiles(Arc<Mutex<HashMap<PathBuf, Vec<u8>>>>);
+
+    impl AssetWriter for SavedFiles {
+        async fn write<'a>(&'a self, _: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+            unimplemented!()
+        }
+        async fn write_meta<'a>(&'a self, _: &'a Path) -> Result<Box<Writer>, AssetWriterError> {
+            unimplemented!()
+        }
+        async fn remove<'a>(&'a self, _: &'a Path) -> Result<(), AssetWriterError> {
+            unimplemented!()
+        }
+        async fn remove_meta<'a>(&'a self, _: &'a Path) -> Result<(), AssetWriterError> {
+            unimplemented!()
+        }
+        async fn rename<'a>(
+            &'a self,
+            old_path: &'a Path,
+            new_path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            let mut files = self.0.lock().unwrap();
+            let bytes = files.remove(old_path).unwrap();
+            files.insert(new_path.to_owned(), bytes);
+            Ok(())
+        }
+        async fn rename_meta<'a>(
+            &'a self,
+            old_path: &'a Path,
+            new_path: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            self.rename(&get_meta_path(old_path), &get_meta_path(new_path))
+                .await
+        }
+        async fn remove_directory<'a>(&'a self, _: &'a Path) -> Result<(), AssetWriterError> {
+            unimplemented!()
+        }
+        async fn remove_empty_directory<'a>(&'a self, _: &'a Path) -> Result<(), AssetWriterError> {
+            unimplemented!()
+        }
+        async fn remove_assets_in_directory<'a>(
+            &'a self,
+            _: &'a Path,
+        ) -> Result<(), AssetWriterError> {
+            unimplemented!()
+        }
+        async fn write_bytes<'a>(
+            &'a self,
+            path: &'a Path,
+            bytes: &'a [u8],
+        ) -> Result<(), AssetWriterError> {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(path.to_owned(), bytes.to_vec());
+            Ok(())
+        }
+        async fn write_meta_bytes<'a>(
+            &'a self,
+            path: &'a Path,
+            bytes: &'a [u8],
+        ) -> Result<(), AssetWriterError> {
+            self.write_bytes(&get_meta_path(path), bytes).await
+        }
+    }
+
+    struct CoolTextSaver;
+
+    impl AssetSaver for CoolTextSaver {
+        type Asset = CoolText;
+        type Settings = ();
+        type OutputLoader = CoolTextLoader;
+        type Error = ron::Error;
+
+        async fn save<'a>(
+            &'a self,
+            writer: &'a mut Writer,
+            asset: SavedAsset<'a, Self::Asset>,
+            _settings: &'a Self::Settings,
+        ) -> Result<(), Self::Error> {
+            let ron = CoolTextRon {
+                text: asset.text.clone(),
+                dependencies: Vec::new(),
+                embedded_dependencies: Vec::new(),
+                sub_texts: Vec::new(),
+            };
+            writer.write_all(ron::to_string(&ron)?.as_bytes()).await?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn save_asset() {
+        let files = SavedFiles::default();
+        let mut app = App::new();
+        let writer = files.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(|| Box::new(MemoryAssetReader::default()))
+                .with_writer(move |_| Some(Box::new(writer.clone()))),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<CoolText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_saver(CoolTextSaver);
+
+        let handle = app.world_mut().add_asset::<CoolText>(CoolText {
+            text: "saved".to_string(),
+            ..Default::default()
+        });
+        let server = app.world().resource::<AssetServer>().clone();
+        let mut saved = Box::pin(server.save(&handle, "text.cool.ron"));
+        run_app_until(&mut app, |_| {
+            block_on(poll_once(&mut saved)).map(Result::unwrap)
+        });
+
+        let files = files.0.lock().unwrap();
+        assert_eq!(files.len(), 2);
+        let ron: CoolTextRon = ron::de::from_bytes(&files[Path::new("text.cool.ron")]).unwrap();
+        assert_eq!(ron.text, "saved");
+        let meta = &files[Path::new("text.cool.ron.meta")];
+        assert!(std::str::from_utf8(meta)
+            .unwrap()
+            .contains("CoolTextLoader"));
+
+        let missing = server.save(&Handle::<SubText>::default(), "text.sub");
+        app.update();
+        assert!(matches!(
+            block_on(missing),
+            Err(SaveAssetError::MissingAssetSaver { .. })
+        ));
+    }
+
+    #[derive(Resource, AssetCollection)]
+    struct TextCollection {
+        #[asset(path = "a.cool.ron")]
+        a: Handle<CoolText>,
+        #[asset(path = "b.cool.ron")]
+        b: Handle<CoolText>,
+        name: String,
+    }
+
+    #[derive(Resource, AssetCollection)]
+    struct MissingCollection(#[asset(path = "missing.cool.ron")] Handle<CoolText>);
+
+    #[test]
+    fn asset_collection() {
+        let dir = Dir::default();
+        let a_ron = r#"
+(
+    text: "a",
+    dependencies: ["b.cool.ron"],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new("a.cool.ron"), a_ron);
+        dir.insert_asset_text(Path::new("b.cool.ron"), b_ron);
+
+        #[derive(Resource, Default)]
+        struct Ready(bool);
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .init_resource::<Ready>()
+        .init_asset_collection::<TextCollection>()
+        .load_asset_collection_in::<MissingCollection>(Startup)
+        .add_systems(
+            Update,
+            (|mut ready: ResMut<Ready>| ready.0 = true)
+                .run_if(asset_collection_loaded::<TextCollection>),
+        );
+
+        let collection = app.world().resource::<TextCollection>();
+        assert!(collection.name.is_empty());
+        let server = app.world().resource::<AssetServer>();
+        assert_eq!(collection.progress(server).total, 2);
+
+        run_app_until(&mut app, |world| world.resource::<Ready>().0.then_some(()));
+        let collection = app.world().resource::<TextCollection>();
+        let texts = app.world().resource::<Assets<CoolText>>();
+        assert_eq!(texts.get(&collection.a).unwrap().text, "a");
+        assert_eq!(texts.get(&collection.b).unwrap().text, "b");
+
+        run_app_until(&mut app, |world| {
+            world
+                .run_system_once(asset_collection_failed::<MissingCollection>)
+                .then_some(())
+        });
+        let collection = app.world().resource::<MissingCollection>();
+        let server = app.world().resource::<AssetServer>();
+        assert_eq!(
+            collection.progress(server),
+            AssetCollectionProgress {
+                loaded: 0,
+                failed: 1,
+                total: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn asset_retention() {
+        let dir = Dir::default();
+        let b_ron = r#"
+(
+    text: "b",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#;
+        dir.insert_asset_text(Path::new("b.cool.ron"), b_ron);
+
+        #[derive(Resource, Default)]
+        struct Unloading(usize);
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .set_asset_retention::<CoolText>(AssetRetention::Manual)
+        .init_resource::<Unloading>()
+        .add_systems(
+            Update,
+            |mut events: EventReader<AssetUnloading<CoolText>>,
+             mut unloading: ResMut<Unloading>| {
+                unloading.0 += events.read().count();
+            },
+        );
+
+        let handle = app.world_mut().add_asset::<CoolText>(CoolText::default());
+        let id = handle.id();
+        drop(handle);
+        app.update();
+        let assets = app.world().resource::<Assets<CoolText>>();
+        assert!(assets.contains(id));
+        assert!(assets.is_retained(id));
+
+        // a cancelled unload keeps the asset
+        assert!(app
+            .world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .unload(id));
+        app.update();
+        assert!(app
+            .world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .cancel_unload(id));
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<Unloading>().0, 1);
+        assert!(app.world().resource::<Assets<CoolText>>().contains(id));
+
+        app.world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .unload(id);
+        run_app_until(&mut app, |world| {
+            (!world.resource::<Assets<CoolText>>().contains(id)).then_some(())
+        });
+        assert_eq!(app.world().resource::<Unloading>().0, 2);
+
+        // loading a retained asset again reuses it
+        app.world_mut()
+            .resource_mut::<Assets<CoolText>>()
+            .set_retention(AssetRetention::Timeout(std::time::Duration::ZERO));
+        let server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<CoolText> = server.load("b.cool.ron");
+        let id = handle.id();
+        run_app_until(&mut app, |_| {
+            server.is_loaded_with_dependencies(id).then_some(())
+        });
+        drop(handle);
+        app.update();
+        assert!(app.world().resource::<Assets<CoolText>>().is_retained(id));
+        let handle: Handle<CoolText> = server.load("b.cool.ron");
+        assert_eq!(handle.id(), id);
+        assert_eq!(server.load_state(id), LoadState::Loaded);
+        app.update();
+        assert!(!app.world().resource::<Assets<CoolText>>().is_retained(id));
+
+        drop(handle);
+        run_app_until(&mut app, |world| {
+            (!world.resource::<Assets<CoolText>>().contains(id)).then_some(())
+        });
+        assert!(!server.is_loaded_with_dependencies(id));
+    }
+
+    #[test]
+    fn deduplicate_assets() {
+        let dir = Dir::default();
+        let text = |text: &str| {
+            format!(
+                r#"
+(
+    text: "{text}",
+    dependencies: [],
+    embedded_dependencies: [],
+    sub_texts: [],
+)"#
+            )
+        };
+        dir.insert_asset_text(Path::new("a.cool.ron"), &text("a"));
+        dir.insert_asset_text(Path::new("copy.cool.ron"), &text("a"));
+        dir.insert_asset_text(Path::new("b.cool.ron"), &text("b"));
+
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .deduplicate_assets::<CoolText>();
+
+        let server = app.world().resource::<AssetServer>().clone();
+        let a: Handle<CoolText> = server.load("a.cool.ron");
+        run_app_until(&mut app, |_| {
+            server.is_loaded_with_dependencies(&a).then_some(())
+        });
+        let copy: Handle<CoolText> = server.load("copy.cool.ron");
+        let b: Handle<CoolText> = server.load("b.cool.ron");
+        run_app_until(&mut app, |_| {
+            (server.is_loaded_with_dependencies(&copy) && server.is_loaded_with_dependencies(&b))
+                .then_some(())
+        });
+
+        let assets = app.world().resource::<Assets<CoolText>>();
+        assert_ne!(copy.id(), a.id());
+        assert_eq!(assets.get(&copy).unwrap().text, "a");
+        assert_eq!(assets.get(&b).unwrap().text, "b");
+        assert_eq!(assets.len(), 2);
+        assert_eq!(
+            server.duplicate_assets(),
+            vec![("copy.cool.ron".into(), "a.cool.ron".into())]
+        );
+
+        // the duplicate keeps the original alive
+        let a_id = a.id();
+        drop(a);
+        app.update();
+        app.update();
+        assert!(app.world().resource::<Assets<CoolText>>().contains(a_id));
+        assert_eq!(
+            app.world()
+                .resource::<Assets<CoolText>>()
+                .get(&copy)
+                .unwrap()
+                .text,
+            "a"
+        );
+
+        drop(copy);
+        run_app_until(&mut app, |world| {
+            (!world.resource::<Assets<CoolText>>().contains(a_id)).then_some(())
+        });
+        assert!(server.duplicate_assets().is_empty());
+    }
+
     // validate the Asset derive macro for various asset types
     #[derive(Asset, TypePath)]
     pub struct TestAsset;
@@ -248,6 +248,28 @@ impl<A: Asset> AssetContainer for A {
     }
 }
 
+/// An [`AssetContainer`] for an asset loaded with the same content as the asset `0`, which makes
+/// the asset share its value. See [`AssetApp::deduplicate_assets`](crate::AssetApp::deduplicate_assets).
+pub(crate) struct AssetAlias<A: Asset>(pub(crate) Handle<A>);
+
+impl<A: Asset> AssetAlias<A> {
+    pub(crate) fn new_erased(original: UntypedHandle) -> Box<dyn AssetContainer> {
+        Box::new(Self(original.typed_debug_checked()))
+    }
+}
+
+impl<A: Asset> AssetContainer for AssetAlias<A> {
+    fn insert(self: Box<Self>, id: UntypedAssetId, world: &mut World) {
+        world
+            .resource_mut::<Assets<A>>()
+            .insert_alias(id.typed(), self.0);
+    }
+
+    fn asset_type_name(&self) -> &'static str {
+        std::any::type_name::<A>()
+    }
+}
+
 /// An error that occurs when attempting to call [`LoadContext::load_direct`]
 #[derive(Error, Debug)]
 #[error("Failed to load dependency {dependency:?} {error}")]
@@ -0,0 +1,69 @@
+use crate::{Asset, Handle};
+use bevy_utils::{Duration, Instant};
+use std::sync::Arc;
+
+/// When the assets of a type are unloaded once they are no longer used, configured with
+/// [`Assets::set_retention`](crate::Assets::set_retention) or
+/// [`AssetApp::set_asset_retention`](crate::AssetApp::set_asset_retention).
+///
+/// An asset is unused once all of its strong [`Handle`]s are dropped. The [`Assets`](crate::Assets)
+/// collection then keeps the asset alive itself, until the policy decides to unload it.
+/// Loading the asset again in the meantime, with [`AssetServer::load`](crate::AssetServer::load)
+/// or [`AssetServer::get_id_handle`](crate::AssetServer::get_id_handle), reuses it instead of
+/// loading it from scratch.
+///
+/// Before unloading an asset that is kept alive, an [`AssetUnloading`](crate::AssetUnloading)
+/// event is sent, and the asset is only unloaded two updates later, so that systems can veto it
+/// with [`Assets::cancel_unload`](crate::Assets::cancel_unload).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AssetRetention {
+    /// Unload the assets as soon as they are unused, without sending [`AssetUnloading`](crate::AssetUnloading)
+    /// events.
+    #[default]
+    Immediate,
+    /// Unload the assets once they have been unused for the given duration.
+    Timeout(Duration),
+    /// Only unload the assets when requested with [`Assets::unload`](crate::Assets::unload).
+    Manual,
+    /// Never unload the assets, unless they are removed with [`Assets::remove`](crate::Assets::remove).
+    Forever,
+}
+
+/// An unused asset kept alive by its [`AssetRetention`] policy.
+pub(crate) struct RetainedAsset<A: Asset> {
+    handle: Handle<A>,
+    unused_since: Option<Instant>,
+    /// The [`Assets::track_assets`](crate::Assets::track_assets) pass in which an unload was
+    /// requested.
+    pub(crate) unloading_at: Option<u32>,
+}
+
+impl<A: Asset> RetainedAsset<A> {
+    pub(crate) fn new(handle: Handle<A>) -> Self {
+        Self {
+            handle,
+            unused_since: None,
+            unloading_at: None,
+        }
+    }
+
+    /// Returns `true` if there are other strong handles to the asset, sharing the retained one.
+    pub(crate) fn is_shared(&self) -> bool {
+        match &self.handle {
+            Handle::Strong(handle) => Arc::strong_count(handle) > 1,
+            Handle::Weak(_) => false,
+        }
+    }
+
+    /// Marks the asset as used, cancelling any requested unload.
+    pub(crate) fn mark_used(&mut self) {
+        self.unused_since = None;
+        self.unloading_at = None;
+    }
+
+    /// Returns for how long the asset has been unused, starting to count from `now` if it was in
+    /// use.
+    pub(crate) fn unused_for(&mut self, now: Instant) -> Duration {
+        now - *self.unused_since.get_or_insert(now)
+    }
+}
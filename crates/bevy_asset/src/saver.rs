@@ -1,9 +1,19 @@
 use crate::transformer::TransformedAsset;
-use crate::{io::Writer, meta::Settings, Asset, ErasedLoadedAsset};
+use crate::{
+    io::Writer,
+    meta::{AssetAction, AssetMeta, AssetMetaDyn, Settings},
+    Asset, ErasedLoadedAsset,
+};
 use crate::{AssetLoader, Handle, LabeledAsset, UntypedHandle};
 use bevy_utils::{BoxedFuture, ConditionalSendFuture, CowArc, HashMap};
 use serde::{Deserialize, Serialize};
-use std::{borrow::Borrow, hash::Hash, ops::Deref};
+use std::{
+    any::{Any, TypeId},
+    borrow::Borrow,
+    hash::Hash,
+    ops::Deref,
+    sync::Arc,
+};
 
 /// Saves an [`Asset`] of a given [`AssetSaver::Asset`] type. [`AssetSaver::OutputLoader`] will then be used to load the saved asset
 /// in the final deployed application. The saver should produce asset bytes in a format that [`AssetSaver::OutputLoader`] can read.
@@ -67,6 +77,91 @@ impl<S: AssetSaver> ErasedAssetSaver for S {
     }
 }
 
+/// The bytes of an asset and of its meta, produced by a registered [`AssetSaver`].
+pub(crate) struct SavedBytes {
+    pub(crate) asset: Vec<u8>,
+    pub(crate) meta: Vec<u8>,
+}
+
+/// An [`AssetSaver`] registered with the [`AssetServer`](crate::AssetServer), with its asset type
+/// erased.
+pub(crate) trait RegisteredAssetSaver: Send + Sync + 'static {
+    /// The type name of the [`AssetSaver::OutputLoader`].
+    fn output_loader_type_name(&self) -> &'static str;
+
+    /// Saves `asset`, which must be an [`AssetSaver::Asset`], with the default settings.
+    fn save_to_bytes<'a>(
+        &'a self,
+        asset: &'a (dyn Any + Send + Sync),
+    ) -> BoxedFuture<'a, Result<SavedBytes, Box<dyn std::error::Error + Send + Sync + 'static>>>;
+}
+
+impl<S: AssetSaver> RegisteredAssetSaver for S {
+    fn output_loader_type_name(&self) -> &'static str {
+        std::any::type_name::<S::OutputLoader>()
+    }
+
+    fn save_to_bytes<'a>(
+        &'a self,
+        asset: &'a (dyn Any + Send + Sync),
+    ) -> BoxedFuture<'a, Result<SavedBytes, Box<dyn std::error::Error + Send + Sync + 'static>>>
+    {
+        Box::pin(async move {
+            let labeled_assets = HashMap::default();
+            let saved_asset = SavedAsset {
+                value: asset
+                    .downcast_ref::<S::Asset>()
+                    .expect("the asset should match the saver type"),
+                labeled_assets: &labeled_assets,
+            };
+            let mut bytes = Vec::new();
+            let settings = S::Settings::default();
+            let loader_settings = AssetSaver::save(self, &mut bytes, saved_asset, &settings)
+                .await
+                .map_err(Into::into)?;
+            let meta = AssetMeta::<S::OutputLoader, ()>::new(AssetAction::Load {
+                loader: self.output_loader_type_name().to_string(),
+                settings: loader_settings,
+            });
+            Ok(SavedBytes {
+                asset: bytes,
+                meta: AssetMetaDyn::serialize(&meta),
+            })
+        })
+    }
+}
+
+/// The [`AssetSaver`]s registered with the [`AssetServer`](crate::AssetServer), by asset type.
+#[derive(Default)]
+pub(crate) struct AssetSavers {
+    savers: HashMap<TypeId, Vec<Arc<dyn RegisteredAssetSaver>>>,
+}
+
+impl AssetSavers {
+    pub(crate) fn push<S: AssetSaver>(&mut self, saver: S) {
+        self.savers
+            .entry(TypeId::of::<S::Asset>())
+            .or_default()
+            .push(Arc::new(saver));
+    }
+
+    /// Returns the saver for the asset type `type_id` whose output is loaded by `loader_type_name`,
+    /// or the last registered one if none matches.
+    pub(crate) fn find(
+        &self,
+        type_id: TypeId,
+        loader_type_name: Option<&str>,
+    ) -> Option<Arc<dyn RegisteredAssetSaver>> {
+        let savers = self.savers.get(&type_id)?;
+        savers
+            .iter()
+            .rev()
+            .find(|saver| Some(saver.output_loader_type_name()) == loader_type_name)
+            .or_else(|| savers.last())
+            .cloned()
+    }
+}
+
 /// An [`Asset`] (and any labeled "sub assets") intended to be saved.
 pub struct SavedAsset<'a, A: Asset> {
     value: &'a A,
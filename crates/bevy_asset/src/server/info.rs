@@ -1,4 +1,5 @@
 use crate::{
+    loader::AssetContainer,
     meta::{AssetHash, MetaTransform},
     Asset, AssetHandleProvider, AssetLoadError, AssetPath, DependencyLoadState, ErasedLoadedAsset,
     Handle, InternalAssetEvent, LoadState, RecursiveDependencyLoadState, StrongHandle,
@@ -37,6 +38,11 @@ pub(crate) struct AssetInfo {
     /// The number of handle drops to skip for this asset.
     /// See usage (and comments) in `get_or_create_path_handle` for context.
     handle_drops_to_skip: usize,
+    /// The hash of the content this asset was loaded from, if its type is deduplicated and other
+    /// assets with the same content share its value.
+    content_hash: Option<AssetHash>,
+    /// The asset this asset shares its value with, if it was loaded with the same content.
+    pub(crate) duplicate_of: Option<UntypedAssetId>,
 }
 
 impl AssetInfo {
@@ -55,6 +61,8 @@ impl AssetInfo {
             dependants_waiting_on_load: HashSet::default(),
             dependants_waiting_on_recursive_dep_load: HashSet::default(),
             handle_drops_to_skip: 0,
+            content_hash: None,
+            duplicate_of: None,
         }
     }
 }
@@ -76,6 +84,11 @@ pub(crate) struct AssetInfos {
     pub(crate) dependency_loaded_event_sender: TypeIdMap<fn(&mut World, UntypedAssetId)>,
     pub(crate) dependency_failed_event_sender:
         TypeIdMap<fn(&mut World, UntypedAssetId, AssetPath<'static>, AssetLoadError)>,
+    /// The asset types whose assets are deduplicated by content, with the constructor of the
+    /// [`AssetAlias`](crate::loader::AssetAlias) container of each type.
+    pub(crate) deduplicated_types: TypeIdMap<fn(UntypedHandle) -> Box<dyn AssetContainer>>,
+    /// The assets of deduplicated types, by type and content hash.
+    content_hashes: HashMap<(TypeId, AssetHash), UntypedAssetId>,
 }
 
 impl std::fmt::Debug for AssetInfos {
@@ -332,6 +345,82 @@ impl AssetInfos {
         Some(UntypedHandle::Strong(strong_handle))
     }
 
+    /// Creates a new strong handle for an asset whose last strong handle was just dropped, so that
+    /// the drop doesn't remove it. Returns `None` if a new handle was already created since, or if
+    /// the asset isn't managed by the asset server.
+    pub(crate) fn revive_handle(&mut self, id: UntypedAssetId) -> Option<UntypedHandle> {
+        let info = self.infos.get_mut(&id)?;
+        if info.handle_drops_to_skip > 0 || info.weak_handle.strong_count() > 0 {
+            return None;
+        }
+        let provider = self.handle_providers.get(&id.type_id())?;
+        let handle = provider.get_handle(id.internal(), true, info.path.clone(), None);
+        info.weak_handle = Arc::downgrade(&handle);
+        Some(UntypedHandle::Strong(handle))
+    }
+
+    /// Records that the asset `id` of a deduplicated type was loaded from content with the given
+    /// `hash`.
+    ///
+    /// Returns a strong handle to an asset that was already loaded from the same content, in which
+    /// case `id` is marked as its duplicate instead.
+    pub(crate) fn deduplicate(
+        &mut self,
+        id: UntypedAssetId,
+        hash: AssetHash,
+    ) -> Option<UntypedHandle> {
+        let key = (id.type_id(), hash);
+        let mut original = None;
+        if let Some(&original_id) = self.content_hashes.get(&key) {
+            let info = self
+                .infos
+                .get(&original_id)
+                .filter(|info| original_id != id && info.content_hash == Some(hash));
+            if let Some(info) = info {
+                // an original that is still loading can't be shared yet, so this asset is loaded
+                // on its own
+                original = Some(
+                    (info.load_state == LoadState::Loaded)
+                        .then(|| self.get_id_handle(original_id))
+                        .flatten()
+                        .map(|handle| (original_id, handle)),
+                );
+            }
+        }
+
+        let info = self.infos.get_mut(&id)?;
+        if let Some(previous_hash) = info.content_hash.take() {
+            if self.content_hashes.get(&(id.type_id(), previous_hash)) == Some(&id) {
+                self.content_hashes.remove(&(id.type_id(), previous_hash));
+            }
+        }
+        info.duplicate_of = None;
+        match original {
+            Some(Some((original_id, handle))) => {
+                info.duplicate_of = Some(original_id);
+                Some(handle)
+            }
+            Some(None) => None,
+            None => {
+                info.content_hash = Some(hash);
+                self.content_hashes.insert(key, id);
+                None
+            }
+        }
+    }
+
+    /// Returns the paths of the assets that share their value with another asset because they
+    /// were loaded with the same content, along with the path of that asset.
+    pub(crate) fn duplicates(&self) -> Vec<(AssetPath<'static>, AssetPath<'static>)> {
+        self.infos
+            .values()
+            .filter_map(|info| {
+                let original = self.infos.get(&info.duplicate_of?)?;
+                Some((info.path.clone()?, original.path.clone()?))
+            })
+            .collect()
+    }
+
     /// Returns `true` if the asset this path points to is still alive
     pub(crate) fn is_path_alive<'a>(&self, path: impl Into<AssetPath<'a>>) -> bool {
         let path = path.into();
@@ -362,6 +451,7 @@ impl AssetInfos {
         Self::process_handle_drop_internal(
             &mut self.infos,
             &mut self.path_to_id,
+            &mut self.content_hashes,
             &mut self.loader_dependants,
             &mut self.living_labeled_assets,
             self.watching_for_changes,
@@ -640,6 +730,7 @@ impl AssetInfos {
     fn process_handle_drop_internal(
         infos: &mut HashMap<UntypedAssetId, AssetInfo>,
         path_to_id: &mut HashMap<AssetPath<'static>, TypeIdMap<UntypedAssetId>>,
+        content_hashes: &mut HashMap<(TypeId, AssetHash), UntypedAssetId>,
         loader_dependants: &mut HashMap<AssetPath<'static>, HashSet<AssetPath<'static>>>,
         living_labeled_assets: &mut HashMap<AssetPath<'static>, HashSet<Box<str>>>,
         watching_for_changes: bool,
@@ -659,6 +750,11 @@ impl AssetInfos {
         let type_id = entry.key().type_id();
 
         let info = entry.remove();
+        if let Some(hash) = info.content_hash {
+            if content_hashes.get(&(type_id, hash)) == Some(&id) {
+                content_hashes.remove(&(type_id, hash));
+            }
+        }
         let Some(path) = &info.path else {
             return true;
         };
@@ -696,6 +792,7 @@ impl AssetInfos {
                     Self::process_handle_drop_internal(
                         &mut self.infos,
                         &mut self.path_to_id,
+                        &mut self.content_hashes,
                         &mut self.loader_dependants,
                         &mut self.living_labeled_assets,
                         self.watching_for_changes,
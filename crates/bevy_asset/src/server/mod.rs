@@ -5,28 +5,32 @@ use crate::{
     folder::LoadedFolder,
     io::{
         AssetReaderError, AssetSource, AssetSourceEvent, AssetSourceId, AssetSources,
-        ErasedAssetReader, MissingAssetSourceError, MissingProcessedAssetReaderError, Reader,
+        AssetWriterError, ErasedAssetReader, MissingAssetSourceError, MissingAssetWriterError,
+        MissingProcessedAssetReaderError, Reader, VecReader,
+    },
+    loader::{
+        AssetAlias, AssetContainer, AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset,
     },
-    loader::{AssetLoader, ErasedAssetLoader, LoadContext, LoadedAsset},
     meta::{
         loader_settings_meta_transform, AssetActionMinimal, AssetMetaDyn, AssetMetaMinimal,
         MetaTransform, Settings,
     },
     path::AssetPath,
+    saver::{AssetSaver, AssetSavers, SavedBytes},
     Asset, AssetEvent, AssetHandleProvider, AssetId, AssetLoadFailedEvent, AssetMetaCheck, Assets,
     DeserializeMetaError, ErasedLoadedAsset, Handle, LoadedUntypedAsset, UntypedAssetId,
     UntypedAssetLoadFailedEvent, UntypedHandle,
 };
 use bevy_ecs::prelude::*;
-use bevy_tasks::IoTaskPool;
+use bevy_tasks::{block_on, IoTaskPool};
 use bevy_utils::tracing::{error, info};
 use bevy_utils::{CowArc, HashSet};
 use crossbeam_channel::{Receiver, Sender};
-use futures_lite::StreamExt;
+use futures_lite::{AsyncReadExt, StreamExt};
 use info::*;
 use loaders::*;
 use parking_lot::RwLock;
-use std::{any::Any, path::PathBuf};
+use std::{any::Any, future::Future, path::PathBuf};
 use std::{any::TypeId, path::Path, sync::Arc};
 use thiserror::Error;
 
@@ -57,8 +61,11 @@ pub struct AssetServer {
 pub(crate) struct AssetServerData {
     pub(crate) infos: RwLock<AssetInfos>,
     pub(crate) loaders: Arc<RwLock<AssetLoaders>>,
+    savers: RwLock<AssetSavers>,
     asset_event_sender: Sender<InternalAssetEvent>,
     asset_event_receiver: Receiver<InternalAssetEvent>,
+    save_request_sender: Sender<SaveRequest>,
+    save_request_receiver: Receiver<SaveRequest>,
     sources: AssetSources,
     mode: AssetServerMode,
     meta_check: AssetMetaCheck,
@@ -111,6 +118,7 @@ impl AssetServer {
         watching_for_changes: bool,
     ) -> Self {
         let (asset_event_sender, asset_event_receiver) = crossbeam_channel::unbounded();
+        let (save_request_sender, save_request_receiver) = crossbeam_channel::unbounded();
         let mut infos = AssetInfos::default();
         infos.watching_for_changes = watching_for_changes;
         Self {
@@ -120,7 +128,10 @@ impl AssetServer {
                 meta_check,
                 asset_event_sender,
                 asset_event_receiver,
+                save_request_sender,
+                save_request_receiver,
                 loaders,
+                savers: Default::default(),
                 infos: RwLock::new(infos),
             }),
         }
@@ -144,6 +155,33 @@ impl AssetServer {
         self.data.loaders.write().push(loader);
     }
 
+    /// Registers a new [`AssetSaver`], used by [`AssetServer::save`] to save assets of its type.
+    pub fn register_saver<S: AssetSaver>(&self, saver: S) {
+        self.data.savers.write().push(saver);
+    }
+
+    /// Makes assets of type `A` loaded from different paths share the same value if they were
+    /// loaded from identical bytes with identical settings. Prefer
+    /// [`AssetApp::deduplicate_assets`](crate::AssetApp::deduplicate_assets) when building an app.
+    ///
+    /// See [`AssetServer::duplicate_assets`] to list the assets that were deduplicated.
+    pub fn deduplicate_assets<A: Asset>(&self) {
+        self.data
+            .infos
+            .write()
+            .deduplicated_types
+            .insert(TypeId::of::<A>(), AssetAlias::<A>::new_erased);
+    }
+
+    /// Returns the paths of the assets that share their value with another asset because they were
+    /// loaded from the same content, along with the path of that asset.
+    ///
+    /// Only assets of types registered with [`AssetServer::deduplicate_assets`] are deduplicated.
+    /// This can be used to find copy-pasted files in a project.
+    pub fn duplicate_assets(&self) -> Vec<(AssetPath<'static>, AssetPath<'static>)> {
+        self.data.infos.read().duplicates()
+    }
+
     /// Registers a new [`Asset`] type. [`Asset`] types must be registered before assets of that type can be loaded.
     pub fn register_asset<A: Asset>(&self, assets: &Assets<A>) {
         self.register_handle_provider(assets.get_handle_provider());
@@ -500,6 +538,35 @@ impl AssetServer {
             (*meta_transform)(&mut *meta);
         }
 
+        if path.label().is_none() {
+            let original = self
+                .find_duplicate(&base_handle, &*meta, &mut reader)
+                .await
+                .inspect_err(|err| {
+                    self.send_asset_event(InternalAssetEvent::Failed {
+                        id: base_handle.id(),
+                        error: err.clone(),
+                        path: path.clone_owned(),
+                    });
+                })?;
+            if let Some((original, alias)) = original {
+                if let Some(original_path) = original.path() {
+                    info!("{path} has the same content as {original_path}, so it shares its value");
+                }
+                self.send_loaded_asset(
+                    base_handle.id(),
+                    ErasedLoadedAsset {
+                        value: alias,
+                        dependencies: [original.id()].into_iter().collect(),
+                        loader_dependencies: Default::default(),
+                        labeled_assets: Default::default(),
+                        meta: None,
+                    },
+                );
+                return Ok(base_handle);
+            }
+        }
+
         match self
             .load_with_meta_loader_and_reader(&base_path, meta, &*loader, &mut *reader, true, false)
             .await
@@ -541,6 +608,46 @@ impl AssetServer {
         }
     }
 
+    /// If the type of the asset of `handle` is deduplicated, hashes its content and settings and
+    /// returns a handle to an asset that was loaded from the same content, along with the
+    /// container that makes the asset share its value.
+    ///
+    /// The content is read from `reader`, which is replaced with a reader over the read bytes.
+    async fn find_duplicate(
+        &self,
+        handle: &UntypedHandle,
+        meta: &dyn AssetMetaDyn,
+        reader: &mut Box<Reader<'_>>,
+    ) -> Result<Option<(UntypedHandle, Box<dyn AssetContainer>)>, AssetLoadError> {
+        let Some(new_alias) = self
+            .data
+            .infos
+            .read()
+            .deduplicated_types
+            .get(&handle.type_id())
+            .copied()
+        else {
+            return Ok(None);
+        };
+
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(AssetReaderError::from)?;
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(&meta.serialize());
+        hasher.update(&bytes);
+        let hash = *hasher.finalize().as_bytes();
+        *reader = Box::new(VecReader::new(bytes));
+
+        let original = self.data.infos.write().deduplicate(handle.id(), hash);
+        Ok(original.map(|original| {
+            let alias = new_alias(original.clone());
+            (original, alias)
+        }))
+    }
+
     /// Sends a load event for the given `loaded_asset` and does the same recursively for all
     /// labeled assets.
     fn send_loaded_asset(&self, id: UntypedAssetId, mut loaded_asset: ErasedLoadedAsset) {
@@ -592,6 +699,137 @@ impl AssetServer {
         self.load_asset(LoadedAsset::new_with_dependencies(asset, None))
     }
 
+    /// Saves the asset of `handle` to `path` with the [`AssetSaver`] registered for its type, and
+    /// returns a future that resolves once the asset is written.
+    ///
+    /// The asset is saved in [`Last`](bevy_app::Last), with its value at the end of the current
+    /// update. If several savers are registered for the asset type, the one whose output is loaded
+    /// by the loader of `path`'s extension is used, otherwise the last registered one. The saver
+    /// runs with its default settings, then its output is written on the [`IoTaskPool`] by the
+    /// [`AssetWriter`] of the unprocessed asset source of `path`. The asset and its meta are written
+    /// with [`AssetWriter::write_bytes_atomic`], so an interrupted save never leaves a partially
+    /// written asset behind. The meta is skipped if the [`AssetMetaCheck`] would ignore it.
+    ///
+    /// The returned future doesn't need to be polled for the asset to be saved, and errors are
+    /// also logged.
+    pub fn save<'a, A: Asset>(
+        &self,
+        handle: &Handle<A>,
+        path: impl Into<AssetPath<'a>>,
+    ) -> impl Future<Output = Result<(), SaveAssetError>> + 'static {
+        fn get_asset<A: Asset>(
+            world: &World,
+            id: UntypedAssetId,
+        ) -> Option<&(dyn Any + Send + Sync)> {
+            let asset = world
+                .get_resource::<Assets<A>>()?
+                .get(id.typed_debug_checked::<A>())?;
+            Some(asset)
+        }
+
+        let path = path.into().into_owned();
+        let (sender, mut receiver) = async_broadcast::broadcast(1);
+        self.data
+            .save_request_sender
+            .send(SaveRequest {
+                handle: handle.clone().untyped(),
+                type_name: std::any::type_name::<A>(),
+                path: path.clone(),
+                get_asset: get_asset::<A>,
+                sender,
+            })
+            .unwrap();
+        async move {
+            receiver
+                .recv()
+                .await
+                .unwrap_or(Err(SaveAssetError::Cancelled(path)))
+        }
+    }
+
+    /// Runs the saver of a [`SaveRequest`], and writes its output on the [`IoTaskPool`].
+    fn save_requested_asset(&self, world: &World, request: SaveRequest) {
+        let saved = self.run_saver(world, &request);
+        let SaveRequest { path, sender, .. } = request;
+        let write_meta = match &self.data.meta_check {
+            AssetMetaCheck::Always => true,
+            AssetMetaCheck::Paths(paths) => paths.contains(&path),
+            AssetMetaCheck::Never => false,
+        };
+
+        let server = self.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let result = match saved {
+                    Ok(bytes) => server.write_saved_asset(&path, bytes, write_meta).await,
+                    Err(error) => Err(error),
+                };
+                if let Err(error) = &result {
+                    error!("{error}");
+                }
+                // the caller may have dropped the future, in which case nobody waits for the result
+                let _ = sender.broadcast(result).await;
+            })
+            .detach();
+    }
+
+    fn run_saver(
+        &self,
+        world: &World,
+        request: &SaveRequest,
+    ) -> Result<SavedBytes, SaveAssetError> {
+        let type_id = request.handle.type_id();
+        let loader_type_name =
+            match self
+                .data
+                .loaders
+                .read()
+                .find(None, Some(type_id), None, Some(&request.path))
+            {
+                Some(MaybeAssetLoader::Ready(loader)) => Some(loader.type_name()),
+                _ => None,
+            };
+        let saver = self
+            .data
+            .savers
+            .read()
+            .find(type_id, loader_type_name)
+            .ok_or_else(|| SaveAssetError::MissingAssetSaver {
+                path: request.path.clone(),
+                asset_type: request.type_name,
+            })?;
+        let asset = (request.get_asset)(world, request.handle.id())
+            .ok_or_else(|| SaveAssetError::AssetNotLoaded(request.path.clone()))?;
+        block_on(saver.save_to_bytes(asset)).map_err(|error| SaveAssetError::AssetSaverError {
+            path: request.path.clone(),
+            error: error.into(),
+        })
+    }
+
+    async fn write_saved_asset(
+        &self,
+        path: &AssetPath<'static>,
+        bytes: SavedBytes,
+        write_meta: bool,
+    ) -> Result<(), SaveAssetError> {
+        let writer = self.get_source(path.source())?.writer()?;
+        let writer_error = |error| SaveAssetError::AssetWriterError {
+            path: path.clone(),
+            error: Arc::new(error),
+        };
+        writer
+            .write_bytes_atomic(path.path(), &bytes.asset)
+            .await
+            .map_err(writer_error)?;
+        if write_meta {
+            writer
+                .write_meta_bytes_atomic(path.path(), &bytes.meta)
+                .await
+                .map_err(writer_error)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn load_asset<A: Asset>(&self, asset: impl Into<LoadedAsset<A>>) -> Handle<A> {
         let loaded_asset: LoadedAsset<A> = asset.into();
         let erased_loaded_asset: ErasedLoadedAsset = loaded_asset.into();
@@ -1054,6 +1292,26 @@ impl AssetServer {
     }
 }
 
+/// A system that saves the assets requested with [`AssetServer::save`].
+pub fn save_requested_assets(world: &World) {
+    let Some(server) = world.get_resource::<AssetServer>() else {
+        return;
+    };
+    for request in server.data.save_request_receiver.try_iter() {
+        server.save_requested_asset(world, request);
+    }
+}
+
+/// A request to save an asset, queued by [`AssetServer::save`].
+struct SaveRequest {
+    /// Keeps the asset alive until it is saved.
+    handle: UntypedHandle,
+    type_name: &'static str,
+    path: AssetPath<'static>,
+    get_asset: fn(&World, UntypedAssetId) -> Option<&(dyn Any + Send + Sync)>,
+    sender: async_broadcast::Sender<Result<(), SaveAssetError>>,
+}
+
 /// A system that manages internal [`AssetServer`] events, such as finalizing asset loads.
 pub fn handle_internal_asset_events(world: &mut World) {
     world.resource_scope(|world, server: Mut<AssetServer>| {
@@ -1333,6 +1591,34 @@ pub struct MissingAssetLoaderForTypeIdError {
     pub type_id: TypeId,
 }
 
+/// An error that occurs when saving an asset with [`AssetServer::save`].
+#[derive(Error, Debug, Clone)]
+pub enum SaveAssetError {
+    #[error("no `AssetSaver` is registered for the asset type `{asset_type}` to save '{path}'")]
+    MissingAssetSaver {
+        path: AssetPath<'static>,
+        asset_type: &'static str,
+    },
+    #[error("the asset to save to '{0}' is not loaded")]
+    AssetNotLoaded(AssetPath<'static>),
+    #[error(transparent)]
+    MissingAssetSource(#[from] MissingAssetSourceError),
+    #[error(transparent)]
+    MissingAssetWriter(#[from] MissingAssetWriterError),
+    #[error("failed to save asset '{path}': {error}")]
+    AssetSaverError {
+        path: AssetPath<'static>,
+        error: Arc<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("failed to write asset '{path}': {error}")]
+    AssetWriterError {
+        path: AssetPath<'static>,
+        error: Arc<AssetWriterError>,
+    },
+    #[error("the asset server was dropped before '{0}' was saved")]
+    Cancelled(AssetPath<'static>),
+}
+
 fn format_missing_asset_ext(exts: &[String]) -> String {
     if !exts.is_empty() {
         format!(
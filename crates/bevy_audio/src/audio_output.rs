@@ -26,7 +26,7 @@ use crate::AudioSink;
 /// However, repeatedly inserting this resource into the app will **leak more memory**.
 #[derive(Resource)]
 pub(crate) struct AudioOutput {
-    stream_handle: Option<OutputStreamHandle>,
+    pub(crate) stream_handle: Option<OutputStreamHandle>,
 }
 
 impl Default for AudioOutput {
@@ -31,6 +31,7 @@ mod audio;
 mod audio_output;
 mod audio_source;
 mod pitch;
+mod sfx;
 mod sinks;
 
 #[allow(missing_docs)]
@@ -38,13 +39,15 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         AudioBundle, AudioSink, AudioSinkPlayback, AudioSource, AudioSourceBundle, Decodable,
-        GlobalVolume, Pitch, PitchBundle, PlaybackSettings, SpatialAudioSink, SpatialListener,
+        GlobalVolume, Pitch, PitchBundle, PlaySfxExt, PlaybackSettings, SfxParams, SfxPool,
+        SpatialAudioSink, SpatialListener,
     };
 }
 
 pub use audio::*;
 pub use audio_source::*;
 pub use pitch::*;
+pub use sfx::*;
 
 pub use rodio::cpal::Sample as CpalSample;
 pub use rodio::source::Source;
@@ -94,7 +97,8 @@ impl Plugin for AudioPlugin {
                 PostUpdate,
                 (update_emitter_positions, update_listener_positions).in_set(AudioPlaySet),
             )
-            .init_resource::<AudioOutput>();
+            .init_resource::<AudioOutput>()
+            .init_resource::<SfxPool>();
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         {
@@ -112,10 +116,12 @@ impl AddAudioSource for App {
         T: Decodable + Asset,
         f32: rodio::cpal::FromSample<T::DecoderItem>,
     {
-        self.init_asset::<T>().add_systems(
-            PostUpdate,
-            (play_queued_audio_system::<T>, cleanup_finished_audio::<T>).in_set(AudioPlaySet),
-        );
+        self.init_asset::<T>()
+            .add_systems(
+                PostUpdate,
+                (play_queued_audio_system::<T>, cleanup_finished_audio::<T>).in_set(AudioPlaySet),
+            )
+            .add_systems(PostUpdate, sfx::play_queued_sfx::<T>);
         self
     }
 }
@@ -0,0 +1,515 @@
+use crate::{audio_output::AudioOutput, Decodable, GlobalVolume, Volume};
+use bevy_asset::{
+    Asset, AssetEvent, AssetServer, Assets, Handle, LoadState, UntypedAssetId, UntypedHandle,
+};
+use bevy_ecs::prelude::*;
+use bevy_tasks::AsyncComputeTaskPool;
+use bevy_utils::{tracing::warn, HashMap};
+use crossbeam_channel::{Receiver, Sender};
+use rodio::{OutputStreamHandle, Sink, Source};
+use std::{any::TypeId, sync::Arc, time::Duration};
+
+/// Sound effects longer than this are truncated when they are decoded.
+const MAX_SFX_DURATION_SECS: usize = 30;
+
+/// Settings used to play a sound effect with [`SfxPool::play`] or [`PlaySfxExt::play_sfx`].
+#[derive(Clone, Copy, Debug)]
+pub struct SfxParams {
+    /// Volume to play at.
+    pub volume: Volume,
+    /// Speed to play at.
+    pub speed: f32,
+    /// The maximum number of instances of this sound that can play at the same time.
+    ///
+    /// When the limit is reached, the oldest instance is stopped to play the new one.
+    pub max_instances: Option<usize>,
+}
+
+impl Default for SfxParams {
+    fn default() -> Self {
+        Self {
+            volume: Volume::default(),
+            speed: 1.0,
+            max_instances: None,
+        }
+    }
+}
+
+impl SfxParams {
+    /// Returns these params with the given volume.
+    pub const fn with_volume(mut self, volume: Volume) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Returns these params with the given speed.
+    pub const fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Returns these params with the given maximum number of instances.
+    pub const fn with_max_instances(mut self, max_instances: usize) -> Self {
+        self.max_instances = Some(max_instances);
+        self
+    }
+}
+
+/// A pool of voices to play short, frequent sound effects with low latency.
+///
+/// Unlike an [`AudioBundle`](crate::AudioBundle), a sound effect played through the pool doesn't
+/// spawn an entity, and its samples are decoded once in the background and shared by every time it
+/// is played. This suits rapid-fire sounds like gunshots or footsteps, but not music or long
+/// ambiences, as the whole sound is kept decoded in memory. Sounds longer than 30 seconds are
+/// truncated.
+///
+/// At most [`max_voices`](Self::max_voices) sounds play at the same time. When the limit is
+/// reached, the oldest playing sound is stopped to play the new one.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_asset::prelude::*;
+/// # use bevy_audio::{AudioSource, PlaySfxExt, SfxParams};
+/// fn shoot(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     commands.play_sfx(
+///         asset_server.load::<AudioSource>("sounds/shot.ogg"),
+///         SfxParams::default().with_max_instances(4),
+///     );
+/// }
+/// ```
+#[derive(Resource)]
+pub struct SfxPool {
+    max_voices: usize,
+    voices: Vec<SfxVoice>,
+    queued: Vec<(UntypedHandle, SfxParams)>,
+    preloaded: HashMap<UntypedAssetId, UntypedHandle>,
+    decoded: DecodedSfx,
+}
+
+struct SfxVoice {
+    source: UntypedAssetId,
+    // dropping the sink stops the sound
+    sink: Sink,
+}
+
+impl Default for SfxPool {
+    fn default() -> Self {
+        Self::new(32)
+    }
+}
+
+impl SfxPool {
+    /// Creates a pool that plays at most `max_voices` sounds at the same time.
+    pub fn new(max_voices: usize) -> Self {
+        Self {
+            max_voices,
+            voices: Vec::new(),
+            queued: Vec::new(),
+            preloaded: HashMap::default(),
+            decoded: DecodedSfx::default(),
+        }
+    }
+
+    /// Returns the maximum number of sounds that play at the same time.
+    pub fn max_voices(&self) -> usize {
+        self.max_voices
+    }
+
+    /// Sets the maximum number of sounds that play at the same time, stopping the oldest ones
+    /// if more are playing.
+    pub fn set_max_voices(&mut self, max_voices: usize) {
+        self.max_voices = max_voices;
+        let excess = self.voices.len().saturating_sub(max_voices);
+        self.voices.drain(..excess);
+    }
+
+    /// Returns the number of sounds playing.
+    pub fn active_voices(&self) -> usize {
+        self.voices
+            .iter()
+            .filter(|voice| !voice.sink.empty())
+            .count()
+    }
+
+    /// Plays `source` once with the given `params`.
+    ///
+    /// The sound starts during the next [`PostUpdate`](bevy_app::PostUpdate), or once it is
+    /// loaded and decoded.
+    pub fn play<T: Asset>(&mut self, source: Handle<T>, params: SfxParams) {
+        self.queued.push((source.untyped(), params));
+    }
+
+    /// Decodes `source` in the background as soon as it is loaded and keeps it in memory, so that
+    /// it plays without delay the first time.
+    pub fn preload<T: Asset>(&mut self, source: Handle<T>) {
+        let source = source.untyped();
+        self.preloaded.insert(source.id(), source);
+    }
+
+    /// Releases the decoded samples of `source`, and the handle kept by [`preload`](Self::preload).
+    pub fn unload(&mut self, source: impl Into<UntypedAssetId>) {
+        let source = source.into();
+        self.preloaded.remove(&source);
+        self.decoded.remove(source);
+    }
+
+    /// Returns `true` if `source` is decoded and plays without delay.
+    pub fn is_decoded(&self, source: impl Into<UntypedAssetId>) -> bool {
+        self.decoded.samples.contains_key(&source.into())
+    }
+
+    /// Stops every playing sound.
+    pub fn stop_all(&mut self) {
+        self.voices.clear();
+    }
+
+    fn start_voice(
+        &mut self,
+        stream_handle: &OutputStreamHandle,
+        source: UntypedAssetId,
+        samples: SfxSamples,
+        params: SfxParams,
+        global_volume: Volume,
+    ) {
+        if !self.reserve_voice(source, params.max_instances) {
+            return;
+        }
+        let sink = match Sink::try_new(stream_handle) {
+            Ok(sink) => sink,
+            Err(err) => {
+                warn!("Error creating sink: {err:?}");
+                return;
+            }
+        };
+        self.push_voice(source, sink, samples, params, global_volume);
+    }
+
+    /// Stops the sounds that must stop for a new instance of `source` to play, and returns
+    /// whether it can play.
+    fn reserve_voice(&mut self, source: UntypedAssetId, max_instances: Option<usize>) -> bool {
+        if let Some(max_instances) = max_instances {
+            if max_instances == 0 {
+                return false;
+            }
+            let mut instances = self
+                .voices
+                .iter()
+                .filter(|voice| voice.source == source)
+                .count();
+            while instances >= max_instances {
+                let oldest = self
+                    .voices
+                    .iter()
+                    .position(|voice| voice.source == source)
+                    .unwrap();
+                self.voices.remove(oldest);
+                instances -= 1;
+            }
+        }
+        if self.max_voices == 0 {
+            return false;
+        }
+        if self.voices.len() >= self.max_voices {
+            let excess = self.voices.len() + 1 - self.max_voices;
+            self.voices.drain(..excess);
+        }
+        true
+    }
+
+    fn push_voice(
+        &mut self,
+        source: UntypedAssetId,
+        sink: Sink,
+        samples: SfxSamples,
+        params: SfxParams,
+        global_volume: Volume,
+    ) {
+        sink.set_speed(params.speed);
+        sink.set_volume(params.volume.0 * global_volume.0);
+        sink.append(samples);
+        self.voices.push(SfxVoice { source, sink });
+    }
+}
+
+/// Extension trait to play sound effects through the [`SfxPool`] from [`Commands`].
+pub trait PlaySfxExt {
+    /// Plays `source` once through the [`SfxPool`] with the given `params`.
+    fn play_sfx<T: Asset>(&mut self, source: Handle<T>, params: SfxParams);
+}
+
+impl PlaySfxExt for Commands<'_, '_> {
+    fn play_sfx<T: Asset>(&mut self, source: Handle<T>, params: SfxParams) {
+        self.add(move |world: &mut World| {
+            if let Some(mut pool) = world.get_resource_mut::<SfxPool>() {
+                pool.play(source, params);
+            }
+        });
+    }
+}
+
+/// The decoded sound effects, and the ones being decoded in the background.
+struct DecodedSfx {
+    samples: HashMap<UntypedAssetId, SfxSamples>,
+    // the task decoding each sound, to ignore the results of outdated tasks
+    pending: HashMap<UntypedAssetId, u64>,
+    next_task: u64,
+    sender: Sender<(UntypedAssetId, u64, SfxSamples)>,
+    receiver: Receiver<(UntypedAssetId, u64, SfxSamples)>,
+}
+
+impl Default for DecodedSfx {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        Self {
+            samples: HashMap::default(),
+            pending: HashMap::default(),
+            next_task: 0,
+            sender,
+            receiver,
+        }
+    }
+}
+
+impl DecodedSfx {
+    /// Starts decoding `source` on the [`AsyncComputeTaskPool`], unless it is decoded or being
+    /// decoded already.
+    fn start<T: Decodable>(&mut self, id: UntypedAssetId, source: &T)
+    where
+        f32: rodio::cpal::FromSample<T::DecoderItem>,
+    {
+        if self.samples.contains_key(&id) || self.pending.contains_key(&id) {
+            return;
+        }
+        let task = self.next_task;
+        self.next_task += 1;
+        self.pending.insert(id, task);
+        let decoder = source.decoder();
+        let sender = self.sender.clone();
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                // the pool may have been dropped in the meantime
+                let _ = sender.send((id, task, SfxSamples::decode::<T>(decoder)));
+            })
+            .detach();
+    }
+
+    /// Stores the samples decoded by the tasks that finished.
+    fn receive(&mut self) {
+        for (id, task, samples) in self.receiver.try_iter() {
+            if self.pending.get(&id) == Some(&task) {
+                self.pending.remove(&id);
+                self.samples.insert(id, samples);
+            }
+        }
+    }
+
+    fn remove(&mut self, id: UntypedAssetId) {
+        self.samples.remove(&id);
+        self.pending.remove(&id);
+    }
+}
+
+/// The decoded samples of a sound effect, shared by all the voices playing it.
+#[derive(Clone)]
+struct SfxSamples {
+    samples: Arc<[f32]>,
+    channels: u16,
+    sample_rate: u32,
+    position: usize,
+}
+
+impl SfxSamples {
+    fn decode<T: Decodable>(decoder: T::Decoder) -> Self
+    where
+        f32: rodio::cpal::FromSample<T::DecoderItem>,
+    {
+        let channels = decoder.channels().max(1);
+        let sample_rate = decoder.sample_rate().max(1);
+        let max_samples = MAX_SFX_DURATION_SECS * sample_rate as usize * channels as usize;
+        let mut samples: Vec<f32> = decoder
+            .convert_samples::<f32>()
+            .take(max_samples + 1)
+            .collect();
+        if samples.len() > max_samples {
+            warn!(
+                "Sound effect of type {} is longer than {MAX_SFX_DURATION_SECS} seconds and was truncated.",
+                std::any::type_name::<T>()
+            );
+            samples.truncate(max_samples);
+        }
+        Self {
+            samples: samples.into(),
+            channels,
+            sample_rate,
+            position: 0,
+        }
+    }
+}
+
+impl Iterator for SfxSamples {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.samples.get(self.position).copied();
+        self.position += 1;
+        sample
+    }
+}
+
+impl Source for SfxSamples {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some(self.samples.len().saturating_sub(self.position))
+    }
+
+    fn channels(&self) -> u16 {
+        self.channels
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        let frames = self.samples.len() / self.channels as usize;
+        Some(Duration::from_secs_f64(
+            frames as f64 / self.sample_rate as f64,
+        ))
+    }
+}
+
+/// Decodes the sound effects of type `T` and plays the queued ones through the [`SfxPool`].
+pub(crate) fn play_queued_sfx<T: Decodable + Asset>(
+    audio_output: Res<AudioOutput>,
+    sources: Res<Assets<T>>,
+    asset_server: Res<AssetServer>,
+    global_volume: Res<GlobalVolume>,
+    mut events: EventReader<AssetEvent<T>>,
+    mut pool: ResMut<SfxPool>,
+) where
+    f32: rodio::cpal::FromSample<T::DecoderItem>,
+{
+    let pool = &mut *pool;
+    for event in events.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            pool.decoded.remove(id.untyped());
+        }
+    }
+    pool.voices.retain(|voice| !voice.sink.empty());
+
+    let stream_handle = audio_output.stream_handle.as_ref();
+    if stream_handle.is_none() {
+        // audio output unavailable; cannot play sound
+        pool.queued
+            .retain(|(source, _)| source.type_id() != TypeId::of::<T>());
+    }
+
+    let loaded = pool
+        .preloaded
+        .keys()
+        .copied()
+        .chain(pool.queued.iter().map(|(source, _)| source.id()))
+        .filter(|id| id.type_id() == TypeId::of::<T>())
+        .filter_map(|id| Some((id, sources.get(id.typed_debug_checked::<T>())?)));
+    for (id, source) in loaded {
+        pool.decoded.start(id, source);
+    }
+    pool.decoded.receive();
+
+    let Some(stream_handle) = stream_handle else {
+        return;
+    };
+
+    let mut waiting = Vec::new();
+    for (source, params) in std::mem::take(&mut pool.queued) {
+        if source.type_id() != TypeId::of::<T>() {
+            waiting.push((source, params));
+            continue;
+        }
+        let id = source.id();
+        if let Some(samples) = pool.decoded.samples.get(&id).cloned() {
+            pool.start_voice(stream_handle, id, samples, params, global_volume.volume);
+        } else if pool.decoded.pending.contains_key(&id)
+            || !matches!(
+                asset_server.get_load_state(id),
+                None | Some(LoadState::Failed(_))
+            )
+        {
+            // the sound is still loading or decoding
+            waiting.push((source, params));
+        }
+    }
+    pool.queued = waiting;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AudioSource;
+
+    fn source(index: u128) -> UntypedAssetId {
+        Handle::<AudioSource>::weak_from_u128(index).id().untyped()
+    }
+
+    // plays through a sink that isn't attached to an output, so the sound never finishes
+    fn play(pool: &mut SfxPool, source: UntypedAssetId, params: SfxParams) {
+        if pool.reserve_voice(source, params.max_instances) {
+            let samples = SfxSamples {
+                samples: vec![0.0; 64].into(),
+                channels: 1,
+                sample_rate: 44_100,
+                position: 0,
+            };
+            let (sink, _output) = Sink::new_idle();
+            pool.push_voice(source, sink, samples, params, Volume::default());
+        }
+    }
+
+    fn playing(pool: &SfxPool) -> Vec<(UntypedAssetId, f32)> {
+        pool.voices
+            .iter()
+            .map(|voice| (voice.source, voice.sink.speed()))
+            .collect()
+    }
+
+    #[test]
+    fn max_instances_stops_oldest_instance() {
+        let mut pool = SfxPool::new(8);
+        let params = SfxParams::default().with_max_instances(2);
+        play(&mut pool, source(0), params.with_speed(1.0));
+        play(&mut pool, source(1), params);
+        play(&mut pool, source(0), params.with_speed(2.0));
+        play(&mut pool, source(0), params.with_speed(3.0));
+
+        assert_eq!(
+            playing(&pool),
+            vec![(source(1), 1.0), (source(0), 2.0), (source(0), 3.0)]
+        );
+        assert_eq!(pool.active_voices(), 3);
+    }
+
+    #[test]
+    fn zero_max_instances_plays_nothing() {
+        let mut pool = SfxPool::new(8);
+        let params = SfxParams::default().with_max_instances(0);
+        play(&mut pool, source(0), params);
+
+        assert!(playing(&pool).is_empty());
+    }
+
+    #[test]
+    fn max_voices_steals_oldest_voice() {
+        let mut pool = SfxPool::new(2);
+        play(&mut pool, source(0), SfxParams::default());
+        play(&mut pool, source(1), SfxParams::default());
+        play(&mut pool, source(2), SfxParams::default());
+
+        assert_eq!(playing(&pool), vec![(source(1), 1.0), (source(2), 1.0)]);
+
+        pool.set_max_voices(1);
+        assert_eq!(playing(&pool), vec![(source(2), 1.0)]);
+
+        pool.set_max_voices(0);
+        play(&mut pool, source(0), SfxParams::default());
+        assert!(playing(&pool).is_empty());
+    }
+}
@@ -0,0 +1,355 @@
+use crate::{Color, HexColorError, Hue, Mix, Oklaba, Oklcha, Srgba};
+use bevy_reflect::prelude::*;
+use thiserror::Error;
+
+/// A color in a [`ColorPalette`], with its name.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct Swatch {
+    /// The name of the swatch, unique within its palette.
+    pub name: String,
+    /// The color of the swatch.
+    pub color: Color,
+}
+
+/// An ordered set of named colors.
+///
+/// Palettes can be parsed from [GIMP palettes](ColorPalette::from_gpl) (`.gpl`),
+/// [JASC palettes](ColorPalette::from_jasc_pal) (`.pal`) and [lists of hex colors](ColorPalette::from_hex)
+/// (`.hex`), or generated from [harmonies](ColorPalette::harmony) and [ramps](ColorPalette::ramp).
+///
+/// With the `bevy_asset` feature, palettes are assets loaded by the `ColorPalettePlugin`, and
+/// their swatches can be referenced with a `PaletteColor`.
+///
+/// ```
+/// # use bevy_color::{palettes::basic, Color, ColorPalette};
+/// let palette = ColorPalette::from_gpl(
+///     "GIMP Palette
+/// Name: Traffic
+/// 255   0   0 Stop
+/// 255 165   0 Slow
+///   0 255   0 Go",
+/// )
+/// .unwrap();
+/// assert_eq!(palette.name.as_deref(), Some("Traffic"));
+/// assert_eq!(palette.get("Stop"), Some(Color::from(basic::RED)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(PartialEq, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+#[cfg_attr(feature = "bevy_asset", derive(bevy_asset::Asset))]
+pub struct ColorPalette {
+    /// The name of the palette, if any.
+    pub name: Option<String>,
+    /// The swatches of the palette, in order.
+    pub swatches: Vec<Swatch>,
+}
+
+impl ColorPalette {
+    /// Creates a palette from `colors`, naming each swatch after its index.
+    pub fn from_colors(colors: impl IntoIterator<Item = Color>) -> Self {
+        let mut palette = Self::default();
+        for color in colors {
+            palette.push(palette.len().to_string(), color);
+        }
+        palette
+    }
+
+    /// Adds a swatch at the end of the palette, or replaces the color of the swatch named `name`.
+    pub fn push(&mut self, name: impl Into<String>, color: Color) {
+        let name = name.into();
+        match self.swatches.iter_mut().find(|swatch| swatch.name == name) {
+            Some(swatch) => swatch.color = color,
+            None => self.swatches.push(Swatch { name, color }),
+        }
+    }
+
+    /// Returns this palette with a swatch added or replaced, see [`ColorPalette::push`].
+    pub fn with(mut self, name: impl Into<String>, color: Color) -> Self {
+        self.push(name, color);
+        self
+    }
+
+    /// Returns the color of the swatch named `name`.
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.swatches
+            .iter()
+            .find(|swatch| swatch.name == name)
+            .map(|swatch| swatch.color)
+    }
+
+    /// Returns the color of the swatch at `index`.
+    pub fn get_index(&self, index: usize) -> Option<Color> {
+        self.swatches.get(index).map(|swatch| swatch.color)
+    }
+
+    /// Iterates over the colors of the palette, in order.
+    pub fn colors(&self) -> impl Iterator<Item = Color> + '_ {
+        self.swatches.iter().map(|swatch| swatch.color)
+    }
+
+    /// Returns the number of swatches.
+    pub fn len(&self) -> usize {
+        self.swatches.len()
+    }
+
+    /// Returns `true` if the palette has no swatches.
+    pub fn is_empty(&self) -> bool {
+        self.swatches.is_empty()
+    }
+
+    /// Creates a palette of the colors in `harmony` with `base`.
+    pub fn harmony(base: Color, harmony: ColorHarmony) -> Self {
+        Self::from_colors(harmony.colors(base))
+    }
+
+    /// Creates a palette of `steps` colors evenly interpolated from `start` to `end`, in the
+    /// perceptually uniform [`Oklaba`] space.
+    pub fn ramp(start: Color, end: Color, steps: usize) -> Self {
+        let (start, end) = (Oklaba::from(start), Oklaba::from(end));
+        Self::from_colors((0..steps).map(|step| {
+            let factor = step as f32 / (steps - 1).max(1) as f32;
+            start.mix(&end, factor).into()
+        }))
+    }
+
+    /// Parses a palette, detecting its format from its header: [GIMP](ColorPalette::from_gpl),
+    /// [JASC](ColorPalette::from_jasc_pal) or else [hex](ColorPalette::from_hex).
+    pub fn parse(text: &str) -> Result<Self, PaletteParseError> {
+        let header = text.trim_start().lines().next().unwrap_or("").trim();
+        match header {
+            "GIMP Palette" => Self::from_gpl(text),
+            "JASC-PAL" => Self::from_jasc_pal(text),
+            _ => Self::from_hex(text),
+        }
+    }
+
+    /// Parses a GIMP palette (`.gpl`).
+    ///
+    /// Swatches without a name are named after their index.
+    pub fn from_gpl(text: &str) -> Result<Self, PaletteParseError> {
+        let mut lines = lines(text);
+        expect_header(&mut lines, "GIMP Palette")?;
+        let mut palette = Self::default();
+        for (line_number, line) in lines {
+            if let Some(name) = line.strip_prefix("Name:") {
+                palette.name = Some(name.trim().to_owned());
+                continue;
+            }
+            if line.starts_with("Columns:") || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let mut channel = || {
+                parts
+                    .next()
+                    .and_then(|channel| channel.parse().ok())
+                    .ok_or(PaletteParseError::InvalidLine(line_number))
+            };
+            let color = Srgba::rgb_u8(channel()?, channel()?, channel()?);
+            let name = parts.collect::<Vec<_>>().join(" ");
+            let name = if name.is_empty() {
+                palette.len().to_string()
+            } else {
+                name
+            };
+            palette.push(name, color.into());
+        }
+        Ok(palette)
+    }
+
+    /// Parses a JASC palette (`.pal`), as exported by Paint Shop Pro and many pixel art editors.
+    ///
+    /// Swatches are named after their index.
+    pub fn from_jasc_pal(text: &str) -> Result<Self, PaletteParseError> {
+        let mut lines = lines(text);
+        expect_header(&mut lines, "JASC-PAL")?;
+        let mut palette = Self::default();
+        // skip the version and the color count
+        for (line_number, line) in lines.skip(2) {
+            let channels = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_| PaletteParseError::InvalidLine(line_number))?;
+            let [red, green, blue] = channels[..] else {
+                return Err(PaletteParseError::InvalidLine(line_number));
+            };
+            palette.push(
+                palette.len().to_string(),
+                Srgba::rgb_u8(red, green, blue).into(),
+            );
+        }
+        Ok(palette)
+    }
+
+    /// Parses a list of hex colors (`.hex`), one per line, as exported by Lospec.
+    ///
+    /// Swatches are named after their index.
+    pub fn from_hex(text: &str) -> Result<Self, PaletteParseError> {
+        let mut palette = Self::default();
+        for (line_number, line) in lines(text) {
+            let color = Srgba::hex(line)
+                .map_err(|error| PaletteParseError::InvalidHex(line_number, error))?;
+            palette.push(palette.len().to_string(), color.into());
+        }
+        Ok(palette)
+    }
+}
+
+/// Iterates over the non-empty lines of `text`, with their line number.
+fn lines(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+}
+
+fn expect_header<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    header: &'static str,
+) -> Result<(), PaletteParseError> {
+    match lines.next() {
+        Some((_, line)) if line == header => Ok(()),
+        _ => Err(PaletteParseError::MissingHeader(header)),
+    }
+}
+
+/// Error returned if a [`ColorPalette`] could not be parsed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PaletteParseError {
+    /// The palette doesn't start with the header of its format.
+    #[error("Expected the palette to start with `{0}`")]
+    MissingHeader(&'static str),
+    /// A line isn't a valid color.
+    #[error("Invalid color at line {0}")]
+    InvalidLine(usize),
+    /// A line isn't a valid hex color.
+    #[error("Invalid hex color at line {0}: {1}")]
+    InvalidHex(usize, HexColorError),
+}
+
+/// A set of colors with hues evenly placed around the color wheel, that look harmonious together.
+///
+/// Hues are rotated in the perceptually uniform [`Oklcha`] space, so that the colors of a harmony
+/// keep the same perceived lightness and chroma as the base color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum ColorHarmony {
+    /// The base color and the opposite hue.
+    Complementary,
+    /// The base color and its two neighbors, 30° apart.
+    Analogous,
+    /// Three hues, 120° apart.
+    Triadic,
+    /// The base color and the two neighbors of its complement, 150° and 210° away.
+    SplitComplementary,
+    /// Two pairs of complementary hues, 60° apart.
+    Tetradic,
+    /// Four hues, 90° apart.
+    Square,
+}
+
+impl ColorHarmony {
+    /// Returns the hue rotation of each color of the harmony from the base color, in degrees.
+    pub fn hue_offsets(self) -> &'static [f32] {
+        match self {
+            ColorHarmony::Complementary => &[0.0, 180.0],
+            ColorHarmony::Analogous => &[0.0, -30.0, 30.0],
+            ColorHarmony::Triadic => &[0.0, 120.0, 240.0],
+            ColorHarmony::SplitComplementary => &[0.0, 150.0, 210.0],
+            ColorHarmony::Tetradic => &[0.0, 60.0, 180.0, 240.0],
+            ColorHarmony::Square => &[0.0, 90.0, 180.0, 270.0],
+        }
+    }
+
+    /// Returns the colors of the harmony with `base`, starting with `base` itself.
+    pub fn colors(self, base: Color) -> Vec<Color> {
+        let oklcha = Oklcha::from(base);
+        self.hue_offsets()
+            .iter()
+            .map(|&offset| {
+                if offset == 0.0 {
+                    base
+                } else {
+                    oklcha.rotate_hue(offset).into()
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{palettes::basic, testing::assert_approx_eq};
+
+    #[test]
+    fn parse_formats() {
+        let gpl = "GIMP Palette\nName: Test\nColumns: 2\n#\n255 0 0\tRed One\n  0 0 255\n";
+        let palette = ColorPalette::parse(gpl).unwrap();
+        assert_eq!(palette.name.as_deref(), Some("Test"));
+        assert_eq!(palette.get("Red One"), Some(basic::RED.into()));
+        assert_eq!(palette.get("1"), Some(basic::BLUE.into()));
+
+        let pal = "JASC-PAL\n0100\n2\n0 255 0\n255 255 255\n";
+        let palette = ColorPalette::parse(pal).unwrap();
+        assert_eq!(
+            palette.colors().collect::<Vec<_>>(),
+            [Color::from(basic::LIME), Color::from(basic::WHITE)]
+        );
+
+        let hex = "ff0000\n#00ff00\n";
+        let palette = ColorPalette::parse(hex).unwrap();
+        assert_eq!(palette.get_index(1), Some(basic::LIME.into()));
+
+        assert_eq!(
+            ColorPalette::from_gpl("255 0 0"),
+            Err(PaletteParseError::MissingHeader("GIMP Palette"))
+        );
+        assert_eq!(
+            ColorPalette::from_jasc_pal("JASC-PAL\n0100\n1\n0 256 0"),
+            Err(PaletteParseError::InvalidLine(4))
+        );
+        assert!(matches!(
+            ColorPalette::from_hex("ff0000\nnope"),
+            Err(PaletteParseError::InvalidHex(2, _))
+        ));
+    }
+
+    #[test]
+    fn harmonies_and_ramps() {
+        let base = Color::from(basic::RED);
+        let triadic = ColorPalette::harmony(base, ColorHarmony::Triadic);
+        assert_eq!(triadic.len(), 3);
+        assert_eq!(triadic.get_index(0), Some(base));
+        let base_hue = Oklcha::from(base).hue;
+        for (color, offset) in triadic.colors().zip([0.0, 120.0, 240.0]) {
+            let color = Oklcha::from(color);
+            assert_approx_eq!(color.hue, (base_hue + offset) % 360.0, 0.01);
+        }
+
+        let ramp = ColorPalette::ramp(basic::BLACK.into(), basic::WHITE.into(), 5);
+        assert_eq!(ramp.len(), 5);
+        assert_eq!(
+            ramp.get("0"),
+            Some(Oklaba::from(Color::from(basic::BLACK)).into())
+        );
+        let middle = Oklaba::from(ramp.get_index(2).unwrap());
+        assert_approx_eq!(middle.lightness, 0.5, 0.001);
+    }
+}
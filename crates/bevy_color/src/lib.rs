@@ -93,6 +93,7 @@
 mod color;
 pub mod color_difference;
 mod color_ops;
+mod color_palette;
 mod color_range;
 mod hsla;
 mod hsva;
@@ -102,6 +103,8 @@ mod lcha;
 mod linear_rgba;
 mod oklaba;
 mod oklcha;
+#[cfg(feature = "bevy_asset")]
+mod palette_asset;
 pub mod palettes;
 mod srgba;
 #[cfg(test)]
@@ -128,6 +131,7 @@ pub mod prelude {
 
 pub use color::*;
 pub use color_ops::*;
+pub use color_palette::*;
 pub use color_range::*;
 pub use hsla::*;
 pub use hsva::*;
@@ -137,6 +141,8 @@ pub use lcha::*;
 pub use linear_rgba::*;
 pub use oklaba::*;
 pub use oklcha::*;
+#[cfg(feature = "bevy_asset")]
+pub use palette_asset::*;
 pub use srgba::*;
 pub use xyza::*;
 
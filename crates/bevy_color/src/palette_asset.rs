@@ -0,0 +1,122 @@
+use crate::{Color, ColorPalette, PaletteParseError, Swatch};
+use bevy_app::{App, Plugin};
+use bevy_asset::{io::Reader, AssetApp, AssetLoader, Assets, AsyncReadExt, Handle, LoadContext};
+use bevy_reflect::prelude::*;
+use thiserror::Error;
+
+/// Adds support for loading [`ColorPalette`] assets, and registers the palette types for
+/// reflection.
+///
+/// Palettes are reloaded when their file changes if the `file_watcher` feature of `bevy_asset` is
+/// enabled. Code referencing palette entries with a [`PaletteColor`] should resolve them again on
+/// [`AssetEvent::Modified`](bevy_asset::AssetEvent::Modified) events.
+#[derive(Default)]
+pub struct ColorPalettePlugin;
+
+impl Plugin for ColorPalettePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<ColorPalette>()
+            .init_asset_loader::<ColorPaletteLoader>()
+            .register_asset_reflect::<ColorPalette>()
+            .register_type::<Swatch>()
+            .register_type::<PaletteColor>();
+    }
+}
+
+/// Loads [`ColorPalette`]s from GIMP (`.gpl`), JASC (`.pal`) and hex (`.hex`) palette files.
+///
+/// The format is detected from the contents of the file, see [`ColorPalette::parse`].
+#[derive(Default)]
+pub struct ColorPaletteLoader;
+
+/// Possible errors that can be produced by [`ColorPaletteLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum ColorPaletteLoaderError {
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The file isn't valid UTF-8.
+    #[error(transparent)]
+    Utf8(#[from] std::string::FromUtf8Error),
+    /// The file isn't a valid palette.
+    #[error(transparent)]
+    Parse(#[from] PaletteParseError),
+}
+
+impl AssetLoader for ColorPaletteLoader {
+    type Asset = ColorPalette;
+    type Settings = ();
+    type Error = ColorPaletteLoaderError;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<ColorPalette, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let mut palette = ColorPalette::parse(&String::from_utf8(bytes)?)?;
+        if palette.name.is_none() {
+            palette.name = load_context
+                .path()
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned());
+        }
+        Ok(palette)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["gpl", "pal", "hex"]
+    }
+}
+
+/// A reference to a swatch of a [`ColorPalette`] asset, so that themes and materials can use
+/// palette entries that follow changes to the palette.
+#[derive(Debug, Clone, PartialEq, Reflect)]
+pub struct PaletteColor {
+    /// The palette containing the swatch.
+    pub palette: Handle<ColorPalette>,
+    /// The name of the swatch.
+    pub swatch: String,
+}
+
+impl PaletteColor {
+    /// Creates a reference to the swatch named `swatch` of `palette`.
+    pub fn new(palette: Handle<ColorPalette>, swatch: impl Into<String>) -> Self {
+        Self {
+            palette,
+            swatch: swatch.into(),
+        }
+    }
+
+    /// Returns the current color of the swatch, if the palette is loaded and has the swatch.
+    pub fn resolve(&self, palettes: &Assets<ColorPalette>) -> Option<Color> {
+        palettes.get(&self.palette)?.get(&self.swatch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::palettes::basic;
+
+    #[test]
+    fn resolve_palette_color() {
+        let mut app = App::new();
+        app.add_plugins((bevy_asset::AssetPlugin::default(), ColorPalettePlugin));
+
+        let mut palettes = app.world_mut().resource_mut::<Assets<ColorPalette>>();
+        let handle = palettes.add(ColorPalette::default().with("accent", basic::RED.into()));
+        let accent = PaletteColor::new(handle.clone(), "accent");
+        let missing = PaletteColor::new(handle.clone(), "missing");
+        assert_eq!(accent.resolve(&palettes), Some(basic::RED.into()));
+        assert_eq!(missing.resolve(&palettes), None);
+
+        palettes
+            .get_mut(&handle)
+            .unwrap()
+            .push("accent", basic::BLUE.into());
+        assert_eq!(accent.resolve(&palettes), Some(basic::BLUE.into()));
+    }
+}
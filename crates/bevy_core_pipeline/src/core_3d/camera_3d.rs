@@ -3,7 +3,7 @@ use crate::{
     tonemapping::{DebandDither, Tonemapping},
 };
 use bevy_ecs::prelude::*;
-use bevy_reflect::{Reflect, ReflectDeserialize, ReflectSerialize};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, ReflectDeserialize, ReflectSerialize};
 use bevy_render::{
     camera::{Camera, CameraMainTextureUsages, CameraRenderGraph, Exposure, Projection},
     extract_component::ExtractComponent,
@@ -104,6 +104,21 @@ impl From<Camera3dDepthLoadOp> for LoadOp<f32> {
     }
 }
 
+/// Adds a stencil buffer to the depth texture of a [`Camera3d`], for materials using a
+/// `MaterialStencil` to mask where they're drawn, such as portals or silhouette outlines.
+///
+/// The depth texture then uses [`CORE_3D_DEPTH_STENCIL_FORMAT`](crate::core_3d::CORE_3D_DEPTH_STENCIL_FORMAT),
+/// which requires [`WgpuFeatures::DEPTH32FLOAT_STENCIL8`](bevy_render::settings::WgpuFeatures::DEPTH32FLOAT_STENCIL8).
+/// Without it, this component is ignored with a warning.
+///
+/// Meshlet meshes don't support cameras with a stencil buffer yet.
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct CameraStencil {
+    /// The value the stencil buffer is cleared with, whenever the depth buffer is cleared.
+    pub clear_value: u32,
+}
+
 /// The quality of the screen space transmission blur effect, applied to whatever's “behind” transmissive
 /// objects when their `roughness` is greater than `0.0`.
 ///
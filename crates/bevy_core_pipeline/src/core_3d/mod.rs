@@ -40,6 +40,8 @@ pub mod graph {
 
 // PERF: vulkan docs recommend using 24 bit depth for better performance
 pub const CORE_3D_DEPTH_FORMAT: TextureFormat = TextureFormat::Depth32Float;
+/// The format of the depth texture of the cameras with a [`CameraStencil`].
+pub const CORE_3D_DEPTH_STENCIL_FORMAT: TextureFormat = TextureFormat::Depth32FloatStencil8;
 
 use std::ops::Range;
 
@@ -65,14 +67,16 @@ use bevy_render::{
     },
     render_resource::{
         BindGroupId, CachedRenderPipelineId, Extent3d, FilterMode, Sampler, SamplerDescriptor,
-        Texture, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages, TextureView,
+        Texture, TextureAspect, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        TextureView, TextureViewDescriptor,
     },
     renderer::RenderDevice,
-    texture::{BevyDefault, ColorAttachment, Image, TextureCache},
+    settings::WgpuFeatures,
+    texture::{BevyDefault, CachedTexture, ColorAttachment, Image, TextureCache},
     view::{ExtractedView, ViewDepthTexture, ViewTarget},
     Extract, ExtractSchedule, Render, RenderApp, RenderSet,
 };
-use bevy_utils::{tracing::warn, HashMap};
+use bevy_utils::{tracing::warn, warn_once, HashMap};
 
 use crate::{
     core_3d::main_transmissive_pass_3d_node::MainTransmissivePass3dNode,
@@ -99,6 +103,7 @@ pub struct Core3dPlugin;
 impl Plugin for Core3dPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<Camera3d>()
+            .register_type::<CameraStencil>()
             .register_type::<ScreenSpaceTransmissionQuality>()
             .add_plugins((SkyboxPlugin, ExtractComponentPlugin::<Camera3d>::default()))
             .add_systems(PostUpdate, check_msaa);
@@ -117,6 +122,7 @@ impl Plugin for Core3dPlugin {
             .init_resource::<DrawFunctions<AlphaMask3dDeferred>>()
             .add_systems(ExtractSchedule, extract_core_3d_camera_phases)
             .add_systems(ExtractSchedule, extract_camera_prepass_phase)
+            .add_systems(ExtractSchedule, extract_camera_stencil)
             .add_systems(
                 Render,
                 (
@@ -540,13 +546,43 @@ pub fn extract_camera_prepass_phase(
     }
 }
 
+/// Extracts the [`CameraStencil`] of the active 3D cameras, if the render device supports
+/// [`CORE_3D_DEPTH_STENCIL_FORMAT`].
+pub fn extract_camera_stencil(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    cameras_3d: Extract<Query<(Entity, &Camera, &CameraStencil), With<Camera3d>>>,
+) {
+    for (entity, camera, stencil) in cameras_3d.iter() {
+        if !camera.is_active {
+            continue;
+        }
+        if !render_device
+            .features()
+            .contains(WgpuFeatures::DEPTH32FLOAT_STENCIL8)
+        {
+            warn_once!(
+                "CameraStencil requires the DEPTH32FLOAT_STENCIL8 feature, which isn't supported by the render device. It will be ignored."
+            );
+            return;
+        }
+        commands.get_or_spawn(entity).insert(*stencil);
+    }
+}
+
 pub fn prepare_core_3d_depth_textures(
     mut commands: Commands,
     mut texture_cache: ResMut<TextureCache>,
     msaa: Res<Msaa>,
     render_device: Res<RenderDevice>,
     views_3d: Query<
-        (Entity, &ExtractedCamera, Option<&DepthPrepass>, &Camera3d),
+        (
+            Entity,
+            &ExtractedCamera,
+            Option<&DepthPrepass>,
+            &Camera3d,
+            Option<&CameraStencil>,
+        ),
         (
             With<BinnedRenderPhase<Opaque3d>>,
             With<BinnedRenderPhase<AlphaMask3d>>,
@@ -556,7 +592,7 @@ pub fn prepare_core_3d_depth_textures(
     >,
 ) {
     let mut render_target_usage = HashMap::default();
-    for (_, camera, depth_prepass, camera_3d) in &views_3d {
+    for (_, camera, depth_prepass, camera_3d, stencil) in &views_3d {
         // Default usage required to write to the depth texture
         let mut usage: TextureUsages = camera_3d.depth_texture_usages.into();
         if depth_prepass.is_some() {
@@ -564,19 +600,21 @@ pub fn prepare_core_3d_depth_textures(
             usage |= TextureUsages::COPY_SRC;
         }
         render_target_usage
-            .entry(camera.target.clone())
+            .entry((camera.target.clone(), stencil.is_some()))
             .and_modify(|u| *u |= usage)
             .or_insert_with(|| usage);
     }
 
     let mut textures = HashMap::default();
-    for (entity, camera, _, camera_3d) in &views_3d {
+    for (entity, camera, _, camera_3d, stencil) in &views_3d {
         let Some(physical_target_size) = camera.physical_target_size else {
             continue;
         };
 
+        // Cameras sharing a target only share a depth texture if they agree on the stencil.
+        let key = (camera.target.clone(), stencil.is_some());
         let cached_texture = textures
-            .entry(camera.target.clone())
+            .entry(key.clone())
             .or_insert_with(|| {
                 // The size of the depth texture
                 let size = Extent3d {
@@ -586,7 +624,7 @@ pub fn prepare_core_3d_depth_textures(
                 };
 
                 let usage = *render_target_usage
-                    .get(&camera.target.clone())
+                    .get(&key)
                     .expect("The depth texture usage should already exist for this target");
 
                 let descriptor = TextureDescriptor {
@@ -595,7 +633,11 @@ pub fn prepare_core_3d_depth_textures(
                     mip_level_count: 1,
                     sample_count: msaa.samples(),
                     dimension: TextureDimension::D2,
-                    format: CORE_3D_DEPTH_FORMAT,
+                    format: if stencil.is_some() {
+                        CORE_3D_DEPTH_STENCIL_FORMAT
+                    } else {
+                        CORE_3D_DEPTH_FORMAT
+                    },
                     usage,
                     view_formats: &[],
                 };
@@ -604,13 +646,17 @@ pub fn prepare_core_3d_depth_textures(
             })
             .clone();
 
-        commands.entity(entity).insert(ViewDepthTexture::new(
+        let mut depth_texture = ViewDepthTexture::new(
             cached_texture,
             match camera_3d.depth_load_op {
                 Camera3dDepthLoadOp::Clear(v) => Some(v),
                 Camera3dDepthLoadOp::Load => None,
             },
-        ));
+        );
+        if let Some(stencil) = stencil {
+            depth_texture = depth_texture.with_stencil(stencil.clear_value);
+        }
+        commands.entity(entity).insert(depth_texture);
     }
 }
 
@@ -734,6 +780,7 @@ pub fn prepare_prepass_textures(
             Has<NormalPrepass>,
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
+            Has<CameraStencil>,
         ),
         Or<(
             With<BinnedRenderPhase<Opaque3dPrepass>>,
@@ -748,8 +795,15 @@ pub fn prepare_prepass_textures(
     let mut deferred_textures = HashMap::default();
     let mut deferred_lighting_id_textures = HashMap::default();
     let mut motion_vectors_textures = HashMap::default();
-    for (entity, camera, depth_prepass, normal_prepass, motion_vector_prepass, deferred_prepass) in
-        &views_3d
+    for (
+        entity,
+        camera,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        deferred_prepass,
+        stencil,
+    ) in &views_3d
     {
         let Some(physical_target_size) = camera.physical_target_size else {
             continue;
@@ -763,21 +817,38 @@ pub fn prepare_prepass_textures(
 
         let cached_depth_texture = depth_prepass.then(|| {
             depth_textures
-                .entry(camera.target.clone())
+                .entry((camera.target.clone(), stencil))
                 .or_insert_with(|| {
+                    // The depth is copied from the view depth texture, so the formats must match.
                     let descriptor = TextureDescriptor {
                         label: Some("prepass_depth_texture"),
                         size,
                         mip_level_count: 1,
                         sample_count: msaa.samples(),
                         dimension: TextureDimension::D2,
-                        format: CORE_3D_DEPTH_FORMAT,
+                        format: if stencil {
+                            CORE_3D_DEPTH_STENCIL_FORMAT
+                        } else {
+                            CORE_3D_DEPTH_FORMAT
+                        },
                         usage: TextureUsages::COPY_DST
                             | TextureUsages::RENDER_ATTACHMENT
                             | TextureUsages::TEXTURE_BINDING,
                         view_formats: &[],
                     };
-                    texture_cache.get(&render_device, descriptor)
+                    let cached_texture = texture_cache.get(&render_device, descriptor);
+                    if !stencil {
+                        return cached_texture;
+                    }
+                    // Depth-stencil textures can only be sampled through a view of one aspect.
+                    CachedTexture {
+                        default_view: cached_texture.texture.create_view(&TextureViewDescriptor {
+                            label: Some("prepass_depth_texture_depth_view"),
+                            aspect: TextureAspect::DepthOnly,
+                            ..Default::default()
+                        }),
+                        texture: cached_texture.texture,
+                    }
                 })
                 .clone()
         });
@@ -0,0 +1,324 @@
+//! User-defined render phases for 3D cameras, such as an outline or x-ray phase.
+//!
+//! A custom phase is declared with a label type implementing [`CustomPhase`], which is also the
+//! label of the render graph node drawing the phase:
+//!
+//! ```
+//! # use bevy_core_pipeline::custom_phase::{CustomPhase, CustomPhaseSortOrder};
+//! # use bevy_render::render_graph::RenderLabel;
+//! #[derive(RenderLabel, Debug, Clone, Default, PartialEq, Eq, Hash)]
+//! struct OutlinePhase;
+//!
+//! impl CustomPhase for OutlinePhase {
+//!     const NAME: &'static str = "outline_phase";
+//!     const SORT_ORDER: CustomPhaseSortOrder = CustomPhaseSortOrder::FrontToBack;
+//! }
+//! ```
+//!
+//! Adding a [`CustomPhasePlugin`] gives every active [`Camera3d`] a
+//! [`SortedRenderPhase<CustomPhaseItem<P>>`], sorts it with [`CustomPhase::SORT_ORDER`] and draws
+//! it after the main transparent pass. Entities opt in with the [`InCustomPhase`] component, and
+//! are queued into the phase by a system in [`RenderSet::Queue`], with a draw function registered
+//! in [`DrawFunctions<CustomPhaseItem<P>>`]. `bevy_pbr` provides such a system for its materials.
+
+use std::{marker::PhantomData, ops::Range};
+
+use bevy_app::{App, Plugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With},
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query},
+    world::World,
+};
+use bevy_math::FloatOrd;
+use bevy_render::{
+    batching::GetFullBatchData,
+    camera::{Camera, ExtractedCamera},
+    diagnostic::RecordDiagnostics,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_graph::{
+        NodeRunError, RenderGraphApp, RenderGraphContext, RenderLabel, ViewNode, ViewNodeRunner,
+    },
+    render_phase::{
+        sort_phase_system, CachedRenderPipelinePhaseItem, DrawFunctionId, DrawFunctions, PhaseItem,
+        PhaseItemExtraIndex, SortedPhaseItem, SortedRenderPhase, SortedRenderPhasePlugin,
+    },
+    render_resource::{CachedRenderPipelineId, RenderPassDescriptor, StoreOp},
+    renderer::RenderContext,
+    view::{ViewDepthTexture, ViewTarget},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use crate::core_3d::{
+    graph::{Core3d, Node3d},
+    Camera3d,
+};
+
+/// A user-defined render phase, drawn by every active [`Camera3d`] once its [`CustomPhasePlugin`]
+/// is added.
+///
+/// The implementing type is also the [`RenderLabel`] of the render graph node drawing the phase,
+/// so more edges can be added to order it relative to other nodes of the [`Core3d`] graph.
+pub trait CustomPhase: RenderLabel + Clone + Default {
+    /// The label of the render pass drawing the phase, also used for its diagnostics.
+    const NAME: &'static str;
+
+    /// The order the items of the phase are drawn in.
+    const SORT_ORDER: CustomPhaseSortOrder = CustomPhaseSortOrder::BackToFront;
+}
+
+/// The order the items of a [`CustomPhase`] are drawn in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CustomPhaseSortOrder {
+    /// The farthest items are drawn first, as required when blending them with the painter's
+    /// algorithm.
+    #[default]
+    BackToFront,
+    /// The nearest items are drawn first, which reduces overdraw for items writing depth.
+    FrontToBack,
+    /// The items are drawn in the order they were queued.
+    Unsorted,
+}
+
+/// A [`PhaseItem`] of a [`CustomPhase`], queued into the
+/// [`SortedRenderPhase<CustomPhaseItem<P>>`] of a view.
+pub struct CustomPhaseItem<P: CustomPhase> {
+    /// The distance of the item to the view, as returned by the
+    /// [`ViewRangefinder3d`](bevy_render::render_phase::ViewRangefinder3d) of the view.
+    ///
+    /// Values increase towards the camera.
+    pub distance: f32,
+    pub pipeline: CachedRenderPipelineId,
+    pub entity: Entity,
+    pub draw_function: DrawFunctionId,
+    pub batch_range: Range<u32>,
+    pub extra_index: PhaseItemExtraIndex,
+    marker: PhantomData<fn() -> P>,
+}
+
+impl<P: CustomPhase> CustomPhaseItem<P> {
+    /// Creates an item drawing `entity` with the given pipeline and draw function.
+    pub fn new(
+        entity: Entity,
+        distance: f32,
+        pipeline: CachedRenderPipelineId,
+        draw_function: DrawFunctionId,
+    ) -> Self {
+        Self {
+            distance,
+            pipeline,
+            entity,
+            draw_function,
+            batch_range: 0..1,
+            extra_index: PhaseItemExtraIndex::NONE,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<P: CustomPhase> PhaseItem for CustomPhaseItem<P> {
+    #[inline]
+    fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    #[inline]
+    fn draw_function(&self) -> DrawFunctionId {
+        self.draw_function
+    }
+
+    #[inline]
+    fn batch_range(&self) -> &Range<u32> {
+        &self.batch_range
+    }
+
+    #[inline]
+    fn batch_range_mut(&mut self) -> &mut Range<u32> {
+        &mut self.batch_range
+    }
+
+    #[inline]
+    fn extra_index(&self) -> PhaseItemExtraIndex {
+        self.extra_index
+    }
+
+    #[inline]
+    fn batch_range_and_extra_index_mut(&mut self) -> (&mut Range<u32>, &mut PhaseItemExtraIndex) {
+        (&mut self.batch_range, &mut self.extra_index)
+    }
+}
+
+impl<P: CustomPhase> SortedPhaseItem for CustomPhaseItem<P> {
+    type SortKey = FloatOrd;
+
+    #[inline]
+    fn sort_key(&self) -> Self::SortKey {
+        match P::SORT_ORDER {
+            CustomPhaseSortOrder::FrontToBack => FloatOrd(-self.distance),
+            _ => FloatOrd(self.distance),
+        }
+    }
+
+    #[inline]
+    fn sort(items: &mut [Self]) {
+        // NOTE: Values increase towards the camera, so back-to-front is an ascending sort.
+        match P::SORT_ORDER {
+            CustomPhaseSortOrder::BackToFront => radsort::sort_by_key(items, |item| item.distance),
+            CustomPhaseSortOrder::FrontToBack => radsort::sort_by_key(items, |item| -item.distance),
+            CustomPhaseSortOrder::Unsorted => {}
+        }
+    }
+}
+
+impl<P: CustomPhase> CachedRenderPipelinePhaseItem for CustomPhaseItem<P> {
+    #[inline]
+    fn cached_pipeline(&self) -> CachedRenderPipelineId {
+        self.pipeline
+    }
+}
+
+/// Tags an entity to be drawn in the custom phase `P`, in addition to the phases it's already
+/// drawn in.
+#[derive(Component)]
+pub struct InCustomPhase<P: CustomPhase>(PhantomData<fn() -> P>);
+
+impl<P: CustomPhase> Default for InCustomPhase<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: CustomPhase> Clone for InCustomPhase<P> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl<P: CustomPhase> ExtractComponent for InCustomPhase<P> {
+    type QueryData = ();
+    type QueryFilter = With<Self>;
+    type Out = Self;
+
+    fn extract_component(_: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(Self::default())
+    }
+}
+
+/// Adds the custom phase `P` to every active [`Camera3d`], drawn after the
+/// [`Node3d::MainTransparentPass`].
+///
+/// `GFBD` is the pipeline providing the per-instance data of the queued items, such as
+/// `MeshPipeline` for meshes.
+pub struct CustomPhasePlugin<P: CustomPhase, GFBD: GetFullBatchData>(
+    PhantomData<fn() -> (P, GFBD)>,
+);
+
+impl<P: CustomPhase, GFBD: GetFullBatchData> Default for CustomPhasePlugin<P, GFBD> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P, GFBD> Plugin for CustomPhasePlugin<P, GFBD>
+where
+    P: CustomPhase,
+    GFBD: GetFullBatchData + Sync + Send + 'static,
+{
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<InCustomPhase<P>>::default(),
+            SortedRenderPhasePlugin::<CustomPhaseItem<P>, GFBD>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<DrawFunctions<CustomPhaseItem<P>>>()
+            .add_systems(ExtractSchedule, extract_custom_phase_cameras::<P>)
+            .add_systems(
+                Render,
+                sort_phase_system::<CustomPhaseItem<P>>.in_set(RenderSet::PhaseSort),
+            )
+            .add_render_graph_node::<ViewNodeRunner<CustomPhaseNode<P>>>(Core3d, P::default())
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::MainTransparentPass,
+                    P::default(),
+                    Node3d::EndMainPass,
+                ),
+            );
+    }
+}
+
+/// Adds the [`SortedRenderPhase`] of the custom phase `P` to the active 3D cameras.
+pub fn extract_custom_phase_cameras<P: CustomPhase>(
+    mut commands: Commands,
+    cameras_3d: Extract<Query<(Entity, &Camera), With<Camera3d>>>,
+) {
+    for (entity, camera) in &cameras_3d {
+        if camera.is_active {
+            commands
+                .get_or_spawn(entity)
+                .insert(SortedRenderPhase::<CustomPhaseItem<P>>::default());
+        }
+    }
+}
+
+/// A [`bevy_render::render_graph::Node`] that runs the [`SortedRenderPhase`] of the custom
+/// phase `P`, on top of the main pass of the view.
+pub struct CustomPhaseNode<P: CustomPhase>(PhantomData<fn() -> P>);
+
+impl<P: CustomPhase> Default for CustomPhaseNode<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: CustomPhase> ViewNode for CustomPhaseNode<P> {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static SortedRenderPhase<CustomPhaseItem<P>>,
+        &'static ViewTarget,
+        &'static ViewDepthTexture,
+    );
+
+    fn run(
+        &self,
+        graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, phase, target, depth): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if phase.items.is_empty() {
+            return Ok(());
+        }
+
+        let view_entity = graph.view_entity();
+        let diagnostics = render_context.diagnostic_recorder();
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some(P::NAME),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: Some(depth.get_attachment(StoreOp::Store)),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        let pass_span = diagnostics.pass_span(&mut render_pass, P::NAME);
+
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+
+        phase.render(&mut render_pass, world, view_entity);
+
+        pass_span.end(&mut render_pass);
+
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@ pub mod bloom;
 pub mod contrast_adaptive_sharpening;
 pub mod core_2d;
 pub mod core_3d;
+pub mod custom_phase;
 pub mod deferred;
 pub mod dof;
 pub mod fullscreen_vertex_shader;
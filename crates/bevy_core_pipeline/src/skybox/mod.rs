@@ -2,7 +2,7 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, Handle};
 use bevy_ecs::{
     prelude::{Component, Entity},
-    query::{QueryItem, With},
+    query::{Has, QueryItem, With},
     schedule::IntoSystemConfigs,
     system::{Commands, Query, Res, ResMut, Resource},
 };
@@ -23,7 +23,7 @@ use bevy_render::{
     Render, RenderApp, RenderSet,
 };
 
-use crate::core_3d::CORE_3D_DEPTH_FORMAT;
+use crate::core_3d::{CameraStencil, CORE_3D_DEPTH_FORMAT, CORE_3D_DEPTH_STENCIL_FORMAT};
 
 const SKYBOX_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(55594763423201);
 
@@ -209,16 +209,20 @@ fn prepare_skybox_pipelines(
     mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxPipeline>>,
     pipeline: Res<SkyboxPipeline>,
     msaa: Res<Msaa>,
-    views: Query<(Entity, &ExtractedView), With<Skybox>>,
+    views: Query<(Entity, &ExtractedView, Has<CameraStencil>), With<Skybox>>,
 ) {
-    for (entity, view) in &views {
+    for (entity, view, stencil) in &views {
         let pipeline_id = pipelines.specialize(
             &pipeline_cache,
             &pipeline,
             SkyboxPipelineKey {
                 hdr: view.hdr,
                 samples: msaa.samples(),
-                depth_format: CORE_3D_DEPTH_FORMAT,
+                depth_format: if stencil {
+                    CORE_3D_DEPTH_STENCIL_FORMAT
+                } else {
+                    CORE_3D_DEPTH_FORMAT
+                },
             },
         );
 
@@ -15,6 +15,8 @@ pub mod ci_testing;
 
 pub mod fps_overlay;
 
+pub mod test_app;
+
 #[cfg(feature = "bevy_ui_debug")]
 pub mod ui_debug_overlay;
 
@@ -0,0 +1,391 @@
+//! A headless [`App`] wrapper for integration tests.
+//!
+//! [`TestApp`] builds an app with the minimal set of plugins needed by gameplay code, steps it one
+//! frame at a time with a fixed, controllable frame time, simulates input and window events, and
+//! provides assertions with readable failure messages.
+//!
+//! ```
+//! # use bevy_dev_tools::test_app::TestApp;
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::{keyboard::KeyCode, ButtonInput};
+//! # use bevy_app::Update;
+//! #[derive(Component)]
+//! struct Bullet;
+//!
+//! fn shoot(mut commands: Commands, keys: Res<ButtonInput<KeyCode>>) {
+//!     if keys.just_pressed(KeyCode::Space) {
+//!         commands.spawn(Bullet);
+//!     }
+//! }
+//!
+//! let mut app = TestApp::new();
+//! app.add_systems(Update, shoot);
+//!
+//! app.press_key(KeyCode::Space);
+//! app.step();
+//! app.assert_count::<With<Bullet>>(1);
+//!
+//! app.step_frames(10);
+//! app.assert_count::<With<Bullet>>(1);
+//! ```
+
+use std::{any::type_name, fmt::Debug};
+
+use bevy_app::{App, PluginsState};
+use bevy_core::{FrameCountPlugin, TaskPoolPlugin, TypeRegistrationPlugin};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, Events},
+    query::{QueryFilter, With},
+    system::Resource,
+};
+use bevy_input::{
+    keyboard::{Key, KeyCode, KeyboardInput, NativeKey},
+    mouse::{MouseButton, MouseButtonInput},
+    ButtonState, InputPlugin,
+};
+use bevy_math::Vec2;
+use bevy_time::{Real, Time, TimePlugin, TimeUpdateStrategy};
+use bevy_utils::Duration;
+use bevy_window::{CursorMoved, PrimaryWindow, Window, WindowPlugin, WindowResized};
+
+/// A headless [`App`] for integration tests.
+///
+/// It dereferences to [`App`], so plugins, systems and resources are added as usual.
+/// See the [module-level documentation](self) for an example.
+pub struct TestApp {
+    app: App,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    /// The default duration of a frame: 1/60th of a second.
+    pub const DEFAULT_FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+    /// Creates an app with the task pool, type registration, frame count, time, input and window
+    /// plugins.
+    ///
+    /// The primary window is only a [`Window`] entity: nothing is displayed.
+    /// Every frame advances time by [`TestApp::DEFAULT_FRAME_TIME`], including the first one.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            TypeRegistrationPlugin,
+            FrameCountPlugin,
+            TimePlugin,
+            InputPlugin,
+            WindowPlugin::default(),
+        ));
+        // Start the clock now, so that the first frame advances time like the following ones.
+        app.world_mut()
+            .resource_mut::<Time<Real>>()
+            .update_with_duration(Duration::ZERO);
+        let mut test_app = Self { app };
+        test_app.set_frame_time(Self::DEFAULT_FRAME_TIME);
+        test_app
+    }
+
+    /// Returns the wrapped [`App`].
+    pub fn into_inner(self) -> App {
+        self.app
+    }
+
+    /// Sets the duration by which time advances on each frame.
+    pub fn set_frame_time(&mut self, frame_time: Duration) -> &mut Self {
+        self.app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame_time));
+        self
+    }
+
+    /// Returns the duration by which time advances on each frame.
+    pub fn frame_time(&self) -> Duration {
+        match self.app.world().get_resource::<TimeUpdateStrategy>() {
+            Some(TimeUpdateStrategy::ManualDuration(frame_time)) => *frame_time,
+            _ => Self::DEFAULT_FRAME_TIME,
+        }
+    }
+
+    /// Runs a single frame.
+    ///
+    /// The first call finishes building the plugins before running the frame.
+    pub fn step(&mut self) -> &mut Self {
+        if self.app.plugins_state() == PluginsState::Ready {
+            self.app.finish();
+            self.app.cleanup();
+        }
+        self.app.update();
+        self
+    }
+
+    /// Runs `frames` frames.
+    pub fn step_frames(&mut self, frames: u32) -> &mut Self {
+        for _ in 0..frames {
+            self.step();
+        }
+        self
+    }
+
+    /// Runs as many frames as needed for at least `duration` to elapse.
+    pub fn step_for(&mut self, duration: Duration) -> &mut Self {
+        let frame_time = self.frame_time();
+        assert!(
+            !frame_time.is_zero(),
+            "cannot step for {duration:?} with a frame time of zero"
+        );
+        let frames = duration.as_nanos().div_ceil(frame_time.as_nanos());
+        self.step_frames(frames.try_into().unwrap_or(u32::MAX))
+    }
+
+    /// Runs a single frame lasting `duration` instead of the configured frame time.
+    pub fn step_by(&mut self, duration: Duration) -> &mut Self {
+        let frame_time = self.frame_time();
+        self.set_frame_time(duration);
+        self.step();
+        self.set_frame_time(frame_time)
+    }
+
+    /// Returns the primary window entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no primary window.
+    pub fn primary_window(&mut self) -> Entity {
+        self.app
+            .world_mut()
+            .query_filtered::<Entity, With<PrimaryWindow>>()
+            .get_single(self.app.world())
+            .expect("the TestApp has no primary window")
+    }
+
+    /// Sends an event, which will be read during the next frame.
+    pub fn send<E: Event>(&mut self, event: E) -> &mut Self {
+        self.app.world_mut().send_event(event);
+        self
+    }
+
+    /// Simulates pressing `key` in the primary window.
+    pub fn press_key(&mut self, key: KeyCode) -> &mut Self {
+        self.send_key(key, ButtonState::Pressed)
+    }
+
+    /// Simulates releasing `key` in the primary window.
+    pub fn release_key(&mut self, key: KeyCode) -> &mut Self {
+        self.send_key(key, ButtonState::Released)
+    }
+
+    fn send_key(&mut self, key_code: KeyCode, state: ButtonState) -> &mut Self {
+        let window = self.primary_window();
+        self.send(KeyboardInput {
+            key_code,
+            logical_key: Key::Unidentified(NativeKey::Unidentified),
+            state,
+            window,
+        })
+    }
+
+    /// Simulates pressing the mouse `button` in the primary window.
+    pub fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_mouse_button(button, ButtonState::Pressed)
+    }
+
+    /// Simulates releasing the mouse `button` in the primary window.
+    pub fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_mouse_button(button, ButtonState::Released)
+    }
+
+    fn send_mouse_button(&mut self, button: MouseButton, state: ButtonState) -> &mut Self {
+        let window = self.primary_window();
+        self.send(MouseButtonInput {
+            button,
+            state,
+            window,
+        })
+    }
+
+    /// Simulates moving the cursor to `position`, in logical pixels, in the primary window.
+    pub fn move_cursor(&mut self, position: Vec2) -> &mut Self {
+        let window = self.primary_window();
+        let mut window_component = self.app.world_mut().get_mut::<Window>(window).unwrap();
+        let delta = window_component
+            .cursor_position()
+            .map(|previous| position - previous);
+        window_component.set_cursor_position(Some(position));
+        self.send(CursorMoved {
+            window,
+            position,
+            delta,
+        })
+    }
+
+    /// Simulates resizing the primary window to `width` by `height` logical pixels.
+    pub fn resize_window(&mut self, width: f32, height: f32) -> &mut Self {
+        let window = self.primary_window();
+        self.app
+            .world_mut()
+            .get_mut::<Window>(window)
+            .unwrap()
+            .resolution
+            .set(width, height);
+        self.send(WindowResized {
+            window,
+            width,
+            height,
+        })
+    }
+
+    /// Returns the number of entities matching the filter `F`.
+    pub fn count<F: QueryFilter>(&mut self) -> usize {
+        let world = self.app.world_mut();
+        world.query_filtered::<Entity, F>().iter(world).count()
+    }
+
+    /// Asserts that exactly `expected` entities match the filter `F`.
+    #[track_caller]
+    pub fn assert_count<F: QueryFilter>(&mut self, expected: usize) -> &mut Self {
+        let count = self.count::<F>();
+        assert_eq!(
+            count,
+            expected,
+            "expected {expected} entities matching `{}`, found {count}",
+            type_name::<F>()
+        );
+        self
+    }
+
+    /// Asserts that `entity` has the component `C`, equal to `expected`.
+    #[track_caller]
+    pub fn assert_component<C: Component + PartialEq + Debug>(
+        &self,
+        entity: Entity,
+        expected: &C,
+    ) -> &Self {
+        let Some(entity_ref) = self.app.world().get_entity(entity) else {
+            panic!("entity {entity:?} does not exist");
+        };
+        let Some(component) = entity_ref.get::<C>() else {
+            panic!(
+                "entity {entity:?} does not have a `{}` component",
+                type_name::<C>()
+            );
+        };
+        assert_eq!(
+            component,
+            expected,
+            "unexpected `{}` on entity {entity:?}",
+            type_name::<C>()
+        );
+        self
+    }
+
+    /// Asserts that the resource `R` exists and is equal to `expected`.
+    #[track_caller]
+    pub fn assert_resource<R: Resource + PartialEq + Debug>(&self, expected: &R) -> &Self {
+        let Some(resource) = self.app.world().get_resource::<R>() else {
+            panic!("resource `{}` does not exist", type_name::<R>());
+        };
+        assert_eq!(
+            resource,
+            expected,
+            "unexpected value of resource `{}`",
+            type_name::<R>()
+        );
+        self
+    }
+
+    /// Returns the events of type `E` sent during the last two frames, oldest first.
+    pub fn events<E: Event + Clone>(&self) -> Vec<E> {
+        let Some(events) = self.app.world().get_resource::<Events<E>>() else {
+            return Vec::new();
+        };
+        events.get_reader().read(events).cloned().collect()
+    }
+
+    /// Asserts that an event equal to `expected` was sent during the last two frames.
+    #[track_caller]
+    pub fn assert_event_sent<E: Event + Clone + PartialEq + Debug>(&self, expected: &E) -> &Self {
+        let events = self.events::<E>();
+        assert!(
+            events.contains(expected),
+            "expected a `{}` event equal to {expected:?}, found {events:?}",
+            type_name::<E>()
+        );
+        self
+    }
+}
+
+impl std::ops::Deref for TestApp {
+    type Target = App;
+
+    fn deref(&self) -> &Self::Target {
+        &self.app
+    }
+}
+
+impl std::ops::DerefMut for TestApp {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.app
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TestApp;
+    use bevy_app::Update;
+    use bevy_ecs::prelude::*;
+    use bevy_input::{keyboard::KeyCode, ButtonInput};
+    use bevy_time::Time;
+    use bevy_utils::Duration;
+
+    #[derive(Resource, Default, Debug, PartialEq)]
+    struct Jumps(u32);
+
+    #[test]
+    fn steps_with_fixed_frame_time() {
+        let mut app = TestApp::new();
+        app.set_frame_time(Duration::from_millis(100));
+        app.step_frames(3);
+        assert_eq!(
+            app.world().resource::<Time>().elapsed(),
+            Duration::from_millis(300)
+        );
+
+        app.step_for(Duration::from_millis(250));
+        assert_eq!(
+            app.world().resource::<Time>().elapsed(),
+            Duration::from_millis(600)
+        );
+    }
+
+    #[test]
+    fn simulated_input() {
+        let mut app = TestApp::new();
+        app.init_resource::<Jumps>().add_systems(
+            Update,
+            |keys: Res<ButtonInput<KeyCode>>, mut jumps: ResMut<Jumps>| {
+                if keys.just_pressed(KeyCode::Space) {
+                    jumps.0 += 1;
+                }
+            },
+        );
+
+        app.press_key(KeyCode::Space).step();
+        app.step().release_key(KeyCode::Space).step();
+        app.press_key(KeyCode::Space).step();
+        app.assert_resource(&Jumps(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "unexpected value of resource")]
+    fn readable_assertion_failure() {
+        let mut app = TestApp::new();
+        app.init_resource::<Jumps>();
+        app.step().assert_resource(&Jumps(1));
+    }
+}
@@ -0,0 +1,275 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    archetype::ArchetypeId,
+    component::{ComponentId, StorageType},
+    prelude::*,
+    storage::TableId,
+};
+use bevy_utils::Instant;
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, RegisterDiagnostic,
+};
+
+/// Adds diagnostics about the memory used by archetypes and tables to an App.
+///
+/// Besides the totals, a diagnostic is reported for each non-empty archetype, under
+/// `archetype/<index>/entity_count`, `archetype/<index>/component_bytes` and
+/// `archetype/<index>/table_occupancy`. The detailed breakdown of the last frame, including the
+/// size of each component, is available in the [`ArchetypeMemoryReport`] resource.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct ArchetypeMemoryDiagnosticsPlugin {
+    /// Whether to report a diagnostic for each archetype, on top of the totals.
+    pub per_archetype: bool,
+}
+
+impl Default for ArchetypeMemoryDiagnosticsPlugin {
+    fn default() -> Self {
+        Self {
+            per_archetype: true,
+        }
+    }
+}
+
+impl Plugin for ArchetypeMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ARCHETYPE_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::COMPONENT_BYTES).with_suffix("B"))
+            .register_diagnostic(Diagnostic::new(Self::TABLE_CAPACITY_BYTES).with_suffix("B"))
+            .register_diagnostic(Diagnostic::new(Self::TABLE_OCCUPANCY))
+            .init_resource::<ArchetypeMemoryReport>();
+
+        if self.per_archetype {
+            app.add_systems(Last, Self::per_archetype_diagnostic_system);
+        } else {
+            app.add_systems(Last, Self::diagnostic_system);
+        }
+    }
+}
+
+impl ArchetypeMemoryDiagnosticsPlugin {
+    pub const ARCHETYPE_COUNT: DiagnosticPath = DiagnosticPath::const_new("archetype/count");
+    pub const COMPONENT_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("archetype/component_bytes");
+    pub const TABLE_CAPACITY_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("table/capacity_bytes");
+    pub const TABLE_OCCUPANCY: DiagnosticPath = DiagnosticPath::const_new("table/occupancy");
+
+    /// Returns the path of the diagnostic named `name` for the archetype `archetype`.
+    pub fn archetype_path(archetype: ArchetypeId, name: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!("archetype/{}/{name}", archetype.index()))
+    }
+
+    /// Updates the [`ArchetypeMemoryReport`] and the total diagnostics.
+    pub fn diagnostic_system(world: &mut World) {
+        Self::update(world, false);
+    }
+
+    /// Updates the [`ArchetypeMemoryReport`], the total diagnostics and the diagnostics of each
+    /// archetype.
+    pub fn per_archetype_diagnostic_system(world: &mut World) {
+        Self::update(world, true);
+    }
+
+    fn update(world: &mut World, per_archetype: bool) {
+        let report = ArchetypeMemoryReport::collect(world);
+        let (table_bytes, table_len, table_capacity) =
+            world
+                .storages()
+                .tables
+                .iter()
+                .fold((0, 0, 0), |(bytes, len, capacity), table| {
+                    let row_size: usize =
+                        table.iter().map(|column| column.item_layout().size()).sum();
+                    (
+                        bytes + row_size * table.entity_capacity(),
+                        len + table.entity_count(),
+                        capacity + table.entity_capacity(),
+                    )
+                });
+
+        let mut store = world.resource_mut::<DiagnosticsStore>();
+        let now = Instant::now();
+        let mut measure = |path: &DiagnosticPath, value: f64| {
+            if store.get(path).is_none() {
+                store.add(Diagnostic::new(path.clone()));
+            }
+            if let Some(diagnostic) = store.get_mut(path).filter(|d| d.is_enabled) {
+                diagnostic.add_measurement(DiagnosticMeasurement { time: now, value });
+            }
+        };
+
+        measure(&Self::ARCHETYPE_COUNT, report.archetypes.len() as f64);
+        measure(&Self::COMPONENT_BYTES, report.component_bytes() as f64);
+        measure(&Self::TABLE_CAPACITY_BYTES, table_bytes as f64);
+        measure(&Self::TABLE_OCCUPANCY, occupancy(table_len, table_capacity));
+
+        if per_archetype {
+            for archetype in report.archetypes.iter().filter(|a| a.entity_count > 0) {
+                let path = |name| Self::archetype_path(archetype.archetype, name);
+                measure(&path("entity_count"), archetype.entity_count as f64);
+                measure(&path("component_bytes"), archetype.component_bytes as f64);
+                measure(&path("table_occupancy"), archetype.table_occupancy());
+            }
+        }
+
+        world.insert_resource(report);
+    }
+}
+
+fn occupancy(len: usize, capacity: usize) -> f64 {
+    if capacity == 0 {
+        1.0
+    } else {
+        len as f64 / capacity as f64
+    }
+}
+
+/// The memory used by a component in an archetype.
+#[derive(Debug, Clone)]
+pub struct ComponentMemoryStats {
+    /// The id of the component.
+    pub id: ComponentId,
+    /// The name of the component.
+    pub name: String,
+    /// Where the component is stored.
+    pub storage_type: StorageType,
+    /// The size of a single value of the component.
+    pub size: usize,
+    /// The bytes used by the values of this component in the archetype.
+    pub bytes: usize,
+}
+
+/// The memory used by an archetype.
+#[derive(Debug, Clone)]
+pub struct ArchetypeMemoryStats {
+    /// The id of the archetype.
+    pub archetype: ArchetypeId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The memory used by each component of the archetype.
+    pub components: Vec<ComponentMemoryStats>,
+    /// The bytes used by the component values of the archetype's entities.
+    pub component_bytes: usize,
+    /// The table storing the archetype's table components.
+    ///
+    /// Tables can be shared by several archetypes that only differ by sparse set components.
+    pub table: TableId,
+    /// The number of entities in the table.
+    pub table_entity_count: usize,
+    /// The number of entities the table can hold without reallocating.
+    pub table_capacity: usize,
+}
+
+impl ArchetypeMemoryStats {
+    /// Returns the fraction of the table's capacity that is in use, between `0.0` and `1.0`.
+    pub fn table_occupancy(&self) -> f64 {
+        occupancy(self.table_entity_count, self.table_capacity)
+    }
+}
+
+/// The memory used by each archetype, as of the last update of the
+/// [`ArchetypeMemoryDiagnosticsPlugin`].
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ArchetypeMemoryReport {
+    pub archetypes: Vec<ArchetypeMemoryStats>,
+}
+
+impl ArchetypeMemoryReport {
+    /// Measures the memory used by each archetype of `world`.
+    pub fn collect(world: &World) -> Self {
+        let components = world.components();
+        let tables = &world.storages().tables;
+        let archetypes = world
+            .archetypes()
+            .iter()
+            .map(|archetype| {
+                let entity_count = archetype.len();
+                let components: Vec<_> = archetype
+                    .components()
+                    .filter_map(|id| components.get_info(id))
+                    .map(|info| ComponentMemoryStats {
+                        id: info.id(),
+                        name: info.name().to_string(),
+                        storage_type: info.storage_type(),
+                        size: info.layout().size(),
+                        bytes: info.layout().size() * entity_count,
+                    })
+                    .collect();
+                let table = tables.get(archetype.table_id());
+                ArchetypeMemoryStats {
+                    archetype: archetype.id(),
+                    entity_count,
+                    component_bytes: components.iter().map(|component| component.bytes).sum(),
+                    components,
+                    table: archetype.table_id(),
+                    table_entity_count: table.map_or(0, |table| table.entity_count()),
+                    table_capacity: table.map_or(0, |table| table.entity_capacity()),
+                }
+            })
+            .collect();
+        Self { archetypes }
+    }
+
+    /// Returns the bytes used by the component values of all archetypes.
+    pub fn component_bytes(&self) -> usize {
+        self.archetypes
+            .iter()
+            .map(|archetype| archetype.component_bytes)
+            .sum()
+    }
+
+    /// Iterates over the archetypes, from the one using the most component bytes to the least.
+    pub fn largest(&self) -> impl Iterator<Item = &ArchetypeMemoryStats> {
+        let mut archetypes: Vec<_> = self.archetypes.iter().collect();
+        archetypes.sort_by_key(|archetype| std::cmp::Reverse(archetype.component_bytes));
+        archetypes.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Component)]
+    struct Position([f32; 3]);
+
+    #[allow(dead_code)]
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Marker(u64);
+
+    #[test]
+    fn reports_archetype_memory() {
+        let mut app = App::new();
+        app.add_plugins(ArchetypeMemoryDiagnosticsPlugin::default());
+        let world = app.world_mut();
+        world.spawn_batch((0..10).map(|_| Position([0.0; 3])));
+        world.spawn_batch((0..8).map(|_| (Position([0.0; 3]), Marker(0))));
+        app.update();
+
+        let report = app.world().resource::<ArchetypeMemoryReport>();
+        let mut largest = report.largest();
+        let first = largest.next().unwrap();
+        assert_eq!(first.entity_count, 8);
+        assert_eq!(first.component_bytes, 8 * (12 + 8));
+        let second = largest.next().unwrap();
+        assert_eq!(second.entity_count, 10);
+        assert_eq!(second.component_bytes, 10 * 12);
+        assert_eq!(first.table, second.table);
+        assert_eq!(second.table_entity_count, 18);
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let bytes = store
+            .get_measurement(&ArchetypeMemoryDiagnosticsPlugin::COMPONENT_BYTES)
+            .unwrap();
+        assert_eq!(bytes.value, (8 * 20 + 10 * 12) as f64);
+        let path =
+            ArchetypeMemoryDiagnosticsPlugin::archetype_path(second.archetype, "entity_count");
+        assert_eq!(store.get_measurement(&path).unwrap().value, 10.0);
+    }
+}
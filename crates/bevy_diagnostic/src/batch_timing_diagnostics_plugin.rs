@@ -0,0 +1,160 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{batching::BatchTimings, prelude::*};
+use bevy_utils::{HashMap, Instant};
+
+use crate::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+
+/// Adds diagnostics about the batches of parallel queries to an App.
+///
+/// Queries are tracked by name through the [`ParallelBatchTimings`] resource: the
+/// [`BatchTimings`] returned by [`ParallelBatchTimings::register`] are set on the
+/// [`BatchingStrategy`](bevy_ecs::batching::BatchingStrategy) of the query, and the plugin
+/// collects and resets them at the end of every frame. For each name, diagnostics are reported
+/// under `parallel_batches/<name>/batches`, `.../items`, `.../mean_batch_time` and
+/// `.../max_batch_time`.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, batching::BatchingStrategy};
+/// # use bevy_diagnostic::ParallelBatchTimings;
+/// # #[derive(Component)]
+/// # struct Velocity;
+/// fn setup(mut timings: ResMut<ParallelBatchTimings>, mut commands: Commands) {
+///     let strategy = BatchingStrategy::adaptive().with_timings(timings.register("velocity"));
+///     commands.insert_resource(VelocityStrategy(strategy));
+/// }
+///
+/// #[derive(Resource)]
+/// struct VelocityStrategy(BatchingStrategy);
+///
+/// fn integrate(query: Query<&Velocity>, strategy: Res<VelocityStrategy>) {
+///     query
+///         .par_iter()
+///         .batching_strategy(strategy.0.clone())
+///         .for_each(|velocity| {
+///             // ...
+///         });
+/// }
+/// ```
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct BatchTimingDiagnosticsPlugin;
+
+impl Plugin for BatchTimingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ParallelBatchTimings>()
+            .add_systems(Last, Self::diagnostic_system);
+    }
+}
+
+impl BatchTimingDiagnosticsPlugin {
+    /// Returns the path of the diagnostic named `name` for the queries registered as `query`.
+    pub fn path(query: &str, name: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!("parallel_batches/{query}/{name}"))
+    }
+
+    /// Reports and resets the timings of the [`ParallelBatchTimings`].
+    pub fn diagnostic_system(
+        mut store: ResMut<DiagnosticsStore>,
+        timings: Res<ParallelBatchTimings>,
+    ) {
+        let now = Instant::now();
+        let mut measure = |path: DiagnosticPath, value: f64, suffix: &'static str| {
+            if store.get(&path).is_none() {
+                store.add(Diagnostic::new(path.clone()).with_suffix(suffix));
+            }
+            if let Some(diagnostic) = store.get_mut(&path).filter(|d| d.is_enabled) {
+                diagnostic.add_measurement(DiagnosticMeasurement { time: now, value });
+            }
+        };
+
+        for (query, timings) in timings.iter() {
+            let mean = timings.mean_batch_time().unwrap_or_default();
+            measure(
+                Self::path(query, "batches"),
+                timings.batch_count() as f64,
+                "",
+            );
+            measure(Self::path(query, "items"), timings.item_count() as f64, "");
+            measure(
+                Self::path(query, "mean_batch_time"),
+                mean.as_secs_f64() * 1000.0,
+                "ms",
+            );
+            measure(
+                Self::path(query, "max_batch_time"),
+                timings.max_batch_time().as_secs_f64() * 1000.0,
+                "ms",
+            );
+            timings.reset();
+        }
+    }
+}
+
+/// The [`BatchTimings`] reported by the [`BatchTimingDiagnosticsPlugin`], by name.
+#[derive(Resource, Debug, Default)]
+pub struct ParallelBatchTimings {
+    timings: HashMap<String, BatchTimings>,
+}
+
+impl ParallelBatchTimings {
+    /// Returns the timings reported under `name`, registering them if needed.
+    ///
+    /// The returned timings share their counters with the registered ones.
+    pub fn register(&mut self, name: impl Into<String>) -> BatchTimings {
+        self.timings.entry(name.into()).or_default().clone()
+    }
+
+    /// Returns the timings reported under `name`, if they were registered.
+    pub fn get(&self, name: &str) -> Option<&BatchTimings> {
+        self.timings.get(name)
+    }
+
+    /// Stops reporting the timings registered under `name`.
+    pub fn unregister(&mut self, name: &str) -> Option<BatchTimings> {
+        self.timings.remove(name)
+    }
+
+    /// Iterates over the registered names and timings, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &BatchTimings)> {
+        self.timings
+            .iter()
+            .map(|(name, timings)| (name.as_str(), timings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DiagnosticsPlugin;
+    use bevy_utils::Duration;
+
+    #[test]
+    fn reports_and_resets_timings() {
+        let mut app = App::new();
+        app.add_plugins((DiagnosticsPlugin, BatchTimingDiagnosticsPlugin));
+        let timings = app
+            .world_mut()
+            .resource_mut::<ParallelBatchTimings>()
+            .register("movement");
+        timings.record(100, Duration::from_millis(2));
+        timings.record(50, Duration::from_millis(4));
+
+        app.update();
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let value = |name| {
+            store
+                .get_measurement(&BatchTimingDiagnosticsPlugin::path("movement", name))
+                .unwrap()
+                .value
+        };
+        assert_eq!(value("batches"), 2.0);
+        assert_eq!(value("items"), 150.0);
+        assert!((value("mean_batch_time") - 3.0).abs() < 1e-6);
+        assert!((value("max_batch_time") - 4.0).abs() < 1e-6);
+        assert_eq!(timings.batch_count(), 0);
+    }
+}
@@ -0,0 +1,248 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    component::ComponentId,
+    prelude::*,
+    world::{ComponentLifecycleCounts, ComponentLifecycleStats},
+};
+use bevy_utils::Instant;
+
+use crate::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore, RegisterDiagnostic,
+};
+
+/// Adds diagnostics about component insertions, removals and archetype moves to an App, to find
+/// the components responsible for archetype churn.
+///
+/// The plugin enables the [`ComponentLifecycleStats`] of the main world, and collects and clears
+/// them at the end of every frame: all the measurements are per frame.
+/// Besides the totals, a diagnostic is reported for each component with lifecycle events, under
+/// `component_churn/<component name>/inserted`, `.../removed`, `.../moved` and
+/// `.../caused_bytes_moved`. The counts of the last frame are available in the
+/// [`ComponentChurnReport`] resource.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+pub struct ComponentChurnDiagnosticsPlugin {
+    /// Whether to report a diagnostic for each component, on top of the totals.
+    pub per_component: bool,
+}
+
+impl Default for ComponentChurnDiagnosticsPlugin {
+    fn default() -> Self {
+        Self {
+            per_component: true,
+        }
+    }
+}
+
+impl Plugin for ComponentChurnDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.world_mut().lifecycle_stats_mut().set_enabled(true);
+        app.register_diagnostic(Diagnostic::new(Self::ARCHETYPE_MOVES))
+            .register_diagnostic(Diagnostic::new(Self::TABLE_MOVES))
+            .register_diagnostic(Diagnostic::new(Self::BYTES_MOVED).with_suffix("B"))
+            .init_resource::<ComponentChurnReport>();
+
+        if self.per_component {
+            app.add_systems(Last, Self::per_component_diagnostic_system);
+        } else {
+            app.add_systems(Last, Self::diagnostic_system);
+        }
+    }
+}
+
+impl ComponentChurnDiagnosticsPlugin {
+    pub const ARCHETYPE_MOVES: DiagnosticPath =
+        DiagnosticPath::const_new("component_churn/archetype_moves");
+    pub const TABLE_MOVES: DiagnosticPath =
+        DiagnosticPath::const_new("component_churn/table_moves");
+    pub const BYTES_MOVED: DiagnosticPath =
+        DiagnosticPath::const_new("component_churn/bytes_moved");
+
+    /// Returns the path of the diagnostic named `name` for the component named `component`.
+    pub fn component_path(component: &str, name: &str) -> DiagnosticPath {
+        DiagnosticPath::new(format!("component_churn/{component}/{name}"))
+    }
+
+    /// Updates the [`ComponentChurnReport`] and the total diagnostics.
+    pub fn diagnostic_system(world: &mut World) {
+        Self::update(world, false);
+    }
+
+    /// Updates the [`ComponentChurnReport`], the total diagnostics and the diagnostics of each
+    /// component.
+    pub fn per_component_diagnostic_system(world: &mut World) {
+        Self::update(world, true);
+    }
+
+    fn update(world: &mut World, per_component: bool) {
+        let report = ComponentChurnReport::collect(world);
+        world.lifecycle_stats_mut().clear();
+
+        let mut store = world.resource_mut::<DiagnosticsStore>();
+        let now = Instant::now();
+        let mut measure = |path: &DiagnosticPath, value: f64| {
+            if store.get(path).is_none() {
+                store.add(Diagnostic::new(path.clone()));
+            }
+            if let Some(diagnostic) = store.get_mut(path).filter(|d| d.is_enabled) {
+                diagnostic.add_measurement(DiagnosticMeasurement { time: now, value });
+            }
+        };
+
+        measure(&Self::ARCHETYPE_MOVES, report.archetype_moves as f64);
+        measure(&Self::TABLE_MOVES, report.table_moves as f64);
+        measure(&Self::BYTES_MOVED, report.bytes_moved as f64);
+
+        if per_component {
+            for component in &report.components {
+                let path = |name| Self::component_path(&component.name, name);
+                let counts = &component.counts;
+                measure(&path("inserted"), counts.inserted as f64);
+                measure(&path("removed"), counts.removed as f64);
+                measure(&path("moved"), counts.moved as f64);
+                measure(
+                    &path("caused_bytes_moved"),
+                    counts.caused_bytes_moved as f64,
+                );
+            }
+        }
+
+        world.insert_resource(report);
+    }
+}
+
+/// The lifecycle events of a component during the last frame.
+#[derive(Debug, Clone)]
+pub struct ComponentChurnStats {
+    /// The id of the component.
+    pub id: ComponentId,
+    /// The name of the component.
+    pub name: String,
+    /// The number of insertions, removals and moves of the component.
+    pub counts: ComponentLifecycleCounts,
+}
+
+/// The component lifecycle events of the last frame, as of the last update of the
+/// [`ComponentChurnDiagnosticsPlugin`].
+#[derive(Resource, Debug, Clone, Default)]
+pub struct ComponentChurnReport {
+    /// The components with lifecycle events.
+    pub components: Vec<ComponentChurnStats>,
+    /// See [`ComponentLifecycleStats::archetype_moves`].
+    pub archetype_moves: u64,
+    /// See [`ComponentLifecycleStats::table_moves`].
+    pub table_moves: u64,
+    /// See [`ComponentLifecycleStats::bytes_moved`].
+    pub bytes_moved: u64,
+}
+
+impl ComponentChurnReport {
+    /// Copies the [`ComponentLifecycleStats`] recorded by `world` since they were last cleared.
+    pub fn collect(world: &World) -> Self {
+        let stats: &ComponentLifecycleStats = world.lifecycle_stats();
+        let components = stats
+            .iter()
+            .map(|(id, counts)| ComponentChurnStats {
+                id,
+                name: world
+                    .components()
+                    .get_info(id)
+                    .map_or_else(|| format!("{id:?}"), |info| info.name().to_string()),
+                counts: *counts,
+            })
+            .collect();
+        Self {
+            components,
+            archetype_moves: stats.archetype_moves(),
+            table_moves: stats.table_moves(),
+            bytes_moved: stats.bytes_moved(),
+        }
+    }
+
+    /// Iterates over the components, from the one causing the most archetype churn to the least.
+    ///
+    /// Components are ranked by the bytes moved by the archetype moves they caused, then by the
+    /// number of moves.
+    pub fn hottest(&self) -> impl Iterator<Item = &ComponentChurnStats> {
+        let mut components: Vec<_> = self.components.iter().collect();
+        components.sort_by_key(|component| {
+            std::cmp::Reverse((
+                component.counts.caused_bytes_moved,
+                component.counts.caused_moves,
+            ))
+        });
+        components.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Component)]
+    struct Position([f32; 3]);
+
+    #[derive(Component)]
+    struct Burning;
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Selected;
+
+    fn churn(world: &mut World) {
+        let entities: Vec<_> = world
+            .query_filtered::<Entity, With<Position>>()
+            .iter(world)
+            .collect();
+        for entity in entities {
+            world.entity_mut(entity).insert((Burning, Selected));
+            world.entity_mut(entity).remove::<Burning>();
+        }
+    }
+
+    #[test]
+    fn reports_component_churn() {
+        let mut app = App::new();
+        app.add_plugins(ComponentChurnDiagnosticsPlugin::default())
+            .add_systems(Update, churn);
+        app.world_mut()
+            .spawn_batch((0..4).map(|_| Position([0.0; 3])));
+        app.update();
+
+        let report = app.world().resource::<ComponentChurnReport>();
+        assert_eq!(report.archetype_moves, 8);
+        assert_eq!(report.table_moves, 8);
+        assert_eq!(report.bytes_moved, 8 * 12);
+        let hottest = report.hottest().next().unwrap();
+        assert!(hottest.name.ends_with("Burning"));
+        assert_eq!(hottest.counts.caused_moves, 8);
+        let position = report
+            .components
+            .iter()
+            .find(|component| component.name.ends_with("Position"))
+            .unwrap();
+        assert_eq!(position.counts.inserted, 4);
+        assert_eq!(position.counts.moved, 8);
+
+        let store = app.world().resource::<DiagnosticsStore>();
+        let moves = store
+            .get_measurement(&ComponentChurnDiagnosticsPlugin::ARCHETYPE_MOVES)
+            .unwrap();
+        assert_eq!(moves.value, 8.0);
+        let path = ComponentChurnDiagnosticsPlugin::component_path(&hottest.name, "removed");
+        assert_eq!(store.get_measurement(&path).unwrap().value, 4.0);
+
+        app.update();
+        let report = app.world().resource::<ComponentChurnReport>();
+        assert_eq!(report.archetype_moves, 8);
+        let position = report
+            .components
+            .iter()
+            .find(|component| component.name.ends_with("Position"))
+            .unwrap();
+        assert_eq!(position.counts.inserted, 0);
+    }
+}
@@ -0,0 +1,327 @@
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    system::{Adapt, AdapterSystem},
+};
+use bevy_time::{Real, Time};
+use bevy_utils::{tracing::warn, Duration, HashMap, Instant};
+
+/// Checks the duration of frames and of budgeted systems against configured budgets, and reports
+/// the ones going over budget on average over a rolling window.
+///
+/// Systems are budgeted by wrapping them with [`FrameBudgets::budgeted`].
+/// Each run of a budgeted system counts as one sample, and each frame counts as one sample for the
+/// frame budget.
+/// When the average of the last [`window`](Self::window) samples exceeds the budget, a
+/// [`BudgetExceeded`] event is sent and the configured [`BudgetAction`] is taken.
+///
+/// Using [`BudgetAction::Panic`] in tests makes performance regressions fail the test run.
+///
+/// ```
+/// # use bevy_app::{App, Update};
+/// # use bevy_diagnostic::{FrameBudgetDiagnosticsPlugin, FrameBudgets};
+/// # use bevy_utils::Duration;
+/// fn pathfinding() {}
+///
+/// let mut app = App::new();
+/// app.add_plugins(FrameBudgetDiagnosticsPlugin::default());
+/// let system = app
+///     .world_mut()
+///     .resource_mut::<FrameBudgets>()
+///     .budgeted(pathfinding, Duration::from_millis(2));
+/// app.add_systems(Update, system);
+/// ```
+pub struct FrameBudgetDiagnosticsPlugin {
+    /// The budget for a whole frame, measured with [`Time<Real>`].
+    pub frame_budget: Option<Duration>,
+    /// The number of samples averaged before comparing them with the budget.
+    pub window: usize,
+    /// What to do when a budget is exceeded.
+    pub action: BudgetAction,
+}
+
+impl Default for FrameBudgetDiagnosticsPlugin {
+    fn default() -> Self {
+        Self {
+            frame_budget: None,
+            window: 60,
+            action: BudgetAction::Warn,
+        }
+    }
+}
+
+impl Plugin for FrameBudgetDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FrameBudgets {
+            window: self.window.max(1),
+            action: self.action,
+            frame: self.frame_budget.map(BudgetTracker::new),
+            systems: HashMap::default(),
+            samples: Arc::default(),
+        })
+        .add_event::<BudgetExceeded>()
+        .add_systems(Last, Self::check_budgets_system);
+    }
+}
+
+impl FrameBudgetDiagnosticsPlugin {
+    /// Compares the samples collected during this frame with their budgets.
+    pub fn check_budgets_system(
+        mut budgets: ResMut<FrameBudgets>,
+        time: Option<Res<Time<Real>>>,
+        mut exceeded: EventWriter<BudgetExceeded>,
+    ) {
+        let FrameBudgets {
+            window,
+            action,
+            frame,
+            systems,
+            samples,
+        } = &mut *budgets;
+
+        let mut report = |target: BudgetTarget, average: Duration, budget: Duration| {
+            let event = BudgetExceeded {
+                target,
+                average,
+                budget,
+                window: *window,
+            };
+            match action {
+                BudgetAction::Warn => warn!("{event}"),
+                BudgetAction::Panic => panic!("{event}"),
+            }
+            exceeded.send(event);
+        };
+
+        let samples = std::mem::take(&mut *samples.lock().unwrap());
+        for (name, sample) in samples {
+            let Some(tracker) = systems.get_mut(&name) else {
+                continue;
+            };
+            if let Some(average) = tracker.push(sample, *window) {
+                report(BudgetTarget::System(name), average, tracker.budget);
+            }
+        }
+
+        if let (Some(tracker), Some(time)) = (frame, time) {
+            let delta = time.delta();
+            if delta.is_zero() {
+                return;
+            }
+            if let Some(average) = tracker.push(delta, *window) {
+                report(BudgetTarget::Frame, average, tracker.budget);
+            }
+        }
+    }
+}
+
+/// What [`FrameBudgetDiagnosticsPlugin`] does when a budget is exceeded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BudgetAction {
+    /// Log a warning.
+    #[default]
+    Warn,
+    /// Panic, failing the test or the app.
+    Panic,
+}
+
+/// What a budget applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BudgetTarget {
+    /// The whole frame.
+    Frame,
+    /// The system with the given name.
+    System(Cow<'static, str>),
+}
+
+impl fmt::Display for BudgetTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetTarget::Frame => write!(f, "the frame"),
+            BudgetTarget::System(name) => write!(f, "system `{name}`"),
+        }
+    }
+}
+
+/// Sent by the [`FrameBudgetDiagnosticsPlugin`] when a budget is exceeded on average.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct BudgetExceeded {
+    /// The frame or system that went over budget.
+    pub target: BudgetTarget,
+    /// The average duration over the window.
+    pub average: Duration,
+    /// The configured budget.
+    pub budget: Duration,
+    /// The number of samples averaged.
+    pub window: usize,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} exceeded its budget of {:?}, taking {:?} on average over the last {} runs",
+            self.target, self.budget, self.average, self.window
+        )
+    }
+}
+
+/// The samples of a single budget.
+struct BudgetTracker {
+    budget: Duration,
+    samples: VecDeque<Duration>,
+}
+
+impl BudgetTracker {
+    fn new(budget: Duration) -> Self {
+        Self {
+            budget,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// Adds a sample, returning the average of the window if it is full and over budget.
+    ///
+    /// The window is cleared when over budget, so a regression is reported once per window.
+    fn push(&mut self, sample: Duration, window: usize) -> Option<Duration> {
+        self.samples.push_back(sample);
+        while self.samples.len() > window {
+            self.samples.pop_front();
+        }
+        if self.samples.len() < window {
+            return None;
+        }
+        let average = self.samples.iter().sum::<Duration>() / window as u32;
+        if average <= self.budget {
+            return None;
+        }
+        self.samples.clear();
+        Some(average)
+    }
+}
+
+type Samples = Arc<Mutex<Vec<(Cow<'static, str>, Duration)>>>;
+
+/// The budgets checked by the [`FrameBudgetDiagnosticsPlugin`].
+#[derive(Resource)]
+pub struct FrameBudgets {
+    window: usize,
+    action: BudgetAction,
+    frame: Option<BudgetTracker>,
+    systems: HashMap<Cow<'static, str>, BudgetTracker>,
+    samples: Samples,
+}
+
+impl FrameBudgets {
+    /// Wraps `system` so that its run time is measured and checked against `budget`.
+    ///
+    /// Budgets are identified by system name: budgeting two systems with the same name shares a
+    /// single budget between them.
+    pub fn budgeted<M, S: IntoSystem<(), (), M>>(
+        &mut self,
+        system: S,
+        budget: Duration,
+    ) -> BudgetedSystem<S::System> {
+        let system = IntoSystem::into_system(system);
+        let name = system.name();
+        self.systems
+            .insert(name.clone(), BudgetTracker::new(budget));
+        AdapterSystem::new(
+            BudgetTimer {
+                name: name.clone(),
+                samples: self.samples.clone(),
+            },
+            system,
+            name,
+        )
+    }
+
+    /// Sets the budget for a whole frame, or removes it.
+    pub fn set_frame_budget(&mut self, budget: Option<Duration>) {
+        self.frame = budget.map(BudgetTracker::new);
+    }
+
+    /// Sets what to do when a budget is exceeded.
+    pub fn set_action(&mut self, action: BudgetAction) {
+        self.action = action;
+    }
+}
+
+/// A system wrapped by [`FrameBudgets::budgeted`].
+pub type BudgetedSystem<S> = AdapterSystem<BudgetTimer, S>;
+
+/// Measures the run time of a [`BudgetedSystem`].
+pub struct BudgetTimer {
+    name: Cow<'static, str>,
+    samples: Samples,
+}
+
+impl<S: System> Adapt<S> for BudgetTimer {
+    type In = S::In;
+    type Out = S::Out;
+
+    fn adapt(&mut self, input: Self::In, run_system: impl FnOnce(S::In) -> S::Out) -> Self::Out {
+        let start = Instant::now();
+        let out = run_system(input);
+        let elapsed = start.elapsed();
+        self.samples
+            .lock()
+            .unwrap()
+            .push((self.name.clone(), elapsed));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slow_system() {
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    fn app(action: BudgetAction) -> App {
+        let mut app = App::new();
+        app.add_plugins(FrameBudgetDiagnosticsPlugin {
+            window: 2,
+            action,
+            ..Default::default()
+        });
+        let system = app
+            .world_mut()
+            .resource_mut::<FrameBudgets>()
+            .budgeted(slow_system, Duration::ZERO);
+        app.add_systems(Update, system);
+        app
+    }
+
+    #[test]
+    fn reports_system_over_budget() {
+        let mut app = app(BudgetAction::Warn);
+        app.update();
+        assert!(app.world().resource::<Events<BudgetExceeded>>().is_empty());
+
+        app.update();
+        let events = app.world().resource::<Events<BudgetExceeded>>();
+        let event = events.iter_current_update_events().next().unwrap();
+        assert!(
+            matches!(&event.target, BudgetTarget::System(name) if name.contains("slow_system"))
+        );
+        assert!(event.average >= Duration::from_millis(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeded its budget")]
+    fn panics_over_budget() {
+        let mut app = app(BudgetAction::Panic);
+        app.update();
+        app.update();
+    }
+}
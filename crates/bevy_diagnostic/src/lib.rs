@@ -11,18 +11,40 @@
 //! It allows users to easily add diagnostic functionality to their Bevy applications, enhancing
 //! their ability to monitor and optimize their game's.
 
+mod archetype_memory_diagnostics_plugin;
+mod batch_timing_diagnostics_plugin;
+mod component_churn_diagnostics_plugin;
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
+mod frame_budget_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+mod storage_advisor_plugin;
 #[cfg(feature = "sysinfo_plugin")]
 mod system_information_diagnostics_plugin;
 
 pub use diagnostic::*;
 
+pub use archetype_memory_diagnostics_plugin::{
+    ArchetypeMemoryDiagnosticsPlugin, ArchetypeMemoryReport, ArchetypeMemoryStats,
+    ComponentMemoryStats,
+};
+pub use batch_timing_diagnostics_plugin::{BatchTimingDiagnosticsPlugin, ParallelBatchTimings};
+pub use component_churn_diagnostics_plugin::{
+    ComponentChurnDiagnosticsPlugin, ComponentChurnReport, ComponentChurnStats,
+};
+
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+pub use frame_budget_diagnostics_plugin::{
+    BudgetAction, BudgetExceeded, BudgetTarget, BudgetTimer, BudgetedSystem,
+    FrameBudgetDiagnosticsPlugin, FrameBudgets,
+};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+pub use storage_advisor_plugin::{
+    update_storage_recommendations, StorageAdvisor, StorageAdvisorPlugin, StorageRecommendation,
+    StorageRecommendations,
+};
 #[cfg(feature = "sysinfo_plugin")]
 pub use system_information_diagnostics_plugin::{SystemInfo, SystemInformationDiagnosticsPlugin};
 
@@ -0,0 +1,291 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    component::{ComponentId, StorageType},
+    prelude::*,
+};
+use bevy_utils::{tracing::info, HashMap};
+
+use crate::{ComponentChurnDiagnosticsPlugin, ComponentChurnReport};
+
+/// Monitors the components whose storage type was left to its default, and recommends switching
+/// them between [`StorageType::Table`] and [`StorageType::SparseSet`] based on how often adding
+/// and removing them moves entities between archetypes, compared to how many of them are alive.
+///
+/// Table storage makes iteration fast but moves every table component of an entity when a
+/// component is added or removed, while sparse set storage makes adding and removing cheap but
+/// iteration slower.
+/// Each live component is assumed to be iterated once per frame, so the advisor compares the
+/// archetype moves caused by a component to its number of live instances, averaged over
+/// [`window`](Self::window) frames. Spawning and despawning entities doesn't count as churn.
+///
+/// Recommendations are kept in the [`StorageRecommendations`] resource.
+/// The storage type of a component is part of its type, so they can't be applied automatically:
+/// apply them with `#[component(storage = "...")]`, which also stops the component from being
+/// monitored.
+///
+/// This plugin adds the [`ComponentChurnDiagnosticsPlugin`] if it wasn't added before.
+pub struct StorageAdvisorPlugin {
+    /// The number of frames averaged before updating the recommendations.
+    pub window: u32,
+    /// The number of archetype moves per live instance above which [`StorageType::Table`]
+    /// components are recommended to use [`StorageType::SparseSet`].
+    pub sparse_set_threshold: f64,
+    /// The number of archetype moves per live instance below which [`StorageType::SparseSet`]
+    /// components are recommended to use [`StorageType::Table`].
+    pub table_threshold: f64,
+    /// Whether to also monitor the components with an explicit storage type.
+    pub include_explicit: bool,
+    /// Whether to log the recommendations when they change.
+    pub log_recommendations: bool,
+}
+
+impl Default for StorageAdvisorPlugin {
+    fn default() -> Self {
+        Self {
+            window: 300,
+            sparse_set_threshold: 0.1,
+            table_threshold: 0.01,
+            include_explicit: false,
+            log_recommendations: true,
+        }
+    }
+}
+
+impl Plugin for StorageAdvisorPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<ComponentChurnDiagnosticsPlugin>() {
+            app.add_plugins(ComponentChurnDiagnosticsPlugin {
+                per_component: false,
+            });
+        }
+        app.insert_resource(StorageAdvisor {
+            window: self.window.max(1),
+            sparse_set_threshold: self.sparse_set_threshold,
+            table_threshold: self.table_threshold,
+            include_explicit: self.include_explicit,
+            log_recommendations: self.log_recommendations,
+            frames: 0,
+            samples: HashMap::default(),
+        })
+        .init_resource::<StorageRecommendations>()
+        .add_systems(
+            Last,
+            update_storage_recommendations
+                .after(ComponentChurnDiagnosticsPlugin::diagnostic_system)
+                .after(ComponentChurnDiagnosticsPlugin::per_component_diagnostic_system),
+        );
+    }
+}
+
+/// The configuration and samples of the [`StorageAdvisorPlugin`].
+#[derive(Resource, Debug)]
+pub struct StorageAdvisor {
+    /// See [`StorageAdvisorPlugin::window`].
+    pub window: u32,
+    /// See [`StorageAdvisorPlugin::sparse_set_threshold`].
+    pub sparse_set_threshold: f64,
+    /// See [`StorageAdvisorPlugin::table_threshold`].
+    pub table_threshold: f64,
+    /// See [`StorageAdvisorPlugin::include_explicit`].
+    pub include_explicit: bool,
+    /// See [`StorageAdvisorPlugin::log_recommendations`].
+    pub log_recommendations: bool,
+    frames: u32,
+    samples: HashMap<ComponentId, StorageSample>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct StorageSample {
+    churn: u64,
+    instances: u64,
+}
+
+/// A storage type recommended by the [`StorageAdvisorPlugin`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageRecommendation {
+    /// The id of the component.
+    pub id: ComponentId,
+    /// The name of the component.
+    pub name: String,
+    /// The storage type the component currently uses.
+    pub current: StorageType,
+    /// The storage type the component should use.
+    pub recommended: StorageType,
+    /// The average number of archetype moves caused by the component per frame.
+    pub churn: f64,
+    /// The average number of live instances per frame.
+    pub instances: f64,
+}
+
+impl StorageRecommendation {
+    /// Returns the number of archetype moves per live instance, compared with the thresholds of
+    /// the [`StorageAdvisorPlugin`].
+    pub fn churn_ratio(&self) -> f64 {
+        self.churn / self.instances.max(1.0)
+    }
+}
+
+/// The storage types recommended by the [`StorageAdvisorPlugin`] at the end of its last window.
+///
+/// Only the components whose storage type should change are listed.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct StorageRecommendations {
+    pub recommendations: Vec<StorageRecommendation>,
+}
+
+impl StorageRecommendations {
+    /// Returns the recommendation for the component `id`, if its storage type should change.
+    pub fn get(&self, id: ComponentId) -> Option<&StorageRecommendation> {
+        self.recommendations
+            .iter()
+            .find(|recommendation| recommendation.id == id)
+    }
+}
+
+/// Samples the churn and live instances of the monitored components, and updates the
+/// [`StorageRecommendations`] at the end of each window.
+pub fn update_storage_recommendations(world: &mut World) {
+    world.resource_scope(|world, mut advisor: Mut<StorageAdvisor>| {
+        let include_explicit = advisor.include_explicit;
+        let monitored = |id: ComponentId| {
+            world
+                .components()
+                .get_info(id)
+                .is_some_and(|info| include_explicit || info.has_default_storage_type())
+        };
+
+        for component in &world.resource::<ComponentChurnReport>().components {
+            if monitored(component.id) {
+                let sample = advisor.samples.entry(component.id).or_default();
+                sample.churn += component.counts.caused_moves;
+            }
+        }
+        for archetype in world.archetypes().iter().filter(|a| !a.is_empty()) {
+            for id in archetype.components().filter(|&id| monitored(id)) {
+                advisor.samples.entry(id).or_default().instances += archetype.len() as u64;
+            }
+        }
+
+        advisor.frames += 1;
+        if advisor.frames < advisor.window {
+            return;
+        }
+        let frames = advisor.frames as f64;
+        advisor.frames = 0;
+
+        let advisor = &mut *advisor;
+        let mut recommendations = Vec::new();
+        for (id, sample) in advisor.samples.drain() {
+            let Some(info) = world.components().get_info(id) else {
+                continue;
+            };
+            let churn = sample.churn as f64 / frames;
+            let instances = sample.instances as f64 / frames;
+            let ratio = churn / instances.max(1.0);
+            let recommended = match info.storage_type() {
+                StorageType::Table if ratio > advisor.sparse_set_threshold => {
+                    StorageType::SparseSet
+                }
+                StorageType::SparseSet if ratio < advisor.table_threshold => StorageType::Table,
+                _ => continue,
+            };
+            recommendations.push(StorageRecommendation {
+                id,
+                name: info.name().to_string(),
+                current: info.storage_type(),
+                recommended,
+                churn,
+                instances,
+            });
+        }
+        recommendations.sort_by_key(|recommendation| recommendation.id);
+
+        let mut previous = world.resource_mut::<StorageRecommendations>();
+        if advisor.log_recommendations {
+            for recommendation in &recommendations {
+                let changed = previous
+                    .get(recommendation.id)
+                    .map_or(true, |previous| {
+                        previous.recommended != recommendation.recommended
+                    });
+                if changed {
+                    info!(
+                        "Component `{}` would be better stored in a {:?}: {:.1} archetype moves per frame for {:.1} live instances",
+                        recommendation.name,
+                        recommendation.recommended,
+                        recommendation.churn,
+                        recommendation.instances
+                    );
+                }
+            }
+        }
+        previous.recommendations = recommendations;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Component)]
+    struct Position([f32; 3]);
+
+    #[derive(Component)]
+    struct Burning;
+
+    #[derive(Component)]
+    #[component(storage = "Table")]
+    struct Frozen;
+
+    #[allow(dead_code)]
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Team(u8);
+
+    fn churn(world: &mut World) {
+        let entities: Vec<_> = world
+            .query_filtered::<Entity, With<Position>>()
+            .iter(world)
+            .collect();
+        for entity in entities {
+            world.entity_mut(entity).insert((Burning, Frozen));
+            world.entity_mut(entity).remove::<(Burning, Frozen)>();
+        }
+    }
+
+    #[test]
+    fn recommends_storage_types() {
+        let mut app = App::new();
+        app.add_plugins(StorageAdvisorPlugin {
+            window: 2,
+            include_explicit: false,
+            log_recommendations: false,
+            ..Default::default()
+        })
+        .add_systems(Update, churn);
+        app.world_mut()
+            .spawn_batch((0..4).map(|_| (Position([0.0; 3]), Team(0))));
+
+        app.update();
+        assert!(app
+            .world()
+            .resource::<StorageRecommendations>()
+            .recommendations
+            .is_empty());
+
+        app.update();
+        let [position, burning, frozen] = [
+            app.world_mut().init_component::<Position>(),
+            app.world_mut().init_component::<Burning>(),
+            app.world_mut().init_component::<Frozen>(),
+        ];
+        let recommendations = app.world().resource::<StorageRecommendations>();
+        assert_eq!(recommendations.recommendations.len(), 1);
+        let burning = recommendations.get(burning).unwrap();
+        assert_eq!(burning.current, StorageType::Table);
+        assert_eq!(burning.recommended, StorageType::SparseSet);
+        assert!(recommendations.get(position).is_none());
+        assert!(recommendations.get(frozen).is_none());
+    }
+}
@@ -0,0 +1,58 @@
+//! Name-based access to components and resources for dynamically loaded plugins.
+//!
+//! A dynamic plugin and the app loading it don't necessarily agree on [`TypeId`](std::any::TypeId)s,
+//! so instead of naming types directly, plugins can look them up by their reflected type path in
+//! the [`AppTypeRegistry`] and work with the resulting [`ComponentId`]s and reflection data.
+
+use bevy_ecs::{
+    component::ComponentId,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+    world::World,
+};
+use bevy_reflect::{TypeRegistration, TypeRegistry};
+
+/// Looks up a registration by its full type path, falling back to its short type path.
+fn registration_by_name<'a>(
+    registry: &'a TypeRegistry,
+    name: &str,
+) -> Option<&'a TypeRegistration> {
+    registry
+        .get_with_type_path(name)
+        .or_else(|| registry.get_with_short_type_path(name))
+}
+
+/// Returns the [`ComponentId`] of the component registered under the given type path.
+///
+/// Returns `None` if the world has no [`AppTypeRegistry`], if no type is registered under that
+/// name, or if the component hasn't been initialized in the world yet.
+pub fn component_id_by_name(world: &World, name: &str) -> Option<ComponentId> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    let registration = registration_by_name(&registry, name)?;
+    world.components().get_id(registration.type_id())
+}
+
+/// Returns the [`ComponentId`] of the resource registered under the given type path.
+///
+/// Returns `None` if the world has no [`AppTypeRegistry`], if no type is registered under that
+/// name, or if the resource hasn't been initialized in the world yet.
+pub fn resource_id_by_name(world: &World, name: &str) -> Option<ComponentId> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    let registration = registration_by_name(&registry, name)?;
+    world.components().get_resource_id(registration.type_id())
+}
+
+/// Returns the [`ReflectComponent`] of the component registered under the given type path.
+pub fn reflect_component_by_name(world: &World, name: &str) -> Option<ReflectComponent> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    registration_by_name(&registry, name)?
+        .data::<ReflectComponent>()
+        .cloned()
+}
+
+/// Returns the [`ReflectResource`] of the resource registered under the given type path.
+pub fn reflect_resource_by_name(world: &World, name: &str) -> Option<ReflectResource> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    registration_by_name(&registry, name)?
+        .data::<ReflectResource>()
+        .cloned()
+}
@@ -9,6 +9,12 @@
 //! This crate allows loading dynamic libraries (`.dylib`, `.so`) that export a single
 //! [`Plugin`](bevy_app::Plugin). For usage, see [`dynamically_load_plugin`].
 //!
+//! Loaded libraries are tracked in the [`DynamicPlugins`] resource. A library can additionally
+//! export [`DynamicPluginHooks`] to run code when it is loaded or unloaded, and to opt into being
+//! unloaded at runtime with [`unload_dynamic_plugin`]. Since [`TypeId`](std::any::TypeId)s are not
+//! stable across compilations, the [`bridge`] module lets plugins address components and resources
+//! by their reflected type path instead.
+//!
 //! Note that dynamic linking and loading is inherently unsafe because it allows executing foreign
 //! code. Additionally, Rust does not have a stable ABI and may produce
 //! incompatible libraries across Rust versions, or even subsequent compilations. This will not work
@@ -25,6 +31,7 @@
 //! [Bevy Assets - Development tools]: https://bevyengine.org/assets/#development-tools
 //! [`stabby`]: https://github.com/ZettaScaleLabs/stabby
 
+pub mod bridge;
 mod loader;
 
 pub use loader::*;
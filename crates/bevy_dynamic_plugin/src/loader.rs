@@ -2,9 +2,11 @@
 
 use libloading::{Library, Symbol};
 use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use bevy_app::{App, CreatePlugin, Plugin};
+use bevy_ecs::{system::Resource, world::World};
 
 /// Errors that can occur when loading a dynamic plugin
 #[derive(Debug, Error)]
@@ -17,6 +19,109 @@ pub enum DynamicPluginLoadError {
     Plugin(#[source] libloading::Error),
 }
 
+/// Errors that can occur when unloading a dynamic plugin
+#[derive(Debug, Error)]
+pub enum DynamicPluginUnloadError {
+    /// No dynamic plugin with the given name is loaded.
+    #[error("no dynamic plugin named {0} is loaded")]
+    NotLoaded(String),
+    /// The dynamic plugin didn't opt into being unloaded.
+    #[error("dynamic plugin {0} does not support being unloaded")]
+    NotUnloadable(String),
+    /// An error occurred when closing the dynamic library.
+    #[error("cannot unload library for dynamic plugin: {0}")]
+    Library(#[source] libloading::Error),
+}
+
+/// Lifecycle hooks of a dynamic plugin.
+///
+/// A library can provide them by exporting a function with the [`GetDynamicPluginHooks`]
+/// signature named `_bevy_dynamic_plugin_hooks`. Libraries that don't export it get the default
+/// hooks, which do nothing and don't allow unloading.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct DynamicPluginHooks {
+    /// Called once the plugin has been built, after [`Plugin::build`].
+    pub on_load: Option<fn(&mut World)>,
+    /// Called right before the library is unloaded with [`unload_dynamic_plugin`].
+    ///
+    /// This must remove every system, observer, hook, type registration or other piece of
+    /// code or static data from the library that the app still references.
+    pub on_unload: Option<fn(&mut World)>,
+    /// Whether the library may be unloaded at runtime with [`unload_dynamic_plugin`].
+    pub unloadable: bool,
+}
+
+/// A function exported by a dynamic library as `_bevy_dynamic_plugin_hooks` to provide its
+/// [`DynamicPluginHooks`].
+pub type GetDynamicPluginHooks = unsafe fn() -> DynamicPluginHooks;
+
+/// A dynamic library loaded with [`DynamicPluginExt::load_plugin`].
+pub struct LoadedDynamicPlugin {
+    name: String,
+    path: PathBuf,
+    hooks: DynamicPluginHooks,
+    library: Option<Library>,
+}
+
+impl LoadedDynamicPlugin {
+    /// The [name](Plugin::name) of the plugin exported by the library.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The path the library was loaded from.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The lifecycle hooks exported by the library.
+    pub fn hooks(&self) -> &DynamicPluginHooks {
+        &self.hooks
+    }
+}
+
+impl Drop for LoadedDynamicPlugin {
+    fn drop(&mut self) {
+        // Code from the library may still be referenced by the app (systems, hooks, ...), so the
+        // library is only ever closed explicitly through `unload_dynamic_plugin`.
+        std::mem::forget(self.library.take());
+    }
+}
+
+/// A [`Resource`] listing the dynamic plugins loaded with [`DynamicPluginExt::load_plugin`].
+#[derive(Resource, Default)]
+pub struct DynamicPlugins {
+    loaded: Vec<LoadedDynamicPlugin>,
+}
+
+impl DynamicPlugins {
+    /// Returns the loaded dynamic plugin with the given [name](Plugin::name), if any.
+    pub fn get(&self, name: &str) -> Option<&LoadedDynamicPlugin> {
+        self.loaded.iter().find(|plugin| plugin.name == name)
+    }
+
+    /// Iterates over all loaded dynamic plugins, in the order they were loaded.
+    pub fn iter(&self) -> impl Iterator<Item = &LoadedDynamicPlugin> {
+        self.loaded.iter()
+    }
+
+    /// Removes the loaded dynamic plugin with the given name, if it can be unloaded.
+    fn remove_unloadable(
+        &mut self,
+        name: &str,
+    ) -> Result<LoadedDynamicPlugin, DynamicPluginUnloadError> {
+        let index = self
+            .loaded
+            .iter()
+            .position(|plugin| plugin.name == name)
+            .ok_or_else(|| DynamicPluginUnloadError::NotLoaded(name.to_string()))?;
+        if !self.loaded[index].hooks.unloadable {
+            return Err(DynamicPluginUnloadError::NotUnloadable(name.to_string()));
+        }
+        Ok(self.loaded.remove(index))
+    }
+}
+
 /// Dynamically links a plugin at the given path. The plugin must export a function with the
 /// [`CreatePlugin`] signature named `_bevy_create_plugin`.
 ///
@@ -50,24 +155,187 @@ pub unsafe fn dynamically_load_plugin<P: AsRef<OsStr>>(
     Ok((lib, plugin))
 }
 
+/// Reads the [`DynamicPluginHooks`] exported by `lib`, if any.
+///
+/// # Safety
+///
+/// If present, the `_bevy_dynamic_plugin_hooks` symbol must have the [`GetDynamicPluginHooks`]
+/// signature.
+unsafe fn dynamic_plugin_hooks(lib: &Library) -> DynamicPluginHooks {
+    // SAFETY: Caller guarantees the symbol has the `GetDynamicPluginHooks` signature.
+    match unsafe { lib.get::<GetDynamicPluginHooks>(b"_bevy_dynamic_plugin_hooks") } {
+        // SAFETY: Caller guarantees the symbol has the `GetDynamicPluginHooks` signature.
+        Ok(func) => unsafe { func() },
+        Err(_) => DynamicPluginHooks::default(),
+    }
+}
+
+/// Unloads the dynamic plugin with the given [name](Plugin::name), running its
+/// [`on_unload`](DynamicPluginHooks::on_unload) hook and closing its library.
+///
+/// Only plugins whose hooks set [`unloadable`](DynamicPluginHooks::unloadable) can be unloaded.
+///
+/// # Safety
+///
+/// After the `on_unload` hook ran, nothing in the `world` (or anywhere else in the program) may
+/// still reference code or static data from the library, such as systems, component hooks or
+/// type registrations. Dropping such a reference after the library is closed is undefined behavior.
+pub unsafe fn unload_dynamic_plugin(
+    world: &mut World,
+    name: &str,
+) -> Result<(), DynamicPluginUnloadError> {
+    // Without the resource, no dynamic plugin was loaded.
+    let mut plugin = world
+        .get_resource_mut::<DynamicPlugins>()
+        .ok_or_else(|| DynamicPluginUnloadError::NotLoaded(name.to_string()))?
+        .remove_unloadable(name)?;
+
+    if let Some(on_unload) = plugin.hooks.on_unload {
+        on_unload(world);
+    }
+
+    match plugin.library.take() {
+        Some(library) => library.close().map_err(DynamicPluginUnloadError::Library),
+        None => Ok(()),
+    }
+}
+
 /// An extension trait for [`App`] that allows loading dynamic plugins.
 pub trait DynamicPluginExt {
     /// Dynamically links a plugin at the given path, registering the plugin.
     ///
+    /// The library is kept loaded and listed in the [`DynamicPlugins`] resource. If it exports
+    /// [`DynamicPluginHooks`], the [`on_load`](DynamicPluginHooks::on_load) hook runs once the
+    /// plugin has been built.
+    ///
     /// For more details, see [`dynamically_load_plugin`].
     ///
     /// # Safety
     ///
-    /// See [`dynamically_load_plugin`]'s safety section.
+    /// See [`dynamically_load_plugin`]'s safety section. In addition, if the library exports a
+    /// `_bevy_dynamic_plugin_hooks` symbol, it must have the [`GetDynamicPluginHooks`] signature.
     unsafe fn load_plugin<P: AsRef<OsStr>>(&mut self, path: P) -> &mut Self;
 }
 
 impl DynamicPluginExt for App {
     unsafe fn load_plugin<P: AsRef<OsStr>>(&mut self, path: P) -> &mut Self {
+        let path = PathBuf::from(path.as_ref());
         // SAFETY: Follows the same safety requirements as `dynamically_load_plugin`.
-        let (lib, plugin) = unsafe { dynamically_load_plugin(path).unwrap() };
-        std::mem::forget(lib); // Ensure that the library is not automatically unloaded
-        plugin.build(self);
+        let (lib, plugin) = unsafe { dynamically_load_plugin(&path).unwrap() };
+        // SAFETY: Caller guarantees the hooks symbol has the right signature.
+        let hooks = unsafe { dynamic_plugin_hooks(&lib) };
+        add_dynamic_plugin(self, plugin.as_ref(), hooks, path, Some(lib));
         self
     }
 }
+
+/// Builds `plugin`, runs its [`on_load`](DynamicPluginHooks::on_load) hook and lists it in the
+/// [`DynamicPlugins`] resource.
+fn add_dynamic_plugin(
+    app: &mut App,
+    plugin: &dyn Plugin,
+    hooks: DynamicPluginHooks,
+    path: PathBuf,
+    library: Option<Library>,
+) {
+    plugin.build(app);
+    if let Some(on_load) = hooks.on_load {
+        on_load(app.world_mut());
+    }
+    app.world_mut()
+        .get_resource_or_insert_with(DynamicPlugins::default)
+        .loaded
+        .push(LoadedDynamicPlugin {
+            name: plugin.name().to_string(),
+            path,
+            hooks,
+            library,
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The lifecycle events of [`TestPlugin`], in the order they happened.
+    #[derive(Resource, Default)]
+    struct Events(Vec<&'static str>);
+
+    struct TestPlugin;
+
+    impl Plugin for TestPlugin {
+        fn build(&self, app: &mut App) {
+            app.init_resource::<Events>();
+            app.world_mut().resource_mut::<Events>().0.push("build");
+        }
+    }
+
+    fn hooks() -> DynamicPluginHooks {
+        DynamicPluginHooks {
+            on_load: Some(|world| world.resource_mut::<Events>().0.push("load")),
+            on_unload: Some(|world| world.resource_mut::<Events>().0.push("unload")),
+            unloadable: true,
+        }
+    }
+
+    #[test]
+    fn hooks_run_in_order() {
+        let mut app = App::new();
+        add_dynamic_plugin(&mut app, &TestPlugin, hooks(), "test".into(), None);
+        assert_eq!(app.world().resource::<Events>().0, ["build", "load"]);
+        let plugins = app.world().resource::<DynamicPlugins>();
+        let plugin = plugins.get(TestPlugin.name()).unwrap();
+        assert_eq!(plugin.path(), Path::new("test"));
+
+        // SAFETY: The plugin isn't loaded from a library.
+        unsafe { unload_dynamic_plugin(app.world_mut(), TestPlugin.name()) }.unwrap();
+        assert_eq!(
+            app.world().resource::<Events>().0,
+            ["build", "load", "unload"]
+        );
+        assert!(app
+            .world()
+            .resource::<DynamicPlugins>()
+            .get(TestPlugin.name())
+            .is_none());
+
+        // SAFETY: The plugin isn't loaded from a library.
+        let result = unsafe { unload_dynamic_plugin(app.world_mut(), TestPlugin.name()) };
+        assert!(matches!(
+            result,
+            Err(DynamicPluginUnloadError::NotLoaded(_))
+        ));
+    }
+
+    #[test]
+    fn unloading_without_dynamic_plugins_fails() {
+        let mut app = App::new();
+
+        // SAFETY: No plugin is loaded.
+        let result = unsafe { unload_dynamic_plugin(app.world_mut(), TestPlugin.name()) };
+        assert!(matches!(
+            result,
+            Err(DynamicPluginUnloadError::NotLoaded(_))
+        ));
+    }
+
+    #[test]
+    fn plugins_are_not_unloadable_by_default() {
+        let mut app = App::new();
+        let hooks = DynamicPluginHooks::default();
+        add_dynamic_plugin(&mut app, &TestPlugin, hooks, "test".into(), None);
+
+        // SAFETY: The plugin isn't loaded from a library.
+        let result = unsafe { unload_dynamic_plugin(app.world_mut(), TestPlugin.name()) };
+        assert!(matches!(
+            result,
+            Err(DynamicPluginUnloadError::NotUnloadable(_))
+        ));
+        assert!(app
+            .world()
+            .resource::<DynamicPlugins>()
+            .get(TestPlugin.name())
+            .is_some());
+        assert_eq!(app.world().resource::<Events>().0, ["build"]);
+    }
+}
@@ -49,6 +49,16 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     };
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
+    let default_storage = (!attrs.explicit_storage).then(|| {
+        quote! {
+            const STORAGE_TYPE_IS_DEFAULT: bool = true;
+        }
+    });
+    let singleton = attrs.singleton.then(|| {
+        quote! {
+            const SINGLETON: bool = true;
+        }
+    });
 
     ast.generics
         .make_where_clause()
@@ -61,15 +71,20 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             const STORAGE_TYPE: #bevy_ecs_path::component::StorageType = #storage;
+            #default_storage
+            #singleton
         }
     })
 }
 
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
+pub const SINGLETON: &str = "singleton";
 
 struct Attrs {
     storage: StorageTy,
+    explicit_storage: bool,
+    singleton: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -85,6 +100,8 @@ const SPARSE_SET: &str = "SparseSet";
 fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
     let mut attrs = Attrs {
         storage: StorageTy::Table,
+        explicit_storage: false,
+        singleton: false,
     };
 
     for meta in ast.attrs.iter().filter(|a| a.path().is_ident(COMPONENT)) {
@@ -99,6 +116,10 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                         )));
                     }
                 };
+                attrs.explicit_storage = true;
+                Ok(())
+            } else if nested.path.is_ident(SINGLETON) {
+                attrs.singleton = true;
                 Ok(())
             } else {
                 Err(nested.error("Unsupported attribute"))
@@ -27,6 +27,7 @@ enum BundleFieldKind {
 
 const BUNDLE_ATTRIBUTE_NAME: &str = "bundle";
 const BUNDLE_ATTRIBUTE_IGNORE_NAME: &str = "ignore";
+const BUNDLE_ATTRIBUTE_ON_SPAWN_NAME: &str = "on_spawn";
 
 #[proc_macro_derive(Bundle, attributes(bundle))]
 pub fn derive_bundle(input: TokenStream) -> TokenStream {
@@ -38,6 +39,26 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
         Err(e) => return e.into_compile_error().into(),
     };
 
+    let mut on_spawn = None;
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident(BUNDLE_ATTRIBUTE_NAME))
+    {
+        if let Err(error) = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(BUNDLE_ATTRIBUTE_ON_SPAWN_NAME) {
+                on_spawn = Some(meta.value()?.parse::<syn::ExprPath>()?);
+                Ok(())
+            } else {
+                Err(meta.error(format!(
+                    "Invalid bundle attribute. Use `{BUNDLE_ATTRIBUTE_ON_SPAWN_NAME} = ...`"
+                )))
+            }
+        }) {
+            return error.into_compile_error().into();
+        }
+    }
+
     let mut field_kind = Vec::with_capacity(named_fields.len());
 
     for field in named_fields {
@@ -76,6 +97,7 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
     let mut field_component_ids = Vec::new();
     let mut field_get_components = Vec::new();
     let mut field_from_components = Vec::new();
+    let mut field_on_spawn = Vec::new();
     for (((i, field_type), field_kind), field) in field_type
         .iter()
         .enumerate()
@@ -87,6 +109,9 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
                 field_component_ids.push(quote! {
                 <#field_type as #ecs_path::bundle::Bundle>::component_ids(components, storages, &mut *ids);
                 });
+                field_on_spawn.push(quote! {
+                    <#field_type as #ecs_path::bundle::Bundle>
+                });
                 match field {
                     Some(field) => {
                         field_get_components.push(quote! {
@@ -118,6 +143,8 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
     let generics = ast.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     let struct_name = &ast.ident;
+    let has_on_spawn = on_spawn.is_some();
+    let on_spawn = on_spawn.map(|on_spawn| quote! { #on_spawn(entity); });
 
     TokenStream::from(quote! {
         // SAFETY:
@@ -142,6 +169,14 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
                     #(#field_from_components)*
                 }
             }
+
+            const HAS_ON_SPAWN: bool = #has_on_spawn #(|| #field_on_spawn::HAS_ON_SPAWN)*;
+
+            #[allow(unused_variables)]
+            fn on_spawn(entity: &mut #ecs_path::world::EntityWorldMut) {
+                #(#field_on_spawn::on_spawn(entity);)*
+                #on_spawn
+            }
         }
 
         impl #impl_generics #ecs_path::bundle::DynamicBundle for #struct_name #ty_generics #where_clause {
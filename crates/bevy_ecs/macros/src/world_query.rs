@@ -164,6 +164,10 @@ pub(crate) fn world_query_impl(
                 #( <#field_types>::update_component_access(&state.#named_field_idents, _access); )*
             }
 
+            fn update_traversal_access(state: &Self::State, _access: &mut #path::query::Access<#path::component::ComponentId>) {
+                #( <#field_types>::update_traversal_access(&state.#named_field_idents, _access); )*
+            }
+
             fn init_state(world: &mut #path::world::World) -> #state_struct_name #user_ty_generics {
                 #state_struct_name {
                     #(#named_field_idents: <#field_types>::init_state(world),)*
@@ -23,7 +23,9 @@ use crate::{
     bundle::BundleId,
     component::{ComponentId, Components, StorageType},
     entity::{Entity, EntityLocation},
-    storage::{ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow},
+    storage::{
+        shrink_vec, ImmutableSparseSet, SparseArray, SparseSet, SparseSetIndex, TableId, TableRow,
+    },
 };
 use std::{
     hash::Hash,
@@ -532,6 +534,30 @@ impl Archetype {
         }
     }
 
+    /// Sorts the entities of the archetype by [`TableRow`], so that iterating the archetype
+    /// walks its table in order. Returns `true` if any entity was moved.
+    ///
+    /// The [`ArchetypeRow`] of the moved entities is stale afterwards: the caller must update
+    /// their locations.
+    pub(crate) fn sort_entities_by_table_row(&mut self) -> bool {
+        if self
+            .entities
+            .windows(2)
+            .all(|w| w[0].table_row.as_usize() <= w[1].table_row.as_usize())
+        {
+            return false;
+        }
+        self.entities
+            .sort_unstable_by_key(|archetype_entity| archetype_entity.table_row.as_usize());
+        true
+    }
+
+    /// Shrinks the entity list of the archetype to its length, returning the number of bytes
+    /// freed.
+    pub(crate) fn shrink_to_fit(&mut self) -> usize {
+        shrink_vec(&mut self.entities)
+    }
+
     /// Gets the total number of entities that belong to the archetype.
     #[inline]
     pub fn len(&self) -> usize {
@@ -773,6 +799,20 @@ impl Archetypes {
         self.archetypes.iter()
     }
 
+    /// Gets the archetype id matching the given components, if it exists.
+    /// `table_components` and `sparse_set_components` must be sorted
+    pub(crate) fn get_id(
+        &self,
+        table_components: &[ComponentId],
+        sparse_set_components: &[ComponentId],
+    ) -> Option<ArchetypeId> {
+        let archetype_identity = ArchetypeComponents {
+            table_components: table_components.into(),
+            sparse_set_components: sparse_set_components.into(),
+        };
+        self.by_components.get(&archetype_identity).copied()
+    }
+
     /// Gets the archetype id matching the given inputs or inserts a new one if it doesn't exist.
     /// `table_components` and `sparse_set_components` must be sorted
     ///
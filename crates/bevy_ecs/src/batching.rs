@@ -1,6 +1,14 @@
 //! Types for controlling batching behavior during parallel processing.
 
-use std::ops::Range;
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use bevy_utils::Duration;
 
 /// Dictates how a parallel operation chunks up large quantities
 /// during iteration.
@@ -14,7 +22,11 @@ use std::ops::Range;
 /// of threads (rounded up). This attempts to minimize the overhead of scheduling
 /// tasks onto multiple threads, but assumes each entity has roughly the
 /// same amount of work to be done, which may not hold true in every
-/// workload.
+/// workload. For such workloads, parallel queries can instead run one task per matched
+/// storage, or let threads claim small chunks of entities until none is left, see [`BatchMode`].
+///
+/// The time taken by each batch of a parallel query can be recorded into [`BatchTimings`], to be
+/// reported to diagnostics.
 ///
 /// See [`Query::par_iter`], [`EventReader::par_read`] for more information.
 ///
@@ -37,6 +49,147 @@ pub struct BatchingStrategy {
     ///
     /// [`ComputeTaskPool`]: bevy_tasks::ComputeTaskPool
     pub batches_per_thread: usize,
+    /// How a parallel query splits its matched entities into batches.
+    ///
+    /// Other parallel iterators always use [`BatchMode::Uniform`].
+    ///
+    /// Defaults to [`BatchMode::Uniform`].
+    pub mode: BatchMode,
+    /// Where the time taken by each batch of a parallel query is recorded, if anywhere.
+    ///
+    /// Nothing is recorded when the query isn't iterated in parallel, for instance when the
+    /// [`ComputeTaskPool`](bevy_tasks::ComputeTaskPool) has a single thread.
+    ///
+    /// Defaults to `None`.
+    pub timings: Option<BatchTimings>,
+}
+
+/// How a parallel query splits its matched entities into batches, see [`BatchingStrategy`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BatchMode {
+    /// Storages larger than the batch size are split into batches of that size, and smaller ones
+    /// are merged together, each batch being spawned as its own task.
+    #[default]
+    Uniform,
+    /// Each matched table or archetype is iterated by its own task, whatever its size.
+    ///
+    /// This suits queries whose cost per entity depends on the archetype, for instance when
+    /// optional components are only present in some of them.
+    PerStorage,
+    /// One task is spawned per thread, and each task repeatedly claims the next chunk of
+    /// batch size entities until every entity has been claimed.
+    ///
+    /// Threads that finish their chunks early keep claiming more, so uneven workloads are
+    /// balanced at the cost of some synchronization per chunk. The batch size is computed
+    /// from the total number of matched entities rather than the size of the largest storage.
+    Adaptive,
+}
+
+/// Per-batch timings of parallel queries, recorded when set in [`BatchingStrategy::timings`].
+///
+/// Clones share the same counters, so a clone can be kept, for instance by a diagnostics plugin,
+/// to read the timings recorded by the queries using the strategy.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::batching::{BatchTimings, BatchingStrategy};
+/// # use bevy_tasks::{ComputeTaskPool, TaskPool};
+/// # ComputeTaskPool::get_or_init(TaskPool::default);
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// let mut world = World::new();
+/// world.spawn_batch((0..1000).map(|_| Health(1.0)));
+///
+/// let timings = BatchTimings::new();
+/// let mut query = world.query::<&mut Health>();
+/// query
+///     .par_iter_mut(&mut world)
+///     .batching_strategy(BatchingStrategy::adaptive().with_timings(timings.clone()))
+///     .for_each(|mut health| health.0 -= 0.1);
+///
+/// if let Some(mean) = timings.mean_batch_time() {
+///     println!("{} batches, {mean:?} on average", timings.batch_count());
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BatchTimings {
+    inner: Arc<BatchTimingsInner>,
+}
+
+#[derive(Debug, Default)]
+struct BatchTimingsInner {
+    batches: AtomicUsize,
+    items: AtomicUsize,
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl BatchTimings {
+    /// Creates empty timings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a batch of `items` entities that took `duration` to run.
+    pub fn record(&self, items: usize, duration: Duration) {
+        let nanos = duration.as_nanos().min(u64::MAX as u128) as u64;
+        let inner = &self.inner;
+        inner.batches.fetch_add(1, Ordering::Relaxed);
+        inner.items.fetch_add(items, Ordering::Relaxed);
+        inner.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        inner.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// The number of batches recorded since the last [`reset`](Self::reset).
+    pub fn batch_count(&self) -> usize {
+        self.inner.batches.load(Ordering::Relaxed)
+    }
+
+    /// The number of entities visited by the batches recorded since the last
+    /// [`reset`](Self::reset).
+    pub fn item_count(&self) -> usize {
+        self.inner.items.load(Ordering::Relaxed)
+    }
+
+    /// The total time taken by the batches recorded since the last [`reset`](Self::reset).
+    pub fn total_time(&self) -> Duration {
+        Duration::from_nanos(self.inner.total_nanos.load(Ordering::Relaxed))
+    }
+
+    /// The time taken by the longest batch recorded since the last [`reset`](Self::reset).
+    pub fn max_batch_time(&self) -> Duration {
+        Duration::from_nanos(self.inner.max_nanos.load(Ordering::Relaxed))
+    }
+
+    /// The average time taken by the batches recorded since the last [`reset`](Self::reset),
+    /// or `None` if none was recorded.
+    pub fn mean_batch_time(&self) -> Option<Duration> {
+        let batches = self.batch_count();
+        (batches > 0).then(|| self.total_time() / batches as u32)
+    }
+
+    /// Clears the recorded timings.
+    pub fn reset(&self) {
+        let inner = &self.inner;
+        inner.batches.store(0, Ordering::Relaxed);
+        inner.items.store(0, Ordering::Relaxed);
+        inner.total_nanos.store(0, Ordering::Relaxed);
+        inner.max_nanos.store(0, Ordering::Relaxed);
+    }
+
+    /// Runs `f` on a batch of `items` entities, recording its duration into `timings` if set.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+    #[inline]
+    pub(crate) fn time<R>(timings: Option<&Self>, items: usize, f: impl FnOnce() -> R) -> R {
+        let Some(timings) = timings else {
+            return f();
+        };
+        let start = bevy_utils::Instant::now();
+        let result = f();
+        timings.record(items, start.elapsed());
+        result
+    }
 }
 
 impl Default for BatchingStrategy {
@@ -51,6 +204,8 @@ impl BatchingStrategy {
         Self {
             batch_size_limits: 1..usize::MAX,
             batches_per_thread: 1,
+            mode: BatchMode::Uniform,
+            timings: None,
         }
     }
 
@@ -59,9 +214,47 @@ impl BatchingStrategy {
         Self {
             batch_size_limits: batch_size..batch_size,
             batches_per_thread: 1,
+            mode: BatchMode::Uniform,
+            timings: None,
         }
     }
 
+    /// Declares a batching strategy running one task per matched storage,
+    /// see [`BatchMode::PerStorage`].
+    pub const fn per_storage() -> Self {
+        Self {
+            batch_size_limits: 1..usize::MAX,
+            batches_per_thread: 1,
+            mode: BatchMode::PerStorage,
+            timings: None,
+        }
+    }
+
+    /// Declares a work stealing batching strategy, see [`BatchMode::Adaptive`].
+    ///
+    /// Threads claim 8 chunks each on average, which can be changed with
+    /// [`batches_per_thread`](Self::batches_per_thread).
+    pub const fn adaptive() -> Self {
+        Self {
+            batch_size_limits: 1..usize::MAX,
+            batches_per_thread: 8,
+            mode: BatchMode::Adaptive,
+            timings: None,
+        }
+    }
+
+    /// Configures the [`BatchMode`] of this instance.
+    pub fn mode(mut self, mode: BatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Configures the [`BatchTimings`] the batches of this instance are recorded into.
+    pub fn with_timings(mut self, timings: BatchTimings) -> Self {
+        self.timings = Some(timings);
+        self
+    }
+
     /// Configures the minimum allowed batch size of this instance.
     pub const fn min_batch_size(mut self, batch_size: usize) -> Self {
         self.batch_size_limits.start = batch_size;
@@ -106,3 +299,254 @@ impl BatchingStrategy {
         batch_size.clamp(self.batch_size_limits.start, self.batch_size_limits.end)
     }
 }
+
+/// A parallel iterator over the elements of a slice, split into batches according to a
+/// [`BatchingStrategy`].
+///
+/// This is the counterpart of [`Query::par_iter`] and [`EventReader::par_read`] for large
+/// collections stored in resources, and is created with [`BatchedParallelSlice::batched_par_iter`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::batching::{BatchedParallelSlice, BatchingStrategy};
+/// # use std::sync::atomic::{AtomicU32, Ordering};
+/// #[derive(Resource)]
+/// struct Contacts(Vec<(Entity, Entity)>);
+///
+/// fn count_contacts(contacts: Res<Contacts>) {
+///     let count = AtomicU32::new(0);
+///     contacts
+///         .0
+///         .batched_par_iter()
+///         .batching_strategy(BatchingStrategy::fixed(256))
+///         .for_each(|_contact| {
+///             count.fetch_add(1, Ordering::Relaxed);
+///         });
+/// }
+/// # bevy_ecs::system::assert_is_system(count_contacts);
+/// ```
+///
+/// [`Query::par_iter`]: crate::system::Query::par_iter
+/// [`EventReader::par_read`]: crate::event::EventReader::par_read
+pub struct SliceParIter<'a, T> {
+    slice: &'a [T],
+    batching_strategy: BatchingStrategy,
+}
+
+impl<'a, T: Sync> SliceParIter<'a, T> {
+    /// Creates a new parallel iterator over `slice`.
+    pub fn new(slice: &'a [T]) -> Self {
+        Self {
+            slice,
+            batching_strategy: BatchingStrategy::default(),
+        }
+    }
+
+    /// Changes the batching strategy used when iterating.
+    ///
+    /// For more information on how this affects the resultant iteration, see
+    /// [`BatchingStrategy`].
+    pub fn batching_strategy(mut self, strategy: BatchingStrategy) -> Self {
+        self.batching_strategy = strategy;
+        self
+    }
+
+    /// Runs the provided closure for each element in parallel.
+    ///
+    /// Unlike normal iteration, the order is not guaranteed in any form.
+    ///
+    /// # Panics
+    /// If the [`ComputeTaskPool`] is not initialized. If using this from a system that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::ComputeTaskPool
+    pub fn for_each<FN: Fn(&'a T) + Send + Sync + Clone>(self, func: FN) {
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        {
+            self.slice.iter().for_each(func);
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+        {
+            let pool = bevy_tasks::ComputeTaskPool::get();
+            let thread_count = pool.thread_num();
+            if thread_count <= 1 || self.slice.is_empty() {
+                return self.slice.iter().for_each(func);
+            }
+
+            let batch_size = self
+                .batching_strategy
+                .calc_batch_size(|| self.slice.len(), thread_count)
+                .max(1);
+            pool.scope(|scope| {
+                for batch in self.slice.chunks(batch_size) {
+                    let func = func.clone();
+                    scope.spawn(async move { batch.iter().for_each(func) });
+                }
+            });
+        }
+    }
+}
+
+/// A parallel iterator over the mutable elements of a slice, split into batches according to a
+/// [`BatchingStrategy`].
+///
+/// This is created with [`BatchedParallelSliceMut::batched_par_iter_mut`].
+/// See [`SliceParIter`] for details.
+pub struct SliceParIterMut<'a, T> {
+    slice: &'a mut [T],
+    batching_strategy: BatchingStrategy,
+}
+
+impl<'a, T: Send> SliceParIterMut<'a, T> {
+    /// Creates a new parallel iterator over `slice`.
+    pub fn new(slice: &'a mut [T]) -> Self {
+        Self {
+            slice,
+            batching_strategy: BatchingStrategy::default(),
+        }
+    }
+
+    /// Changes the batching strategy used when iterating.
+    ///
+    /// For more information on how this affects the resultant iteration, see
+    /// [`BatchingStrategy`].
+    pub fn batching_strategy(mut self, strategy: BatchingStrategy) -> Self {
+        self.batching_strategy = strategy;
+        self
+    }
+
+    /// Runs the provided closure for each element in parallel.
+    ///
+    /// Unlike normal iteration, the order is not guaranteed in any form.
+    ///
+    /// # Panics
+    /// If the [`ComputeTaskPool`] is not initialized. If using this from a system that is being
+    /// initialized and run from the ECS scheduler, this should never panic.
+    ///
+    /// [`ComputeTaskPool`]: bevy_tasks::ComputeTaskPool
+    pub fn for_each<FN: Fn(&'a mut T) + Send + Sync + Clone>(self, func: FN) {
+        #[cfg(any(target_arch = "wasm32", not(feature = "multi_threaded")))]
+        {
+            self.slice.iter_mut().for_each(func);
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
+        {
+            let pool = bevy_tasks::ComputeTaskPool::get();
+            let thread_count = pool.thread_num();
+            if thread_count <= 1 || self.slice.is_empty() {
+                return self.slice.iter_mut().for_each(func);
+            }
+
+            let batch_size = self
+                .batching_strategy
+                .calc_batch_size(|| self.slice.len(), thread_count)
+                .max(1);
+            pool.scope(|scope| {
+                for batch in self.slice.chunks_mut(batch_size) {
+                    let func = func.clone();
+                    scope.spawn(async move { batch.iter_mut().for_each(func) });
+                }
+            });
+        }
+    }
+}
+
+/// Extends slices, [`Vec`]s and other collections that can be viewed as a slice with
+/// [`SliceParIter`].
+pub trait BatchedParallelSlice<T: Sync>: AsRef<[T]> {
+    /// Returns a parallel iterator over the elements of this collection.
+    fn batched_par_iter(&self) -> SliceParIter<'_, T> {
+        SliceParIter::new(self.as_ref())
+    }
+}
+
+impl<S: AsRef<[T]> + ?Sized, T: Sync> BatchedParallelSlice<T> for S {}
+
+/// Extends slices, [`Vec`]s and other collections that can be viewed as a mutable slice with
+/// [`SliceParIterMut`].
+pub trait BatchedParallelSliceMut<T: Send>: AsMut<[T]> {
+    /// Returns a parallel iterator over the mutable elements of this collection.
+    fn batched_par_iter_mut(&mut self) -> SliceParIterMut<'_, T> {
+        SliceParIterMut::new(self.as_mut())
+    }
+}
+
+impl<S: AsMut<[T]> + ?Sized, T: Send> BatchedParallelSliceMut<T> for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::{component::Component, world::World};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Component)]
+    struct A(usize);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn query_batch_modes() {
+        bevy_tasks::ComputeTaskPool::get_or_init(|| {
+            bevy_tasks::TaskPoolBuilder::new().num_threads(4).build()
+        });
+
+        let mut world = World::new();
+        world.spawn_batch((0..1000).map(A));
+        world.spawn_batch((1000..1100).map(|i| (A(i), B)));
+        world.spawn((A(1100), B, C));
+        let mut query = world.query::<&A>();
+
+        for strategy in [
+            BatchingStrategy::new(),
+            BatchingStrategy::fixed(7),
+            BatchingStrategy::per_storage(),
+            BatchingStrategy::adaptive(),
+            BatchingStrategy::adaptive().max_batch_size(3),
+        ] {
+            let timings = BatchTimings::new();
+            let sum = AtomicUsize::new(0);
+            query
+                .par_iter(&world)
+                .batching_strategy(strategy.clone().with_timings(timings.clone()))
+                .for_each(|a| {
+                    sum.fetch_add(a.0, Ordering::Relaxed);
+                });
+            assert_eq!(sum.into_inner(), 1100 * 1101 / 2, "{strategy:?}");
+            if bevy_tasks::ComputeTaskPool::get().thread_num() > 1 {
+                assert_eq!(timings.item_count(), 1101, "{strategy:?}");
+                if strategy.mode == BatchMode::PerStorage {
+                    assert_eq!(timings.batch_count(), 3);
+                }
+            }
+            assert!(timings.max_batch_time() <= timings.total_time());
+
+            timings.reset();
+            assert_eq!(timings.batch_count(), 0);
+            assert_eq!(timings.mean_batch_time(), None);
+        }
+    }
+
+    #[test]
+    fn batched_slice_iteration() {
+        bevy_tasks::ComputeTaskPool::get_or_init(bevy_tasks::TaskPool::default);
+
+        let mut values: Vec<usize> = (0..1000).collect();
+        values
+            .batched_par_iter_mut()
+            .batching_strategy(BatchingStrategy::fixed(64))
+            .for_each(|value| *value *= 2);
+
+        let sum = AtomicUsize::new(0);
+        values.batched_par_iter().for_each(|value| {
+            sum.fetch_add(*value, Ordering::Relaxed);
+        });
+        assert_eq!(sum.into_inner(), 999 * 1000);
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     prelude::World,
     query::DebugCheckedUnwrap,
     storage::{SparseSetIndex, SparseSets, Storages, Table, TableRow},
-    world::unsafe_world_cell::UnsafeWorldCell,
+    world::{unsafe_world_cell::UnsafeWorldCell, EntityWorldMut},
 };
 use bevy_ptr::{ConstNonNull, OwningPtr};
 use bevy_utils::all_tuples;
@@ -129,6 +129,40 @@ use std::ptr::NonNull;
 /// struct PointName(String);
 /// ```
 ///
+/// ## Spawn effects
+///
+/// A derived bundle can declare a function to run on the entity once the bundle has been inserted,
+/// with `#[bundle(on_spawn = path::to::function)]`.
+/// This is useful for work that inserting components can't do on its own, such as spawning linked
+/// entities or registering the entity in an index.
+/// The effects of nested bundles run first, in field order.
+/// See [`Bundle::on_spawn`] for details.
+///
+/// ```
+/// # use bevy_ecs::{component::Component, bundle::Bundle, entity::Entity, world::{EntityWorldMut, World}};
+/// #[derive(Component)]
+/// struct Turret;
+/// #[derive(Component)]
+/// struct Mount(Entity);
+///
+/// #[derive(Bundle)]
+/// #[bundle(on_spawn = spawn_mount)]
+/// struct TurretBundle {
+///     turret: Turret,
+/// }
+///
+/// fn spawn_mount(entity: &mut EntityWorldMut) {
+///     let turret = entity.id();
+///     entity.world_scope(|world| {
+///         world.spawn(Mount(turret));
+///     });
+/// }
+///
+/// let mut world = World::new();
+/// let turret = world.spawn(TurretBundle { turret: Turret }).id();
+/// assert_eq!(world.query::<&Mount>().single(&world).0, turret);
+/// ```
+///
 /// # Safety
 ///
 /// Manual implementations of this trait are unsupported.
@@ -162,6 +196,19 @@ pub unsafe trait Bundle: DynamicBundle + Send + Sync + 'static {
         // Ensure that the `OwningPtr` is used correctly
         F: for<'a> FnMut(&'a mut T) -> OwningPtr<'a>,
         Self: Sized;
+
+    /// Whether [`Bundle::on_spawn`] does anything, so that callers can skip it.
+    #[doc(hidden)]
+    const HAS_ON_SPAWN: bool = false;
+
+    /// Runs on the entity once this bundle has been inserted into it, either when spawning it or
+    /// when inserting the bundle into an existing entity.
+    ///
+    /// Derived bundles run the effects of their fields, followed by the function declared with
+    /// `#[bundle(on_spawn = ...)]`.
+    /// Entities spawned by [`World::spawn_batch`] and [`World::insert_or_spawn_batch`] run it
+    /// once the whole batch has been spawned.
+    fn on_spawn(_entity: &mut EntityWorldMut) {}
 }
 
 /// The parts from [`Bundle`] that don't require statically knowing the components of the bundle.
@@ -233,6 +280,13 @@ macro_rules! tuple_impl {
                 // https://doc.rust-lang.org/reference/expressions.html#evaluation-order-of-operands
                 unsafe { ($(<$name as Bundle>::from_components(ctx, func),)*) }
             }
+
+            const HAS_ON_SPAWN: bool = false $(|| <$name as Bundle>::HAS_ON_SPAWN)*;
+
+            #[allow(unused_variables)]
+            fn on_spawn(entity: &mut EntityWorldMut) {
+                $(<$name as Bundle>::on_spawn(entity);)*
+            }
         }
 
         impl<$($name: Bundle),*> DynamicBundle for ($($name,)*) {
@@ -415,6 +469,8 @@ impl BundleInfo {
             }
             bundle_component += 1;
         });
+        // SAFETY: The caller ensures `table_row` is in range.
+        unsafe { table.set_entity_changed_tick_unchecked(table_row, change_tick) };
     }
 
     /// Adds a bundle to the given archetype and returns the resulting archetype. This could be the
@@ -508,6 +564,38 @@ impl BundleInfo {
     }
 }
 
+/// Panics if one of the singleton `components` about to be added to `entity` is already on
+/// another entity.
+///
+/// This is checked before `entity` is moved to its new archetype, so that the world is left
+/// untouched when it panics.
+pub(crate) fn assert_singletons_unique(
+    world: UnsafeWorldCell,
+    entity: Entity,
+    components: impl Iterator<Item = ComponentId>,
+) {
+    for component_id in components {
+        let Some(info) = world.components().get_info(component_id) else {
+            continue;
+        };
+        let Some(singleton) = info.singleton() else {
+            continue;
+        };
+        // the entity may have been despawned without triggering hooks, by `World::clear_entities`
+        if let Some(other) = singleton.get().filter(|&other| {
+            other != entity
+                && world
+                    .get_entity(other)
+                    .is_some_and(|other| other.contains_id(component_id))
+        }) {
+            panic!(
+                "Cannot add the singleton component `{}` to {entity:?}: {other:?} already has it.",
+                info.name()
+            );
+        }
+    }
+}
+
 // SAFETY: We have exclusive world access so our pointers can't be invalidated externally
 pub(crate) struct BundleInserter<'w> {
     world: UnsafeWorldCell<'w>,
@@ -639,6 +727,23 @@ impl<'w> BundleInserter<'w> {
     ) -> EntityLocation {
         let bundle_info = self.bundle_info.as_ref();
         let add_bundle = self.add_bundle.as_ref();
+
+        if let InsertBundleResult::NewArchetypeSameTable { new_archetype }
+        | InsertBundleResult::NewArchetypeNewTable { new_archetype, .. } = &self.result
+        {
+            if new_archetype.as_ref().has_on_add() {
+                assert_singletons_unique(
+                    self.world,
+                    entity,
+                    bundle_info
+                        .iter_components()
+                        .zip(add_bundle.bundle_status.iter())
+                        .filter(|(_, &status)| status == ComponentStatus::Added)
+                        .map(|(id, _)| id),
+                );
+            }
+        }
+
         let table = self.table.as_mut();
         let archetype = self.archetype.as_mut();
 
@@ -781,6 +886,25 @@ impl<'w> BundleInserter<'w> {
             }
         };
 
+        // SAFETY: No other references to the lifecycle stats exist
+        let stats = unsafe { self.world.lifecycle_stats_mut() };
+        if stats.is_enabled() && new_archetype.id() != location.archetype_id {
+            // SAFETY: `self.archetype` still points to the source archetype, which is not mutably
+            // borrowed anymore
+            let old_archetype = unsafe { self.archetype.as_ref() };
+            stats.record_move(
+                self.world.components(),
+                bundle_info
+                    .iter_components()
+                    .zip(add_bundle.bundle_status.iter())
+                    .filter(|(_, &status)| status == ComponentStatus::Added)
+                    .map(|(id, _)| id),
+                old_archetype.table_components(),
+                matches!(self.result, InsertBundleResult::NewArchetypeNewTable { .. }),
+                true,
+            );
+        }
+
         // SAFETY: We have no outstanding mutable references to world as they were dropped
         let mut deferred_world = unsafe { self.world.into_deferred() };
 
@@ -796,6 +920,14 @@ impl<'w> BundleInserter<'w> {
                         .filter(|(_, &status)| status == ComponentStatus::Added)
                         .map(|(id, _)| id),
                 );
+                deferred_world.trigger_batched_on_add(
+                    &[(entity, new_location.table_row)],
+                    bundle_info
+                        .iter_components()
+                        .zip(add_bundle.bundle_status.iter())
+                        .filter(|(_, &status)| status == ComponentStatus::Added)
+                        .map(|(id, _)| id),
+                );
             }
         }
         if new_archetype.has_on_insert() {
@@ -803,6 +935,14 @@ impl<'w> BundleInserter<'w> {
             // as they must be initialized before creating the BundleInfo.
             unsafe { deferred_world.trigger_on_insert(entity, bundle_info.iter_components()) }
         }
+        if !deferred_world.complete_hooks().is_empty()
+            && new_archetype.id() != location.archetype_id
+        {
+            // SAFETY: `self.archetype` still points to the source archetype, which is not mutably
+            // borrowed anymore
+            let old_archetype = unsafe { self.archetype.as_ref() };
+            deferred_world.trigger_on_complete(entity, Some(old_archetype), new_archetype);
+        }
 
         new_location
     }
@@ -821,6 +961,8 @@ pub(crate) struct BundleSpawner<'w> {
     table: NonNull<Table>,
     archetype: NonNull<Archetype>,
     change_tick: Tick,
+    // The spawned entities whose batched `on_add` hooks are deferred until the end of the batch.
+    batch: Option<Vec<(Entity, TableRow)>>,
 }
 
 impl<'w> BundleSpawner<'w> {
@@ -858,6 +1000,7 @@ impl<'w> BundleSpawner<'w> {
             archetype: archetype.into(),
             change_tick,
             world: world.as_unsafe_world_cell(),
+            batch: None,
         }
     }
 
@@ -869,6 +1012,17 @@ impl<'w> BundleSpawner<'w> {
         table.reserve(additional);
     }
 
+    /// Panics if a singleton component of the bundle is already on an entity other than `entity`.
+    #[inline]
+    pub(crate) fn assert_singletons_unique(&self, entity: Entity) {
+        // SAFETY: The archetype and bundle info are only read
+        let (archetype, bundle_info) =
+            unsafe { (self.archetype.as_ref(), self.bundle_info.as_ref()) };
+        if archetype.has_on_add() {
+            assert_singletons_unique(self.world, entity, bundle_info.iter_components());
+        }
+    }
+
     /// # Safety
     /// `entity` must be allocated (but non-existent), `T` must match this [`BundleInfo`]'s type
     #[inline]
@@ -876,6 +1030,23 @@ impl<'w> BundleSpawner<'w> {
         &mut self,
         entity: Entity,
         bundle: T,
+    ) -> EntityLocation {
+        self.assert_singletons_unique(entity);
+        // SAFETY: The singleton components were just checked, the caller ensures the rest
+        unsafe { self.spawn_non_existent_unchecked(entity, bundle) }
+    }
+
+    /// Like [`spawn_non_existent`](Self::spawn_non_existent), but doesn't check that the singleton
+    /// components of the bundle are unique.
+    ///
+    /// # Safety
+    /// - `entity` must be allocated (but non-existent), `T` must match this [`BundleInfo`]'s type
+    /// - [`assert_singletons_unique`](Self::assert_singletons_unique) must have been called
+    #[inline]
+    pub(crate) unsafe fn spawn_non_existent_unchecked<T: DynamicBundle>(
+        &mut self,
+        entity: Entity,
+        bundle: T,
     ) -> EntityLocation {
         let table = self.table.as_mut();
         let archetype = self.archetype.as_mut();
@@ -903,18 +1074,38 @@ impl<'w> BundleSpawner<'w> {
             location
         };
 
+        // SAFETY: No other references to the lifecycle stats exist
+        let stats = unsafe { self.world.lifecycle_stats_mut() };
+        if stats.is_enabled() {
+            stats.record_spawn(bundle_info.iter_components());
+        }
+
         // SAFETY: We have no outstanding mutable references to world as they were dropped
         let mut deferred_world = unsafe { self.world.into_deferred() };
         if archetype.has_on_add() {
             // SAFETY: All components in the bundle are guaranteed to exist in the World
             // as they must be initialized before creating the BundleInfo.
             unsafe { deferred_world.trigger_on_add(entity, bundle_info.iter_components()) };
+            match &mut self.batch {
+                Some(batch) => batch.push((entity, location.table_row)),
+                // SAFETY: All components in the bundle are guaranteed to exist in the World
+                // as they must be initialized before creating the BundleInfo.
+                None => unsafe {
+                    deferred_world.trigger_batched_on_add(
+                        &[(entity, location.table_row)],
+                        bundle_info.iter_components(),
+                    );
+                },
+            }
         }
         if archetype.has_on_insert() {
             // SAFETY: All components in the bundle are guaranteed to exist in the World
             // as they must be initialized before creating the BundleInfo.
             unsafe { deferred_world.trigger_on_insert(entity, bundle_info.iter_components()) };
         }
+        if !deferred_world.complete_hooks().is_empty() {
+            deferred_world.trigger_on_complete(entity, None, archetype);
+        }
 
         location
     }
@@ -923,10 +1114,13 @@ impl<'w> BundleSpawner<'w> {
     /// `T` must match this [`BundleInfo`]'s type
     #[inline]
     pub unsafe fn spawn<T: Bundle>(&mut self, bundle: T) -> Entity {
+        // checked before allocating the entity, so that it isn't leaked on panic
+        self.assert_singletons_unique(Entity::PLACEHOLDER);
         let entity = self.entities().alloc();
-        // SAFETY: entity is allocated (but non-existent), `T` matches this BundleInfo's type
+        // SAFETY: entity is allocated (but non-existent), `T` matches this BundleInfo's type, and
+        // the singleton components were checked
         unsafe {
-            self.spawn_non_existent(entity, bundle);
+            self.spawn_non_existent_unchecked(entity, bundle);
         }
         entity
     }
@@ -937,6 +1131,38 @@ impl<'w> BundleSpawner<'w> {
         unsafe { &mut self.world.world_mut().entities }
     }
 
+    /// # Safety:
+    /// - `Self` must be dropped after using the returned [`World`] as it may invalidate internal pointers.
+    #[inline]
+    pub(crate) unsafe fn world_mut(&mut self) -> &mut World {
+        self.world.world_mut()
+    }
+
+    /// Defers the batched `on_add` hooks of the entities spawned from now on until
+    /// [`trigger_batched_hooks`](Self::trigger_batched_hooks), to run them once for all entities.
+    #[inline]
+    pub(crate) fn start_batch(&mut self) {
+        self.batch.get_or_insert_with(Vec::new);
+    }
+
+    /// Runs the batched `on_add` hooks deferred since [`start_batch`](Self::start_batch).
+    #[inline]
+    pub(crate) fn trigger_batched_hooks(&mut self) {
+        let Some(batch) = self.batch.as_mut().filter(|batch| !batch.is_empty()) else {
+            return;
+        };
+        let batch = std::mem::take(batch);
+        // SAFETY: No outstanding references to self.world, the table rows are still valid as no
+        // structural change can happen before the commands are flushed
+        let mut deferred_world = unsafe { self.world.into_deferred() };
+        // SAFETY: `self.bundle_info` points to the bundle of this spawner, and all its components
+        // exist in the World as they must be initialized before creating the BundleInfo.
+        unsafe {
+            deferred_world
+                .trigger_batched_on_add(&batch, self.bundle_info.as_ref().iter_components());
+        }
+    }
+
     /// # Safety:
     /// - `Self` must be dropped after running this function as it may invalidate internal pointers.
     #[inline]
@@ -968,6 +1194,11 @@ impl Bundles {
         self.bundle_infos.get(bundle_id.index())
     }
 
+    /// Iterates over the metadata of all bundles registered with the world.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &BundleInfo> {
+        self.bundle_infos.iter()
+    }
+
     /// Gets the value identifying a specific type of bundle.
     /// Returns `None` if the bundle does not exist in the world,
     /// or if `type_id` does not correspond to a type of bundle.
@@ -1230,4 +1461,145 @@ mod tests {
         world.spawn(A).flush();
         assert_eq!(4, world.resource::<R>().0);
     }
+
+    #[derive(Bundle)]
+    #[bundle(on_spawn = count_inner)]
+    struct InnerBundle {
+        a: A,
+    }
+
+    fn count_inner(entity: &mut crate::world::EntityWorldMut) {
+        entity.world_scope(|world| world.resource_mut::<R>().assert_order(0));
+    }
+
+    #[derive(Bundle)]
+    #[bundle(on_spawn = count_outer)]
+    struct OuterBundle {
+        inner: InnerBundle,
+        b: B,
+    }
+
+    fn count_outer(entity: &mut crate::world::EntityWorldMut) {
+        assert!(entity.contains::<A>() && entity.contains::<B>());
+        entity.insert(C);
+        entity.world_scope(|world| world.resource_mut::<R>().assert_order(1));
+    }
+
+    #[test]
+    fn bundle_on_spawn() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        let outer = || OuterBundle {
+            inner: InnerBundle { a: A },
+            b: B,
+        };
+
+        let entity = world.spawn(outer()).id();
+        assert!(world.entity(entity).contains::<C>());
+        assert_eq!(2, world.resource::<R>().0);
+
+        world.resource_mut::<R>().0 = 0;
+        let entity = world.spawn(D).insert((outer(), D)).id();
+        assert!(world.entity(entity).contains::<C>());
+        assert_eq!(2, world.resource::<R>().0);
+
+        world.resource_mut::<R>().0 = 0;
+        world.spawn_batch([InnerBundle { a: A }]);
+        assert_eq!(1, world.resource::<R>().0);
+        const _: () = assert!(!<(A, D) as Bundle>::HAS_ON_SPAWN);
+        const _: () = assert!(<(D, InnerBundle) as Bundle>::HAS_ON_SPAWN);
+    }
+
+    #[test]
+    fn ordered_component_hooks() {
+        use crate::component::OrderedHook;
+
+        let mut world = World::new();
+        world.init_resource::<R>();
+        world
+            .register_component_hooks::<A>()
+            .add_on_add(
+                OrderedHook::new("last", |mut world, _, _| {
+                    world.resource_mut::<R>().assert_order(3);
+                })
+                .after("second"),
+            )
+            .add_on_add(OrderedHook::new("second", |mut world, _, _| {
+                world.resource_mut::<R>().assert_order(2);
+            }))
+            .add_on_add(
+                OrderedHook::new("first", |mut world, _, _| {
+                    world.resource_mut::<R>().assert_order(1);
+                })
+                .before("second"),
+            )
+            .on_add(|mut world, _, _| world.resource_mut::<R>().assert_order(0))
+            .add_on_remove(OrderedHook::new("remove", |mut world, _, _| {
+                world.resource_mut::<R>().assert_order(4);
+            }));
+
+        let hooks = world.register_component_hooks::<B>();
+        hooks.add_on_insert(OrderedHook::new("a", |_, _, _| {}).before("b"));
+        assert!(hooks
+            .try_add_on_insert(OrderedHook::new("b", |_, _, _| {}).before("a"))
+            .is_none());
+        assert!(hooks
+            .try_add_on_insert(OrderedHook::new("a", |_, _, _| {}))
+            .is_none());
+        assert!(hooks
+            .try_add_on_insert(OrderedHook::new("b", |_, _, _| {}).after("a"))
+            .is_some());
+
+        let entity = world.spawn(A).id();
+        world.despawn(entity);
+        assert_eq!(5, world.resource::<R>().0);
+    }
+
+    #[test]
+    fn batched_component_hooks() {
+        use crate::component::OrderedHook;
+        use crate::storage::TableRow;
+
+        #[derive(Resource, Default)]
+        struct Batches(Vec<Vec<(Entity, TableRow)>>);
+
+        let mut world = World::new();
+        world.init_resource::<Batches>();
+        world
+            .register_component_hooks::<A>()
+            .add_batched_on_add(OrderedHook::new_batched(
+                "sync",
+                |mut world, entities, _| {
+                    world.resource_mut::<Batches>().0.push(entities.to_vec());
+                },
+            ))
+            .add_batched_on_remove(OrderedHook::new_batched(
+                "sync",
+                |mut world, entities, _| {
+                    world.resource_mut::<Batches>().0.push(entities.to_vec());
+                },
+            ));
+
+        let entities: Vec<_> = world.spawn_batch([A, A, A]).collect();
+        let batches = std::mem::take(&mut world.resource_mut::<Batches>().0);
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0],
+            entities
+                .iter()
+                .map(|&entity| (entity, world.entity(entity).location().table_row))
+                .collect::<Vec<_>>()
+        );
+
+        let entity = world.spawn(B).insert(A).id();
+        let row = world.entity(entity).location().table_row;
+        world.entity_mut(entity).remove::<A>();
+        world.despawn(entities[0]);
+        let batches = std::mem::take(&mut world.resource_mut::<Batches>().0);
+        assert_eq!(batches.len(), 3);
+        assert_eq!(batches[0], vec![(entity, row)]);
+        assert_eq!(batches[1], vec![(entity, row)]);
+        assert_eq!(batches[2].len(), 1);
+        assert_eq!(batches[2][0].0, entities[0]);
+    }
 }
@@ -1,13 +1,14 @@
 //! Types that detect when their internal data mutate.
 
 use crate::{
-    component::{Tick, TickCells},
+    component::{ComponentTicks, Tick, TickCells},
     ptr::PtrMut,
     system::Resource,
 };
 use bevy_ptr::{Ptr, UnsafeCellDeref};
-use std::mem;
 use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::{cell::UnsafeCell, mem};
 
 /// The (arbitrarily chosen) minimum number of world tick increments between `check_tick` scans.
 ///
@@ -63,6 +64,11 @@ pub trait DetectChanges {
     /// [`SystemChangeTick`](crate::system::SystemChangeTick)
     /// [`SystemParam`](crate::system::SystemParam).
     fn last_changed(&self) -> Tick;
+
+    /// Returns the ticks recording when this data was added and most recently changed.
+    ///
+    /// They can be transplanted to another value with [`DetectChangesMut::set_ticks`].
+    fn ticks(&self) -> ComponentTicks;
 }
 
 /// Types that implement reliable change detection.
@@ -125,6 +131,28 @@ pub trait DetectChangesMut: DetectChanges {
     /// you are trying to synchronize representations using change detection and need to avoid infinite recursion.
     fn bypass_change_detection(&mut self) -> &mut Self::Inner;
 
+    /// Bypasses change detection like [`bypass_change_detection`](DetectChangesMut::bypass_change_detection),
+    /// but records the mutation as a change made at `tick`.
+    ///
+    /// This lets layers applying state from elsewhere, such as networking or deserialization,
+    /// decide whether their writes count as changes for the systems that run after them:
+    /// a `tick` older than the last run of a system is not seen as a change by that system.
+    ///
+    /// # Warning
+    /// Like [`set_last_changed`](DetectChangesMut::set_last_changed), this is intended for
+    /// rollback and replication, where ticks come from the same [`World`](crate::world::World).
+    fn set_changed_as(&mut self, tick: Tick) -> &mut Self::Inner;
+
+    /// Flags this value as having been added, and therefore changed.
+    ///
+    /// Systems will see it as newly added, for example through [`Added`](crate::query::Added)
+    /// filters, as if it had just been inserted.
+    fn set_added(&mut self);
+
+    /// Overwrites the ticks recording when this value was added and last changed, for example
+    /// with the [`ticks`](DetectChanges::ticks) of another value it was copied from.
+    fn set_ticks(&mut self, ticks: ComponentTicks);
+
     /// Overwrites this smart pointer with the given value, if and only if `*self != value`.
     /// Returns `true` if the value was overwritten, and returns `false` if it was not.
     ///
@@ -280,6 +308,14 @@ macro_rules! change_detection_impl {
             fn last_changed(&self) -> Tick {
                 *self.ticks.changed
             }
+
+            #[inline]
+            fn ticks(&self) -> ComponentTicks {
+                ComponentTicks {
+                    added: *self.ticks.added,
+                    changed: *self.ticks.changed,
+                }
+            }
         }
 
         impl<$($generics),*: ?Sized $(+ $traits)?> Deref for $name<$($generics),*> {
@@ -308,6 +344,7 @@ macro_rules! change_detection_mut_impl {
             #[inline]
             fn set_changed(&mut self) {
                 *self.ticks.changed = self.ticks.this_run;
+                self.ticks.propagate_change(self.ticks.this_run);
             }
 
             #[inline]
@@ -319,6 +356,27 @@ macro_rules! change_detection_mut_impl {
             fn bypass_change_detection(&mut self) -> &mut Self::Inner {
                 self.value
             }
+
+            #[inline]
+            fn set_changed_as(&mut self, tick: Tick) -> &mut Self::Inner {
+                *self.ticks.changed = tick;
+                self.ticks.propagate_change(tick);
+                self.value
+            }
+
+            #[inline]
+            fn set_added(&mut self) {
+                *self.ticks.added = self.ticks.this_run;
+                *self.ticks.changed = self.ticks.this_run;
+                self.ticks.propagate_change(self.ticks.this_run);
+            }
+
+            #[inline]
+            fn set_ticks(&mut self, ticks: ComponentTicks) {
+                *self.ticks.added = ticks.added;
+                *self.ticks.changed = ticks.changed;
+                self.ticks.propagate_change(ticks.changed);
+            }
         }
 
         impl<$($generics),* : ?Sized $(+ $traits)?> DerefMut for $name<$($generics),*> {
@@ -359,6 +417,8 @@ macro_rules! impl_methods {
                     ticks: TicksMut {
                         added: self.ticks.added,
                         changed: self.ticks.changed,
+                        entity_changed: self.ticks.entity_changed,
+                        fields_changed: self.ticks.fields_changed,
                         last_run: self.ticks.last_run,
                         this_run: self.ticks.this_run,
                     }
@@ -452,10 +512,33 @@ impl<'w> Ticks<'w> {
 pub(crate) struct TicksMut<'w> {
     pub(crate) added: &'w mut Tick,
     pub(crate) changed: &'w mut Tick,
+    /// The tick of the table row of the entity owning the value, updated with `changed`, or
+    /// `None` for resources.
+    pub(crate) entity_changed: Option<&'w AtomicU32>,
+    /// The ticks of the fields of the value changed by writes through these ticks, updated with
+    /// `changed`. Empty if the value doesn't track the changes of its fields.
+    pub(crate) fields_changed: FieldTicksMut<'w>,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
 }
 
+/// The ticks of the fields of a value, borrowed by a [`TicksMut`] with unique access to them.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct FieldTicksMut<'w>(pub(crate) &'w [UnsafeCell<Tick>]);
+
+// SAFETY: A `FieldTicksMut` is only written through `&mut TicksMut`, which has unique access to
+// the ticks, so it is used like a `&'w mut [Tick]`, which is `Send` and `Sync`.
+unsafe impl Send for FieldTicksMut<'_> {}
+// SAFETY: See above.
+unsafe impl Sync for FieldTicksMut<'_> {}
+
+// Mutable borrows are sent to other threads, for example to extract the render world in
+// pipelined rendering.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Mut<'static, u32>>();
+};
+
 impl<'w> TicksMut<'w> {
     /// # Safety
     /// This should never alias the underlying ticks. All access must be unique.
@@ -470,10 +553,40 @@ impl<'w> TicksMut<'w> {
             added: unsafe { cells.added.deref_mut() },
             // SAFETY: Caller ensures there is no alias to the cell.
             changed: unsafe { cells.changed.deref_mut() },
+            entity_changed: None,
+            fields_changed: FieldTicksMut(cells.fields),
             last_run,
             this_run,
         }
     }
+
+    /// Returns these ticks, also recording changes in `entity_changed`, the tick of the table row
+    /// of the entity owning the value.
+    #[inline]
+    pub(crate) fn with_entity_changed(mut self, entity_changed: Option<&'w AtomicU32>) -> Self {
+        self.entity_changed = entity_changed;
+        self
+    }
+
+    /// Records a change at `tick` in the ticks tracking the value beyond its own: the tick of its
+    /// entity, and the ticks of its changed fields.
+    #[inline]
+    pub(crate) fn propagate_change(&mut self, tick: Tick) {
+        if let Some(entity_changed) = self.entity_changed {
+            // Other components of the entity may be written in parallel, and values can be marked
+            // as changed at an older tick: only keep the newest tick.
+            let this_run = self.this_run;
+            let _ = entity_changed.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+                tick.is_newer_than(Tick::new(current), this_run)
+                    .then_some(tick.get())
+            });
+        }
+        for field_changed in self.fields_changed.0 {
+            // SAFETY: These ticks are only accessed through this `TicksMut`, which has unique
+            // access to the value.
+            unsafe { *field_changed.get() = tick };
+        }
+    }
 }
 
 impl<'w> From<TicksMut<'w>> for Ticks<'w> {
@@ -820,6 +933,8 @@ impl<'w, T: ?Sized> Mut<'w, T> {
             ticks: TicksMut {
                 added,
                 changed: last_changed,
+                entity_changed: None,
+                fields_changed: FieldTicksMut::default(),
                 last_run,
                 this_run,
             },
@@ -898,6 +1013,8 @@ impl<'w> MutUntyped<'w> {
             ticks: TicksMut {
                 added: self.ticks.added,
                 changed: self.ticks.changed,
+                entity_changed: self.ticks.entity_changed,
+                fields_changed: self.ticks.fields_changed,
                 last_run: self.ticks.last_run,
                 this_run: self.ticks.this_run,
             },
@@ -986,6 +1103,14 @@ impl<'w> DetectChanges for MutUntyped<'w> {
     fn last_changed(&self) -> Tick {
         *self.ticks.changed
     }
+
+    #[inline]
+    fn ticks(&self) -> ComponentTicks {
+        ComponentTicks {
+            added: *self.ticks.added,
+            changed: *self.ticks.changed,
+        }
+    }
 }
 
 impl<'w> DetectChangesMut for MutUntyped<'w> {
@@ -994,6 +1119,7 @@ impl<'w> DetectChangesMut for MutUntyped<'w> {
     #[inline]
     fn set_changed(&mut self) {
         *self.ticks.changed = self.ticks.this_run;
+        self.ticks.propagate_change(self.ticks.this_run);
     }
 
     #[inline]
@@ -1005,6 +1131,27 @@ impl<'w> DetectChangesMut for MutUntyped<'w> {
     fn bypass_change_detection(&mut self) -> &mut Self::Inner {
         &mut self.value
     }
+
+    #[inline]
+    fn set_changed_as(&mut self, tick: Tick) -> &mut Self::Inner {
+        *self.ticks.changed = tick;
+        self.ticks.propagate_change(tick);
+        &mut self.value
+    }
+
+    #[inline]
+    fn set_added(&mut self) {
+        *self.ticks.added = self.ticks.this_run;
+        *self.ticks.changed = self.ticks.this_run;
+        self.ticks.propagate_change(self.ticks.this_run);
+    }
+
+    #[inline]
+    fn set_ticks(&mut self, ticks: ComponentTicks) {
+        *self.ticks.added = ticks.added;
+        *self.ticks.changed = ticks.changed;
+        self.ticks.propagate_change(ticks.changed);
+    }
 }
 
 impl std::fmt::Debug for MutUntyped<'_> {
@@ -1034,7 +1181,8 @@ mod tests {
     use crate::{
         self as bevy_ecs,
         change_detection::{
-            Mut, NonSendMut, Ref, ResMut, TicksMut, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE,
+            FieldTicksMut, Mut, NonSendMut, Ref, ResMut, TicksMut, CHECK_TICK_THRESHOLD,
+            MAX_CHANGE_AGE,
         },
         component::{Component, ComponentTicks, Tick},
         system::{IntoSystem, Query, System},
@@ -1156,6 +1304,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            entity_changed: None,
+            fields_changed: FieldTicksMut::default(),
             last_run: Tick::new(3),
             this_run: Tick::new(4),
         };
@@ -1192,6 +1342,40 @@ mod tests {
         assert!(val.is_changed());
     }
 
+    #[test]
+    fn set_changed_as_and_added() {
+        let mut component_ticks = ComponentTicks {
+            added: Tick::new(1),
+            changed: Tick::new(1),
+        };
+        let mut res = R {};
+        let mut val = Mut::new(
+            &mut res,
+            &mut component_ticks.added,
+            &mut component_ticks.changed,
+            Tick::new(2), // last_run
+            Tick::new(4), // this_run
+        );
+
+        // A write recorded before the last run isn't a change for this system.
+        val.set_changed_as(Tick::new(2));
+        assert!(!val.is_changed());
+        val.set_changed_as(Tick::new(3));
+        assert!(val.is_changed() && !val.is_added());
+
+        val.set_added();
+        assert!(val.is_added());
+        assert_eq!(val.ticks().added_tick(), Tick::new(4));
+
+        let mut untyped: MutUntyped = val.into();
+        untyped.set_ticks(ComponentTicks {
+            added: Tick::new(1),
+            changed: Tick::new(1),
+        });
+        assert!(!untyped.is_added() && !untyped.is_changed());
+        assert_eq!(untyped.ticks().last_changed_tick(), Tick::new(1));
+    }
+
     #[test]
     fn mut_from_non_send_mut() {
         let mut component_ticks = ComponentTicks {
@@ -1201,6 +1385,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            entity_changed: None,
+            fields_changed: FieldTicksMut::default(),
             last_run: Tick::new(3),
             this_run: Tick::new(4),
         };
@@ -1231,6 +1417,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            entity_changed: None,
+            fields_changed: FieldTicksMut::default(),
             last_run,
             this_run,
         };
@@ -1316,6 +1504,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            entity_changed: None,
+            fields_changed: FieldTicksMut::default(),
             last_run,
             this_run,
         };
@@ -1350,6 +1540,8 @@ mod tests {
         let ticks = TicksMut {
             added: &mut component_ticks.added,
             changed: &mut component_ticks.changed,
+            entity_changed: None,
+            fields_changed: FieldTicksMut::default(),
             last_run: Tick::new(3),
             this_run: Tick::new(4),
         };
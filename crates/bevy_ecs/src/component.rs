@@ -5,7 +5,7 @@ use crate::{
     archetype::ArchetypeFlags,
     change_detection::MAX_CHANGE_AGE,
     entity::Entity,
-    storage::{SparseSetIndex, Storages},
+    storage::{SparseSetIndex, Storages, TableRow},
     system::{Local, Resource, SystemParam},
     world::{DeferredWorld, FromWorld, World},
 };
@@ -21,6 +21,7 @@ use std::{
     borrow::Cow,
     marker::PhantomData,
     mem::needs_drop,
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 /// A data type that can be used to store data for an [entity].
@@ -154,6 +155,31 @@ pub trait Component: Send + Sync + 'static {
     /// A constant indicating the storage type used for this component.
     const STORAGE_TYPE: StorageType;
 
+    /// `true` if [`STORAGE_TYPE`](Self::STORAGE_TYPE) was left to its default by the derive
+    /// macro, rather than chosen explicitly.
+    ///
+    /// Tools recommending storage types only consider these components.
+    #[doc(hidden)]
+    const STORAGE_TYPE_IS_DEFAULT: bool = false;
+
+    /// `true` if at most one entity can have this component at a time, set with
+    /// `#[component(singleton)]`.
+    ///
+    /// Adding a singleton component to a second entity panics, and the entity that has it can be
+    /// found without iterating with [`World::singleton`].
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[component(singleton)]
+    /// struct Player;
+    ///
+    /// let mut world = World::new();
+    /// let player = world.spawn(Player).id();
+    /// assert_eq!(world.singleton_entity::<Player>(), Some(player));
+    /// ```
+    const SINGLETON: bool = false;
+
     /// Called when registering this component, allowing mutable access to its [`ComponentHooks`].
     fn register_component_hooks(_hooks: &mut ComponentHooks) {}
 }
@@ -182,12 +208,157 @@ pub enum StorageType {
 /// The type used for [`Component`] lifecycle hooks such as `on_add`, `on_insert` or `on_remove`
 pub type ComponentHook = for<'w> fn(DeferredWorld<'w>, Entity, ComponentId);
 
+/// The type used for batched [`Component`] lifecycle hooks, registered with
+/// [`ComponentHooks::add_batched_on_add`] and [`ComponentHooks::add_batched_on_remove`].
+///
+/// The hook receives the entities of an archetype move at once, with the row of each entity in
+/// its table: the table it is added to for `on_add` hooks, and the table it is removed from for
+/// `on_remove` hooks.
+pub type BatchedComponentHook =
+    for<'w, 'a> fn(DeferredWorld<'w>, &'a [(Entity, TableRow)], ComponentId);
+
+/// A named hook, ordered relative to the other named hooks of the same event of a component.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, component::OrderedHook};
+/// #[derive(Component)]
+/// struct RigidBody;
+///
+/// let mut world = World::new();
+/// world
+///     .register_component_hooks::<RigidBody>()
+///     .add_on_add(OrderedHook::new("physics", |_world, _entity, _id| {
+///         // create the body in the physics engine
+///     }))
+///     .add_on_add(
+///         OrderedHook::new("debug_render", |_world, _entity, _id| {
+///             // draw the body created by the physics hook
+///         })
+///         .after("physics"),
+///     );
+/// ```
+#[derive(Debug, Clone)]
+pub struct OrderedHook<H = ComponentHook> {
+    name: &'static str,
+    hook: H,
+    before: Vec<&'static str>,
+    after: Vec<&'static str>,
+}
+
+impl OrderedHook {
+    /// Creates a hook named `name`.
+    pub fn new(name: &'static str, hook: ComponentHook) -> Self {
+        Self {
+            name,
+            hook,
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+}
+
+impl OrderedHook<BatchedComponentHook> {
+    /// Creates a batched hook named `name`.
+    pub fn new_batched(name: &'static str, hook: BatchedComponentHook) -> Self {
+        Self {
+            name,
+            hook,
+            before: Vec::new(),
+            after: Vec::new(),
+        }
+    }
+}
+
+impl<H> OrderedHook<H> {
+    /// Runs this hook before the hook named `name`, if the component has one.
+    pub fn before(mut self, name: &'static str) -> Self {
+        self.before.push(name);
+        self
+    }
+
+    /// Runs this hook after the hook named `name`, if the component has one.
+    pub fn after(mut self, name: &'static str) -> Self {
+        self.after.push(name);
+        self
+    }
+
+    /// Returns the name of this hook.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn runs_before(&self, other: &Self) -> bool {
+        self.before.contains(&other.name) || other.after.contains(&self.name)
+    }
+}
+
+/// The named hooks of an event of a component, sorted in the order they run.
+#[derive(Debug, Clone)]
+pub(crate) struct OrderedHooks<H> {
+    hooks: Vec<OrderedHook<H>>,
+}
+
+impl<H> Default for OrderedHooks<H> {
+    fn default() -> Self {
+        Self { hooks: Vec::new() }
+    }
+}
+
+impl<H: Copy> OrderedHooks<H> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = H> + '_ {
+        self.hooks.iter().map(|hook| hook.hook)
+    }
+
+    /// Adds `hook` and sorts the hooks, returning `false` if a hook with the same name exists or
+    /// if the ordering constraints form a cycle.
+    fn try_add(&mut self, hook: OrderedHook<H>) -> bool {
+        if self.hooks.iter().any(|other| other.name == hook.name) {
+            return false;
+        }
+        let mut pending = self.hooks.clone();
+        pending.push(hook);
+        let mut sorted = Vec::with_capacity(pending.len());
+        // pick the first hook that no remaining hook must run before, to keep the order stable
+        while let Some(next) = pending.iter().position(|hook| {
+            !pending
+                .iter()
+                .any(|other| other.name != hook.name && other.runs_before(hook))
+        }) {
+            sorted.push(pending.remove(next));
+        }
+        if !pending.is_empty() {
+            return false;
+        }
+        self.hooks = sorted;
+        true
+    }
+}
+
 /// Lifecycle hooks for a given [`Component`], stored in its [`ComponentInfo`]
+///
+/// Each event has an unnamed hook, registered with [`on_add`](Self::on_add),
+/// [`on_insert`](Self::on_insert) or [`on_remove`](Self::on_remove), which runs first. Any number
+/// of named [`OrderedHook`]s can be added after it, to let several plugins react to the same
+/// component in a given order.
+///
+/// [Batched hooks](BatchedComponentHook) run once per archetype move with all the entities that
+/// moved, after the other hooks of the event ran for each of them. Spawning entities with
+/// [`World::spawn_batch`] runs them once for the whole batch, which makes hooks that sync
+/// components to an external engine scale to mass spawns.
 #[derive(Debug, Clone, Default)]
 pub struct ComponentHooks {
     pub(crate) on_add: Option<ComponentHook>,
     pub(crate) on_insert: Option<ComponentHook>,
     pub(crate) on_remove: Option<ComponentHook>,
+    pub(crate) ordered_on_add: OrderedHooks<ComponentHook>,
+    pub(crate) ordered_on_insert: OrderedHooks<ComponentHook>,
+    pub(crate) ordered_on_remove: OrderedHooks<ComponentHook>,
+    pub(crate) batched_on_add: OrderedHooks<BatchedComponentHook>,
+    pub(crate) batched_on_remove: OrderedHooks<BatchedComponentHook>,
 }
 
 impl ComponentHooks {
@@ -249,6 +420,117 @@ impl ComponentHooks {
         self.on_remove = Some(hook);
         Some(self)
     }
+
+    /// Adds a named hook run when this component is added to an entity, after the
+    /// [`on_add`](Self::on_add) hook.
+    ///
+    /// Will panic if the component already has an `on_add` hook with the same name, or if the
+    /// ordering constraints of the hook form a cycle.
+    pub fn add_on_add(&mut self, hook: OrderedHook) -> &mut Self {
+        let name = hook.name;
+        self.try_add_on_add(hook).unwrap_or_else(|| {
+            panic!("Cannot add the on_add hook {name}: a hook has the same name or the ordering has a cycle")
+        })
+    }
+
+    /// Adds a named hook run when this component is added or replaced, after the
+    /// [`on_insert`](Self::on_insert) hook.
+    ///
+    /// Will panic if the component already has an `on_insert` hook with the same name, or if the
+    /// ordering constraints of the hook form a cycle.
+    pub fn add_on_insert(&mut self, hook: OrderedHook) -> &mut Self {
+        let name = hook.name;
+        self.try_add_on_insert(hook).unwrap_or_else(|| {
+            panic!("Cannot add the on_insert hook {name}: a hook has the same name or the ordering has a cycle")
+        })
+    }
+
+    /// Adds a named hook run when this component is removed from an entity, after the
+    /// [`on_remove`](Self::on_remove) hook.
+    ///
+    /// Will panic if the component already has an `on_remove` hook with the same name, or if the
+    /// ordering constraints of the hook form a cycle.
+    pub fn add_on_remove(&mut self, hook: OrderedHook) -> &mut Self {
+        let name = hook.name;
+        self.try_add_on_remove(hook).unwrap_or_else(|| {
+            panic!("Cannot add the on_remove hook {name}: a hook has the same name or the ordering has a cycle")
+        })
+    }
+
+    /// Adds a named [`BatchedComponentHook`] run when this component is added to entities.
+    ///
+    /// Will panic if the component already has a batched `on_add` hook with the same name, or if
+    /// the ordering constraints of the hook form a cycle.
+    pub fn add_batched_on_add(&mut self, hook: OrderedHook<BatchedComponentHook>) -> &mut Self {
+        let name = hook.name;
+        self.try_add_batched_on_add(hook).unwrap_or_else(|| {
+            panic!("Cannot add the batched on_add hook {name}: a hook has the same name or the ordering has a cycle")
+        })
+    }
+
+    /// Adds a named [`BatchedComponentHook`] run when this component is removed from entities.
+    ///
+    /// Will panic if the component already has a batched `on_remove` hook with the same name, or
+    /// if the ordering constraints of the hook form a cycle.
+    pub fn add_batched_on_remove(&mut self, hook: OrderedHook<BatchedComponentHook>) -> &mut Self {
+        let name = hook.name;
+        self.try_add_batched_on_remove(hook).unwrap_or_else(|| {
+            panic!("Cannot add the batched on_remove hook {name}: a hook has the same name or the ordering has a cycle")
+        })
+    }
+
+    /// Fallible version of [`Self::add_on_add`].
+    /// Returns `None` if the hook can't be added.
+    pub fn try_add_on_add(&mut self, hook: OrderedHook) -> Option<&mut Self> {
+        self.ordered_on_add.try_add(hook).then_some(self)
+    }
+
+    /// Fallible version of [`Self::add_on_insert`].
+    /// Returns `None` if the hook can't be added.
+    pub fn try_add_on_insert(&mut self, hook: OrderedHook) -> Option<&mut Self> {
+        self.ordered_on_insert.try_add(hook).then_some(self)
+    }
+
+    /// Fallible version of [`Self::add_on_remove`].
+    /// Returns `None` if the hook can't be added.
+    pub fn try_add_on_remove(&mut self, hook: OrderedHook) -> Option<&mut Self> {
+        self.ordered_on_remove.try_add(hook).then_some(self)
+    }
+
+    /// Fallible version of [`Self::add_batched_on_add`].
+    /// Returns `None` if the hook can't be added.
+    pub fn try_add_batched_on_add(
+        &mut self,
+        hook: OrderedHook<BatchedComponentHook>,
+    ) -> Option<&mut Self> {
+        self.batched_on_add.try_add(hook).then_some(self)
+    }
+
+    /// Fallible version of [`Self::add_batched_on_remove`].
+    /// Returns `None` if the hook can't be added.
+    pub fn try_add_batched_on_remove(
+        &mut self,
+        hook: OrderedHook<BatchedComponentHook>,
+    ) -> Option<&mut Self> {
+        self.batched_on_remove.try_add(hook).then_some(self)
+    }
+
+    /// Returns `true` if any hook runs when this component is added to an entity.
+    pub(crate) fn has_on_add(&self) -> bool {
+        self.on_add.is_some() || !self.ordered_on_add.is_empty() || !self.batched_on_add.is_empty()
+    }
+
+    /// Returns `true` if any hook runs when this component is inserted.
+    pub(crate) fn has_on_insert(&self) -> bool {
+        self.on_insert.is_some() || !self.ordered_on_insert.is_empty()
+    }
+
+    /// Returns `true` if any hook runs when this component is removed from an entity.
+    pub(crate) fn has_on_remove(&self) -> bool {
+        self.on_remove.is_some()
+            || !self.ordered_on_remove.is_empty()
+            || !self.batched_on_remove.is_empty()
+    }
 }
 
 /// Stores metadata for a type of component or resource stored in a specific [`World`].
@@ -257,6 +539,8 @@ pub struct ComponentInfo {
     id: ComponentId,
     descriptor: ComponentDescriptor,
     hooks: ComponentHooks,
+    tracked_fields: Option<Box<[&'static str]>>,
+    singleton: Option<SingletonEntity>,
 }
 
 impl ComponentInfo {
@@ -302,6 +586,13 @@ impl ComponentInfo {
         self.descriptor.storage_type
     }
 
+    /// Returns `true` if the storage type of the component wasn't chosen explicitly, and was left
+    /// to its default by `#[derive(Component)]`.
+    #[inline]
+    pub fn has_default_storage_type(&self) -> bool {
+        self.descriptor.default_storage_type
+    }
+
     /// Returns `true` if the underlying component type can be freely shared between threads.
     /// If this returns `false`, then extra care must be taken to ensure that components
     /// are not accessed from the wrong thread.
@@ -310,35 +601,103 @@ impl ComponentInfo {
         self.descriptor.is_send_and_sync
     }
 
+    /// Returns the names of the fields whose changes are tracked separately, if the component
+    /// tracks the changes of its fields.
+    ///
+    /// Field tracking is enabled by [`World::track_field_changes_by_id`](crate::world::World::track_field_changes_by_id),
+    /// and is used by `Mut::map_field_reflect` and the [`FieldChanged`](crate::query::FieldChanged)
+    /// filter.
+    #[inline]
+    pub fn tracked_fields(&self) -> Option<&[&'static str]> {
+        self.tracked_fields.as_deref()
+    }
+
+    /// Returns the index of the field `name` in [`tracked_fields`](Self::tracked_fields).
+    #[inline]
+    pub fn tracked_field_index(&self, name: &str) -> Option<usize> {
+        self.tracked_fields()?
+            .iter()
+            .position(|field| *field == name)
+    }
+
+    /// Returns `true` if at most one entity can have this component at a time, see
+    /// [`Component::SINGLETON`].
+    #[inline]
+    pub fn is_singleton(&self) -> bool {
+        self.singleton.is_some()
+    }
+
+    /// Returns the entity that has this component, if it is a singleton component.
+    #[inline]
+    pub fn singleton_entity(&self) -> Option<Entity> {
+        self.singleton.as_ref()?.get()
+    }
+
     /// Create a new [`ComponentInfo`].
     pub(crate) fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
         ComponentInfo {
             id,
+            singleton: descriptor.singleton.then(SingletonEntity::default),
             descriptor,
             hooks: ComponentHooks::default(),
+            tracked_fields: None,
         }
     }
 
     /// Update the given flags to include any [`ComponentHook`] registered to self
     #[inline]
     pub(crate) fn update_archetype_flags(&self, flags: &mut ArchetypeFlags) {
-        if self.hooks().on_add.is_some() {
+        // singletons are tracked when the hooks are triggered
+        if self.hooks().has_on_add() || self.is_singleton() {
             flags.insert(ArchetypeFlags::ON_ADD_HOOK);
         }
-        if self.hooks().on_insert.is_some() {
+        if self.hooks().has_on_insert() {
             flags.insert(ArchetypeFlags::ON_INSERT_HOOK);
         }
-        if self.hooks().on_remove.is_some() {
+        if self.hooks().has_on_remove() || self.is_singleton() {
             flags.insert(ArchetypeFlags::ON_REMOVE_HOOK);
         }
     }
 
+    /// Returns the slot storing the entity that has this singleton component.
+    #[inline]
+    pub(crate) fn singleton(&self) -> Option<&SingletonEntity> {
+        self.singleton.as_ref()
+    }
+
     /// Provides a reference to the collection of hooks associated with this [`Component`]
     pub fn hooks(&self) -> &ComponentHooks {
         &self.hooks
     }
 }
 
+/// The entity that has a singleton component, see [`Component::SINGLETON`].
+///
+/// It is set when the component is added and cleared when it is removed, which only happens
+/// with exclusive access to the world.
+#[derive(Debug, Default)]
+pub(crate) struct SingletonEntity(AtomicU64);
+
+impl Clone for SingletonEntity {
+    fn clone(&self) -> Self {
+        Self(AtomicU64::new(self.0.load(Ordering::Relaxed)))
+    }
+}
+
+impl SingletonEntity {
+    #[inline]
+    pub(crate) fn get(&self) -> Option<Entity> {
+        // no entity has the bits `0`, since their generation is never zero
+        Entity::try_from_bits(self.0.load(Ordering::Relaxed)).ok()
+    }
+
+    #[inline]
+    pub(crate) fn set(&self, entity: Option<Entity>) {
+        self.0
+            .store(entity.map_or(0, Entity::to_bits), Ordering::Relaxed);
+    }
+}
+
 /// A value which uniquely identifies the type of a [`Component`] of [`Resource`] within a
 /// [`World`].
 ///
@@ -404,6 +763,8 @@ pub struct ComponentDescriptor {
     // SAFETY: This must remain private. It must match the statically known StorageType of the
     // associated rust component type if one exists.
     storage_type: StorageType,
+    default_storage_type: bool,
+    singleton: bool,
     // SAFETY: This must remain private. It must only be set to "true" if this component is
     // actually Send + Sync
     is_send_and_sync: bool,
@@ -444,6 +805,8 @@ impl ComponentDescriptor {
         Self {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type: T::STORAGE_TYPE,
+            default_storage_type: T::STORAGE_TYPE_IS_DEFAULT,
+            singleton: T::SINGLETON,
             is_send_and_sync: true,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
@@ -465,6 +828,8 @@ impl ComponentDescriptor {
         Self {
             name: name.into(),
             storage_type,
+            default_storage_type: false,
+            singleton: false,
             is_send_and_sync: true,
             type_id: None,
             layout,
@@ -481,6 +846,8 @@ impl ComponentDescriptor {
             // PERF: `SparseStorage` may actually be a more
             // reasonable choice as `storage_type` for resources.
             storage_type: StorageType::Table,
+            default_storage_type: false,
+            singleton: false,
             is_send_and_sync: true,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
@@ -492,6 +859,8 @@ impl ComponentDescriptor {
         Self {
             name: Cow::Borrowed(std::any::type_name::<T>()),
             storage_type,
+            default_storage_type: false,
+            singleton: false,
             is_send_and_sync: false,
             type_id: Some(TypeId::of::<T>()),
             layout: Layout::new::<T>(),
@@ -525,6 +894,7 @@ pub struct Components {
     components: Vec<ComponentInfo>,
     indices: TypeIdMap<ComponentId>,
     resource_indices: TypeIdMap<ComponentId>,
+    sealed: bool,
 }
 
 impl Components {
@@ -532,6 +902,10 @@ impl Components {
     /// If a component of this type has already been initialized, this will return
     /// the ID of the pre-existing component.
     ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't registered yet and the registry is [sealed](Components::is_sealed).
+    ///
     /// # See also
     ///
     /// * [`Components::component_id()`]
@@ -543,11 +917,13 @@ impl Components {
         let Components {
             indices,
             components,
+            sealed,
             ..
         } = self;
         *indices.entry(type_id).or_insert_with(|| {
             let index = Components::init_component_inner(
                 components,
+                *sealed,
                 storages,
                 ComponentDescriptor::new::<T>(),
             );
@@ -563,6 +939,10 @@ impl Components {
     /// If this method is called multiple times with identical descriptors, a distinct `ComponentId`
     /// will be created for each one.
     ///
+    /// # Panics
+    ///
+    /// Panics if the registry is [sealed](Components::is_sealed).
+    ///
     /// # See also
     ///
     /// * [`Components::component_id()`]
@@ -572,15 +952,22 @@ impl Components {
         storages: &mut Storages,
         descriptor: ComponentDescriptor,
     ) -> ComponentId {
-        Components::init_component_inner(&mut self.components, storages, descriptor)
+        Components::init_component_inner(&mut self.components, self.sealed, storages, descriptor)
     }
 
     #[inline]
     fn init_component_inner(
         components: &mut Vec<ComponentInfo>,
+        sealed: bool,
         storages: &mut Storages,
         descriptor: ComponentDescriptor,
     ) -> ComponentId {
+        assert!(
+            !sealed,
+            "Cannot register component `{}`: the component registry is sealed. \
+            Register it before calling `World::seal_component_registry`.",
+            descriptor.name()
+        );
         let component_id = ComponentId(components.len());
         let info = ComponentInfo::new(component_id, descriptor);
         if info.descriptor.storage_type == StorageType::SparseSet {
@@ -590,6 +977,18 @@ impl Components {
         component_id
     }
 
+    /// Returns `true` if registering new components is forbidden, see
+    /// [`World::seal_component_registry`](crate::world::World::seal_component_registry).
+    #[inline]
+    pub fn is_sealed(&self) -> bool {
+        self.sealed
+    }
+
+    /// Forbids registering new components.
+    pub(crate) fn seal(&mut self) {
+        self.sealed = true;
+    }
+
     /// Returns the number of components registered with this instance.
     #[inline]
     pub fn len(&self) -> usize {
@@ -629,6 +1028,11 @@ impl Components {
         unsafe { self.components.get_unchecked(id.0) }
     }
 
+    /// Sets the fields of the component `id` whose changes are tracked separately.
+    pub(crate) fn set_tracked_fields(&mut self, id: ComponentId, fields: Box<[&'static str]>) {
+        self.components[id.0].tracked_fields = Some(fields);
+    }
+
     #[inline]
     pub(crate) fn get_hooks_mut(&mut self, id: ComponentId) -> Option<&mut ComponentHooks> {
         self.components.get_mut(id.0).map(|info| &mut info.hooks)
@@ -767,6 +1171,8 @@ impl Components {
 /// This is used to power change detection.
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "bevy_reflect", derive(Reflect), reflect(Debug, PartialEq))]
+// Transparent so that the tick arrays of a `TableView` can be read as `u32`s.
+#[repr(transparent)]
 pub struct Tick {
     tick: u32,
 }
@@ -844,6 +1250,10 @@ pub struct TickCells<'a> {
     pub added: &'a UnsafeCell<Tick>,
     /// The tick indicating the last time the value was modified.
     pub changed: &'a UnsafeCell<Tick>,
+    /// The ticks indicating the last time each field of the value was modified, in the order of
+    /// [`ComponentInfo::tracked_fields`]. Empty if the value doesn't track the changes of its
+    /// fields.
+    pub fields: &'a [UnsafeCell<Tick>],
 }
 
 impl<'a> TickCells<'a> {
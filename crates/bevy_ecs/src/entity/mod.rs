@@ -55,7 +55,7 @@ use crate::{
         masks::{IdentifierMask, HIGH_MASK},
         Identifier,
     },
-    storage::{SparseSetIndex, TableId, TableRow},
+    storage::{shrink_vec, SparseSetIndex, TableId, TableRow},
 };
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -74,6 +74,41 @@ use std::sync::atomic::AtomicIsize as AtomicIdCursor;
 #[cfg(not(target_has_atomic = "64"))]
 type IdCursor = isize;
 
+/// The number of shards [`Entities`] splits its reservations into.
+///
+/// Threads are spread over the shards, so that parallel systems reserving entities touch
+/// different atomics instead of contending on a single one.
+#[cfg(feature = "sharded_entity_reservation")]
+const SHARD_COUNT: usize = 16;
+#[cfg(not(feature = "sharded_entity_reservation"))]
+const SHARD_COUNT: usize = 1;
+
+/// The maximum number of brand new IDs set aside for a single shard at each rebalance.
+///
+///
+/// Without sharding, there are no blocks: new IDs are handed out by the single counter past the
+/// freelist instead.
+#[cfg(feature = "sharded_entity_reservation")]
+const MAX_BLOCK_LEN: u32 = 1024;
+
+/// Returns the shard of [`Entities`] the current thread reserves from.
+#[cfg(feature = "sharded_entity_reservation")]
+fn shard_index() -> usize {
+    use std::sync::atomic::AtomicUsize;
+
+    static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+    thread_local! {
+        static SHARD: usize = NEXT_SHARD.fetch_add(1, Ordering::Relaxed) % SHARD_COUNT;
+    }
+    SHARD.with(|shard| *shard)
+}
+
+/// Returns the shard of [`Entities`] the current thread reserves from.
+#[cfg(not(feature = "sharded_entity_reservation"))]
+fn shard_index() -> usize {
+    0
+}
+
 /// Lightweight identifier of an [entity](crate::entity).
 ///
 /// The identifier is implemented using a [generational index]: a combination of an index and a generation.
@@ -422,7 +457,10 @@ pub struct ReserveEntitiesIterator<'a> {
     // Reserved indices formerly in the freelist to hand out.
     index_iter: std::slice::Iter<'a, u32>,
 
-    // New Entity indices to hand out, outside the range of meta.len().
+    // New Entity indices from the block of the shard, outside the range of meta.len().
+    block_range: std::ops::Range<u32>,
+
+    // New Entity indices past the blocks of all shards, outside the range of meta.len().
     index_range: std::ops::Range<u32>,
 }
 
@@ -435,11 +473,12 @@ impl<'a> Iterator for ReserveEntitiesIterator<'a> {
             .map(|&index| {
                 Entity::from_raw_and_generation(index, self.meta[index as usize].generation)
             })
+            .or_else(|| self.block_range.next().map(Entity::from_raw))
             .or_else(|| self.index_range.next().map(Entity::from_raw))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        let len = self.index_iter.len() + self.index_range.len();
+        let len = self.index_iter.len() + self.block_range.len() + self.index_range.len();
         (len, Some(len))
     }
 }
@@ -447,6 +486,104 @@ impl<'a> Iterator for ReserveEntitiesIterator<'a> {
 impl<'a> ExactSizeIterator for ReserveEntitiesIterator<'a> {}
 impl<'a> core::iter::FusedIterator for ReserveEntitiesIterator<'a> {}
 
+/// A shard of the IDs [`Entities`] can reserve concurrently, used by some of the threads.
+///
+/// A shard owns a slice of the freelist and a block of brand new IDs past `meta.len()`. Its
+/// `cursor` counts down as IDs are reserved, first from its freelist slice, then from its block:
+///
+/// ```txt
+///  overflow   |   block    |  freelist slice
+/// ------------0------------|------------------
+///                      block_len     block_len + free_len
+/// ```
+///
+/// Once both run out, the cursor goes negative, and IDs are reserved past the blocks of all
+/// shards, from a counter shared by every shard.
+///
+/// Shards are aligned to cache lines, so that threads reserving from different shards don't
+/// contend on the same line.
+#[derive(Debug)]
+#[repr(align(64))]
+struct EntityShard {
+    cursor: AtomicIdCursor,
+    /// The start of the slice of `pending` owned by this shard.
+    free_start: u32,
+    /// The length of the slice of `pending` owned by this shard.
+    free_len: u32,
+    /// The start of the block of new IDs owned by this shard, relative to `meta.len()`, so that
+    /// allocating with [`Entities::alloc`] doesn't invalidate it.
+    #[cfg(feature = "sharded_entity_reservation")]
+    block_offset: u32,
+    /// The length of the block of new IDs owned by this shard.
+    #[cfg(feature = "sharded_entity_reservation")]
+    block_len: u32,
+}
+
+impl EntityShard {
+    const fn new() -> Self {
+        EntityShard {
+            cursor: AtomicIdCursor::new(0),
+            free_start: 0,
+            free_len: 0,
+            #[cfg(feature = "sharded_entity_reservation")]
+            block_offset: 0,
+            #[cfg(feature = "sharded_entity_reservation")]
+            block_len: 0,
+        }
+    }
+
+    /// The start of the block of new IDs owned by this shard, relative to `meta.len()`.
+    #[cfg(feature = "sharded_entity_reservation")]
+    #[inline]
+    fn block_offset(&self) -> u32 {
+        self.block_offset
+    }
+
+    /// Without sharding, shards have no block.
+    #[cfg(not(feature = "sharded_entity_reservation"))]
+    #[inline]
+    fn block_offset(&self) -> u32 {
+        0
+    }
+
+    /// The length of the block of new IDs owned by this shard.
+    #[cfg(feature = "sharded_entity_reservation")]
+    #[inline]
+    fn block_len(&self) -> u32 {
+        self.block_len
+    }
+
+    /// Without sharding, shards have no block.
+    #[cfg(not(feature = "sharded_entity_reservation"))]
+    #[inline]
+    fn block_len(&self) -> u32 {
+        0
+    }
+
+    /// The value of the cursor when nothing was reserved from this shard.
+    fn available(&self) -> IdCursor {
+        self.free_len as IdCursor + self.block_len() as IdCursor
+    }
+
+    /// Sets the cursor back to [`available`](Self::available), once its reservations are
+    /// flushed or its slices changed.
+    fn reset_cursor(&mut self) {
+        *self.cursor.get_mut() = self.available();
+    }
+
+    /// Returns the number of IDs of the freelist slice left unreserved when the cursor is at
+    /// `cursor`.
+    fn free_remaining(&self, cursor: IdCursor) -> u32 {
+        (cursor - self.block_len() as IdCursor).clamp(0, self.free_len as IdCursor) as u32
+    }
+
+    /// Returns the number of IDs of the block reserved when the cursor is at `cursor`.
+    fn block_used(&self, cursor: IdCursor) -> u32 {
+        let block_len = self.block_len();
+        block_len - cursor.clamp(0, block_len as IdCursor) as u32
+    }
+}
+
 /// A [`World`]'s internal metadata store on all of its entities.
 ///
 /// Contains metadata on:
@@ -454,12 +591,17 @@ impl<'a> core::iter::FusedIterator for ReserveEntitiesIterator<'a> {}
 ///  - The alive/dead status of a particular entity. (i.e. "has entity 3 been despawned?")
 ///  - The location of the entity's components in memory (via [`EntityLocation`])
 ///
+/// Entity IDs can be reserved concurrently from any thread, from a single counter. To avoid
+/// contention when many threads reserve entities at once, enable the `sharded_entity_reservation`
+/// feature: reservations are then split into shards, and each thread reserves from its own shard,
+/// at the cost of more memory and of IDs that depend on which threads reserved them.
+///
 /// [`World`]: crate::world::World
 #[derive(Debug)]
 pub struct Entities {
     meta: Vec<EntityMeta>,
 
-    /// The `pending` and `free_cursor` fields describe three sets of Entity IDs
+    /// The `pending` list and the `shards` describe three sets of Entity IDs
     /// that have been freed or are in the process of being allocated:
     ///
     /// - The `freelist` IDs, previously freed by `free()`. These IDs are available to any of
@@ -473,44 +615,60 @@ pub struct Entities {
     /// - The count of new IDs that do not yet exist in `self.meta`, but which we have handed out
     ///   and reserved. [`flush`] will allocate room for them in `self.meta`.
     ///
-    /// The contents of `pending` look like this:
+    /// `pending` is split into one slice per shard. Each slice looks like this:
     ///
     /// ```txt
     /// ----------------------------
     /// |  freelist  |  reserved   |
     /// ----------------------------
-    ///              ^             ^
-    ///          free_cursor   pending.len()
+    /// ^            ^             ^
+    /// free_start   cursor    free_start + free_len
     /// ```
     ///
-    /// As IDs are allocated, `free_cursor` is atomically decremented, moving
-    /// items from the freelist into the reserved list by sliding over the boundary.
+    /// As IDs are allocated, the cursor of the shard is atomically decremented, moving
+    /// items from its freelist into its reserved list by sliding over the boundary.
     ///
-    /// Once the freelist runs out, `free_cursor` starts going negative.
-    /// The more negative it is, the more IDs have been reserved starting exactly at
-    /// the end of `meta.len()`.
+    /// Once its freelist runs out, the shard hands out the new IDs of its block, then IDs past
+    /// the blocks of all shards. See [`EntityShard`] for details.
     ///
-    /// This formulation allows us to reserve any number of IDs first from the freelist
-    /// and then from the new IDs, using only a single atomic subtract.
+    /// This formulation allows each thread to reserve any number of IDs first from the freelist
+    /// and then from the new IDs, using a single atomic subtract in the common case.
     ///
-    /// Once [`flush`] is done, `free_cursor` will equal `pending.len()`.
+    /// [`flush`] allocates the reserved IDs, then rebalances the shards: the freelist is split
+    /// between them and new blocks are set aside, according to the number of IDs each shard
+    /// reserved since the previous flush. The shard of the thread calling [`flush`] owns the end
+    /// of `pending`, which [`alloc`] and [`free`] pop from and push to.
     ///
     /// [`alloc`]: Entities::alloc
+    /// [`free`]: Entities::free
     /// [`reserve_entity`]: Entities::reserve_entity
     /// [`reserve_entities`]: Entities::reserve_entities
     /// [`flush`]: Entities::flush
     pending: Vec<u32>,
-    free_cursor: AtomicIdCursor,
+    shards: [EntityShard; SHARD_COUNT],
+    /// The shard owning the end of `pending`.
+    tail_shard: usize,
+    /// The number of new IDs set aside for the blocks of the shards, past `meta.len()`.
+    overflow_offset: u32,
+    /// The number of IDs reserved past the blocks of all shards.
+    ///
+    /// With a single shard, its negative cursor counts these instead.
+    #[cfg(feature = "sharded_entity_reservation")]
+    overflow: AtomicIdCursor,
     /// Stores the number of free entities for [`len`](Entities::len)
     len: u32,
 }
 
 impl Entities {
-    pub(crate) const fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Entities {
             meta: Vec::new(),
             pending: Vec::new(),
-            free_cursor: AtomicIdCursor::new(0),
+            shards: std::array::from_fn(|_| EntityShard::new()),
+            tail_shard: 0,
+            overflow_offset: 0,
+            #[cfg(feature = "sharded_entity_reservation")]
+            overflow: AtomicIdCursor::new(0),
             len: 0,
         }
     }
@@ -520,45 +678,40 @@ impl Entities {
     /// Storage for entity generation and location is lazily allocated by calling [`flush`](Entities::flush).
     #[allow(clippy::unnecessary_fallible_conversions)] // Because `IdCursor::try_from` may fail on 32-bit platforms.
     pub fn reserve_entities(&self, count: u32) -> ReserveEntitiesIterator {
-        // Use one atomic subtract to grab a range of new IDs. The range might be
-        // entirely nonnegative, meaning all IDs come from the freelist, or entirely
-        // negative, meaning they are all new IDs to allocate, or a mix of both.
-        let range_end = self
-            .free_cursor
+        let shard = &self.shards[shard_index()];
+        // Use one atomic subtract to grab a range of IDs from the shard. The range might be
+        // entirely above the block, meaning all IDs come from the freelist, or entirely
+        // negative, meaning they are all new IDs past the blocks, or a mix of them.
+        let range_end = shard
+            .cursor
             // Unwrap: these conversions can only fail on platforms that don't support 64-bit atomics
             // and use AtomicIsize instead (see note on `IdCursor`).
             .fetch_sub(IdCursor::try_from(count).unwrap(), Ordering::Relaxed);
         let range_start = range_end - IdCursor::try_from(count).unwrap();
 
-        let freelist_range = range_start.max(0) as usize..range_end.max(0) as usize;
+        // Values above the block index the freelist slice of the shard.
+        let block_len = shard.block_len() as IdCursor;
+        let free_start = shard.free_start as usize;
+        let freelist_range = free_start + (range_start.max(block_len) - block_len) as usize
+            ..free_start + (range_end.max(block_len) - block_len) as usize;
 
-        let (new_id_start, new_id_end) = if range_start >= 0 {
-            // We satisfied all requests from the freelist.
-            (0, 0)
-        } else {
-            // We need to allocate some new Entity IDs outside of the range of self.meta.
-            //
-            // `range_start` covers some negative territory, e.g. `-3..6`.
-            // Since the nonnegative values `0..6` are handled by the freelist, that
-            // means we need to handle the negative range here.
-            //
-            // In this example, we truncate the end to 0, leaving us with `-3..0`.
-            // Then we negate these values to indicate how far beyond the end of `meta.end()`
-            // to go, yielding `meta.len()+0 .. meta.len()+3`.
-            let base = self.meta.len() as IdCursor;
-
-            let new_id_end = u32::try_from(base - range_start).expect("too many entities");
-
-            // `new_id_end` is in range, so no need to check `start`.
-            let new_id_start = (base - range_end.min(0)) as u32;
-
-            (new_id_start, new_id_end)
-        };
+        // Values in `1..=block_len` count down the block, so the first IDs reserved from the
+        // block are at its start.
+        let block_start = range_end.clamp(0, block_len);
+        let block_end = range_start.clamp(0, block_len);
+        let block_range = self.new_index(shard.block_offset() as IdCursor + block_len - block_start)
+            ..self.new_index(shard.block_offset() as IdCursor + block_len - block_end);
+
+        // The nonpositive values are new IDs past the blocks of all shards.
+        let overflow_range = self.reserve_overflow(range_start, range_end);
+        let index_range = self.new_index(self.overflow_offset as IdCursor + overflow_range.start)
+            ..self.new_index(self.overflow_offset as IdCursor + overflow_range.end);
 
         ReserveEntitiesIterator {
             meta: &self.meta[..],
             index_iter: self.pending[freelist_range].iter(),
-            index_range: new_id_start..new_id_end,
+            block_range,
+            index_range,
         }
     }
 
@@ -566,23 +719,71 @@ impl Entities {
     ///
     /// Equivalent to `self.reserve_entities(1).next().unwrap()`, but more efficient.
     pub fn reserve_entity(&self) -> Entity {
-        let n = self.free_cursor.fetch_sub(1, Ordering::Relaxed);
-        if n > 0 {
+        let shard = &self.shards[shard_index()];
+        let n = shard.cursor.fetch_sub(1, Ordering::Relaxed);
+        let block_len = shard.block_len() as IdCursor;
+        if n > block_len {
             // Allocate from the freelist.
-            let index = self.pending[(n - 1) as usize];
+            let index = self.pending[shard.free_start as usize + (n - block_len - 1) as usize];
             Entity::from_raw_and_generation(index, self.meta[index as usize].generation)
+        } else if n > 0 {
+            // Grab a new ID from the block of the shard. `flush()` must eventually be called to
+            // make it valid.
+            Entity::from_raw(self.new_index(shard.block_offset() as IdCursor + block_len - n))
         } else {
-            // Grab a new ID, outside the range of `meta.len()`. `flush()` must
-            // eventually be called to make it valid.
-            //
-            // As `self.free_cursor` goes more and more negative, we return IDs farther
-            // and farther beyond `meta.len()`.
-            Entity::from_raw(
-                u32::try_from(self.meta.len() as IdCursor - n).expect("too many entities"),
-            )
+            // Grab a new ID past the blocks of all shards.
+            let offset = self.reserve_overflow(n - 1, n).start;
+            Entity::from_raw(self.new_index(self.overflow_offset as IdCursor + offset))
         }
     }
 
+    /// Returns the index of the new ID `offset` IDs past the end of `meta`.
+    fn new_index(&self, offset: IdCursor) -> u32 {
+        u32::try_from(self.meta.len() as IdCursor + offset).expect("too many entities")
+    }
+
+    /// Reserves the IDs past the blocks of all shards for the nonpositive values of the cursor
+    /// of a shard in `range_start..range_end`, returning their offsets past the blocks.
+    #[cfg(feature = "sharded_entity_reservation")]
+    fn reserve_overflow(
+        &self,
+        range_start: IdCursor,
+        range_end: IdCursor,
+    ) -> std::ops::Range<IdCursor> {
+        let count = range_end.min(0) - range_start.min(0);
+        if count == 0 {
+            return 0..0;
+        }
+        let start = self.overflow.fetch_add(count, Ordering::Relaxed);
+        start..start + count
+    }
+
+    /// Reserves the IDs past the freelist for the negative values of the cursor in
+    /// `range_start..range_end`, returning their offsets past the end of `meta`.
+    ///
+    /// As the cursor goes more and more negative, we return IDs farther and farther beyond
+    /// `meta.len()`.
+    #[cfg(not(feature = "sharded_entity_reservation"))]
+    fn reserve_overflow(
+        &self,
+        range_start: IdCursor,
+        range_end: IdCursor,
+    ) -> std::ops::Range<IdCursor> {
+        -range_end.min(0)..-range_start.min(0)
+    }
+
+    /// Returns the number of IDs reserved past the blocks of all shards.
+    #[cfg(feature = "sharded_entity_reservation")]
+    fn overflow_len(&self) -> IdCursor {
+        self.overflow.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of IDs reserved past the freelist.
+    #[cfg(not(feature = "sharded_entity_reservation"))]
+    fn overflow_len(&self) -> IdCursor {
+        (-self.shards[0].cursor.load(Ordering::Relaxed)).max(0)
+    }
+
     /// Check that we do not have pending work requiring `flush()` to be called.
     fn verify_flushed(&mut self) {
         debug_assert!(
@@ -591,13 +792,43 @@ impl Entities {
         );
     }
 
+    /// Gives the whole freelist to `shard`, which then owns the end of `pending`.
+    fn give_freelist_to(&mut self, shard: usize) {
+        for shard in &mut self.shards {
+            shard.free_start = 0;
+            shard.free_len = 0;
+        }
+        self.shards[shard].free_len = self.pending.len() as u32;
+        for shard in &mut self.shards {
+            shard.reset_cursor();
+        }
+        self.tail_shard = shard;
+    }
+
+    /// Gives the end of `pending` to the shard of the current thread, if it can be done without
+    /// taking IDs from the freelist slices of other shards.
+    ///
+    /// This keeps the freed IDs available to the thread freeing them, such as the main thread
+    /// of a single-threaded app.
+    fn claim_tail(&mut self) {
+        let shard = shard_index();
+        if shard != self.tail_shard && self.shards[self.tail_shard].free_start == 0 {
+            self.give_freelist_to(shard);
+        }
+    }
+
     /// Allocate an entity ID directly.
     pub fn alloc(&mut self) -> Entity {
         self.verify_flushed();
         self.len += 1;
+        self.claim_tail();
+        if self.shards[self.tail_shard].free_len == 0 && !self.pending.is_empty() {
+            self.give_freelist_to(shard_index());
+        }
         if let Some(index) = self.pending.pop() {
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            let tail = &mut self.shards[self.tail_shard];
+            tail.free_len -= 1;
+            tail.reset_cursor();
             Entity::from_raw_and_generation(index, self.meta[index as usize].generation)
         } else {
             let index = u32::try_from(self.meta.len()).expect("too many entities");
@@ -616,16 +847,14 @@ impl Entities {
         let loc = if entity.index() as usize >= self.meta.len() {
             self.pending
                 .extend((self.meta.len() as u32)..entity.index());
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.give_freelist_to(shard_index());
             self.meta
                 .resize(entity.index() as usize + 1, EntityMeta::EMPTY);
             self.len += 1;
             None
         } else if let Some(index) = self.pending.iter().position(|item| *item == entity.index()) {
             self.pending.swap_remove(index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.give_freelist_to(shard_index());
             self.len += 1;
             None
         } else {
@@ -652,16 +881,14 @@ impl Entities {
         let result = if entity.index() as usize >= self.meta.len() {
             self.pending
                 .extend((self.meta.len() as u32)..entity.index());
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.give_freelist_to(shard_index());
             self.meta
                 .resize(entity.index() as usize + 1, EntityMeta::EMPTY);
             self.len += 1;
             AllocAtWithoutReplacement::DidNotExist
         } else if let Some(index) = self.pending.iter().position(|item| *item == entity.index()) {
             self.pending.swap_remove(index);
-            let new_free_cursor = self.pending.len() as IdCursor;
-            *self.free_cursor.get_mut() = new_free_cursor;
+            self.give_freelist_to(shard_index());
             self.len += 1;
             AllocAtWithoutReplacement::DidNotExist
         } else {
@@ -701,25 +928,24 @@ impl Entities {
 
         let loc = mem::replace(&mut meta.location, EntityMeta::EMPTY.location);
 
+        self.claim_tail();
         self.pending.push(entity.index());
 
-        let new_free_cursor = self.pending.len() as IdCursor;
-        *self.free_cursor.get_mut() = new_free_cursor;
+        let tail = &mut self.shards[self.tail_shard];
+        tail.free_len += 1;
+        tail.reset_cursor();
         self.len -= 1;
         Some(loc)
     }
 
     /// Ensure at least `n` allocations can succeed without reallocating.
-    #[allow(clippy::unnecessary_fallible_conversions)] // Because `IdCursor::try_from` may fail on 32-bit platforms.
     pub fn reserve(&mut self, additional: u32) {
         self.verify_flushed();
 
-        let freelist_size = *self.free_cursor.get_mut();
-        // Unwrap: these conversions can only fail on platforms that don't support 64-bit atomics
-        // and use AtomicIsize instead (see note on `IdCursor`).
-        let shortfall = IdCursor::try_from(additional).unwrap() - freelist_size;
+        let freelist_size = self.pending.len();
+        let shortfall = additional as usize - freelist_size.min(additional as usize);
         if shortfall > 0 {
-            self.meta.reserve(shortfall as usize);
+            self.meta.reserve(shortfall);
         }
     }
 
@@ -731,14 +957,33 @@ impl Entities {
             .map_or(false, |e| e.generation() == entity.generation())
     }
 
+    /// Resets the shards and the IDs reserved past their blocks, leaving the freelist to the
+    /// first shard.
+    fn reset_shards(&mut self) {
+        self.shards = std::array::from_fn(|_| EntityShard::new());
+        self.tail_shard = 0;
+        self.overflow_offset = 0;
+        #[cfg(feature = "sharded_entity_reservation")]
+        {
+            *self.overflow.get_mut() = 0;
+        }
+        self.shards[0].free_len = self.pending.len() as u32;
+        self.shards[0].reset_cursor();
+    }
+
     /// Clears all [`Entity`] from the World.
     pub fn clear(&mut self) {
         self.meta.clear();
         self.pending.clear();
-        *self.free_cursor.get_mut() = 0;
+        self.reset_shards();
         self.len = 0;
     }
 
+    /// Shrinks the free list to its length, returning the number of bytes freed.
+    pub(crate) fn shrink_to_fit(&mut self) -> usize {
+        shrink_vec(&mut self.pending)
+    }
+
     /// Returns the location of an [`Entity`].
     /// Note: for pending entities, returns `Some(EntityLocation::INVALID)`.
     #[inline]
@@ -799,22 +1044,56 @@ impl Entities {
         if let Some(&EntityMeta { generation, .. }) = self.meta.get(idu) {
             Some(Entity::from_raw_and_generation(index, generation))
         } else {
-            // `id` is outside of the meta list - check whether it is reserved but not yet flushed.
-            let free_cursor = self.free_cursor.load(Ordering::Relaxed);
-            // If this entity was manually created, then free_cursor might be positive
-            // Returning None handles that case correctly
-            let num_pending = usize::try_from(-free_cursor).ok()?;
-            (idu < self.meta.len() + num_pending).then_some(Entity::from_raw(index))
+            // `id` is outside of the meta list - check whether it is reserved but not yet flushed,
+            // either from the block of a shard or past the blocks.
+            let offset = (idu - self.meta.len()) as IdCursor;
+            let in_block = self.shards.iter().any(|shard| {
+                let used = shard.block_used(shard.cursor.load(Ordering::Relaxed));
+                let start = shard.block_offset() as IdCursor;
+                (start..start + used as IdCursor).contains(&offset)
+            });
+            let overflow_offset = self.overflow_offset as IdCursor;
+            let in_overflow =
+                (overflow_offset..overflow_offset + self.overflow_len()).contains(&offset);
+            (in_block || in_overflow).then_some(Entity::from_raw(index))
         }
     }
 
     fn needs_flush(&mut self) -> bool {
-        *self.free_cursor.get_mut() != self.pending.len() as IdCursor
+        self.shards
+            .iter_mut()
+            .any(|shard| *shard.cursor.get_mut() != shard.available())
+    }
+
+    /// Returns the end of the new IDs allocated from the blocks of the shards, relative to
+    /// `meta.len()`.
+    #[cfg(feature = "sharded_entity_reservation")]
+    fn blocks_end(&mut self) -> u32 {
+        self.shards
+            .iter_mut()
+            .map(|shard| {
+                let cursor = *shard.cursor.get_mut();
+                match shard.block_used(cursor) {
+                    0 => 0,
+                    used => shard.block_offset + used,
+                }
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Without sharding, shards have no block.
+    #[cfg(not(feature = "sharded_entity_reservation"))]
+    fn blocks_end(&mut self) -> u32 {
+        0
     }
 
     /// Allocates space for entities previously reserved with [`reserve_entity`](Entities::reserve_entity) or
     /// [`reserve_entities`](Entities::reserve_entities), then initializes each one using the supplied function.
     ///
+    /// This also rebalances the shards the entities are reserved from, according to the number of
+    /// entities reserved from each of them since the previous flush.
+    ///
     /// # Safety
     /// Flush _must_ set the entity location to the correct [`ArchetypeId`] for the given [`Entity`]
     /// each time init is called. This _can_ be [`ArchetypeId::INVALID`], provided the [`Entity`]
@@ -823,35 +1102,134 @@ impl Entities {
     /// Note: freshly-allocated entities (ones which don't come from the pending list) are guaranteed
     /// to be initialized with the invalid archetype.
     pub unsafe fn flush(&mut self, mut init: impl FnMut(Entity, &mut EntityLocation)) {
-        let free_cursor = self.free_cursor.get_mut();
-        let current_free_cursor = *free_cursor;
+        if !self.needs_flush() {
+            return;
+        }
 
-        let new_free_cursor = if current_free_cursor >= 0 {
-            current_free_cursor as usize
+        // Allocate the new IDs reserved past the blocks, and from the blocks with sharding.
+        let overflow_len = self.overflow_len() as u32;
+        let overflow_end = if overflow_len > 0 {
+            self.overflow_offset + overflow_len
         } else {
-            let old_meta_len = self.meta.len();
-            let new_meta_len = old_meta_len + -current_free_cursor as usize;
-            self.meta.resize(new_meta_len, EntityMeta::EMPTY);
-            self.len += -current_free_cursor as u32;
-            for (index, meta) in self.meta.iter_mut().enumerate().skip(old_meta_len) {
-                init(
-                    Entity::from_raw_and_generation(index as u32, meta.generation),
-                    &mut meta.location,
-                );
-            }
-
-            *free_cursor = 0;
             0
         };
+        let new_len = overflow_end.max(self.blocks_end());
+        #[cfg(feature = "sharded_entity_reservation")]
+        let mut demand = [0; SHARD_COUNT];
+        #[cfg(feature = "sharded_entity_reservation")]
+        for (shard, demand) in self.shards.iter_mut().zip(&mut demand) {
+            *demand = (shard.available() - *shard.cursor.get_mut()) as u32;
+        }
 
-        self.len += (self.pending.len() - new_free_cursor) as u32;
-        for index in self.pending.drain(new_free_cursor..) {
+        let old_meta_len = self.meta.len();
+        self.meta
+            .resize(old_meta_len + new_len as usize, EntityMeta::EMPTY);
+        let mut init_new = |offset: u32| {
+            let index = old_meta_len as u32 + offset;
             let meta = &mut self.meta[index as usize];
             init(
                 Entity::from_raw_and_generation(index, meta.generation),
                 &mut meta.location,
             );
+        };
+        #[cfg(feature = "sharded_entity_reservation")]
+        let mut holes = Vec::new();
+        #[cfg(feature = "sharded_entity_reservation")]
+        for shard in &mut self.shards {
+            let cursor = *shard.cursor.get_mut();
+            let used = shard.block_used(cursor);
+            let block = shard.block_offset..shard.block_offset + shard.block_len;
+            for offset in block.start..block.start + used {
+                init_new(offset);
+            }
+            // Unused IDs of the block below the last allocated ID are now free.
+            holes.extend(block.start + used..block.end.min(new_len));
+        }
+        for offset in self.overflow_offset..self.overflow_offset + overflow_len {
+            init_new(offset);
+        }
+        self.len += new_len;
+        #[cfg(feature = "sharded_entity_reservation")]
+        {
+            self.len -= holes.len() as u32;
         }
+
+        // Allocate the IDs reserved from the freelist slices, which sit at the end of each slice,
+        // and move the rest of each slice next to the others.
+        let mut by_start: [usize; SHARD_COUNT] = std::array::from_fn(|shard| shard);
+        by_start.sort_unstable_by_key(|&shard| self.shards[shard].free_start);
+        let mut free_len = 0;
+        for shard in by_start {
+            let shard = &mut self.shards[shard];
+            let start = shard.free_start as usize;
+            let cursor = *shard.cursor.get_mut();
+            let remaining = shard.free_remaining(cursor) as usize;
+            for &index in &self.pending[start + remaining..start + shard.free_len as usize] {
+                let meta = &mut self.meta[index as usize];
+                init(
+                    Entity::from_raw_and_generation(index, meta.generation),
+                    &mut meta.location,
+                );
+            }
+            self.len += shard.free_len - remaining as u32;
+            self.pending.copy_within(start..start + remaining, free_len);
+            free_len += remaining;
+        }
+        self.pending.truncate(free_len);
+
+        #[cfg(feature = "sharded_entity_reservation")]
+        {
+            self.pending
+                .extend(holes.into_iter().map(|offset| old_meta_len as u32 + offset));
+            self.rebalance(&demand);
+        }
+        #[cfg(not(feature = "sharded_entity_reservation"))]
+        self.give_freelist_to(0);
+    }
+
+    /// Splits the freelist between the shards and sets aside blocks of new IDs for them,
+    /// according to the number of IDs each shard reserved since the previous flush.
+    ///
+    /// Shards get as many IDs from the freelist as they reserved, while the rest goes to the
+    /// shard of the current thread. Shards that reserved more IDs than they get from the
+    /// freelist get a block of new IDs for the difference.
+    #[cfg(feature = "sharded_entity_reservation")]
+    fn rebalance(&mut self, demand: &[u32; SHARD_COUNT]) {
+        let home = shard_index();
+        let mut remaining = self.pending.len() as u32;
+        for (shard, &demand) in self.shards.iter_mut().zip(demand) {
+            shard.free_len = demand.min(remaining);
+            remaining -= shard.free_len;
+        }
+        self.shards[home].free_len += remaining;
+
+        // Lay the slices out with the shard of the current thread last, so that it owns the end
+        // of `pending`.
+        let mut free_start = 0;
+        for shard in (0..SHARD_COUNT)
+            .filter(|&shard| shard != home)
+            .chain([home])
+        {
+            let shard = &mut self.shards[shard];
+            shard.free_start = free_start;
+            free_start += shard.free_len;
+        }
+        self.tail_shard = home;
+
+        let mut block_offset = 0;
+        for (shard, &demand) in self.shards.iter_mut().zip(demand) {
+            let shortfall = demand.saturating_sub(shard.free_len);
+            shard.block_offset = block_offset;
+            shard.block_len = if shortfall > 0 {
+                shortfall.next_power_of_two().min(MAX_BLOCK_LEN)
+            } else {
+                0
+            };
+            block_offset += shard.block_len;
+            shard.reset_cursor();
+        }
+        self.overflow_offset = block_offset;
+        *self.overflow.get_mut() = 0;
     }
 
     /// Flushes all reserved entities to an "invalid" state. Attempting to retrieve them will return `None`
@@ -870,8 +1248,8 @@ impl Entities {
     ///
     /// This function is safe if and only if the world this Entities is on has no entities.
     pub unsafe fn flush_and_reserve_invalid_assuming_no_entities(&mut self, count: usize) {
-        let free_cursor = self.free_cursor.get_mut();
-        *free_cursor = 0;
+        self.pending.clear();
+        self.reset_shards();
         self.meta.reserve(count);
         // SAFETY: The EntityMeta struct only contains integers, and it is valid to have all bytes set to u8::MAX
         unsafe {
@@ -1017,6 +1395,56 @@ mod tests {
         assert!(entities.get(e).is_none());
     }
 
+    #[test]
+    fn reserve_entities_from_many_threads() {
+        let mut entities = Entities::new();
+        // Rebalancing after each round sets blocks and freelist slices aside for the threads.
+        for _ in 0..3 {
+            let reserved: Vec<Entity> = std::thread::scope(|scope| {
+                let threads: Vec<_> = (0..8)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut reserved: Vec<_> = entities.reserve_entities(50).collect();
+                            reserved.extend((0..50).map(|_| entities.reserve_entity()));
+                            reserved
+                        })
+                    })
+                    .collect();
+                threads
+                    .into_iter()
+                    .flat_map(|thread| thread.join().unwrap())
+                    .collect()
+            });
+            let unique: bevy_utils::HashSet<_> = reserved.iter().map(|e| e.index()).collect();
+            assert_eq!(unique.len(), 800);
+            assert!(reserved.iter().all(|&e| entities.contains(e)));
+
+            entities.flush_as_invalid();
+            assert_eq!(entities.len(), 800);
+            for entity in reserved {
+                assert!(entities.free(entity).is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn reserve_reuses_freed_entities() {
+        let mut entities = Entities::new();
+        let reserved: Vec<_> = entities.reserve_entities(100).collect();
+        entities.flush_as_invalid();
+        for entity in reserved {
+            entities.free(entity);
+        }
+
+        let reserved: Vec<_> = entities.reserve_entities(100).collect();
+        assert!(reserved
+            .iter()
+            .all(|e| e.index() < 100 && e.generation() == 2));
+        entities.flush_as_invalid();
+        assert_eq!(entities.len(), 100);
+        assert_eq!(entities.total_count(), 100);
+    }
+
     #[test]
     fn entity_const() {
         const C1: Entity = Entity::from_raw(42);
@@ -0,0 +1,249 @@
+//! Disabling entities, by hiding them from the queries that don't ask for them.
+//!
+//! An entity with a *disabling component*, such as [`Disabled`], is skipped by every query that
+//! doesn't mention that component. Queries mention a component when they read it, even
+//! optionally, or filter on it with [`With`](crate::query::With),
+//! [`Without`](crate::query::Without) or [`Has`](crate::query::Has):
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::entity_disabling::Disabled;
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! world.spawn(Health(10));
+//! world.spawn((Health(20), Disabled));
+//!
+//! assert_eq!(world.query::<&Health>().iter(&world).count(), 1);
+//! assert_eq!(world.query::<(&Health, Has<Disabled>)>().iter(&world).count(), 2);
+//! ```
+//!
+//! Disabled entities can also be reached from a system with
+//! [`Query::including_disabled`](crate::system::Query::including_disabled).
+//!
+//! [`Disabled`] is the only disabling component of a new [`World`]. The set of disabling
+//! components, such as `Prefab` or `EditorOnly` markers, is built with
+//! [`DefaultQueryFilters::builder`].
+
+use crate::{
+    self as bevy_ecs,
+    component::{Component, ComponentId, StorageType},
+    query::FilteredAccess,
+    world::World,
+};
+
+/// A marker component hiding its entity from the queries that don't mention it.
+///
+/// See the [module docs](crate::entity_disabling) for more information.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Disabled;
+
+/// The disabling components of a [`World`], hiding their entities from the queries that don't
+/// mention them.
+///
+/// The filters of a query are computed when its [`QueryState`](crate::query::QueryState) is
+/// created, so changing the filters of a world with [`World::set_default_query_filters`] only
+/// affects the queries created afterwards, and should be done before adding systems.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DefaultQueryFilters {
+    disabling: Vec<ComponentId>,
+}
+
+impl DefaultQueryFilters {
+    /// Creates filters without any disabling component.
+    pub const fn empty() -> Self {
+        Self {
+            disabling: Vec::new(),
+        }
+    }
+
+    /// Returns a builder adding disabling components to the current filters of `world`.
+    pub fn builder(world: &mut World) -> DefaultQueryFiltersBuilder<'_> {
+        let filters = world.default_query_filters().clone();
+        DefaultQueryFiltersBuilder { world, filters }
+    }
+
+    /// Returns the disabling components.
+    pub fn disabling_components(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.disabling.iter().copied()
+    }
+
+    /// Returns `true` if the component with the given `id` is a disabling component.
+    pub fn is_disabling(&self, id: ComponentId) -> bool {
+        self.disabling.contains(&id)
+    }
+
+    /// Returns the disabling components that aren't mentioned by `access`, and thus hide
+    /// entities from a query with that access.
+    pub(crate) fn filtered_by(&self, access: &FilteredAccess<ComponentId>) -> Vec<ComponentId> {
+        self.disabling
+            .iter()
+            .copied()
+            .filter(|&id| {
+                let index = id.index();
+                !access.access().has_read(id)
+                    && !access.access().has_archetypal(id)
+                    && !access
+                        .filter_sets
+                        .iter()
+                        .any(|set| set.with.contains(index) || set.without.contains(index))
+            })
+            .collect()
+    }
+}
+
+/// A builder for [`DefaultQueryFilters`], created with [`DefaultQueryFilters::builder`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::entity_disabling::DefaultQueryFilters;
+/// #[derive(Component)]
+/// struct Prefab;
+///
+/// #[derive(Component)]
+/// struct EditorOnly;
+///
+/// let mut world = World::new();
+/// let filters = DefaultQueryFilters::builder(&mut world)
+///     .disabling::<Prefab>()
+///     .disabling::<EditorOnly>()
+///     .build();
+/// world.set_default_query_filters(filters);
+/// ```
+pub struct DefaultQueryFiltersBuilder<'w> {
+    world: &'w mut World,
+    filters: DefaultQueryFilters,
+}
+
+impl DefaultQueryFiltersBuilder<'_> {
+    /// Adds `C` to the disabling components.
+    ///
+    /// # Panics
+    ///
+    /// If `C` isn't stored in tables.
+    pub fn disabling<C: Component>(self) -> Self {
+        let id = self.world.init_component::<C>();
+        self.disabling_by_id(id)
+    }
+
+    /// Adds the component with the given `id` to the disabling components.
+    ///
+    /// # Panics
+    ///
+    /// If the component isn't registered in the world, or isn't stored in tables. Queries skip
+    /// disabled entities by skipping whole tables, so [`SparseSet`](StorageType::SparseSet)
+    /// components can't disable entities.
+    pub fn disabling_by_id(mut self, id: ComponentId) -> Self {
+        let info = self
+            .world
+            .components()
+            .get_info(id)
+            .unwrap_or_else(|| panic!("Component {id:?} is not registered in the world."));
+        assert_eq!(
+            info.storage_type(),
+            StorageType::Table,
+            "Disabling component {} must be stored in tables.",
+            info.name()
+        );
+        if !self.filters.is_disabling(id) {
+            self.filters.disabling.push(id);
+        }
+        self
+    }
+
+    /// Removes the component with the given `id` from the disabling components.
+    pub fn enabling_by_id(mut self, id: ComponentId) -> Self {
+        self.filters.disabling.retain(|&disabling| disabling != id);
+        self
+    }
+
+    /// Returns the filters.
+    pub fn build(self) -> DefaultQueryFilters {
+        self.filters
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::*,
+        system::{RunSystemOnce, SystemState},
+    };
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct Prefab;
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Sparse;
+
+    #[test]
+    fn disabled_entities_are_skipped() {
+        let mut world = World::new();
+        let enabled = world.spawn(A).id();
+        let disabled = world.spawn((A, Disabled)).id();
+
+        let mut query = world.query::<Entity>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [enabled]);
+        assert!(world.query::<&A>().get(&world, disabled).is_err());
+        assert_eq!(
+            world
+                .query_filtered::<Entity, With<Disabled>>()
+                .iter(&world)
+                .collect::<Vec<_>>(),
+            [disabled]
+        );
+        assert_eq!(world.query::<Option<&Disabled>>().iter(&world).count(), 2);
+
+        world.entity_mut(disabled).remove::<Disabled>();
+        assert_eq!(query.iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn including_disabled() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn((A, Disabled));
+
+        let counts = world.run_system_once(|mut query: Query<&A>| {
+            let count = query.iter().count();
+            (count, query.including_disabled().query().iter().count())
+        });
+        assert_eq!(counts, (1, 2));
+    }
+
+    #[test]
+    fn custom_disabling_components() {
+        let mut world = World::new();
+        world.spawn(A);
+        world.spawn((A, Prefab));
+        world.spawn((A, Disabled));
+
+        let filters = DefaultQueryFilters::builder(&mut world)
+            .disabling::<Prefab>()
+            .build();
+        world.set_default_query_filters(filters);
+        let mut state = SystemState::<Query<&A>>::new(&mut world);
+        assert_eq!(state.get(&world).iter().count(), 1);
+
+        let disabled = world.init_component::<Disabled>();
+        let filters = DefaultQueryFilters::builder(&mut world)
+            .enabling_by_id(disabled)
+            .build();
+        world.set_default_query_filters(filters);
+        assert_eq!(world.query::<&A>().iter(&world).count(), 2);
+        assert_eq!(world.query::<(&A, &Prefab)>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sparse_disabling_component() {
+        let mut world = World::new();
+        DefaultQueryFilters::builder(&mut world).disabling::<Sparse>();
+    }
+}
@@ -22,6 +22,10 @@ use std::{
     iter::Chain,
     marker::PhantomData,
     slice::Iter,
+    sync::{
+        atomic::{AtomicUsize, Ordering as AtomicOrdering},
+        Arc,
+    },
 };
 
 /// A type that can be stored in an [`Events<E>`] resource
@@ -457,6 +461,10 @@ impl<'w, 's, E: Event> EventReader<'w, 's, E> {
     /// Returns a parallel iterator over the events this [`EventReader`] has not seen yet.
     /// See also [`for_each`](EventParIter::for_each).
     ///
+    /// The size of the batches sent to each task can be set with
+    /// [`batching_strategy`](EventParIter::batching_strategy), for example with
+    /// [`BatchingStrategy::fixed`].
+    ///
     /// # Example
     /// ```
     /// # use bevy_ecs::prelude::*;
@@ -1110,6 +1118,251 @@ impl<E: Event> ExactSizeIterator for SendBatchIds<E> {
     }
 }
 
+/// What a [`BoundedEvents`] channel does with an event sent while it is full.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Drop the oldest pending event to make room for the new one.
+    #[default]
+    DropOldest,
+    /// Drop the event being sent, and return it in a [`BoundedSendError::Full`].
+    DropNewest,
+    /// Panic, for events that must never be lost.
+    Panic,
+    /// Wait for room in [`BoundedEventSender::send_async`], applying backpressure to async
+    /// producers. Sending synchronously fails with [`BoundedSendError::Full`] instead, since
+    /// waiting for the app to drain the channel from a system would deadlock.
+    Block,
+}
+
+/// An error returned when sending an event to a [`BoundedEvents`] channel.
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum BoundedSendError<E> {
+    /// The channel is full, and its [`OverflowPolicy`] rejected the event.
+    #[error("the event channel is full")]
+    Full(E),
+    /// The [`BoundedEvents`] resource was dropped.
+    #[error("the event channel is closed")]
+    Closed(E),
+}
+
+/// A snapshot of the metrics of a [`BoundedEvents`] channel, to detect producers outpacing the
+/// app.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BoundedEventMetrics {
+    /// The number of events accepted by the channel.
+    pub sent: usize,
+    /// The number of events lost to the [`OverflowPolicy`], either evicted or rejected.
+    pub dropped: usize,
+    /// The number of sends that had to wait for room, with [`OverflowPolicy::Block`].
+    pub blocked: usize,
+    /// The highest number of events pending in the channel at once.
+    pub peak_len: usize,
+}
+
+#[derive(Default)]
+struct BoundedEventCounters {
+    sent: AtomicUsize,
+    dropped: AtomicUsize,
+    blocked: AtomicUsize,
+    peak_len: AtomicUsize,
+}
+
+/// A bounded queue of events of type `E`, forwarded to [`Events<E>`] at the start of each update.
+///
+/// Unlike [`Events`], which grows as long as events are sent, this holds at most
+/// [`capacity`](Self::capacity) pending events, and applies an [`OverflowPolicy`] beyond that.
+/// Its [`metrics`](Self::metrics) show when producers outpace the app, for example during frame
+/// hitches. The events are read as usual, with an [`EventReader`].
+///
+/// Use [`sender`](Self::sender) to send events from other threads or async tasks. Add it to an
+/// app with `App::add_bounded_event`, which schedules [`forward_bounded_events`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::event::{BoundedEvents, OverflowPolicy};
+/// #[derive(Event, Debug)]
+/// struct Packet(Vec<u8>);
+///
+/// let packets = BoundedEvents::<Packet>::new(2, OverflowPolicy::DropOldest);
+/// let sender = packets.sender();
+/// for i in 0..3 {
+///     sender.send(Packet(vec![i])).unwrap();
+/// }
+///
+/// // the first packet was dropped to make room for the last one
+/// assert_eq!(packets.len(), 2);
+/// assert_eq!(packets.metrics().dropped, 1);
+/// ```
+#[derive(Resource)]
+pub struct BoundedEvents<E: Event> {
+    sender: BoundedEventSender<E>,
+}
+
+impl<E: Event> BoundedEvents<E> {
+    /// Creates a channel holding at most `capacity` pending events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize, policy: OverflowPolicy) -> Self {
+        assert!(capacity > 0, "a bounded event channel needs a capacity");
+        let (sender, receiver) = async_channel::bounded(capacity);
+        Self {
+            sender: BoundedEventSender {
+                sender,
+                receiver,
+                policy,
+                counters: Arc::default(),
+            },
+        }
+    }
+
+    /// Returns a handle to send events to this channel from any thread.
+    pub fn sender(&self) -> BoundedEventSender<E> {
+        self.sender.clone()
+    }
+
+    /// Sends an event, see [`BoundedEventSender::send`].
+    pub fn send(&self, event: E) -> Result<(), BoundedSendError<E>> {
+        self.sender.send(event)
+    }
+
+    /// Returns the maximum number of pending events.
+    pub fn capacity(&self) -> usize {
+        self.sender.sender.capacity().unwrap_or(usize::MAX)
+    }
+
+    /// Returns what happens to events sent while the channel is full.
+    pub fn policy(&self) -> OverflowPolicy {
+        self.sender.policy
+    }
+
+    /// Returns the number of pending events.
+    pub fn len(&self) -> usize {
+        self.sender.sender.len()
+    }
+
+    /// Returns `true` if there are no pending events.
+    pub fn is_empty(&self) -> bool {
+        self.sender.sender.is_empty()
+    }
+
+    /// Returns the metrics of the channel since it was created.
+    pub fn metrics(&self) -> BoundedEventMetrics {
+        let counters = &self.sender.counters;
+        BoundedEventMetrics {
+            sent: counters.sent.load(AtomicOrdering::Relaxed),
+            dropped: counters.dropped.load(AtomicOrdering::Relaxed),
+            blocked: counters.blocked.load(AtomicOrdering::Relaxed),
+            peak_len: counters.peak_len.load(AtomicOrdering::Relaxed),
+        }
+    }
+
+    /// Removes the pending events, oldest first, making room for new ones.
+    pub fn drain(&self) -> impl Iterator<Item = E> + '_ {
+        std::iter::from_fn(|| self.sender.receiver.try_recv().ok())
+    }
+}
+
+impl<E: Event> Drop for BoundedEvents<E> {
+    fn drop(&mut self) {
+        // wake up and fail the producers waiting for room
+        self.sender.sender.close();
+    }
+}
+
+/// A handle to send events to a [`BoundedEvents`] channel from any thread, returned by
+/// [`BoundedEvents::sender`].
+pub struct BoundedEventSender<E> {
+    sender: async_channel::Sender<E>,
+    // used to evict the oldest event with `OverflowPolicy::DropOldest`
+    receiver: async_channel::Receiver<E>,
+    policy: OverflowPolicy,
+    counters: Arc<BoundedEventCounters>,
+}
+
+impl<E> Clone for BoundedEventSender<E> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            receiver: self.receiver.clone(),
+            policy: self.policy,
+            counters: self.counters.clone(),
+        }
+    }
+}
+
+impl<E: Event> BoundedEventSender<E> {
+    /// Sends an event without waiting, applying the [`OverflowPolicy`] if the channel is full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the channel is full and its policy is [`OverflowPolicy::Panic`].
+    pub fn send(&self, mut event: E) -> Result<(), BoundedSendError<E>> {
+        loop {
+            match self.sender.try_send(event) {
+                Ok(()) => {
+                    self.record_sent();
+                    return Ok(());
+                }
+                Err(async_channel::TrySendError::Closed(event)) => {
+                    return Err(BoundedSendError::Closed(event));
+                }
+                Err(async_channel::TrySendError::Full(rejected)) => match self.policy {
+                    OverflowPolicy::DropOldest => {
+                        if self.receiver.try_recv().is_ok() {
+                            self.counters.dropped.fetch_add(1, AtomicOrdering::Relaxed);
+                        }
+                        // retry, as other producers may have filled the room again
+                        event = rejected;
+                    }
+                    OverflowPolicy::DropNewest | OverflowPolicy::Block => {
+                        self.counters.dropped.fetch_add(1, AtomicOrdering::Relaxed);
+                        return Err(BoundedSendError::Full(rejected));
+                    }
+                    OverflowPolicy::Panic => panic!(
+                        "the bounded event channel of `{}` overflowed its capacity of {}",
+                        std::any::type_name::<E>(),
+                        self.sender.capacity().unwrap_or(usize::MAX)
+                    ),
+                },
+            }
+        }
+    }
+
+    /// Sends an event, waiting for room if the channel is full and its policy is
+    /// [`OverflowPolicy::Block`]. Other policies behave like [`send`](Self::send).
+    pub async fn send_async(&self, event: E) -> Result<(), BoundedSendError<E>> {
+        if self.policy != OverflowPolicy::Block {
+            return self.send(event);
+        }
+        if self.sender.is_full() {
+            self.counters.blocked.fetch_add(1, AtomicOrdering::Relaxed);
+        }
+        self.sender
+            .send(event)
+            .await
+            .map_err(|error| BoundedSendError::Closed(error.into_inner()))?;
+        self.record_sent();
+        Ok(())
+    }
+
+    fn record_sent(&self) {
+        self.counters.sent.fetch_add(1, AtomicOrdering::Relaxed);
+        self.counters
+            .peak_len
+            .fetch_max(self.sender.len(), AtomicOrdering::Relaxed);
+    }
+}
+
+/// Sends the events pending in [`BoundedEvents<E>`] to [`Events<E>`].
+pub fn forward_bounded_events<E: Event>(
+    bounded_events: Res<BoundedEvents<E>>,
+    mut events: EventWriter<E>,
+) {
+    events.send_batch(bounded_events.drain());
+}
+
 #[cfg(test)]
 mod tests {
     use crate::system::assert_is_read_only_system;
@@ -1121,6 +1374,52 @@ mod tests {
         i: usize,
     }
 
+    #[test]
+    fn bounded_events_overflow() {
+        let events = BoundedEvents::<TestEvent>::new(1, OverflowPolicy::DropNewest);
+        assert_eq!(events.send(TestEvent { i: 0 }), Ok(()));
+        assert_eq!(
+            events.send(TestEvent { i: 1 }),
+            Err(BoundedSendError::Full(TestEvent { i: 1 }))
+        );
+        assert_eq!(events.drain().collect::<Vec<_>>(), [TestEvent { i: 0 }]);
+        assert_eq!(
+            events.metrics(),
+            BoundedEventMetrics {
+                sent: 1,
+                dropped: 1,
+                blocked: 0,
+                peak_len: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn bounded_events_block() {
+        let events = BoundedEvents::<TestEvent>::new(1, OverflowPolicy::Block);
+        let sender = events.sender();
+        sender.send(TestEvent { i: 0 }).unwrap();
+
+        let producer =
+            std::thread::spawn(move || bevy_tasks::block_on(sender.send_async(TestEvent { i: 1 })));
+        while events.metrics().blocked == 0 {
+            std::thread::yield_now();
+        }
+
+        // making room unblocks the producer
+        assert_eq!(events.drain().next(), Some(TestEvent { i: 0 }));
+        producer.join().unwrap().unwrap();
+        assert_eq!(events.drain().collect::<Vec<_>>(), [TestEvent { i: 1 }]);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed its capacity of 1")]
+    fn bounded_events_panic() {
+        let events = BoundedEvents::<TestEvent>::new(1, OverflowPolicy::Panic);
+        let _ = events.send(TestEvent { i: 0 });
+        let _ = events.send(TestEvent { i: 1 });
+    }
+
     #[test]
     fn test_events() {
         let mut events = Events::<TestEvent>::default();
@@ -0,0 +1,235 @@
+//! Buffers of past component values, for lag compensation, replays and debugging.
+//!
+//! Insert a [`History<T>`] next to a component `T` and add [`record_history::<T>`] to a schedule
+//! that runs after the systems modifying `T`.
+//! Every time `T` changes, its new value is stored in the history along with the [`Tick`] at which
+//! it changed, keeping up to [`History::capacity`] values.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::history::{record_history, History};
+//! #[derive(Component, Clone)]
+//! struct Position(f32);
+//!
+//! fn move_right(mut query: Query<&mut Position>) {
+//!     for mut position in &mut query {
+//!         position.0 += 1.0;
+//!     }
+//! }
+//!
+//! let mut world = World::new();
+//! let entity = world.spawn((Position(0.0), History::<Position>::new(8))).id();
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems((move_right, record_history::<Position>).chain());
+//! schedule.run(&mut world);
+//! schedule.run(&mut world);
+//!
+//! let history = world.get::<History<Position>>(entity).unwrap();
+//! let positions: Vec<f32> = history.values().map(|position| position.0).collect();
+//! assert_eq!(positions, [2.0, 1.0]);
+//! ```
+
+use std::collections::VecDeque;
+
+use crate::{
+    self as bevy_ecs,
+    change_detection::{DetectChanges, DetectChangesMut, Ref, CHECK_TICK_THRESHOLD},
+    component::{Component, Tick},
+    system::{Local, Query, SystemChangeTick},
+};
+
+/// A [`Component`] keeping the last values of the component `T` on the same entity, along with the
+/// [`Tick`] at which each of them was set.
+///
+/// Values are only recorded by the [`record_history::<T>`] system.
+/// See the [module-level documentation](self) for more details.
+#[derive(Component, Debug, Clone)]
+pub struct History<T: Component + Clone> {
+    /// The recorded values, newest first.
+    entries: VecDeque<(Tick, T)>,
+    capacity: usize,
+}
+
+impl<T: Component + Clone> History<T> {
+    /// Creates an empty history keeping up to `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        assert!(
+            capacity > 0,
+            "a History must be able to hold at least one value"
+        );
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Returns the maximum number of values kept by this history.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of recorded values.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no value has been recorded yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the most recently recorded value.
+    pub fn latest(&self) -> Option<&T> {
+        self.entries.front().map(|(_, value)| value)
+    }
+
+    /// Returns the value recorded before the latest one.
+    pub fn previous(&self) -> Option<&T> {
+        self.entries.get(1).map(|(_, value)| value)
+    }
+
+    /// Returns the value that was current at `tick`: the newest value set at or before `tick`.
+    ///
+    /// `this_run` is the current tick of the system, used as a reference to deal with wraparound.
+    /// Returns `None` if `tick` is older than every recorded value.
+    pub fn at(&self, tick: Tick, this_run: Tick) -> Option<&T> {
+        self.iter()
+            .find(|(changed, _)| !changed.is_newer_than(tick, this_run))
+            .map(|(_, value)| value)
+    }
+
+    /// Iterates over the recorded values and the [`Tick`] at which each was set, newest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Tick, &T)> + ExactSizeIterator + '_ {
+        self.entries.iter().map(|(tick, value)| (*tick, value))
+    }
+
+    /// Iterates over the recorded values, newest first.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator + '_ {
+        self.entries.iter().map(|(_, value)| value)
+    }
+
+    /// Records `value` as set at `tick`, dropping the oldest value if the history is full.
+    pub fn push(&mut self, tick: Tick, value: T) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_back();
+        }
+        self.entries.push_front((tick, value));
+    }
+
+    /// Removes all recorded values.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Clamps the recorded ticks so they stay comparable with `change_tick`,
+    /// like [`World::check_change_ticks`](crate::world::World::check_change_ticks) does for
+    /// component ticks.
+    fn check_change_ticks(&mut self, change_tick: Tick) {
+        for (tick, _) in &mut self.entries {
+            tick.check_tick(change_tick);
+        }
+    }
+}
+
+/// Records the value of `T` into the [`History<T>`] of every entity where it changed since this
+/// system last ran.
+pub fn record_history<T: Component + Clone>(
+    mut query: Query<(Ref<T>, &mut History<T>)>,
+    system_ticks: SystemChangeTick,
+    mut last_check_tick: Local<Tick>,
+) {
+    // Like `World::check_change_ticks`, only clamp the recorded ticks once they could get too old.
+    let this_run = system_ticks.this_run();
+    let check_ticks = this_run.relative_to(*last_check_tick).get() >= CHECK_TICK_THRESHOLD;
+    if check_ticks {
+        *last_check_tick = this_run;
+    }
+    for (value, mut history) in &mut query {
+        if check_ticks {
+            history
+                .bypass_change_detection()
+                .check_change_ticks(this_run);
+        }
+        if value.is_changed() {
+            history.push(value.last_changed(), value.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_history, History};
+    use crate as bevy_ecs;
+    use crate::{change_detection::MAX_CHANGE_AGE, prelude::*};
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    struct Health(u32);
+
+    #[test]
+    fn records_changes_up_to_capacity() {
+        let mut world = World::new();
+        let entity = world.spawn((Health(10), History::<Health>::new(2))).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record_history::<Health>);
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.get::<History<Health>>(entity).unwrap().len(), 1);
+
+        world.get_mut::<Health>(entity).unwrap().0 = 8;
+        schedule.run(&mut world);
+        world.get_mut::<Health>(entity).unwrap().0 = 5;
+        schedule.run(&mut world);
+
+        let history = world.get::<History<Health>>(entity).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.latest(), Some(&Health(5)));
+        assert_eq!(history.previous(), Some(&Health(8)));
+    }
+
+    #[test]
+    fn value_at_tick() {
+        let mut world = World::new();
+        let entity = world.spawn((Health(10), History::<Health>::new(4))).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record_history::<Health>);
+        schedule.run(&mut world);
+
+        let before_change = world.change_tick();
+        world.increment_change_tick();
+        world.get_mut::<Health>(entity).unwrap().0 = 3;
+        schedule.run(&mut world);
+
+        let this_run = world.change_tick();
+        let history = world.get::<History<Health>>(entity).unwrap();
+        assert_eq!(history.at(before_change, this_run), Some(&Health(10)));
+        assert_eq!(history.at(this_run, this_run), Some(&Health(3)));
+    }
+
+    #[test]
+    fn clamps_old_ticks() {
+        let mut world = World::new();
+        let entity = world.spawn((Health(10), History::<Health>::new(4))).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(record_history::<Health>);
+        schedule.run(&mut world);
+
+        let change_tick = world.change_tick.get_mut();
+        *change_tick = change_tick.wrapping_add(MAX_CHANGE_AGE + 1);
+        let this_run = world.change_tick();
+        schedule.run(&mut world);
+
+        let history = world.get::<History<Health>>(entity).unwrap();
+        let (tick, _) = history.iter().next().unwrap();
+        assert_eq!(this_run.relative_to(tick).get(), MAX_CHANGE_AGE);
+        assert_eq!(history.at(tick, this_run), Some(&Health(10)));
+    }
+}
@@ -17,17 +17,22 @@ pub mod bundle;
 pub mod change_detection;
 pub mod component;
 pub mod entity;
+pub mod entity_disabling;
 pub mod event;
+pub mod history;
 pub mod identifier;
 pub mod intern;
 pub mod label;
 pub mod query;
+pub mod query_match;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
+pub mod relationship;
 pub mod removal_detection;
 pub mod schedule;
 pub mod storage;
 pub mod system;
+pub mod tag;
 pub mod world;
 
 pub use bevy_ptr as ptr;
@@ -46,8 +51,12 @@ pub mod prelude {
         component::Component,
         entity::{Entity, EntityMapper},
         event::{Event, EventReader, EventWriter, Events},
-        query::{Added, AnyOf, Changed, Has, Or, QueryBuilder, QueryState, With, Without},
-        removal_detection::RemovedComponents,
+        history::History,
+        query::{
+            Added, AnyOf, Changed, EntityChanged, Has, Or, QueryBuilder, QueryState, With, Without,
+        },
+        relationship::{Depth, Related, Relationship},
+        removal_detection::{RemovedBundle, RemovedComponents},
         schedule::{
             apply_deferred, common_conditions::*, Condition, IntoSystemConfigs, IntoSystemSet,
             IntoSystemSetConfigs, Schedule, Schedules, SystemSet,
@@ -66,10 +75,10 @@ mod tests {
     use crate::prelude::Or;
     use crate::{
         bundle::Bundle,
-        change_detection::Ref,
-        component::{Component, ComponentId},
+        change_detection::{DetectChangesMut, Ref},
+        component::{Component, ComponentId, ComponentTicks},
         entity::Entity,
-        query::{Added, Changed, FilteredAccess, QueryFilter, With, Without},
+        query::{Added, Changed, EntityChanged, FilteredAccess, QueryFilter, With, Without},
         system::Resource,
         world::{EntityRef, Mut, World},
     };
@@ -820,6 +829,32 @@ mod tests {
         // );
     }
 
+    #[test]
+    fn remove_bundle_tracking() {
+        let mut world = World::new();
+        let mut state = crate::system::SystemState::<
+            crate::removal_detection::RemovedBundle<(A, SparseStored)>,
+        >::new(&mut world);
+
+        let a = world.spawn((SparseStored(0), A(1))).id();
+        let b = world.spawn((SparseStored(1), A(2))).id();
+        let c = world.spawn((SparseStored(2), A(3), B(3))).id();
+        let d = world.spawn((SparseStored(3), A(4))).id();
+
+        world.entity_mut(a).despawn();
+        world.entity_mut(b).remove::<(A, SparseStored)>();
+        world.entity_mut(c).remove::<(A, B, SparseStored)>();
+        // Removing the components one after the other isn't a bundle removal.
+        world.entity_mut(d).remove::<A>();
+        world.entity_mut(d).remove::<SparseStored>();
+
+        assert_eq!(
+            state.get_mut(&mut world).read().collect::<Vec<_>>(),
+            &[a, b, c]
+        );
+        assert_eq!(state.get_mut(&mut world).read().count(), 0);
+    }
+
     #[test]
     fn added_tracking() {
         let mut world = World::new();
@@ -1063,6 +1098,76 @@ mod tests {
         assert_eq!(get_filtered::<Changed<SparseStored>>(&mut world), vec![e4]);
     }
 
+    #[test]
+    fn entity_changed_trackers() {
+        let mut world = World::default();
+        let e1 = world.spawn((A(0), B(0))).id();
+        let e2 = world.spawn(A(0)).id();
+        let e3 = world.spawn(SparseStored(0)).id();
+
+        fn get_changed(world: &mut World) -> Vec<Entity> {
+            let mut changed = world
+                .query_filtered::<Entity, EntityChanged>()
+                .iter(world)
+                .collect::<Vec<Entity>>();
+            changed.sort();
+            changed
+        }
+
+        assert_eq!(get_changed(&mut world), vec![e1, e2, e3]);
+        world.clear_trackers();
+        assert!(get_changed(&mut world).is_empty());
+
+        // dereferencing without writing doesn't change the entity
+        for a in world.query::<&mut A>().iter_mut(&mut world) {
+            assert_eq!(a.0, 0);
+        }
+        assert!(get_changed(&mut world).is_empty());
+
+        for mut b in world.query::<&mut B>().iter_mut(&mut world) {
+            b.0 += 1;
+        }
+        world.get_mut::<SparseStored>(e3).unwrap().0 += 1;
+        assert_eq!(get_changed(&mut world), vec![e1, e3]);
+
+        // ensure changing an entity's archetype also moves its changed state
+        world.entity_mut(e1).remove::<B>();
+        world.entity_mut(e2).remove::<A>();
+        assert_eq!(get_changed(&mut world), vec![e1, e3]);
+
+        let tick = world.change_tick();
+        world.clear_trackers();
+        world.entity_mut(e2).insert(C);
+        assert_eq!(get_changed(&mut world), vec![e2]);
+
+        let world = world.as_unsafe_world_cell();
+        assert!(!world.get_entity(e1).unwrap().any_changed_since(tick));
+        assert!(world.get_entity(e2).unwrap().any_changed_since(tick));
+    }
+
+    #[test]
+    fn entity_changed_keeps_newest_tick() {
+        let mut world = World::default();
+        let e = world.spawn((A(0), B(0))).id();
+
+        world.clear_trackers();
+        let last_run = world.change_tick();
+        world.increment_change_tick();
+        world.get_mut::<A>(e).unwrap().0 += 1;
+        // marking another component as changed at an older tick doesn't hide the change of `A`
+        world.get_mut::<B>(e).unwrap().set_changed_as(last_run);
+        let cell = world.as_unsafe_world_cell();
+        assert!(cell.get_entity(e).unwrap().any_changed_since(last_run));
+
+        world.clear_trackers();
+        let last_run = world.change_tick();
+        world.increment_change_tick();
+        let ticks = ComponentTicks::new(world.change_tick());
+        world.get_mut::<B>(e).unwrap().set_ticks(ticks);
+        let cell = world.as_unsafe_world_cell();
+        assert!(cell.get_entity(e).unwrap().any_changed_since(last_run));
+    }
+
     #[test]
     fn empty_spawn() {
         let mut world = World::default();
@@ -0,0 +1,246 @@
+use crate::{component::ComponentId, prelude::*, world::FilteredEntityMut};
+
+/// A term of a [`DynamicQueryBuilder`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DynamicQueryTerm {
+    /// Reads the component, and only matches the entities that have it. Like `&T`.
+    Read(ComponentId),
+    /// Writes the component, and only matches the entities that have it. Like `&mut T`.
+    Write(ComponentId),
+    /// Reads the component if the entity has it. Like `Option<&T>`.
+    OptionalRead(ComponentId),
+    /// Writes the component if the entity has it. Like `Option<&mut T>`.
+    OptionalWrite(ComponentId),
+    /// Only matches the entities that have the component. Like [`With<T>`].
+    With(ComponentId),
+    /// Only matches the entities that don't have the component. Like [`Without<T>`].
+    Without(ComponentId),
+}
+
+impl DynamicQueryTerm {
+    /// Returns the component of the term.
+    pub fn id(&self) -> ComponentId {
+        match *self {
+            Self::Read(id)
+            | Self::Write(id)
+            | Self::OptionalRead(id)
+            | Self::OptionalWrite(id)
+            | Self::With(id)
+            | Self::Without(id) => id,
+        }
+    }
+
+    fn is_write(&self) -> bool {
+        matches!(self, Self::Write(_) | Self::OptionalWrite(_))
+    }
+
+    fn is_data(&self) -> bool {
+        !matches!(self, Self::With(_) | Self::Without(_))
+    }
+}
+
+/// Describes a query purely from [`ComponentId`]s and access modes, without any type parameter.
+///
+/// Unlike [`QueryBuilder`], the description doesn't borrow the [`World`], so that scripting
+/// layers and editors can create it from run-time data, store it, and build it later into a
+/// [`QueryState`] returning [`FilteredEntityMut`] items, or [`FilteredEntityRef`](crate::world::FilteredEntityRef)
+/// items when iterated immutably.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::DynamicQueryBuilder;
+/// #
+/// # #[derive(Component)]
+/// # struct Position(f32);
+/// #
+/// # #[derive(Component)]
+/// # struct Velocity(f32);
+/// #
+/// let mut world = World::new();
+/// world.spawn((Position(0.0), Velocity(1.0)));
+/// let position = world.init_component::<Position>();
+/// let velocity = world.init_component::<Velocity>();
+///
+/// let mut query = DynamicQueryBuilder::new()
+///     .write(position)
+///     .read(velocity)
+///     .build(&mut world);
+///
+/// for mut entity in query.iter_mut(&mut world) {
+///     let velocity = entity.get_by_id(velocity).unwrap();
+///     // SAFETY: `velocity` is the id of `Velocity`.
+///     let velocity = unsafe { velocity.deref::<Velocity>().0 };
+///     let mut position = entity.get_mut_by_id(position).unwrap();
+///     // SAFETY: `position` is the id of `Position`.
+///     unsafe { position.as_mut().deref_mut::<Position>().0 += velocity };
+/// }
+/// ```
+///
+/// Used as a system parameter, the query is a [`DynamicQuery`](crate::system::DynamicQuery),
+/// whose description is given to the system with
+/// [`FunctionSystem::with_dynamic_query`](crate::system::FunctionSystem::with_dynamic_query).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DynamicQueryBuilder {
+    terms: Vec<DynamicQueryTerm>,
+}
+
+impl DynamicQueryBuilder {
+    /// Creates a builder matching every entity, without accessing any component.
+    pub const fn new() -> Self {
+        Self { terms: Vec::new() }
+    }
+
+    /// Adds a term to the query.
+    pub fn term(mut self, term: DynamicQueryTerm) -> Self {
+        self.terms.push(term);
+        self
+    }
+
+    /// Adds [`DynamicQueryTerm::Read`] to the query.
+    pub fn read(self, id: ComponentId) -> Self {
+        self.term(DynamicQueryTerm::Read(id))
+    }
+
+    /// Adds [`DynamicQueryTerm::Write`] to the query.
+    pub fn write(self, id: ComponentId) -> Self {
+        self.term(DynamicQueryTerm::Write(id))
+    }
+
+    /// Adds [`DynamicQueryTerm::OptionalRead`] to the query.
+    pub fn optional_read(self, id: ComponentId) -> Self {
+        self.term(DynamicQueryTerm::OptionalRead(id))
+    }
+
+    /// Adds [`DynamicQueryTerm::OptionalWrite`] to the query.
+    pub fn optional_write(self, id: ComponentId) -> Self {
+        self.term(DynamicQueryTerm::OptionalWrite(id))
+    }
+
+    /// Adds [`DynamicQueryTerm::With`] to the query.
+    pub fn with(self, id: ComponentId) -> Self {
+        self.term(DynamicQueryTerm::With(id))
+    }
+
+    /// Adds [`DynamicQueryTerm::Without`] to the query.
+    pub fn without(self, id: ComponentId) -> Self {
+        self.term(DynamicQueryTerm::Without(id))
+    }
+
+    /// Returns the terms of the query.
+    pub fn terms(&self) -> &[DynamicQueryTerm] {
+        &self.terms
+    }
+
+    /// Creates a [`QueryState`] with the terms of the builder.
+    ///
+    /// # Panics
+    ///
+    /// If a component of the terms isn't registered in `world`, or if the terms conflict, such as
+    /// reading and writing the same component.
+    pub fn build(&self, world: &mut World) -> QueryState<FilteredEntityMut<'static>> {
+        for (index, term) in self.terms.iter().enumerate() {
+            let id = term.id();
+            let Some(info) = world.components().get_info(id) else {
+                panic!("Component {id:?} of a dynamic query is not registered in the world.");
+            };
+            let conflicts = term.is_write()
+                && self.terms.iter().enumerate().any(|(other_index, other)| {
+                    other_index != index && other.id() == id && other.is_data()
+                });
+            assert!(
+                !conflicts,
+                "A dynamic query writes {} and accesses it through another term.",
+                info.name()
+            );
+        }
+
+        let mut builder = QueryBuilder::<FilteredEntityMut<'static>>::new(world);
+        for term in &self.terms {
+            match *term {
+                DynamicQueryTerm::Read(id) => builder.ref_id(id),
+                DynamicQueryTerm::Write(id) => builder.mut_id(id),
+                DynamicQueryTerm::OptionalRead(id) => builder.optional(|builder| {
+                    builder.ref_id(id);
+                }),
+                DynamicQueryTerm::OptionalWrite(id) => builder.optional(|builder| {
+                    builder.mut_id(id);
+                }),
+                DynamicQueryTerm::With(id) => builder.with_id(id),
+                DynamicQueryTerm::Without(id) => builder.without_id(id),
+            };
+        }
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_ecs, world::FilteredEntityRef};
+
+    #[derive(Component, PartialEq, Debug)]
+    struct A(usize);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct B(usize);
+
+    #[derive(Component, PartialEq, Debug)]
+    struct C(usize);
+
+    #[test]
+    fn dynamic_terms() {
+        let mut world = World::new();
+        let entity_ab = world.spawn((A(0), B(1))).id();
+        let entity_a = world.spawn(A(2)).id();
+        world.spawn((A(3), C(4)));
+        let [a, b, c] = [
+            world.init_component::<A>(),
+            world.init_component::<B>(),
+            world.init_component::<C>(),
+        ];
+
+        let mut query = DynamicQueryBuilder::new()
+            .read(a)
+            .optional_read(b)
+            .without(c)
+            .build(&mut world);
+        let mut entities = query
+            .iter(&world)
+            .map(|entity: FilteredEntityRef| (entity.id(), entity.get_by_id(b).is_some()))
+            .collect::<Vec<_>>();
+        entities.sort();
+        let mut expected = vec![(entity_ab, true), (entity_a, false)];
+        expected.sort();
+        assert_eq!(entities, expected);
+    }
+
+    #[test]
+    fn dynamic_write() {
+        let mut world = World::new();
+        let entity = world.spawn((A(0), B(1))).id();
+        let [a, b] = [world.init_component::<A>(), world.init_component::<B>()];
+
+        let mut query = DynamicQueryBuilder::new()
+            .write(a)
+            .with(b)
+            .build(&mut world);
+        for mut entity in query.iter_mut(&mut world) {
+            assert!(entity.get_by_id(b).is_none());
+            let mut a = entity.get_mut_by_id(a).unwrap();
+            // SAFETY: `a` is the id of `A`.
+            unsafe { a.as_mut().deref_mut::<A>().0 = 5 };
+        }
+        assert_eq!(world.get::<A>(entity), Some(&A(5)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn dynamic_conflicting_terms() {
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        DynamicQueryBuilder::new()
+            .read(a)
+            .write(a)
+            .build(&mut world);
+    }
+}
@@ -1,10 +1,10 @@
 use crate::{
     archetype::{Archetype, Archetypes},
-    change_detection::{Ticks, TicksMut},
+    change_detection::{FieldTicksMut, Ticks, TicksMut},
     component::{Component, ComponentId, Components, StorageType, Tick},
     entity::{Entities, Entity, EntityLocation},
     query::{Access, DebugCheckedUnwrap, FilteredAccess, WorldQuery},
-    storage::{ComponentSparseSet, Table, TableRow},
+    storage::{ComponentSparseSet, FieldTicks, Table, TableRow},
     world::{
         unsafe_world_cell::UnsafeWorldCell, EntityMut, EntityRef, FilteredEntityMut,
         FilteredEntityRef, Mut, Ref, World,
@@ -12,7 +12,7 @@ use crate::{
 };
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
 use bevy_utils::all_tuples;
-use std::{cell::UnsafeCell, marker::PhantomData};
+use std::{cell::UnsafeCell, marker::PhantomData, sync::atomic::AtomicU32};
 
 /// Types that can be fetched from a [`World`] using a [`Query`].
 ///
@@ -1208,6 +1208,10 @@ pub struct WriteFetch<'w, T> {
     )>,
     // T::STORAGE_TYPE = StorageType::SparseSet
     sparse_set: Option<&'w ComponentSparseSet>,
+    // The entity changed ticks of the table of the current archetype.
+    entity_changed: Option<ThinSlicePtr<'w, AtomicU32>>,
+    // The field ticks of the column of the current table, if `T` tracks the changes of its fields.
+    field_ticks: Option<&'w FieldTicks>,
 
     last_run: Tick,
     this_run: Tick,
@@ -1256,6 +1260,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                         .debug_checked_unwrap()
                 }
             }),
+            entity_changed: None,
+            field_ticks: None,
             last_run,
             this_run,
         }
@@ -1280,6 +1286,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
             unsafe {
                 Self::set_table(fetch, component_id, table);
             }
+        } else {
+            fetch.entity_changed = Some(table.get_entity_changed_ticks_slice().into());
         }
     }
 
@@ -1295,6 +1303,8 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
             column.get_added_ticks_slice().into(),
             column.get_changed_ticks_slice().into(),
         ));
+        fetch.entity_changed = Some(table.get_entity_changed_ticks_slice().into());
+        fetch.field_ticks = column.field_ticks();
     }
 
     #[inline(always)]
@@ -1315,12 +1325,27 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                 let added = unsafe { added_ticks.get(table_row.as_usize()) };
                 // SAFETY: The caller ensures `table_row` is in range.
                 let changed = unsafe { changed_ticks.get(table_row.as_usize()) };
+                // SAFETY: The caller ensures `table_row` is in range.
+                let entity_changed = unsafe {
+                    fetch
+                        .entity_changed
+                        .debug_checked_unwrap()
+                        .get(table_row.as_usize())
+                };
 
                 Mut {
                     value: component.deref_mut(),
                     ticks: TicksMut {
                         added: added.deref_mut(),
                         changed: changed.deref_mut(),
+                        entity_changed: Some(entity_changed),
+                        fields_changed: match fetch.field_ticks {
+                            // SAFETY: The caller ensures `table_row` is in range.
+                            Some(field_ticks) => {
+                                FieldTicksMut(unsafe { field_ticks.row_unchecked(table_row) })
+                            }
+                            None => FieldTicksMut::default(),
+                        },
                         this_run: fetch.this_run,
                         last_run: fetch.last_run,
                     },
@@ -1337,9 +1362,18 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
                         .debug_checked_unwrap()
                 };
 
+                // SAFETY: The caller ensures `table_row` is in range.
+                let entity_changed = unsafe {
+                    fetch
+                        .entity_changed
+                        .debug_checked_unwrap()
+                        .get(table_row.as_usize())
+                };
+
                 Mut {
                     value: component.assert_unique().deref_mut(),
-                    ticks: TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run),
+                    ticks: TicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run)
+                        .with_entity_changed(Some(entity_changed)),
                 }
             }
         }
@@ -1577,6 +1611,10 @@ unsafe impl<T: WorldQuery> WorldQuery for Option<T> {
         access.extend_access(&intermediate);
     }
 
+    fn update_traversal_access(state: &T::State, access: &mut Access<ComponentId>) {
+        T::update_traversal_access(state, access);
+    }
+
     fn init_state(world: &mut World) -> T::State {
         T::init_state(world)
     }
@@ -1877,6 +1915,10 @@ macro_rules! impl_anytuple_fetch {
 
                 *_access = _new_access;
             }
+            fn update_traversal_access(state: &Self::State, _access: &mut Access<ComponentId>) {
+                let ($($name,)*) = state;
+                $($name::update_traversal_access($name, _access);)*
+            }
             #[allow(unused_variables)]
             fn init_state(world: &mut World) -> Self::State {
                 ($($name::init_state(world),)*)
@@ -3,12 +3,16 @@ use crate::{
     component::{Component, ComponentId, Components, StorageType, Tick},
     entity::Entity,
     query::{DebugCheckedUnwrap, FilteredAccess, WorldQuery},
-    storage::{Column, ComponentSparseSet, Table, TableRow},
+    storage::{Column, ComponentSparseSet, FieldTicks, Table, TableRow},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
 use bevy_utils::all_tuples;
-use std::{cell::UnsafeCell, marker::PhantomData};
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 /// Types that filter the results of a [`Query`].
 ///
@@ -16,7 +20,9 @@ use std::{cell::UnsafeCell, marker::PhantomData};
 /// - **Component filters.**
 ///   [`With`] and [`Without`] filters can be applied to check if the queried entity does or does not contain a particular component.
 /// - **Change detection filters.**
-///   [`Added`] and [`Changed`] filters can be applied to detect component changes to an entity.
+///   [`Added`] and [`Changed`] filters can be applied to detect component changes to an entity,
+///   [`EntityChanged`] to detect changes to any of its components, and [`FieldChanged`] to detect
+///   changes to a single field of a component.
 /// - **`QueryFilter` tuples.**
 ///   If every element of a tuple implements `QueryFilter`, then the tuple itself also implements the same trait.
 ///   This enables a single `Query` to filter over multiple conditions.
@@ -926,6 +932,392 @@ impl<T: Component> QueryFilter for Changed<T> {
     }
 }
 
+/// Names a field of a component, for the [`FieldChanged`] filter.
+///
+/// Since string constants can't be used as generic parameters, the field is named by a type
+/// implementing this trait. The same type can name the fields of several components.
+///
+/// ```
+/// # use bevy_ecs::query::FieldName;
+/// struct Translation;
+///
+/// impl FieldName for Translation {
+///     const NAME: &'static str = "translation";
+/// }
+/// ```
+pub trait FieldName: 'static {
+    /// The name of the field, as listed in
+    /// [`ComponentInfo::tracked_fields`](crate::component::ComponentInfo::tracked_fields).
+    const NAME: &'static str;
+}
+
+/// A filter on a field of a component that has been added or changed since the last time the
+/// system ran, for components tracking the changes of their fields.
+///
+/// Unlike [`Changed<T>`], writes to the other fields of `T` are ignored, as long as they're made
+/// through `Mut::map_field_reflect`. Writes made by dereferencing a [`Mut<T>`](crate::change_detection::Mut)
+/// mutably change every field, since they aren't known to leave any field unchanged.
+///
+/// The component must track the changes of its fields, see
+/// [`World::track_field_changes_by_id`]. Initializing a query with this filter panics otherwise.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::{FieldChanged, FieldName};
+/// #[derive(Component)]
+/// struct Transform {
+///     translation: [f32; 3],
+///     scale: f32,
+/// }
+///
+/// struct Translation;
+///
+/// impl FieldName for Translation {
+///     const NAME: &'static str = "translation";
+/// }
+///
+/// fn send_moved_entities(query: Query<(Entity, &Transform), FieldChanged<Transform, Translation>>) {
+///     for (entity, transform) in &query {
+///         println!("{entity:?} moved to {:?}", transform.translation);
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// let id = world.init_component::<Transform>();
+/// world.track_field_changes_by_id(id, ["translation", "scale"]);
+/// # use bevy_ecs::system::RunSystemOnce;
+/// # world.run_system_once(send_moved_entities);
+/// ```
+pub struct FieldChanged<T, F>(PhantomData<(T, F)>);
+
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct FieldChangedState {
+    component_id: ComponentId,
+    field: usize,
+}
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct FieldChangedFetch<'w> {
+    table_ticks: Option<ThinSlicePtr<'w, UnsafeCell<Tick>>>,
+    field_ticks: Option<&'w FieldTicks>,
+    sparse_set: Option<&'w ComponentSparseSet>,
+    field: usize,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+/// SAFETY:
+/// `fetch` accesses a single component in a readonly way.
+/// This is sound because `update_component_access` add read access for that component and panics when appropriate.
+/// `update_component_access` adds a `With` filter for a component.
+/// This is sound because `matches_component_set` returns whether the set contains that component.
+unsafe impl<T: Component, F: FieldName> WorldQuery for FieldChanged<T, F> {
+    type Item<'w> = bool;
+    type Fetch<'w> = FieldChangedFetch<'w>;
+    type State = FieldChangedState;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        state: &FieldChangedState,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        Self::Fetch::<'w> {
+            table_ticks: None,
+            field_ticks: None,
+            sparse_set: (T::STORAGE_TYPE == StorageType::SparseSet).then(|| {
+                world
+                    .storages()
+                    .sparse_sets
+                    .get(state.component_id)
+                    .debug_checked_unwrap()
+            }),
+            field: state.field,
+            last_run,
+            this_run,
+        }
+    }
+
+    const IS_DENSE: bool = {
+        match T::STORAGE_TYPE {
+            StorageType::Table => true,
+            StorageType::SparseSet => false,
+        }
+    };
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &FieldChangedState,
+        _archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        if Self::IS_DENSE {
+            // SAFETY: `set_archetype`'s safety rules are a super set of the `set_table`'s ones.
+            unsafe {
+                Self::set_table(fetch, state, table);
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &FieldChangedState,
+        table: &'w Table,
+    ) {
+        let column = table.get_column(state.component_id).debug_checked_unwrap();
+        fetch.table_ticks = Some(column.get_changed_ticks_slice().into());
+        fetch.field_ticks = column.field_ticks();
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // The ticks of the component are checked too, since they may have been set back with
+        // `DetectChangesMut::set_last_changed` after the field changed.
+        let (changed, field_changed) = match T::STORAGE_TYPE {
+            StorageType::Table => {
+                // SAFETY: STORAGE_TYPE = Table
+                let table = unsafe { fetch.table_ticks.debug_checked_unwrap() };
+                // SAFETY: The caller ensures `table_row` is in range.
+                let changed = unsafe { table.get(table_row.as_usize()) };
+                // SAFETY: The caller ensures `table_row` is in range.
+                let fields = fetch.field_ticks.map_or(&[][..], |field_ticks| unsafe {
+                    field_ticks.row_unchecked(table_row)
+                });
+                (changed, fields.get(fetch.field))
+            }
+            StorageType::SparseSet => {
+                // SAFETY: STORAGE_TYPE = SparseSet
+                let sparse_set = unsafe { &fetch.sparse_set.debug_checked_unwrap() };
+                // SAFETY: The caller ensures `entity` is in range.
+                let changed = unsafe {
+                    ComponentSparseSet::get_changed_tick(sparse_set, entity).debug_checked_unwrap()
+                };
+                // SAFETY: The caller ensures `entity` is in range.
+                let fields = unsafe {
+                    ComponentSparseSet::get_field_ticks(sparse_set, entity).debug_checked_unwrap()
+                };
+                (changed, fields.get(fetch.field))
+            }
+        };
+        changed
+            .deref()
+            .is_newer_than(fetch.last_run, fetch.this_run)
+            && field_changed.map_or(true, |field_changed| {
+                field_changed
+                    .deref()
+                    .is_newer_than(fetch.last_run, fetch.this_run)
+            })
+    }
+
+    #[inline]
+    fn update_component_access(
+        state: &FieldChangedState,
+        access: &mut FilteredAccess<ComponentId>,
+    ) {
+        if access.access().has_write(state.component_id) {
+            panic!(
+                "FieldChanged<{}, {}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                std::any::type_name::<T>(),
+                std::any::type_name::<F>(),
+            );
+        }
+        access.add_read(state.component_id);
+    }
+
+    fn init_state(world: &mut World) -> FieldChangedState {
+        let component_id = world.init_component::<T>();
+        let field = world
+            .components()
+            .get_info(component_id)
+            .and_then(|info| info.tracked_field_index(F::NAME))
+            .unwrap_or_else(|| {
+                panic!(
+                    "FieldChanged<{}, {}> requires the component to track the changes of its field `{}`",
+                    std::any::type_name::<T>(),
+                    std::any::type_name::<F>(),
+                    F::NAME
+                )
+            });
+        FieldChangedState {
+            component_id,
+            field,
+        }
+    }
+
+    fn get_state(components: &Components) -> Option<FieldChangedState> {
+        let component_id = components.component_id::<T>()?;
+        let field = components
+            .get_info(component_id)?
+            .tracked_field_index(F::NAME)?;
+        Some(FieldChangedState {
+            component_id,
+            field,
+        })
+    }
+
+    fn matches_component_set(
+        state: &FieldChangedState,
+        set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        set_contains_id(state.component_id)
+    }
+}
+
+impl<T: Component, F: FieldName> QueryFilter for FieldChanged<T, F> {
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        // SAFETY: The invariants are uphold by the caller.
+        unsafe { Self::fetch(fetch, entity, table_row) }
+    }
+}
+
+/// A filter on entities with any component added or changed since the last time the system ran.
+///
+/// Unlike `Or<(Changed<A>, Changed<B>, ...)>`, this filter doesn't need to list the components,
+/// nor to check each of them: every component write also updates a tick stored per entity, in
+/// its table row, which this filter checks. Writes to sparse set components and component
+/// insertions are included, removals aren't.
+///
+/// This filter doesn't access any component, so it doesn't conflict with systems writing to
+/// components of the same entities. Such systems may run in parallel, in which case whether their
+/// writes are seen depends on which system runs first, like for systems ordered ambiguously.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::entity::Entity;
+/// # use bevy_ecs::query::EntityChanged;
+/// # use bevy_ecs::system::IntoSystem;
+/// # use bevy_ecs::system::Query;
+/// #
+/// fn print_changed_entities_system(query: Query<Entity, EntityChanged>) {
+///     for entity in &query {
+///         println!("Entity changed: {:?}", entity);
+///     }
+/// }
+///
+/// # bevy_ecs::system::assert_is_system(print_changed_entities_system);
+/// ```
+pub struct EntityChanged;
+
+#[doc(hidden)]
+#[derive(Clone)]
+pub struct EntityChangedFetch<'w> {
+    entity_changed_ticks: Option<ThinSlicePtr<'w, AtomicU32>>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+/// SAFETY:
+/// `fetch` only reads the entity changed ticks of the table, which are atomic.
+/// `update_component_access` and `update_archetype_component_access` do nothing.
+/// This is sound because `fetch` does not access any components.
+unsafe impl WorldQuery for EntityChanged {
+    type Item<'w> = bool;
+    type Fetch<'w> = EntityChangedFetch<'w>;
+    type State = ();
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        _world: UnsafeWorldCell<'w>,
+        _state: &(),
+        last_run: Tick,
+        this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        EntityChangedFetch {
+            entity_changed_ticks: None,
+            last_run,
+            this_run,
+        }
+    }
+
+    const IS_DENSE: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        state: &(),
+        _archetype: &'w Archetype,
+        table: &'w Table,
+    ) {
+        // SAFETY: `set_archetype`'s safety rules are a super set of the `set_table`'s ones.
+        unsafe {
+            Self::set_table(fetch, state, table);
+        }
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(fetch: &mut Self::Fetch<'w>, _state: &(), table: &'w Table) {
+        fetch.entity_changed_ticks = Some(table.get_entity_changed_ticks_slice().into());
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        _entity: Entity,
+        table_row: TableRow,
+    ) -> Self::Item<'w> {
+        // SAFETY: `set_table` or `set_archetype` was called before `fetch`.
+        let ticks = unsafe { fetch.entity_changed_ticks.debug_checked_unwrap() };
+        // SAFETY: The caller ensures `table_row` is in range.
+        let tick = unsafe { ticks.get(table_row.as_usize()) };
+
+        Tick::new(tick.load(Ordering::Relaxed)).is_newer_than(fetch.last_run, fetch.this_run)
+    }
+
+    fn update_component_access(_state: &(), _access: &mut FilteredAccess<ComponentId>) {}
+
+    fn init_state(_world: &mut World) {}
+
+    fn get_state(_components: &Components) -> Option<()> {
+        Some(())
+    }
+
+    fn matches_component_set(_state: &(), _set_contains_id: &impl Fn(ComponentId) -> bool) -> bool {
+        true
+    }
+}
+
+impl QueryFilter for EntityChanged {
+    const IS_ARCHETYPAL: bool = false;
+
+    #[inline(always)]
+    unsafe fn filter_fetch(
+        fetch: &mut Self::Fetch<'_>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> bool {
+        // SAFETY: The invariants are uphold by the caller.
+        unsafe { Self::fetch(fetch, entity, table_row) }
+    }
+}
+
 /// A marker trait to indicate that the filter works at an archetype level.
 ///
 /// This is needed to implement [`ExactSizeIterator`] for
@@ -6,7 +6,10 @@ use crate::{
     storage::{Table, TableRow, Tables},
     world::unsafe_world_cell::UnsafeWorldCell,
 };
-use std::{borrow::Borrow, iter::FusedIterator, mem::MaybeUninit, ops::Range};
+use std::{
+    any::Any, borrow::Borrow, cmp::Ordering, iter::FusedIterator, marker::PhantomData,
+    mem::MaybeUninit, ops::Range, sync::Mutex,
+};
 
 use super::{QueryData, QueryFilter, ReadOnlyQueryData};
 
@@ -15,6 +18,7 @@ use super::{QueryData, QueryFilter, ReadOnlyQueryData};
 /// This struct is created by the [`Query::iter`](crate::system::Query::iter) and
 /// [`Query::iter_mut`](crate::system::Query::iter_mut) methods.
 pub struct QueryIter<'w, 's, D: QueryData, F: QueryFilter> {
+    entities: &'w Entities,
     tables: &'w Tables,
     archetypes: &'w Archetypes,
     query_state: &'s QueryState<D, F>,
@@ -33,6 +37,7 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryIter<'w, 's, D, F> {
     ) -> Self {
         QueryIter {
             query_state,
+            entities: world.entities(),
             // SAFETY: We only access table data that has been registered in `query_state`.
             tables: unsafe { &world.storages().tables },
             archetypes: world.archetypes(),
@@ -41,6 +46,139 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryIter<'w, 's, D, F> {
         }
     }
 
+    /// Sorts the remaining items by the key returned by `f`, reusing a buffer stored in the
+    /// [`QueryState`] instead of allocating one on each call.
+    ///
+    /// The key of each item is computed once. The sort is unstable: items with equal keys can be
+    /// returned in any order. Sorting the same query again while the returned iterator is alive
+    /// allocates a new buffer.
+    ///
+    /// Unlike [`Query::sort_unstable_by_cached_key`](crate::system::Query::sort_unstable_by_cached_key),
+    /// the order of the items doesn't depend on [`Query::iter_stable`](crate::system::Query::iter_stable).
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct ZIndex(i32);
+    ///
+    /// fn draw_in_order(query: Query<(Entity, &ZIndex)>) {
+    ///     // no allocation after the first frame
+    ///     for (entity, _) in query.iter().sort_unstable_by_cached_key(|(_, z)| z.0) {
+    ///         // draw `entity`
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(draw_in_order);
+    /// ```
+    pub fn sort_unstable_by_cached_key<K, Func>(self, f: Func) -> QuerySortedIter<'w, 's, D, F, K>
+    where
+        K: Ord + Send + Sync + 'static,
+        Func: FnMut(&D::Item<'w>) -> K,
+    {
+        self.sort_unstable_by_cached(f, K::cmp)
+    }
+
+    /// Sorts the remaining items by the key returned by `f`, compared with `compare`, reusing a
+    /// buffer stored in the [`QueryState`] instead of allocating one on each call.
+    ///
+    /// This is useful for keys that aren't [`Ord`], like distances compared with
+    /// [`f32::total_cmp`]. See [`QueryIter::sort_unstable_by_cached_key`] for more details.
+    pub fn sort_unstable_by_cached<K, Func, Compare>(
+        self,
+        f: Func,
+        mut compare: Compare,
+    ) -> QuerySortedIter<'w, 's, D, F, K>
+    where
+        K: Send + Sync + 'static,
+        Func: FnMut(&D::Item<'w>) -> K,
+        Compare: FnMut(&K, &K) -> Ordering,
+    {
+        let mut sorted = self.collect_keys(f);
+        sorted
+            .buffer
+            .keys()
+            .sort_unstable_by(|(a, _), (b, _)| compare(a, b));
+        sorted
+    }
+
+    /// Returns the `n` remaining items with the smallest keys returned by `f`, from the smallest,
+    /// without sorting the other items.
+    ///
+    /// Like [`QueryIter::sort_unstable_by_cached_key`], this reuses a buffer stored in the
+    /// [`QueryState`]. Use [`Reverse`](std::cmp::Reverse) keys to select the largest ones.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use std::cmp::Reverse;
+    /// #[derive(Component)]
+    /// struct Threat(u32);
+    ///
+    /// fn pick_targets(query: Query<(Entity, &Threat)>) {
+    ///     for (entity, threat) in query.iter().top_k(3, |(_, threat)| Reverse(threat.0)) {
+    ///         // target the 3 most threatening entities
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(pick_targets);
+    /// ```
+    pub fn top_k<K, Func>(self, n: usize, f: Func) -> QuerySortedIter<'w, 's, D, F, K>
+    where
+        K: Ord + Send + Sync + 'static,
+        Func: FnMut(&D::Item<'w>) -> K,
+    {
+        self.top_k_by(n, f, K::cmp)
+    }
+
+    /// Returns the `n` remaining items with the smallest keys returned by `f`, compared with
+    /// `compare`, from the smallest, without sorting the other items.
+    ///
+    /// See [`QueryIter::top_k`] for more details.
+    pub fn top_k_by<K, Func, Compare>(
+        self,
+        n: usize,
+        f: Func,
+        mut compare: Compare,
+    ) -> QuerySortedIter<'w, 's, D, F, K>
+    where
+        K: Send + Sync + 'static,
+        Func: FnMut(&D::Item<'w>) -> K,
+        Compare: FnMut(&K, &K) -> Ordering,
+    {
+        let mut sorted = self.collect_keys(f);
+        let keys = sorted.buffer.keys();
+        if n < keys.len() {
+            keys.select_nth_unstable_by(n, |(a, _), (b, _)| compare(a, b));
+            keys.truncate(n);
+        }
+        keys.sort_unstable_by(|(a, _), (b, _)| compare(a, b));
+        sorted
+    }
+
+    /// Computes the key of each remaining item, in a buffer borrowed from the [`QueryState`].
+    fn collect_keys<K, Func>(mut self, mut f: Func) -> QuerySortedIter<'w, 's, D, F, K>
+    where
+        K: Send + Sync + 'static,
+        Func: FnMut(&D::Item<'w>) -> K,
+    {
+        let mut buffer = SortBuffer::take(&self.query_state.sort_scratch);
+        let keys = buffer.keys();
+        // SAFETY:
+        // `tables` and `archetypes` belong to the same world that the cursor was initialized for.
+        // `query_state` is the state that was passed to `QueryIterationCursor::init`.
+        while let Some(item) = unsafe {
+            self.cursor
+                .next(self.tables, self.archetypes, self.query_state)
+        } {
+            let key = f(&item);
+            // SAFETY: `next` just returned an item.
+            let entity = unsafe { self.cursor.last_entity() };
+            keys.push((key, entity));
+        }
+        QuerySortedIter {
+            iter: self,
+            buffer,
+            index: 0,
+        }
+    }
+
     /// Executes the equivalent of [`Iterator::fold`] over a contiguous segment
     /// from an table.
     ///
@@ -220,6 +358,130 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Iterator for QueryIter<'w, 's, D, F>
 // This is correct as [`QueryIter`] always returns `None` once exhausted.
 impl<'w, 's, D: QueryData, F: QueryFilter> FusedIterator for QueryIter<'w, 's, D, F> {}
 
+/// The buffer reused by the sorted iterators of a [`QueryState`].
+///
+/// It holds the `Vec<(K, Entity)>` of the last key type `K` used to sort the query.
+pub(crate) type SortScratch = Mutex<Option<Box<dyn Any + Send + Sync>>>;
+
+/// The keys of a [`QuerySortedIter`], borrowed from the [`SortScratch`] of its [`QueryState`]
+/// and given back when dropped.
+struct SortBuffer<'s, K: Send + Sync + 'static> {
+    keys: Option<Box<dyn Any + Send + Sync>>,
+    scratch: &'s SortScratch,
+    marker: PhantomData<fn() -> K>,
+}
+
+impl<'s, K: Send + Sync + 'static> SortBuffer<'s, K> {
+    fn take(scratch: &'s SortScratch) -> Self {
+        let keys = scratch
+            .lock()
+            .ok()
+            .and_then(|mut scratch| scratch.take())
+            .filter(|keys| keys.is::<Vec<(K, Entity)>>())
+            .unwrap_or_else(|| Box::new(Vec::<(K, Entity)>::new()));
+        Self {
+            keys: Some(keys),
+            scratch,
+            marker: PhantomData,
+        }
+    }
+
+    fn keys(&mut self) -> &mut Vec<(K, Entity)> {
+        // SAFETY: `keys` is only taken when dropped, and always holds a `Vec<(K, Entity)>`.
+        unsafe {
+            self.keys
+                .as_mut()
+                .and_then(|keys| keys.downcast_mut())
+                .debug_checked_unwrap()
+        }
+    }
+}
+
+impl<K: Send + Sync + 'static> Drop for SortBuffer<'_, K> {
+    fn drop(&mut self) {
+        let Some(mut keys) = self.keys.take() else {
+            return;
+        };
+        if let Some(keys) = keys.downcast_mut::<Vec<(K, Entity)>>() {
+            keys.clear();
+        }
+        if let Ok(mut scratch) = self.scratch.lock() {
+            scratch.get_or_insert(keys);
+        }
+    }
+}
+
+/// An [`Iterator`] over sorted query items, in the order of their keys.
+///
+/// This struct is created by the [`QueryIter::sort_unstable_by_cached_key`],
+/// [`QueryIter::sort_unstable_by_cached`], [`QueryIter::top_k`] and [`QueryIter::top_k_by`]
+/// methods.
+pub struct QuerySortedIter<'w, 's, D: QueryData, F: QueryFilter, K: Send + Sync + 'static> {
+    iter: QueryIter<'w, 's, D, F>,
+    buffer: SortBuffer<'s, K>,
+    index: usize,
+}
+
+impl<'w, 's, D: QueryData, F: QueryFilter, K: Send + Sync + 'static> Iterator
+    for QuerySortedIter<'w, 's, D, F, K>
+{
+    type Item = D::Item<'w>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, entity) = self.buffer.keys().get(self.index)?;
+        let entity = *entity;
+        self.index += 1;
+
+        let iter = &mut self.iter;
+        // SAFETY: `entity` was returned by the query, and the world can't change while it is borrowed.
+        let (archetype, table, row) = unsafe {
+            let location = iter.entities.get(entity).debug_checked_unwrap();
+            (
+                iter.archetypes
+                    .get(location.archetype_id)
+                    .debug_checked_unwrap(),
+                iter.tables.get(location.table_id).debug_checked_unwrap(),
+                location.table_row,
+            )
+        };
+        // SAFETY:
+        // - `archetype` and `table` are from the world that the fetch was created for, and match
+        //   the query since `entity` was returned by it.
+        // - each entity is in the buffer once, so its item is only fetched once.
+        unsafe {
+            D::set_archetype(
+                &mut iter.cursor.fetch,
+                &iter.query_state.fetch_state,
+                archetype,
+                table,
+            );
+            Some(D::fetch(&mut iter.cursor.fetch, entity, row))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self
+            .buffer
+            .keys
+            .as_ref()
+            .and_then(|keys| keys.downcast_ref::<Vec<(K, Entity)>>())
+            .map_or(0, Vec::len)
+            - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<D: QueryData, F: QueryFilter, K: Send + Sync + 'static> ExactSizeIterator
+    for QuerySortedIter<'_, '_, D, F, K>
+{
+}
+
+impl<D: QueryData, F: QueryFilter, K: Send + Sync + 'static> FusedIterator
+    for QuerySortedIter<'_, '_, D, F, K>
+{
+}
+
 /// An [`Iterator`] over the query items generated from an iterator of [`Entity`]s.
 ///
 /// Items are returned in the order of the provided iterator.
@@ -687,6 +949,20 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryIterationCursor<'w, 's, D, F> {
         }
     }
 
+    /// Returns the entity of the item returned by the most recent `next` call.
+    ///
+    /// # Safety
+    /// The most recent `next` call must have returned an item.
+    #[inline]
+    unsafe fn last_entity(&self) -> Entity {
+        let index = self.current_row - 1;
+        if Self::IS_DENSE {
+            *self.table_entities.get_unchecked(index)
+        } else {
+            self.archetype_entities.get_unchecked(index).id()
+        }
+    }
+
     /// How many values will this cursor return at most?
     ///
     /// Note that if `F::IS_ARCHETYPAL`, the return value
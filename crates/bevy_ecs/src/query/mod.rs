@@ -2,6 +2,7 @@
 
 mod access;
 mod builder;
+mod dynamic;
 mod error;
 mod fetch;
 mod filter;
@@ -13,6 +14,7 @@ mod world_query;
 pub use access::*;
 pub use bevy_ecs_macros::{QueryData, QueryFilter};
 pub use builder::*;
+pub use dynamic::*;
 pub use error::*;
 pub use fetch::*;
 pub use filter::*;
@@ -739,6 +741,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stable_and_sorted_iteration() {
+        let mut world = World::new();
+        world.spawn(A(0));
+        world.spawn((A(1), Sparse(0)));
+        world.spawn(A(2));
+
+        fn system(mut query: Query<&mut A>) {
+            let values = |iter: &mut dyn Iterator<Item = &A>| iter.map(|a| a.0).collect::<Vec<_>>();
+            // Both archetypes share a table, which `iter` walks row by row.
+            assert_eq!(values(&mut query.iter()), vec![0, 1, 2]);
+            assert_eq!(values(&mut query.iter_stable()), vec![0, 2, 1]);
+            assert_eq!(
+                values(&mut query.sort_by_cached_key(|a| std::cmp::Reverse(a.0))),
+                vec![2, 1, 0]
+            );
+            assert_eq!(
+                values(&mut query.sort_unstable_by_cached_key(|a| a.0 % 2)),
+                vec![0, 2, 1]
+            );
+
+            let mut iter = query.sort_by_cached_key_mut(|a| std::cmp::Reverse(a.0));
+            let mut index = 0;
+            while let Some(mut a) = iter.fetch_next() {
+                a.0 = index;
+                index += 1;
+            }
+            assert_eq!(values(&mut query.iter_stable()), vec![2, 0, 1]);
+        }
+        let mut system = IntoSystem::into_system(system);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+    }
+
+    #[test]
+    fn sort_with_cached_buffer() {
+        let mut world = World::new();
+        for value in [3, 1, 4, 1, 5, 9, 2, 6] {
+            world.spawn(A(value));
+        }
+        world.spawn((A(7), Sparse(0)));
+
+        let mut query = world.query::<&mut A>();
+        let values = query
+            .iter(&world)
+            .sort_unstable_by_cached_key(|a| a.0)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 1, 2, 3, 4, 5, 6, 7, 9]);
+
+        let iter = query.iter(&world).top_k(3, |a| std::cmp::Reverse(a.0));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.map(|a| a.0).collect::<Vec<_>>(), vec![9, 7, 6]);
+        assert_eq!(query.iter(&world).top_k(20, |a| a.0).count(), 9);
+
+        // sorting again while the buffer is borrowed allocates a new one
+        {
+            let mut outer = query
+                .iter_manual(&world)
+                .sort_unstable_by_cached_key(|a| a.0);
+            let inner = query
+                .iter_manual(&world)
+                .top_k_by(2, |a| a.0 as f32, f32::total_cmp);
+            assert_eq!(inner.map(|a| a.0).collect::<Vec<_>>(), vec![1, 1]);
+            assert_eq!(outer.next(), Some(&A(1)));
+        }
+
+        for (index, mut a) in query
+            .iter_mut(&mut world)
+            .sort_unstable_by_cached_key(|a| std::cmp::Reverse(a.0))
+            .enumerate()
+        {
+            a.0 = index;
+        }
+        let values = query
+            .iter(&world)
+            .sort_unstable_by_cached_key(|a| a.0)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        assert_eq!(values, (0..9).collect::<Vec<_>>());
+    }
+
     #[test]
     fn mut_to_immut_query_methods_have_immut_item() {
         #[derive(Component)]
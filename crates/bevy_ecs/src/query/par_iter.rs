@@ -112,6 +112,7 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryParIter<'w, 's, D, F> {
                     self.state.par_fold_init_unchecked_manual(
                         init,
                         self.world,
+                        &self.batching_strategy,
                         batch_size,
                         func,
                         self.last_run,
@@ -124,23 +125,31 @@ impl<'w, 's, D: QueryData, F: QueryFilter> QueryParIter<'w, 's, D, F> {
 
     #[cfg(all(not(target_arch = "wasm32"), feature = "multi_threaded"))]
     fn get_batch_size(&self, thread_count: usize) -> usize {
+        use crate::{batching::BatchMode, query::state::StorageId};
+
+        let mode = self.batching_strategy.mode;
+        if mode == BatchMode::PerStorage {
+            return usize::MAX;
+        }
         let max_items = || {
-            let id_iter = self.state.matched_storage_ids.iter();
-            if D::IS_DENSE && F::IS_DENSE {
-                // SAFETY: We only access table metadata.
-                let tables = unsafe { &self.world.world_metadata().storages().tables };
-                id_iter
+            let count = |id: &StorageId| {
+                if D::IS_DENSE && F::IS_DENSE {
+                    // SAFETY: We only access table metadata.
+                    let tables = unsafe { &self.world.world_metadata().storages().tables };
                     // SAFETY: The if check ensures that matched_storage_ids stores TableIds
-                    .map(|id| unsafe { tables[id.table_id].entity_count() })
-                    .max()
-            } else {
-                let archetypes = &self.world.archetypes();
-                id_iter
+                    unsafe { tables[id.table_id].entity_count() }
+                } else {
                     // SAFETY: The if check ensures that matched_storage_ids stores ArchetypeIds
-                    .map(|id| unsafe { archetypes[id.archetype_id].len() })
-                    .max()
+                    unsafe { self.world.archetypes()[id.archetype_id].len() }
+                }
+            };
+            let counts = self.state.matched_storage_ids.iter().map(count);
+            // Adaptive batches are claimed across storages.
+            if mode == BatchMode::Adaptive {
+                counts.sum()
+            } else {
+                counts.max().unwrap_or(0)
             }
-            .unwrap_or(0)
         };
         self.batching_strategy
             .calc_batch_size(max_items, thread_count)
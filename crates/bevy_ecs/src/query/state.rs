@@ -18,7 +18,7 @@ use std::{borrow::Borrow, fmt, mem::MaybeUninit, ptr};
 
 use super::{
     NopWorldQuery, QueryBuilder, QueryData, QueryEntityError, QueryFilter, QueryManyIter,
-    QuerySingleError, ROQueryItem,
+    QuerySingleError, ROQueryItem, SortScratch,
 };
 
 /// An ID for either a table or an archetype. Used for Query iteration.
@@ -66,8 +66,21 @@ pub struct QueryState<D: QueryData, F: QueryFilter = ()> {
     /// [`FilteredAccess`] computed by combining the `D` and `F` access. Used to check which other queries
     /// this query can run in parallel with.
     pub(crate) component_access: FilteredAccess<ComponentId>,
+    /// Components read by `D` and `F` on entities other than the fetched ones, such as the
+    /// relationship walked by [`Related`](crate::relationship::Related). These reads apply to
+    /// every archetype of the world, matched by this query or not.
+    pub(crate) traversal_access: Access<ComponentId>,
     // NOTE: we maintain both a bitset and a vec because iterating the vec is faster
     pub(super) matched_storage_ids: Vec<StorageId>,
+    /// The disabling components of the world that aren't mentioned by this query, see
+    /// [`DefaultQueryFilters`](crate::entity_disabling::DefaultQueryFilters).
+    pub(crate) default_filters: Vec<ComponentId>,
+    /// The tables that would be matched by this query if not for its default filters.
+    pub(crate) disabled_tables: FixedBitSet,
+    /// The archetypes that would be matched by this query if not for its default filters.
+    pub(crate) disabled_archetypes: FixedBitSet,
+    /// The buffer reused by the sorted iterators of this query, see [`QueryIter::sort_unstable_by_cached_key`].
+    pub(crate) sort_scratch: SortScratch,
     pub(crate) fetch_state: D::State,
     pub(crate) filter_state: F::State,
     #[cfg(feature = "trace")]
@@ -135,6 +148,11 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         &self.component_access
     }
 
+    /// Returns the components read by this query on entities other than the fetched ones.
+    pub fn traversal_access(&self) -> &Access<ComponentId> {
+        &self.traversal_access
+    }
+
     /// Returns the tables matched by this query.
     pub fn matched_tables(&self) -> impl Iterator<Item = TableId> + '_ {
         self.matched_tables.ones().map(TableId::from_usize)
@@ -168,6 +186,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                     state.update_archetype_component_access(archetype, access);
                 }
             }
+            state.update_archetype_traversal_access(archetype, access);
         }
         state.archetype_generation = world.archetypes.generation();
         state
@@ -194,6 +213,12 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         // properly considered in a global "cross-query" context (both within systems and across systems).
         component_access.extend(&filter_component_access);
 
+        let mut traversal_access = Access::default();
+        D::update_traversal_access(&fetch_state, &mut traversal_access);
+        F::update_traversal_access(&filter_state, &mut traversal_access);
+
+        let default_filters = world.default_query_filters().filtered_by(&component_access);
+
         Self {
             world_id: world.id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -201,6 +226,11 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             fetch_state,
             filter_state,
             component_access,
+            traversal_access,
+            default_filters,
+            disabled_tables: Default::default(),
+            disabled_archetypes: Default::default(),
+            sort_scratch: Default::default(),
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             #[cfg(feature = "trace")]
@@ -218,6 +248,15 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         let filter_state = F::init_state(builder.world_mut());
         D::set_access(&mut fetch_state, builder.access());
 
+        let mut traversal_access = Access::default();
+        D::update_traversal_access(&fetch_state, &mut traversal_access);
+        F::update_traversal_access(&filter_state, &mut traversal_access);
+
+        let default_filters = builder
+            .world()
+            .default_query_filters()
+            .filtered_by(builder.access());
+
         let mut state = Self {
             world_id: builder.world().id(),
             archetype_generation: ArchetypeGeneration::initial(),
@@ -225,6 +264,11 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             fetch_state,
             filter_state,
             component_access: builder.access().clone(),
+            traversal_access,
+            default_filters,
+            disabled_tables: Default::default(),
+            disabled_archetypes: Default::default(),
+            sort_scratch: Default::default(),
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             #[cfg(feature = "trace")]
@@ -393,6 +437,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             // SAFETY: The caller ensures that `archetype` is from the World the state was initialized from.
             unsafe { self.update_archetype_component_access(archetype, access) };
         }
+        self.update_archetype_traversal_access(archetype, access);
     }
 
     /// Process the given [`Archetype`] to update internal metadata about the [`Table`](crate::storage::Table)s
@@ -401,13 +446,29 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     /// Returns `true` if the given `archetype` matches the query. Otherwise, returns `false`.
     /// If there is no match, then there is no need to update the query's [`FilteredAccess`].
     ///
+    /// Archetypes only excluded by the default filters of the query are recorded as disabled,
+    /// and still count as matches, so that [`Query::including_disabled`] has access to them.
+    ///
     /// # Safety
     /// `archetype` must be from the `World` this state was initialized from.
+    ///
+    /// [`Query::including_disabled`]: crate::system::Query::including_disabled
     unsafe fn new_archetype_internal(&mut self, archetype: &Archetype) -> bool {
         if D::matches_component_set(&self.fetch_state, &|id| archetype.contains(id))
             && F::matches_component_set(&self.filter_state, &|id| archetype.contains(id))
             && self.matches_component_set(&|id| archetype.contains(id))
         {
+            if self
+                .default_filters
+                .iter()
+                .any(|&id| archetype.contains(id))
+            {
+                self.disabled_archetypes
+                    .grow_and_insert(archetype.id().index());
+                self.disabled_tables
+                    .grow_and_insert(archetype.table_id().as_usize());
+                return true;
+            }
             let archetype_index = archetype.id().index();
             if !self.matched_archetypes.contains(archetype_index) {
                 self.matched_archetypes.grow_and_insert(archetype_index);
@@ -468,6 +529,20 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         });
     }
 
+    /// For the given `archetype`, adds the components this query reads on entities other than the
+    /// fetched ones to `access`, whether or not the query matches the archetype.
+    fn update_archetype_traversal_access(
+        &self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        self.traversal_access.reads().for_each(|id| {
+            if let Some(id) = archetype.get_archetype_component_id(id) {
+                access.add_read(id);
+            }
+        });
+    }
+
     /// Use this to transform a [`QueryState`] into a more generic [`QueryState`].
     /// This can be useful for passing to another function that might take the more general form.
     /// See [`Query::transmute_lens`](crate::system::Query::transmute_lens) for more details.
@@ -505,6 +580,15 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             std::any::type_name::<(NewD, NewF)>(), std::any::type_name::<(D, F)>()
         );
 
+        let mut traversal_access = Access::default();
+        NewD::update_traversal_access(&fetch_state, &mut traversal_access);
+        NewF::update_traversal_access(&filter_state, &mut traversal_access);
+        assert!(
+            traversal_access.is_subset(&self.traversal_access),
+            "Transmuted state for {} attempts to traverse relationships that are not traversed by original state {}.",
+            std::any::type_name::<(NewD, NewF)>(), std::any::type_name::<(D, F)>()
+        );
+
         QueryState {
             world_id: self.world_id,
             archetype_generation: self.archetype_generation,
@@ -512,6 +596,11 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             fetch_state,
             filter_state,
             component_access: self.component_access.clone(),
+            traversal_access: self.traversal_access.clone(),
+            default_filters: self.default_filters.clone(),
+            disabled_tables: self.disabled_tables.clone(),
+            disabled_archetypes: self.disabled_archetypes.clone(),
+            sort_scratch: Default::default(),
             matched_tables: self.matched_tables.clone(),
             matched_archetypes: self.matched_archetypes.clone(),
             #[cfg(feature = "trace")]
@@ -593,6 +682,17 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             std::any::type_name::<(NewD, NewF)>(), std::any::type_name::<(D, F)>(), std::any::type_name::<(OtherD, OtherF)>()
         );
 
+        let mut traversal_access = Access::default();
+        NewD::update_traversal_access(&new_fetch_state, &mut traversal_access);
+        NewF::update_traversal_access(&new_filter_state, &mut traversal_access);
+        let mut joined_traversal_access = self.traversal_access.clone();
+        joined_traversal_access.extend(&other.traversal_access);
+        assert!(
+            traversal_access.is_subset(&joined_traversal_access),
+            "Joined state for {} attempts to traverse relationships that are not traversed by state {} joined with {}.",
+            std::any::type_name::<(NewD, NewF)>(), std::any::type_name::<(D, F)>(), std::any::type_name::<(OtherD, OtherF)>()
+        );
+
         if self.archetype_generation != other.archetype_generation {
             warn!("You have tried to join queries with different archetype_generations. This could lead to unpredictable results.");
         }
@@ -625,6 +725,11 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             fetch_state: new_fetch_state,
             filter_state: new_filter_state,
             component_access: joined_component_access,
+            traversal_access: joined_traversal_access,
+            default_filters: Vec::new(),
+            disabled_tables: Default::default(),
+            disabled_archetypes: Default::default(),
+            sort_scratch: Default::default(),
             matched_tables,
             matched_archetypes,
             #[cfg(feature = "trace")]
@@ -636,6 +741,57 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// Creates a new [`QueryState`] with the same type signature and [`FilteredAccess`] as self,
+    /// but without default filters, matching the entities disabled by the
+    /// [`DefaultQueryFilters`](crate::entity_disabling::DefaultQueryFilters) of the world too.
+    ///
+    /// Like [`transmute`](Self::transmute), this is best used through a
+    /// [`Query`](crate::system::Query), see
+    /// [`Query::including_disabled`](crate::system::Query::including_disabled).
+    pub fn including_disabled(&self, components: &Components) -> QueryState<D, F> {
+        let mut fetch_state = D::get_state(components).expect("Could not create fetch_state, Please initialize all referenced components before transmuting.");
+        let filter_state = F::get_state(components).expect("Could not create filter_state, Please initialize all referenced components before transmuting.");
+        D::set_access(&mut fetch_state, &self.component_access);
+
+        let mut matched_tables = self.matched_tables.clone();
+        let mut matched_archetypes = self.matched_archetypes.clone();
+        matched_tables.union_with(&self.disabled_tables);
+        matched_archetypes.union_with(&self.disabled_archetypes);
+        let matched_storage_ids = if D::IS_DENSE && F::IS_DENSE {
+            matched_tables
+                .ones()
+                .map(|id| StorageId {
+                    table_id: TableId::from_usize(id),
+                })
+                .collect()
+        } else {
+            matched_archetypes
+                .ones()
+                .map(|id| StorageId {
+                    archetype_id: ArchetypeId::new(id),
+                })
+                .collect()
+        };
+
+        QueryState {
+            world_id: self.world_id,
+            archetype_generation: self.archetype_generation,
+            matched_storage_ids,
+            fetch_state,
+            filter_state,
+            component_access: self.component_access.clone(),
+            traversal_access: self.traversal_access.clone(),
+            default_filters: Vec::new(),
+            disabled_tables: Default::default(),
+            disabled_archetypes: Default::default(),
+            sort_scratch: Default::default(),
+            matched_tables,
+            matched_archetypes,
+            #[cfg(feature = "trace")]
+            par_iter_span: self.par_iter_span.clone(),
+        }
+    }
+
     /// Gets the query result for the given [`World`] and [`Entity`].
     ///
     /// This can only be called for read-only queries, see [`Self::get_mut`] for write-queries.
@@ -1398,6 +1554,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         &self,
         init_accum: INIT,
         world: UnsafeWorldCell<'w>,
+        batching_strategy: &BatchingStrategy,
         batch_size: usize,
         func: FN,
         last_run: Tick,
@@ -1408,17 +1565,109 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
     {
         // NOTE: If you are changing query iteration code, remember to update the following places, where relevant:
         // QueryIter, QueryIterationCursor, QueryManyIter, QueryCombinationIter,QueryState::par_fold_init_unchecked_manual
+        use crate::batching::{BatchMode, BatchTimings};
         use arrayvec::ArrayVec;
+        use std::{
+            ops::Range,
+            sync::atomic::{AtomicUsize, Ordering},
+        };
+
+        let timings = batching_strategy.timings.as_ref();
+
+        let storage_entity_count = |storage_id: StorageId| -> usize {
+            if D::IS_DENSE && F::IS_DENSE {
+                // SAFETY: We only access table data that has been registered in `self.archetype_component_access`.
+                unsafe { world.storages().tables[storage_id.table_id].entity_count() }
+            } else {
+                world.archetypes()[storage_id.archetype_id].len()
+            }
+        };
+
+        // fold over a range of rows of a single storage
+        let fold_over_range = |iter: &mut QueryIter<'w, '_, D, F>,
+                               accum: T,
+                               func: &mut FN,
+                               storage_id: StorageId,
+                               range: Range<usize>| {
+            if D::IS_DENSE && F::IS_DENSE {
+                let id = storage_id.table_id;
+                let table = world.storages().tables.get(id).debug_checked_unwrap();
+                iter.fold_over_table_range(accum, func, table, range)
+            } else {
+                let id = storage_id.archetype_id;
+                let archetype = world.archetypes().get(id).debug_checked_unwrap();
+                iter.fold_over_archetype_range(accum, func, archetype, range)
+            }
+        };
+
+        if batching_strategy.mode == BatchMode::Adaptive {
+            // The end of each non-empty storage, as if all of them were laid out contiguously.
+            let mut storages = Vec::new();
+            let mut ends = Vec::new();
+            let mut total = 0;
+            for storage_id in &self.matched_storage_ids {
+                let count = storage_entity_count(*storage_id);
+                if count > 0 {
+                    total += count;
+                    storages.push(*storage_id);
+                    ends.push(total);
+                }
+            }
+            let task_count = bevy_tasks::ComputeTaskPool::get()
+                .thread_num()
+                .min(total.div_ceil(batch_size));
+            let cursor = AtomicUsize::new(0);
+            let (storages, ends, cursor) = (&storages, &ends, &cursor);
+
+            bevy_tasks::ComputeTaskPool::get().scope(|scope| {
+                for _ in 0..task_count {
+                    let mut func = func.clone();
+                    let init_accum = init_accum.clone();
+                    scope.spawn(async move {
+                        #[cfg(feature = "trace")]
+                        let _span = self.par_iter_span.enter();
+                        let mut iter = self.iter_unchecked_manual(world, last_run, this_run);
+                        let mut accum = init_accum();
+                        loop {
+                            let start = cursor.fetch_add(batch_size, Ordering::Relaxed);
+                            if start >= total {
+                                break;
+                            }
+                            let end = (start + batch_size).min(total);
+                            accum = BatchTimings::time(timings, end - start, || {
+                                // A chunk may span several storages.
+                                let mut index =
+                                    ends.partition_point(|&storage_end| storage_end <= start);
+                                let mut position = start;
+                                while position < end {
+                                    let storage_start =
+                                        ends[index] - storage_entity_count(storages[index]);
+                                    let chunk_end = ends[index].min(end);
+                                    accum = fold_over_range(
+                                        &mut iter,
+                                        accum,
+                                        &mut func,
+                                        storages[index],
+                                        position - storage_start..chunk_end - storage_start,
+                                    );
+                                    position = chunk_end;
+                                    index += 1;
+                                }
+                                accum
+                            });
+                        }
+                    });
+                }
+            });
+            return;
+        }
 
         bevy_tasks::ComputeTaskPool::get().scope(|scope| {
-            // SAFETY: We only access table data that has been registered in `self.archetype_component_access`.
-            let tables = unsafe { &world.storages().tables };
-            let archetypes = world.archetypes();
             let mut batch_queue = ArrayVec::new();
             let mut queue_entity_count = 0;
 
             // submit a list of storages which smaller than batch_size as single task
-            let submit_batch_queue = |queue: &mut ArrayVec<StorageId, 128>| {
+            let submit_batch_queue = |queue: &mut ArrayVec<StorageId, 128>, count: usize| {
                 if queue.is_empty() {
                     return;
                 }
@@ -1428,29 +1677,14 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                 scope.spawn(async move {
                     #[cfg(feature = "trace")]
                     let _span = self.par_iter_span.enter();
-                    let mut iter = self.iter_unchecked_manual(world, last_run, this_run);
-                    let mut accum = init_accum();
-                    for storage_id in queue {
-                        if D::IS_DENSE && F::IS_DENSE {
-                            let id = storage_id.table_id;
-                            let table = &world.storages().tables.get(id).debug_checked_unwrap();
-                            accum = iter.fold_over_table_range(
-                                accum,
-                                &mut func,
-                                table,
-                                0..table.entity_count(),
-                            );
-                        } else {
-                            let id = storage_id.archetype_id;
-                            let archetype = world.archetypes().get(id).debug_checked_unwrap();
-                            accum = iter.fold_over_archetype_range(
-                                accum,
-                                &mut func,
-                                archetype,
-                                0..archetype.len(),
-                            );
+                    BatchTimings::time(timings, count, || {
+                        let mut iter = self.iter_unchecked_manual(world, last_run, this_run);
+                        let mut accum = init_accum();
+                        for storage_id in queue {
+                            let range = 0..storage_entity_count(storage_id);
+                            accum = fold_over_range(&mut iter, accum, &mut func, storage_id, range);
                         }
-                    }
+                    });
                 });
             };
 
@@ -1464,30 +1698,15 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                     scope.spawn(async move {
                         #[cfg(feature = "trace")]
                         let _span = self.par_iter_span.enter();
-                        let accum = init_accum();
-                        if D::IS_DENSE && F::IS_DENSE {
-                            let id = storage_id.table_id;
-                            let table = world.storages().tables.get(id).debug_checked_unwrap();
-                            self.iter_unchecked_manual(world, last_run, this_run)
-                                .fold_over_table_range(accum, &mut func, table, batch);
-                        } else {
-                            let id = storage_id.archetype_id;
-                            let archetype = world.archetypes().get(id).debug_checked_unwrap();
-                            self.iter_unchecked_manual(world, last_run, this_run)
-                                .fold_over_archetype_range(accum, &mut func, archetype, batch);
-                        }
+                        BatchTimings::time(timings, len, || {
+                            let accum = init_accum();
+                            let mut iter = self.iter_unchecked_manual(world, last_run, this_run);
+                            fold_over_range(&mut iter, accum, &mut func, storage_id, batch);
+                        });
                     });
                 }
             };
 
-            let storage_entity_count = |storage_id: StorageId| -> usize {
-                if D::IS_DENSE && F::IS_DENSE {
-                    tables[storage_id.table_id].entity_count()
-                } else {
-                    archetypes[storage_id.archetype_id].len()
-                }
-            };
-
             for storage_id in &self.matched_storage_ids {
                 let count = storage_entity_count(*storage_id);
 
@@ -1495,8 +1714,8 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                 if count == 0 {
                     continue;
                 }
-                // immediately submit large storage
-                if count >= batch_size {
+                // immediately submit large storage, or every storage when running one task per storage
+                if count >= batch_size || batching_strategy.mode == BatchMode::PerStorage {
                     submit_single(count, *storage_id);
                     continue;
                 }
@@ -1506,11 +1725,11 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
 
                 // submit batch_queue
                 if queue_entity_count >= batch_size || batch_queue.is_full() {
-                    submit_batch_queue(&mut batch_queue);
+                    submit_batch_queue(&mut batch_queue, queue_entity_count);
                     queue_entity_count = 0;
                 }
             }
-            submit_batch_queue(&mut batch_queue);
+            submit_batch_queue(&mut batch_queue, queue_entity_count);
         });
     }
 
@@ -2,7 +2,7 @@ use crate::{
     archetype::Archetype,
     component::{ComponentId, Components, Tick},
     entity::Entity,
-    query::FilteredAccess,
+    query::{Access, FilteredAccess},
     storage::{Table, TableRow},
     world::{unsafe_world_cell::UnsafeWorldCell, World},
 };
@@ -126,6 +126,14 @@ pub unsafe trait WorldQuery {
     // and forgetting to do so would be unsound.
     fn update_component_access(state: &Self::State, access: &mut FilteredAccess<ComponentId>);
 
+    /// Adds the components this [`WorldQuery`] reads on entities other than the fetched entity to
+    /// `access`, such as [`Related`](crate::relationship::Related) walking a relationship.
+    ///
+    /// Unlike the accesses of [`update_component_access`](Self::update_component_access), these
+    /// reads aren't limited to the archetypes matched by the query, or restricted by its filters.
+    /// Queries combining other [`WorldQuery`]s must forward this to each of them.
+    fn update_traversal_access(_state: &Self::State, _access: &mut Access<ComponentId>) {}
+
     /// Creates and initializes a [`State`](WorldQuery::State) for this [`WorldQuery`] type.
     fn init_state(world: &mut World) -> Self::State;
 
@@ -212,6 +220,10 @@ macro_rules! impl_tuple_world_query {
                 let ($($name,)*) = state;
                 $($name::update_component_access($name, _access);)*
             }
+            fn update_traversal_access(state: &Self::State, _access: &mut Access<ComponentId>) {
+                let ($($name,)*) = state;
+                $($name::update_traversal_access($name, _access);)*
+            }
             #[allow(unused_variables)]
             fn init_state(world: &mut World) -> Self::State {
                 ($($name::init_state(world),)*)
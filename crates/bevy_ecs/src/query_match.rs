@@ -0,0 +1,182 @@
+//! Events sent when entities start or stop matching an archetypal query filter.
+//!
+//! Whether an entity matches a filter like `(With<Enemy>, With<Frozen>)` only changes when
+//! components are inserted or removed, or when the entity is spawned or despawned.
+//! [`track_query_matches`] turns these changes into [`OnQueryMatch`] and [`OnQueryUnmatch`]
+//! events, so that gameplay rules can react to them instead of polling every frame.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::query_match::{track_query_matches, OnQueryMatch, OnQueryUnmatch};
+//! #[derive(Component)]
+//! struct Enemy;
+//! #[derive(Component)]
+//! struct Frozen;
+//!
+//! type FrozenEnemies = (With<Enemy>, With<Frozen>);
+//!
+//! let mut world = World::new();
+//! world.init_resource::<Events<OnQueryMatch<FrozenEnemies>>>();
+//! world.init_resource::<Events<OnQueryUnmatch<FrozenEnemies>>>();
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(track_query_matches::<FrozenEnemies>);
+//!
+//! let enemy = world.spawn(Enemy).id();
+//! schedule.run(&mut world);
+//! world.entity_mut(enemy).insert(Frozen);
+//! schedule.run(&mut world);
+//!
+//! let events = world.resource::<Events<OnQueryMatch<FrozenEnemies>>>();
+//! let mut reader = events.get_reader();
+//! assert_eq!(reader.read(events).next().unwrap().entity, enemy);
+//! ```
+
+use std::{fmt, marker::PhantomData};
+
+use crate::{
+    entity::{Entity, EntityHashSet},
+    event::{Event, EventWriter},
+    query::ArchetypeFilter,
+    system::{Local, Query},
+};
+
+/// Sent by [`track_query_matches`] when an entity starts matching the filter `F`.
+///
+/// This includes entities spawned with the matching components.
+pub struct OnQueryMatch<F: ArchetypeFilter + 'static> {
+    /// The entity that started matching.
+    pub entity: Entity,
+    _marker: PhantomData<fn() -> F>,
+}
+
+/// Sent by [`track_query_matches`] when an entity stops matching the filter `F`.
+///
+/// This includes matching entities that were despawned.
+pub struct OnQueryUnmatch<F: ArchetypeFilter + 'static> {
+    /// The entity that stopped matching.
+    pub entity: Entity,
+    _marker: PhantomData<fn() -> F>,
+}
+
+macro_rules! impl_query_match_event {
+    ($name:ident) => {
+        impl<F: ArchetypeFilter + 'static> $name<F> {
+            /// Creates a new event for `entity`.
+            pub fn new(entity: Entity) -> Self {
+                Self {
+                    entity,
+                    _marker: PhantomData,
+                }
+            }
+        }
+
+        impl<F: ArchetypeFilter + 'static> Event for $name<F> {}
+
+        impl<F: ArchetypeFilter + 'static> Clone for $name<F> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<F: ArchetypeFilter + 'static> Copy for $name<F> {}
+
+        impl<F: ArchetypeFilter + 'static> PartialEq for $name<F> {
+            fn eq(&self, other: &Self) -> bool {
+                self.entity == other.entity
+            }
+        }
+
+        impl<F: ArchetypeFilter + 'static> Eq for $name<F> {}
+
+        impl<F: ArchetypeFilter + 'static> fmt::Debug for $name<F> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("entity", &self.entity)
+                    .finish()
+            }
+        }
+    };
+}
+
+impl_query_match_event!(OnQueryMatch);
+impl_query_match_event!(OnQueryUnmatch);
+
+/// Sends [`OnQueryMatch`] and [`OnQueryUnmatch`] events for the entities that started or stopped
+/// matching the filter `F` since the last run of this system.
+///
+/// On its first run, every entity matching `F` is reported as starting to match.
+/// An entity that starts and stops matching between two runs isn't reported.
+///
+/// The events must have been registered, for example with `App::add_query_match_events`.
+pub fn track_query_matches<F: ArchetypeFilter + 'static>(
+    query: Query<Entity, F>,
+    mut matched: Local<EntityHashSet>,
+    mut previously_matched: Local<EntityHashSet>,
+    mut on_match: EventWriter<OnQueryMatch<F>>,
+    mut on_unmatch: EventWriter<OnQueryUnmatch<F>>,
+) {
+    std::mem::swap(&mut *matched, &mut *previously_matched);
+    matched.clear();
+    for entity in &query {
+        matched.insert(entity);
+        if !previously_matched.remove(&entity) {
+            on_match.send(OnQueryMatch::new(entity));
+        }
+    }
+    on_unmatch.send_batch(
+        previously_matched
+            .drain()
+            .map(|entity| OnQueryUnmatch::new(entity)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component)]
+    struct Frozen;
+
+    type ActiveEnemies = (With<Enemy>, Without<Frozen>);
+
+    fn matched(world: &mut World) -> Vec<Entity> {
+        let mut events = world.resource_mut::<Events<OnQueryMatch<ActiveEnemies>>>();
+        events.drain().map(|event| event.entity).collect()
+    }
+
+    fn unmatched(world: &mut World) -> Vec<Entity> {
+        let mut events = world.resource_mut::<Events<OnQueryUnmatch<ActiveEnemies>>>();
+        events.drain().map(|event| event.entity).collect()
+    }
+
+    #[test]
+    fn sends_match_and_unmatch_events() {
+        let mut world = World::new();
+        world.init_resource::<Events<OnQueryMatch<ActiveEnemies>>>();
+        world.init_resource::<Events<OnQueryUnmatch<ActiveEnemies>>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(track_query_matches::<ActiveEnemies>);
+
+        let a = world.spawn(Enemy).id();
+        let b = world.spawn((Enemy, Frozen)).id();
+        schedule.run(&mut world);
+        assert_eq!(matched(&mut world), vec![a]);
+        assert!(unmatched(&mut world).is_empty());
+
+        world.entity_mut(a).insert(Frozen);
+        world.entity_mut(b).remove::<Frozen>();
+        schedule.run(&mut world);
+        assert_eq!(matched(&mut world), vec![b]);
+        assert_eq!(unmatched(&mut world), vec![a]);
+
+        world.despawn(b);
+        schedule.run(&mut world);
+        assert!(matched(&mut world).is_empty());
+        assert_eq!(unmatched(&mut world), vec![b]);
+    }
+}
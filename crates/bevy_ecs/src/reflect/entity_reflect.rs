@@ -0,0 +1,177 @@
+use bevy_reflect::{Reflect, TypeRegistry};
+
+use crate::{
+    change_detection::Mut,
+    component::{ComponentId, ComponentInfo, Components},
+    reflect::ReflectComponent,
+    world::{EntityMut, EntityRef},
+};
+
+/// Returns the [`ComponentInfo`] and [`ReflectComponent`] of the component `id`, if it is
+/// registered in `registry` and is the component its [`ReflectComponent`] accesses.
+fn reflect_component<'c, 'r>(
+    components: &'c Components,
+    registry: &'r TypeRegistry,
+    id: ComponentId,
+) -> Option<(&'c ComponentInfo, &'r ReflectComponent)> {
+    let info = components.get_info(id)?;
+    let type_id = info.type_id()?;
+    // components created from a descriptor can share the type of a Rust component, while
+    // `ReflectComponent` always accesses the component registered for the type
+    if components.get_id(type_id) != Some(id) {
+        return None;
+    }
+    Some((info, registry.get_type_data::<ReflectComponent>(type_id)?))
+}
+
+impl<'w> EntityRef<'w> {
+    /// Returns the [`ComponentInfo`] and reflected value of every component of this entity
+    /// registered with [`ReflectComponent`] in `registry`.
+    ///
+    /// Components that aren't reflected are skipped. This is meant for inspectors and save
+    /// systems, which need every component of an entity without knowing their types.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, reflect::AppTypeRegistry};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct NotReflected;
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<AppTypeRegistry>();
+    /// world.resource::<AppTypeRegistry>().write().register::<Health>();
+    ///
+    /// let entity = world.spawn((Health(10), NotReflected)).id();
+    /// let registry = world.resource::<AppTypeRegistry>().read();
+    /// for (info, value) in world.entity(entity).iter_reflect(&registry) {
+    ///     println!("{}: {value:?}", info.name());
+    /// }
+    /// assert_eq!(world.entity(entity).iter_reflect(&registry).count(), 1);
+    /// ```
+    pub fn iter_reflect<'a>(
+        &'a self,
+        registry: &'a TypeRegistry,
+    ) -> impl Iterator<Item = (&'w ComponentInfo, &'w dyn Reflect)> + 'a {
+        let entity = *self;
+        let components = self.0.world().components();
+        self.0.archetype().components().filter_map(move |id| {
+            let (info, reflect_component) = reflect_component(components, registry, id)?;
+            Some((info, reflect_component.reflect(entity)?))
+        })
+    }
+}
+
+impl<'w> EntityMut<'w> {
+    /// Returns the [`ComponentInfo`] and mutable reflected value of every component of this
+    /// entity registered with [`ReflectComponent`] in `registry`.
+    ///
+    /// Components that aren't reflected are skipped. Like with [`EntityMut::get_mut`], a
+    /// component is only marked as changed if its value is mutably dereferenced.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, reflect::AppTypeRegistry};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Reflect)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<AppTypeRegistry>();
+    /// world.resource::<AppTypeRegistry>().write().register::<Health>();
+    /// let registry = world.resource::<AppTypeRegistry>().clone();
+    ///
+    /// let entity = world.spawn(Health(10)).id();
+    /// let mut entity_mut = world.entity_mut(entity);
+    /// for (_, mut value) in EntityMut::from(&mut entity_mut).iter_reflect_mut(&registry.read()) {
+    ///     value.apply(&Health(20));
+    /// }
+    /// assert_eq!(world.get::<Health>(entity).unwrap().0, 20);
+    /// ```
+    pub fn iter_reflect_mut<'a>(
+        &'a mut self,
+        registry: &'a TypeRegistry,
+    ) -> impl Iterator<Item = (&'a ComponentInfo, Mut<'a, dyn Reflect>)> + 'a {
+        let cell = self.0;
+        let components = cell.world().components();
+        cell.archetype().components().filter_map(move |id| {
+            let (info, reflect_component) = reflect_component(components, registry, id)?;
+            // SAFETY: `EntityMut` can mutate every component of the entity, it is borrowed
+            // mutably for as long as the values live, and each component is only accessed once
+            // as the components of an archetype are unique and map to distinct types.
+            let value = unsafe { reflect_component.reflect_unchecked_mut(cell) }?;
+            Some((info, value))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        change_detection::DetectChanges,
+        component::Component,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct A(u32);
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct B(String);
+
+    #[derive(Component)]
+    struct NotReflected;
+
+    #[test]
+    fn iter_reflect() {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<A>();
+            registry.register::<B>();
+        }
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+
+        let entity = world.spawn((A(1), B("b".into()), NotReflected)).id();
+        world.increment_change_tick();
+        let tick = world.change_tick();
+
+        let mut names: Vec<_> = world
+            .entity(entity)
+            .iter_reflect(&registry)
+            .map(|(info, value)| {
+                assert!(value.is::<A>() || value.is::<B>());
+                info.name()
+            })
+            .collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [std::any::type_name::<A>(), std::any::type_name::<B>()]
+        );
+
+        let mut entity_mut = world.entity_mut(entity);
+        for (_, mut value) in
+            bevy_ecs::world::EntityMut::from(&mut entity_mut).iter_reflect_mut(&registry)
+        {
+            // only the components that are mutably dereferenced are marked as changed
+            if value.is::<A>() {
+                value.downcast_mut::<A>().unwrap().0 += 1;
+            }
+        }
+        assert_eq!(world.get::<A>(entity), Some(&A(2)));
+        let entity_ref = world.entity(entity);
+        assert!(entity_ref.get_ref::<A>().unwrap().last_changed() == tick);
+        assert!(entity_ref.get_ref::<B>().unwrap().last_changed() != tick);
+    }
+}
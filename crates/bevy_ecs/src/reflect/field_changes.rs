@@ -0,0 +1,218 @@
+use bevy_reflect::{
+    Access, GetPath, ParsedPath, Reflect, ReflectPathError, ReflectRef, TypeInfo, Typed,
+};
+
+use crate::{
+    change_detection::{FieldTicksMut, Mut, TicksMut},
+    component::{Component, ComponentId},
+    world::World,
+};
+
+impl World {
+    /// Tracks the changes of each field of the reflected struct `T` separately, see
+    /// [`World::track_field_changes_by_id`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't a struct with named fields, or if it exists in any archetypes.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Reflect)]
+    /// struct Health {
+    ///     current: u32,
+    ///     max: u32,
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let id = world.track_field_changes::<Health>();
+    /// let info = world.components().get_info(id).unwrap();
+    /// assert_eq!(info.tracked_fields(), Some(&["current", "max"][..]));
+    /// ```
+    pub fn track_field_changes<T: Component + Typed>(&mut self) -> ComponentId {
+        let TypeInfo::Struct(info) = T::type_info() else {
+            panic!(
+                "Field changes can only be tracked for structs with named fields, which {} isn't",
+                std::any::type_name::<T>()
+            );
+        };
+        let id = self.init_component::<T>();
+        self.track_field_changes_by_id(id, info.field_names());
+        id
+    }
+}
+
+impl<'w, T: Reflect> Mut<'w, T> {
+    /// Maps to the field of the value at the reflection `path`, see [`GetPath`].
+    ///
+    /// If the value tracks the changes of its fields, see [`World::track_field_changes`], writes
+    /// through the returned [`Mut`] only change the field `path` starts with, in addition to the
+    /// whole value. Otherwise, this behaves like mapping to the field with
+    /// [`map_unchanged`](Mut::map_unchanged).
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Reflect)]
+    /// struct Health {
+    ///     current: u32,
+    ///     max: u32,
+    /// }
+    ///
+    /// fn heal(mut query: Query<&mut Health>) {
+    ///     for health in &mut query {
+    ///         let mut current = health.map_field_reflect("current").unwrap();
+    ///         *current.downcast_mut::<u32>().unwrap() += 1;
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(heal);
+    /// ```
+    pub fn map_field_reflect<'p>(
+        self,
+        path: &'p str,
+    ) -> Result<Mut<'w, dyn Reflect>, ReflectPathError<'p>> {
+        let field = ParsedPath::parse(path)?
+            .0
+            .first()
+            .and_then(|first| match &first.access {
+                Access::Field(name) => match self.value.reflect_ref() {
+                    ReflectRef::Struct(value) => (0..value.field_len())
+                        .position(|index| value.name_at(index) == Some(name.as_ref())),
+                    _ => None,
+                },
+                Access::FieldIndex(index) => Some(*index),
+                _ => None,
+            });
+        let Mut { value, ticks } = self;
+        let value = value.reflect_path_mut(path)?;
+        // If the field isn't tracked, writes change every field, like writes to the whole value.
+        let fields_changed = match field {
+            Some(field) if field < ticks.fields_changed.0.len() => {
+                FieldTicksMut(&ticks.fields_changed.0[field..=field])
+            }
+            _ => ticks.fields_changed,
+        };
+        Ok(Mut {
+            value,
+            ticks: TicksMut {
+                fields_changed,
+                ..ticks
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        query::{FieldChanged, FieldName, FilteredAccess, WorldQuery},
+        system::{Query, RunSystemOnce},
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect)]
+    struct Transform {
+        translation: [f32; 3],
+        scale: f32,
+    }
+
+    #[derive(Component, Reflect)]
+    #[component(storage = "SparseSet")]
+    struct Velocity {
+        linear: [f32; 3],
+        angular: f32,
+    }
+
+    struct Translation;
+
+    impl FieldName for Translation {
+        const NAME: &'static str = "translation";
+    }
+
+    struct Scale;
+
+    impl FieldName for Scale {
+        const NAME: &'static str = "scale";
+    }
+
+    struct Angular;
+
+    impl FieldName for Angular {
+        const NAME: &'static str = "angular";
+    }
+
+    #[test]
+    fn field_changed() {
+        let mut world = World::new();
+        world.track_field_changes::<Transform>();
+        world.track_field_changes::<Velocity>();
+        let transform = Transform {
+            translation: [0.0; 3],
+            scale: 1.0,
+        };
+        let velocity = Velocity {
+            linear: [0.0; 3],
+            angular: 0.0,
+        };
+        let a = world.spawn((transform, velocity)).id();
+        let mut translated = world.query_filtered::<(), FieldChanged<Transform, Translation>>();
+        let mut scaled = world.query_filtered::<(), FieldChanged<Transform, Scale>>();
+        let mut turned = world.query_filtered::<(), FieldChanged<Velocity, Angular>>();
+
+        // Spawning changes every field.
+        assert_eq!(translated.iter(&world).count(), 1);
+        assert_eq!(scaled.iter(&world).count(), 1);
+        assert_eq!(turned.iter(&world).count(), 1);
+        world.clear_trackers();
+
+        world.run_system_once(
+            |mut transforms: Query<&mut Transform>, mut velocities: Query<&mut Velocity>| {
+                for transform in &mut transforms {
+                    let mut scale = transform.map_field_reflect("scale").unwrap();
+                    *scale.downcast_mut::<f32>().unwrap() = 2.0;
+                }
+                for velocity in &mut velocities {
+                    let mut angular = velocity.map_field_reflect("angular").unwrap();
+                    *angular.downcast_mut::<f32>().unwrap() = 1.0;
+                }
+            },
+        );
+        assert_eq!(translated.iter(&world).count(), 0);
+        assert_eq!(scaled.iter(&world).count(), 1);
+        assert_eq!(turned.iter(&world).count(), 1);
+        assert_eq!(world.get::<Transform>(a).unwrap().scale, 2.0);
+        world.clear_trackers();
+
+        // Nested paths change the field they start with.
+        let mut x = world
+            .get_mut::<Transform>(a)
+            .unwrap()
+            .map_field_reflect("translation[0]")
+            .unwrap();
+        *x.downcast_mut::<f32>().unwrap() = 3.0;
+        assert_eq!(translated.iter(&world).count(), 1);
+        assert_eq!(scaled.iter(&world).count(), 0);
+        assert_eq!(turned.iter(&world).count(), 0);
+        world.clear_trackers();
+
+        // Writes to the whole component change every field.
+        world.get_mut::<Transform>(a).unwrap().scale = 4.0;
+        assert_eq!(translated.iter(&world).count(), 1);
+        assert_eq!(scaled.iter(&world).count(), 1);
+    }
+
+    #[test]
+    #[should_panic = "FieldChanged<bevy_ecs::reflect::field_changes::tests::Transform, bevy_ecs::reflect::field_changes::tests::Scale> conflicts with a previous access in this query."]
+    fn field_changed_conflicts_with_mutable_access() {
+        let mut world = World::new();
+        world.track_field_changes::<Transform>();
+        let state = FieldChanged::<Transform, Scale>::init_state(&mut world);
+        let mut access = FilteredAccess::default();
+        access.add_write(world.component_id::<Transform>().unwrap());
+        FieldChanged::<Transform, Scale>::update_component_access(&state, &mut access);
+    }
+}
@@ -0,0 +1,724 @@
+//! An undo/redo log of structural [`World`] mutations, built on reflection.
+//!
+//! See [`CommandJournal`] for details.
+
+use std::any::TypeId;
+
+use crate as bevy_ecs;
+use crate::bundle::Bundle;
+use crate::component::ComponentId;
+use crate::entity::Entity;
+use crate::prelude::Mut;
+use crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource};
+use crate::system::Resource;
+use crate::world::{EntityRef, World};
+use bevy_reflect::{Reflect, TypeRegistry};
+
+/// A single invertible operation stored in a [`CommandJournal`].
+///
+/// The journal stores the operation that *reverts* a recorded mutation: spawning an entity is
+/// journaled as [`JournalOp::Despawn`], inserting a component is journaled as a
+/// [`JournalOp::SetComponent`] holding the previous value, and so on.
+/// Applying an operation produces its own inverse, which is what makes redo possible.
+#[derive(Debug)]
+pub enum JournalOp {
+    /// Spawns a new entity with the given reflected components.
+    ///
+    /// Since entities can't be respawned with their old id, every other reference to `entity`
+    /// stored in the journal is remapped to the newly spawned entity.
+    Spawn {
+        /// The entity that was despawned.
+        entity: Entity,
+        /// The reflected components of the entity at the time it was despawned.
+        components: Vec<Box<dyn Reflect>>,
+    },
+    /// Despawns the entity.
+    Despawn {
+        /// The entity to despawn.
+        entity: Entity,
+    },
+    /// Sets the component of the given type on the entity, or removes it if `value` is `None`.
+    SetComponent {
+        /// The entity to modify.
+        entity: Entity,
+        /// The [`TypeId`] of the component.
+        type_id: TypeId,
+        /// The value to restore, if any.
+        value: Option<Box<dyn Reflect>>,
+    },
+    /// Sets the resource of the given type, or removes it if `value` is `None`.
+    SetResource {
+        /// The [`TypeId`] of the resource.
+        type_id: TypeId,
+        /// The value to restore, if any.
+        value: Option<Box<dyn Reflect>>,
+    },
+}
+
+impl JournalOp {
+    /// Applies this operation to the `world`, returning its inverse and, if an entity was
+    /// respawned, the `(old, new)` entity pair.
+    ///
+    /// Returns `None` if the operation no longer applies, for example because the entity has been
+    /// despawned outside of the journal or its types are missing from the `registry`.
+    fn apply(
+        self,
+        world: &mut World,
+        registry: &TypeRegistry,
+    ) -> Option<(JournalOp, Option<(Entity, Entity)>)> {
+        match self {
+            JournalOp::Spawn { entity, components } => {
+                let mut new_entity = world.spawn_empty();
+                for component in &components {
+                    let Some(reflect_component) =
+                        component.get_represented_type_info().and_then(|info| {
+                            registry.get_type_data::<ReflectComponent>(info.type_id())
+                        })
+                    else {
+                        continue;
+                    };
+                    reflect_component.insert(&mut new_entity, &**component, registry);
+                }
+                let new_entity = new_entity.id();
+                Some((
+                    JournalOp::Despawn { entity: new_entity },
+                    Some((entity, new_entity)),
+                ))
+            }
+            JournalOp::Despawn { entity } => {
+                let components = reflect_components(world, entity, registry)?;
+                world.despawn(entity);
+                Some((JournalOp::Spawn { entity, components }, None))
+            }
+            JournalOp::SetComponent {
+                entity,
+                type_id,
+                value,
+            } => {
+                let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+                let mut entity_mut = world.get_entity_mut(entity)?;
+                let previous = reflect_component
+                    .reflect(EntityRef::from(&entity_mut))
+                    .map(Reflect::clone_value);
+                match &value {
+                    Some(value) => {
+                        reflect_component.apply_or_insert(&mut entity_mut, &**value, registry);
+                    }
+                    None => reflect_component.remove(&mut entity_mut),
+                }
+                Some((
+                    JournalOp::SetComponent {
+                        entity,
+                        type_id,
+                        value: previous,
+                    },
+                    None,
+                ))
+            }
+            JournalOp::SetResource { type_id, value } => {
+                let reflect_resource = registry.get_type_data::<ReflectResource>(type_id)?;
+                let previous = reflect_resource.reflect(world).map(Reflect::clone_value);
+                match &value {
+                    Some(value) => reflect_resource.apply_or_insert(world, &**value, registry),
+                    None => reflect_resource.remove(world),
+                }
+                Some((
+                    JournalOp::SetResource {
+                        type_id,
+                        value: previous,
+                    },
+                    None,
+                ))
+            }
+        }
+    }
+
+    fn remap_entity(&mut self, from: Entity, to: Entity) {
+        match self {
+            JournalOp::Spawn { entity, .. }
+            | JournalOp::Despawn { entity }
+            | JournalOp::SetComponent { entity, .. } => {
+                if *entity == from {
+                    *entity = to;
+                }
+            }
+            JournalOp::SetResource { .. } => {}
+        }
+    }
+}
+
+/// Reflects every component of the entity that has [`ReflectComponent`] registered.
+///
+/// Returns `None` if the entity doesn't exist.
+fn reflect_components(
+    world: &World,
+    entity: Entity,
+    registry: &TypeRegistry,
+) -> Option<Vec<Box<dyn Reflect>>> {
+    let entity = world.get_entity(entity)?;
+    let components = entity
+        .archetype()
+        .components()
+        .filter_map(|component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+            reflect_component.reflect(entity).map(Reflect::clone_value)
+        })
+        .collect();
+    Some(components)
+}
+
+/// A [`Resource`] recording structural [`World`] mutations as invertible [`JournalOp`]s,
+/// so they can be reverted with [`World::undo`] and reapplied with [`World::redo`].
+///
+/// Once [`World::enable_journal`] is called, the journal records the mutations made by the
+/// standard [`Commands`] and [`EntityCommands`]: spawning and despawning entities, inserting,
+/// removing and retaining components, and inserting and removing resources.
+/// Mutations made directly through the [`World`] are not recorded.
+/// Recorded operations accumulate until [`CommandJournal::commit`] groups them into a single
+/// undo step. [`World::undo`] commits any pending operations first.
+///
+/// Components and resources are captured through [`ReflectComponent`] and [`ReflectResource`]
+/// looked up in the [`AppTypeRegistry`]: types without that registration are not restored.
+/// Entity references stored inside components are not remapped when an entity is respawned.
+///
+/// [`Commands`]: crate::system::Commands
+/// [`EntityCommands`]: crate::system::EntityCommands
+#[derive(Resource, Default)]
+pub struct CommandJournal {
+    pending: Vec<JournalOp>,
+    undo_stack: Vec<Vec<JournalOp>>,
+    redo_stack: Vec<Vec<JournalOp>>,
+}
+
+impl CommandJournal {
+    /// Records an operation that reverts a mutation.
+    ///
+    /// Recording a new operation discards the redo history.
+    pub fn record(&mut self, op: JournalOp) {
+        self.pending.push(op);
+        self.redo_stack.clear();
+    }
+
+    /// Groups all operations recorded since the last commit into a single undo step.
+    ///
+    /// Does nothing if no operations are pending.
+    pub fn commit(&mut self) {
+        if !self.pending.is_empty() {
+            self.undo_stack.push(std::mem::take(&mut self.pending));
+        }
+    }
+
+    /// Returns `true` if there is anything to undo, including uncommitted operations.
+    pub fn can_undo(&self) -> bool {
+        !self.pending.is_empty() || !self.undo_stack.is_empty()
+    }
+
+    /// Returns `true` if there is anything to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Discards the whole undo and redo history.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    fn remap_entity(&mut self, from: Entity, to: Entity) {
+        self.undo_stack
+            .iter_mut()
+            .chain(self.redo_stack.iter_mut())
+            .flatten()
+            .for_each(|op| op.remap_entity(from, to));
+    }
+}
+
+/// Applies `step` in reverse order, returning the step that reverts it.
+fn apply_step(
+    world: &mut World,
+    journal: &mut CommandJournal,
+    registry: &TypeRegistry,
+    mut step: Vec<JournalOp>,
+) -> Vec<JournalOp> {
+    let mut inverse: Vec<JournalOp> = Vec::with_capacity(step.len());
+    while let Some(op) = step.pop() {
+        let Some((inverse_op, remapped)) = op.apply(world, registry) else {
+            continue;
+        };
+        if let Some((from, to)) = remapped {
+            step.iter_mut()
+                .chain(inverse.iter_mut())
+                .for_each(|op| op.remap_entity(from, to));
+            journal.remap_entity(from, to);
+        }
+        inverse.push(inverse_op);
+    }
+    inverse
+}
+
+impl World {
+    /// Starts recording the mutations made by the standard commands in the [`CommandJournal`],
+    /// inserting an empty one if the world has none.
+    ///
+    /// Recording needs an [`AppTypeRegistry`] to capture the values of components and resources.
+    pub fn enable_journal(&mut self) {
+        self.init_resource::<CommandJournal>();
+        self.journaling = true;
+    }
+
+    /// Stops recording mutations in the [`CommandJournal`], keeping its history.
+    pub fn disable_journal(&mut self) {
+        self.journaling = false;
+    }
+
+    /// Returns `true` if the standard commands record their mutations in the [`CommandJournal`].
+    #[inline]
+    pub fn is_journaling(&self) -> bool {
+        self.journaling
+    }
+
+    /// Reverts the most recent step recorded in the [`CommandJournal`], committing any pending
+    /// operations first.
+    ///
+    /// Returns `false` if there was nothing to undo or the world has no [`CommandJournal`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world has no [`AppTypeRegistry`].
+    pub fn undo(&mut self) -> bool {
+        if !self.contains_resource::<CommandJournal>() {
+            return false;
+        }
+        let registry = self.resource::<AppTypeRegistry>().clone();
+        self.resource_scope(|world, mut journal: Mut<CommandJournal>| {
+            journal.commit();
+            let Some(step) = journal.undo_stack.pop() else {
+                return false;
+            };
+            let inverse = apply_step(world, &mut journal, &registry.read(), step);
+            journal.redo_stack.push(inverse);
+            true
+        })
+    }
+
+    /// Reapplies the most recently undone step of the [`CommandJournal`].
+    ///
+    /// Returns `false` if there was nothing to redo or the world has no [`CommandJournal`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world has no [`AppTypeRegistry`].
+    pub fn redo(&mut self) -> bool {
+        if !self.contains_resource::<CommandJournal>() {
+            return false;
+        }
+        let registry = self.resource::<AppTypeRegistry>().clone();
+        self.resource_scope(|world, mut journal: Mut<CommandJournal>| {
+            let Some(step) = journal.redo_stack.pop() else {
+                return false;
+            };
+            let inverse = apply_step(world, &mut journal, &registry.read(), step);
+            journal.undo_stack.push(inverse);
+            true
+        })
+    }
+}
+
+/// Records the operations built by `ops` if the world is journaling and has a [`CommandJournal`]
+/// and an [`AppTypeRegistry`].
+///
+/// Only the `journaling` flag of the world is read when it isn't journaling, so that the standard
+/// commands don't pay for the journal in worlds that don't use it.
+#[inline]
+fn record(world: &mut World, ops: impl FnOnce(&mut World, &TypeRegistry) -> Vec<JournalOp>) {
+    if !world.journaling {
+        return;
+    }
+    let Some(registry) = world.get_resource::<AppTypeRegistry>().cloned() else {
+        return;
+    };
+    let ops = ops(world, &registry.read());
+    let Some(mut journal) = world.get_resource_mut::<CommandJournal>() else {
+        return;
+    };
+    for op in ops {
+        journal.record(op);
+    }
+}
+
+/// Records the spawn of `entity` by [`Commands::spawn`].
+///
+/// [`Commands::spawn`]: crate::system::Commands::spawn
+pub(crate) fn record_spawn(world: &mut World, entity: Entity) {
+    record(world, |_, _| vec![JournalOp::Despawn { entity }]);
+}
+
+/// Records the spawn of `entities` by [`Commands::spawn_batch`].
+///
+/// [`Commands::spawn_batch`]: crate::system::Commands::spawn_batch
+pub(crate) fn record_spawn_batch(world: &mut World, entities: Vec<Entity>) {
+    record(world, |_, _| {
+        entities
+            .into_iter()
+            .map(|entity| JournalOp::Despawn { entity })
+            .collect()
+    });
+}
+
+/// Records the values of the components of `T` on `entity` before [`EntityCommands::insert`] or
+/// [`EntityCommands::try_insert`] replaces them.
+///
+/// [`EntityCommands::insert`]: crate::system::EntityCommands::insert
+/// [`EntityCommands::try_insert`]: crate::system::EntityCommands::try_insert
+pub(crate) fn record_insert<T: Bundle>(world: &mut World, entity: Entity) {
+    record(world, |world, registry| {
+        let component_ids = world.init_bundle::<T>().components().to_vec();
+        component_ops(world, entity, &component_ids, registry, true)
+    });
+}
+
+/// Records the insertion of `T` on `entity`, or its spawn if it doesn't exist, by
+/// [`Commands::insert_or_spawn_batch`].
+///
+/// [`Commands::insert_or_spawn_batch`]: crate::system::Commands::insert_or_spawn_batch
+pub(crate) fn record_insert_or_spawn<T: Bundle>(world: &mut World, entity: Entity) {
+    if world.get_entity(entity).is_some() {
+        record_insert::<T>(world, entity);
+    } else {
+        record_spawn(world, entity);
+    }
+}
+
+/// Records the values of the components of `T` on `entity` before [`EntityCommands::remove`]
+/// removes them.
+///
+/// [`EntityCommands::remove`]: crate::system::EntityCommands::remove
+pub(crate) fn record_remove<T: Bundle>(world: &mut World, entity: Entity) {
+    record(world, |world, registry| {
+        let component_ids = world.init_bundle::<T>().components().to_vec();
+        component_ops(world, entity, &component_ids, registry, false)
+    });
+}
+
+/// Records the value of the component `component_id` on `entity` before
+/// [`EntityCommands::remove_by_id`] removes it.
+///
+/// [`EntityCommands::remove_by_id`]: crate::system::EntityCommands::remove_by_id
+pub(crate) fn record_remove_by_id(world: &mut World, entity: Entity, component_id: ComponentId) {
+    record(world, |world, registry| {
+        component_ops(world, entity, &[component_id], registry, false)
+    });
+}
+
+/// Records the values of the components of `entity` outside of `T` before
+/// [`EntityCommands::retain`] removes them.
+///
+/// [`EntityCommands::retain`]: crate::system::EntityCommands::retain
+pub(crate) fn record_retain<T: Bundle>(world: &mut World, entity: Entity) {
+    record(world, |world, registry| {
+        let retained = world.init_bundle::<T>().components().to_vec();
+        let Some(entity_ref) = world.get_entity(entity) else {
+            return Vec::new();
+        };
+        let component_ids: Vec<_> = entity_ref
+            .archetype()
+            .components()
+            .filter(|component_id| !retained.contains(component_id))
+            .collect();
+        component_ops(world, entity, &component_ids, registry, false)
+    });
+}
+
+/// Records the components of `entity` before [`EntityCommands::despawn`] despawns it.
+///
+/// [`EntityCommands::despawn`]: crate::system::EntityCommands::despawn
+pub(crate) fn record_despawn(world: &mut World, entity: Entity) {
+    record(world, |world, registry| {
+        reflect_components(world, entity, registry)
+            .map(|components| JournalOp::Spawn { entity, components })
+            .into_iter()
+            .collect()
+    });
+}
+
+/// Records the value of the resource `R` before [`Commands::insert_resource`] replaces it.
+///
+/// [`Commands::insert_resource`]: crate::system::Commands::insert_resource
+pub(crate) fn record_insert_resource<R: Resource>(world: &mut World) {
+    record(world, |world, registry| {
+        resource_ops::<R>(world, registry, true)
+    });
+}
+
+/// Records the insertion of the resource `R` by [`Commands::init_resource`], if the world doesn't
+/// have it yet.
+///
+/// [`Commands::init_resource`]: crate::system::Commands::init_resource
+pub(crate) fn record_init_resource<R: Resource>(world: &mut World) {
+    record(world, |world, registry| {
+        if world.contains_resource::<R>() {
+            return Vec::new();
+        }
+        resource_ops::<R>(world, registry, true)
+    });
+}
+
+/// Records the value of the resource `R` before [`Commands::remove_resource`] removes it.
+///
+/// [`Commands::remove_resource`]: crate::system::Commands::remove_resource
+pub(crate) fn record_remove_resource<R: Resource>(world: &mut World) {
+    record(world, |world, registry| {
+        resource_ops::<R>(world, registry, false)
+    });
+}
+
+/// Builds the operations restoring the current values of the components `component_ids` on
+/// `entity`.
+///
+/// Components the entity doesn't have are restored as absent if `inserted`, and skipped otherwise.
+fn component_ops(
+    world: &World,
+    entity: Entity,
+    component_ids: &[ComponentId],
+    registry: &TypeRegistry,
+    inserted: bool,
+) -> Vec<JournalOp> {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    component_ids
+        .iter()
+        .filter_map(|&component_id| {
+            let type_id = world.components().get_info(component_id)?.type_id()?;
+            let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+            let value = reflect_component
+                .reflect(entity_ref)
+                .map(Reflect::clone_value);
+            (inserted || value.is_some()).then_some(JournalOp::SetComponent {
+                entity,
+                type_id,
+                value,
+            })
+        })
+        .collect()
+}
+
+/// Builds the operation restoring the current value of the resource `R`.
+///
+/// A missing resource is restored as absent if `inserted`, and skipped otherwise.
+fn resource_ops<R: Resource>(
+    world: &World,
+    registry: &TypeRegistry,
+    inserted: bool,
+) -> Vec<JournalOp> {
+    let type_id = TypeId::of::<R>();
+    let Some(reflect_resource) = registry.get_type_data::<ReflectResource>(type_id) else {
+        return Vec::new();
+    };
+    let value = reflect_resource.reflect(world).map(Reflect::clone_value);
+    if !inserted && value.is_none() {
+        return Vec::new();
+    }
+    vec![JournalOp::SetResource { type_id, value }]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandJournal;
+    use crate::prelude::{AppTypeRegistry, ReflectComponent, ReflectResource};
+    use crate::system::{Commands, Resource, SystemState};
+    use crate::{self as bevy_ecs, component::Component, entity::Entity, world::World};
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default, PartialEq, Eq, Debug)]
+    #[reflect(Component)]
+    struct ComponentA(u32);
+
+    #[derive(Component, Reflect, Default, PartialEq, Eq, Debug)]
+    #[reflect(Component)]
+    struct ComponentB(u32);
+
+    #[derive(Resource, Reflect, Default, PartialEq, Eq, Debug)]
+    #[reflect(Resource)]
+    struct ResourceA(u32);
+
+    fn journaled_world() -> World {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        {
+            let mut registry = type_registry.write();
+            registry.register::<ComponentA>();
+            registry.register::<ComponentB>();
+            registry.register::<ResourceA>();
+        }
+        world.insert_resource(type_registry);
+        world.enable_journal();
+        world
+    }
+
+    #[test]
+    fn undo_redo_insert() {
+        let mut world = journaled_world();
+        let entity = world.spawn(ComponentA(1)).id();
+
+        let mut system_state: SystemState<Commands> = SystemState::new(&mut world);
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).insert(ComponentA(2));
+        system_state.apply(&mut world);
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(2)));
+
+        assert!(world.undo());
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(1)));
+        assert!(!world.undo());
+
+        assert!(world.redo());
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(2)));
+        assert!(!world.redo());
+    }
+
+    #[test]
+    fn undo_redo_spawn_and_despawn() {
+        let mut world = journaled_world();
+
+        let mut system_state: SystemState<Commands> = SystemState::new(&mut world);
+        let mut commands = system_state.get_mut(&mut world);
+        let entity = commands.spawn(ComponentA(3)).id();
+        system_state.apply(&mut world);
+        world.resource_mut::<CommandJournal>().commit();
+
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).despawn();
+        system_state.apply(&mut world);
+        assert!(world.get_entity(entity).is_none());
+
+        // Undoing the despawn respawns the entity with a new id.
+        assert!(world.undo());
+        let mut query = world.query::<&ComponentA>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [&ComponentA(3)]);
+
+        // Undoing the spawn follows the respawned entity.
+        assert!(world.undo());
+        assert_eq!(query.iter(&world).count(), 0);
+
+        assert!(world.redo());
+        assert!(world.redo());
+        assert_eq!(query.iter(&world).count(), 0);
+        assert!(world.undo());
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [&ComponentA(3)]);
+    }
+
+    #[test]
+    fn undo_resource() {
+        let mut world = journaled_world();
+
+        let mut system_state: SystemState<Commands> = SystemState::new(&mut world);
+        let mut commands = system_state.get_mut(&mut world);
+        commands.insert_resource(ResourceA(1));
+        commands.insert_resource(ResourceA(2));
+        system_state.apply(&mut world);
+        world.resource_mut::<CommandJournal>().commit();
+
+        let mut commands = system_state.get_mut(&mut world);
+        commands.remove_resource::<ResourceA>();
+        system_state.apply(&mut world);
+        assert!(!world.contains_resource::<ResourceA>());
+
+        assert!(world.undo());
+        assert_eq!(world.resource::<ResourceA>(), &ResourceA(2));
+        assert!(world.undo());
+        assert!(!world.contains_resource::<ResourceA>());
+    }
+
+    #[test]
+    fn records_standard_commands() {
+        let mut world = journaled_world();
+
+        let mut system_state: SystemState<Commands> = SystemState::new(&mut world);
+        let mut commands = system_state.get_mut(&mut world);
+        let entity = commands.spawn(ComponentA(1)).id();
+        commands.insert_resource(ResourceA(1));
+        system_state.apply(&mut world);
+        world.resource_mut::<CommandJournal>().commit();
+
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).insert(ComponentA(2));
+        commands.insert_resource(ResourceA(2));
+        system_state.apply(&mut world);
+        world.resource_mut::<CommandJournal>().commit();
+
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).remove::<ComponentA>();
+        system_state.apply(&mut world);
+        world.resource_mut::<CommandJournal>().commit();
+
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).despawn();
+        system_state.apply(&mut world);
+        assert!(world.get_entity(entity).is_none());
+
+        // Undoing the despawn respawns the entity, without the removed component.
+        let mut query = world.query::<&ComponentA>();
+        assert!(world.undo());
+        assert_eq!(world.query::<Entity>().iter(&world).count(), 1);
+        assert_eq!(query.iter(&world).count(), 0);
+
+        assert!(world.undo());
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [&ComponentA(2)]);
+
+        assert!(world.undo());
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), [&ComponentA(1)]);
+        assert_eq!(world.resource::<ResourceA>(), &ResourceA(1));
+
+        assert!(world.undo());
+        assert_eq!(query.iter(&world).count(), 0);
+        assert!(!world.contains_resource::<ResourceA>());
+        assert!(!world.undo());
+    }
+
+    #[test]
+    fn records_try_insert_retain_and_batches() {
+        let mut world = journaled_world();
+        let entity = world.spawn((ComponentA(1), ComponentB(1))).id();
+
+        let mut system_state: SystemState<Commands> = SystemState::new(&mut world);
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).try_insert(ComponentA(2));
+        commands.spawn_batch([ComponentA(3), ComponentA(4)]);
+        system_state.apply(&mut world);
+        world.resource_mut::<CommandJournal>().commit();
+
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).retain::<ComponentA>();
+        commands.insert_or_spawn_batch([(entity, ComponentA(5))]);
+        system_state.apply(&mut world);
+        assert!(world.get::<ComponentB>(entity).is_none());
+
+        let mut query = world.query::<&ComponentA>();
+        assert!(world.undo());
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(2)));
+        assert_eq!(world.get::<ComponentB>(entity), Some(&ComponentB(1)));
+        assert_eq!(query.iter(&world).count(), 3);
+
+        assert!(world.undo());
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(1)));
+        assert_eq!(query.iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn disabled_journal_records_nothing() {
+        let mut world = journaled_world();
+        world.disable_journal();
+        let entity = world.spawn_empty().id();
+
+        let mut system_state: SystemState<Commands> = SystemState::new(&mut world);
+        let mut commands = system_state.get_mut(&mut world);
+        commands.entity(entity).insert(ComponentA(1));
+        system_state.apply(&mut world);
+
+        assert!(!world.resource::<CommandJournal>().can_undo());
+        assert!(!world.undo());
+        assert_eq!(world.get::<ComponentA>(entity), Some(&ComponentA(1)));
+    }
+}
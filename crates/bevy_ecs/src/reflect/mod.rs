@@ -11,16 +11,29 @@ use bevy_reflect::{Reflect, ReflectFromReflect, TypeRegistry, TypeRegistryArc};
 mod bundle;
 mod component;
 mod entity_commands;
+mod entity_reflect;
+mod field_changes;
 mod from_world;
+mod journal;
 mod map_entities;
 mod resource;
+mod snapshot;
+mod transfer;
 
 pub use bundle::{ReflectBundle, ReflectBundleFns};
 pub use component::{ReflectComponent, ReflectComponentFns};
 pub use entity_commands::ReflectCommandExt;
 pub use from_world::{ReflectFromWorld, ReflectFromWorldFns};
+pub(crate) use journal::{
+    record_despawn, record_init_resource, record_insert, record_insert_or_spawn,
+    record_insert_resource, record_remove, record_remove_by_id, record_remove_resource,
+    record_retain, record_spawn, record_spawn_batch,
+};
+pub use journal::{CommandJournal, JournalOp};
 pub use map_entities::ReflectMapEntities;
 pub use resource::{ReflectResource, ReflectResourceFns};
+pub use snapshot::{SnapshotFilter, WorldSnapshot};
+pub use transfer::EntityTransferError;
 
 /// A [`Resource`] storing [`TypeRegistry`] for
 /// type registrations relevant to a whole app.
@@ -0,0 +1,675 @@
+//! Snapshots of the reflectable state of a [`World`], for rollback and undo.
+//!
+//! See [`WorldSnapshot`] for details.
+
+use std::any::TypeId;
+
+use bevy_reflect::{Reflect, ReflectFromReflect, TypeRegistry};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::component::{Component, Tick};
+use crate::entity::{Entity, EntityHashMap};
+use crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource};
+use crate::system::Resource;
+use crate::world::World;
+
+/// Selects the components and resources captured by a [`WorldSnapshot`].
+///
+/// By default, every component and resource registered with [`ReflectComponent`] or
+/// [`ReflectResource`] is captured. Allowing a type restricts the snapshot to the allowed types,
+/// denying a type excludes it.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, reflect::SnapshotFilter};
+/// # #[derive(Component)] struct Transform;
+/// # #[derive(Component)] struct Velocity;
+/// # #[derive(Resource)] struct Score;
+/// let filter = SnapshotFilter::all()
+///     .allow_component::<Transform>()
+///     .allow_component::<Velocity>()
+///     .deny_resource::<Score>();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SnapshotFilter {
+    components: TypeFilter,
+    resources: TypeFilter,
+}
+
+#[derive(Clone, Debug)]
+enum TypeFilter {
+    Allow(HashSet<TypeId>),
+    Deny(HashSet<TypeId>),
+}
+
+impl Default for TypeFilter {
+    fn default() -> Self {
+        Self::Deny(HashSet::default())
+    }
+}
+
+impl TypeFilter {
+    fn allow(&mut self, type_id: TypeId) {
+        match self {
+            Self::Allow(allowed) => {
+                allowed.insert(type_id);
+            }
+            Self::Deny(_) => *self = Self::Allow([type_id].into_iter().collect()),
+        }
+    }
+
+    fn deny(&mut self, type_id: TypeId) {
+        match self {
+            Self::Allow(allowed) => {
+                allowed.remove(&type_id);
+            }
+            Self::Deny(denied) => {
+                denied.insert(type_id);
+            }
+        }
+    }
+
+    fn is_allowed(&self, type_id: TypeId) -> bool {
+        match self {
+            Self::Allow(allowed) => allowed.contains(&type_id),
+            Self::Deny(denied) => !denied.contains(&type_id),
+        }
+    }
+}
+
+impl SnapshotFilter {
+    /// A filter capturing every reflectable component and resource.
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Captures components of type `T`, and only the allowed component types.
+    pub fn allow_component<T: Component>(mut self) -> Self {
+        self.components.allow(TypeId::of::<T>());
+        self
+    }
+
+    /// Doesn't capture components of type `T`.
+    pub fn deny_component<T: Component>(mut self) -> Self {
+        self.components.deny(TypeId::of::<T>());
+        self
+    }
+
+    /// Captures the resource of type `R`, and only the allowed resource types.
+    pub fn allow_resource<R: Resource>(mut self) -> Self {
+        self.resources.allow(TypeId::of::<R>());
+        self
+    }
+
+    /// Doesn't capture the resource of type `R`.
+    pub fn deny_resource<R: Resource>(mut self) -> Self {
+        self.resources.deny(TypeId::of::<R>());
+        self
+    }
+
+    /// Returns `true` if components of the given type are captured.
+    pub fn is_component_allowed(&self, type_id: TypeId) -> bool {
+        self.components.is_allowed(type_id)
+    }
+
+    /// Returns `true` if the resource of the given type is captured.
+    pub fn is_resource_allowed(&self, type_id: TypeId) -> bool {
+        self.resources.is_allowed(type_id)
+    }
+
+    /// Returns `true` if every entity is captured, rather than only the entities with an allowed
+    /// component.
+    pub fn captures_all_entities(&self) -> bool {
+        matches!(self.components, TypeFilter::Deny(_))
+    }
+}
+
+/// Clones a reflected value into its concrete type if it's registered with
+/// [`ReflectFromReflect`], or into a dynamic representation otherwise.
+fn clone_reflect(value: &dyn Reflect, registry: &TypeRegistry) -> Box<dyn Reflect> {
+    value
+        .get_represented_type_info()
+        .and_then(|info| registry.get_type_data::<ReflectFromReflect>(info.type_id()))
+        .and_then(|from_reflect| from_reflect.from_reflect(value))
+        .unwrap_or_else(|| value.clone_value())
+}
+
+/// The captured components of an entity.
+#[derive(Debug, Default)]
+struct EntitySnapshot {
+    components: Vec<(TypeId, Box<dyn Reflect>)>,
+    /// The components removed since the base of an incremental snapshot.
+    removed: Vec<TypeId>,
+}
+
+/// A copy of the reflectable component and resource state of a [`World`], which can later be
+/// restored, for rollback networking and undo systems.
+///
+/// Components and resources are captured through [`ReflectComponent`] and [`ReflectResource`]
+/// looked up in the [`AppTypeRegistry`]: types without that registration are neither captured
+/// nor restored. Components are captured archetype by archetype, so the values of each table are
+/// read in order.
+///
+/// # Partial snapshots
+///
+/// [`WorldSnapshot::capture_filtered`] only captures the types selected by a [`SnapshotFilter`].
+/// When components are restricted to an allowlist, only the entities with an allowed component
+/// are captured, and restoring the snapshot doesn't despawn other entities.
+///
+/// # Incremental snapshots
+///
+/// [`WorldSnapshot::capture_changes`] only captures what changed since a base snapshot, using
+/// change ticks: changed components and resources, removed ones, and spawned and despawned
+/// entities. Incremental snapshots are cheap to take every frame, and are
+/// [merged](WorldSnapshot::merge) into their base to get the full state at the time they were
+/// taken.
+///
+/// # Entities
+///
+/// Despawned entities can't be respawned with the same id: restoring a snapshot spawns new
+/// entities for them, and returns the map from their captured id to their new one. Entity
+/// references stored inside components aren't remapped.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, reflect::{AppTypeRegistry, ReflectComponent, WorldSnapshot}};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, PartialEq, Debug)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// let player = world.spawn(Health(10)).id();
+///
+/// let snapshot = WorldSnapshot::capture(&world);
+/// world.entity_mut(player).insert(Health(0));
+/// world.spawn(Health(5));
+///
+/// snapshot.restore(&mut world);
+/// assert_eq!(world.get::<Health>(player), Some(&Health(10)));
+/// assert_eq!(world.entities().len(), 1);
+/// ```
+#[derive(Debug)]
+pub struct WorldSnapshot {
+    /// The change tick of the world when the snapshot was taken.
+    tick: Tick,
+    /// The tick of the base snapshot, for incremental snapshots.
+    base_tick: Option<Tick>,
+    filter: SnapshotFilter,
+    entities: EntityHashMap<EntitySnapshot>,
+    /// The entities despawned since the base of an incremental snapshot.
+    despawned: Vec<Entity>,
+    resources: HashMap<TypeId, Box<dyn Reflect>>,
+    /// The resources removed since the base of an incremental snapshot.
+    removed_resources: Vec<TypeId>,
+}
+
+impl WorldSnapshot {
+    /// Captures every reflectable component and resource of the `world`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world has no [`AppTypeRegistry`].
+    pub fn capture(world: &World) -> Self {
+        Self::capture_filtered(world, SnapshotFilter::all())
+    }
+
+    /// Captures the reflectable components and resources of the `world` selected by `filter`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world has no [`AppTypeRegistry`].
+    pub fn capture_filtered(world: &World, filter: SnapshotFilter) -> Self {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let mut snapshot = Self::new(world, filter, None);
+        snapshot.capture_entities(world, &registry, |_| None);
+        snapshot.capture_resources(world, &registry, None);
+        snapshot
+    }
+
+    /// Captures what changed in the `world` since `base` was taken, with the filter of `base`.
+    ///
+    /// `base` must hold the full state the changes apply to: a full snapshot, or one that the
+    /// previous incremental snapshots were [merged](WorldSnapshot::merge) into.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world has no [`AppTypeRegistry`].
+    pub fn capture_changes(world: &World, base: &WorldSnapshot) -> Self {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        let mut snapshot = Self::new(world, base.filter.clone(), Some(base.tick));
+        snapshot.capture_entities(world, &registry, |entity| {
+            base.entities.get(&entity).map(|captured| {
+                captured
+                    .components
+                    .iter()
+                    .map(|(type_id, _)| *type_id)
+                    .collect()
+            })
+        });
+        snapshot.despawned = base
+            .entities
+            .keys()
+            .copied()
+            .filter(|&entity| !snapshot.entities.contains_key(&entity))
+            .filter(|&entity| world.get_entity(entity).is_none())
+            .collect();
+        snapshot.capture_resources(world, &registry, Some(base));
+        snapshot
+    }
+
+    fn new(world: &World, filter: SnapshotFilter, base_tick: Option<Tick>) -> Self {
+        Self {
+            // Changes made after the capture get a newer tick, and are seen by incremental
+            // snapshots based on this one.
+            tick: world.increment_change_tick(),
+            base_tick,
+            filter,
+            entities: EntityHashMap::default(),
+            despawned: Vec::new(),
+            resources: HashMap::default(),
+            removed_resources: Vec::new(),
+        }
+    }
+
+    /// Captures the components of every entity, or only the ones changed since the base tick for
+    /// entities for which `base_components` returns the captured component types.
+    fn capture_entities(
+        &mut self,
+        world: &World,
+        registry: &TypeRegistry,
+        base_components: impl Fn(Entity) -> Option<Vec<TypeId>>,
+    ) {
+        let this_run = world.read_change_tick();
+        for archetype in world.archetypes().iter() {
+            // The reflectable components of the archetype selected by the filter.
+            let components: Vec<_> = archetype
+                .components()
+                .filter_map(|component_id| {
+                    let type_id = world.components().get_info(component_id)?.type_id()?;
+                    if !self.filter.is_component_allowed(type_id) {
+                        return None;
+                    }
+                    let reflect_component = registry.get_type_data::<ReflectComponent>(type_id)?;
+                    Some((component_id, type_id, reflect_component))
+                })
+                .collect();
+            // Entities without any selected component are only captured when every entity is,
+            // or to record the removal of their components in incremental snapshots.
+            let skipped = components.is_empty() && !self.filter.captures_all_entities();
+            if skipped && self.base_tick.is_none() {
+                continue;
+            }
+
+            for archetype_entity in archetype.entities() {
+                let entity = world.entity(archetype_entity.id());
+                let base = base_components(entity.id());
+                if skipped && base.is_none() {
+                    continue;
+                }
+                let mut captured = EntitySnapshot::default();
+                for &(component_id, type_id, reflect_component) in &components {
+                    if let (Some(_), Some(base_tick)) = (&base, self.base_tick) {
+                        let changed = entity
+                            .get_change_ticks_by_id(component_id)
+                            .is_some_and(|ticks| ticks.is_changed(base_tick, this_run));
+                        if !changed {
+                            continue;
+                        }
+                    }
+                    if let Some(value) = reflect_component.reflect(entity) {
+                        captured
+                            .components
+                            .push((type_id, clone_reflect(value, registry)));
+                    }
+                }
+                if let Some(base) = base {
+                    captured.removed = base
+                        .into_iter()
+                        .filter(|type_id| {
+                            !components.iter().any(|(_, present, _)| present == type_id)
+                        })
+                        .collect();
+                    if captured.components.is_empty() && captured.removed.is_empty() {
+                        continue;
+                    }
+                }
+                self.entities.insert(entity.id(), captured);
+            }
+        }
+    }
+
+    /// Captures every resource, or only the ones changed or removed since `base`.
+    fn capture_resources(
+        &mut self,
+        world: &World,
+        registry: &TypeRegistry,
+        base: Option<&WorldSnapshot>,
+    ) {
+        let this_run = world.read_change_tick();
+        for registration in registry.iter() {
+            let type_id = registration.type_id();
+            let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+                continue;
+            };
+            if !self.filter.is_resource_allowed(type_id) {
+                continue;
+            }
+            let Some(value) = reflect_resource.reflect(world) else {
+                if base.is_some_and(|base| base.resources.contains_key(&type_id)) {
+                    self.removed_resources.push(type_id);
+                }
+                continue;
+            };
+            if let Some(base) = base {
+                let unchanged = world
+                    .components()
+                    .get_resource_id(type_id)
+                    .and_then(|component_id| world.get_resource_change_ticks_by_id(component_id))
+                    .is_some_and(|ticks| !ticks.is_changed(base.tick, this_run));
+                if unchanged && base.resources.contains_key(&type_id) {
+                    continue;
+                }
+            }
+            self.resources
+                .insert(type_id, clone_reflect(value, registry));
+        }
+    }
+
+    /// The change tick of the world when the snapshot was taken.
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Returns `true` if this snapshot only holds the changes since a base snapshot.
+    pub fn is_incremental(&self) -> bool {
+        self.base_tick.is_some()
+    }
+
+    /// The filter selecting the captured types.
+    pub fn filter(&self) -> &SnapshotFilter {
+        &self.filter
+    }
+
+    /// The number of entities with captured components.
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if the snapshot holds components of the `entity`.
+    pub fn contains_entity(&self, entity: Entity) -> bool {
+        self.entities.contains_key(&entity)
+    }
+
+    /// Returns the captured component of type `T` of the `entity`, if any.
+    pub fn get<T: Component + Reflect>(&self, entity: Entity) -> Option<&T> {
+        self.entities
+            .get(&entity)?
+            .components
+            .iter()
+            .find(|(type_id, _)| *type_id == TypeId::of::<T>())?
+            .1
+            .downcast_ref()
+    }
+
+    /// Applies the incremental snapshot `changes` to this snapshot, which then holds the state
+    /// at the time `changes` was taken.
+    ///
+    pub fn merge(&mut self, changes: WorldSnapshot) {
+        for entity in changes.despawned {
+            self.entities.remove(&entity);
+        }
+        for (entity, changed) in changes.entities {
+            let captured = self.entities.entry(entity).or_default();
+            captured
+                .components
+                .retain(|(type_id, _)| !changed.removed.contains(type_id));
+            for (type_id, value) in changed.components {
+                match captured
+                    .components
+                    .iter_mut()
+                    .find(|(captured_type, _)| *captured_type == type_id)
+                {
+                    Some((_, captured_value)) => *captured_value = value,
+                    None => captured.components.push((type_id, value)),
+                }
+            }
+        }
+        for type_id in changes.removed_resources {
+            self.resources.remove(&type_id);
+        }
+        self.resources.extend(changes.resources);
+        self.tick = changes.tick;
+    }
+
+    /// Restores the captured state in the `world`, returning the map from the captured id of
+    /// respawned entities to their new id.
+    ///
+    /// Captured components and resources are applied, and the captured types that weren't
+    /// present when the snapshot was taken are removed. Entities that didn't exist when a full
+    /// snapshot capturing every entity was taken are despawned.
+    ///
+    /// Incremental snapshots only restore what they captured: the `world` should be in the state
+    /// of their base.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the world has no [`AppTypeRegistry`].
+    pub fn restore(&self, world: &mut World) -> EntityHashMap<Entity> {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let mut respawned = EntityHashMap::default();
+
+        if self.is_incremental() {
+            for &entity in &self.despawned {
+                if world.get_entity(entity).is_some() {
+                    world.despawn(entity);
+                }
+            }
+        } else if self.filter.captures_all_entities() {
+            let spawned: Vec<Entity> = world
+                .iter_entities()
+                .map(|entity| entity.id())
+                .filter(|entity| !self.entities.contains_key(entity))
+                .collect();
+            for entity in spawned {
+                world.despawn(entity);
+            }
+        }
+
+        for (&entity, captured) in &self.entities {
+            let target = if world.get_entity(entity).is_some() {
+                entity
+            } else {
+                let new_entity = world.spawn_empty().id();
+                respawned.insert(entity, new_entity);
+                new_entity
+            };
+
+            let removed: Vec<TypeId> = if self.is_incremental() {
+                captured.removed.clone()
+            } else {
+                // Every captured type missing from the snapshot was added after it was taken.
+                world
+                    .entity(target)
+                    .archetype()
+                    .components()
+                    .filter_map(|component_id| world.components().get_info(component_id)?.type_id())
+                    .filter(|&type_id| {
+                        self.filter.is_component_allowed(type_id)
+                            && !captured
+                                .components
+                                .iter()
+                                .any(|(captured_type, _)| *captured_type == type_id)
+                    })
+                    .collect()
+            };
+            let mut entity_mut = world.entity_mut(target);
+            for type_id in removed {
+                if let Some(reflect_component) = registry.get_type_data::<ReflectComponent>(type_id)
+                {
+                    reflect_component.remove(&mut entity_mut);
+                }
+            }
+
+            for (type_id, value) in &captured.components {
+                if let Some(reflect_component) =
+                    registry.get_type_data::<ReflectComponent>(*type_id)
+                {
+                    reflect_component.apply_or_insert(&mut entity_mut, &**value, &registry);
+                }
+            }
+        }
+
+        self.restore_resources(world, &registry);
+        respawned
+    }
+
+    fn restore_resources(&self, world: &mut World, registry: &TypeRegistry) {
+        for registration in registry.iter() {
+            let type_id = registration.type_id();
+            let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+                continue;
+            };
+            if !self.filter.is_resource_allowed(type_id) {
+                continue;
+            }
+            match self.resources.get(&type_id) {
+                Some(value) => reflect_resource.apply_or_insert(world, &**value, registry),
+                None if !self.is_incremental() || self.removed_resources.contains(&type_id) => {
+                    reflect_resource.remove(world);
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_reflect::Reflect;
+
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::reflect::{
+        AppTypeRegistry, ReflectComponent, ReflectResource, SnapshotFilter, WorldSnapshot,
+    };
+
+    #[derive(Component, Reflect, Clone, Copy, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Position(f32);
+
+    #[derive(Component, Reflect, Clone, Copy, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Velocity(f32);
+
+    #[derive(Resource, Reflect, PartialEq, Debug)]
+    #[reflect(Resource)]
+    struct Frame(u32);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let mut registry = world.resource::<AppTypeRegistry>().write();
+            registry.register::<Position>();
+            registry.register::<Velocity>();
+            registry.register::<Frame>();
+        }
+        world
+    }
+
+    #[test]
+    fn restore_full_snapshot() {
+        let mut world = world();
+        let a = world.spawn((Position(0.0), Velocity(1.0))).id();
+        let b = world.spawn(Position(5.0)).id();
+        world.insert_resource(Frame(0));
+
+        let snapshot = WorldSnapshot::capture(&world);
+        assert_eq!(snapshot.get::<Position>(b), Some(&Position(5.0)));
+
+        world
+            .entity_mut(a)
+            .insert(Position(1.0))
+            .remove::<Velocity>();
+        world.entity_mut(b).insert(Velocity(2.0));
+        world.despawn(b);
+        let spawned = world.spawn(Position(9.0)).id();
+        world.insert_resource(Frame(10));
+
+        let respawned = snapshot.restore(&mut world);
+        assert_eq!(world.get::<Position>(a), Some(&Position(0.0)));
+        assert_eq!(world.get::<Velocity>(a), Some(&Velocity(1.0)));
+        assert!(world.get_entity(spawned).is_none());
+        let b = respawned[&b];
+        assert_eq!(world.get::<Position>(b), Some(&Position(5.0)));
+        assert_eq!(world.get::<Velocity>(b), None);
+        assert_eq!(world.resource::<Frame>(), &Frame(0));
+        assert_eq!(world.entities().len(), 2);
+    }
+
+    #[test]
+    fn restore_partial_snapshot() {
+        let mut world = world();
+        let a = world.spawn((Position(0.0), Velocity(1.0))).id();
+        world.insert_resource(Frame(0));
+
+        let filter = SnapshotFilter::all()
+            .allow_component::<Position>()
+            .deny_resource::<Frame>();
+        let snapshot = WorldSnapshot::capture_filtered(&world, filter);
+
+        world.entity_mut(a).insert((Position(1.0), Velocity(2.0)));
+        let spawned = world.spawn(Velocity(3.0)).id();
+        world.insert_resource(Frame(1));
+
+        snapshot.restore(&mut world);
+        assert_eq!(world.get::<Position>(a), Some(&Position(0.0)));
+        assert_eq!(world.get::<Velocity>(a), Some(&Velocity(2.0)));
+        assert!(world.get_entity(spawned).is_some());
+        assert_eq!(world.resource::<Frame>(), &Frame(1));
+    }
+
+    #[test]
+    fn incremental_snapshots() {
+        let mut world = world();
+        let a = world.spawn((Position(0.0), Velocity(1.0))).id();
+        let b = world.spawn(Position(5.0)).id();
+        let c = world.spawn(Position(7.0)).id();
+        world.insert_resource(Frame(0));
+        let mut base = WorldSnapshot::capture(&world);
+
+        world.get_mut::<Position>(a).unwrap().0 = 1.0;
+        world.entity_mut(b).remove::<Position>();
+        world.despawn(c);
+        let d = world.spawn(Velocity(3.0)).id();
+        let changes = WorldSnapshot::capture_changes(&world, &base);
+        assert!(changes.is_incremental());
+        // Only the changed component of `a`, the removal on `b` and the new `d` are captured.
+        assert_eq!(changes.entity_count(), 3);
+        assert_eq!(changes.get::<Position>(a), Some(&Position(1.0)));
+        assert_eq!(changes.get::<Velocity>(a), None);
+
+        base.merge(changes);
+        assert_eq!(base.entity_count(), 3);
+        assert!(!base.contains_entity(c));
+
+        world.get_mut::<Position>(a).unwrap().0 = 2.0;
+        world.insert_resource(Frame(1));
+        let changes = WorldSnapshot::capture_changes(&world, &base);
+        assert_eq!(changes.entity_count(), 1);
+
+        // Rolling back to the merged state undoes the last frame.
+        base.restore(&mut world);
+        assert_eq!(world.get::<Position>(a), Some(&Position(1.0)));
+        assert_eq!(world.get::<Position>(b), None);
+        assert_eq!(world.get::<Velocity>(d), Some(&Velocity(3.0)));
+        assert_eq!(world.resource::<Frame>(), &Frame(0));
+
+        // Restoring the changes replays it.
+        changes.restore(&mut world);
+        assert_eq!(world.get::<Position>(a), Some(&Position(2.0)));
+        assert_eq!(world.resource::<Frame>(), &Frame(1));
+    }
+}
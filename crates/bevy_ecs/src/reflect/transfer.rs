@@ -0,0 +1,290 @@
+use std::any::TypeId;
+
+use bevy_reflect::TypeRegistry;
+use bevy_utils::HashSet;
+use thiserror::Error;
+
+use crate::{
+    entity::{Entity, EntityHashMap},
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+    relationship::Relationship,
+    world::{EntityWorldMut, World},
+};
+
+/// The error returned when cloning or moving entities to another [`World`] fails.
+///
+/// The destination world is left untouched when this is returned.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum EntityTransferError {
+    /// The source world has no [`AppTypeRegistry`] resource.
+    #[error("The source world has no AppTypeRegistry resource")]
+    MissingTypeRegistry,
+    /// A component of an entity isn't registered with [`ReflectComponent`].
+    #[error(
+        "The component {component} of entity {entity:?} isn't registered with ReflectComponent"
+    )]
+    NotReflected {
+        /// The entity holding the component.
+        entity: Entity,
+        /// The name of the component.
+        component: String,
+    },
+}
+
+impl<'w> EntityWorldMut<'w> {
+    /// Clones this entity into the `destination` world, returning the new entity.
+    ///
+    /// Components are cloned through reflection, using the [`AppTypeRegistry`] of the source
+    /// world: every component of the entity must be registered with [`ReflectComponent`].
+    /// Components registered with [`ReflectMapEntities`] have the [`Entity`] they reference
+    /// remapped to the destination world. References to entities that aren't cloned are mapped
+    /// to entities that don't exist in the destination world, like when spawning scenes.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, reflect::AppTypeRegistry};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Component, Reflect, Debug, PartialEq)]
+    /// #[reflect(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut server = World::new();
+    /// server.init_resource::<AppTypeRegistry>();
+    /// server.resource::<AppTypeRegistry>().write().register::<Health>();
+    /// let mut client = World::new();
+    ///
+    /// let entity = server.spawn(Health(10)).id();
+    /// let clone = server.entity_mut(entity).clone_to(&mut client).unwrap();
+    /// assert_eq!(client.get::<Health>(clone), Some(&Health(10)));
+    /// ```
+    pub fn clone_to(&self, destination: &mut World) -> Result<Entity, EntityTransferError> {
+        let entity = self.id();
+        let map = clone_entities(self.world(), destination, &[entity])?;
+        Ok(map[&entity])
+    }
+
+    /// Moves this entity into the `destination` world, returning the new entity.
+    ///
+    /// This clones the entity like [`clone_to`](Self::clone_to), then despawns it from the source
+    /// world if cloning succeeded. References to it from other entities of the source world
+    /// aren't updated.
+    pub fn move_to(self, destination: &mut World) -> Result<Entity, EntityTransferError> {
+        let entity = self.clone_to(destination)?;
+        self.despawn();
+        Ok(entity)
+    }
+
+    /// Clones this entity and the entities reachable from it through the relationship `R`, such
+    /// as its descendants, into the `destination` world.
+    ///
+    /// Returns the map from the cloned entities to their clones. The references between the
+    /// cloned entities, including the relationship, are remapped if their components are
+    /// registered with [`ReflectMapEntities`]. See [`clone_to`](Self::clone_to) for details.
+    pub fn clone_related_to<R: Relationship>(
+        &self,
+        destination: &mut World,
+    ) -> Result<EntityHashMap<Entity>, EntityTransferError> {
+        let entities = collect_related::<R>(self.world(), self.id());
+        clone_entities(self.world(), destination, &entities)
+    }
+
+    /// Moves this entity and the entities reachable from it through the relationship `R`, such as
+    /// its descendants, into the `destination` world.
+    ///
+    /// This clones the entities like [`clone_related_to`](Self::clone_related_to), then despawns
+    /// them from the source world if cloning succeeded. References to them from other entities of
+    /// the source world aren't updated.
+    pub fn move_related_to<R: Relationship>(
+        self,
+        destination: &mut World,
+    ) -> Result<EntityHashMap<Entity>, EntityTransferError> {
+        let map = self.clone_related_to::<R>(destination)?;
+        let world = self.into_world_mut();
+        for &entity in map.keys() {
+            world.despawn(entity);
+        }
+        Ok(map)
+    }
+}
+
+/// Returns `root` and the entities reachable from it through `R`, in breadth-first order.
+fn collect_related<R: Relationship>(world: &World, root: Entity) -> Vec<Entity> {
+    let mut visited = HashSet::from([root]);
+    let mut entities = vec![root];
+    let mut next = 0;
+    while let Some(&entity) = entities.get(next) {
+        next += 1;
+        let Some(relationship) = world.get::<R>(entity) else {
+            continue;
+        };
+        for &related in relationship.related() {
+            if world.get_entity(related).is_some() && visited.insert(related) {
+                entities.push(related);
+            }
+        }
+    }
+    entities
+}
+
+fn clone_entities(
+    source: &World,
+    destination: &mut World,
+    entities: &[Entity],
+) -> Result<EntityHashMap<Entity>, EntityTransferError> {
+    let registry = source
+        .get_resource::<AppTypeRegistry>()
+        .ok_or(EntityTransferError::MissingTypeRegistry)?
+        .clone();
+    let registry = registry.read();
+
+    // Check every component before spawning anything, to leave the destination untouched on error.
+    let mut components = Vec::with_capacity(entities.len());
+    let mut mapped_types = HashSet::new();
+    for &entity in entities {
+        let entity_ref = source.entity(entity);
+        let mut reflect_components = Vec::new();
+        for id in entity_ref.archetype().components() {
+            let info = source.components().get_info(id).unwrap();
+            let not_reflected = || EntityTransferError::NotReflected {
+                entity,
+                component: info.name().to_string(),
+            };
+            let type_id = info.type_id().ok_or_else(not_reflected)?;
+            let registration = registry.get(type_id).ok_or_else(not_reflected)?;
+            let reflect_component = registration
+                .data::<ReflectComponent>()
+                .ok_or_else(not_reflected)?;
+            if registration.data::<ReflectMapEntities>().is_some() {
+                mapped_types.insert(type_id);
+            }
+            reflect_components.push(reflect_component);
+        }
+        components.push(reflect_components);
+    }
+
+    let mut map = EntityHashMap::default();
+    for &entity in entities {
+        map.insert(entity, destination.spawn_empty().id());
+    }
+    for (&entity, reflect_components) in entities.iter().zip(components) {
+        for reflect_component in reflect_components {
+            reflect_component.copy(source, destination, entity, map[&entity], &registry);
+        }
+    }
+    remap_entities(destination, &registry, &map, &mapped_types);
+    Ok(map)
+}
+
+fn remap_entities(
+    destination: &mut World,
+    registry: &TypeRegistry,
+    map: &EntityHashMap<Entity>,
+    mapped_types: &HashSet<TypeId>,
+) {
+    let clones: Vec<Entity> = map.values().copied().collect();
+    // Mapping references to entities that weren't cloned adds them to the map, keep them out of
+    // the returned one.
+    let mut mapper_map = map.clone();
+    for &type_id in mapped_types {
+        let map_entities = registry
+            .get_type_data::<ReflectMapEntities>(type_id)
+            .unwrap();
+        map_entities.map_entities(destination, &mut mapper_map, &clones);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EntityTransferError;
+    use crate::{
+        self as bevy_ecs,
+        component::Component,
+        entity::{Entity, EntityMapper, MapEntities},
+        reflect::{AppTypeRegistry, ReflectComponent, ReflectMapEntities},
+        relationship::Relationship,
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component, MapEntities)]
+    struct Children(Vec<Entity>);
+
+    impl MapEntities for Children {
+        fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
+            for entity in &mut self.0 {
+                *entity = entity_mapper.map_entity(*entity);
+            }
+        }
+    }
+
+    impl Relationship for Children {
+        fn related(&self) -> &[Entity] {
+            &self.0
+        }
+    }
+
+    #[derive(Component)]
+    struct NotReflected;
+
+    fn source_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let mut registry = registry.write();
+        registry.register::<Name>();
+        registry.register::<Children>();
+        world
+    }
+
+    #[test]
+    fn move_related_to() {
+        let mut source = source_world();
+        let leaf = source.spawn(Name("leaf".into())).id();
+        let branch = source
+            .spawn((Name("branch".into()), Children(vec![leaf])))
+            .id();
+        let root = source
+            .spawn((Name("root".into()), Children(vec![branch, leaf])))
+            .id();
+        let unrelated = source.spawn(Name("unrelated".into())).id();
+
+        let mut destination = World::new();
+        destination.spawn(Name("existing".into()));
+        let map = source
+            .entity_mut(root)
+            .move_related_to::<Children>(&mut destination)
+            .unwrap();
+
+        assert_eq!(map.len(), 3);
+        assert!(source.get_entity(root).is_none());
+        assert!(source.get_entity(leaf).is_none());
+        assert!(source.get_entity(unrelated).is_some());
+        let children = destination.get::<Children>(map[&root]).unwrap();
+        assert_eq!(children.0, vec![map[&branch], map[&leaf]]);
+        let children = destination.get::<Children>(map[&branch]).unwrap();
+        assert_eq!(children.0, vec![map[&leaf]]);
+        assert_eq!(
+            destination.get::<Name>(map[&leaf]),
+            Some(&Name("leaf".into()))
+        );
+    }
+
+    #[test]
+    fn clone_to_checks_components_first() {
+        let mut source = source_world();
+        let entity = source.spawn((Name("a".into()), NotReflected)).id();
+        let mut destination = World::new();
+
+        let result = source.entity_mut(entity).clone_to(&mut destination);
+        assert!(matches!(
+            result,
+            Err(EntityTransferError::NotReflected { entity: e, .. }) if e == entity
+        ));
+        assert_eq!(destination.entities().len(), 0);
+        assert!(source.get::<Name>(entity).is_some());
+    }
+}
@@ -0,0 +1,365 @@
+//! Traversal of relationships between entities in queries.
+//!
+//! A [`Relationship`] is a component pointing from an entity to related entities, like the
+//! parent or the children of an entity in a hierarchy. The [`Related`] query data walks a
+//! relationship from each queried entity, up to a number of hops given by a [`TraversalDepth`]:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::relationship::{Depth, Related, Relationship};
+//! #[derive(Component)]
+//! struct ChildOf(Entity);
+//!
+//! impl Relationship for ChildOf {
+//!     fn related(&self) -> &[Entity] {
+//!         std::slice::from_ref(&self.0)
+//!     }
+//! }
+//!
+//! #[derive(Component)]
+//! struct Name(&'static str);
+//!
+//! let mut world = World::new();
+//! let root = world.spawn(Name("root")).id();
+//! let branch = world.spawn((Name("branch"), ChildOf(root))).id();
+//! world.spawn((Name("leaf"), ChildOf(branch)));
+//!
+//! // Visits the parent and the grandparent of each entity.
+//! let mut query = world.query::<(&Name, Related<ChildOf, Depth<2>>)>();
+//! for (name, ancestors) in query.iter(&world) {
+//!     if name.0 == "leaf" {
+//!         assert_eq!(ancestors.collect::<Vec<_>>(), vec![branch, root]);
+//!     }
+//! }
+//! ```
+//!
+//! The related entities aren't known before the query runs, so [`Related`] reads the
+//! relationship component on every entity of the world: systems writing it can't run in parallel
+//! with systems traversing it, even if their queries are disjoint.
+
+use std::{fmt, marker::PhantomData, slice};
+
+use crate::{
+    archetype::Archetype,
+    component::{Component, ComponentId, Components, Tick},
+    entity::Entity,
+    query::{Access, FilteredAccess, QueryData, ReadOnlyQueryData, WorldQuery},
+    storage::{Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// A component pointing from an entity to related entities, walked by [`Related`].
+pub trait Relationship: Component {
+    /// The entities related to the entity holding this component.
+    fn related(&self) -> &[Entity];
+}
+
+/// The maximum number of hops walked by [`Related`].
+pub trait TraversalDepth: Send + Sync + 'static {
+    /// The maximum number of hops.
+    const MAX: usize;
+}
+
+/// Walks at most `N` hops of a relationship.
+pub struct Depth<const N: usize>;
+
+impl<const N: usize> TraversalDepth for Depth<N> {
+    const MAX: usize = N;
+}
+
+/// Walks a relationship until no entity is left.
+///
+/// Walking a relationship with cycles without a bounded [`Depth`] never ends.
+pub struct Unbounded;
+
+impl TraversalDepth for Unbounded {
+    const MAX: usize = usize::MAX;
+}
+
+/// [`QueryData`] walking the relationship `R` from each queried entity, up to `D` hops, without
+/// manual recursion.
+///
+/// The item is a [`RelatedIter`] over the entities reached, in depth-first pre-order: with
+/// a relationship to the parent it returns the ancestors from the closest, and with a
+/// relationship to the children it returns the descendants.
+///
+/// Entities without `R` are matched, and return an empty iterator.
+///
+/// See the [module documentation](self) for an example.
+pub struct Related<R: Relationship, D: TraversalDepth = Unbounded>(PhantomData<(R, D)>);
+
+#[doc(hidden)]
+pub struct RelatedFetch<'w> {
+    world: UnsafeWorldCell<'w>,
+    component_id: ComponentId,
+}
+
+impl Clone for RelatedFetch<'_> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl Copy for RelatedFetch<'_> {}
+
+/// SAFETY:
+/// `update_component_access` adds a read of `R`, and `update_traversal_access` adds a read of `R`
+/// on every archetype, which covers every entity reached by `fetch`.
+/// This is sound because `fetch` only reads `R`.
+unsafe impl<R: Relationship, D: TraversalDepth> WorldQuery for Related<R, D> {
+    type Item<'w> = RelatedIter<'w, R, D>;
+    type Fetch<'w> = RelatedFetch<'w>;
+    type State = ComponentId;
+
+    fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+        item
+    }
+
+    #[inline]
+    unsafe fn init_fetch<'w>(
+        world: UnsafeWorldCell<'w>,
+        &component_id: &ComponentId,
+        _last_run: Tick,
+        _this_run: Tick,
+    ) -> Self::Fetch<'w> {
+        RelatedFetch {
+            world,
+            component_id,
+        }
+    }
+
+    const IS_DENSE: bool = true;
+
+    #[inline]
+    unsafe fn set_archetype<'w>(
+        _fetch: &mut Self::Fetch<'w>,
+        _state: &ComponentId,
+        _archetype: &'w Archetype,
+        _table: &'w Table,
+    ) {
+    }
+
+    #[inline]
+    unsafe fn set_table<'w>(_fetch: &mut Self::Fetch<'w>, _state: &ComponentId, _table: &'w Table) {
+    }
+
+    #[inline]
+    unsafe fn fetch<'w>(
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        _table_row: TableRow,
+    ) -> Self::Item<'w> {
+        let mut iter = RelatedIter {
+            fetch: *fetch,
+            stack: Vec::new(),
+            marker: PhantomData,
+        };
+        if D::MAX > 0 {
+            if let Some(related) = iter.related(entity) {
+                iter.stack.push(related.iter());
+            }
+        }
+        iter
+    }
+
+    fn update_component_access(
+        &component_id: &ComponentId,
+        access: &mut FilteredAccess<ComponentId>,
+    ) {
+        assert!(
+            !access.access().has_write(component_id),
+            "Related<{}> conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+            std::any::type_name::<R>(),
+        );
+        // Entities without `R` are matched too, so this doesn't add a `With<R>` filter.
+        access.access_mut().add_read(component_id);
+    }
+
+    fn update_traversal_access(&component_id: &ComponentId, access: &mut Access<ComponentId>) {
+        access.add_read(component_id);
+    }
+
+    fn init_state(world: &mut World) -> ComponentId {
+        world.init_component::<R>()
+    }
+
+    fn get_state(components: &Components) -> Option<ComponentId> {
+        components.component_id::<R>()
+    }
+
+    fn matches_component_set(
+        _state: &ComponentId,
+        _set_contains_id: &impl Fn(ComponentId) -> bool,
+    ) -> bool {
+        true
+    }
+}
+
+/// SAFETY: `Self` is the same as `Self::ReadOnly`
+unsafe impl<R: Relationship, D: TraversalDepth> QueryData for Related<R, D> {
+    type ReadOnly = Self;
+}
+
+/// SAFETY: access is read only
+unsafe impl<R: Relationship, D: TraversalDepth> ReadOnlyQueryData for Related<R, D> {}
+
+/// The entities reached by walking a relationship, returned by [`Related`].
+pub struct RelatedIter<'w, R: Relationship, D: TraversalDepth = Unbounded> {
+    fetch: RelatedFetch<'w>,
+    /// The related entities left to visit at each hop.
+    stack: Vec<slice::Iter<'w, Entity>>,
+    marker: PhantomData<fn() -> (R, D)>,
+}
+
+impl<'w, R: Relationship, D: TraversalDepth> RelatedIter<'w, R, D> {
+    /// The number of hops from the queried entity to the entity returned last.
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    fn related(&self, entity: Entity) -> Option<&'w [Entity]> {
+        let component = self.fetch.world.get_entity(entity)?;
+        // SAFETY: `Related` registered a read of `R` on every archetype, and no write of `R`.
+        let relationship = unsafe { component.get_by_id(self.fetch.component_id)? };
+        // SAFETY: the component with this id is an `R`.
+        let relationship = unsafe { relationship.deref::<R>() };
+        Some(relationship.related())
+    }
+}
+
+impl<'w, R: Relationship, D: TraversalDepth> Iterator for RelatedIter<'w, R, D> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        loop {
+            let Some(entity) = self.stack.last_mut()?.next().copied() else {
+                self.stack.pop();
+                continue;
+            };
+            if self.stack.len() < D::MAX {
+                if let Some(related) = self.related(entity) {
+                    self.stack.push(related.iter());
+                }
+            }
+            return Some(entity);
+        }
+    }
+}
+
+impl<R: Relationship, D: TraversalDepth> fmt::Debug for RelatedIter<'_, R, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RelatedIter")
+            .field("relationship", &std::any::type_name::<R>())
+            .field("depth", &self.stack.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_ecs, prelude::*, schedule::Schedule};
+
+    #[derive(Component)]
+    struct ChildOf(Entity);
+
+    impl Relationship for ChildOf {
+        fn related(&self) -> &[Entity] {
+            slice::from_ref(&self.0)
+        }
+    }
+
+    #[derive(Component, Default)]
+    struct Children(Vec<Entity>);
+
+    impl Relationship for Children {
+        fn related(&self) -> &[Entity] {
+            &self.0
+        }
+    }
+
+    #[derive(Component)]
+    struct A;
+
+    /// Spawns `root -> (a -> (c -> d), b)`.
+    fn spawn_tree(world: &mut World) -> [Entity; 5] {
+        let [root, a, b, c, d] = [(); 5].map(|_| world.spawn_empty().id());
+        for (parent, children) in [(root, vec![a, b]), (a, vec![c]), (c, vec![d])] {
+            for &child in &children {
+                world.entity_mut(child).insert(ChildOf(parent));
+            }
+            world.entity_mut(parent).insert(Children(children));
+        }
+        [root, a, b, c, d]
+    }
+
+    #[test]
+    fn ancestors_up_to_depth() {
+        let mut world = World::new();
+        let [root, a, _, c, d] = spawn_tree(&mut world);
+
+        let mut query = world.query::<Related<ChildOf, Depth<2>>>();
+        assert_eq!(query.get(&world, d).unwrap().collect::<Vec<_>>(), [c, a]);
+        assert_eq!(query.get(&world, a).unwrap().collect::<Vec<_>>(), [root]);
+        assert_eq!(query.get(&world, root).unwrap().count(), 0);
+
+        let mut query = world.query::<Related<ChildOf>>();
+        assert_eq!(
+            query.get(&world, d).unwrap().collect::<Vec<_>>(),
+            [c, a, root]
+        );
+    }
+
+    #[test]
+    fn descendants_in_pre_order() {
+        let mut world = World::new();
+        let [root, a, b, c, d] = spawn_tree(&mut world);
+
+        let mut query = world.query::<Related<Children>>();
+        assert_eq!(
+            query.get(&world, root).unwrap().collect::<Vec<_>>(),
+            [a, c, d, b]
+        );
+
+        let mut query = world.query::<Related<Children, Depth<2>>>();
+        assert_eq!(
+            query.get(&world, root).unwrap().collect::<Vec<_>>(),
+            [a, c, b]
+        );
+
+        let mut query = world.query::<Related<Children, Depth<0>>>();
+        assert_eq!(query.get(&world, root).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn traversal_reads_every_archetype() {
+        let mut world = World::new();
+        spawn_tree(&mut world);
+        world.spawn((A, ChildOf(Entity::PLACEHOLDER)));
+
+        let state = world.query_filtered::<Entity, (With<A>, Without<ChildOf>)>();
+        let mut traversing = world.query::<(&A, Related<ChildOf>)>();
+        let child_of = world.component_id::<ChildOf>().unwrap();
+        assert!(traversing.traversal_access().has_read(child_of));
+        assert!(!state.traversal_access().has_any_read());
+        assert_eq!(traversing.iter(&world).count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn traversal_conflicts_with_disjoint_write() {
+        fn system(_: Query<(&A, Related<ChildOf>)>, _: Query<&mut ChildOf, Without<A>>) {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(&mut world);
+    }
+
+    #[test]
+    #[should_panic]
+    fn traversal_conflicts_with_write_in_query() {
+        let mut world = World::new();
+        world.query::<(Related<ChildOf>, &mut ChildOf)>();
+    }
+}
@@ -2,14 +2,16 @@
 
 use crate::{
     self as bevy_ecs,
+    bundle::Bundle,
     component::{Component, ComponentId, ComponentIdFor, Tick},
     entity::Entity,
     event::{Event, EventId, EventIterator, EventIteratorWithId, Events, ManualEventReader},
     prelude::Local,
     storage::SparseSet,
     system::{ReadOnlySystemParam, SystemMeta, SystemParam},
-    world::{unsafe_world_cell::UnsafeWorldCell, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, FromWorld, World},
 };
+use bevy_utils::HashSet;
 
 use std::{
     fmt::Debug,
@@ -21,8 +23,11 @@ use std::{
 
 /// Wrapper around [`Entity`] for [`RemovedComponents`].
 /// Internally, `RemovedComponents` uses these as an `Events<RemovedComponentEntity>`.
+///
+/// This also records the removal operation the event was sent by, so that [`RemovedBundle`] can
+/// tell apart components removed together from components removed one after the other.
 #[derive(Event, Debug, Clone)]
-pub struct RemovedComponentEntity(Entity);
+pub struct RemovedComponentEntity(Entity, u32);
 
 impl From<RemovedComponentEntity> for Entity {
     fn from(value: RemovedComponentEntity) -> Self {
@@ -67,6 +72,7 @@ impl<T: Component> DerefMut for RemovedComponentReader<T> {
 #[derive(Default, Debug)]
 pub struct RemovedComponentEvents {
     event_sets: SparseSet<ComponentId, Events<RemovedComponentEntity>>,
+    operation: u32,
 }
 
 impl RemovedComponentEvents {
@@ -97,10 +103,18 @@ impl RemovedComponentEvents {
     }
 
     /// Sends a removal event for the specified component.
+    ///
+    /// The events sent until the next call to [`begin_operation`](Self::begin_operation) are
+    /// considered to be part of the same removal operation by [`RemovedBundle`].
     pub fn send(&mut self, component_id: impl Into<ComponentId>, entity: Entity) {
         self.event_sets
             .get_or_insert_with(component_id.into(), Default::default)
-            .send(RemovedComponentEntity(entity));
+            .send(RemovedComponentEntity(entity, self.operation));
+    }
+
+    /// Starts a new removal operation, such as removing a bundle or despawning an entity.
+    pub fn begin_operation(&mut self) {
+        self.operation = self.operation.wrapping_add(1);
     }
 }
 
@@ -248,6 +262,108 @@ impl<'w, 's, T: Component> RemovedComponents<'w, 's, T> {
     }
 }
 
+/// A [`SystemParam`] that yields entities that had every component of the [`Bundle`] `B`
+/// removed in a single operation, such as [`EntityWorldMut::remove::<B>`] or despawning.
+///
+/// This aggregates the [`RemovedComponents`] streams of the components of `B`: entities that
+/// lost these components in separate operations, or only some of them, aren't yielded. Like
+/// [`RemovedComponents`], the events are only kept for two updates.
+///
+/// [`EntityWorldMut::remove::<B>`]: crate::world::EntityWorldMut::remove
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # #[derive(Component)]
+/// # struct Mesh;
+/// # #[derive(Component)]
+/// # struct Material;
+/// fn cleanup_renderables(mut removed: RemovedBundle<(Mesh, Material)>) {
+///     for entity in removed.read() {
+///         println!("{entity:?} isn't renderable anymore");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(cleanup_renderables);
+/// ```
+#[derive(SystemParam)]
+pub struct RemovedBundle<'w, 's, B: Bundle> {
+    reader: Local<'s, RemovedBundleReader<B>>,
+    event_sets: &'w RemovedComponentEvents,
+}
+
+/// The readers of the removal events of each component of the [`Bundle`] `B`, for
+/// [`RemovedBundle`].
+pub struct RemovedBundleReader<B: Bundle> {
+    component_ids: Vec<ComponentId>,
+    readers: Vec<ManualEventReader<RemovedComponentEntity>>,
+    marker: PhantomData<fn() -> B>,
+}
+
+impl<B: Bundle> FromWorld for RemovedBundleReader<B> {
+    fn from_world(world: &mut World) -> Self {
+        let component_ids = world.init_bundle::<B>().components().to_vec();
+        Self {
+            readers: component_ids.iter().map(|_| Default::default()).collect(),
+            component_ids,
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Iterator over entities that had a bundle removed.
+///
+/// See [`RemovedBundle`].
+pub type RemovedBundleIter = std::vec::IntoIter<Entity>;
+
+impl<'w, 's, B: Bundle> RemovedBundle<'w, 's, B> {
+    /// Iterates over the entities that had every component of `B` removed in a single operation
+    /// since the last read, in the order they were removed.
+    pub fn read(&mut self) -> RemovedBundleIter {
+        let RemovedBundleReader {
+            component_ids,
+            readers,
+            ..
+        } = &mut *self.reader;
+        let mut removals = component_ids.iter().zip(readers).map(|(&id, reader)| {
+            self.event_sets
+                .get(id)
+                .map(|events| {
+                    reader
+                        .read(events)
+                        .map(|event| (event.0, event.1))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        });
+        let Some(mut removed) = removals.next() else {
+            return Vec::new().into_iter();
+        };
+        // Read every stream, even once nothing is left, so that old events aren't read later.
+        for other in removals {
+            let other: HashSet<_> = other.into_iter().collect();
+            removed.retain(|removal| other.contains(removal));
+        }
+        removed
+            .into_iter()
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Consumes all available events.
+    pub fn clear(&mut self) {
+        let RemovedBundleReader {
+            component_ids,
+            readers,
+            ..
+        } = &mut *self.reader;
+        for (&id, reader) in component_ids.iter().zip(readers) {
+            if let Some(events) = self.event_sets.get(id) {
+                reader.clear(events);
+            }
+        }
+    }
+}
+
 // SAFETY: Only reads World removed component events
 unsafe impl<'a> ReadOnlySystemParam for &'a RemovedComponentEvents {}
 
@@ -0,0 +1,263 @@
+use std::{collections::BTreeSet, fmt, fs, io, path::Path};
+
+use thiserror::Error;
+
+/// A pair of systems with conflicting access and indeterminate order in a schedule, identified by
+/// name so that it can be stored and recognized across runs.
+///
+/// The systems are identified by their full type path, regardless of
+/// [`ScheduleBuildSettings::use_shortnames`](super::ScheduleBuildSettings::use_shortnames), and
+/// are sorted so that the order they were detected in doesn't matter.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AmbiguityKey {
+    schedule: String,
+    system_a: String,
+    system_b: String,
+}
+
+impl AmbiguityKey {
+    /// Creates the key of the ambiguity between the systems named `system_a` and `system_b` in
+    /// the schedule named `schedule`.
+    pub fn new(
+        schedule: impl Into<String>,
+        system_a: impl Into<String>,
+        system_b: impl Into<String>,
+    ) -> Self {
+        let (system_a, system_b) = (system_a.into(), system_b.into());
+        let (system_a, system_b) = if system_a <= system_b {
+            (system_a, system_b)
+        } else {
+            (system_b, system_a)
+        };
+        Self {
+            schedule: schedule.into(),
+            system_a,
+            system_b,
+        }
+    }
+
+    /// Returns the name of the schedule of the ambiguity.
+    pub fn schedule(&self) -> &str {
+        &self.schedule
+    }
+
+    /// Returns the names of the two systems of the ambiguity.
+    pub fn systems(&self) -> (&str, &str) {
+        (&self.system_a, &self.system_b)
+    }
+}
+
+impl fmt::Display for AmbiguityKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t{}\t{}", self.schedule, self.system_a, self.system_b)
+    }
+}
+
+/// A set of system order ambiguities, used to report the ambiguities of schedules and to
+/// suppress the ones that were reviewed.
+///
+/// Unlike [`Schedules::allow_ambiguous_component`](super::Schedules::allow_ambiguous_component),
+/// which ignores every conflict on a type, this suppresses individual pairs of systems. This lets
+/// large projects adopt [ambiguity detection](super::ScheduleBuildSettings::ambiguity_detection)
+/// incrementally: export the current ambiguities with [`Schedules::ambiguities`](super::Schedules::ambiguities) and
+/// [`save`](Self::save) them, review the file, then [`load`](Self::load) it on startup and pass it to
+/// [`Schedules::suppress_ambiguities`](super::Schedules::suppress_ambiguities). With
+/// [`LogLevel::Error`](super::LogLevel::Error), as typically configured in CI, only ambiguities
+/// that aren't in the file fail the build.
+///
+/// The file has one ambiguity per line, with the schedule and the two systems separated by tabs.
+/// Empty lines and lines starting with `#` are ignored.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::{LogLevel, ScheduleBuildSettings, ScheduleLabel};
+/// #[derive(Resource)]
+/// struct Score(u32);
+///
+/// fn count(mut score: ResMut<Score>) {}
+/// fn show(score: Res<Score>) {}
+///
+/// #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct Update;
+///
+/// fn new_world() -> World {
+///     let mut world = World::new();
+///     world.insert_resource(Score(0));
+///     let mut schedule = Schedule::new(Update);
+///     schedule.add_systems((count, show));
+///     world.add_schedule(schedule);
+///     world
+/// }
+///
+/// // run once and export the ambiguities to review them
+/// let mut world = new_world();
+/// world.run_schedule(Update);
+/// let ambiguities = world.resource::<Schedules>().ambiguities();
+/// assert_eq!(ambiguities.len(), 1);
+/// let file = ambiguities.to_string();
+///
+/// // in strict mode, the next runs only fail on ambiguities that aren't in the file
+/// let mut world = new_world();
+/// let mut schedules = world.resource_mut::<Schedules>();
+/// schedules.suppress_ambiguities(file.parse().unwrap());
+/// schedules.configure_schedules(ScheduleBuildSettings {
+///     ambiguity_detection: LogLevel::Error,
+///     ..Default::default()
+/// });
+/// world.run_schedule(Update);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AmbiguitySet {
+    keys: BTreeSet<AmbiguityKey>,
+}
+
+impl AmbiguitySet {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self {
+            keys: BTreeSet::new(),
+        }
+    }
+
+    /// Reads a set saved with [`save`](Self::save) from the file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadAmbiguitiesError> {
+        Ok(fs::read_to_string(path)?.parse()?)
+    }
+
+    /// Writes this set to the file at `path`, in the format read by [`load`](Self::load).
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// Adds an ambiguity to the set, returning `false` if it was already present.
+    pub fn insert(&mut self, key: AmbiguityKey) -> bool {
+        self.keys.insert(key)
+    }
+
+    /// Removes an ambiguity from the set, returning `true` if it was present.
+    pub fn remove(&mut self, key: &AmbiguityKey) -> bool {
+        self.keys.remove(key)
+    }
+
+    /// Returns `true` if the ambiguity is in the set.
+    pub fn contains(&self, key: &AmbiguityKey) -> bool {
+        self.keys.contains(key)
+    }
+
+    /// Adds all ambiguities of `other` to this set.
+    pub fn extend(&mut self, other: AmbiguitySet) {
+        self.keys.extend(other.keys);
+    }
+
+    /// Returns the ambiguities of this set that are not in `other`.
+    ///
+    /// Comparing the ambiguities of a build with the suppressed ones gives the ambiguities
+    /// introduced since the file was saved, and the other way around the suppressions that are
+    /// stale and can be removed.
+    pub fn difference<'a>(
+        &'a self,
+        other: &'a AmbiguitySet,
+    ) -> impl Iterator<Item = &'a AmbiguityKey> + 'a {
+        self.keys.difference(&other.keys)
+    }
+
+    /// Returns an iterator over the ambiguities of the set, sorted by schedule and system names.
+    pub fn iter(&self) -> impl Iterator<Item = &AmbiguityKey> + '_ {
+        self.keys.iter()
+    }
+
+    /// Returns the number of ambiguities in the set.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl FromIterator<AmbiguityKey> for AmbiguitySet {
+    fn from_iter<I: IntoIterator<Item = AmbiguityKey>>(iter: I) -> Self {
+        Self {
+            keys: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for AmbiguitySet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# schedule\tsystem\tsystem")?;
+        for key in &self.keys {
+            writeln!(f, "{key}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for AmbiguitySet {
+    type Err = ParseAmbiguitiesError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                let mut fields = line.split('\t');
+                match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                    (Some(schedule), Some(system_a), Some(system_b), None) => {
+                        Ok(AmbiguityKey::new(schedule, system_a, system_b))
+                    }
+                    _ => Err(ParseAmbiguitiesError { line: index + 1 }),
+                }
+            })
+            .collect()
+    }
+}
+
+/// An error returned when parsing [`AmbiguitySet`] from text.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Line {line} of the suppressed ambiguities is not of the form `schedule<TAB>system<TAB>system`.")]
+pub struct ParseAmbiguitiesError {
+    /// The line of the text that failed to parse, starting from 1.
+    pub line: usize,
+}
+
+/// An error returned by [`AmbiguitySet::load`].
+#[derive(Error, Debug)]
+pub enum LoadAmbiguitiesError {
+    /// The file couldn't be read.
+    #[error("Failed to read the suppressed ambiguities: {0}")]
+    Io(#[from] io::Error),
+    /// The file isn't in the expected format.
+    #[error(transparent)]
+    Parse(#[from] ParseAmbiguitiesError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suppressed_ambiguities_round_trip() {
+        let ambiguities: AmbiguitySet = [
+            AmbiguityKey::new("Update", "game::b", "game::a"),
+            AmbiguityKey::new("Update", "game::a", "game::b"),
+            AmbiguityKey::new("PostUpdate", "game::c", "game::d"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(ambiguities.len(), 2);
+
+        let text = ambiguities.to_string();
+        assert_eq!(text.parse(), Ok(ambiguities.clone()));
+
+        let with_comments = format!("# reviewed\n\n{text}");
+        assert_eq!(with_comments.parse(), Ok(ambiguities));
+
+        assert_eq!(
+            "Update\tgame::a".parse::<AmbiguitySet>(),
+            Err(ParseAmbiguitiesError { line: 1 })
+        );
+    }
+}
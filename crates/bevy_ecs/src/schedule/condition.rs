@@ -1,8 +1,14 @@
 use std::borrow::Cow;
 use std::ops::Not;
+use std::sync::{Arc, Mutex};
 
-use crate::system::{
-    Adapt, AdapterSystem, CombinatorSystem, Combine, IntoSystem, ReadOnlySystem, System,
+use crate::{
+    archetype::ArchetypeComponentId,
+    component::{ComponentId, Tick},
+    query::Access,
+    schedule::InternedSystemSet,
+    system::{Adapt, AdapterSystem, CombinatorSystem, Combine, IntoSystem, ReadOnlySystem, System},
+    world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
 };
 
 /// A type-erased run condition stored in a [`Box`].
@@ -244,6 +250,56 @@ pub mod common_conditions {
         }
     }
 
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// the first time the condition is run and then once every `n` times.
+    ///
+    /// In the `FixedUpdate` schedule, this runs a system every `n` fixed timesteps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource, Default)]
+    /// # struct Counter(u8);
+    /// # let mut app = Schedule::default();
+    /// # let mut world = World::new();
+    /// # world.init_resource::<Counter>();
+    /// app.add_systems(
+    ///     // `every_n_runs` will only return true every third time it's evaluated
+    ///     my_system.run_if(every_n_runs(3)),
+    /// );
+    ///
+    /// fn my_system(mut counter: ResMut<Counter>) {
+    ///     counter.0 += 1;
+    /// }
+    ///
+    /// // The first evaluation returns true
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// // The next two don't
+    /// app.run(&mut world);
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// // The fourth one does
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// ```
+    pub fn every_n_runs(n: u32) -> impl FnMut() -> bool + Clone {
+        assert!(n > 0, "`every_n_runs` requires `n` to be at least 1");
+        let mut runs = 0;
+        move || {
+            let run = runs == 0;
+            runs = (runs + 1) % n;
+            run
+        }
+    }
+
     /// A [`Condition`](super::Condition)-satisfying system that returns `true`
     /// if the resource exists.
     ///
@@ -654,6 +710,80 @@ pub mod common_conditions {
         }
     }
 
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
+    /// if the value extracted from the resource by `field` differs from the one extracted the last
+    /// time the condition was run.
+    ///
+    /// Unlike [`resource_changed`], this ignores changes to the other fields of the resource, and
+    /// writes that don't change the value. It returns `true` the first time it finds the
+    /// resource, and `false` while the resource doesn't exist. `field` is only called when the
+    /// resource was changed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource, Default)]
+    /// # struct Counter(u8);
+    /// # let mut app = Schedule::default();
+    /// # let mut world = World::new();
+    /// # world.init_resource::<Counter>();
+    /// #[derive(Resource, Default)]
+    /// struct Settings {
+    ///     volume: u8,
+    ///     language: String,
+    /// }
+    ///
+    /// app.add_systems(
+    ///     // `resource_field_changed` will only return true if the volume changed
+    ///     apply_volume.run_if(resource_field_changed(|settings: &Settings| settings.volume)),
+    /// );
+    ///
+    /// fn apply_volume(mut counter: ResMut<Counter>) {
+    ///     counter.0 += 1;
+    /// }
+    ///
+    /// world.init_resource::<Settings>();
+    ///
+    /// // The volume is read for the first time so `apply_volume` will run
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// // Only the language changed so `apply_volume` won't run
+    /// world.resource_mut::<Settings>().language = "fr".to_string();
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// // The volume changed so `apply_volume` will run
+    /// world.resource_mut::<Settings>().volume = 50;
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// ```
+    pub fn resource_field_changed<T, V>(
+        field: impl Fn(&T) -> V + Clone,
+    ) -> impl FnMut(Option<Res<T>>) -> bool + Clone
+    where
+        T: Resource,
+        V: PartialEq + Clone,
+    {
+        let mut previous: Option<V> = None;
+        move |res: Option<Res<T>>| {
+            let Some(res) = res else {
+                return false;
+            };
+            if previous.is_some() && !res.is_changed() {
+                return false;
+            }
+            let value = field(&res);
+            if previous.as_ref() == Some(&value) {
+                false
+            } else {
+                previous = Some(value);
+                true
+            }
+        }
+    }
+
     /// Generates a [`Condition`](super::Condition)-satisfying closure that returns `true`
     /// if there are any new events of the given type since it was last called.
     ///
@@ -698,6 +828,71 @@ pub mod common_conditions {
         move |mut reader: EventReader<T>| reader.read().count() > 0
     }
 
+    /// Generates a [`Condition`](super::Condition)-satisfying closure that backs off
+    /// exponentially while events of the given type are sent, such as the failures of the system
+    /// it runs.
+    ///
+    /// The closure returns `true` until an event is found. It then returns `false` once before
+    /// returning `true` again, and each further event found before a run without events doubles
+    /// the number of skipped runs, up to `max_skipped_runs`. A run without events resets the
+    /// backoff.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Resource, Default)]
+    /// # struct Counter(u8);
+    /// # let mut app = Schedule::default();
+    /// # let mut world = World::new();
+    /// # world.init_resource::<Counter>();
+    /// # world.init_resource::<Events<ConnectionFailed>>();
+    /// # app.add_systems(bevy_ecs::event::event_update_system.before(connect));
+    /// #[derive(Event)]
+    /// struct ConnectionFailed;
+    ///
+    /// app.add_systems(
+    ///     // `exponential_backoff` skips 1, 2, 4, then 8 runs after each failure
+    ///     connect.run_if(exponential_backoff::<ConnectionFailed>(8)),
+    /// );
+    ///
+    /// fn connect(mut counter: ResMut<Counter>, mut failed: EventWriter<ConnectionFailed>) {
+    ///     counter.0 += 1;
+    ///     failed.send(ConnectionFailed);
+    /// }
+    ///
+    /// // `connect` runs and fails, so the next run is skipped
+    /// app.run(&mut world);
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 1);
+    ///
+    /// // `connect` fails again, so the next two runs are skipped
+    /// app.run(&mut world);
+    /// app.run(&mut world);
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 2);
+    /// app.run(&mut world);
+    /// assert_eq!(world.resource::<Counter>().0, 3);
+    /// ```
+    pub fn exponential_backoff<T: Event>(
+        max_skipped_runs: u32,
+    ) -> impl FnMut(EventReader<T>) -> bool + Clone {
+        let mut skipped_runs = 0u32;
+        let mut remaining_skips = 0u32;
+        let mut ran = false;
+        move |mut reader: EventReader<T>| {
+            if reader.read().count() > 0 {
+                skipped_runs = skipped_runs.saturating_mul(2).max(1).min(max_skipped_runs);
+                remaining_skips = skipped_runs;
+            } else if ran {
+                skipped_runs = 0;
+            }
+            ran = remaining_skips == 0;
+            remaining_skips = remaining_skips.saturating_sub(1);
+            ran
+        }
+    }
+
     /// A [`Condition`](super::Condition)-satisfying system that returns `true`
     /// if there are any entities with the given component type.
     ///
@@ -786,6 +981,189 @@ pub mod common_conditions {
     }
 }
 
+/// A run condition shared by many systems or system sets, evaluated at most once per schedule run.
+///
+/// Every clone of a `SharedCondition` wraps the same condition: the first clone evaluated during a
+/// run of a schedule evaluates it, and the others reuse its result until the next schedule run
+/// starts. This avoids re-running the same check, such as `in_state`, for each of the hundreds of
+/// systems it gates.
+///
+/// The world accesses of the wrapped condition are reported by every clone, so the executor
+/// still takes them into account when deciding which systems can run in parallel.
+///
+/// Because the result is reused for the whole schedule run, systems gated by a shared condition
+/// won't see changes made to its inputs earlier in the same run. Use separate conditions for
+/// inputs that change within a schedule.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::SharedCondition;
+/// #[derive(Resource)]
+/// struct Paused(bool);
+///
+/// fn not_paused(paused: Res<Paused>) -> bool {
+///     !paused.0
+/// }
+///
+/// # fn physics() {}
+/// # fn ai() {}
+/// # fn animation() {}
+/// let running = SharedCondition::new(not_paused);
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems((
+///     physics.run_if(running.clone()),
+///     ai.run_if(running.clone()),
+///     animation.run_if(running),
+/// ));
+/// # let mut world = World::new();
+/// # world.insert_resource(Paused(false));
+/// # schedule.run(&mut world);
+/// ```
+pub struct SharedCondition {
+    name: Cow<'static, str>,
+    state: Arc<Mutex<SharedConditionState>>,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+struct SharedConditionState {
+    condition: BoxedCondition,
+    /// The world the condition was initialized for.
+    world: Option<WorldId>,
+    /// The schedule run of the last evaluation, and its result.
+    last_result: Option<(u64, bool)>,
+}
+
+impl SharedCondition {
+    /// Wraps `condition` so that it can be shared by several systems or system sets.
+    pub fn new<M>(condition: impl Condition<M>) -> Self {
+        let condition = IntoSystem::into_system(condition);
+        assert!(
+            condition.is_send(),
+            "Condition `{}` accesses `NonSend` resources. This is not currently supported.",
+            condition.name()
+        );
+        Self {
+            name: condition.name(),
+            state: Arc::new(Mutex::new(SharedConditionState {
+                condition: Box::new(condition),
+                world: None,
+                last_result: None,
+            })),
+            component_access: Access::new(),
+            archetype_component_access: Access::new(),
+        }
+    }
+
+    /// Returns `true` if `other` shares the same condition as `self`.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.state, &other.state)
+    }
+}
+
+impl Clone for SharedCondition {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            state: self.state.clone(),
+            component_access: self.component_access.clone(),
+            archetype_component_access: self.archetype_component_access.clone(),
+        }
+    }
+}
+
+impl System for SharedCondition {
+    type In = ();
+    type Out = bool;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        true
+    }
+
+    fn is_exclusive(&self) -> bool {
+        false
+    }
+
+    fn has_deferred(&self) -> bool {
+        self.state.lock().unwrap().condition.has_deferred()
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: (), world: UnsafeWorldCell) -> bool {
+        let state = &mut *self.state.lock().unwrap();
+        // SAFETY: only the world metadata is accessed.
+        let schedule_runs = unsafe { world.world_metadata() }.schedule_runs;
+        if let Some((run, result)) = state.last_result {
+            if run == schedule_runs {
+                return result;
+            }
+        }
+        // SAFETY: the caller updated the access of the shared condition, and copied it to
+        // `self.archetype_component_access`, right before this call.
+        let result = unsafe { state.condition.run_unsafe((), world) };
+        state.last_result = Some((schedule_runs, result));
+        result
+    }
+
+    fn apply_deferred(&mut self, world: &mut World) {
+        self.state.lock().unwrap().condition.apply_deferred(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        let state = &mut *self.state.lock().unwrap();
+        if state.world != Some(world.id()) {
+            state.condition.initialize(world);
+            state.world = Some(world.id());
+            state.last_result = None;
+        }
+        self.component_access = state.condition.component_access().clone();
+        self.archetype_component_access = state.condition.archetype_component_access().clone();
+    }
+
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        let state = &mut *self.state.lock().unwrap();
+        state.condition.update_archetype_component_access(world);
+        self.archetype_component_access
+            .extend(state.condition.archetype_component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        self.state
+            .lock()
+            .unwrap()
+            .condition
+            .check_change_tick(change_tick);
+    }
+
+    fn default_system_sets(&self) -> Vec<InternedSystemSet> {
+        self.state.lock().unwrap().condition.default_system_sets()
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.state.lock().unwrap().condition.get_last_run()
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.state.lock().unwrap().condition.set_last_run(last_run);
+    }
+}
+
+// SAFETY: The shared condition is read-only.
+unsafe impl ReadOnlySystem for SharedCondition {}
+
 /// Invokes [`Not`] with the output of another system.
 ///
 /// See [`common_conditions::not`] for examples.
@@ -858,7 +1236,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{common_conditions::*, Condition};
+    use super::{common_conditions::*, Condition, SharedCondition};
     use crate as bevy_ecs;
     use crate::component::Component;
     use crate::schedule::IntoSystemConfigs;
@@ -960,6 +1338,40 @@ mod tests {
         schedule.run(&mut world);
         assert_eq!(world.resource::<Counter>().0, 0);
     }
+    #[test]
+    fn shared_condition_runs_once_per_schedule_run() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let evaluations = Arc::new(AtomicUsize::new(0));
+        let evaluated = evaluations.clone();
+        let shared = SharedCondition::new(move |mut flag: Local<bool>| {
+            evaluated.fetch_add(1, Ordering::Relaxed);
+            *flag = !*flag;
+            *flag
+        });
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            increment_counter.run_if(shared.clone()),
+            increment_counter.run_if(shared.clone()),
+            increment_counter.run_if(shared),
+        ));
+
+        schedule.run(&mut world);
+        assert_eq!(evaluations.load(Ordering::Relaxed), 1);
+        assert_eq!(world.resource::<Counter>().0, 3);
+
+        schedule.run(&mut world);
+        assert_eq!(evaluations.load(Ordering::Relaxed), 2);
+        assert_eq!(world.resource::<Counter>().0, 3);
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 6);
+    }
+
     #[derive(Component)]
     struct TestComponent;
 
@@ -983,7 +1395,10 @@ mod tests {
                 .distributive_run_if(resource_exists_and_changed::<TestResource>)
                 .distributive_run_if(resource_changed_or_removed::<TestResource>())
                 .distributive_run_if(resource_removed::<TestResource>())
+                .distributive_run_if(resource_field_changed(|_: &TestResource| ()))
                 .distributive_run_if(on_event::<TestEvent>())
+                .distributive_run_if(exponential_backoff::<TestEvent>(8))
+                .distributive_run_if(every_n_runs(2))
                 .distributive_run_if(any_with_component::<TestComponent>)
                 .distributive_run_if(not(run_once())),
         );
@@ -0,0 +1,228 @@
+#[cfg(feature = "trace")]
+use bevy_utils::tracing::info_span;
+use bevy_utils::{Duration, Instant};
+use fixedbitset::FixedBitSet;
+use std::{borrow::Cow, panic::AssertUnwindSafe};
+
+use crate::{
+    schedule::{is_apply_deferred, BoxedCondition, SystemSchedule},
+    world::World,
+};
+
+use super::__rust_begin_short_backtrace;
+
+/// The progress of a [`Schedule`](crate::schedule::Schedule) run with a time budget, returned by
+/// [`Schedule::run_budgeted`](crate::schedule::Schedule::run_budgeted).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleProgress {
+    /// Every system of the schedule has run or been skipped, and the next call starts a new pass.
+    Completed,
+    /// The budget ran out: the next call resumes the pass.
+    Paused {
+        /// The number of systems left to run or skip in this pass.
+        remaining_systems: usize,
+    },
+}
+
+impl ScheduleProgress {
+    /// Returns `true` if the pass over the schedule is complete.
+    pub fn is_completed(&self) -> bool {
+        matches!(self, ScheduleProgress::Completed)
+    }
+}
+
+/// How long a system took to run, returned by
+/// [`Schedule::run_timed`](crate::schedule::Schedule::run_timed).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemTiming {
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+    /// How long the system took to run.
+    pub duration: Duration,
+}
+
+/// Runs the systems of a schedule in order on a single thread, pausing when the time budget of a
+/// call runs out and resuming on the next call.
+#[derive(Default)]
+pub(crate) struct BudgetedExecutor {
+    /// System sets whose conditions have been evaluated during this pass.
+    evaluated_sets: FixedBitSet,
+    /// Systems that have run or been skipped during this pass.
+    completed_systems: FixedBitSet,
+    /// Systems that have run but have not had their buffers applied.
+    unapplied_systems: FixedBitSet,
+    /// The index of the next system to consider, or `None` between passes.
+    next_system: Option<usize>,
+}
+
+impl BudgetedExecutor {
+    /// Returns `true` if a pass was started and not completed yet.
+    pub(crate) fn in_progress(&self) -> bool {
+        self.next_system.is_some()
+    }
+
+    /// Abandons the current pass, without applying the buffers of the systems that ran.
+    pub(crate) fn reset(&mut self) {
+        self.evaluated_sets.clear();
+        self.completed_systems.clear();
+        self.unapplied_systems.clear();
+        self.next_system = None;
+    }
+
+    /// Starts a new pass over `schedule`, skipping `skip_systems`.
+    pub(crate) fn start(&mut self, schedule: &SystemSchedule, skip_systems: Option<&FixedBitSet>) {
+        self.reset();
+        self.evaluated_sets.grow(schedule.set_ids.len());
+        self.completed_systems.grow(schedule.system_ids.len());
+        self.unapplied_systems.grow(schedule.system_ids.len());
+        if let Some(skip_systems) = skip_systems {
+            self.completed_systems |= skip_systems;
+        }
+        self.next_system = Some(0);
+    }
+
+    /// Runs systems of the current pass until it completes or `budget` has elapsed.
+    ///
+    /// At least one system is considered per call, so that every pass eventually completes.
+    pub(crate) fn run(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        budget: Duration,
+    ) -> ScheduleProgress {
+        let start = Instant::now();
+        let first_system = self.next_system.unwrap_or(0);
+        let mut system_index = first_system;
+
+        while system_index < schedule.systems.len() {
+            if system_index != first_system && start.elapsed() >= budget {
+                self.next_system = Some(system_index);
+                // pausing is a sync point: the world can be used by other code until the next call
+                self.apply_deferred(schedule, world);
+                return ScheduleProgress::Paused {
+                    remaining_systems: schedule.systems.len() - system_index,
+                };
+            }
+
+            self.run_system(schedule, world, system_index);
+            system_index += 1;
+        }
+
+        self.apply_deferred(schedule, world);
+        self.reset();
+        ScheduleProgress::Completed
+    }
+
+    /// Runs a whole pass over `schedule`, measuring how long each system that runs takes.
+    pub(crate) fn run_timed(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+    ) -> Vec<SystemTiming> {
+        let mut timings = Vec::new();
+        for system_index in 0..schedule.systems.len() {
+            let start = Instant::now();
+            if self.run_system(schedule, world, system_index) {
+                timings.push(SystemTiming {
+                    name: schedule.systems[system_index].name(),
+                    duration: start.elapsed(),
+                });
+            }
+        }
+
+        self.apply_deferred(schedule, world);
+        self.reset();
+        timings
+    }
+
+    /// Runs the system if its conditions are met, and returns whether it ran.
+    fn run_system(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        system_index: usize,
+    ) -> bool {
+        #[cfg(feature = "trace")]
+        let name = schedule.systems[system_index].name();
+        #[cfg(feature = "trace")]
+        let should_run_span = info_span!("check_conditions", name = &*name).entered();
+
+        let mut should_run = !self.completed_systems.contains(system_index);
+        for set_idx in schedule.sets_with_conditions_of_systems[system_index].ones() {
+            if self.evaluated_sets.contains(set_idx) {
+                continue;
+            }
+
+            // evaluate system set's conditions
+            let set_conditions_met =
+                evaluate_and_fold_conditions(&mut schedule.set_conditions[set_idx], world);
+
+            if !set_conditions_met {
+                self.completed_systems
+                    .union_with(&schedule.systems_in_sets_with_conditions[set_idx]);
+            }
+
+            should_run &= set_conditions_met;
+            self.evaluated_sets.insert(set_idx);
+        }
+
+        // evaluate system's conditions
+        let system_conditions_met =
+            evaluate_and_fold_conditions(&mut schedule.system_conditions[system_index], world);
+
+        should_run &= system_conditions_met;
+
+        #[cfg(feature = "trace")]
+        should_run_span.exit();
+
+        // system has either been skipped or will run
+        self.completed_systems.insert(system_index);
+
+        if !should_run {
+            return false;
+        }
+
+        let system = &mut schedule.systems[system_index];
+        if is_apply_deferred(system) {
+            self.apply_deferred(schedule, world);
+            return true;
+        }
+
+        let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            if system.is_exclusive() {
+                __rust_begin_short_backtrace::run(&mut **system, world);
+            } else {
+                // Use run_unsafe to avoid immediately applying deferred buffers
+                let world = world.as_unsafe_world_cell();
+                system.update_archetype_component_access(world);
+                // SAFETY: We have exclusive, single-threaded access to the world and
+                // update_archetype_component_access is being called immediately before this.
+                unsafe { __rust_begin_short_backtrace::run_unsafe(&mut **system, world) };
+            }
+        }));
+        if let Err(payload) = res {
+            eprintln!("Encountered a panic in system `{}`!", &*system.name());
+            std::panic::resume_unwind(payload);
+        }
+        self.unapplied_systems.insert(system_index);
+        true
+    }
+
+    fn apply_deferred(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+        for system_index in self.unapplied_systems.ones() {
+            let system = &mut schedule.systems[system_index];
+            system.apply_deferred(world);
+        }
+
+        self.unapplied_systems.clear();
+    }
+}
+
+fn evaluate_and_fold_conditions(conditions: &mut [BoxedCondition], world: &mut World) -> bool {
+    // not short-circuiting is intentional
+    #[allow(clippy::unnecessary_fold)]
+    conditions
+        .iter_mut()
+        .map(|condition| __rust_begin_short_backtrace::readonly_run(&mut **condition, world))
+        .fold(true, |acc, res| acc && res)
+}
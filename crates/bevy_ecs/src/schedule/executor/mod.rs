@@ -1,11 +1,15 @@
+mod budgeted;
 mod multi_threaded;
 mod simple;
 mod single_threaded;
 
+pub use self::budgeted::{ScheduleProgress, SystemTiming};
 pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
 pub use self::simple::SimpleExecutor;
 pub use self::single_threaded::SingleThreadedExecutor;
 
+pub(super) use self::budgeted::BudgetedExecutor;
+
 use fixedbitset::FixedBitSet;
 
 use crate::{
@@ -96,6 +100,66 @@ impl SystemSchedule {
             systems_in_sets_with_conditions: Vec::new(),
         }
     }
+
+    /// Removes the system with the given [`NodeId`] and returns it along with its conditions.
+    ///
+    /// The systems that immediately depended on it now depend on its own dependencies instead,
+    /// so every ordering that went through it is preserved without sorting the schedule again.
+    pub(super) fn remove_system(
+        &mut self,
+        id: NodeId,
+    ) -> Option<(BoxedSystem, Vec<BoxedCondition>)> {
+        let index = self
+            .system_ids
+            .iter()
+            .position(|&system_id| system_id == id)?;
+
+        let dependents = self.system_dependents.remove(index);
+        for &dependent in &dependents {
+            self.system_dependencies[dependent] -= 1;
+        }
+        for system_dependents in &mut self.system_dependents {
+            let Some(position) = system_dependents.iter().position(|&i| i == index) else {
+                continue;
+            };
+            system_dependents.swap_remove(position);
+            for &dependent in &dependents {
+                if !system_dependents.contains(&dependent) {
+                    system_dependents.push(dependent);
+                    self.system_dependencies[dependent] += 1;
+                }
+            }
+        }
+
+        // Shift the indices of the systems after the removed one.
+        self.system_dependencies.remove(index);
+        self.sets_with_conditions_of_systems.remove(index);
+        for system_dependents in &mut self.system_dependents {
+            for dependent in system_dependents.iter_mut() {
+                if *dependent > index {
+                    *dependent -= 1;
+                }
+            }
+        }
+        let system_count = self.system_ids.len() - 1;
+        for systems in &mut self.systems_in_sets_with_conditions {
+            let mut shifted = FixedBitSet::with_capacity(system_count);
+            shifted.extend(systems.ones().filter(|&i| i != index).map(|i| {
+                if i > index {
+                    i - 1
+                } else {
+                    i
+                }
+            }));
+            *systems = shifted;
+        }
+
+        self.system_ids.remove(index);
+        Some((
+            self.systems.remove(index),
+            self.system_conditions.remove(index),
+        ))
+    }
 }
 
 /// Instructs the executor to call [`System::apply_deferred`](crate::system::System::apply_deferred)
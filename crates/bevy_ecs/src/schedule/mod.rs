@@ -1,5 +1,6 @@
 //! Contains APIs for ordering systems and executing them on a [`World`](crate::world::World)
 
+mod ambiguity;
 mod condition;
 mod config;
 mod executor;
@@ -9,6 +10,7 @@ mod schedule;
 mod set;
 mod stepping;
 
+pub use self::ambiguity::*;
 pub use self::condition::*;
 pub use self::config::*;
 pub use self::executor::*;
@@ -1011,6 +1013,7 @@ mod tests {
                 world.components(),
                 TestSchedule.intern(),
                 &BTreeSet::new(),
+                &AmbiguitySet::new(),
             );
 
             let ambiguities: Vec<_> = schedule
@@ -1060,6 +1063,7 @@ mod tests {
                 world.components(),
                 TestSchedule.intern(),
                 &BTreeSet::new(),
+                &AmbiguitySet::new(),
             );
 
             let ambiguities: Vec<_> = schedule
@@ -1095,6 +1099,45 @@ mod tests {
             schedule.initialize(&mut world).unwrap();
             assert!(schedule.graph().conflicting_systems().is_empty());
         }
+
+        #[test]
+        fn suppressed_ambiguities() {
+            let mut world = World::new();
+            world.insert_resource(R);
+            let mut schedule = Schedule::new(TestSchedule);
+            schedule.add_systems((resmut_system, res_system));
+            schedule.initialize(&mut world).unwrap();
+
+            let ambiguities = schedule.graph().ambiguities().clone();
+            assert_eq!(ambiguities.len(), 1);
+            let key = ambiguities.iter().next().unwrap();
+            assert_eq!(key.schedule(), "TestSchedule");
+            assert_eq!(
+                key.systems(),
+                (
+                    "bevy_ecs::schedule::tests::system_ambiguity::res_system",
+                    "bevy_ecs::schedule::tests::system_ambiguity::resmut_system"
+                )
+            );
+
+            // the suppressed ambiguity doesn't fail in strict mode, but new ones still do
+            world.suppress_ambiguities(ambiguities.to_string().parse().unwrap());
+            let mut schedule = Schedule::new(TestSchedule);
+            schedule.set_build_settings(ScheduleBuildSettings {
+                ambiguity_detection: LogLevel::Error,
+                ..Default::default()
+            });
+            schedule.add_systems((resmut_system, res_system));
+            schedule.initialize(&mut world).unwrap();
+            assert_eq!(schedule.graph().conflicting_systems().len(), 1);
+
+            schedule.add_systems(nonsendmut_system);
+            schedule.add_systems(nonsend_system);
+            assert!(matches!(
+                schedule.initialize(&mut world),
+                Err(ScheduleBuildError::Ambiguity(_))
+            ));
+        }
     }
 
     #[cfg(feature = "bevy_debug_stepping")]
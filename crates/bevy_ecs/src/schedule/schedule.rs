@@ -8,7 +8,7 @@ use bevy_utils::tracing::info_span;
 use bevy_utils::{default, tracing::info};
 use bevy_utils::{
     tracing::{error, warn},
-    HashMap, HashSet,
+    Duration, HashMap, HashSet,
 };
 use fixedbitset::FixedBitSet;
 use petgraph::{algo::TarjanScc, prelude::*};
@@ -23,7 +23,7 @@ use crate::{
     world::World,
 };
 
-pub use stepping::Stepping;
+pub use stepping::{BreakpointHit, Stepping, SteppingDiagnostics};
 
 /// Resource that stores [`Schedule`]s mapped to [`ScheduleLabel`]s excluding the current running [`Schedule`].
 #[derive(Default, Resource)]
@@ -31,6 +31,8 @@ pub struct Schedules {
     inner: HashMap<InternedScheduleLabel, Schedule>,
     /// List of [`ComponentId`]s to ignore when reporting system order ambiguity conflicts
     pub ignored_scheduling_ambiguities: BTreeSet<ComponentId>,
+    /// Pairs of systems to ignore when reporting system order ambiguity conflicts
+    pub suppressed_ambiguities: AmbiguitySet,
 }
 
 impl Schedules {
@@ -39,6 +41,7 @@ impl Schedules {
         Self {
             inner: HashMap::new(),
             ignored_scheduling_ambiguities: BTreeSet::new(),
+            suppressed_ambiguities: AmbiguitySet::new(),
         }
     }
 
@@ -154,6 +157,27 @@ impl Schedules {
         info!("{}", message);
     }
 
+    /// Ignore the system order ambiguities in `ambiguities`, typically
+    /// [loaded](AmbiguitySet::load) from a file of reviewed ambiguities.
+    ///
+    /// The schedules that were already built only apply them the next time they change.
+    pub fn suppress_ambiguities(&mut self, ambiguities: AmbiguitySet) {
+        self.suppressed_ambiguities.extend(ambiguities);
+    }
+
+    /// Returns the system order ambiguities of all stored schedules, including the suppressed
+    /// ones, as of their last build.
+    ///
+    /// Save the result to review the ambiguities, and suppress them on the next runs with
+    /// [`suppress_ambiguities`](Self::suppress_ambiguities).
+    pub fn ambiguities(&self) -> AmbiguitySet {
+        let mut ambiguities = AmbiguitySet::new();
+        for schedule in self.inner.values() {
+            ambiguities.extend(schedule.graph().ambiguities().clone());
+        }
+        ambiguities
+    }
+
     /// Adds one or more systems to the [`Schedule`] matching the provided [`ScheduleLabel`].
     pub fn add_systems<M>(
         &mut self,
@@ -263,6 +287,7 @@ pub struct Schedule {
     executable: SystemSchedule,
     executor: Box<dyn SystemExecutor>,
     executor_initialized: bool,
+    budgeted: BudgetedExecutor,
 }
 
 #[derive(ScheduleLabel, Hash, PartialEq, Eq, Debug, Clone)]
@@ -287,6 +312,7 @@ impl Schedule {
             executable: SystemSchedule::new(),
             executor: make_executor(ExecutorKind::default()),
             executor_initialized: false,
+            budgeted: BudgetedExecutor::default(),
         }
     }
 
@@ -301,6 +327,57 @@ impl Schedule {
         self
     }
 
+    /// Adds a collection of systems to the schedule, returning the [`NodeId`] of each added system
+    /// in the order they were defined.
+    ///
+    /// Unlike [`Schedule::add_systems`], this is meant for schedules that have already been built:
+    /// the returned ids can later be passed to [`Schedule::remove_system`].
+    ///
+    /// New systems can be ordered against any other system of the schedule, so this doesn't
+    /// resolve the graph incrementally: the whole schedule is rebuilt the next time it is
+    /// initialized or run, like after [`Schedule::add_systems`].
+    pub fn add_systems_with_ids<M>(&mut self, systems: impl IntoSystemConfigs<M>) -> Vec<NodeId> {
+        self.graph
+            .process_configs(systems.into_configs(), true)
+            .nodes
+    }
+
+    /// Removes the system with the given [`NodeId`] from the schedule and returns it.
+    ///
+    /// Orderings that went through the removed system are preserved: any system that had to run
+    /// before it still runs before the systems that had to run after it.
+    ///
+    /// If the schedule is built, the system is taken out of it in place, without sorting the
+    /// graph or checking it for ambiguities again; only the executor is re-initialized on the
+    /// next run.
+    ///
+    /// Returns `None` if there is no such system, or if it was automatically inserted by the
+    /// schedule (such as sync points).
+    pub fn remove_system(&mut self, id: NodeId) -> Option<BoxedSystem> {
+        if !self.graph.contains_system(id) {
+            return None;
+        }
+        let changed = self.graph.changed;
+        let built = match self.executable.remove_system(id) {
+            Some((system, conditions)) => {
+                self.graph.systems[id.index()].inner = Some(system);
+                self.graph.system_conditions[id.index()] = conditions;
+                true
+            }
+            None => false,
+        };
+        if built {
+            self.executor_initialized = false;
+            self.budgeted.reset();
+        }
+        let system = self.graph.remove_system(id);
+        if built {
+            // The executable schedule already matches the graph, no need to rebuild it.
+            self.graph.changed = changed;
+        }
+        system
+    }
+
     /// Suppress warnings and errors that would result from systems in these sets having ambiguities
     /// (conflicting access but indeterminate order) with systems in `set`.
     #[track_caller]
@@ -332,6 +409,54 @@ impl Schedule {
         self
     }
 
+    /// Adds the systems and system sets of the `child` schedule to this schedule, in the
+    /// [`ChildScheduleSet`] of its label.
+    ///
+    /// The child keeps its own sets, orderings and run conditions, and is ordered or gated as a
+    /// unit by configuring its [`ChildScheduleSet`] in this schedule. Unlike running the child
+    /// from an exclusive system, its systems run in parallel with the systems of this schedule,
+    /// and ambiguities between them are detected when this schedule is built. System sets with
+    /// the same label in both schedules are the same set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the child schedule has already been initialized or run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::schedule::{ChildScheduleSet, ScheduleLabel};
+    /// #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    /// struct Physics;
+    ///
+    /// #[derive(Resource)]
+    /// struct Paused(bool);
+    ///
+    /// # fn integrate() {}
+    /// # fn collide() {}
+    /// # fn input() {}
+    /// let mut physics = Schedule::new(Physics);
+    /// physics.add_systems((integrate, collide).chain());
+    ///
+    /// let mut update = Schedule::default();
+    /// update.add_systems(input).add_child(physics).configure_sets(
+    ///     ChildScheduleSet::new(Physics)
+    ///         .after(input)
+    ///         .run_if(|paused: Res<Paused>| !paused.0),
+    /// );
+    /// ```
+    pub fn add_child(&mut self, child: Schedule) -> &mut Self {
+        assert!(
+            !child.executor_initialized,
+            "Could not add child schedule {:?} to {:?}: it has already been initialized",
+            child.label, self.label
+        );
+        self.graph
+            .merge_child(child.graph, ChildScheduleSet(child.label).intern());
+        self
+    }
+
     /// Configures a collection of system sets in this schedule, adding them if they does not exist.
     #[track_caller]
     pub fn configure_sets(&mut self, sets: impl IntoSystemSetConfigs) -> &mut Self {
@@ -379,6 +504,7 @@ impl Schedule {
         let _span = info_span!("schedule", name = ?self.label).entered();
 
         world.check_change_ticks();
+        world.schedule_runs += 1;
         self.initialize(world)
             .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.label));
 
@@ -387,16 +513,114 @@ impl Schedule {
 
         #[cfg(feature = "bevy_debug_stepping")]
         {
-            let skip_systems = match world.get_resource_mut::<Stepping>() {
-                None => None,
-                Some(mut stepping) => stepping.skipped_systems(self),
-            };
+            let skip_systems = self.stepping_skipped_systems(world);
 
             self.executor
                 .run(&mut self.executable, world, skip_systems.as_ref());
         }
     }
 
+    /// Runs the systems of the schedule in order until `budget` has elapsed, and resumes where it
+    /// stopped on the next call.
+    ///
+    /// This time-slices a schedule over several calls, for example to run a background simulation
+    /// world on the main thread. Each call runs at least one system, so a pass over the schedule
+    /// always completes eventually. Systems run on the current thread, regardless of the
+    /// [`ExecutorKind`] of the schedule.
+    ///
+    /// A paused pass doesn't behave exactly like a single [`run`](Self::run):
+    /// - Pausing is a sync point: the commands of the systems that ran are applied before
+    ///   returning, as if an [`apply_deferred`](crate::schedule::apply_deferred) system was
+    ///   inserted there.
+    /// - The world can be changed between two calls, and the remaining systems of the pass will
+    ///   see those changes.
+    /// - The conditions of a system set are evaluated once per pass, when its first system is
+    ///   reached, even if that happens several calls after the pass started.
+    /// - Changing the systems of the schedule abandons the current pass: the next call starts a
+    ///   new one from the first system.
+    ///
+    /// Running the schedule with [`run`](Self::run) doesn't affect the pass in progress.
+    pub fn run_budgeted(&mut self, world: &mut World, budget: Duration) -> ScheduleProgress {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("schedule", name = ?self.label).entered();
+
+        self.initialize(world)
+            .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.label));
+
+        if !self.budgeted.in_progress() {
+            world.check_change_ticks();
+            world.schedule_runs += 1;
+
+            #[cfg(not(feature = "bevy_debug_stepping"))]
+            self.budgeted.start(&self.executable, None);
+
+            #[cfg(feature = "bevy_debug_stepping")]
+            {
+                let skip_systems = self.stepping_skipped_systems(world);
+                self.budgeted.start(&self.executable, skip_systems.as_ref());
+            }
+        }
+
+        self.budgeted.run(&mut self.executable, world, budget)
+    }
+
+    /// Runs all systems in this schedule on the `world`, and returns how long each system that ran
+    /// took, in the order they ran.
+    ///
+    /// Systems run one at a time on the current thread, regardless of the [`ExecutorKind`], so
+    /// that their timings don't overlap. This is slower than [`run`](Self::run), and meant to
+    /// profile schedules that run rarely, like the startup schedules of an app.
+    ///
+    /// The commands of the systems are applied by [`apply_deferred`] systems, which have their own
+    /// timings, and at the end of the run.
+    pub fn run_timed(&mut self, world: &mut World) -> Vec<SystemTiming> {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("schedule", name = ?self.label).entered();
+
+        world.check_change_ticks();
+        world.schedule_runs += 1;
+        self.initialize(world)
+            .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.label));
+
+        // a separate executor, to leave a budgeted pass in progress untouched
+        let mut executor = BudgetedExecutor::default();
+
+        #[cfg(not(feature = "bevy_debug_stepping"))]
+        executor.start(&self.executable, None);
+
+        #[cfg(feature = "bevy_debug_stepping")]
+        {
+            let skip_systems = self.stepping_skipped_systems(world);
+            executor.start(&self.executable, skip_systems.as_ref());
+        }
+
+        executor.run_timed(&mut self.executable, world)
+    }
+
+    /// Returns the systems [`Stepping`] skips in this run, after evaluating its breakpoint
+    /// conditions, and updates [`SteppingDiagnostics`].
+    #[cfg(feature = "bevy_debug_stepping")]
+    fn stepping_skipped_systems(&self, world: &mut World) -> Option<FixedBitSet> {
+        if !world.contains_resource::<Stepping>() {
+            return None;
+        }
+
+        world.resource_scope(|world, mut stepping: crate::world::Mut<Stepping>| {
+            stepping.evaluate_breakpoint_conditions(self, world);
+            let skip_systems = stepping.skipped_systems(self);
+            if let Some(mut diagnostics) = world.get_resource_mut::<SteppingDiagnostics>() {
+                *diagnostics = stepping.diagnostics();
+            }
+            skip_systems
+        })
+    }
+
+    /// Returns `true` if a pass started by [`run_budgeted`](Self::run_budgeted) is paused, and
+    /// will be resumed by the next call.
+    pub fn is_budgeted_run_in_progress(&self) -> bool {
+        self.budgeted.in_progress()
+    }
+
     /// Initializes any newly-added systems and conditions, rebuilds the executable schedule,
     /// and re-initializes the executor.
     ///
@@ -404,18 +628,18 @@ impl Schedule {
     pub fn initialize(&mut self, world: &mut World) -> Result<(), ScheduleBuildError> {
         if self.graph.changed {
             self.graph.initialize(world);
-            let ignored_ambiguities = world
-                .get_resource_or_insert_with::<Schedules>(Schedules::default)
-                .ignored_scheduling_ambiguities
-                .clone();
+            world.get_resource_or_insert_with::<Schedules>(Schedules::default);
+            let schedules = world.resource::<Schedules>();
             self.graph.update_schedule(
                 &mut self.executable,
                 world.components(),
-                &ignored_ambiguities,
+                &schedules.ignored_scheduling_ambiguities,
+                &schedules.suppressed_ambiguities,
                 self.label,
             )?;
             self.graph.changed = false;
             self.executor_initialized = false;
+            self.budgeted.reset();
         }
 
         if !self.executor_initialized {
@@ -502,7 +726,7 @@ impl Schedule {
     /// Returns the number of systems in this schedule.
     pub fn systems_len(&self) -> usize {
         if !self.executor_initialized {
-            self.graph.systems.len()
+            self.graph.systems.len() - self.graph.removed_systems
         } else {
             self.executable.systems.len()
         }
@@ -609,7 +833,10 @@ pub struct ScheduleGraph {
     ambiguous_with: UnGraphMap<NodeId, ()>,
     ambiguous_with_all: HashSet<NodeId>,
     conflicting_systems: Vec<(NodeId, NodeId, Vec<ComponentId>)>,
+    ambiguities: AmbiguitySet,
     anonymous_sets: usize,
+    /// Number of systems removed with [`ScheduleGraph::remove_system`]
+    removed_systems: usize,
     changed: bool,
     settings: ScheduleBuildSettings,
     /// Dependency edges that will **not** automatically insert an instance of `apply_deferred` on the edge.
@@ -632,7 +859,9 @@ impl ScheduleGraph {
             ambiguous_with: UnGraphMap::new(),
             ambiguous_with_all: HashSet::new(),
             conflicting_systems: Vec::new(),
+            ambiguities: AmbiguitySet::new(),
             anonymous_sets: 0,
+            removed_systems: 0,
             changed: false,
             settings: default(),
             no_sync_edges: BTreeSet::new(),
@@ -711,6 +940,20 @@ impl ScheduleGraph {
         &self.hierarchy
     }
 
+    /// Returns `true` if the system or system set `id` is in `set`, directly or through nested
+    /// sets.
+    pub fn is_in_set(&self, id: NodeId, set: InternedSystemSet) -> bool {
+        let Some(&set_id) = self.system_set_ids.get(&set) else {
+            return false;
+        };
+        let mut found = false;
+        self.traverse_sets_containing_node(id, &mut |parent| {
+            found |= parent == set_id;
+            !found
+        });
+        found
+    }
+
     /// Returns the [`Dag`] of the dependencies in the schedule.
     ///
     /// Nodes in this graph are systems and sets, and edges denote that
@@ -727,6 +970,176 @@ impl ScheduleGraph {
         &self.conflicting_systems
     }
 
+    /// Returns the [`conflicting_systems`](Self::conflicting_systems) identified by name, to be
+    /// stored across runs.
+    ///
+    /// Must be called after [`ScheduleGraph::build_schedule`] to be non-empty.
+    pub fn ambiguities(&self) -> &AmbiguitySet {
+        &self.ambiguities
+    }
+
+    /// Returns `true` if the system with the given [`NodeId`] was added to the graph and hasn't
+    /// been removed.
+    pub fn contains_system(&self, id: NodeId) -> bool {
+        id.is_system() && self.hierarchy.graph.contains_node(id)
+    }
+
+    /// Removes the system with the given [`NodeId`] and its conditions from the graph, returning
+    /// the system.
+    ///
+    /// Dependency edges that went through the system are rerouted, so that its dependencies keep
+    /// running before its dependents.
+    ///
+    /// Returns `None` if the system doesn't exist or has been moved into a [`SystemSchedule`].
+    pub fn remove_system(&mut self, id: NodeId) -> Option<BoxedSystem> {
+        if !self.contains_system(id) {
+            return None;
+        }
+        let system = self.systems[id.index()].inner.take()?;
+        self.system_conditions[id.index()].clear();
+
+        let before: Vec<_> = self
+            .dependency
+            .graph
+            .neighbors_directed(id, Incoming)
+            .collect();
+        let after: Vec<_> = self
+            .dependency
+            .graph
+            .neighbors_directed(id, Outgoing)
+            .collect();
+        for &lhs in &before {
+            for &rhs in &after {
+                self.dependency.graph.add_edge(lhs, rhs, ());
+                if self.no_sync_edges.contains(&(lhs, id))
+                    && self.no_sync_edges.contains(&(id, rhs))
+                {
+                    self.no_sync_edges.insert((lhs, rhs));
+                }
+            }
+        }
+
+        self.hierarchy.graph.remove_node(id);
+        self.dependency.graph.remove_node(id);
+        self.ambiguous_with.remove_node(id);
+        self.ambiguous_with_all.remove(&id);
+        self.no_sync_edges
+            .retain(|&(lhs, rhs)| lhs != id && rhs != id);
+        self.uninit.retain(|&(uninit, _)| uninit != id);
+        self.hierarchy.topsort.retain(|&node| node != id);
+        self.dependency.topsort.retain(|&node| node != id);
+        self.conflicting_systems
+            .retain(|&(a, b, _)| a != id && b != id);
+        self.removed_systems += 1;
+        self.changed = true;
+
+        Some(system)
+    }
+
+    /// Moves the nodes, edges and conditions of `child` into this graph, adding the systems of
+    /// `child` to the `unit` set.
+    fn merge_child(&mut self, child: ScheduleGraph, unit: InternedSystemSet) {
+        let ScheduleGraph {
+            systems,
+            system_conditions,
+            system_sets,
+            system_set_conditions,
+            uninit,
+            hierarchy,
+            dependency,
+            ambiguous_with,
+            ambiguous_with_all,
+            no_sync_edges,
+            ..
+        } = child;
+
+        let unit = match self.system_set_ids.get(&unit) {
+            Some(&id) => id,
+            None => self.add_set(unit),
+        };
+        self.hierarchy.graph.add_node(unit);
+        self.dependency.graph.add_node(unit);
+
+        let mut ids = HashMap::<NodeId, NodeId>::new();
+        let mut uninit_conditions = HashMap::<NodeId, usize>::new();
+        for (id, first_uninit) in uninit {
+            uninit_conditions.insert(id, first_uninit);
+        }
+
+        for (index, (system, conditions)) in systems.into_iter().zip(system_conditions).enumerate()
+        {
+            let child_id = NodeId::System(index);
+            // Removed systems leave an empty node behind.
+            let Some(system) = system.inner else {
+                continue;
+            };
+            let id = NodeId::System(self.systems.len());
+            self.systems.push(SystemNode::new(system));
+            self.system_conditions.push(conditions);
+            if uninit_conditions.contains_key(&child_id) {
+                self.uninit.push((id, 0));
+            }
+            self.hierarchy.graph.add_edge(unit, id, ());
+            self.dependency.graph.add_node(id);
+            ids.insert(child_id, id);
+        }
+
+        for (index, (set, mut conditions)) in system_sets
+            .into_iter()
+            .zip(system_set_conditions)
+            .enumerate()
+        {
+            let child_id = NodeId::Set(index);
+            // Anonymous sets are numbered per schedule, so they get a new number in this one.
+            let set = if set.is_anonymous() {
+                self.create_anonymous_set().intern()
+            } else {
+                set.inner
+            };
+            let id = match self.system_set_ids.get(&set) {
+                Some(&id) => id,
+                None => self.add_set(set),
+            };
+            let set_conditions = &mut self.system_set_conditions[id.index()];
+            if let Some(first_uninit) = uninit_conditions.get(&child_id) {
+                self.uninit.push((id, set_conditions.len() + first_uninit));
+            }
+            set_conditions.append(&mut conditions);
+            ids.insert(child_id, id);
+        }
+
+        for id in hierarchy.graph.nodes().chain(dependency.graph.nodes()) {
+            if let Some(&id) = ids.get(&id) {
+                self.hierarchy.graph.add_node(id);
+                self.dependency.graph.add_node(id);
+            }
+        }
+        for (lhs, rhs, _) in hierarchy.graph.all_edges() {
+            if let (Some(&lhs), Some(&rhs)) = (ids.get(&lhs), ids.get(&rhs)) {
+                self.hierarchy.graph.add_edge(lhs, rhs, ());
+            }
+        }
+        for (lhs, rhs, _) in dependency.graph.all_edges() {
+            if let (Some(&lhs), Some(&rhs)) = (ids.get(&lhs), ids.get(&rhs)) {
+                self.dependency.graph.add_edge(lhs, rhs, ());
+            }
+        }
+        for (lhs, rhs, _) in ambiguous_with.all_edges() {
+            if let (Some(&lhs), Some(&rhs)) = (ids.get(&lhs), ids.get(&rhs)) {
+                self.ambiguous_with.add_edge(lhs, rhs, ());
+            }
+        }
+        self.ambiguous_with_all
+            .extend(ambiguous_with_all.iter().filter_map(|id| ids.get(id)));
+        self.no_sync_edges.extend(
+            no_sync_edges
+                .iter()
+                .filter_map(|(lhs, rhs)| Some((*ids.get(lhs)?, *ids.get(rhs)?))),
+        );
+
+        self.changed = true;
+    }
+
     fn process_config<T: ProcessNodeConfig>(
         &mut self,
         config: NodeConfig<T>,
@@ -1067,12 +1480,13 @@ impl ScheduleGraph {
     ///
     /// This method also
     /// - checks for dependency or hierarchy cycles
-    /// - checks for system access conflicts and reports ambiguities
+    /// - checks for system access conflicts and reports ambiguities that aren't suppressed
     pub fn build_schedule(
         &mut self,
         components: &Components,
         schedule_label: InternedScheduleLabel,
         ignored_ambiguities: &BTreeSet<ComponentId>,
+        suppressed_ambiguities: &AmbiguitySet,
     ) -> Result<SystemSchedule, ScheduleBuildError> {
         // check hierarchy for cycles
         self.hierarchy.topsort =
@@ -1131,8 +1545,20 @@ impl ScheduleGraph {
             &ambiguous_with_flattened,
             ignored_ambiguities,
         );
-        self.optionally_check_conflicts(&conflicting_systems, components, schedule_label)?;
+        let keys: Vec<_> = conflicting_systems
+            .iter()
+            .map(|(a, b, _)| self.ambiguity_key(schedule_label, a, b))
+            .collect();
+        let reported: Vec<_> = conflicting_systems
+            .iter()
+            .zip(&keys)
+            .filter(|(_, key)| !suppressed_ambiguities.contains(key))
+            .map(|(conflict, _)| conflict.clone())
+            .collect();
+        let suppressed = conflicting_systems.len() - reported.len();
         self.conflicting_systems = conflicting_systems;
+        self.ambiguities = keys.into_iter().collect();
+        self.optionally_check_conflicts(&reported, suppressed, components, schedule_label)?;
 
         // build the schedule
         Ok(self.build_schedule_inner(dependency_flattened_dag, hier_results.reachable))
@@ -1414,7 +1840,7 @@ impl ScheduleGraph {
             })
             .unzip();
 
-        let sys_count = self.systems.len();
+        let sys_count = dg_system_ids.len();
         let set_with_conditions_count = hg_set_ids.len();
         let hg_node_count = self.hierarchy.graph.node_count();
 
@@ -1479,19 +1905,8 @@ impl ScheduleGraph {
         }
     }
 
-    /// Updates the `SystemSchedule` from the `ScheduleGraph`.
-    fn update_schedule(
-        &mut self,
-        schedule: &mut SystemSchedule,
-        components: &Components,
-        ignored_ambiguities: &BTreeSet<ComponentId>,
-        schedule_label: InternedScheduleLabel,
-    ) -> Result<(), ScheduleBuildError> {
-        if !self.uninit.is_empty() {
-            return Err(ScheduleBuildError::Uninitialized);
-        }
-
-        // move systems out of old schedule
+    /// Moves all systems and conditions out of the [`SystemSchedule`] and back into the graph.
+    fn reclaim_systems(&mut self, schedule: &mut SystemSchedule) {
         for ((id, system), conditions) in schedule
             .system_ids
             .drain(..)
@@ -1509,8 +1924,29 @@ impl ScheduleGraph {
         {
             self.system_set_conditions[id.index()] = conditions;
         }
+    }
+
+    /// Updates the `SystemSchedule` from the `ScheduleGraph`.
+    fn update_schedule(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        components: &Components,
+        ignored_ambiguities: &BTreeSet<ComponentId>,
+        suppressed_ambiguities: &AmbiguitySet,
+        schedule_label: InternedScheduleLabel,
+    ) -> Result<(), ScheduleBuildError> {
+        if !self.uninit.is_empty() {
+            return Err(ScheduleBuildError::Uninitialized);
+        }
+
+        self.reclaim_systems(schedule);
 
-        *schedule = self.build_schedule(components, schedule_label, ignored_ambiguities)?;
+        *schedule = self.build_schedule(
+            components,
+            schedule_label,
+            ignored_ambiguities,
+            suppressed_ambiguities,
+        )?;
 
         // move systems into new schedule
         for &id in &schedule.system_ids {
@@ -1566,6 +2002,17 @@ enum ReportCycles {
 
 // methods for reporting errors
 impl ScheduleGraph {
+    /// Identifies the ambiguity between two systems by their full names, for [`AmbiguitySet`].
+    fn ambiguity_key(
+        &self,
+        schedule_label: InternedScheduleLabel,
+        a: &NodeId,
+        b: &NodeId,
+    ) -> AmbiguityKey {
+        let name = |id: &NodeId| self.systems[id.index()].get().unwrap().name();
+        AmbiguityKey::new(format!("{schedule_label:?}"), name(a), name(b))
+    }
+
     fn get_node_name(&self, id: &NodeId) -> String {
         self.get_node_name_inner(id, self.settings.report_sets)
     }
@@ -1833,6 +2280,7 @@ impl ScheduleGraph {
     fn optionally_check_conflicts(
         &self,
         conflicts: &[(NodeId, NodeId, Vec<ComponentId>)],
+        suppressed: usize,
         components: &Components,
         schedule_label: InternedScheduleLabel,
     ) -> Result<(), ScheduleBuildError> {
@@ -1840,7 +2288,14 @@ impl ScheduleGraph {
             return Ok(());
         }
 
-        let message = self.get_conflicts_error_message(conflicts, components);
+        let mut message = self.get_conflicts_error_message(conflicts, components);
+        if suppressed > 0 {
+            writeln!(
+                message,
+                "{suppressed} suppressed pairs of systems are not shown."
+            )
+            .unwrap();
+        }
         match self.settings.ambiguity_detection {
             LogLevel::Ignore => Ok(()),
             LogLevel::Warn => {
@@ -2043,14 +2498,15 @@ mod tests {
         self as bevy_ecs,
         prelude::{Res, Resource},
         schedule::{
-            tests::ResMut, IntoSystemConfigs, IntoSystemSetConfigs, Schedule,
-            ScheduleBuildSettings, SystemSet,
+            tests::ResMut, ChildScheduleSet, IntoSystemConfigs, IntoSystemSetConfigs, LogLevel,
+            Schedule, ScheduleBuildError, ScheduleBuildSettings, ScheduleProgress, SystemSet,
         },
         system::Commands,
         world::World,
     };
 
     use super::Schedules;
+    use bevy_utils::Duration;
 
     #[derive(Resource)]
     struct Resource1;
@@ -2554,6 +3010,202 @@ mod tests {
         assert_eq!(value.0, 1);
     }
 
+    #[test]
+    fn remove_system_from_built_schedule() {
+        let mut world = World::new();
+        world.insert_resource(CheckSystemRan(0));
+        let mut schedule = Schedule::new(TestSchedule);
+
+        let ids = schedule.add_systems_with_ids(
+            (
+                |mut ran: ResMut<CheckSystemRan>| ran.0 += 1,
+                |mut ran: ResMut<CheckSystemRan>| ran.0 += 10,
+            )
+                .chain(),
+        );
+        assert_eq!(ids.len(), 2);
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 11);
+
+        assert!(schedule.remove_system(ids[1]).is_some());
+        assert!(schedule.remove_system(ids[1]).is_none());
+        assert_eq!(schedule.systems_len(), 1);
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 12);
+    }
+
+    #[test]
+    fn remove_system_preserves_ordering() {
+        let mut world = World::new();
+        world.insert_resource(CheckSystemRan(0));
+        let mut schedule = Schedule::new(TestSchedule);
+
+        let ids = schedule.add_systems_with_ids(
+            (
+                |mut ran: ResMut<CheckSystemRan>| {
+                    assert_eq!(ran.0, 0);
+                    ran.0 += 1;
+                },
+                |mut ran: ResMut<CheckSystemRan>| ran.0 += 10,
+                |mut ran: ResMut<CheckSystemRan>| {
+                    assert_eq!(ran.0 % 10, 1);
+                    ran.0 = 0;
+                },
+            )
+                .chain(),
+        );
+        schedule.run(&mut world);
+
+        schedule.remove_system(ids[1]);
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..Default::default()
+        });
+        for _ in 0..10 {
+            schedule.run(&mut world);
+        }
+    }
+
+    #[test]
+    fn add_systems_with_ids_to_built_schedule() {
+        let mut world = World::new();
+        world.insert_resource(CheckSystemRan(0));
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems(|mut ran: ResMut<CheckSystemRan>| ran.0 += 1);
+        schedule.run(&mut world);
+
+        let ids = schedule.add_systems_with_ids(|mut ran: ResMut<CheckSystemRan>| ran.0 += 10);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 12);
+
+        schedule.remove_system(ids[0]);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 13);
+    }
+
+    #[test]
+    fn remove_system_without_rebuilding() {
+        let mut world = World::new();
+        world.insert_resource(CheckSystemRan(0));
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.configure_sets(TestSet::First.run_if(|| false));
+
+        let ids = schedule.add_systems_with_ids(
+            (
+                |mut ran: ResMut<CheckSystemRan>| ran.0 += 1,
+                |mut ran: ResMut<CheckSystemRan>| ran.0 += 10,
+                (|mut ran: ResMut<CheckSystemRan>| ran.0 += 100).in_set(TestSet::First),
+                |mut ran: ResMut<CheckSystemRan>| {
+                    assert_eq!(ran.0 % 10, 1);
+                    ran.0 += 1000;
+                },
+            )
+                .chain(),
+        );
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 1011);
+
+        world.resource_mut::<CheckSystemRan>().0 = 0;
+        assert!(schedule.remove_system(ids[1]).is_some());
+        assert!(!schedule.graph.changed);
+        assert_eq!(schedule.systems_len(), 3);
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 1001);
+    }
+
+    #[derive(ScheduleLabel, Hash, Debug, Clone, PartialEq, Eq)]
+    struct ChildSchedule;
+
+    #[derive(Resource)]
+    struct RunChild(bool);
+
+    #[test]
+    fn child_schedule_runs_as_unit() {
+        let mut world = World::new();
+        world.insert_resource(CheckSystemRan(0));
+        world.insert_resource(RunChild(true));
+
+        let mut child = Schedule::new(ChildSchedule);
+        child.configure_sets(TestSet::Second.run_if(|| false));
+        child.add_systems(
+            (
+                |mut ran: ResMut<CheckSystemRan>| {
+                    assert_eq!(ran.0, 1);
+                    ran.0 += 10;
+                },
+                |mut ran: ResMut<CheckSystemRan>| {
+                    assert_eq!(ran.0, 11);
+                    ran.0 += 100;
+                },
+                (|mut ran: ResMut<CheckSystemRan>| ran.0 += 1000).in_set(TestSet::Second),
+            )
+                .chain(),
+        );
+
+        let mut schedule = Schedule::new(TestSchedule);
+        let first = |mut ran: ResMut<CheckSystemRan>| ran.0 += 1;
+        schedule
+            .add_systems(first.in_set(TestSet::First))
+            .add_child(child)
+            .configure_sets(
+                ChildScheduleSet::new(ChildSchedule)
+                    .after(TestSet::First)
+                    .run_if(|run: Res<RunChild>| run.0),
+            );
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..Default::default()
+        });
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 111);
+
+        world.resource_mut::<RunChild>().0 = false;
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 112);
+    }
+
+    #[test]
+    fn child_schedule_ambiguities_are_detected() {
+        let mut world = World::new();
+        world.insert_resource(CheckSystemRan(0));
+
+        let mut child = Schedule::new(ChildSchedule);
+        child.add_systems(|mut ran: ResMut<CheckSystemRan>| ran.0 += 1);
+
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule
+            .add_systems(|mut ran: ResMut<CheckSystemRan>| ran.0 += 10)
+            .add_child(child);
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..Default::default()
+        });
+        assert!(matches!(
+            schedule.initialize(&mut world),
+            Err(ScheduleBuildError::Ambiguity(_))
+        ));
+
+        // Ordering the child schedule as a unit resolves the ambiguity.
+        let mut child = Schedule::new(ChildSchedule);
+        child.add_systems(|mut ran: ResMut<CheckSystemRan>| ran.0 += 1);
+
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule
+            .add_systems((|mut ran: ResMut<CheckSystemRan>| ran.0 += 10).in_set(TestSet::First))
+            .add_child(child)
+            .configure_sets(ChildScheduleSet::new(ChildSchedule).before(TestSet::First));
+        schedule.set_build_settings(ScheduleBuildSettings {
+            ambiguity_detection: LogLevel::Error,
+            ..Default::default()
+        });
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CheckSystemRan>().0, 11);
+    }
+
     #[derive(SystemSet, Debug, Hash, Clone, PartialEq, Eq)]
     enum TestSet {
         First,
@@ -2632,4 +3284,80 @@ mod tests {
             .expect("CheckSystemRan Resource Should Exist");
         assert_eq!(value.0, 2);
     }
+
+    #[test]
+    fn run_budgeted_resumes() {
+        #[derive(Resource, Default)]
+        struct Log(Vec<u32>);
+
+        #[derive(Resource)]
+        struct Marker;
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                |mut log: ResMut<Log>| log.0.push(0),
+                |mut log: ResMut<Log>, mut commands: Commands| {
+                    log.0.push(1);
+                    commands.insert_resource(Marker);
+                },
+                |mut log: ResMut<Log>| log.0.push(2),
+            )
+                .chain_ignore_deferred(),
+        );
+        let mut world = World::new();
+        world.init_resource::<Log>();
+
+        // a zero budget runs a single system per call
+        let progress = schedule.run_budgeted(&mut world, Duration::ZERO);
+        assert_eq!(
+            progress,
+            ScheduleProgress::Paused {
+                remaining_systems: 2
+            }
+        );
+        assert_eq!(world.resource::<Log>().0, vec![0]);
+        assert!(schedule.is_budgeted_run_in_progress());
+
+        schedule.run_budgeted(&mut world, Duration::ZERO);
+        // pausing applies the commands of the systems that ran
+        assert!(world.contains_resource::<Marker>());
+
+        let progress = schedule.run_budgeted(&mut world, Duration::ZERO);
+        assert!(progress.is_completed());
+        assert!(!schedule.is_budgeted_run_in_progress());
+        assert_eq!(world.resource::<Log>().0, vec![0, 1, 2]);
+
+        let progress = schedule.run_budgeted(&mut world, Duration::MAX);
+        assert!(progress.is_completed());
+        assert_eq!(world.resource::<Log>().0, vec![0, 1, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    fn run_timed() {
+        #[derive(Resource)]
+        struct Marker;
+
+        fn slow() {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        fn insert_marker(mut commands: Commands) {
+            commands.insert_resource(Marker);
+        }
+        fn skipped() {}
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((slow, insert_marker, skipped.run_if(|| false)).chain());
+        let mut world = World::new();
+
+        let timings = schedule.run_timed(&mut world);
+        assert!(world.contains_resource::<Marker>());
+        let names: Vec<_> = timings.iter().map(|timing| &*timing.name).collect();
+        // the commands are applied by the `apply_deferred` system inserted by `chain`
+        assert_eq!(names.len(), 3);
+        assert!(names[0].ends_with("slow"));
+        assert!(names[1].ends_with("insert_marker"));
+        assert!(names[2].ends_with("apply_deferred"));
+        assert!(timings[0].duration >= Duration::from_millis(5));
+    }
 }
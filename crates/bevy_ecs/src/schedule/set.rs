@@ -148,6 +148,36 @@ impl SystemSet for AnonymousSet {
     }
 }
 
+/// The [`SystemSet`] of the systems of a child schedule, added to its parent schedule with
+/// [`Schedule::add_child`](super::Schedule::add_child).
+///
+/// Configure this set in the parent schedule to order the child schedule or add run conditions
+/// to it as a unit.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ChildScheduleSet(pub InternedScheduleLabel);
+
+impl ChildScheduleSet {
+    /// Returns the set of the child schedule with the given `label`.
+    pub fn new(label: impl ScheduleLabel) -> Self {
+        Self(label.intern())
+    }
+}
+
+impl SystemSet for ChildScheduleSet {
+    fn dyn_clone(&self) -> Box<dyn SystemSet> {
+        Box::new(*self)
+    }
+
+    fn as_dyn_eq(&self) -> &dyn DynEq {
+        self
+    }
+
+    fn dyn_hash(&self, mut state: &mut dyn Hasher) {
+        TypeId::of::<Self>().hash(&mut state);
+        self.hash(&mut state);
+    }
+}
+
 /// Types that can be converted into a [`SystemSet`].
 pub trait IntoSystemSet<Marker>: Sized {
     /// The type of [`SystemSet`] this instance converts into.
@@ -1,10 +1,15 @@
 use fixedbitset::FixedBitSet;
 use std::any::TypeId;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::{
-    schedule::{InternedScheduleLabel, NodeId, Schedule, ScheduleLabel},
-    system::{IntoSystem, ResMut, Resource},
+    schedule::{
+        BoxedCondition, Condition, InternedScheduleLabel, InternedSystemSet, IntoSystemSet, NodeId,
+        Schedule, ScheduleLabel, SystemSet,
+    },
+    system::{IntoSystem, ResMut, Resource, System},
+    world::World,
 };
 use bevy_utils::{
     tracing::{error, info, warn},
@@ -53,6 +58,11 @@ enum SystemBehavior {
     /// When [`Action::Step`] this system will be stepped
     /// When [`Action::Continue`] this system will be run
     Continue,
+
+    /// Behaves like [`SystemBehavior::Break`] when the condition at this index
+    /// in `Stepping::conditions` was met this frame, and like
+    /// [`SystemBehavior::Continue`] otherwise
+    ConditionalBreak(usize),
 }
 
 // schedule_order index, and schedule start point
@@ -64,10 +74,12 @@ struct Cursor {
     pub system: usize,
 }
 
-// Two methods of referring to Systems, via TypeId, or per-Schedule NodeId
+// Three methods of referring to Systems, via TypeId, per-Schedule NodeId, or
+// all systems in a SystemSet
 enum SystemIdentifier {
     Type(TypeId),
     Node(NodeId),
+    Set(InternedSystemSet),
 }
 
 /// Updates to [`Stepping.schedule_states`] that will be applied at the start
@@ -85,6 +97,29 @@ enum Update {
     SetBehavior(InternedScheduleLabel, SystemIdentifier, SystemBehavior),
     /// Clear any system-specific behavior for this schedule & system
     ClearBehavior(InternedScheduleLabel, SystemIdentifier),
+    /// Set the system set stepped by [`Action::Step`]
+    SetStepSet(Option<InternedSystemSet>),
+}
+
+/// A condition for a [`SystemBehavior::ConditionalBreak`]
+struct BreakCondition {
+    schedule: InternedScheduleLabel,
+    condition: BoxedCondition,
+    initialized: bool,
+}
+
+// drop the conditions of the conditional breakpoints in this schedule
+fn drop_conditions(
+    conditions: &mut [Option<BreakCondition>],
+    conditions_met: &mut FixedBitSet,
+    label: InternedScheduleLabel,
+) {
+    for (index, condition) in conditions.iter_mut().enumerate() {
+        if condition.as_ref().is_some_and(|c| c.schedule == label) {
+            *condition = None;
+            conditions_met.set(index, false);
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -111,6 +146,51 @@ pub struct Stepping {
 
     // Updates apply at the start of the next render frame
     updates: Vec<Update>,
+
+    // conditions of conditional breakpoints, indexed by
+    // [`SystemBehavior::ConditionalBreak`]; `None` once their schedule has
+    // been cleared
+    conditions: Vec<Option<BreakCondition>>,
+
+    // indices in [`conditions`] of the conditions met this frame
+    conditions_met: FixedBitSet,
+
+    // systems in this set are stepped together by [`Action::Step`]
+    step_set: Option<InternedSystemSet>,
+
+    // the last breakpoint execution stopped at
+    last_breakpoint: Option<(InternedScheduleLabel, NodeId)>,
+}
+
+/// A breakpoint hit by [`Stepping`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakpointHit {
+    /// The schedule containing the system.
+    pub schedule: InternedScheduleLabel,
+    /// The system execution stopped before.
+    pub system: NodeId,
+    /// The name of the system.
+    pub name: Cow<'static, str>,
+}
+
+/// A snapshot of the [`Stepping`] state, for debugger front-ends.
+///
+/// This resource is updated each time a schedule with stepping enabled runs.
+/// Unlike [`Stepping`], it can be read without mutable access, and while
+/// stepping is disabled it still reports the last breakpoint that was hit.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct SteppingDiagnostics {
+    /// Whether stepping is enabled.
+    pub enabled: bool,
+    /// The schedules with stepping enabled, in the order they run.
+    pub schedules: Vec<InternedScheduleLabel>,
+    /// The schedule and system that will run next when stepping, see
+    /// [`Stepping::cursor`].
+    pub cursor: Option<(InternedScheduleLabel, NodeId)>,
+    /// The name of the system under the cursor.
+    pub cursor_system: Option<Cow<'static, str>>,
+    /// The last breakpoint execution stopped at.
+    pub last_breakpoint: Option<BreakpointHit>,
 }
 
 impl std::fmt::Debug for Stepping {
@@ -230,6 +310,23 @@ impl Stepping {
     ///
     /// NOTE: This will have no impact unless stepping has been enabled
     pub fn step_frame(&mut self) -> &mut Self {
+        self.updates.push(Update::SetStepSet(None));
+        self.updates.push(Update::SetAction(Action::Step));
+        self
+    }
+
+    /// Run the next system during the next render frame, along with the
+    /// systems following it for as long as they are in the provided set
+    ///
+    /// This steps over a whole [`SystemSet`](crate::schedule::SystemSet) at
+    /// once when the cursor is on its first system.  The systems of the set
+    /// must be contiguous in the schedule; use `.chain()` or ordering
+    /// constraints to ensure it.
+    ///
+    /// NOTE: This will have no impact unless stepping has been enabled
+    pub fn step_set<Marker>(&mut self, set: impl IntoSystemSet<Marker>) -> &mut Self {
+        let set = set.into_system_set().intern();
+        self.updates.push(Update::SetStepSet(Some(set)));
         self.updates.push(Update::SetAction(Action::Step));
         self
     }
@@ -345,6 +442,145 @@ impl Stepping {
         self
     }
 
+    /// Add a breakpoint for every system in the set
+    ///
+    /// Systems added to the set later in the same schedule are not affected
+    /// until this is called again.
+    pub fn set_breakpoint_set<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        set: impl IntoSystemSet<Marker>,
+    ) -> &mut Self {
+        let set = set.into_system_set().intern();
+        self.updates.push(Update::SetBehavior(
+            schedule.intern(),
+            SystemIdentifier::Set(set),
+            SystemBehavior::Break,
+        ));
+        self
+    }
+
+    /// Add a breakpoint for every system in the set that only stops execution
+    /// in frames where the condition is met
+    ///
+    /// The condition is evaluated before the schedule runs, each frame
+    /// stepping is enabled.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::schedule::{ScheduleLabel, Stepping};
+    /// # #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+    /// # struct FixedUpdate;
+    /// #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    /// struct PhysicsSet;
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity(f32);
+    ///
+    /// let mut stepping = Stepping::new();
+    /// stepping
+    ///     .add_schedule(FixedUpdate)
+    ///     .enable()
+    ///     .set_conditional_breakpoint(
+    ///         FixedUpdate,
+    ///         PhysicsSet,
+    ///         |query: Query<(), Changed<Velocity>>| !query.is_empty(),
+    ///     );
+    /// ```
+    pub fn set_conditional_breakpoint<Marker, ConditionMarker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        set: impl IntoSystemSet<Marker>,
+        condition: impl Condition<ConditionMarker>,
+    ) -> &mut Self {
+        let schedule = schedule.intern();
+        let set = set.into_system_set().intern();
+        let condition = IntoSystem::into_system(condition);
+        assert!(
+            condition.is_send(),
+            "Condition `{}` accesses `NonSend` resources. This is not currently supported.",
+            condition.name()
+        );
+
+        let index = self.conditions.len();
+        self.conditions.push(Some(BreakCondition {
+            schedule,
+            condition: Box::new(condition),
+            initialized: false,
+        }));
+        self.conditions_met.grow(self.conditions.len());
+        self.updates.push(Update::SetBehavior(
+            schedule,
+            SystemIdentifier::Set(set),
+            SystemBehavior::ConditionalBreak(index),
+        ));
+        self
+    }
+
+    /// Clear any behavior set for the systems in the set
+    pub fn clear_set<Marker>(
+        &mut self,
+        schedule: impl ScheduleLabel,
+        set: impl IntoSystemSet<Marker>,
+    ) -> &mut Self {
+        let set = set.into_system_set().intern();
+        self.updates.push(Update::ClearBehavior(
+            schedule.intern(),
+            SystemIdentifier::Set(set),
+        ));
+        self
+    }
+
+    /// Return the last breakpoint execution stopped at, if any
+    pub fn last_breakpoint(&self) -> Option<(InternedScheduleLabel, NodeId)> {
+        self.last_breakpoint
+    }
+
+    /// Return a snapshot of the stepping state
+    pub fn diagnostics(&self) -> SteppingDiagnostics {
+        let cursor = self.cursor();
+        let system_name = |(label, node): (InternedScheduleLabel, NodeId)| {
+            self.schedule_states.get(&label)?.system_name(node)
+        };
+        SteppingDiagnostics {
+            enabled: self.is_enabled(),
+            schedules: self.schedule_order.clone(),
+            cursor,
+            cursor_system: cursor.and_then(system_name),
+            last_breakpoint: self
+                .last_breakpoint
+                .map(|(schedule, system)| BreakpointHit {
+                    schedule,
+                    system,
+                    name: system_name((schedule, system)).unwrap_or_default(),
+                }),
+        }
+    }
+
+    /// Evaluate the conditions of the conditional breakpoints in this
+    /// schedule
+    ///
+    /// Note: This is called by [`Schedule::run`] before
+    /// [`Stepping::skipped_systems`], which uses the results.
+    pub fn evaluate_breakpoint_conditions(&mut self, schedule: &Schedule, world: &mut World) {
+        if self.action == Action::RunAll {
+            return;
+        }
+
+        let label = schedule.label();
+        for (index, condition) in self.conditions.iter_mut().enumerate() {
+            let Some(condition) = condition.as_mut().filter(|c| c.schedule == label) else {
+                continue;
+            };
+            if !condition.initialized {
+                condition.condition.initialize(world);
+                condition.initialized = true;
+            }
+            let met = condition.condition.run((), world);
+            self.conditions_met.set(index, met);
+        }
+    }
+
     /// Clear any behavior set for the system
     pub fn clear_system<Marker>(
         &mut self,
@@ -413,6 +649,7 @@ impl Stepping {
             match update {
                 Update::SetAction(Action::RunAll) => {
                     self.action = Action::RunAll;
+                    self.conditions_met.clear();
                     reset_cursor = true;
                 }
                 Update::SetAction(action) => {
@@ -468,13 +705,17 @@ impl Stepping {
                 }
                 Update::RemoveSchedule(label) => {
                     self.schedule_states.remove(&label);
+                    drop_conditions(&mut self.conditions, &mut self.conditions_met, label);
                     if let Some(index) = self.schedule_order.iter().position(|l| l == &label) {
                         self.schedule_order.remove(index);
                     }
                     reset_cursor = true;
                 }
                 Update::ClearSchedule(label) => match self.schedule_states.get_mut(&label) {
-                    Some(state) => state.clear_behaviors(),
+                    Some(state) => {
+                        state.clear_behaviors();
+                        drop_conditions(&mut self.conditions, &mut self.conditions_met, label);
+                    }
                     None => {
                         warn!(
                             "stepping is not enabled for schedule {:?}; \
@@ -507,6 +748,7 @@ impl Stepping {
                         }
                     }
                 }
+                Update::SetStepSet(set) => self.step_set = set,
             }
         }
 
@@ -561,8 +803,29 @@ impl Stepping {
         // cursor schedule, we'll run the schedule with the waiting action.
         let cursor = self.cursor;
         let (skip_list, next_system) = if index == cursor.schedule {
-            let (skip_list, next_system) =
-                state.skipped_systems(schedule, cursor.system, self.action);
+            // systems of the step set are stepped together
+            let step_set = self
+                .step_set
+                .filter(|_| self.action == Action::Step)
+                .map(|set| {
+                    schedule
+                        .systems()
+                        .unwrap()
+                        .enumerate()
+                        .filter(|(_, (node_id, _))| schedule.graph().is_in_set(*node_id, set))
+                        .map(|(i, _)| i)
+                        .collect::<FixedBitSet>()
+                });
+            let (skip_list, next_system) = state.skipped_systems(
+                schedule,
+                cursor.system,
+                self.action,
+                step_set.as_ref(),
+                &self.conditions_met,
+            );
+            if let Some(node_id) = state.hit_breakpoint.take() {
+                self.last_breakpoint = Some((label, node_id));
+            }
 
             // if we just stepped this schedule, then we'll switch the action
             // to be waiting
@@ -573,7 +836,8 @@ impl Stepping {
         } else {
             // we're not supposed to run any systems in this schedule, so pull
             // the skip list, but ignore any changes it makes to the cursor.
-            let (skip_list, _) = state.skipped_systems(schedule, 0, Action::Waiting);
+            let (skip_list, _) =
+                state.skipped_systems(schedule, 0, Action::Waiting, None, &self.conditions_met);
             (skip_list, Some(cursor.system))
         };
 
@@ -613,12 +877,23 @@ struct ScheduleState {
     /// [`NodeId`]s to the caller.
     node_ids: Vec<NodeId>,
 
+    /// names of the systems in `node_ids`, for [`SteppingDiagnostics`]
+    system_names: Vec<Cow<'static, str>>,
+
     /// changes to system behavior that should be applied the next time
     /// [`ScheduleState::skipped_systems()`] is called
     behavior_updates: TypeIdMap<Option<SystemBehavior>>,
 
+    /// changes to the behavior of all systems in a set, applied after
+    /// `behavior_updates` in the order they were made
+    set_behavior_updates: Vec<(InternedSystemSet, Option<SystemBehavior>)>,
+
     /// This field contains the first steppable system in the schedule.
     first: Option<usize>,
+
+    /// the breakpoint the last call to [`ScheduleState::skipped_systems()`]
+    /// stopped at
+    hit_breakpoint: Option<NodeId>,
 }
 
 impl ScheduleState {
@@ -635,6 +910,9 @@ impl ScheduleState {
             SystemIdentifier::Type(type_id) => {
                 self.behavior_updates.insert(type_id, Some(behavior));
             }
+            SystemIdentifier::Set(set) => {
+                self.set_behavior_updates.push((set, Some(behavior)));
+            }
         }
     }
 
@@ -649,6 +927,9 @@ impl ScheduleState {
             SystemIdentifier::Type(type_id) => {
                 self.behavior_updates.insert(type_id, None);
             }
+            SystemIdentifier::Set(set) => {
+                self.set_behavior_updates.push((set, None));
+            }
         }
     }
 
@@ -656,9 +937,16 @@ impl ScheduleState {
     fn clear_behaviors(&mut self) {
         self.behaviors.clear();
         self.behavior_updates.clear();
+        self.set_behavior_updates.clear();
         self.first = None;
     }
 
+    // look up the name of a system in this schedule
+    fn system_name(&self, node_id: NodeId) -> Option<Cow<'static, str>> {
+        let index = self.node_ids.iter().position(|id| *id == node_id)?;
+        self.system_names.get(index).cloned()
+    }
+
     // apply system behavior updates by looking up the node id of the system in
     // the schedule, and updating `systems`
     fn apply_behavior_updates(&mut self, schedule: &Schedule) {
@@ -681,6 +969,19 @@ impl ScheduleState {
         }
         self.behavior_updates.clear();
 
+        // Set membership is also only known to the schedule.
+        for (set, behavior) in self.set_behavior_updates.drain(..) {
+            for (node_id, _) in schedule.systems().unwrap() {
+                if !schedule.graph().is_in_set(node_id, set) {
+                    continue;
+                }
+                match behavior {
+                    None => self.behaviors.remove(&node_id),
+                    Some(behavior) => self.behaviors.insert(node_id, behavior),
+                };
+            }
+        }
+
         #[cfg(test)]
         debug!("apply_updates(): {:?}", self.behaviors);
     }
@@ -690,6 +991,8 @@ impl ScheduleState {
         schedule: &Schedule,
         start: usize,
         mut action: Action,
+        step_set: Option<&FixedBitSet>,
+        conditions_met: &FixedBitSet,
     ) -> (FixedBitSet, Option<usize>) {
         use std::cmp::Ordering;
 
@@ -697,12 +1000,17 @@ impl ScheduleState {
         // schedule
         if self.node_ids.len() != schedule.systems_len() {
             self.node_ids.clone_from(&schedule.executable().system_ids);
+            self.system_names = schedule
+                .systems()
+                .unwrap()
+                .map(|(_, system)| system.name())
+                .collect();
         }
 
         // Now that we have the schedule, apply any pending system behavior
         // updates.  The schedule is required to map from system `TypeId` to
         // `NodeId`.
-        if !self.behavior_updates.is_empty() {
+        if !self.behavior_updates.is_empty() || !self.set_behavior_updates.is_empty() {
             self.apply_behavior_updates(schedule);
         }
 
@@ -723,10 +1031,15 @@ impl ScheduleState {
         let mut pos = start;
 
         for (i, (node_id, _system)) in schedule.systems().unwrap().enumerate() {
-            let behavior = self
-                .behaviors
-                .get(&node_id)
-                .unwrap_or(&SystemBehavior::Continue);
+            let behavior = match self.behaviors.get(&node_id) {
+                Some(SystemBehavior::ConditionalBreak(index))
+                    if conditions_met.contains(*index) =>
+                {
+                    &SystemBehavior::Break
+                }
+                None | Some(SystemBehavior::ConditionalBreak(_)) => &SystemBehavior::Continue,
+                Some(behavior) => behavior,
+            };
 
             #[cfg(test)]
             debug!(
@@ -771,14 +1084,18 @@ impl ScheduleState {
                     Ordering::Less => skip.insert(i),
                     Ordering::Equal => {
                         pos += 1;
-                        action = Action::Waiting;
+                        // keep stepping through the rest of the step set
+                        if !step_set.is_some_and(|set| set.contains(i) && set.contains(i + 1)) {
+                            action = Action::Waiting;
+                        }
                     }
                     Ordering::Greater => unreachable!(),
                 },
                 // If we're continuing, and the step behavior is continue, we
                 // want to skip any systems prior to our start position.  That's
                 // where the stepping frame left off last time we ran anything.
-                (Action::Continue, SystemBehavior::Continue) => {
+                (Action::Continue, SystemBehavior::Continue)
+                | (Action::Continue, SystemBehavior::ConditionalBreak(_)) => {
                     if i < start {
                         skip.insert(i);
                     }
@@ -799,6 +1116,7 @@ impl ScheduleState {
                         // system under the cursor.
                         if i > start {
                             action = Action::Waiting;
+                            self.hit_breakpoint = Some(node_id);
                         }
                     }
                 }
@@ -1399,6 +1717,92 @@ mod tests {
         );
     }
 
+    #[derive(SystemSet, Clone, Debug, PartialEq, Eq, Hash)]
+    struct TestSet;
+
+    #[test]
+    fn breakpoint_set_then_step_set() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((first_system, (second_system, third_system).in_set(TestSet)).chain());
+        schedule.initialize(&mut world).unwrap();
+        let second_node = schedule.systems().unwrap().nth(1).unwrap().0;
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(TestSchedule)
+            .enable()
+            .set_breakpoint_set(TestSchedule, TestSet)
+            .continue_frame();
+
+        // continuing stops at the first system of the set
+        assert_schedule_runs!(&schedule, &mut stepping, first_system);
+        assert_eq!(
+            stepping.last_breakpoint(),
+            Some((TestSchedule.intern(), second_node))
+        );
+
+        // stepping the set runs all of its systems at once
+        stepping.step_set(TestSet);
+        assert_schedule_runs!(&schedule, &mut stepping, second_system, third_system);
+
+        // clearing the set removes its breakpoints
+        stepping.clear_set(TestSchedule, TestSet).continue_frame();
+        assert_schedule_runs!(
+            &schedule,
+            &mut stepping,
+            first_system,
+            second_system,
+            third_system
+        );
+    }
+
+    #[test]
+    fn conditional_breakpoint() {
+        #[derive(Resource)]
+        struct Pause(bool);
+
+        let mut world = World::new();
+        world.insert_resource(Pause(false));
+        world.init_resource::<SteppingDiagnostics>();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((first_system, second_system.in_set(TestSet)).chain());
+        schedule.initialize(&mut world).unwrap();
+
+        let mut stepping = Stepping::new();
+        stepping
+            .add_schedule(TestSchedule)
+            .enable()
+            .set_conditional_breakpoint(TestSchedule, TestSet, |pause: Res<Pause>| pause.0);
+        world.insert_resource(stepping);
+
+        // the condition isn't met, so the whole frame runs
+        world
+            .resource_mut::<Stepping>()
+            .continue_frame()
+            .next_frame();
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Stepping>().cursor(), None);
+        assert!(world.resource::<SteppingDiagnostics>().enabled);
+        assert!(world
+            .resource::<SteppingDiagnostics>()
+            .last_breakpoint
+            .is_none());
+
+        // the condition is met, so execution stops before second_system
+        world.resource_mut::<Pause>().0 = true;
+        world
+            .resource_mut::<Stepping>()
+            .continue_frame()
+            .next_frame();
+        schedule.run(&mut world);
+        let diagnostics = world.resource::<SteppingDiagnostics>();
+        let hit = diagnostics.last_breakpoint.as_ref().unwrap();
+        assert_eq!(hit.schedule, TestSchedule.intern());
+        assert!(hit.name.ends_with("second_system"));
+        assert_eq!(diagnostics.cursor, Some((hit.schedule, hit.system)));
+    }
+
     #[test]
     fn remove_schedule() {
         let (schedule, _world) = setup();
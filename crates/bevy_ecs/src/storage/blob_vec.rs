@@ -181,6 +181,60 @@ impl BlobVec {
         self.capacity = new_capacity;
     }
 
+    /// Shrinks the capacity of the vector to its length, returning the number of bytes freed.
+    pub fn shrink_to_fit(&mut self) -> usize {
+        if self.item_layout.size() == 0 || self.capacity == self.len {
+            return 0;
+        }
+        let old_layout =
+            array_layout(&self.item_layout, self.capacity).expect("array layout should be valid");
+        let new_layout =
+            array_layout(&self.item_layout, self.len).expect("array layout should be valid");
+        if self.len == 0 {
+            // SAFETY: the data ptr was allocated with `old_layout`, which has a non-zero size
+            unsafe { std::alloc::dealloc(self.get_ptr_mut().as_ptr(), old_layout) };
+            let align = NonZeroUsize::new(self.item_layout.align()).expect("alignment must be > 0");
+            self.data = bevy_ptr::dangling_with_align(align);
+        } else {
+            // SAFETY:
+            // - ptr was allocated via this allocator, with `old_layout`
+            // - `item_layout.size() > 0` and `self.len > 0`, so the new size is non-zero
+            // - the new size is smaller than the old one, so it can't overflow
+            let new_data = unsafe {
+                std::alloc::realloc(self.get_ptr_mut().as_ptr(), old_layout, new_layout.size())
+            };
+            self.data = NonNull::new(new_data).unwrap_or_else(|| handle_alloc_error(new_layout));
+        }
+        self.capacity = self.len;
+        old_layout.size() - new_layout.size()
+    }
+
+    /// Returns the number of bytes allocated by the vector.
+    pub fn allocated_bytes(&self) -> usize {
+        if self.item_layout.size() == 0 {
+            return 0;
+        }
+        array_layout(&self.item_layout, self.capacity)
+            .expect("array layout should be valid")
+            .size()
+    }
+
+    /// Swaps the elements at indices `a` and `b`.
+    ///
+    /// # Safety
+    /// `a` and `b` must be in bounds.
+    pub unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {
+        debug_assert!(a < self.len() && b < self.len());
+        if a == b {
+            return;
+        }
+        let size = self.item_layout.size();
+        let data = self.get_ptr_mut().as_ptr();
+        // SAFETY: `a` and `b` are distinct indices in bounds, so the items they point to are
+        // valid and don't overlap.
+        unsafe { std::ptr::swap_nonoverlapping(data.add(a * size), data.add(b * size), size) };
+    }
+
     /// Initializes the value at `index` to `value`. This function does not do any bounds checking.
     ///
     /// # Safety
@@ -13,21 +13,27 @@
 //!    lookup and regular insertion/removal of components.
 //!  - [`Resources`] - singleton storage for the resources in the world
 //!
+//! The raw memory of a table can be handed to native code or GPU upload paths through a
+//! [`TableView`], fetched with [`World::table_view`].
+//!
 //! # Safety
 //! To avoid trivially unsound use of the APIs in this module, it is explicitly impossible to get a mutable
 //! reference to [`Storages`] from [`World`], and none of the types publicly expose a mutable interface.
 //!
 //! [`World`]: crate::world::World
 //! [`World::storages`]: crate::world::World::storages
+//! [`World::table_view`]: crate::world::World::table_view
 
 mod blob_vec;
 mod resource;
 mod sparse_set;
 mod table;
+mod table_view;
 
 pub use resource::*;
 pub use sparse_set::*;
 pub use table::*;
+pub use table_view::*;
 
 /// The raw data stores of a [`World`](crate::world::World)
 #[derive(Default)]
@@ -41,3 +47,40 @@ pub struct Storages {
     /// Backing storage for `!Send` resources.
     pub non_send_resources: Resources<false>,
 }
+
+/// Shrinks `vec` to fit its length, returning the number of bytes freed.
+pub(crate) fn shrink_vec<T>(vec: &mut Vec<T>) -> usize {
+    let freed = (vec.capacity() - vec.len()) * std::mem::size_of::<T>();
+    vec.shrink_to_fit();
+    freed
+}
+
+/// Returns the number of bytes allocated by `vec`.
+pub(crate) fn vec_allocated_bytes<T>(vec: &Vec<T>) -> usize {
+    vec.capacity() * std::mem::size_of::<T>()
+}
+
+/// Reorders rows in place with `swap`, so that the row at `order[i]` ends up at `i`.
+///
+/// Returns the number of swaps performed, which is zero if `order` is already sorted.
+pub(crate) fn permute_rows(order: &[usize], mut swap: impl FnMut(usize, usize)) -> usize {
+    // `position[row]` is the current row of the item originally at `row`, and `at[row]` is the
+    // original row of the item currently at `row`.
+    let mut position: Vec<usize> = (0..order.len()).collect();
+    let mut at = position.clone();
+    let mut swaps = 0;
+    for (target, &original) in order.iter().enumerate() {
+        let current = position[original];
+        if current == target {
+            continue;
+        }
+        swap(target, current);
+        let displaced = at[target];
+        at[current] = displaced;
+        position[displaced] = current;
+        at[target] = original;
+        position[original] = target;
+        swaps += 1;
+    }
+    swaps
+}
@@ -3,7 +3,12 @@ use crate::change_detection::{MutUntyped, TicksMut};
 use crate::component::{ComponentId, ComponentTicks, Components, Tick, TickCells};
 use crate::storage::{blob_vec::BlobVec, SparseSet};
 use bevy_ptr::{OwningPtr, Ptr, UnsafeCellDeref};
-use std::{cell::UnsafeCell, mem::ManuallyDrop, thread::ThreadId};
+use std::{
+    cell::UnsafeCell,
+    mem::ManuallyDrop,
+    sync::atomic::{AtomicU64, Ordering},
+    thread::ThreadId,
+};
 
 /// The type-erased backing storage and metadata for a single resource within a [`World`].
 ///
@@ -14,6 +19,9 @@ pub struct ResourceData<const SEND: bool> {
     data: ManuallyDrop<BlobVec>,
     added_ticks: UnsafeCell<Tick>,
     changed_ticks: UnsafeCell<Tick>,
+    /// The version of the resource in the high 32 bits, and the changed tick the version was last
+    /// increased for in the low 32 bits. See [`ResourceData::version`].
+    version: AtomicU64,
     type_name: String,
     id: ArchetypeComponentId,
     origin_thread_id: Option<ThreadId>,
@@ -103,6 +111,36 @@ impl<const SEND: bool> ResourceData<SEND> {
         }
     }
 
+    /// Returns the version of the resource, a wrapping counter increased each time the resource
+    /// is found to have changed since its version was last read.
+    ///
+    /// Several changes between two reads increase the version only once, so versions can be
+    /// compared for equality to detect changes, but don't count them.
+    pub fn version(&self) -> u32 {
+        // SAFETY: This is being fetched through a read-only reference to Self, so no other mutable
+        // references to the ticks can exist.
+        let changed = unsafe { self.changed_ticks.read() }.get();
+        let mut packed = self.version.load(Ordering::Acquire);
+        loop {
+            if packed as u32 == changed {
+                return (packed >> 32) as u32;
+            }
+            let version = ((packed >> 32) as u32).wrapping_add(1);
+            let bumped = (u64::from(version) << 32) | u64::from(changed);
+            // Other readers may race to increase the version for the same change, but only one of
+            // them can succeed.
+            match self.version.compare_exchange_weak(
+                packed,
+                bumped,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return version,
+                Err(current) => packed = current,
+            }
+        }
+    }
+
     /// Returns references to the resource and its change ticks, if it exists.
     ///
     /// # Panics
@@ -118,6 +156,7 @@ impl<const SEND: bool> ResourceData<SEND> {
                 TickCells {
                     added: &self.added_ticks,
                     changed: &self.changed_ticks,
+                    fields: &[],
                 },
             )
         })
@@ -244,7 +283,15 @@ impl<const SEND: bool> ResourceData<SEND> {
 
     pub(crate) fn check_change_ticks(&mut self, change_tick: Tick) {
         self.added_ticks.get_mut().check_tick(change_tick);
-        self.changed_ticks.get_mut().check_tick(change_tick);
+        let changed = self.changed_ticks.get_mut();
+        let previous = changed.get();
+        if changed.check_tick(change_tick) {
+            // Clamping the tick isn't a change, so the version follows it without being increased.
+            let packed = self.version.get_mut();
+            if *packed as u32 == previous {
+                *packed = (*packed & !u64::from(u32::MAX)) | u64::from(changed.get());
+            }
+        }
     }
 }
 
@@ -330,6 +377,7 @@ impl<const SEND: bool> Resources<SEND> {
                 data: ManuallyDrop::new(data),
                 added_ticks: UnsafeCell::new(Tick::new(0)),
                 changed_ticks: UnsafeCell::new(Tick::new(0)),
+                version: AtomicU64::new(0),
                 type_name: String::from(component_info.name()),
                 id: f(),
                 origin_thread_id: None,
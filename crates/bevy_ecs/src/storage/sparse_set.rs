@@ -1,7 +1,7 @@
 use crate::{
     component::{ComponentId, ComponentInfo, ComponentTicks, Tick, TickCells},
     entity::Entity,
-    storage::{Column, TableRow},
+    storage::{permute_rows, shrink_vec, vec_allocated_bytes, Column, TableRow},
 };
 use bevy_ptr::{OwningPtr, Ptr};
 use nonmax::NonMaxUsize;
@@ -103,6 +103,18 @@ impl<I: SparseSetIndex, V> SparseArray<I, V> {
         self.values.clear();
     }
 
+    /// Drops the trailing empty values and shrinks the array to fit, returning the number of
+    /// bytes freed.
+    pub(crate) fn shrink_to_fit(&mut self) -> usize {
+        let len = self
+            .values
+            .iter()
+            .rposition(Option::is_some)
+            .map_or(0, |last| last + 1);
+        self.values.truncate(len);
+        shrink_vec(&mut self.values)
+    }
+
     /// Converts the [`SparseArray`] into an immutable variant.
     pub(crate) fn into_immutable(self) -> ImmutableSparseArray<I, V> {
         ImmutableSparseArray {
@@ -233,11 +245,25 @@ impl ComponentSparseSet {
                 TickCells {
                     added: self.dense.get_added_tick_unchecked(dense_index),
                     changed: self.dense.get_changed_tick_unchecked(dense_index),
+                    fields: self.dense.get_field_ticks_unchecked(dense_index),
                 },
             ))
         }
     }
 
+    /// Returns the "changed" ticks of each field of the entity's component value, which are empty
+    /// if the component doesn't track the changes of its fields.
+    ///
+    /// Returns `None` if `entity` does not have a component in the sparse set.
+    #[inline]
+    pub fn get_field_ticks(&self, entity: Entity) -> Option<&[UnsafeCell<Tick>]> {
+        let dense_index = *self.sparse.get(entity.index())?;
+        #[cfg(debug_assertions)]
+        assert_eq!(entity, self.entities[dense_index.as_usize()]);
+        // SAFETY: if the sparse index points to something in the dense vec, it exists
+        unsafe { Some(self.dense.get_field_ticks_unchecked(dense_index)) }
+    }
+
     /// Returns a reference to the "added" tick of the entity's component value.
     ///
     /// Returns `None` if `entity` does not have a component in the sparse set.
@@ -327,6 +353,48 @@ impl ComponentSparseSet {
     pub(crate) fn check_change_ticks(&mut self, change_tick: Tick) {
         self.dense.check_change_ticks(change_tick);
     }
+
+    /// Reorders the dense storage by entity index. Returns the number of values that were moved.
+    pub(crate) fn sort_by_entity(&mut self) -> usize {
+        #[cfg(not(debug_assertions))]
+        let index = |entity: &EntityIndex| *entity;
+        #[cfg(debug_assertions)]
+        let index = |entity: &Entity| entity.index();
+        if self
+            .entities
+            .windows(2)
+            .all(|w| index(&w[0]) <= index(&w[1]))
+        {
+            return 0;
+        }
+        let mut order: Vec<usize> = (0..self.entities.len()).collect();
+        order.sort_unstable_by_key(|&row| index(&self.entities[row]));
+        let moved = permute_rows(&order, |a, b| {
+            self.entities.swap(a, b);
+            // SAFETY: `permute_rows` only swaps rows within the bounds of `order`.
+            unsafe {
+                self.dense
+                    .swap_unchecked(TableRow::from_usize(a), TableRow::from_usize(b));
+            }
+        });
+        for (row, entity) in self.entities.iter().enumerate() {
+            *self.sparse.get_mut(index(entity)).unwrap() = TableRow::from_usize(row);
+        }
+        moved
+    }
+
+    /// Shrinks the allocations of the sparse set to its length, returning the number of bytes
+    /// freed.
+    pub(crate) fn shrink_to_fit(&mut self) -> usize {
+        shrink_vec(&mut self.entities) + self.dense.shrink_to_fit() + self.sparse.shrink_to_fit()
+    }
+
+    /// Returns the number of bytes allocated by the sparse set.
+    pub fn allocated_bytes(&self) -> usize {
+        vec_allocated_bytes(&self.entities)
+            + self.dense.allocated_bytes()
+            + vec_allocated_bytes(&self.sparse.values)
+    }
 }
 
 /// A data structure that blends dense and sparse storage
@@ -607,6 +675,11 @@ impl SparseSets {
         self.sets.get_mut(component_id)
     }
 
+    /// Mutably iterates over all of the [`ComponentSparseSet`]s.
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut ComponentSparseSet> {
+        self.sets.values_mut()
+    }
+
     /// Clear entities stored in each [`ComponentSparseSet`]
     pub(crate) fn clear_entities(&mut self) {
         for set in self.sets.values_mut() {
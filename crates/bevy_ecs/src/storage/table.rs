@@ -2,7 +2,10 @@ use crate::{
     component::{ComponentId, ComponentInfo, ComponentTicks, Components, Tick, TickCells},
     entity::Entity,
     query::DebugCheckedUnwrap,
-    storage::{blob_vec::BlobVec, ImmutableSparseSet, SparseSet},
+    storage::{
+        blob_vec::BlobVec, permute_rows, shrink_vec, vec_allocated_bytes, ImmutableSparseSet,
+        SparseSet,
+    },
 };
 use bevy_ptr::{OwningPtr, Ptr, PtrMut, UnsafeCellDeref};
 use bevy_utils::HashMap;
@@ -10,6 +13,7 @@ use std::alloc::Layout;
 use std::{
     cell::UnsafeCell,
     ops::{Index, IndexMut},
+    sync::atomic::{AtomicU32, Ordering},
 };
 
 /// An opaque unique ID for a [`Table`] within a [`World`].
@@ -152,6 +156,76 @@ pub struct Column {
     data: BlobVec,
     added_ticks: Vec<UnsafeCell<Tick>>,
     changed_ticks: Vec<UnsafeCell<Tick>>,
+    field_ticks: Option<FieldTicks>,
+}
+
+/// The "changed" ticks of each field of the values of a [`Column`], for components tracking the
+/// changes of their fields, see [`ComponentInfo::tracked_fields`].
+#[derive(Debug)]
+pub(crate) struct FieldTicks {
+    fields: usize,
+    /// The ticks of the fields of each row, one row after the other.
+    ticks: Vec<UnsafeCell<Tick>>,
+}
+
+impl FieldTicks {
+    fn with_capacity(fields: usize, capacity: usize) -> Self {
+        Self {
+            fields,
+            ticks: Vec::with_capacity(fields * capacity),
+        }
+    }
+
+    /// Returns the ticks of the fields of `row`.
+    ///
+    /// # Safety
+    /// `row` must be in bounds.
+    #[inline]
+    pub(crate) unsafe fn row_unchecked(&self, row: TableRow) -> &[UnsafeCell<Tick>] {
+        let start = row.as_usize() * self.fields;
+        debug_assert!(start + self.fields <= self.ticks.len());
+        self.ticks.get_unchecked(start..start + self.fields)
+    }
+
+    fn row_mut(&mut self, row: TableRow) -> &mut [UnsafeCell<Tick>] {
+        let start = row.as_usize() * self.fields;
+        &mut self.ticks[start..start + self.fields]
+    }
+
+    fn push(&mut self, tick: Tick) {
+        self.ticks
+            .extend(std::iter::repeat_with(|| UnsafeCell::new(tick)).take(self.fields));
+    }
+
+    fn set(&mut self, row: TableRow, tick: Tick) {
+        for field in self.row_mut(row) {
+            *field.get_mut() = tick;
+        }
+    }
+
+    fn swap(&mut self, a: TableRow, b: TableRow) {
+        for field in 0..self.fields {
+            self.ticks.swap(
+                a.as_usize() * self.fields + field,
+                b.as_usize() * self.fields + field,
+            );
+        }
+    }
+
+    /// Removes the ticks of `row`, replacing them with the ticks of the last row.
+    fn swap_remove(&mut self, row: TableRow) {
+        let last = TableRow::from_usize(self.ticks.len() / self.fields - 1);
+        self.swap(row, last);
+        self.ticks.truncate(last.as_usize() * self.fields);
+    }
+
+    /// Copies the ticks of `src_row` in `other` to `dst_row`, then removes them from `other`.
+    fn take_from(&mut self, other: &mut FieldTicks, src_row: TableRow, dst_row: TableRow) {
+        for (dst, src) in self.row_mut(dst_row).iter_mut().zip(other.row_mut(src_row)) {
+            *dst.get_mut() = *src.get_mut();
+        }
+        other.swap_remove(src_row);
+    }
 }
 
 impl Column {
@@ -163,6 +237,10 @@ impl Column {
             data: unsafe { BlobVec::new(component_info.layout(), component_info.drop(), capacity) },
             added_ticks: Vec::with_capacity(capacity),
             changed_ticks: Vec::with_capacity(capacity),
+            field_ticks: component_info
+                .tracked_fields()
+                .filter(|fields| !fields.is_empty())
+                .map(|fields| FieldTicks::with_capacity(fields.len(), capacity)),
         }
     }
 
@@ -187,6 +265,9 @@ impl Column {
             .changed_ticks
             .get_unchecked_mut(row.as_usize())
             .get_mut() = tick;
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks.set(row, tick);
+        }
     }
 
     /// Writes component data to the column at given row.
@@ -202,6 +283,9 @@ impl Column {
             .changed_ticks
             .get_unchecked_mut(row.as_usize())
             .get_mut() = change_tick;
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks.set(row, change_tick);
+        }
     }
 
     /// Gets the current number of elements stored in the column.
@@ -231,6 +315,9 @@ impl Column {
         self.data.swap_remove_and_drop_unchecked(row.as_usize());
         self.added_ticks.swap_remove(row.as_usize());
         self.changed_ticks.swap_remove(row.as_usize());
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks.swap_remove(row);
+        }
     }
 
     /// Removes an element from the [`Column`] and returns it and its change detection ticks.
@@ -253,6 +340,9 @@ impl Column {
         let data = self.data.swap_remove_and_forget_unchecked(row.as_usize());
         let added = self.added_ticks.swap_remove(row.as_usize()).into_inner();
         let changed = self.changed_ticks.swap_remove(row.as_usize()).into_inner();
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks.swap_remove(row);
+        }
         (data, ComponentTicks { added, changed })
     }
 
@@ -281,6 +371,11 @@ impl Column {
             other.added_ticks.swap_remove(src_row.as_usize());
         *self.changed_ticks.get_unchecked_mut(dst_row.as_usize()) =
             other.changed_ticks.swap_remove(src_row.as_usize());
+        if let (Some(field_ticks), Some(other_field_ticks)) =
+            (&mut self.field_ticks, &mut other.field_ticks)
+        {
+            field_ticks.take_from(other_field_ticks, src_row, dst_row);
+        }
     }
 
     /// Pushes a new value onto the end of the [`Column`].
@@ -291,6 +386,9 @@ impl Column {
         self.data.push(ptr);
         self.added_ticks.push(UnsafeCell::new(ticks.added));
         self.changed_ticks.push(UnsafeCell::new(ticks.changed));
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks.push(ticks.changed);
+        }
     }
 
     #[inline]
@@ -298,6 +396,47 @@ impl Column {
         self.data.reserve_exact(additional);
         self.added_ticks.reserve_exact(additional);
         self.changed_ticks.reserve_exact(additional);
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks
+                .ticks
+                .reserve_exact(additional * field_ticks.fields);
+        }
+    }
+
+    /// Shrinks the capacity of the column to its length, returning the number of bytes freed.
+    pub(crate) fn shrink_to_fit(&mut self) -> usize {
+        self.data.shrink_to_fit()
+            + shrink_vec(&mut self.added_ticks)
+            + shrink_vec(&mut self.changed_ticks)
+            + self
+                .field_ticks
+                .as_mut()
+                .map_or(0, |field_ticks| shrink_vec(&mut field_ticks.ticks))
+    }
+
+    /// Returns the number of bytes allocated by the column, including its change detection ticks.
+    pub fn allocated_bytes(&self) -> usize {
+        self.data.allocated_bytes()
+            + vec_allocated_bytes(&self.added_ticks)
+            + vec_allocated_bytes(&self.changed_ticks)
+            + self
+                .field_ticks
+                .as_ref()
+                .map_or(0, |field_ticks| vec_allocated_bytes(&field_ticks.ticks))
+    }
+
+    /// Swaps the components and change detection ticks at rows `a` and `b`.
+    ///
+    /// # Safety
+    /// `a` and `b` must be in bounds.
+    #[inline]
+    pub(crate) unsafe fn swap_unchecked(&mut self, a: TableRow, b: TableRow) {
+        self.data.swap_unchecked(a.as_usize(), b.as_usize());
+        self.added_ticks.swap(a.as_usize(), b.as_usize());
+        self.changed_ticks.swap(a.as_usize(), b.as_usize());
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks.swap(a, b);
+        }
     }
 
     /// Fetches the data pointer to the first element of the [`Column`].
@@ -356,6 +495,7 @@ impl Column {
                     TickCells {
                         added: self.added_ticks.get_unchecked(row.as_usize()),
                         changed: self.changed_ticks.get_unchecked(row.as_usize()),
+                        fields: self.get_field_ticks_unchecked(row),
                     },
                 )
             })
@@ -468,6 +608,26 @@ impl Column {
         self.changed_ticks.get_unchecked(row.as_usize())
     }
 
+    /// Fetches the "changed" ticks of each field of the value at `row`, in the order of
+    /// [`ComponentInfo::tracked_fields`]. This is empty if the component doesn't track the changes
+    /// of its fields.
+    ///
+    /// # Safety
+    /// `row` must be within the range `[0, self.len())`.
+    #[inline]
+    pub unsafe fn get_field_ticks_unchecked(&self, row: TableRow) -> &[UnsafeCell<Tick>] {
+        match &self.field_ticks {
+            Some(field_ticks) => field_ticks.row_unchecked(row),
+            None => &[],
+        }
+    }
+
+    /// Returns the field ticks of the column, if its component tracks the changes of its fields.
+    #[inline]
+    pub(crate) fn field_ticks(&self) -> Option<&FieldTicks> {
+        self.field_ticks.as_ref()
+    }
+
     /// Fetches the change detection ticks for the value at `row`. Unlike [`Column::get_ticks`]
     /// this function does not do any bounds checking.
     ///
@@ -490,6 +650,9 @@ impl Column {
         self.data.clear();
         self.added_ticks.clear();
         self.changed_ticks.clear();
+        if let Some(field_ticks) = &mut self.field_ticks {
+            field_ticks.ticks.clear();
+        }
     }
 
     #[inline]
@@ -500,6 +663,11 @@ impl Column {
         for component_ticks in &mut self.changed_ticks {
             component_ticks.get_mut().check_tick(change_tick);
         }
+        if let Some(field_ticks) = &mut self.field_ticks {
+            for field_tick in &mut field_ticks.ticks {
+                field_tick.get_mut().check_tick(change_tick);
+            }
+        }
     }
 }
 
@@ -541,6 +709,7 @@ impl TableBuilder {
         Table {
             columns: self.columns.into_immutable(),
             entities: Vec::with_capacity(self.capacity),
+            entity_changed_ticks: Vec::with_capacity(self.capacity),
         }
     }
 }
@@ -560,6 +729,10 @@ impl TableBuilder {
 pub struct Table {
     columns: ImmutableSparseSet<ComponentId, Column>,
     entities: Vec<Entity>,
+    /// The last time any component of the entity in each row was written, including its sparse
+    /// set components. Atomic, since systems writing different components of the same entity
+    /// may run in parallel.
+    entity_changed_ticks: Vec<AtomicU32>,
 }
 
 impl Table {
@@ -580,6 +753,7 @@ impl Table {
         }
         let is_last = row.as_usize() == self.entities.len() - 1;
         self.entities.swap_remove(row.as_usize());
+        self.entity_changed_ticks.swap_remove(row.as_usize());
         if is_last {
             None
         } else {
@@ -603,6 +777,8 @@ impl Table {
         debug_assert!(row.as_usize() < self.entity_count());
         let is_last = row.as_usize() == self.entities.len() - 1;
         let new_row = new_table.allocate(self.entities.swap_remove(row.as_usize()));
+        new_table.entity_changed_ticks[new_row.as_usize()] =
+            self.entity_changed_ticks.swap_remove(row.as_usize());
         for (component_id, column) in self.columns.iter_mut() {
             if let Some(new_column) = new_table.get_column_mut(*component_id) {
                 new_column.initialize_from_unchecked(column, row, new_row);
@@ -635,6 +811,8 @@ impl Table {
         debug_assert!(row.as_usize() < self.entity_count());
         let is_last = row.as_usize() == self.entities.len() - 1;
         let new_row = new_table.allocate(self.entities.swap_remove(row.as_usize()));
+        new_table.entity_changed_ticks[new_row.as_usize()] =
+            self.entity_changed_ticks.swap_remove(row.as_usize());
         for (component_id, column) in self.columns.iter_mut() {
             if let Some(new_column) = new_table.get_column_mut(*component_id) {
                 new_column.initialize_from_unchecked(column, row, new_row);
@@ -666,6 +844,8 @@ impl Table {
         debug_assert!(row.as_usize() < self.entity_count());
         let is_last = row.as_usize() == self.entities.len() - 1;
         let new_row = new_table.allocate(self.entities.swap_remove(row.as_usize()));
+        new_table.entity_changed_ticks[new_row.as_usize()] =
+            self.entity_changed_ticks.swap_remove(row.as_usize());
         for (component_id, column) in self.columns.iter_mut() {
             new_table
                 .get_column_mut(*component_id)
@@ -682,6 +862,36 @@ impl Table {
         }
     }
 
+    /// Returns the last time any component of the entity at `row` was written, including its
+    /// sparse set components, or `None` if `row` is out of bounds.
+    ///
+    /// Unlike the ticks of each [`Column`], this tick doesn't need every component of the
+    /// entity to be checked to know whether it changed.
+    #[inline]
+    pub fn get_entity_changed_tick(&self, row: TableRow) -> Option<Tick> {
+        self.entity_changed_ticks
+            .get(row.as_usize())
+            .map(|tick| Tick::new(tick.load(Ordering::Relaxed)))
+    }
+
+    /// Fetches the slice of ticks returned by
+    /// [`get_entity_changed_tick`](Self::get_entity_changed_tick) for each row.
+    #[inline]
+    pub(crate) fn get_entity_changed_ticks_slice(&self) -> &[AtomicU32] {
+        &self.entity_changed_ticks
+    }
+
+    /// Records that a component of the entity at `row` was written at `tick`.
+    ///
+    /// # Safety
+    /// `row` must be in-bounds
+    #[inline]
+    pub(crate) unsafe fn set_entity_changed_tick_unchecked(&self, row: TableRow, tick: Tick) {
+        self.entity_changed_ticks
+            .get_unchecked(row.as_usize())
+            .store(tick.get(), Ordering::Relaxed);
+    }
+
     /// Fetches a read-only reference to the [`Column`] for a given [`Component`] within the
     /// table.
     ///
@@ -721,6 +931,8 @@ impl Table {
 
             // use entities vector capacity as driving capacity for all related allocations
             let new_capacity = self.entities.capacity();
+            self.entity_changed_ticks
+                .reserve_exact(new_capacity - self.entity_changed_ticks.len());
 
             for column in self.columns.values_mut() {
                 column.reserve_exact(new_capacity - column.len());
@@ -736,10 +948,14 @@ impl Table {
         self.reserve(1);
         let index = self.entities.len();
         self.entities.push(entity);
+        self.entity_changed_ticks.push(AtomicU32::new(0));
         for column in self.columns.values_mut() {
             column.data.set_len(self.entities.len());
             column.added_ticks.push(UnsafeCell::new(Tick::new(0)));
             column.changed_ticks.push(UnsafeCell::new(Tick::new(0)));
+            if let Some(field_ticks) = &mut column.field_ticks {
+                field_ticks.push(Tick::new(0));
+            }
         }
         TableRow::from_usize(index)
     }
@@ -775,6 +991,64 @@ impl Table {
         for column in self.columns.values_mut() {
             column.check_change_ticks(change_tick);
         }
+        for entity_changed_tick in &mut self.entity_changed_ticks {
+            let mut tick = Tick::new(*entity_changed_tick.get_mut());
+            tick.check_tick(change_tick);
+            *entity_changed_tick.get_mut() = tick.get();
+        }
+    }
+
+    /// Swaps the entities and components at rows `a` and `b`.
+    ///
+    /// # Safety
+    /// `a` and `b` must be in bounds.
+    pub(crate) unsafe fn swap_rows(&mut self, a: TableRow, b: TableRow) {
+        self.entities.swap(a.as_usize(), b.as_usize());
+        self.entity_changed_ticks.swap(a.as_usize(), b.as_usize());
+        for column in self.columns.values_mut() {
+            column.swap_unchecked(a, b);
+        }
+    }
+
+    /// Reorders the rows of the table by entity index, so that entities spawned together are
+    /// stored together. Returns the number of rows that were moved.
+    ///
+    /// The [`TableRow`] of the moved entities is stale afterwards: the caller must update their
+    /// locations.
+    pub(crate) fn sort_by_entity(&mut self) -> usize {
+        let len = self.entities.len();
+        if self
+            .entities
+            .windows(2)
+            .all(|w| w[0].index() <= w[1].index())
+        {
+            return 0;
+        }
+        let mut order: Vec<usize> = (0..len).collect();
+        order.sort_unstable_by_key(|&row| self.entities[row].index());
+        permute_rows(&order, |a, b| {
+            // SAFETY: `permute_rows` only swaps rows within the bounds of `order`.
+            unsafe { self.swap_rows(TableRow::from_usize(a), TableRow::from_usize(b)) };
+        })
+    }
+
+    /// Shrinks the allocations of the table to its entity count, returning the number of bytes
+    /// freed.
+    pub(crate) fn shrink_to_fit(&mut self) -> usize {
+        shrink_vec(&mut self.entities)
+            + shrink_vec(&mut self.entity_changed_ticks)
+            + self
+                .columns
+                .values_mut()
+                .map(Column::shrink_to_fit)
+                .sum::<usize>()
+    }
+
+    /// Returns the number of bytes allocated by the table and its columns.
+    pub fn allocated_bytes(&self) -> usize {
+        vec_allocated_bytes(&self.entities)
+            + vec_allocated_bytes(&self.entity_changed_ticks)
+            + self.iter().map(Column::allocated_bytes).sum::<usize>()
     }
 
     /// Iterates over the [`Column`]s of the [`Table`].
@@ -782,9 +1056,15 @@ impl Table {
         self.columns.values()
     }
 
+    /// Iterates over the [`Column`]s of the [`Table`] along with the [`ComponentId`]s they store.
+    pub(crate) fn iter_with_ids(&self) -> impl Iterator<Item = (ComponentId, &Column)> {
+        self.columns.iter().map(|(id, column)| (*id, column))
+    }
+
     /// Clears all of the stored components in the [`Table`].
     pub(crate) fn clear(&mut self) {
         self.entities.clear();
+        self.entity_changed_ticks.clear();
         for column in self.columns.values_mut() {
             column.clear();
         }
@@ -883,6 +1163,11 @@ impl Tables {
         self.tables.iter()
     }
 
+    /// Mutably iterates through all of the tables stored within in [`TableId`] order.
+    pub(crate) fn iter_mut(&mut self) -> std::slice::IterMut<'_, Table> {
+        self.tables.iter_mut()
+    }
+
     /// Clears all data from all [`Table`]s stored within.
     pub(crate) fn clear(&mut self) {
         for table in &mut self.tables {
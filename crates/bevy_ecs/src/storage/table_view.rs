@@ -0,0 +1,212 @@
+use crate::{
+    component::{Component, ComponentId, Components, Tick},
+    entity::Entity,
+    storage::Table,
+};
+use std::{alloc::Layout, marker::PhantomData};
+
+/// The layout of a column of a [`TableView`], with a stable `#[repr(C)]` representation.
+///
+/// The column holds one item per entity of the table. Item `i` starts at `data + i * stride`,
+/// and was added and last changed at `added_ticks[i]` and `changed_ticks[i]`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnDescriptor {
+    /// The [`ComponentId`] of the column, as returned by [`ComponentId::index`].
+    pub component_id: usize,
+    /// A pointer to the first item of the column.
+    ///
+    /// For zero-sized components, this is a dangling pointer that must not be read.
+    pub data: *const u8,
+    /// The size of an item in bytes.
+    pub item_size: usize,
+    /// The alignment of an item in bytes.
+    pub item_align: usize,
+    /// The distance between two consecutive items in bytes.
+    pub stride: usize,
+    /// A pointer to the tick each item was added at.
+    pub added_ticks: *const u32,
+    /// A pointer to the tick each item was last changed at.
+    pub changed_ticks: *const u32,
+}
+
+/// The layout of a [`TableView`], with a stable `#[repr(C)]` representation that can be handed to
+/// native code.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct TableDescriptor {
+    /// The number of entities in the table, and of items in each column.
+    pub len: usize,
+    /// A pointer to the entities of the table, in the bit representation of [`Entity::to_bits`].
+    pub entities: *const u64,
+    /// The number of columns.
+    pub column_count: usize,
+    /// A pointer to the descriptors of the columns.
+    pub columns: *const ColumnDescriptor,
+    /// The change tick of the world when the view was created.
+    ///
+    /// Ticks wrap around, so an item changed since a tick `last_run` if
+    /// `change_tick - changed_ticks[i] < change_tick - last_run`, using wrapping subtraction.
+    pub change_tick: u32,
+}
+
+/// A read-only view of the raw memory of a [`Table`], returned by
+/// [`World::table_view`](crate::world::World::table_view).
+///
+/// This hands out the pointers, strides and change ticks of every column of the table at once, so
+/// that native plugins and GPU upload paths can read component data in bulk instead of going
+/// through per-entity function calls. The [`descriptor`](Self::descriptor) can be passed across
+/// an FFI boundary as is.
+///
+/// The world is borrowed for as long as the view lives, so the pointers stay valid and no
+/// component of the table can be written to in the meantime. They must not be written to
+/// through the view either.
+pub struct TableView<'w> {
+    descriptor: TableDescriptor,
+    columns: Vec<ColumnDescriptor>,
+    entities: &'w [Entity],
+    components: &'w Components,
+    _marker: PhantomData<&'w Table>,
+}
+
+impl<'w> TableView<'w> {
+    /// Creates a view of `table`, whose columns store the components registered in `components`.
+    pub(crate) fn new(table: &'w Table, components: &'w Components, change_tick: Tick) -> Self {
+        let columns: Vec<ColumnDescriptor> = table
+            .iter_with_ids()
+            .map(|(component_id, column)| {
+                let layout = column.item_layout();
+                ColumnDescriptor {
+                    component_id: component_id.index(),
+                    data: column.get_data_ptr().as_ptr(),
+                    item_size: layout.size(),
+                    item_align: layout.align(),
+                    stride: layout.size(),
+                    added_ticks: column.get_added_ticks_slice().as_ptr().cast(),
+                    changed_ticks: column.get_changed_ticks_slice().as_ptr().cast(),
+                }
+            })
+            .collect();
+        let entities = table.entities();
+        Self {
+            descriptor: TableDescriptor {
+                len: entities.len(),
+                entities: entities.as_ptr().cast(),
+                column_count: columns.len(),
+                columns: columns.as_ptr(),
+                change_tick: change_tick.get(),
+            },
+            columns,
+            entities,
+            components,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the `#[repr(C)]` descriptor of the table, valid for as long as this view lives.
+    pub fn descriptor(&self) -> &TableDescriptor {
+        &self.descriptor
+    }
+
+    /// Returns the number of entities in the table.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if the table has no entities.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    /// Returns the entities of the table, in the order of the items of the columns.
+    pub fn entities(&self) -> &'w [Entity] {
+        self.entities
+    }
+
+    /// Returns the descriptors of the columns of the table.
+    pub fn columns(&self) -> &[ColumnDescriptor] {
+        &self.columns
+    }
+
+    /// Returns the descriptor of the column of the component `component_id`, if the table has
+    /// one.
+    pub fn column(&self, component_id: ComponentId) -> Option<&ColumnDescriptor> {
+        self.columns
+            .iter()
+            .find(|column| column.component_id == component_id.index())
+    }
+
+    /// Returns the items of the column of the component `T` as a slice, if the table has one.
+    pub fn column_slice<T: Component>(&self) -> Option<&'w [T]> {
+        let column = self.column(self.components.component_id::<T>()?)?;
+        if Layout::from_size_align(column.item_size, column.item_align) != Ok(Layout::new::<T>()) {
+            return None;
+        }
+        // SAFETY: the column stores items of type `T`, as its component id is the one of `T` in
+        // the world owning the table, and the world is borrowed for `'w`, so no item can be
+        // written to while the slice lives.
+        Some(unsafe { std::slice::from_raw_parts(column.data.cast::<T>(), self.len()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{component::Component, world::World};
+
+    #[derive(Component, Debug, Clone, Copy, PartialEq)]
+    #[repr(C)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn table_view() {
+        let mut world = World::new();
+        let a = world.spawn((Position { x: 1.0, y: 2.0 }, Marker)).id();
+        let b = world.spawn((Position { x: 3.0, y: 4.0 }, Marker)).id();
+        world.increment_change_tick();
+        world.get_mut::<Position>(b).unwrap().x = 5.0;
+
+        let table_id = world.entity(a).archetype().table_id();
+        let position_id = world.component_id::<Position>().unwrap();
+        let view = world.table_view(table_id).unwrap();
+        assert_eq!(view.len(), 2);
+        assert_eq!(view.entities(), &[a, b]);
+        assert_eq!(view.columns().len(), 2);
+        assert_eq!(
+            view.column_slice::<Position>(),
+            Some(&[Position { x: 1.0, y: 2.0 }, Position { x: 5.0, y: 4.0 }][..])
+        );
+
+        // read the table through the descriptor like native code would
+        let descriptor = view.descriptor();
+        // SAFETY: The descriptor points into `view`, which is still borrowed.
+        let columns =
+            unsafe { std::slice::from_raw_parts(descriptor.columns, descriptor.column_count) };
+        // SAFETY: See above.
+        let entities = unsafe { std::slice::from_raw_parts(descriptor.entities, descriptor.len) };
+        assert_eq!(entities, &[a.to_bits(), b.to_bits()]);
+        let column = columns
+            .iter()
+            .find(|column| column.component_id == position_id.index())
+            .unwrap();
+        assert_eq!(column.item_size, 8);
+        assert_eq!(column.stride, 8);
+        // SAFETY: The column stores `Position`s, and has two rows.
+        let second = unsafe { *column.data.add(column.stride).cast::<Position>() };
+        assert_eq!(second, Position { x: 5.0, y: 4.0 });
+        // SAFETY: The tick arrays have a tick per row of the table.
+        let changed = unsafe { std::slice::from_raw_parts(column.changed_ticks, descriptor.len) };
+        // SAFETY: See above.
+        let added = unsafe { std::slice::from_raw_parts(column.added_ticks, descriptor.len) };
+        assert_eq!(added[0], added[1]);
+        assert_eq!(changed[0], added[0]);
+        assert!(changed[1] > changed[0]);
+        assert_eq!(descriptor.change_tick, changed[1]);
+    }
+}
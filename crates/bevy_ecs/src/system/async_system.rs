@@ -0,0 +1,465 @@
+use std::{
+    borrow::Cow,
+    future::{poll_fn, Future},
+    pin::Pin,
+    sync::{Arc, Mutex, PoisonError},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    thread::{self, ThreadId},
+};
+
+use bevy_utils::synccell::SyncCell;
+
+use crate::{
+    archetype::{ArchetypeComponentId, ArchetypeGeneration},
+    component::{ComponentId, Tick},
+    query::Access,
+    schedule::{InternedSystemSet, SystemSet},
+    system::{check_system_change_tick, System, SystemMeta, SystemParam, SystemParamItem},
+    world::{unsafe_world_cell::UnsafeWorldCell, Command, CommandQueue, World, WorldId},
+};
+
+/// A [`System`] running a [`Future`], which can suspend and resume on a later run of the
+/// system, such as to wait for an asset to load or for network IO.
+///
+/// The future is created from the function of the system, given an [`AsyncAccess`] to the
+/// parameters `P` of the system. Each time the system runs, its future is polled once, and a
+/// new future is created when the previous one is complete. The parameters are only available
+/// while the system runs, through [`AsyncAccess::with`], so that the system holds its declared
+/// accesses like any other system while it runs, and none of them while its future is suspended:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{AsyncAccess, AsyncSystem};
+/// #[derive(Resource, Default)]
+/// struct Score(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<Score>();
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems(AsyncSystem::new(|access: AsyncAccess<ResMut<Score>>| async move {
+///     access.with(|mut score: ResMut<Score>| score.0 += 1).await;
+///     // Suspend until the next run of the system.
+///     access.next_frame().await;
+///     access.with(|mut score: ResMut<Score>| score.0 += 10).await;
+/// }));
+///
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Score>().0, 1);
+/// schedule.run(&mut world);
+/// assert_eq!(world.resource::<Score>().0, 11);
+/// ```
+///
+/// Commands can be queued at any time, even from other threads, with the [`AsyncCommands`] of
+/// [`AsyncAccess::commands`]. They are applied with the other deferred operations of the system.
+pub struct AsyncSystem<P: SystemParam + 'static, F> {
+    func: F,
+    param_state: Option<P::State>,
+    system_meta: SystemMeta,
+    world_id: Option<WorldId>,
+    archetype_generation: ArchetypeGeneration,
+    context: Arc<AsyncContext<P>>,
+    future: Option<SyncCell<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+}
+
+impl<P, F, Fut> AsyncSystem<P, F>
+where
+    P: SystemParam + 'static,
+    F: FnMut(AsyncAccess<P>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    /// Message shown when a system isn't initialised
+    const PARAM_MESSAGE: &'static str = "System's param_state was not found. Did you forget to initialize this system before running it?";
+
+    /// Creates a system running the futures returned by `func`.
+    pub fn new(func: F) -> Self {
+        Self {
+            func,
+            param_state: None,
+            system_meta: SystemMeta::new::<F>(),
+            world_id: None,
+            archetype_generation: ArchetypeGeneration::initial(),
+            context: Arc::new(AsyncContext {
+                poll: Mutex::new(None),
+                commands: Arc::new(Mutex::new(CommandQueue::default())),
+            }),
+            future: None,
+        }
+    }
+
+    /// Returns `true` if the future of the system is suspended, waiting for a later run.
+    pub fn is_suspended(&self) -> bool {
+        self.future.is_some()
+    }
+}
+
+impl<P, F, Fut> System for AsyncSystem<P, F>
+where
+    P: SystemParam + 'static,
+    F: FnMut(AsyncAccess<P>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    type In = ();
+    type Out = ();
+
+    #[inline]
+    fn name(&self) -> Cow<'static, str> {
+        self.system_meta.name.clone()
+    }
+
+    #[inline]
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system_meta.component_access_set.combined_access()
+    }
+
+    #[inline]
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.system_meta.archetype_component_access
+    }
+
+    #[inline]
+    fn is_send(&self) -> bool {
+        self.system_meta.is_send()
+    }
+
+    #[inline]
+    fn is_exclusive(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn has_deferred(&self) -> bool {
+        self.system_meta.has_deferred()
+    }
+
+    unsafe fn run_unsafe(&mut self, _input: (), world: UnsafeWorldCell) {
+        #[cfg(feature = "trace")]
+        let _span_guard = self.system_meta.system_span.enter();
+
+        let change_tick = world.increment_change_tick();
+
+        let param_state = self.param_state.as_mut().expect(Self::PARAM_MESSAGE);
+        // The parameters are only reachable from the future while it is polled below.
+        let _guard = self.context.begin_poll(PollState {
+            thread: thread::current().id(),
+            param_state,
+            system_meta: &self.system_meta,
+            // SAFETY: The world is only used while this guard is alive, during this run.
+            world: unsafe {
+                std::mem::transmute::<UnsafeWorldCell<'_>, UnsafeWorldCell<'static>>(world)
+            },
+            change_tick,
+        });
+
+        let future = self.future.get_or_insert_with(|| {
+            let access = AsyncAccess {
+                context: self.context.clone(),
+            };
+            SyncCell::new(Box::pin((self.func)(access)))
+        });
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+        if future.get().as_mut().poll(&mut context).is_ready() {
+            self.future = None;
+        }
+
+        self.system_meta.last_run = change_tick;
+    }
+
+    #[inline]
+    fn apply_deferred(&mut self, world: &mut World) {
+        let param_state = self.param_state.as_mut().expect(Self::PARAM_MESSAGE);
+        P::apply(param_state, &self.system_meta, world);
+        let mut commands = std::mem::take(
+            &mut *self
+                .context
+                .commands
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+        commands.apply(world);
+    }
+
+    #[inline]
+    fn initialize(&mut self, world: &mut World) {
+        self.world_id = Some(world.id());
+        self.system_meta.last_run = world.change_tick().relative_to(Tick::MAX);
+        self.param_state = Some(P::init_state(world, &mut self.system_meta));
+        // The commands of `AsyncCommands` are applied as deferred operations.
+        self.system_meta.set_has_deferred();
+    }
+
+    fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
+        assert_eq!(self.world_id, Some(world.id()), "Encountered a mismatched World. A System cannot be used with Worlds other than the one it was initialized with.");
+        let archetypes = world.archetypes();
+        let old_generation =
+            std::mem::replace(&mut self.archetype_generation, archetypes.generation());
+
+        for archetype in &archetypes[old_generation..] {
+            let param_state = self.param_state.as_mut().unwrap();
+            // SAFETY: The assertion above ensures that the param_state was initialized from `world`.
+            unsafe { P::new_archetype(param_state, archetype, &mut self.system_meta) };
+        }
+    }
+
+    #[inline]
+    fn check_change_tick(&mut self, change_tick: Tick) {
+        check_system_change_tick(
+            &mut self.system_meta.last_run,
+            change_tick,
+            self.system_meta.name.as_ref(),
+        );
+    }
+
+    fn default_system_sets(&self) -> Vec<InternedSystemSet> {
+        let set = crate::schedule::SystemTypeSet::<Self>::new();
+        vec![set.intern()]
+    }
+
+    fn get_last_run(&self) -> Tick {
+        self.system_meta.last_run
+    }
+
+    fn set_last_run(&mut self, last_run: Tick) {
+        self.system_meta.last_run = last_run;
+    }
+}
+
+/// The access of the future of an [`AsyncSystem`] to the parameters `P` of the system.
+pub struct AsyncAccess<P: SystemParam + 'static> {
+    context: Arc<AsyncContext<P>>,
+}
+
+impl<P: SystemParam + 'static> Clone for AsyncAccess<P> {
+    fn clone(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+        }
+    }
+}
+
+impl<P: SystemParam + 'static> AsyncAccess<P> {
+    /// Calls `func` with the parameters of the system.
+    ///
+    /// The returned future completes immediately while the future of the system is polled by the
+    /// system, and is pending otherwise, such as when it is polled from another task. The
+    /// parameters can't be kept across suspension points, as the system doesn't hold its
+    /// accesses in between runs.
+    pub async fn with<R>(
+        &self,
+        func: impl for<'w, 's> FnOnce(SystemParamItem<'w, 's, P>) -> R,
+    ) -> R {
+        let mut func = Some(func);
+        poll_fn(|_| {
+            let Some(poll) = self.context.take_poll() else {
+                return Poll::Pending;
+            };
+            let func = func.take().expect("Polled a completed future.");
+            // SAFETY:
+            // - The poll state is only set while the system runs and polls its future, on the
+            //   thread of the poll, so the pointers are valid and the system holds the accesses
+            //   of `P`.
+            // - The poll state is taken out of the context while the parameters are used, so
+            //   they aren't aliased by another call.
+            let output = unsafe {
+                let param = P::get_param(
+                    &mut *poll.param_state,
+                    &*poll.system_meta,
+                    poll.world,
+                    poll.change_tick,
+                );
+                func(param)
+            };
+            self.context.restore_poll(poll);
+            Poll::Ready(output)
+        })
+        .await
+    }
+
+    /// Suspends the future until the next run of the system.
+    pub async fn next_frame(&self) {
+        let mut suspended = false;
+        poll_fn(|_| {
+            if suspended {
+                Poll::Ready(())
+            } else {
+                suspended = true;
+                Poll::Pending
+            }
+        })
+        .await;
+    }
+
+    /// Returns a handle to queue commands, applied with the other deferred operations of the
+    /// system.
+    pub fn commands(&self) -> AsyncCommands {
+        AsyncCommands {
+            queue: self.context.commands.clone(),
+        }
+    }
+}
+
+/// A handle queuing [`Command`]s for an [`AsyncSystem`], created with [`AsyncAccess::commands`].
+///
+/// Unlike [`Commands`](super::Commands), it can be used at any time and from any thread, and its
+/// commands are applied with the deferred operations of the system after its next run.
+#[derive(Clone)]
+pub struct AsyncCommands {
+    queue: Arc<Mutex<CommandQueue>>,
+}
+
+impl AsyncCommands {
+    /// Queues `command`.
+    pub fn add<C: Command>(&self, command: C) {
+        self.queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(command);
+    }
+}
+
+struct AsyncContext<P: SystemParam> {
+    poll: Mutex<Option<PollState<P>>>,
+    commands: Arc<Mutex<CommandQueue>>,
+}
+
+impl<P: SystemParam> AsyncContext<P> {
+    fn begin_poll(&self, poll: PollState<P>) -> PollGuard<'_, P> {
+        *self.poll.lock().unwrap_or_else(PoisonError::into_inner) = Some(poll);
+        PollGuard { context: self }
+    }
+
+    /// Takes the poll state, if the system is polling its future on this thread.
+    fn take_poll(&self) -> Option<PollState<P>> {
+        let mut poll = self.poll.lock().unwrap_or_else(PoisonError::into_inner);
+        if poll
+            .as_ref()
+            .is_some_and(|poll| poll.thread == thread::current().id())
+        {
+            poll.take()
+        } else {
+            None
+        }
+    }
+
+    fn restore_poll(&self, poll: PollState<P>) {
+        *self.poll.lock().unwrap_or_else(PoisonError::into_inner) = Some(poll);
+    }
+}
+
+/// Ends the poll of the future of a system, even if it panics.
+struct PollGuard<'a, P: SystemParam> {
+    context: &'a AsyncContext<P>,
+}
+
+impl<P: SystemParam> Drop for PollGuard<'_, P> {
+    fn drop(&mut self) {
+        *self
+            .context
+            .poll
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = None;
+    }
+}
+
+/// What the future of a system needs to get the parameters of the system while it is polled.
+struct PollState<P: SystemParam> {
+    thread: ThreadId,
+    param_state: *mut P::State,
+    system_meta: *const SystemMeta,
+    world: UnsafeWorldCell<'static>,
+    change_tick: Tick,
+}
+
+// SAFETY: The pointers are only dereferenced on the thread polling the future of the system,
+// while the system runs.
+unsafe impl<P: SystemParam> Send for PollState<P> {}
+
+fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(std::ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    // SAFETY: The functions of the vtable don't use the data pointer.
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    #[derive(Component)]
+    struct A;
+
+    #[test]
+    fn suspends_across_runs() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(AsyncSystem::new(
+            |access: AsyncAccess<ResMut<Counter>>| async move {
+                for _ in 0..3 {
+                    access
+                        .with(|mut counter: ResMut<Counter>| counter.0 += 1)
+                        .await;
+                    access.next_frame().await;
+                }
+            },
+        ));
+
+        // The fourth run completes the future, and the fifth one starts a new one.
+        for expected in [1, 2, 3, 3, 4] {
+            schedule.run(&mut world);
+            assert_eq!(world.resource::<Counter>().0, expected);
+        }
+    }
+
+    #[test]
+    fn async_commands() {
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(AsyncSystem::new(|access: AsyncAccess<()>| async move {
+            access.commands().add(|world: &mut World| {
+                world.spawn(A);
+            });
+        }));
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        assert_eq!(world.query::<&A>().iter(&world).count(), 2);
+    }
+
+    #[test]
+    fn access_outside_of_the_system_is_pending() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut system = AsyncSystem::new(move |access: AsyncAccess<Res<Counter>>| {
+            sender.send(access).unwrap();
+            async {}
+        });
+        system.initialize(&mut world);
+        system.run((), &mut world);
+
+        let access = receiver.recv().unwrap();
+        let mut future = Box::pin(access.with(|counter: Res<Counter>| counter.0));
+        let waker = noop_waker();
+        let mut context = Context::from_waker(&waker);
+        assert!(future.as_mut().poll(&mut context).is_pending());
+    }
+
+    #[test]
+    #[should_panic = "error[B0002]"]
+    fn conflicting_access() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let mut system =
+            AsyncSystem::new(|_: AsyncAccess<(Res<Counter>, ResMut<Counter>)>| async {});
+        system.initialize(&mut world);
+    }
+}
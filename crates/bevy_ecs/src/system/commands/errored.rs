@@ -0,0 +1,386 @@
+use super::Commands;
+use crate::{
+    self as bevy_ecs,
+    bundle::Bundle,
+    entity::Entity,
+    system::Resource,
+    world::{Command, World},
+};
+use bevy_utils::tracing::error;
+use std::any::Any;
+use thiserror::Error;
+
+/// The reason a [`FallibleCommand`] failed.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// The entity the command applies to doesn't exist.
+    #[error("The entity {0:?} does not exist")]
+    NoSuchEntity(Entity),
+    /// The command failed for another reason.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// A [`FallibleCommand`] that failed, along with the reason it failed.
+pub struct CommandFailure<C> {
+    /// The command, with its payload preserved so that it can be retried.
+    pub command: C,
+    /// The reason the command failed.
+    pub error: CommandError,
+}
+
+impl<C> CommandFailure<C> {
+    /// Creates the failure of `command`.
+    pub fn new(command: C, error: CommandError) -> Self {
+        Self { command, error }
+    }
+}
+
+/// A [`Command`] that can fail, and gives itself back when it does so that it can be retried.
+///
+/// Fallible commands are queued with [`Commands::add_fallible`]. When one fails, it is stored in
+/// the [`ErroredCommands`] resource if it exists, where systems can inspect it, retry it or drop
+/// it. Otherwise the error is logged.
+pub trait FallibleCommand: Send + Sync + Sized + 'static {
+    /// Applies this command to the world, or returns it along with the reason it failed.
+    fn try_apply(self, world: &mut World) -> Result<(), CommandFailure<Self>>;
+}
+
+/// How many times a [`FallibleCommand`] is retried by [`retry_errored_commands`] after it failed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+    /// The number of times the command is retried. A command that failed more times than that
+    /// stays in [`ErroredCommands`] until it is retried manually or dropped.
+    pub max_retries: u32,
+}
+
+impl RetryPolicy {
+    /// Never retry the command automatically.
+    pub const NEVER: Self = Self { max_retries: 0 };
+
+    /// Retry the command automatically up to `max_retries` times.
+    pub const fn retries(max_retries: u32) -> Self {
+        Self { max_retries }
+    }
+}
+
+/// The object-safe counterpart of [`FallibleCommand`].
+trait ErasedFallibleCommand: Send + Sync + 'static {
+    fn try_apply(
+        self: Box<Self>,
+        world: &mut World,
+    ) -> Result<(), (Box<dyn ErasedFallibleCommand>, CommandError)>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+    fn type_name(&self) -> &'static str;
+}
+
+impl<C: FallibleCommand> ErasedFallibleCommand for C {
+    fn try_apply(
+        self: Box<Self>,
+        world: &mut World,
+    ) -> Result<(), (Box<dyn ErasedFallibleCommand>, CommandError)> {
+        FallibleCommand::try_apply(*self, world)
+            .map_err(|failure| (Box::new(failure.command) as _, failure.error))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<C>()
+    }
+}
+
+/// A [`FallibleCommand`] that failed, stored in [`ErroredCommands`].
+pub struct ErroredCommand {
+    command: Box<dyn ErasedFallibleCommand>,
+    error: CommandError,
+    policy: RetryPolicy,
+    attempts: u32,
+}
+
+impl ErroredCommand {
+    /// Returns the reason the last attempt to apply the command failed.
+    pub fn error(&self) -> &CommandError {
+        &self.error
+    }
+
+    /// Returns the number of times the command failed.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// Returns the retry policy the command was queued with.
+    pub fn policy(&self) -> RetryPolicy {
+        self.policy
+    }
+
+    /// Returns `true` if [`retry_errored_commands`] will retry the command.
+    pub fn can_retry(&self) -> bool {
+        self.attempts <= self.policy.max_retries
+    }
+
+    /// Returns the type name of the command.
+    pub fn command_name(&self) -> &'static str {
+        self.command.type_name()
+    }
+
+    /// Returns `true` if the command is of type `C`.
+    pub fn is<C: FallibleCommand>(&self) -> bool {
+        self.command.as_any().is::<C>()
+    }
+
+    /// Returns a reference to the command, if it is of type `C`.
+    pub fn downcast_ref<C: FallibleCommand>(&self) -> Option<&C> {
+        self.command.as_any().downcast_ref()
+    }
+
+    /// Returns a mutable reference to the command, if it is of type `C`, for example to fix it
+    /// before retrying it.
+    pub fn downcast_mut<C: FallibleCommand>(&mut self) -> Option<&mut C> {
+        self.command.as_any_mut().downcast_mut()
+    }
+
+    /// Takes the command out, if it is of type `C`.
+    pub fn into_command<C: FallibleCommand>(self) -> Result<C, Self> {
+        if self.is::<C>() {
+            Ok(*self.command.into_any().downcast().unwrap())
+        } else {
+            Err(self)
+        }
+    }
+
+    /// Queues the command to be applied again.
+    ///
+    /// If it fails again, it is stored back in [`ErroredCommands`] with one more attempt.
+    pub fn retry(self, commands: &mut Commands) {
+        commands.add(self);
+    }
+}
+
+impl Command for ErroredCommand {
+    fn apply(self, world: &mut World) {
+        if let Err((command, error)) = self.command.try_apply(world) {
+            report(
+                world,
+                ErroredCommand {
+                    command,
+                    error,
+                    policy: self.policy,
+                    attempts: self.attempts + 1,
+                },
+            );
+        }
+    }
+}
+
+/// A [`Resource`] that collects the [`FallibleCommand`]s that failed, with their payload.
+///
+/// Failed commands are only collected if this resource exists: initialize it to stop logging
+/// the errors of fallible commands and handle them instead. Systems can inspect the failed
+/// commands, [retry](ErroredCommand::retry) them or drop them, and the [`retry_errored_commands`]
+/// system retries the ones whose [`RetryPolicy`] allows it.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::{ErroredCommands, InsertBundle, RetryPolicy};
+/// # #[derive(Component)]
+/// # struct Target;
+/// fn insert_target(mut commands: Commands, entity: Entity) {
+///     commands.entity(entity).insert_fallible(Target, RetryPolicy::NEVER);
+/// }
+///
+/// fn handle_failures(mut commands: Commands, mut errored: ResMut<ErroredCommands>) {
+///     for failure in errored.drain() {
+///         match failure.into_command::<InsertBundle<Target>>() {
+///             // the entity is gone, insert the component on a new one instead
+///             Ok(insert) => {
+///                 commands.spawn(insert.bundle);
+///             }
+///             Err(failure) => println!("{} failed: {}", failure.command_name(), failure.error()),
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(handle_failures);
+/// ```
+#[derive(Resource, Default)]
+pub struct ErroredCommands {
+    commands: Vec<ErroredCommand>,
+}
+
+impl ErroredCommands {
+    /// Returns the number of failed commands.
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns `true` if no command failed.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns an iterator over the failed commands, in the order they failed.
+    pub fn iter(&self) -> impl Iterator<Item = &ErroredCommand> {
+        self.commands.iter()
+    }
+
+    /// Returns a mutable iterator over the failed commands, in the order they failed.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut ErroredCommand> {
+        self.commands.iter_mut()
+    }
+
+    /// Removes and returns all failed commands.
+    pub fn drain(&mut self) -> impl Iterator<Item = ErroredCommand> + '_ {
+        self.commands.drain(..)
+    }
+
+    /// Keeps only the failed commands for which `f` returns `true`, dropping the others.
+    pub fn retain(&mut self, f: impl FnMut(&ErroredCommand) -> bool) {
+        self.commands.retain(f);
+    }
+
+    /// Drops all failed commands.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+}
+
+/// Stores a failed command in [`ErroredCommands`], or logs its error if the resource doesn't
+/// exist.
+fn report(world: &mut World, errored: ErroredCommand) {
+    match world.get_resource_mut::<ErroredCommands>() {
+        Some(mut errored_commands) => errored_commands.commands.push(errored),
+        None => error!(
+            "Failed to apply command {}: {}",
+            errored.command_name(),
+            errored.error
+        ),
+    }
+}
+
+/// An exclusive system that applies again the failed commands of [`ErroredCommands`] whose
+/// [`RetryPolicy`] allows it.
+pub fn retry_errored_commands(world: &mut World) {
+    let Some(mut errored_commands) = world.get_resource_mut::<ErroredCommands>() else {
+        return;
+    };
+    let (retried, kept) = errored_commands
+        .commands
+        .drain(..)
+        .partition::<Vec<_>, _>(ErroredCommand::can_retry);
+    errored_commands.commands = kept;
+    for errored in retried {
+        errored.apply(world);
+    }
+}
+
+/// Applies a [`FallibleCommand`], reporting it to [`ErroredCommands`] if it fails.
+pub(super) struct Fallible<C> {
+    pub(super) command: C,
+    pub(super) policy: RetryPolicy,
+}
+
+impl<C: FallibleCommand> Command for Fallible<C> {
+    fn apply(self, world: &mut World) {
+        if let Err(failure) = self.command.try_apply(world) {
+            report(
+                world,
+                ErroredCommand {
+                    command: Box::new(failure.command),
+                    error: failure.error,
+                    policy: self.policy,
+                    attempts: 1,
+                },
+            );
+        }
+    }
+}
+
+/// A [`FallibleCommand`] that inserts a [`Bundle`] on an entity, and fails if the entity doesn't
+/// exist. Queued by [`EntityCommands::insert_fallible`](super::EntityCommands::insert_fallible).
+pub struct InsertBundle<B: Bundle> {
+    /// The entity to insert the bundle on.
+    pub entity: Entity,
+    /// The bundle to insert.
+    pub bundle: B,
+}
+
+impl<B: Bundle> FallibleCommand for InsertBundle<B> {
+    fn try_apply(self, world: &mut World) -> Result<(), CommandFailure<Self>> {
+        match world.get_entity_mut(self.entity) {
+            Some(mut entity) => {
+                entity.insert(self.bundle);
+                Ok(())
+            }
+            None => {
+                let error = CommandError::NoSuchEntity(self.entity);
+                Err(CommandFailure::new(self, error))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{component::Component, world::CommandQueue};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(u32);
+
+    #[test]
+    fn errored_commands() {
+        let mut world = World::new();
+        world.init_resource::<ErroredCommands>();
+        let entity = world.spawn_empty().id();
+
+        // the entity is despawned before the command is applied
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world)
+            .entity(entity)
+            .insert_fallible(A(1), RetryPolicy::retries(1));
+        world.despawn(entity);
+        queue.apply(&mut world);
+        let errored = world.resource::<ErroredCommands>();
+        assert_eq!(errored.len(), 1);
+        let failure = errored.iter().next().unwrap();
+        assert_eq!(failure.error(), &CommandError::NoSuchEntity(entity));
+        assert_eq!(failure.attempts(), 1);
+        assert!(failure.can_retry());
+        assert_eq!(
+            failure.downcast_ref::<InsertBundle<A>>().unwrap().bundle,
+            A(1)
+        );
+
+        // the retry fails too, and exhausts the policy
+        retry_errored_commands(&mut world);
+        retry_errored_commands(&mut world);
+        let errored = world.resource::<ErroredCommands>();
+        assert_eq!(errored.len(), 1);
+        assert_eq!(errored.iter().next().unwrap().attempts(), 2);
+
+        // fix the command and retry it manually
+        let target = world.spawn_empty().id();
+        let mut failure = world
+            .resource_mut::<ErroredCommands>()
+            .drain()
+            .next()
+            .unwrap();
+        failure.downcast_mut::<InsertBundle<A>>().unwrap().entity = target;
+        failure.retry(&mut Commands::new(&mut queue, &world));
+        queue.apply(&mut world);
+        assert!(world.resource::<ErroredCommands>().is_empty());
+        assert_eq!(world.get::<A>(target), Some(&A(1)));
+    }
+}
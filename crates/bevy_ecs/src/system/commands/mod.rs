@@ -1,16 +1,24 @@
+mod errored;
 mod parallel_scope;
 
-use super::{Deferred, IntoSystem, RegisterSystem, Resource};
+use super::{
+    Deferred, IntoSystem, ReadOnlySystemParam, RegisterSystem, Resource, SystemMeta, SystemParam,
+};
 use crate::{
-    self as bevy_ecs,
     bundle::Bundle,
-    component::ComponentId,
+    component::{ComponentId, Tick},
     entity::{Entities, Entity},
     system::{RunSystemWithInput, SystemId},
-    world::{Command, CommandQueue, EntityWorldMut, FromWorld, World},
+    world::{
+        unsafe_world_cell::UnsafeWorldCell, Command, CommandQueue, EntityWorldMut, FromWorld,
+        SystemCommandQueue, World,
+    },
+};
+use bevy_utils::{
+    synccell::SyncCell,
+    tracing::{error, info},
 };
-use bevy_ecs_macros::SystemParam;
-use bevy_utils::tracing::{error, info};
+pub use errored::*;
 pub use parallel_scope::*;
 use std::marker::PhantomData;
 
@@ -19,6 +27,8 @@ use std::marker::PhantomData;
 /// Since each command requires exclusive access to the `World`,
 /// all queued commands are automatically applied in sequence
 /// when the `apply_deferred` system runs (see [`apply_deferred`] documentation for more details).
+/// Commands queued by different systems are applied in the order of the systems in the schedule,
+/// whether or not the systems ran in parallel, so the order is the same every time the schedule runs.
 ///
 /// Each command can be used to modify the [`World`] in arbitrary ways:
 /// * spawning or despawning entities
@@ -65,12 +75,39 @@ use std::marker::PhantomData;
 /// ```
 ///
 /// [`apply_deferred`]: crate::schedule::apply_deferred
-#[derive(SystemParam)]
 pub struct Commands<'w, 's> {
     queue: Deferred<'s, CommandQueue>,
     entities: &'w Entities,
 }
 
+// SAFETY: `Commands` only reads `Entities`, which doesn't conflict with any access, and its queue
+// is local to the system.
+unsafe impl SystemParam for Commands<'_, '_> {
+    // The queue of a system is wrapped to apply the `CommandQueueBudget`.
+    type State = SyncCell<SystemCommandQueue>;
+    type Item<'w, 's> = Commands<'w, 's>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        <Deferred<SystemCommandQueue> as SystemParam>::init_state(world, system_meta)
+    }
+
+    fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
+        <Deferred<SystemCommandQueue> as SystemParam>::apply(state, system_meta, world);
+    }
+
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        Commands::new_from_entities(state.get().queue_mut(), world.entities())
+    }
+}
+
+// SAFETY: Only reads `Entities`, and accesses the local queue.
+unsafe impl ReadOnlySystemParam for Commands<'_, '_> {}
+
 impl<'w, 's> Commands<'w, 's> {
     /// Returns a new `Commands` instance from a [`CommandQueue`] and a [`World`].
     ///
@@ -242,7 +279,7 @@ impl<'w, 's> Commands<'w, 's> {
     /// - [`spawn_batch`](Self::spawn_batch) to spawn entities with a bundle each.
     pub fn spawn<T: Bundle>(&mut self, bundle: T) -> EntityCommands {
         let mut e = self.spawn_empty();
-        e.insert(bundle);
+        e.add(spawn(bundle));
         e
     }
 
@@ -620,6 +657,15 @@ impl<'w, 's> Commands<'w, 's> {
     pub fn add<C: Command>(&mut self, command: C) {
         self.queue.push(command);
     }
+
+    /// Pushes a [`FallibleCommand`] to the queue for applying it later.
+    ///
+    /// If the command fails, it is stored along with its payload in the [`ErroredCommands`]
+    /// resource if it exists, where it is retried according to `policy` by the
+    /// [`retry_errored_commands`] system. Otherwise the error is logged.
+    pub fn add_fallible<C: FallibleCommand>(&mut self, command: C, policy: RetryPolicy) {
+        self.queue.push(errored::Fallible { command, policy });
+    }
 }
 
 /// A [`Command`] which gets executed for a given [`Entity`].
@@ -849,6 +895,18 @@ impl EntityCommands<'_> {
         self.add(try_insert(bundle))
     }
 
+    /// Adds a [`Bundle`] of components to the entity, or reports the failure to
+    /// [`ErroredCommands`] if the entity doesn't exist when the command is applied.
+    ///
+    /// The failed [`InsertBundle`] command keeps the bundle, and is retried according to
+    /// `policy`. See [`Commands::add_fallible`].
+    pub fn insert_fallible(&mut self, bundle: impl Bundle, policy: RetryPolicy) -> &mut Self {
+        let entity = self.entity;
+        self.commands
+            .add_fallible(InsertBundle { entity, bundle }, policy);
+        self
+    }
+
     /// Removes a [`Bundle`] of components from the entity.
     ///
     /// # Example
@@ -1046,6 +1104,12 @@ where
     B: Bundle,
 {
     move |world: &mut World| {
+        #[cfg(feature = "bevy_reflect")]
+        if world.is_journaling() {
+            let entities = world.spawn_batch(bundles_iter).collect();
+            crate::reflect::record_spawn_batch(world, entities);
+            return;
+        }
         world.spawn_batch(bundles_iter);
     }
 }
@@ -1060,13 +1124,32 @@ where
     B: Bundle,
 {
     move |world: &mut World| {
-        if let Err(invalid_entities) = world.insert_or_spawn_batch(bundles_iter) {
-            error!(
-                "Failed to 'insert or spawn' bundle of type {} into the following invalid entities: {:?}",
-                std::any::type_name::<B>(),
-                invalid_entities
-            );
+        #[cfg(feature = "bevy_reflect")]
+        if world.is_journaling() {
+            let bundles: Vec<_> = bundles_iter.into_iter().collect();
+            for &(entity, _) in &bundles {
+                crate::reflect::record_insert_or_spawn::<B>(world, entity);
+            }
+            insert_or_spawn_bundles(world, bundles);
+            return;
         }
+        insert_or_spawn_bundles(world, bundles_iter);
+    }
+}
+
+/// Adds the [`Bundle`]s to their entities, spawning the entities that don't exist, and logs the
+/// entities that are invalid.
+fn insert_or_spawn_bundles<I, B>(world: &mut World, bundles_iter: I)
+where
+    I: IntoIterator<Item = (Entity, B)>,
+    B: Bundle,
+{
+    if let Err(invalid_entities) = world.insert_or_spawn_batch(bundles_iter) {
+        error!(
+            "Failed to 'insert or spawn' bundle of type {} into the following invalid entities: {:?}",
+            std::any::type_name::<B>(),
+            invalid_entities
+        );
     }
 }
 
@@ -1078,23 +1161,43 @@ where
 /// This won't clean up external references to the entity (such as parent-child relationships
 /// if you're using `bevy_hierarchy`), which may leave the world in an invalid state.
 fn despawn(entity: Entity, world: &mut World) {
+    #[cfg(feature = "bevy_reflect")]
+    crate::reflect::record_despawn(world, entity);
     world.despawn(entity);
 }
 
+/// An [`EntityCommand`] that adds the components in a [`Bundle`] to a newly spawned entity.
+fn spawn<T: Bundle>(bundle: T) -> impl EntityCommand {
+    move |entity: Entity, world: &mut World| {
+        #[cfg(feature = "bevy_reflect")]
+        crate::reflect::record_spawn(world, entity);
+        insert_bundle(entity, world, bundle);
+    }
+}
+
 /// An [`EntityCommand`] that adds the components in a [`Bundle`] to an entity.
 fn insert<T: Bundle>(bundle: T) -> impl EntityCommand {
     move |entity: Entity, world: &mut World| {
-        if let Some(mut entity) = world.get_entity_mut(entity) {
-            entity.insert(bundle);
-        } else {
-            panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/#b0003", std::any::type_name::<T>(), entity);
-        }
+        #[cfg(feature = "bevy_reflect")]
+        crate::reflect::record_insert::<T>(world, entity);
+        insert_bundle(entity, world, bundle);
+    }
+}
+
+/// Adds the components in a [`Bundle`] to an entity, panicking if it doesn't exist.
+fn insert_bundle<T: Bundle>(entity: Entity, world: &mut World, bundle: T) {
+    if let Some(mut entity) = world.get_entity_mut(entity) {
+        entity.insert(bundle);
+    } else {
+        panic!("error[B0003]: Could not insert a bundle (of type `{}`) for entity {:?} because it doesn't exist in this World. See: https://bevyengine.org/learn/errors/#b0003", std::any::type_name::<T>(), entity);
     }
 }
 
 /// An [`EntityCommand`] that attempts to add the components in a [`Bundle`] to an entity.
-fn try_insert(bundle: impl Bundle) -> impl EntityCommand {
+fn try_insert<T: Bundle>(bundle: T) -> impl EntityCommand {
     move |entity, world: &mut World| {
+        #[cfg(feature = "bevy_reflect")]
+        crate::reflect::record_insert::<T>(world, entity);
         if let Some(mut entity) = world.get_entity_mut(entity) {
             entity.insert(bundle);
         }
@@ -1105,6 +1208,8 @@ fn try_insert(bundle: impl Bundle) -> impl EntityCommand {
 /// For a [`Bundle`] type `T`, this will remove any components in the bundle.
 /// Any components in the bundle that aren't found on the entity will be ignored.
 fn remove<T: Bundle>(entity: Entity, world: &mut World) {
+    #[cfg(feature = "bevy_reflect")]
+    crate::reflect::record_remove::<T>(world, entity);
     if let Some(mut entity) = world.get_entity_mut(entity) {
         entity.remove::<T>();
     }
@@ -1116,6 +1221,8 @@ fn remove<T: Bundle>(entity: Entity, world: &mut World) {
 /// Panics if the provided [`ComponentId`] does not exist in the [`World`].
 fn remove_by_id(component_id: ComponentId) -> impl EntityCommand {
     move |entity: Entity, world: &mut World| {
+        #[cfg(feature = "bevy_reflect")]
+        crate::reflect::record_remove_by_id(world, entity, component_id);
         if let Some(mut entity) = world.get_entity_mut(entity) {
             entity.remove_by_id(component_id);
         }
@@ -1126,6 +1233,8 @@ fn remove_by_id(component_id: ComponentId) -> impl EntityCommand {
 /// For a [`Bundle`] type `T`, this will remove all components except those in the bundle.
 /// Any components in the bundle that aren't found on the entity will be ignored.
 fn retain<T: Bundle>(entity: Entity, world: &mut World) {
+    #[cfg(feature = "bevy_reflect")]
+    crate::reflect::record_retain::<T>(world, entity);
     if let Some(mut entity_mut) = world.get_entity_mut(entity) {
         entity_mut.retain::<T>();
     }
@@ -1134,17 +1243,23 @@ fn retain<T: Bundle>(entity: Entity, world: &mut World) {
 /// A [`Command`] that inserts a [`Resource`] into the world using a value
 /// created with the [`FromWorld`] trait.
 fn init_resource<R: Resource + FromWorld>(world: &mut World) {
+    #[cfg(feature = "bevy_reflect")]
+    crate::reflect::record_init_resource::<R>(world);
     world.init_resource::<R>();
 }
 
 /// A [`Command`] that removes the [resource](Resource) `R` from the world.
 fn remove_resource<R: Resource>(world: &mut World) {
+    #[cfg(feature = "bevy_reflect")]
+    crate::reflect::record_remove_resource::<R>(world);
     world.remove_resource::<R>();
 }
 
 /// A [`Command`] that inserts a [`Resource`] into the world.
 fn insert_resource<R: Resource>(resource: R) -> impl Command {
     move |world: &mut World| {
+        #[cfg(feature = "bevy_reflect")]
+        crate::reflect::record_insert_resource::<R>(world);
         world.insert_resource(resource);
     }
 }
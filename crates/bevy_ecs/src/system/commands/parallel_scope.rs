@@ -2,7 +2,7 @@ use bevy_utils::Parallel;
 
 use crate::{
     self as bevy_ecs,
-    entity::Entities,
+    entity::{Entities, Entity},
     prelude::World,
     system::{Deferred, SystemBuffer, SystemMeta, SystemParam},
 };
@@ -12,11 +12,16 @@ use super::{CommandQueue, Commands};
 #[derive(Default)]
 struct ParallelCommandQueue {
     thread_queues: Parallel<CommandQueue>,
+    entity_queues: Parallel<Vec<(Entity, CommandQueue)>>,
+    sorted_entity_queues: Vec<(Entity, CommandQueue)>,
 }
 
 /// An alternative to [`Commands`] that can be used in parallel contexts, such as those in [`Query::par_iter`](crate::system::Query::par_iter)
 ///
 /// Note: Because command application order will depend on how many threads are ran, non-commutative commands may result in non-deterministic results.
+/// Commands queued with [`entity_command_scope`](Self::entity_command_scope) are instead applied
+/// in a deterministic order, sorted by the entity they were queued for, after all the commands
+/// queued with [`command_scope`](Self::command_scope).
 ///
 /// Example:
 /// ```
@@ -54,6 +59,14 @@ impl SystemBuffer for ParallelCommandQueue {
         for cq in self.thread_queues.iter_mut() {
             cq.apply(world);
         }
+
+        self.entity_queues
+            .drain_into(&mut self.sorted_entity_queues);
+        // the sort is stable, so commands queued for an entity on a single thread keep their order
+        self.sorted_entity_queues.sort_by_key(|(entity, _)| *entity);
+        for (_, mut cq) in self.sorted_entity_queues.drain(..) {
+            cq.apply(world);
+        }
     }
 }
 
@@ -67,4 +80,100 @@ impl<'w, 's> ParallelCommands<'w, 's> {
             f(commands)
         })
     }
+
+    /// Temporarily provides access to [`Commands`] whose commands are ordered by `entity`.
+    ///
+    /// When this system's commands are applied, the commands queued with
+    /// [`command_scope`](Self::command_scope) are applied first, then the commands queued with
+    /// this method, sorted by `entity` regardless of which thread queued them.
+    /// This makes non-commutative commands, such as inserting and removing the same component,
+    /// deterministic when each entity is only processed by one task, as in
+    /// [`Query::par_iter`](crate::system::Query::par_iter).
+    ///
+    /// Mixing both methods reorders commands: a command queued with this method is applied after
+    /// the commands queued with [`command_scope`](Self::command_scope), even those queued later on
+    /// the same thread. Commands that must run in the order they were queued should all be queued
+    /// with the same method.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Health(f32);
+    /// # #[derive(Component)]
+    /// # struct Dead;
+    /// fn kill_system(query: Query<(Entity, &Health)>, par_commands: ParallelCommands) {
+    ///     query.par_iter().for_each(|(entity, health)| {
+    ///         if health.0 <= 0.0 {
+    ///             par_commands.entity_command_scope(entity, |mut commands| {
+    ///                 commands.entity(entity).insert(Dead).remove::<Health>();
+    ///             });
+    ///         }
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(kill_system);
+    /// ```
+    pub fn entity_command_scope<R>(&self, entity: Entity, f: impl FnOnce(Commands) -> R) -> R {
+        let mut queue = CommandQueue::default();
+        let result = f(Commands::new_from_entities(&mut queue, self.entities));
+        if !queue.is_empty() {
+            self.state
+                .entity_queues
+                .scope(|queues| queues.push((entity, queue)));
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, batching::BatchingStrategy, prelude::*, system::RunSystemOnce};
+    use bevy_tasks::{ComputeTaskPool, TaskPool};
+
+    #[derive(Resource, Default)]
+    struct Order(Vec<u32>);
+
+    #[test]
+    fn entity_command_scopes_are_sorted() {
+        ComputeTaskPool::get_or_init(TaskPool::default);
+        let mut world = World::new();
+        world.init_resource::<Order>();
+        let entities: Vec<_> = (0..64).map(|_| world.spawn_empty().id()).collect();
+
+        world.run_system_once(|query: Query<Entity>, par_commands: ParallelCommands| {
+            query
+                .par_iter()
+                .batching_strategy(BatchingStrategy::fixed(1))
+                .for_each(|entity| {
+                    par_commands.entity_command_scope(entity, |mut commands| {
+                        commands.add(move |world: &mut World| {
+                            world.resource_mut::<Order>().0.push(entity.index());
+                        });
+                    });
+                });
+        });
+
+        let order = &world.resource::<Order>().0;
+        let expected: Vec<_> = entities.iter().map(|entity| entity.index()).collect();
+        assert_eq!(*order, expected);
+    }
+
+    #[test]
+    fn command_scopes_apply_before_entity_command_scopes() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+        let first = world.spawn_empty().id();
+        let second = world.spawn_empty().id();
+
+        fn push(value: u32) -> impl FnOnce(&mut World) {
+            move |world: &mut World| world.resource_mut::<Order>().0.push(value)
+        }
+        world.run_system_once(move |par_commands: ParallelCommands| {
+            par_commands.entity_command_scope(second, |mut commands| commands.add(push(2)));
+            par_commands.command_scope(|mut commands| commands.add(push(0)));
+            par_commands.entity_command_scope(first, |mut commands| commands.add(push(1)));
+            par_commands.command_scope(|mut commands| commands.add(push(3)));
+        });
+
+        assert_eq!(world.resource::<Order>().0, vec![0, 3, 1, 2]);
+    }
 }
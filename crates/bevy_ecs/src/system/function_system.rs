@@ -2,7 +2,7 @@ use crate::{
     archetype::{ArchetypeComponentId, ArchetypeGeneration},
     component::{ComponentId, Tick},
     prelude::FromWorld,
-    query::{Access, FilteredAccessSet},
+    query::{Access, DynamicQueryBuilder, FilteredAccessSet},
     schedule::{InternedSystemSet, SystemSet},
     system::{check_system_change_tick, ReadOnlySystemParam, System, SystemParam, SystemParamItem},
     world::{unsafe_world_cell::UnsafeWorldCell, World, WorldId},
@@ -27,6 +27,8 @@ pub struct SystemMeta {
     is_send: bool,
     has_deferred: bool,
     pub(crate) last_run: Tick,
+    // The descriptions of the `DynamicQuery` parameters not initialized yet, in reverse order.
+    pub(crate) dynamic_queries: Vec<DynamicQueryBuilder>,
     #[cfg(feature = "trace")]
     pub(crate) system_span: Span,
     #[cfg(feature = "trace")]
@@ -43,6 +45,7 @@ impl SystemMeta {
             is_send: true,
             has_deferred: false,
             last_run: Tick::new(0),
+            dynamic_queries: Vec::new(),
             #[cfg(feature = "trace")]
             system_span: info_span!("system", name = name),
             #[cfg(feature = "trace")]
@@ -81,6 +84,23 @@ impl SystemMeta {
     pub fn set_has_deferred(&mut self) {
         self.has_deferred = true;
     }
+
+    /// Sets the descriptions of the [`DynamicQuery`](super::DynamicQuery) parameters, in order.
+    fn set_dynamic_queries(&mut self, queries: impl IntoIterator<Item = DynamicQueryBuilder>) {
+        self.dynamic_queries = queries.into_iter().collect();
+        self.dynamic_queries.reverse();
+    }
+
+    /// Asserts that every description given to the system was used by a
+    /// [`DynamicQuery`](super::DynamicQuery) parameter.
+    fn assert_dynamic_queries_initialized(&self) {
+        assert!(
+            self.dynamic_queries.is_empty(),
+            "System {} was given {} more dynamic queries than it has `DynamicQuery` parameters.",
+            self.name,
+            self.dynamic_queries.len()
+        );
+    }
 }
 
 // TODO: Actually use this in FunctionSystem. We should probably only do this once Systems are constructed using a World reference
@@ -191,9 +211,24 @@ impl<Param: SystemParam> SystemState<Param> {
     /// `new` does not cache any of the world's archetypes, so you must call [`SystemState::update_archetypes`]
     /// manually before calling `get_manual{_mut}`.
     pub fn new(world: &mut World) -> Self {
+        Self::with_dynamic_queries(world, [])
+    }
+
+    /// Creates a new [`SystemState`], whose [`DynamicQuery`](super::DynamicQuery) parameters
+    /// are built from `queries`, in order.
+    ///
+    /// # Panics
+    ///
+    /// If the number of `queries` doesn't match the number of `DynamicQuery` parameters.
+    pub fn with_dynamic_queries(
+        world: &mut World,
+        queries: impl IntoIterator<Item = DynamicQueryBuilder>,
+    ) -> Self {
         let mut meta = SystemMeta::new::<Param>();
         meta.last_run = world.change_tick().relative_to(Tick::MAX);
+        meta.set_dynamic_queries(queries);
         let param_state = Param::init_state(world, &mut meta);
+        meta.assert_dynamic_queries_initialized();
         Self {
             meta,
             param_state,
@@ -391,6 +426,7 @@ where
     func: F,
     param_state: Option<<F::Param as SystemParam>::State>,
     system_meta: SystemMeta,
+    dynamic_queries: Vec<DynamicQueryBuilder>,
     world_id: Option<WorldId>,
     archetype_generation: ArchetypeGeneration,
     // NOTE: PhantomData<fn()-> T> gives this safe Send/Sync impls
@@ -407,6 +443,7 @@ where
             func: self.func.clone(),
             param_state: None,
             system_meta: SystemMeta::new::<F>(),
+            dynamic_queries: self.dynamic_queries.clone(),
             world_id: None,
             archetype_generation: ArchetypeGeneration::initial(),
             marker: PhantomData,
@@ -429,6 +466,7 @@ where
             func,
             param_state: None,
             system_meta: SystemMeta::new::<F>(),
+            dynamic_queries: Vec::new(),
             world_id: None,
             archetype_generation: ArchetypeGeneration::initial(),
             marker: PhantomData,
@@ -444,6 +482,34 @@ where
     // When lines get too long, rustfmt can sometimes refuse to format them.
     // Work around this by storing the message separately.
     const PARAM_MESSAGE: &'static str = "System's param_state was not found. Did you forget to initialize this system before running it?";
+
+    /// Adds the description of the next [`DynamicQuery`](super::DynamicQuery) parameter of the
+    /// system, which are built from their descriptions in order when the system is initialized.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::query::DynamicQueryBuilder;
+    /// # use bevy_ecs::system::DynamicQuery;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// #
+    /// fn count(query: DynamicQuery) {
+    ///     println!("{} entities", query.iter().count());
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// let health = world.init_component::<Health>();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems(
+    ///     IntoSystem::into_system(count).with_dynamic_query(DynamicQueryBuilder::new().read(health)),
+    /// );
+    /// schedule.run(&mut world);
+    /// ```
+    pub fn with_dynamic_query(mut self, query: DynamicQueryBuilder) -> Self {
+        self.dynamic_queries.push(query);
+        self
+    }
 }
 
 impl<Marker, F> System for FunctionSystem<Marker, F>
@@ -519,7 +585,10 @@ where
     fn initialize(&mut self, world: &mut World) {
         self.world_id = Some(world.id());
         self.system_meta.last_run = world.change_tick().relative_to(Tick::MAX);
+        self.system_meta
+            .set_dynamic_queries(self.dynamic_queries.iter().cloned());
         self.param_state = Some(F::Param::init_state(world, &mut self.system_meta));
+        self.system_meta.assert_dynamic_queries_initialized();
     }
 
     fn update_archetype_component_access(&mut self, world: UnsafeWorldCell) {
@@ -87,6 +87,7 @@
 //! - [`ResMut`] and `Option<ResMut>`
 //! - [`Commands`]
 //! - [`Local`]
+//! - [`Scratch`]
 //! - [`EventReader`](crate::event::EventReader)
 //! - [`EventWriter`](crate::event::EventWriter)
 //! - [`NonSend`] and `Option<NonSend>`
@@ -102,31 +103,37 @@
 //! - [`()` (unit primitive type)](https://doc.rust-lang.org/stable/std/primitive.unit.html)
 
 mod adapter_system;
+mod async_system;
 mod combinator;
 mod commands;
 mod exclusive_function_system;
 mod exclusive_system_param;
 mod function_system;
 mod query;
+mod scratch;
 #[allow(clippy::module_inception)]
 mod system;
 mod system_name;
 mod system_param;
 mod system_registry;
+mod world_scope;
 
 use std::{any::TypeId, borrow::Cow};
 
 pub use adapter_system::*;
+pub use async_system::*;
 pub use combinator::*;
 pub use commands::*;
 pub use exclusive_function_system::*;
 pub use exclusive_system_param::*;
 pub use function_system::*;
 pub use query::*;
+pub use scratch::*;
 pub use system::*;
 pub use system_name::*;
 pub use system_param::*;
 pub use system_registry::*;
+pub use world_scope::*;
 
 use crate::world::World;
 
@@ -1052,6 +1059,43 @@ mod tests {
         with_filter.run((), &mut world);
     }
 
+    #[test]
+    fn query_iter_join() {
+        #[derive(Component)]
+        struct W(u32);
+
+        fn join(
+            left: Query<(Entity, &W), With<A>>,
+            right: Query<Entity, Without<C>>,
+            changed: Query<&W, Changed<W>>,
+        ) {
+            // Archetypal filters only: the length is known exactly.
+            assert_eq!(left.iter_join(&right).size_hint(), (2, Some(2)));
+            assert_eq!(changed.iter_join(&right).size_hint(), (0, Some(3)));
+            assert_eq!(changed.iter_join(&right).count(), 3);
+
+            let mut joined: Vec<_> = left
+                .iter_join(&right)
+                .map(|((entity, w), other)| {
+                    assert_eq!(entity, other);
+                    w.0
+                })
+                .collect();
+            joined.sort();
+            assert_eq!(joined, [1, 3]);
+        }
+
+        let mut world = World::default();
+        world.spawn((W(1), A));
+        world.spawn((W(2), A, C));
+        world.spawn((W(3), A, B));
+        world.spawn((W(4), B));
+
+        let mut join = IntoSystem::into_system(join);
+        join.initialize(&mut world);
+        join.run((), &mut world);
+    }
+
     #[test]
     #[allow(clippy::too_many_arguments)]
     fn can_have_16_parameters() {
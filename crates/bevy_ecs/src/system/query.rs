@@ -1,11 +1,13 @@
 use crate::{
+    archetype::{ArchetypeEntity, ArchetypeId},
     batching::BatchingStrategy,
     component::Tick,
     entity::Entity,
     query::{
         QueryCombinationIter, QueryData, QueryEntityError, QueryFilter, QueryIter, QueryManyIter,
-        QueryParIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyQueryData,
+        QueryParIter, QuerySingleError, QueryState, ROQueryItem, ReadOnlyQueryData, WorldQuery,
     },
+    tag::{TaggedEntities, WithTag},
     world::unsafe_world_cell::UnsafeWorldCell,
 };
 use std::borrow::Borrow;
@@ -443,6 +445,57 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns an iterator over the read-only query items of the entities tagged with the name of
+    /// `filter`, in no particular order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::tag::WithTag;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// fn log_enemies(query: Query<&Health>) {
+    ///     for health in query.iter_tagged(WithTag("enemy")) {
+    ///         println!("Enemy health: {}", health.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(log_enemies);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`iter_tagged_mut`](Self::iter_tagged_mut) to get mutable query items.
+    /// - [`World::entities_with_tag`](crate::world::World::entities_with_tag) to get the tagged entities.
+    #[inline]
+    pub fn iter_tagged(
+        &self,
+        filter: WithTag,
+    ) -> QueryManyIter<'_, 's, D::ReadOnly, F, TaggedEntities<'w>> {
+        // SAFETY: The tag index is only mutated through `&mut World` or by hooks, neither of which
+        // can run while a query is alive.
+        let tag_index = unsafe { self.world.world_metadata() }.tag_index();
+        self.iter_many(tag_index.entities(filter))
+    }
+
+    /// Returns an iterator over the query items of the entities tagged with the name of `filter`,
+    /// in no particular order.
+    ///
+    /// # See also
+    ///
+    /// - [`iter_tagged`](Self::iter_tagged) to get read-only query items.
+    #[inline]
+    pub fn iter_tagged_mut(
+        &mut self,
+        filter: WithTag,
+    ) -> QueryManyIter<'_, 's, D, F, TaggedEntities<'w>> {
+        // SAFETY: The tag index is only mutated through `&mut World` or by hooks, neither of which
+        // can run while a query is alive.
+        let tag_index = unsafe { self.world.world_metadata() }.tag_index();
+        self.iter_many_mut(tag_index.entities(filter))
+    }
+
     /// Returns an [`Iterator`] over the query items.
     ///
     /// This iterator is always guaranteed to return results from each matching entity once and only once.
@@ -478,6 +531,138 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         }
     }
 
+    /// Returns an [`Iterator`] over the read-only query items, in a deterministic order.
+    ///
+    /// Items are returned by archetype, in the order the archetypes were created, and then by
+    /// the row of the entity in its table.
+    /// As a result, two worlds built by the same sequence of operations iterate in the same order,
+    /// which replays and lockstep simulations can rely on.
+    ///
+    /// This order still changes when archetypes are created in a different order, for example
+    /// when components are inserted in a different order.
+    /// To iterate in an order that only depends on the items, use
+    /// [`sort_by_cached_key`](Self::sort_by_cached_key).
+    ///
+    /// This collects the matching entities first, so it is slower than [`iter`](Self::iter).
+    ///
+    /// # See also
+    ///
+    /// [`iter_stable_mut`](Self::iter_stable_mut) for mutable query items.
+    pub fn iter_stable(&self) -> QueryManyIter<'_, 's, D::ReadOnly, F, std::vec::IntoIter<Entity>> {
+        self.iter_many(self.stable_entities())
+    }
+
+    /// Returns an iterator over the query items, in the deterministic order described in
+    /// [`iter_stable`](Self::iter_stable).
+    pub fn iter_stable_mut(&mut self) -> QueryManyIter<'_, 's, D, F, std::vec::IntoIter<Entity>> {
+        let entities = self.stable_entities();
+        self.iter_many_mut(entities)
+    }
+
+    /// Returns an [`Iterator`] over the read-only query items, sorted by the key computed by `f`
+    /// for each item.
+    ///
+    /// The key is computed once per item. The sort is stable: items with equal keys are returned in
+    /// the order of [`iter_stable`](Self::iter_stable).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Unit { id: u32, health: u32 }
+    /// fn resolve_combat(query: Query<&Unit>) {
+    ///     // Units are processed in the same order on every peer.
+    ///     for unit in query.sort_by_cached_key(|unit| unit.id) {
+    ///         // ...
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(resolve_combat);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// - [`sort_unstable_by_cached_key`](Self::sort_unstable_by_cached_key) for a faster sort
+    ///   when keys are unique.
+    /// - [`sort_by_cached_key_mut`](Self::sort_by_cached_key_mut) for mutable query items.
+    pub fn sort_by_cached_key<K: Ord>(
+        &self,
+        f: impl FnMut(ROQueryItem<'_, D>) -> K,
+    ) -> QueryManyIter<'_, 's, D::ReadOnly, F, std::vec::IntoIter<Entity>> {
+        self.iter_many(self.sorted_entities(f, false))
+    }
+
+    /// Returns an [`Iterator`] over the read-only query items, sorted by the key computed by `f`
+    /// for each item.
+    ///
+    /// Unlike [`sort_by_cached_key`](Self::sort_by_cached_key), the order of items with equal keys
+    /// is unspecified.
+    pub fn sort_unstable_by_cached_key<K: Ord>(
+        &self,
+        f: impl FnMut(ROQueryItem<'_, D>) -> K,
+    ) -> QueryManyIter<'_, 's, D::ReadOnly, F, std::vec::IntoIter<Entity>> {
+        self.iter_many(self.sorted_entities(f, true))
+    }
+
+    /// Returns an iterator over the query items, sorted by the key computed by `f` for each
+    /// read-only item.
+    ///
+    /// See [`sort_by_cached_key`](Self::sort_by_cached_key) for details.
+    pub fn sort_by_cached_key_mut<K: Ord>(
+        &mut self,
+        f: impl FnMut(ROQueryItem<'_, D>) -> K,
+    ) -> QueryManyIter<'_, 's, D, F, std::vec::IntoIter<Entity>> {
+        let entities = self.sorted_entities(f, false);
+        self.iter_many_mut(entities)
+    }
+
+    /// Returns an iterator over the query items, sorted by the key computed by `f` for each
+    /// read-only item.
+    ///
+    /// See [`sort_unstable_by_cached_key`](Self::sort_unstable_by_cached_key) for details.
+    pub fn sort_unstable_by_cached_key_mut<K: Ord>(
+        &mut self,
+        f: impl FnMut(ROQueryItem<'_, D>) -> K,
+    ) -> QueryManyIter<'_, 's, D, F, std::vec::IntoIter<Entity>> {
+        let entities = self.sorted_entities(f, true);
+        self.iter_many_mut(entities)
+    }
+
+    /// Returns the entities matched by the archetypes of this query, in the order of
+    /// [`iter_stable`](Self::iter_stable).
+    fn stable_entities(&self) -> Vec<Entity> {
+        let archetypes = self.world.archetypes();
+        let mut entities = Vec::new();
+        for index in self.state.matched_archetypes.ones() {
+            let mut rows: Vec<_> = archetypes[ArchetypeId::new(index)]
+                .entities()
+                .iter()
+                .map(|entity| (entity.table_row().as_usize(), entity.id()))
+                .collect();
+            rows.sort_unstable_by_key(|(row, _)| *row);
+            entities.extend(rows.into_iter().map(|(_, entity)| entity));
+        }
+        entities
+    }
+
+    fn sorted_entities<K: Ord>(
+        &self,
+        mut f: impl FnMut(ROQueryItem<'_, D>) -> K,
+        unstable: bool,
+    ) -> Vec<Entity> {
+        let mut keys: Vec<_> = self
+            .stable_entities()
+            .into_iter()
+            .filter_map(|entity| Some((f(self.get(entity).ok()?), entity)))
+            .collect();
+        if unstable {
+            keys.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        } else {
+            keys.sort_by(|(a, _), (b, _)| a.cmp(b));
+        }
+        keys.into_iter().map(|(_, entity)| entity).collect()
+    }
+
     /// Returns a [`QueryCombinationIter`] over all combinations of `K` read-only query items without repetition.
     ///
     /// This iterator is always guaranteed to return results from each unique pair of matching entities.
@@ -1383,6 +1568,38 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
         self.transmute_lens()
     }
 
+    /// Returns a [`QueryLens`] that can be used to get a query matching the same entities as this
+    /// one, and the entities hidden from it by the
+    /// [`DefaultQueryFilters`](crate::entity_disabling::DefaultQueryFilters) of the world, such as
+    /// the entities with a [`Disabled`](crate::entity_disabling::Disabled) component.
+    ///
+    /// ## Example
+    ///
+    /// ```rust
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Health(u32);
+    /// #
+    /// fn count_all_health(mut query: Query<&Health>) {
+    ///     let enabled = query.iter().count();
+    ///     let all = query.including_disabled().query().iter().count();
+    ///     println!("{} of {all} entities are disabled", all - enabled);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(count_all_health);
+    /// ```
+    #[track_caller]
+    pub fn including_disabled(&mut self) -> QueryLens<'_, D, F> {
+        let components = self.world.components();
+        let state = self.state.including_disabled(components);
+        QueryLens {
+            world: self.world,
+            state,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        }
+    }
+
     /// Returns a [`QueryLens`] that can be used to get a query with the combined fetch.
     ///
     /// For example, this can take a `Query<&A>` and a `Queryy<&B>` and return a `Query<&A, &B>`.
@@ -1471,6 +1688,215 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
             this_run: self.this_run,
         }
     }
+
+    /// Returns an [`Iterator`] over the read-only items of the entities matched by both this
+    /// query and `other`, as `(item, other_item)` pairs.
+    ///
+    /// The join is resolved per archetype rather than per entity: only the archetypes matched by
+    /// both queries are visited, and the items of both queries are fetched straight from the rows of
+    /// each of them, without looking entities up like [`get`](Self::get) does. The query matching
+    /// the fewest archetypes is the one scanned for shared archetypes, so joining a narrow query
+    /// with a broad one costs about as much as iterating the narrow one.
+    ///
+    /// Since every entity lives in exactly one archetype, no hash join on entities is needed.
+    ///
+    /// Unlike [`join`](Self::join), this doesn't build a new [`QueryState`], and works with any
+    /// pair of queries of the same world, whose components don't need to be related.
+    ///
+    /// Iteration order is not guaranteed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #
+    /// # #[derive(Component)]
+    /// # struct Position(f32);
+    /// # #[derive(Component)]
+    /// # struct Target(Entity);
+    /// #
+    /// fn follow_system(
+    ///     positions: Query<&Position>,
+    ///     followers: Query<(Entity, &Target)>,
+    /// ) {
+    ///     for (position, (follower, target)) in positions.iter_join(&followers) {
+    ///         println!("{follower:?} at {} follows {:?}", position.0, target.0);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(follow_system);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` doesn't belong to the same [`World`](crate::world::World) as this query.
+    ///
+    /// # See also
+    ///
+    /// - [`join`](Self::join) to combine the terms of two queries into a single new query.
+    pub fn iter_join<'a, OtherD: QueryData, OtherF: QueryFilter>(
+        &'a self,
+        other: &'a Query<'_, '_, OtherD, OtherF>,
+    ) -> QueryJoinIter<'a, D, F, OtherD, OtherF> {
+        assert_eq!(
+            self.world.id(),
+            other.world.id(),
+            "Attempted to join queries of different worlds."
+        );
+
+        let (narrow, broad) = if self.state.matched_archetypes.count_ones(..)
+            <= other.state.matched_archetypes.count_ones(..)
+        {
+            (
+                &self.state.matched_archetypes,
+                &other.state.matched_archetypes,
+            )
+        } else {
+            (
+                &other.state.matched_archetypes,
+                &self.state.matched_archetypes,
+            )
+        };
+        let archetypes = narrow
+            .ones()
+            .filter(|&index| broad.contains(index))
+            .map(ArchetypeId::new)
+            .collect::<Vec<_>>();
+
+        let left = self.state.as_readonly();
+        let right = other.state.as_readonly();
+        // SAFETY:
+        // - Both queries have permission to access their components, and were checked to belong
+        //   to the same world.
+        // - The fetches are read-only, so they can be aliased even if the queries are mutable.
+        unsafe {
+            QueryJoinIter {
+                world: self.world,
+                left_fetch: <D::ReadOnly as WorldQuery>::init_fetch(
+                    self.world,
+                    &left.fetch_state,
+                    self.last_run,
+                    self.this_run,
+                ),
+                left_filter: F::init_fetch(
+                    self.world,
+                    &left.filter_state,
+                    self.last_run,
+                    self.this_run,
+                ),
+                right_fetch: <OtherD::ReadOnly as WorldQuery>::init_fetch(
+                    other.world,
+                    &right.fetch_state,
+                    other.last_run,
+                    other.this_run,
+                ),
+                right_filter: OtherF::init_fetch(
+                    other.world,
+                    &right.filter_state,
+                    other.last_run,
+                    other.this_run,
+                ),
+                left,
+                right,
+                archetypes: archetypes.into_iter(),
+                entities: [].iter(),
+            }
+        }
+    }
+}
+
+/// An [`Iterator`] over the read-only items of the entities matched by two queries.
+///
+/// This struct is created by the [`Query::iter_join`] method.
+pub struct QueryJoinIter<'a, D: QueryData, F: QueryFilter, OtherD: QueryData, OtherF: QueryFilter> {
+    world: UnsafeWorldCell<'a>,
+    left: &'a QueryState<D::ReadOnly, F>,
+    right: &'a QueryState<OtherD::ReadOnly, OtherF>,
+    left_fetch: <D::ReadOnly as WorldQuery>::Fetch<'a>,
+    left_filter: F::Fetch<'a>,
+    right_fetch: <OtherD::ReadOnly as WorldQuery>::Fetch<'a>,
+    right_filter: OtherF::Fetch<'a>,
+    archetypes: std::vec::IntoIter<ArchetypeId>,
+    entities: std::slice::Iter<'a, ArchetypeEntity>,
+}
+
+impl<'a, D: QueryData, F: QueryFilter, OtherD: QueryData, OtherF: QueryFilter> Iterator
+    for QueryJoinIter<'a, D, F, OtherD, OtherF>
+{
+    type Item = (ROQueryItem<'a, D>, ROQueryItem<'a, OtherD>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            for entity in self.entities.by_ref() {
+                let (id, row) = (entity.id(), entity.table_row());
+                // SAFETY: set_archetype was called prior on all the fetches, for the archetype
+                // `entity` belongs to, and each entity is fetched at most once.
+                unsafe {
+                    // Both queries match the archetype, but their non-archetypal filters may not.
+                    if F::filter_fetch(&mut self.left_filter, id, row)
+                        && OtherF::filter_fetch(&mut self.right_filter, id, row)
+                    {
+                        return Some((
+                            <D::ReadOnly as WorldQuery>::fetch(&mut self.left_fetch, id, row),
+                            <OtherD::ReadOnly as WorldQuery>::fetch(&mut self.right_fetch, id, row),
+                        ));
+                    }
+                }
+            }
+
+            let archetype_id = self.archetypes.next()?;
+            let archetype = &self.world.archetypes()[archetype_id];
+            // SAFETY: The tables are only used to look up the table of `archetype`.
+            let table = &unsafe { self.world.storages() }.tables[archetype.table_id()];
+            // SAFETY: `archetype` and `table` are from the world the fetches were created for,
+            // the states are the ones the fetches were initialized with, and both queries match
+            // `archetype`.
+            unsafe {
+                <D::ReadOnly as WorldQuery>::set_archetype(
+                    &mut self.left_fetch,
+                    &self.left.fetch_state,
+                    archetype,
+                    table,
+                );
+                F::set_archetype(
+                    &mut self.left_filter,
+                    &self.left.filter_state,
+                    archetype,
+                    table,
+                );
+                <OtherD::ReadOnly as WorldQuery>::set_archetype(
+                    &mut self.right_fetch,
+                    &self.right.fetch_state,
+                    archetype,
+                    table,
+                );
+                OtherF::set_archetype(
+                    &mut self.right_filter,
+                    &self.right.filter_state,
+                    archetype,
+                    table,
+                );
+            }
+            self.entities = archetype.entities().iter();
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let archetypes = self.world.archetypes();
+        let remaining = self.entities.len()
+            + self
+                .archetypes
+                .as_slice()
+                .iter()
+                .map(|&archetype| archetypes[archetype].len())
+                .sum::<usize>();
+        // Without per-entity filters, every entity of a shared archetype is yielded.
+        let min_size = if F::IS_ARCHETYPAL && OtherF::IS_ARCHETYPAL {
+            remaining
+        } else {
+            0
+        };
+        (min_size, Some(remaining))
+    }
 }
 
 impl<'w, 's, D: QueryData, F: QueryFilter> IntoIterator for &'w Query<'_, 's, D, F> {
@@ -0,0 +1,474 @@
+use crate::component::Tick;
+use crate::prelude::World;
+use crate::system::{ExclusiveSystemParam, ReadOnlySystemParam, SystemMeta, SystemParam};
+use crate::world::unsafe_world_cell::UnsafeWorldCell;
+use bevy_utils::{synccell::SyncCell, tracing::warn};
+use std::alloc::{self, Layout};
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+/// The capacity in bytes of a [`Scratch`] whose capacity isn't specified.
+pub const DEFAULT_SCRATCH_CAPACITY: usize = 16 * 1024;
+
+/// The alignment of the memory chunks of a [`ScratchArena`].
+const CHUNK_ALIGN: usize = 16;
+
+/// [`SystemParam`] that provides frame-local scratch memory, in a bump allocator that is reset
+/// every time the system runs.
+///
+/// Systems that build temporary collections every frame can allocate them in the scratch memory
+/// of the system instead of the global allocator: allocating only bumps a pointer, and the memory
+/// is reused from one run to the next. Use [`Scratch::vec`] for growable vectors, and
+/// [`Scratch::alloc_slice_copy`] or [`Scratch::alloc_from_iter`] for slices.
+///
+/// The `CAPACITY` in bytes is allocated up-front when the system is initialized. If a run needs
+/// more, the arena allocates more memory, logs a warning with the peak usage, and grows its
+/// capacity to fit it on the next runs. [`Scratch::peak_bytes`] helps picking the right capacity.
+///
+/// Each [`Scratch`] parameter has its own memory, which is not visible to other systems.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::Scratch;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// fn lowest_health(healths: Query<(Entity, &Health)>, scratch: Scratch<{ 64 * 1024 }>) {
+///     let mut sorted = scratch.vec();
+///     sorted.extend(healths.iter().map(|(entity, health)| (health.0, entity)));
+///     sorted.sort_unstable();
+///     for (health, entity) in sorted.iter().take(10) {
+///         // ...
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(lowest_health);
+/// ```
+pub struct Scratch<'s, const CAPACITY: usize = DEFAULT_SCRATCH_CAPACITY> {
+    arena: &'s ScratchArena,
+}
+
+impl<'s, const CAPACITY: usize> Scratch<'s, CAPACITY> {
+    /// Moves `value` to the scratch memory, and returns a reference to it.
+    pub fn alloc<T: Copy>(&self, value: T) -> &'s mut T {
+        let ptr = self.arena.alloc_layout(Layout::new::<T>()).cast::<T>();
+        // SAFETY: the pointer is valid for writes of `T`, and the memory isn't reused until the
+        // arena is reset, which requires the `'s` borrow of the arena to end.
+        unsafe {
+            ptr.as_ptr().write(value);
+            &mut *ptr.as_ptr()
+        }
+    }
+
+    /// Copies `slice` to the scratch memory, and returns a reference to the copy.
+    pub fn alloc_slice_copy<T: Copy>(&self, slice: &[T]) -> &'s mut [T] {
+        let ptr = self.arena.alloc_array::<T>(slice.len());
+        // SAFETY: the pointer is valid for writes of `slice.len()` items, and doesn't overlap
+        // with `slice` as it was just allocated.
+        unsafe {
+            ptr.as_ptr()
+                .copy_from_nonoverlapping(slice.as_ptr(), slice.len());
+            std::slice::from_raw_parts_mut(ptr.as_ptr(), slice.len())
+        }
+    }
+
+    /// Allocates a slice of `len` items in the scratch memory, initialized with the values
+    /// returned by `f` for each index.
+    pub fn alloc_slice_fill_with<T: Copy>(
+        &self,
+        len: usize,
+        mut f: impl FnMut(usize) -> T,
+    ) -> &'s mut [T] {
+        let ptr = self.arena.alloc_array::<T>(len);
+        for index in 0..len {
+            // SAFETY: the pointer is valid for writes of `len` items.
+            unsafe { ptr.as_ptr().add(index).write(f(index)) };
+        }
+        // SAFETY: all `len` items were initialized.
+        unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr(), len) }
+    }
+
+    /// Collects `iter` into a slice in the scratch memory.
+    pub fn alloc_from_iter<T: Copy>(&self, iter: impl IntoIterator<Item = T>) -> &'s mut [T] {
+        let mut vec = self.vec();
+        vec.extend(iter);
+        vec.into_slice()
+    }
+
+    /// Creates an empty [`ScratchVec`] in the scratch memory.
+    pub fn vec<T>(&self) -> ScratchVec<'s, T> {
+        ScratchVec::new(self.arena)
+    }
+
+    /// Creates an empty [`ScratchVec`] in the scratch memory, with room for `capacity` items.
+    pub fn vec_with_capacity<T>(&self, capacity: usize) -> ScratchVec<'s, T> {
+        let mut vec = ScratchVec::new(self.arena);
+        vec.reserve(capacity);
+        vec
+    }
+
+    /// Returns the number of bytes allocated in the scratch memory during this run.
+    pub fn allocated_bytes(&self) -> usize {
+        self.arena.used.get()
+    }
+
+    /// Returns the largest number of bytes allocated in the scratch memory during a run of the
+    /// system so far, this run included.
+    pub fn peak_bytes(&self) -> usize {
+        self.arena.peak.max(self.arena.used.get())
+    }
+
+    /// Returns the number of bytes the scratch memory can hold without allocating more memory.
+    ///
+    /// This starts at `CAPACITY`, and grows if a run needs more.
+    pub fn capacity(&self) -> usize {
+        self.arena.capacity
+    }
+}
+
+impl<const CAPACITY: usize> std::fmt::Debug for Scratch<'_, CAPACITY> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scratch")
+            .field("allocated_bytes", &self.allocated_bytes())
+            .field("peak_bytes", &self.peak_bytes())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+// SAFETY: only accesses internal state
+unsafe impl<const CAPACITY: usize> SystemParam for Scratch<'_, CAPACITY> {
+    type State = SyncCell<ScratchArena>;
+    type Item<'w, 's> = Scratch<'s, CAPACITY>;
+
+    fn init_state(_world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        SyncCell::new(ScratchArena::new(CAPACITY, system_meta.name.clone()))
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        _world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        let arena = state.get();
+        arena.reset();
+        Scratch { arena }
+    }
+}
+
+// SAFETY: only accesses internal state
+unsafe impl<const CAPACITY: usize> ReadOnlySystemParam for Scratch<'_, CAPACITY> {}
+
+impl<const CAPACITY: usize> ExclusiveSystemParam for Scratch<'_, CAPACITY> {
+    type State = SyncCell<ScratchArena>;
+    type Item<'s> = Scratch<'s, CAPACITY>;
+
+    fn init(_world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        SyncCell::new(ScratchArena::new(CAPACITY, system_meta.name.clone()))
+    }
+
+    fn get_param<'s>(state: &'s mut Self::State, _system_meta: &SystemMeta) -> Self::Item<'s> {
+        let arena = state.get();
+        arena.reset();
+        Scratch { arena }
+    }
+}
+
+/// A block of memory owned by a [`ScratchArena`].
+struct Chunk {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl Chunk {
+    fn new(size: usize) -> Self {
+        let layout = Layout::from_size_align(size.max(CHUNK_ALIGN), CHUNK_ALIGN)
+            .expect("scratch memory chunk is too large");
+        // SAFETY: the layout has a non-zero size.
+        let ptr = unsafe { alloc::alloc(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        Self { ptr, layout }
+    }
+}
+
+impl Drop for Chunk {
+    fn drop(&mut self) {
+        // SAFETY: the pointer was allocated with this layout.
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
+
+/// The state of a [`Scratch`] system parameter: a bump allocator over chunks of memory.
+pub struct ScratchArena {
+    chunks: RefCell<Vec<Chunk>>,
+    /// The offset of the next allocation in the last chunk.
+    offset: Cell<usize>,
+    /// The number of bytes allocated since the last reset, including alignment padding.
+    used: Cell<usize>,
+    capacity: usize,
+    peak: usize,
+    system_name: Cow<'static, str>,
+}
+
+// SAFETY: the chunks are only pointers to memory owned by the arena, which holds no `!Send` values
+// since `ScratchVec` drops its items and the other allocations are `Copy`.
+unsafe impl Send for ScratchArena {}
+
+impl ScratchArena {
+    fn new(capacity: usize, system_name: Cow<'static, str>) -> Self {
+        let chunks = if capacity > 0 {
+            vec![Chunk::new(capacity)]
+        } else {
+            Vec::new()
+        };
+        Self {
+            chunks: RefCell::new(chunks),
+            offset: Cell::new(0),
+            used: Cell::new(0),
+            capacity,
+            peak: 0,
+            system_name,
+        }
+    }
+
+    /// Frees all allocations, and merges the chunks into a single one if the last run needed more
+    /// than the capacity.
+    fn reset(&mut self) {
+        let used = *self.used.get_mut();
+        self.peak = self.peak.max(used);
+        let chunks = self.chunks.get_mut();
+        if chunks.len() > 1 {
+            warn!(
+                "The scratch memory of {} needed {} bytes, more than its capacity of {} bytes. Consider increasing its capacity.",
+                self.system_name, used, self.capacity
+            );
+            // the chunks were all needed, as each one only starts when the previous is full
+            self.capacity = chunks.iter().map(|chunk| chunk.layout.size()).sum();
+            *chunks = vec![Chunk::new(self.capacity)];
+        }
+        *self.offset.get_mut() = 0;
+        *self.used.get_mut() = 0;
+    }
+
+    fn alloc_layout(&self, layout: Layout) -> NonNull<u8> {
+        let mut chunks = self.chunks.borrow_mut();
+        loop {
+            if let Some(chunk) = chunks.last() {
+                let base = chunk.ptr.as_ptr() as usize;
+                let offset = self.offset.get();
+                let start = (base + offset).next_multiple_of(layout.align()) - base;
+                let end = start + layout.size();
+                if end <= chunk.layout.size() {
+                    self.offset.set(end);
+                    self.used.set(self.used.get() + end - offset);
+                    // SAFETY: `start` is within the chunk.
+                    return unsafe { NonNull::new_unchecked(chunk.ptr.as_ptr().add(start)) };
+                }
+            }
+            let last_size = chunks.last().map_or(0, |chunk| chunk.layout.size());
+            let size = (layout.size() + layout.align()).max(last_size * 2);
+            chunks.push(Chunk::new(size));
+            self.offset.set(0);
+        }
+    }
+
+    fn alloc_array<T>(&self, len: usize) -> NonNull<T> {
+        if std::mem::size_of::<T>() == 0 {
+            return NonNull::dangling();
+        }
+        let layout = Layout::array::<T>(len).expect("scratch allocation is too large");
+        self.alloc_layout(layout).cast()
+    }
+}
+
+/// A growable vector in the scratch memory of a system, created with [`Scratch::vec`].
+///
+/// Growing the vector moves its items to a new allocation in the scratch memory, and the previous
+/// one is only reclaimed when the scratch memory is reset. Reserving the expected capacity up-front
+/// with [`Scratch::vec_with_capacity`] avoids that waste.
+pub struct ScratchVec<'s, T> {
+    arena: &'s ScratchArena,
+    ptr: NonNull<T>,
+    len: usize,
+    capacity: usize,
+}
+
+impl<'s, T> ScratchVec<'s, T> {
+    fn new(arena: &'s ScratchArena) -> Self {
+        let capacity = if std::mem::size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            0
+        };
+        Self {
+            arena,
+            ptr: NonNull::dangling(),
+            len: 0,
+            capacity,
+        }
+    }
+
+    /// Returns the number of items the vector can hold without growing.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Reserves room for at least `additional` more items.
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self
+            .len
+            .checked_add(additional)
+            .expect("scratch vector capacity overflow");
+        if required <= self.capacity {
+            return;
+        }
+        let capacity = required.max(self.capacity * 2).max(4);
+        let ptr = self.arena.alloc_array::<T>(capacity);
+        // SAFETY: both allocations are valid for `len` items and don't overlap.
+        unsafe {
+            ptr.as_ptr()
+                .copy_from_nonoverlapping(self.ptr.as_ptr(), self.len);
+        }
+        self.ptr = ptr;
+        self.capacity = capacity;
+    }
+
+    /// Appends `value` to the end of the vector.
+    pub fn push(&mut self, value: T) {
+        if self.len == self.capacity {
+            self.reserve(1);
+        }
+        // SAFETY: the vector has room for one more item.
+        unsafe { self.ptr.as_ptr().add(self.len).write(value) };
+        self.len += 1;
+    }
+
+    /// Removes the last item of the vector and returns it, or `None` if it is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        // SAFETY: the item at `len` was initialized, and is no longer part of the vector.
+        Some(unsafe { self.ptr.as_ptr().add(self.len).read() })
+    }
+
+    /// Removes all items of the vector, keeping its capacity.
+    pub fn clear(&mut self) {
+        let items: *mut [T] = self.as_mut_slice();
+        self.len = 0;
+        // SAFETY: the items were initialized, and are no longer part of the vector.
+        unsafe { items.drop_in_place() };
+    }
+
+    /// Returns the items of the vector as a slice.
+    pub fn as_slice(&self) -> &[T] {
+        // SAFETY: the first `len` items are initialized.
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Returns the items of the vector as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        // SAFETY: the first `len` items are initialized.
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+
+    /// Converts the vector into a slice that lives as long as the scratch memory.
+    pub fn into_slice(self) -> &'s mut [T]
+    where
+        T: Copy,
+    {
+        let this = std::mem::ManuallyDrop::new(self);
+        // SAFETY: the first `len` items are initialized, and the memory isn't reused until the
+        // arena is reset.
+        unsafe { std::slice::from_raw_parts_mut(this.ptr.as_ptr(), this.len) }
+    }
+}
+
+impl<T> Drop for ScratchVec<'_, T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T> Deref for ScratchVec<'_, T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for ScratchVec<'_, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> Extend<T> for ScratchVec<'_, T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for ScratchVec<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::system::{Local, RunSystemOnce};
+    use crate::world::World;
+
+    #[test]
+    fn scratch_memory() {
+        fn system(scratch: Scratch<256>, mut runs: Local<u32>) -> (usize, usize, usize) {
+            *runs += 1;
+            let mut vec = scratch.vec();
+            vec.extend(0..*runs * 32);
+            assert_eq!(vec.iter().sum::<u32>(), (0..*runs * 32).sum());
+            let strings = scratch.vec_with_capacity::<String>(2);
+            drop(strings);
+            let copied = scratch.alloc_slice_copy(&vec[..4]);
+            assert_eq!(copied, &[0, 1, 2, 3]);
+            assert_eq!(*scratch.alloc(7u64), 7);
+            (
+                scratch.allocated_bytes(),
+                scratch.peak_bytes(),
+                scratch.capacity(),
+            )
+        }
+
+        let mut world = World::new();
+        let id = world.register_system(system);
+        let (used, peak, capacity) = world.run_system(id).unwrap();
+        assert!(used <= 256);
+        assert_eq!(peak, used);
+        assert_eq!(capacity, 256);
+
+        // the second run needs more than the capacity, so the third one has room for it
+        let (used, peak, capacity) = world.run_system(id).unwrap();
+        assert!(used > 256);
+        assert_eq!(peak, used);
+        assert_eq!(capacity, 256);
+        let (_, _, capacity) = world.run_system(id).unwrap();
+        assert!(capacity >= peak);
+
+        let slice = world.run_system_once(|scratch: Scratch<0>| {
+            scratch.alloc_from_iter((0..5).map(|i| i * 2)).to_vec()
+        });
+        assert_eq!(slice, vec![0, 2, 4, 6, 8]);
+    }
+}
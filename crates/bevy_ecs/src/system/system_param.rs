@@ -2,7 +2,7 @@ pub use crate::change_detection::{NonSendMut, Res, ResMut};
 use crate::{
     archetype::{Archetype, Archetypes},
     bundle::Bundles,
-    change_detection::{Ticks, TicksMut},
+    change_detection::{FieldTicksMut, Ticks, TicksMut},
     component::{ComponentId, ComponentTicks, Components, Tick},
     entity::Entities,
     query::{
@@ -10,7 +10,7 @@ use crate::{
         ReadOnlyQueryData,
     },
     system::{Query, SystemMeta},
-    world::{unsafe_world_cell::UnsafeWorldCell, FromWorld, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, FilteredEntityMut, FromWorld, World},
 };
 use bevy_ecs_macros::impl_param_set;
 pub use bevy_ecs_macros::Resource;
@@ -198,17 +198,13 @@ unsafe impl<D: QueryData + 'static, F: QueryFilter + 'static> SystemParam for Qu
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
         let state = QueryState::new_with_access(world, &mut system_meta.archetype_component_access);
-        assert_component_access_compatibility(
-            &system_meta.name,
+        add_query_component_access(
+            system_meta,
             std::any::type_name::<D>(),
             std::any::type_name::<F>(),
-            &system_meta.component_access_set,
-            &state.component_access,
+            &state,
             world,
         );
-        system_meta
-            .component_access_set
-            .add(state.component_access.clone());
         state
     }
 
@@ -254,6 +250,129 @@ fn assert_component_access_compatibility(
     panic!("error[B0001]: Query<{query_type}, {filter_type}> in system {system_name} accesses component(s) {accesses} in a way that conflicts with a previous system parameter. Consider using `Without<T>` to create disjoint Queries or merging conflicting Queries into a `ParamSet`. See: https://bevyengine.org/learn/errors/#b0001");
 }
 
+fn add_query_component_access<D: QueryData, F: QueryFilter>(
+    system_meta: &mut SystemMeta,
+    query_type: &'static str,
+    filter_type: &'static str,
+    state: &QueryState<D, F>,
+    world: &World,
+) {
+    assert_component_access_compatibility(
+        &system_meta.name,
+        query_type,
+        filter_type,
+        &system_meta.component_access_set,
+        &state.component_access,
+        world,
+    );
+    system_meta
+        .component_access_set
+        .add(state.component_access.clone());
+    if state.traversal_access.has_any_read() {
+        // Relationships may be traversed to any entity, so these reads aren't filtered.
+        let mut traversal_access = FilteredAccess::default();
+        traversal_access
+            .access_mut()
+            .extend(&state.traversal_access);
+        assert_component_access_compatibility(
+            &system_meta.name,
+            query_type,
+            filter_type,
+            &system_meta.component_access_set,
+            &traversal_access,
+            world,
+        );
+        system_meta.component_access_set.add(traversal_access);
+    }
+}
+
+/// A [`Query`] built at run time from a [`DynamicQueryBuilder`](crate::query::DynamicQueryBuilder), returning
+/// [`FilteredEntityMut`] items, or [`FilteredEntityRef`](crate::world::FilteredEntityRef) items
+/// when iterated immutably.
+///
+/// The description of the query is given to the system with
+/// [`FunctionSystem::with_dynamic_query`](super::FunctionSystem::with_dynamic_query), or to a
+/// [`SystemState`](super::SystemState) with
+/// [`SystemState::with_dynamic_queries`](super::SystemState::with_dynamic_queries). The access of
+/// the query is only known once the system is initialized, and is then checked against the other
+/// parameters of the system and scheduled like the access of any other [`Query`].
+///
+/// # Panics
+///
+/// When the system is initialized, if it wasn't given a description for this parameter.
+pub struct DynamicQuery<'w, 's> {
+    query: Query<'w, 's, FilteredEntityMut<'static>>,
+}
+
+impl<'w, 's> Deref for DynamicQuery<'w, 's> {
+    type Target = Query<'w, 's, FilteredEntityMut<'static>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.query
+    }
+}
+
+impl<'w, 's> DerefMut for DynamicQuery<'w, 's> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.query
+    }
+}
+
+impl<'w, 's> DynamicQuery<'w, 's> {
+    /// Returns the inner [`Query`].
+    pub fn into_inner(self) -> Query<'w, 's, FilteredEntityMut<'static>> {
+        self.query
+    }
+}
+
+// SAFETY: Relevant query ComponentId and ArchetypeComponentId access is applied to SystemMeta. If
+// this Query conflicts with any prior access, a panic will occur.
+unsafe impl SystemParam for DynamicQuery<'_, '_> {
+    type State = QueryState<FilteredEntityMut<'static>>;
+    type Item<'w, 's> = DynamicQuery<'w, 's>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let Some(builder) = system_meta.dynamic_queries.pop() else {
+            panic!(
+                "DynamicQuery in system {} has no description. Give one with `FunctionSystem::with_dynamic_query`.",
+                system_meta.name
+            );
+        };
+        let state = builder.build(world);
+        add_query_component_access(
+            system_meta,
+            "DynamicQuery",
+            std::any::type_name::<()>(),
+            &state,
+            world,
+        );
+        state
+    }
+
+    unsafe fn new_archetype(
+        state: &mut Self::State,
+        archetype: &Archetype,
+        system_meta: &mut SystemMeta,
+    ) {
+        // SAFETY: The caller ensures that `archetype` is from the World the state was initialized from.
+        unsafe { state.new_archetype(archetype, &mut system_meta.archetype_component_access) };
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: We have registered all of the query's world accesses,
+        // so the caller ensures that `world` has permission to access any
+        // world data that the query needs.
+        let query = unsafe { Query::new(world, state, system_meta.last_run, change_tick) };
+        DynamicQuery { query }
+    }
+}
+
 /// A collection of potentially conflicting [`SystemParam`]s allowed by disjoint access.
 ///
 /// Allows systems to safely access and interact with up to 8 mutually exclusive [`SystemParam`]s, such as
@@ -529,6 +648,89 @@ unsafe impl<'a, T: Resource> SystemParam for Option<Res<'a, T>> {
     }
 }
 
+/// A [`SystemParam`] reading the version of the resource `T`, see [`World::resource_version`].
+///
+/// Unlike change detection, the version doesn't depend on when the system last ran, so it can be
+/// shared with other systems or async tasks to tell whether the resource changed in between.
+///
+/// # Panics
+///
+/// Panics when used as a [`SystemParam`] if the resource does not exist.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::ResourceVersion;
+/// #[derive(Resource)]
+/// struct Settings;
+///
+/// fn save_settings(version: ResourceVersion<Settings>, mut saved: Local<Option<u32>>) {
+///     if *saved != Some(version.get()) {
+///         // Save the settings...
+///         *saved = Some(version.get());
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(save_settings);
+/// ```
+pub struct ResourceVersion<'w, T: Resource> {
+    version: u32,
+    marker: PhantomData<&'w T>,
+}
+
+impl<'w, T: Resource> ResourceVersion<'w, T> {
+    /// Returns the version of the resource.
+    pub fn get(&self) -> u32 {
+        self.version
+    }
+}
+
+impl<'w, T: Resource> Debug for ResourceVersion<'w, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ResourceVersion")
+            .field(&self.version)
+            .finish()
+    }
+}
+
+// SAFETY: Only reads the version of a single World resource
+unsafe impl<'a, T: Resource> ReadOnlySystemParam for ResourceVersion<'a, T> {}
+
+// SAFETY: this impl defers to `Res`, which initializes and validates the correct world access.
+unsafe impl<'a, T: Resource> SystemParam for ResourceVersion<'a, T> {
+    type State = ComponentId;
+    type Item<'w, 's> = ResourceVersion<'w, T>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        Res::<T>::init_state(world, system_meta)
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        &mut component_id: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        // SAFETY: The resource is registered as read in `init_state`, so no system can write its
+        // ticks while this one runs.
+        let version = unsafe { world.storages() }
+            .resources
+            .get(component_id)
+            .filter(|data| data.is_present())
+            .map(|data| data.version())
+            .unwrap_or_else(|| {
+                panic!(
+                    "Resource requested by {} does not exist: {}",
+                    system_meta.name,
+                    std::any::type_name::<T>()
+                )
+            });
+        ResourceVersion {
+            version,
+            marker: PhantomData,
+        }
+    }
+}
+
 // SAFETY: Res ComponentId and ArchetypeComponentId access is applied to SystemMeta. If this Res
 // conflicts with any prior access, a panic will occur.
 unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
@@ -584,6 +786,8 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
             ticks: TicksMut {
                 added: value.ticks.added,
                 changed: value.ticks.changed,
+                entity_changed: None,
+                fields_changed: FieldTicksMut::default(),
                 last_run: system_meta.last_run,
                 this_run: change_tick,
             },
@@ -614,6 +818,8 @@ unsafe impl<'a, T: Resource> SystemParam for Option<ResMut<'a, T>> {
                 ticks: TicksMut {
                     added: value.ticks.added,
                     changed: value.ticks.changed,
+                    entity_changed: None,
+                    fields_changed: FieldTicksMut::default(),
                     last_run: system_meta.last_run,
                     this_run: change_tick,
                 },
@@ -784,6 +990,9 @@ unsafe impl<'a, T: FromWorld + Send + 'static> SystemParam for Local<'a, T> {
 /// computations up-front as possible. Buffers cannot be applied in parallel,
 /// so you should try to minimize the time spent in [`SystemBuffer::apply`].
 pub trait SystemBuffer: FromWorld + Send + 'static {
+    /// Prepares the buffer once it is created with [`FromWorld`], when the system is initialized.
+    fn init(&mut self, _system_meta: &SystemMeta, _world: &mut World) {}
+
     /// Applies any deferred mutations to the [`World`].
     fn apply(&mut self, system_meta: &SystemMeta, world: &mut World);
 }
@@ -943,7 +1152,9 @@ unsafe impl<T: SystemBuffer> SystemParam for Deferred<'_, T> {
 
     fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
         system_meta.set_has_deferred();
-        SyncCell::new(T::from_world(world))
+        let mut buffer = T::from_world(world);
+        buffer.init(system_meta, world);
+        SyncCell::new(buffer)
     }
 
     fn apply(state: &mut Self::State, system_meta: &SystemMeta, world: &mut World) {
@@ -1546,6 +1757,7 @@ mod tests {
     use super::*;
     use crate::{
         self as bevy_ecs, // Necessary for the `SystemParam` Derive when used inside `bevy_ecs`.
+        prelude::*,
         system::assert_is_system,
     };
     use std::cell::RefCell;
@@ -1760,4 +1972,67 @@ mod tests {
         schedule.add_systems((non_send_param_set, non_send_param_set, non_send_param_set));
         schedule.run(&mut world);
     }
+
+    #[test]
+    fn dynamic_query() {
+        #[derive(Component)]
+        struct A(u32);
+
+        #[derive(Component)]
+        struct B;
+
+        #[derive(Resource, Default)]
+        struct Count(usize);
+
+        fn increment(mut query: DynamicQuery, mut count: ResMut<Count>) {
+            for mut entity in query.iter_mut() {
+                let a = entity.components().next().unwrap();
+                let mut a = entity.get_mut_by_id(a).unwrap();
+                // SAFETY: The only component of the query is `A`.
+                unsafe { a.as_mut().deref_mut::<A>().0 += 1 };
+                count.0 += 1;
+            }
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Count>();
+        let entity = world.spawn(A(0)).id();
+        world.spawn((A(0), B));
+        let a = world.init_component::<A>();
+        let b = world.init_component::<B>();
+
+        let system = IntoSystem::into_system(increment)
+            .with_dynamic_query(crate::query::DynamicQueryBuilder::new().write(a).without(b));
+        let mut schedule = crate::schedule::Schedule::default();
+        schedule.add_systems(system);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Count>().0, 2);
+        assert_eq!(world.get::<A>(entity).unwrap().0, 2);
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn dynamic_query_conflicts() {
+        #[derive(Component)]
+        struct A;
+
+        fn conflicting(_: DynamicQuery, _: Query<&A>) {}
+
+        let mut world = World::new();
+        let a = world.init_component::<A>();
+        let mut system = IntoSystem::into_system(conflicting)
+            .with_dynamic_query(crate::query::DynamicQueryBuilder::new().write(a));
+        system.initialize(&mut world);
+    }
+
+    #[test]
+    #[should_panic]
+    fn dynamic_query_without_description() {
+        fn no_description(_: DynamicQuery) {}
+
+        let mut world = World::new();
+        IntoSystem::into_system(no_description).initialize(&mut world);
+    }
 }
@@ -0,0 +1,445 @@
+use std::{any::TypeId, marker::PhantomData};
+
+use bevy_utils::all_tuples;
+
+use crate::{
+    archetype::Archetype,
+    change_detection::{FieldTicksMut, Mut, MutUntyped, TicksMut},
+    component::{Component, ComponentId, Tick},
+    entity::Entity,
+    query::{Access, FilteredAccess},
+    system::{Res, ResMut, Resource, SystemMeta, SystemParam},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// A [`SystemParam`] giving a system access to a restricted view of the [`World`], limited to the
+/// components and resources declared by `F`.
+///
+/// Unlike an exclusive system taking `&mut World`, a system with a `WorldScope` still runs in
+/// parallel with the systems that don't conflict with its declared access. The access is declared
+/// by `F`, a [`ScopeFilter`]: a tuple of `&C` and `&mut C` for components, and `Res<R>` and
+/// `ResMut<R>` for resources.
+///
+/// Components are accessed on any entity, by [`Entity`], so a `WorldScope` conflicts with every
+/// query accessing the same components in an incompatible way.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::WorldScope;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Target(Entity);
+///
+/// #[derive(Resource)]
+/// struct Damage(u32);
+///
+/// fn attack(
+///     attackers: Query<&Target>,
+///     mut scope: WorldScope<(&mut Health, Res<Damage>)>,
+/// ) {
+///     let damage = scope.resource::<Damage>().0;
+///     for target in &attackers {
+///         if let Some(mut health) = scope.get_mut::<Health>(target.0) {
+///             health.0 = health.0.saturating_sub(damage);
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(attack);
+/// ```
+///
+/// # Panics
+///
+/// The methods of a `WorldScope` panic when accessing a component or resource outside of its
+/// declared access.
+pub struct WorldScope<'w, 's, F: ScopeFilter> {
+    world: UnsafeWorldCell<'w>,
+    state: &'s WorldScopeState,
+    last_run: Tick,
+    this_run: Tick,
+    marker: PhantomData<F>,
+}
+
+/// The state of a [`WorldScope`]: the components and resources it may access.
+pub struct WorldScopeState {
+    components: Access<ComponentId>,
+    resources: Access<ComponentId>,
+}
+
+impl WorldScopeState {
+    /// Returns the components accessed by the scope.
+    pub fn components(&self) -> &Access<ComponentId> {
+        &self.components
+    }
+
+    /// Returns the resources accessed by the scope.
+    pub fn resources(&self) -> &Access<ComponentId> {
+        &self.resources
+    }
+}
+
+/// The components and resources a [`WorldScope`] may access.
+///
+/// This is implemented for `&C` and `&mut C` where `C` is a [`Component`], for [`Res<R>`] and
+/// [`ResMut<R>`] where `R` is a [`Resource`], and for tuples of these.
+pub trait ScopeFilter {
+    /// Registers the components and resources in `world`, and adds them to `state`.
+    fn init_access(world: &mut World, state: &mut WorldScopeState);
+}
+
+impl<C: Component> ScopeFilter for &C {
+    fn init_access(world: &mut World, state: &mut WorldScopeState) {
+        state.components.add_read(world.init_component::<C>());
+    }
+}
+
+impl<C: Component> ScopeFilter for &mut C {
+    fn init_access(world: &mut World, state: &mut WorldScopeState) {
+        state.components.add_write(world.init_component::<C>());
+    }
+}
+
+impl<R: Resource> ScopeFilter for Res<'_, R> {
+    fn init_access(world: &mut World, state: &mut WorldScopeState) {
+        state
+            .resources
+            .add_read(world.components.init_resource::<R>());
+    }
+}
+
+impl<R: Resource> ScopeFilter for ResMut<'_, R> {
+    fn init_access(world: &mut World, state: &mut WorldScopeState) {
+        state
+            .resources
+            .add_write(world.components.init_resource::<R>());
+    }
+}
+
+macro_rules! impl_scope_filter_tuple {
+    ($($filter: ident),*) => {
+        impl<$($filter: ScopeFilter),*> ScopeFilter for ($($filter,)*) {
+            #[allow(unused_variables)]
+            fn init_access(world: &mut World, state: &mut WorldScopeState) {
+                $($filter::init_access(world, state);)*
+            }
+        }
+    };
+}
+
+all_tuples!(impl_scope_filter_tuple, 0, 15, F);
+
+// SAFETY: the components and resources of the scope are registered in `init_state`, for
+// conflict detection, and in `new_archetype`, for the archetypes containing the components. The
+// methods of the scope check that the accessed components and resources were registered.
+unsafe impl<F: ScopeFilter + 'static> SystemParam for WorldScope<'_, '_, F> {
+    type State = WorldScopeState;
+    type Item<'w, 's> = WorldScope<'w, 's, F>;
+
+    fn init_state(world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        let mut state = WorldScopeState {
+            components: Access::default(),
+            resources: Access::default(),
+        };
+        F::init_access(world, &mut state);
+
+        let mut access = FilteredAccess::default();
+        access.access_mut().extend(&state.components);
+        access.access_mut().extend(&state.resources);
+        let conflicts = system_meta
+            .component_access_set
+            .get_conflicts_single(&access);
+        if !conflicts.is_empty() {
+            let accesses = conflicts
+                .into_iter()
+                .map(|id| world.components.get_info(id).unwrap().name())
+                .collect::<Vec<_>>()
+                .join(", ");
+            panic!(
+                "error[B0001]: WorldScope<{}> in system {} accesses {accesses} in a way that conflicts with a previous system parameter.",
+                std::any::type_name::<F>(),
+                system_meta.name,
+            );
+        }
+        system_meta.component_access_set.add(access);
+
+        for id in state.resources.reads_and_writes() {
+            world.initialize_resource_internal(id);
+            let archetype_component_id = world.get_resource_archetype_component_id(id).unwrap();
+            if state.resources.has_write(id) {
+                system_meta
+                    .archetype_component_access
+                    .add_write(archetype_component_id);
+            } else {
+                system_meta
+                    .archetype_component_access
+                    .add_read(archetype_component_id);
+            }
+        }
+
+        state
+    }
+
+    unsafe fn new_archetype(
+        state: &mut Self::State,
+        archetype: &Archetype,
+        system_meta: &mut SystemMeta,
+    ) {
+        for id in state.components.reads_and_writes() {
+            let Some(archetype_component_id) = archetype.get_archetype_component_id(id) else {
+                continue;
+            };
+            if state.components.has_write(id) {
+                system_meta
+                    .archetype_component_access
+                    .add_write(archetype_component_id);
+            } else {
+                system_meta
+                    .archetype_component_access
+                    .add_read(archetype_component_id);
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        WorldScope {
+            world,
+            state,
+            last_run: system_meta.last_run,
+            this_run: change_tick,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'w, 's, F: ScopeFilter> WorldScope<'w, 's, F> {
+    /// Returns the components and resources the scope may access.
+    pub fn state(&self) -> &WorldScopeState {
+        self.state
+    }
+
+    /// Returns `true` if `entity` exists and has a component `C`.
+    ///
+    /// This only reads metadata, so `C` doesn't need to be in the access of the scope.
+    pub fn contains<C: Component>(&self, entity: Entity) -> bool {
+        self.world
+            .get_entity(entity)
+            .is_some_and(|entity| entity.contains::<C>())
+    }
+
+    /// Returns the component `C` of `entity`, if it exists.
+    ///
+    /// # Panics
+    ///
+    /// If the scope can't read `C`.
+    pub fn get<C: Component>(&self, entity: Entity) -> Option<&C> {
+        self.component_id::<C>(false)?;
+        // SAFETY: the scope has registered read access to `C`, and the shared borrow of the scope
+        // prevents mutable access to `C` through it.
+        unsafe { self.world.get_entity(entity)?.get::<C>() }
+    }
+
+    /// Returns the component `C` of `entity`, if it exists.
+    ///
+    /// # Panics
+    ///
+    /// If the scope can't write `C`.
+    pub fn get_mut<C: Component>(&mut self, entity: Entity) -> Option<Mut<'_, C>> {
+        self.component_id::<C>(true)?;
+        // SAFETY: the scope has registered write access to `C`, and the mutable borrow of the
+        // scope prevents other access to `C` through it.
+        unsafe {
+            self.world
+                .get_entity(entity)?
+                .get_mut_using_ticks::<C>(self.last_run, self.this_run)
+        }
+    }
+
+    /// Returns the resource `R`.
+    ///
+    /// # Panics
+    ///
+    /// If the resource doesn't exist, or the scope can't read it.
+    pub fn resource<R: Resource>(&self) -> &R {
+        match self.get_resource() {
+            Some(resource) => resource,
+            None => panic!(
+                "Resource requested by a WorldScope does not exist: {}",
+                std::any::type_name::<R>()
+            ),
+        }
+    }
+
+    /// Returns the resource `R`, if it exists.
+    ///
+    /// # Panics
+    ///
+    /// If the scope can't read `R`.
+    pub fn get_resource<R: Resource>(&self) -> Option<&R> {
+        let id = self.resource_id::<R>(false)?;
+        // SAFETY: the scope has registered read access to `R`, and the shared borrow of the scope
+        // prevents mutable access to `R` through it.
+        unsafe {
+            self.world
+                .get_resource_by_id(id)
+                .map(|ptr| ptr.deref::<R>())
+        }
+    }
+
+    /// Returns the resource `R`.
+    ///
+    /// # Panics
+    ///
+    /// If the resource doesn't exist, or the scope can't write it.
+    pub fn resource_mut<R: Resource>(&mut self) -> Mut<'_, R> {
+        match self.get_resource_mut() {
+            Some(resource) => resource,
+            None => panic!(
+                "Resource requested by a WorldScope does not exist: {}",
+                std::any::type_name::<R>()
+            ),
+        }
+    }
+
+    /// Returns the resource `R`, if it exists.
+    ///
+    /// # Panics
+    ///
+    /// If the scope can't write `R`.
+    pub fn get_resource_mut<R: Resource>(&mut self) -> Option<Mut<'_, R>> {
+        let id = self.resource_id::<R>(true)?;
+        // SAFETY: the scope has registered write access to `R`, and the mutable borrow of the
+        // scope prevents other access to `R` through it.
+        let MutUntyped { value, ticks } = unsafe { self.world.get_resource_mut_by_id(id)? };
+        Some(Mut {
+            // SAFETY: the resource with this id is of type `R`.
+            value: unsafe { value.deref_mut::<R>() },
+            ticks: TicksMut {
+                added: ticks.added,
+                changed: ticks.changed,
+                entity_changed: None,
+                fields_changed: FieldTicksMut::default(),
+                last_run: self.last_run,
+                this_run: self.this_run,
+            },
+        })
+    }
+
+    /// Returns the id of the component `C`, or `None` if it was never registered, and panics if
+    /// the scope can't access it.
+    fn component_id<C: Component>(&self, write: bool) -> Option<ComponentId> {
+        let id = self.world.components().get_id(TypeId::of::<C>());
+        self.check_access(
+            id,
+            &self.state.components,
+            write,
+            std::any::type_name::<C>(),
+        );
+        id
+    }
+
+    /// Returns the id of the resource `R`, or `None` if it was never registered, and panics if
+    /// the scope can't access it.
+    fn resource_id<R: Resource>(&self, write: bool) -> Option<ComponentId> {
+        let id = self.world.components().get_resource_id(TypeId::of::<R>());
+        self.check_access(id, &self.state.resources, write, std::any::type_name::<R>());
+        id
+    }
+
+    fn check_access(
+        &self,
+        id: Option<ComponentId>,
+        access: &Access<ComponentId>,
+        write: bool,
+        name: &str,
+    ) {
+        let allowed = id.is_some_and(|id| {
+            if write {
+                access.has_write(id)
+            } else {
+                access.has_read(id)
+            }
+        });
+        if !allowed {
+            panic!(
+                "WorldScope<{}> does not have {} access to {name}.",
+                std::any::type_name::<F>(),
+                if write { "write" } else { "read" },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{
+        prelude::*,
+        system::{RunSystemOnce, WorldScope},
+    };
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Resource)]
+    struct R(u32);
+
+    #[test]
+    fn scoped_access() {
+        let mut world = World::new();
+        world.insert_resource(R(2));
+        let entity = world.spawn((A(1), B)).id();
+
+        world.run_system_once(move |mut scope: WorldScope<(&mut A, ResMut<R>)>| {
+            let value = scope.get::<A>(entity).unwrap().0;
+            scope.resource_mut::<R>().0 += value;
+            scope.get_mut::<A>(entity).unwrap().0 = 10;
+            assert!(scope.contains::<B>(entity));
+        });
+        assert_eq!(world.get::<A>(entity), Some(&A(10)));
+        assert_eq!(world.resource::<R>().0, 3);
+    }
+
+    #[test]
+    fn registers_access() {
+        let mut world = World::new();
+        world.spawn(A(0));
+        world.insert_resource(R(0));
+        let a = world.init_component::<A>();
+        let r = world.components().resource_id::<R>().unwrap();
+
+        let mut system = IntoSystem::into_system(|_: WorldScope<(&mut A, Res<R>)>| {});
+        system.initialize(&mut world);
+        system.update_archetype_component_access(world.as_unsafe_world_cell());
+        assert!(system.component_access().has_write(a));
+        assert!(system.component_access().has_read(r));
+        assert!(!system.component_access().has_write(r));
+        assert!(system.archetype_component_access().has_any_write());
+    }
+
+    #[test]
+    #[should_panic = "conflicts with a previous system parameter"]
+    fn conflicting_access() {
+        let mut world = World::new();
+        world.run_system_once(|_: Query<&A>, _: WorldScope<&mut A>| {});
+    }
+
+    #[test]
+    #[should_panic = "does not have write access"]
+    fn undeclared_access() {
+        let mut world = World::new();
+        let entity = world.spawn(A(0)).id();
+        world.run_system_once(move |mut scope: WorldScope<&A>| {
+            scope.get_mut::<A>(entity);
+        });
+    }
+}
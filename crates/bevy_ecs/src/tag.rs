@@ -0,0 +1,345 @@
+//! Tagging entities with string labels, without declaring a marker component type per tag.
+//!
+//! An entity is tagged by giving it a [`Tags`] component. Each [`Tag`] is an interned string, so
+//! comparing tags is as cheap as comparing pointers, and every [`World`] keeps a [`TagIndex`] of
+//! the entities with each tag:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::tag::{Tags, WithTag};
+//! #[derive(Component)]
+//! struct Health(u32);
+//!
+//! let mut world = World::new();
+//! let goblin = world.spawn((Health(10), Tags::new(["enemy", "melee"]))).id();
+//! world.spawn((Health(20), Tags::new(["ally"])));
+//!
+//! assert_eq!(world.entities_with_tag("enemy").collect::<Vec<_>>(), [goblin]);
+//!
+//! fn hurt_enemies(mut query: Query<&mut Health>) {
+//!     let mut enemies = query.iter_tagged_mut(WithTag("enemy"));
+//!     while let Some(mut health) = enemies.fetch_next() {
+//!         health.0 -= 1;
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(hurt_enemies);
+//! ```
+//!
+//! The index is updated by the hooks of [`Tags`], when it is inserted or removed. To change the
+//! tags of an entity, insert a new [`Tags`] rather than assigning it through a query.
+
+use std::fmt;
+
+use bevy_utils::{hashbrown, HashMap};
+
+use crate::{
+    component::{Component, ComponentHooks, ComponentId, StorageType},
+    entity::{Entity, EntityHashSet},
+    intern::{Interned, Interner},
+    world::{DeferredWorld, World},
+};
+
+static TAG_INTERNER: Interner<str> = Interner::new();
+
+/// An interned string labeling entities, stored in their [`Tags`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Tag(Interned<str>);
+
+impl Tag {
+    /// Returns the tag named `name`, interning it if it's used for the first time.
+    pub fn new(name: &str) -> Self {
+        Self(TAG_INTERNER.intern(name))
+    }
+
+    /// Returns the name of the tag.
+    pub fn as_str(&self) -> &'static str {
+        self.0 .0
+    }
+}
+
+impl From<&str> for Tag {
+    fn from(name: &str) -> Self {
+        Self::new(name)
+    }
+}
+
+impl From<WithTag<'_>> for Tag {
+    fn from(filter: WithTag<'_>) -> Self {
+        Self::new(filter.0)
+    }
+}
+
+impl fmt::Debug for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The [`Tag`]s of an entity, indexed by its [`World`] to find the entities with a given tag.
+///
+/// See the [module docs](crate::tag) for more information.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Tags(Vec<Tag>);
+
+impl Tags {
+    /// Creates the tags from a list of names or [`Tag`]s, ignoring duplicates.
+    pub fn new<T: Into<Tag>>(tags: impl IntoIterator<Item = T>) -> Self {
+        tags.into_iter().collect()
+    }
+
+    /// Returns the tags with `tag` added.
+    pub fn with(mut self, tag: impl Into<Tag>) -> Self {
+        let tag = tag.into();
+        if !self.0.contains(&tag) {
+            self.0.push(tag);
+        }
+        self
+    }
+
+    /// Returns the tags with `tag` removed.
+    pub fn without(mut self, tag: impl Into<Tag>) -> Self {
+        let tag = tag.into();
+        self.0.retain(|&other| other != tag);
+        self
+    }
+
+    /// Returns `true` if `tag` is one of the tags.
+    pub fn contains(&self, tag: impl Into<Tag>) -> bool {
+        self.0.contains(&tag.into())
+    }
+
+    /// Iterates over the tags, in the order they were added.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Tag> + '_ {
+        self.0.iter().copied()
+    }
+
+    /// Returns the number of tags.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no tags.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T: Into<Tag>> FromIterator<T> for Tags {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Tags::default(), |tags, tag| tags.with(tag))
+    }
+}
+
+impl Component for Tags {
+    const STORAGE_TYPE: StorageType = StorageType::Table;
+
+    fn register_component_hooks(hooks: &mut ComponentHooks) {
+        hooks.on_insert(on_insert_tags).on_remove(on_remove_tags);
+    }
+}
+
+fn on_insert_tags(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let (tags, index) = world.tags_and_index_mut(entity);
+    // The tags this entity had before being replaced are unknown, so it's removed from every tag.
+    index.remove_entity(entity);
+    if let Some(tags) = tags {
+        index.insert(entity, tags);
+    }
+}
+
+fn on_remove_tags(mut world: DeferredWorld, entity: Entity, _: ComponentId) {
+    let (tags, index) = world.tags_and_index_mut(entity);
+    if let Some(tags) = tags {
+        index.remove(entity, tags);
+    }
+}
+
+/// The entities with each [`Tag`] in a [`World`], kept up to date by the hooks of [`Tags`].
+///
+/// Retrieved with [`World::tag_index`].
+#[derive(Debug, Default)]
+pub struct TagIndex {
+    entities: HashMap<Tag, EntityHashSet>,
+}
+
+impl TagIndex {
+    /// Iterates over the entities with `tag`, in no particular order.
+    pub fn entities(&self, tag: impl Into<Tag>) -> TaggedEntities<'_> {
+        TaggedEntities {
+            iter: self
+                .entities
+                .get(&tag.into())
+                .map(|entities| entities.iter()),
+        }
+    }
+
+    /// Returns the number of entities with `tag`.
+    pub fn count(&self, tag: impl Into<Tag>) -> usize {
+        self.entities.get(&tag.into()).map_or(0, EntityHashSet::len)
+    }
+
+    /// Iterates over the tags of at least one entity.
+    pub fn tags(&self) -> impl Iterator<Item = Tag> + '_ {
+        self.entities
+            .iter()
+            .filter(|(_, entities)| !entities.is_empty())
+            .map(|(&tag, _)| tag)
+    }
+
+    fn insert(&mut self, entity: Entity, tags: &Tags) {
+        for tag in tags.iter() {
+            self.entities.entry(tag).or_default().insert(entity);
+        }
+    }
+
+    fn remove(&mut self, entity: Entity, tags: &Tags) {
+        for tag in tags.iter() {
+            if let Some(entities) = self.entities.get_mut(&tag) {
+                entities.remove(&entity);
+            }
+        }
+    }
+
+    fn remove_entity(&mut self, entity: Entity) {
+        for entities in self.entities.values_mut() {
+            entities.remove(&entity);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entities.clear();
+    }
+}
+
+/// An iterator over the entities with a [`Tag`], returned by [`World::entities_with_tag`] and
+/// [`TagIndex::entities`].
+#[derive(Clone)]
+pub struct TaggedEntities<'a> {
+    iter: Option<hashbrown::hash_set::Iter<'a, Entity>>,
+}
+
+impl<'a> Iterator for TaggedEntities<'a> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        self.iter.as_mut()?.next().copied()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.as_ref().map_or((0, Some(0)), Iterator::size_hint)
+    }
+}
+
+impl<'a> ExactSizeIterator for TaggedEntities<'a> {}
+
+/// Only matches the entities tagged with the given name, for
+/// [`Query::iter_tagged`](crate::system::Query::iter_tagged) and
+/// [`Query::iter_tagged_mut`](crate::system::Query::iter_tagged_mut).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WithTag<'a>(pub &'a str);
+
+impl World {
+    /// Retrieves the [`TagIndex`] of this world, listing the entities with each [`Tag`].
+    #[inline]
+    pub fn tag_index(&self) -> &TagIndex {
+        &self.tag_index
+    }
+
+    /// Iterates over the entities whose [`Tags`] contain `tag`, in no particular order.
+    #[inline]
+    pub fn entities_with_tag(&self, tag: impl Into<Tag>) -> TaggedEntities<'_> {
+        self.tag_index.entities(tag)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Tag, Tags, WithTag};
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::system::RunSystemOnce;
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    fn tagged(world: &World, tag: &str) -> Vec<Entity> {
+        let mut entities: Vec<_> = world.entities_with_tag(tag).collect();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn tags_are_interned() {
+        let tag = Tag::new("enemy");
+        assert_eq!(tag, Tag::new(&String::from("enemy")));
+        assert_ne!(tag, Tag::new("ally"));
+        assert_eq!(tag.as_str(), "enemy");
+
+        let tags = Tags::new(["enemy", "melee", "enemy"]);
+        assert_eq!(tags.len(), 2);
+        assert!(tags.contains("melee"));
+        assert!(!tags.without("melee").contains("melee"));
+    }
+
+    #[test]
+    fn index_follows_tags() {
+        let mut world = World::new();
+        let a = world.spawn(Tags::new(["enemy"])).id();
+        let b = world.spawn(Tags::new(["enemy", "flying"])).id();
+        let c = world.spawn(Tags::new(["ally"])).id();
+
+        assert_eq!(tagged(&world, "enemy"), [a, b]);
+        assert_eq!(tagged(&world, "flying"), [b]);
+        assert_eq!(tagged(&world, "unknown"), []);
+
+        // Replacing the tags moves the entity to its new tags.
+        world.entity_mut(c).insert(Tags::new(["enemy"]));
+        assert_eq!(tagged(&world, "enemy"), [a, b, c]);
+        assert_eq!(world.tag_index().count("ally"), 0);
+
+        world.entity_mut(a).remove::<Tags>();
+        world.despawn(b);
+        assert_eq!(tagged(&world, "enemy"), [c]);
+        assert_eq!(tagged(&world, "flying"), []);
+        assert_eq!(
+            world.tag_index().tags().collect::<Vec<_>>(),
+            [Tag::new("enemy")]
+        );
+
+        world.clear_entities();
+        assert_eq!(tagged(&world, "enemy"), []);
+    }
+
+    #[test]
+    fn query_with_tag() {
+        let mut world = World::new();
+        world.spawn((Health(10), Tags::new(["enemy"])));
+        world.spawn((Health(10), Tags::new(["ally"])));
+        world.spawn(Tags::new(["enemy"]));
+
+        fn hurt_enemies(mut query: Query<&mut Health>) {
+            let mut enemies = query.iter_tagged_mut(WithTag("enemy"));
+            while let Some(mut health) = enemies.fetch_next() {
+                health.0 -= 5;
+            }
+        }
+        world.run_system_once(hurt_enemies);
+
+        let mut query = world.query::<&Health>();
+        let mut health: Vec<_> = query.iter(&world).map(|health| health.0).collect();
+        health.sort();
+        assert_eq!(health, [5, 10]);
+
+        fn count_enemies(query: Query<&Health>) -> usize {
+            query.iter_tagged(WithTag("enemy")).count()
+        }
+        assert_eq!(world.run_system_once(count_enemies), 1);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::system::{SystemBuffer, SystemMeta};
+use crate::{
+    self as bevy_ecs,
+    system::{Resource, SystemBuffer, SystemMeta},
+};
 
 use std::{fmt::Debug, mem::MaybeUninit};
 
@@ -19,6 +22,12 @@ struct CommandMeta {
 }
 
 /// Densely and efficiently stores a queue of heterogenous types implementing [`Command`].
+///
+/// The queue is an arena: commands are written one after the other into a single buffer, which is
+/// reset without being freed when the queue is applied, so that a system queuing a similar amount
+/// of commands every frame doesn't allocate. The queues of the [`Commands`](crate::system::Commands)
+/// of systems are wrapped in a [`SystemCommandQueue`], which sizes this buffer according to the
+/// [`CommandQueueBudget`] of the world.
 //
 // NOTE: [`CommandQueue`] is implemented via a `Vec<MaybeUninit<u8>>` instead of a `Vec<Box<dyn Command>>`
 // as an optimization. Since commands are used frequently in systems as a way to spawn
@@ -55,6 +64,12 @@ unsafe impl Send for CommandQueue {}
 unsafe impl Sync for CommandQueue {}
 
 impl CommandQueue {
+    /// Returns the number of bytes used by the queued commands.
+    #[inline]
+    pub fn len_bytes(&self) -> usize {
+        self.bytes.len()
+    }
+
     /// Push a [`Command`] onto the queue.
     #[inline]
     pub fn push<C>(&mut self, command: C)
@@ -113,12 +128,15 @@ impl CommandQueue {
         }
     }
 
-    /// Execute the queued [`Command`]s in the world after applying any commands in the world's internal queue.
+    /// Execute the queued [`Command`]s in the world after applying any commands in the world's internal queue
+    /// and any changes queued through [`StructuralCommands`](crate::world::StructuralCommands).
     /// This clears the queue.
     #[inline]
     pub fn apply(&mut self, world: &mut World) {
         // flush the previously queued entities
         world.flush_entities();
+        // apply the structural changes queued from parallel systems in the same pass
+        world.flush_structural_commands();
 
         self.apply_or_drop_queued(Some(world));
     }
@@ -226,6 +244,123 @@ impl CommandQueue {
     }
 }
 
+/// The [`CommandQueue`] of the [`Commands`](crate::system::Commands) of a system, sized according
+/// to a [`CommandQueueBudget`].
+///
+/// The budget is only enforced by this wrapper, so plain [`CommandQueue`]s don't pay for it: it
+/// is checked each time the queue is applied, rather than each time a command is pushed.
+#[derive(Debug, Default)]
+pub struct SystemCommandQueue {
+    queue: CommandQueue,
+    budget: Option<CommandQueueBudget>,
+    // The largest number of bytes applied at once.
+    peak_bytes: usize,
+}
+
+impl SystemCommandQueue {
+    /// Creates an empty queue, allocating the capacity of `budget` up front.
+    pub fn with_budget(budget: CommandQueueBudget) -> Self {
+        let mut queue = Self::default();
+        queue.set_budget(Some(budget));
+        queue
+    }
+
+    /// Returns the budget of the queue, if it has one.
+    #[inline]
+    pub fn budget(&self) -> Option<CommandQueueBudget> {
+        self.budget
+    }
+
+    /// Sets the budget of the queue, allocating its capacity.
+    ///
+    /// If the queue holds more than the capacity of the budget, after an
+    /// [overflow](CommandQueueOverflow), the memory beyond the capacity is freed once the queue
+    /// is applied.
+    pub fn set_budget(&mut self, budget: Option<CommandQueueBudget>) {
+        self.budget = budget;
+        if let Some(budget) = budget {
+            let bytes = &mut self.queue.bytes;
+            if bytes.capacity() > budget.capacity {
+                bytes.shrink_to(budget.capacity);
+            }
+            bytes.reserve_exact(budget.capacity.saturating_sub(bytes.len()));
+        }
+    }
+
+    /// Returns the largest number of bytes the queue held when it was applied.
+    #[inline]
+    pub fn peak_bytes(&self) -> usize {
+        self.peak_bytes
+    }
+
+    /// Returns the wrapped queue.
+    #[inline]
+    pub fn queue(&self) -> &CommandQueue {
+        &self.queue
+    }
+
+    /// Returns the wrapped queue mutably, to push commands to it.
+    #[inline]
+    pub fn queue_mut(&mut self) -> &mut CommandQueue {
+        &mut self.queue
+    }
+}
+
+/// The capacity of the [`CommandQueue`] of each system, and how commands beyond it are handled.
+///
+/// When this resource is present, the queue of the [`Commands`](crate::system::Commands) of each
+/// system allocates its capacity when the system is initialized, and follows changes of the
+/// budget each time it is applied. Without it, queues grow as needed and keep their memory.
+///
+/// The [`CommandQueueStats`] resource tracks the usage of the queues, to tune the capacity.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommandQueueBudget {
+    /// The number of bytes allocated for the commands of each system.
+    ///
+    /// Each command uses the size of its type, plus the size of a function pointer.
+    pub capacity: usize,
+    /// How commands beyond the capacity are handled.
+    pub overflow: CommandQueueOverflow,
+}
+
+impl Default for CommandQueueBudget {
+    fn default() -> Self {
+        Self {
+            capacity: 4096,
+            overflow: CommandQueueOverflow::default(),
+        }
+    }
+}
+
+/// How a [`CommandQueue`] handles the commands beyond the capacity of its [`CommandQueueBudget`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CommandQueueOverflow {
+    /// Grows the buffer of the queue, which is shrunk back to the capacity once the queue is
+    /// applied.
+    #[default]
+    Grow,
+    /// Grows the buffer like [`CommandQueueOverflow::Grow`], and logs a warning naming the system
+    /// when the queue is applied.
+    Warn,
+    /// Panics when the queue is applied, naming the system.
+    Panic,
+}
+
+/// Statistics on the usage of the [`CommandQueue`]s of systems, updated as they are applied when
+/// this resource is present.
+///
+/// The statistics accumulate until the resource is reset, such as every frame.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CommandQueueStats {
+    /// The largest number of bytes applied at once by the queue of a system.
+    pub peak_bytes: usize,
+    /// The total number of bytes applied by the queues of systems.
+    pub total_bytes: usize,
+    /// The number of times the queue of a system was applied holding more than the capacity of
+    /// its [`CommandQueueBudget`].
+    pub overflows: usize,
+}
+
 impl Drop for CommandQueue {
     fn drop(&mut self) {
         if !self.bytes.is_empty() {
@@ -244,6 +379,51 @@ impl SystemBuffer for CommandQueue {
     }
 }
 
+impl SystemBuffer for SystemCommandQueue {
+    fn init(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+        self.set_budget(world.get_resource::<CommandQueueBudget>().copied());
+    }
+
+    #[inline]
+    fn apply(&mut self, system_meta: &SystemMeta, world: &mut World) {
+        let len = self.queue.len_bytes();
+        let overflow = self
+            .budget
+            .filter(|budget| len > budget.capacity)
+            .map(|budget| budget.overflow);
+        if overflow == Some(CommandQueueOverflow::Panic) {
+            panic!(
+                "System {} queued {len} bytes of commands, more than the {} bytes of the CommandQueueBudget.",
+                system_meta.name,
+                self.budget.unwrap().capacity,
+            );
+        }
+
+        SystemBuffer::apply(&mut self.queue, system_meta, world);
+        self.peak_bytes = self.peak_bytes.max(len);
+
+        let overflowed = overflow.is_some();
+        if overflow == Some(CommandQueueOverflow::Warn) {
+            warn!(
+                "System {} queued {len} bytes of commands, more than the {} bytes of the CommandQueueBudget.",
+                system_meta.name,
+                self.budget.unwrap().capacity,
+            );
+        }
+        if let Some(mut stats) = world.get_resource_mut::<CommandQueueStats>() {
+            stats.peak_bytes = stats.peak_bytes.max(len);
+            stats.total_bytes += len;
+            stats.overflows += overflowed as usize;
+        }
+
+        // Follow changes of the budget, and free the memory of an overflow.
+        let budget = world.get_resource::<CommandQueueBudget>().copied();
+        if budget.is_some() || overflowed {
+            self.set_budget(budget);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -379,6 +559,68 @@ mod test {
         assert_eq!(world.entities().len(), 2);
     }
 
+    #[test]
+    fn test_command_queue_budget() {
+        let budget = CommandQueueBudget {
+            capacity: 64,
+            overflow: CommandQueueOverflow::Grow,
+        };
+        let mut queue = SystemCommandQueue::with_budget(budget);
+        assert!(queue.queue.bytes.capacity() >= 64);
+
+        for _ in 0..32 {
+            queue.queue_mut().push(SpawnCommand);
+        }
+        let overflow_len = queue.queue().len_bytes();
+        assert!(overflow_len > 64);
+
+        let mut world = World::new();
+        world.insert_resource(budget);
+        SystemBuffer::apply(&mut queue, &SystemMeta::new::<()>(), &mut world);
+        assert_eq!(world.entities().len(), 32);
+        assert_eq!(queue.peak_bytes(), overflow_len);
+        // The memory of the overflow is freed once the queue is applied.
+        assert!(queue.queue.bytes.capacity() < overflow_len);
+    }
+
+    #[test]
+    #[should_panic = "CommandQueueBudget"]
+    fn test_command_queue_budget_panic() {
+        let mut queue = SystemCommandQueue::with_budget(CommandQueueBudget {
+            capacity: 16,
+            overflow: CommandQueueOverflow::Panic,
+        });
+        for _ in 0..32 {
+            queue.queue_mut().push(SpawnCommand);
+        }
+        SystemBuffer::apply(&mut queue, &SystemMeta::new::<()>(), &mut World::new());
+    }
+
+    #[test]
+    fn test_command_queue_stats() {
+        use crate::{schedule::Schedule, system::Commands};
+
+        let mut world = World::new();
+        world.insert_resource(CommandQueueBudget {
+            capacity: 32,
+            overflow: CommandQueueOverflow::Grow,
+        });
+        world.init_resource::<CommandQueueStats>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|mut commands: Commands| {
+            for _ in 0..8 {
+                commands.add(SpawnCommand);
+            }
+        });
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        let stats = world.resource::<CommandQueueStats>();
+        assert!(stats.peak_bytes > 32);
+        assert_eq!(stats.total_bytes, stats.peak_bytes * 2);
+        assert_eq!(stats.overflows, 2);
+    }
+
     // NOTE: `CommandQueue` is `Send` because `Command` is send.
     // If the `Command` trait gets reworked to be non-send, `CommandQueue`
     // should be reworked.
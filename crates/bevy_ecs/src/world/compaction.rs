@@ -0,0 +1,148 @@
+use crate::{archetype::ArchetypeRow, storage::TableRow, world::World};
+
+/// The outcome of a [`World::compact`] pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionReport {
+    /// The number of bytes of over-allocated storage that were freed.
+    pub bytes_reclaimed: usize,
+    /// The number of table rows and sparse set values moved to restore entity order.
+    pub rows_moved: usize,
+    /// The number of tables whose rows were reordered.
+    pub tables_reordered: usize,
+    /// The number of archetypes whose entities were reordered.
+    pub archetypes_reordered: usize,
+}
+
+impl World {
+    /// Defragments the storage of the world and releases the memory it doesn't use.
+    ///
+    /// This sorts the rows of each [`Table`](crate::storage::Table) and
+    /// [`ComponentSparseSet`](crate::storage::ComponentSparseSet) by entity index, since
+    /// removals shuffle them with `swap_remove`, and sorts the entities of each archetype by table
+    /// row so that queries read their tables in order. It then shrinks tables, sparse sets and
+    /// archetypes to their length, returning the capacity left behind by despawned entities or
+    /// entities that moved to other archetypes.
+    ///
+    /// The cost is proportional to the number of stored entities and components, so this is meant
+    /// to run occasionally, such as after a level unloads. Compacting a world doesn't change
+    /// which components entities have, but the storage reserved for the next spawns is freed too,
+    /// which may cause them to allocate again.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let mut world = World::new();
+    /// let entities: Vec<Entity> = (0..100).map(|i| world.spawn(Health(i)).id()).collect();
+    /// for entity in &entities[..90] {
+    ///     world.despawn(*entity);
+    /// }
+    ///
+    /// let report = world.compact();
+    /// assert!(report.bytes_reclaimed > 0);
+    /// assert_eq!(world.get::<Health>(entities[95]).unwrap().0, 95);
+    /// ```
+    pub fn compact(&mut self) -> CompactionReport {
+        self.flush_entities();
+        let mut report = CompactionReport::default();
+
+        for table in self.storages.tables.iter_mut() {
+            let moved = table.sort_by_entity();
+            if moved > 0 {
+                report.rows_moved += moved;
+                report.tables_reordered += 1;
+                for (row, &entity) in table.entities().iter().enumerate() {
+                    let mut location = self.entities.get(entity).unwrap();
+                    location.table_row = TableRow::from_usize(row);
+                    self.archetypes.archetypes[location.archetype_id.index()]
+                        .set_entity_table_row(location.archetype_row, location.table_row);
+                    // SAFETY: the entity is alive, and its table row was just updated.
+                    unsafe { self.entities.set(entity.index(), location) };
+                }
+            }
+            report.bytes_reclaimed += table.shrink_to_fit();
+        }
+
+        for archetype in &mut self.archetypes.archetypes {
+            if archetype.sort_entities_by_table_row() {
+                report.archetypes_reordered += 1;
+                for (row, archetype_entity) in archetype.entities().iter().enumerate() {
+                    let entity = archetype_entity.id();
+                    let mut location = self.entities.get(entity).unwrap();
+                    location.archetype_row = ArchetypeRow::new(row);
+                    // SAFETY: the entity is alive, and its archetype row was just updated.
+                    unsafe { self.entities.set(entity.index(), location) };
+                }
+            }
+            report.bytes_reclaimed += archetype.shrink_to_fit();
+        }
+
+        for sparse_set in self.storages.sparse_sets.values_mut() {
+            report.rows_moved += sparse_set.sort_by_entity();
+            report.bytes_reclaimed += sparse_set.shrink_to_fit();
+        }
+
+        report.bytes_reclaimed += self.entities.shrink_to_fit();
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, component::Component, entity::Entity, world::World};
+
+    #[derive(Component, Debug, PartialEq)]
+    struct A(usize);
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(storage = "SparseSet")]
+    struct B(usize);
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn compact_preserves_components() {
+        let mut world = World::new();
+        let entities: Vec<Entity> = (0..64).map(|i| world.spawn((A(i), B(i))).id()).collect();
+        // Despawn every other entity and move some to another archetype, to shuffle the rows.
+        for (i, &entity) in entities.iter().enumerate() {
+            if i % 2 == 0 {
+                world.despawn(entity);
+            } else if i % 3 == 0 {
+                world.entity_mut(entity).insert(C);
+            }
+        }
+        for (i, &entity) in entities.iter().enumerate().skip(3).step_by(12) {
+            world.entity_mut(entity).remove::<C>();
+            assert_eq!(world.get::<A>(entity), Some(&A(i)));
+        }
+
+        let report = world.compact();
+        assert!(report.bytes_reclaimed > 0);
+        assert!(report.rows_moved > 0);
+
+        for (i, &entity) in entities.iter().enumerate() {
+            if i % 2 == 0 {
+                assert!(world.get_entity(entity).is_none());
+                continue;
+            }
+            assert_eq!(world.get::<A>(entity), Some(&A(i)));
+            assert_eq!(world.get::<B>(entity), Some(&B(i)));
+        }
+        let mut query = world.query::<(Entity, &A, &B)>();
+        assert_eq!(query.iter(&world).count(), 32);
+        for (entity, a, b) in query.iter(&world) {
+            assert_eq!(entities[a.0], entity);
+            assert_eq!(a.0, b.0);
+        }
+        for table in world.storages().tables.iter() {
+            let indices: Vec<u32> = table.entities().iter().map(|e| e.index()).collect();
+            assert!(indices.windows(2).all(|w| w[0] <= w[1]));
+        }
+
+        // A second pass has nothing left to do.
+        assert_eq!(world.compact(), Default::default());
+    }
+}
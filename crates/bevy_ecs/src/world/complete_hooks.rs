@@ -0,0 +1,105 @@
+use crate::{archetype::Archetype, component::ComponentId, entity::Entity};
+
+use super::DeferredWorld;
+
+/// The type used for hooks run when an entity gets every component of a [`Bundle`], registered
+/// with [`World::register_complete_hook`](super::World::register_complete_hook).
+///
+/// [`Bundle`]: crate::bundle::Bundle
+pub type BundleCompleteHook = for<'w> fn(DeferredWorld<'w>, Entity);
+
+/// The hooks registered with [`World::register_complete_hook`](super::World::register_complete_hook),
+/// with the components each of them waits for.
+#[derive(Debug, Default)]
+pub struct BundleCompleteHooks {
+    hooks: Vec<(Box<[ComponentId]>, BundleCompleteHook)>,
+}
+
+impl BundleCompleteHooks {
+    /// Returns `true` if no hooks are registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Iterates over the registered hooks and the components they wait for.
+    pub fn iter(&self) -> impl Iterator<Item = (&[ComponentId], BundleCompleteHook)> {
+        self.hooks
+            .iter()
+            .map(|(components, hook)| (&**components, *hook))
+    }
+
+    pub(crate) fn push(&mut self, components: &[ComponentId], hook: BundleCompleteHook) {
+        self.hooks.push((components.into(), hook));
+    }
+
+    /// Returns the hooks whose components are all in `new_archetype`, but were not all in
+    /// `old_archetype`.
+    pub(crate) fn completed<'a>(
+        &'a self,
+        old_archetype: Option<&'a Archetype>,
+        new_archetype: &'a Archetype,
+    ) -> impl Iterator<Item = BundleCompleteHook> + 'a {
+        let has_all = |archetype: &Archetype, components: &[ComponentId]| {
+            components.iter().all(|&id| archetype.contains(id))
+        };
+        self.iter()
+            .filter(move |(components, _)| {
+                has_all(new_archetype, components)
+                    && !old_archetype.is_some_and(|old| has_all(old, components))
+            })
+            .map(|(_, hook)| hook)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::world::DeferredWorld;
+
+    #[derive(Component)]
+    struct Position;
+
+    #[derive(Component)]
+    struct Velocity;
+
+    #[derive(Component)]
+    struct Mesh;
+
+    #[derive(Component)]
+    struct Assembled;
+
+    fn assemble(mut world: DeferredWorld, entity: Entity) {
+        world.commands().entity(entity).insert(Assembled);
+    }
+
+    #[test]
+    fn complete_hook_runs_when_every_component_is_present() {
+        let mut world = World::new();
+        world.register_complete_hook::<(Position, Velocity, Mesh)>(assemble);
+
+        let entity = world.spawn(Position).id();
+        world.entity_mut(entity).insert(Mesh);
+        world.flush_commands();
+        assert!(!world.entity(entity).contains::<Assembled>());
+
+        world.entity_mut(entity).insert(Velocity);
+        world.flush_commands();
+        assert!(world.entity(entity).contains::<Assembled>());
+
+        world.entity_mut(entity).remove::<Assembled>();
+        world.entity_mut(entity).insert(Velocity);
+        world.flush_commands();
+        assert!(!world.entity(entity).contains::<Assembled>());
+
+        world.entity_mut(entity).remove::<Velocity>();
+        world.entity_mut(entity).insert(Velocity);
+        world.flush_commands();
+        assert!(world.entity(entity).contains::<Assembled>());
+
+        let spawned = world.spawn((Position, Velocity, Mesh)).id();
+        world.flush_commands();
+        assert!(world.entity(spawned).contains::<Assembled>());
+    }
+}
@@ -1,13 +1,17 @@
 use std::ops::Deref;
 
 use crate::{
+    archetype::Archetype,
+    bundle::Bundle,
     change_detection::MutUntyped,
     component::ComponentId,
     entity::Entity,
     event::{Event, EventId, Events, SendBatchIds},
     prelude::{Component, QueryState},
     query::{QueryData, QueryFilter},
+    storage::TableRow,
     system::{Commands, Query, Resource},
+    tag::{TagIndex, Tags},
 };
 
 use super::{
@@ -52,6 +56,21 @@ impl<'w> From<&'w mut World> for DeferredWorld<'w> {
 }
 
 impl<'w> DeferredWorld<'w> {
+    /// Returns the [`Tags`] of `entity` along with the world's [`TagIndex`], for the hooks of
+    /// [`Tags`] to update the index.
+    pub(crate) fn tags_and_index_mut(&mut self, entity: Entity) -> (Option<&Tags>, &mut TagIndex) {
+        // SAFETY:
+        // - &mut self ensures that there are no outstanding accesses to the world
+        // - the tag index is disjoint from the component data
+        unsafe {
+            let tags = self
+                .world
+                .get_entity(entity)
+                .and_then(|entity| entity.get::<Tags>());
+            (tags, self.world.tag_index_mut())
+        }
+    }
+
     /// Creates a [`Commands`] instance that pushes to the world's command queue
     #[inline]
     pub fn commands(&mut self) -> Commands {
@@ -60,6 +79,31 @@ impl<'w> DeferredWorld<'w> {
         Commands::new_from_entities(queue, self.world.entities())
     }
 
+    /// Queues the spawn of an entity for each bundle of `iter` in the world's structural buffer,
+    /// returning the reserved entities.
+    ///
+    /// Unlike [`commands`](Self::commands), this only needs `&self`, the spawns are applied at
+    /// the next sync point. See [`StructuralCommands`](crate::world::StructuralCommands) for
+    /// details.
+    #[inline]
+    pub fn spawn_batch_deferred<I>(&self, iter: I) -> Vec<Entity>
+    where
+        I: IntoIterator,
+        I::Item: Bundle,
+    {
+        self.world.structural_commands().spawn_batch(iter)
+    }
+
+    /// Queues the despawn of `entity` in the world's structural buffer.
+    ///
+    /// Unlike [`commands`](Self::commands), this only needs `&self`, the despawn is applied at
+    /// the next sync point. See [`StructuralCommands`](crate::world::StructuralCommands) for
+    /// details.
+    #[inline]
+    pub fn despawn_deferred(&self, entity: Entity) {
+        self.world.structural_commands().despawn(entity);
+    }
+
     /// Retrieves a mutable reference to the given `entity`'s [`Component`] of the given type.
     /// Returns `None` if the `entity` does not have a [`Component`] of the given type.
     #[inline]
@@ -270,10 +314,38 @@ impl<'w> DeferredWorld<'w> {
     ) {
         for component_id in targets {
             // SAFETY: Caller ensures that these components exist
-            let hooks = unsafe { self.components().get_info_unchecked(component_id) }.hooks();
+            let info = unsafe { self.world.components().get_info_unchecked(component_id) };
+            if let Some(singleton) = info.singleton() {
+                // uniqueness was checked before the entity was moved, see `assert_singletons_unique`
+                singleton.set(Some(entity));
+            }
+            let hooks = info.hooks();
             if let Some(hook) = hooks.on_add {
                 hook(DeferredWorld { world: self.world }, entity, component_id);
             }
+            for hook in hooks.ordered_on_add.iter() {
+                hook(DeferredWorld { world: self.world }, entity, component_id);
+            }
+        }
+    }
+
+    /// Triggers all batched `on_add` hooks for [`ComponentId`] in target, with the `entities`
+    /// added to an archetype and their table rows.
+    ///
+    /// # Safety
+    /// Caller must ensure [`ComponentId`] in target exist in self.
+    #[inline]
+    pub(crate) unsafe fn trigger_batched_on_add(
+        &mut self,
+        entities: &[(Entity, TableRow)],
+        targets: impl Iterator<Item = ComponentId>,
+    ) {
+        for component_id in targets {
+            // SAFETY: Caller ensures that these components exist
+            let hooks = unsafe { self.world.components().get_info_unchecked(component_id) }.hooks();
+            for hook in hooks.batched_on_add.iter() {
+                hook(DeferredWorld { world: self.world }, entities, component_id);
+            }
         }
     }
 
@@ -293,6 +365,26 @@ impl<'w> DeferredWorld<'w> {
             if let Some(hook) = hooks.on_insert {
                 hook(DeferredWorld { world: self.world }, entity, component_id);
             }
+            for hook in hooks.ordered_on_insert.iter() {
+                hook(DeferredWorld { world: self.world }, entity, component_id);
+            }
+        }
+    }
+
+    /// Triggers the [`BundleCompleteHook`](super::BundleCompleteHook)s whose components are all in
+    /// `new_archetype` but were not all in `old_archetype`.
+    #[inline]
+    pub(crate) fn trigger_on_complete(
+        &mut self,
+        entity: Entity,
+        old_archetype: Option<&Archetype>,
+        new_archetype: &Archetype,
+    ) {
+        // SAFETY: hooks can't be registered through a `DeferredWorld`, so no mutable references
+        // to them exist while they run
+        let hooks = unsafe { self.world.world_metadata() }.complete_hooks();
+        for hook in hooks.completed(old_archetype, new_archetype) {
+            hook(DeferredWorld { world: self.world }, entity);
         }
     }
 
@@ -308,10 +400,39 @@ impl<'w> DeferredWorld<'w> {
     ) {
         for component_id in targets {
             // SAFETY: Caller ensures that these components exist
-            let hooks = unsafe { self.world.components().get_info_unchecked(component_id) }.hooks();
+            let info = unsafe { self.world.components().get_info_unchecked(component_id) };
+            let hooks = info.hooks();
             if let Some(hook) = hooks.on_remove {
                 hook(DeferredWorld { world: self.world }, entity, component_id);
             }
+            for hook in hooks.ordered_on_remove.iter() {
+                hook(DeferredWorld { world: self.world }, entity, component_id);
+            }
+            if let Some(singleton) = info.singleton() {
+                if singleton.get() == Some(entity) {
+                    singleton.set(None);
+                }
+            }
+        }
+    }
+
+    /// Triggers all batched `on_remove` hooks for [`ComponentId`] in target, with the `entities`
+    /// removed from an archetype and their table rows.
+    ///
+    /// # Safety
+    /// Caller must ensure [`ComponentId`] in target exist in self.
+    #[inline]
+    pub(crate) unsafe fn trigger_batched_on_remove(
+        &mut self,
+        entities: &[(Entity, TableRow)],
+        targets: impl Iterator<Item = ComponentId>,
+    ) {
+        for component_id in targets {
+            // SAFETY: Caller ensures that these components exist
+            let hooks = unsafe { self.world.components().get_info_unchecked(component_id) }.hooks();
+            for hook in hooks.batched_on_remove.iter() {
+                hook(DeferredWorld { world: self.world }, entities, component_id);
+            }
         }
     }
 }
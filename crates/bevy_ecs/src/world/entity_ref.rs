@@ -1,8 +1,10 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes},
-    bundle::{Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle},
+    bundle::{
+        assert_singletons_unique, Bundle, BundleId, BundleInfo, BundleInserter, DynamicBundle,
+    },
     change_detection::MutUntyped,
-    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    component::{Component, ComponentId, ComponentInfo, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
     query::{Access, DebugCheckedUnwrap},
     removal_detection::RemovedComponentEvents,
@@ -10,7 +12,7 @@ use crate::{
     world::{Mut, World},
 };
 use bevy_ptr::{OwningPtr, Ptr};
-use std::{any::TypeId, marker::PhantomData};
+use std::{alloc::Layout, any::TypeId, marker::PhantomData, ptr::NonNull};
 use thiserror::Error;
 
 use super::{unsafe_world_cell::UnsafeEntityCell, Ref};
@@ -34,7 +36,7 @@ use super::{unsafe_world_cell::UnsafeEntityCell, Ref};
 /// # bevy_ecs::system::assert_is_system(disjoint_system);
 /// ```
 #[derive(Copy, Clone)]
-pub struct EntityRef<'w>(UnsafeEntityCell<'w>);
+pub struct EntityRef<'w>(pub(crate) UnsafeEntityCell<'w>);
 
 impl<'w> EntityRef<'w> {
     /// # Safety
@@ -262,7 +264,7 @@ impl<'a> TryFrom<&'a FilteredEntityMut<'_>> for EntityRef<'a> {
 /// }
 /// # bevy_ecs::system::assert_is_system(disjoint_system);
 /// ```
-pub struct EntityMut<'w>(UnsafeEntityCell<'w>);
+pub struct EntityMut<'w>(pub(crate) UnsafeEntityCell<'w>);
 
 impl<'w> EntityMut<'w> {
     /// # Safety
@@ -771,6 +773,7 @@ impl<'w> EntityWorldMut<'w> {
             BundleInserter::new::<T>(self.world, self.location.archetype_id, change_tick);
         // SAFETY: location matches current entity. `T` matches `bundle_info`
         self.location = unsafe { bundle_inserter.insert(self.entity, self.location, bundle) };
+        T::on_spawn(self);
         self
     }
 
@@ -902,14 +905,26 @@ impl<'w> EntityWorldMut<'w> {
             // SAFETY: All components in the archetype exist in world
             unsafe {
                 deferred_world.trigger_on_remove(entity, bundle_info.iter_components());
+                deferred_world.trigger_batched_on_remove(
+                    &[(entity, old_location.table_row)],
+                    bundle_info.iter_components(),
+                );
             }
         }
 
+        record_remove(
+            world,
+            bundle_info,
+            old_location.archetype_id,
+            new_archetype_id,
+        );
+
         let archetypes = &mut world.archetypes;
         let storages = &mut world.storages;
         let components = &mut world.components;
         let entities = &mut world.entities;
         let removed_components = &mut world.removed_components;
+        removed_components.begin_operation();
 
         let entity = self.entity;
         let mut bundle_components = bundle_info.iter_components();
@@ -1079,10 +1094,17 @@ impl<'w> EntityWorldMut<'w> {
             // SAFETY: All components in the archetype exist in world
             unsafe {
                 deferred_world.trigger_on_remove(entity, bundle_info.iter_components());
+                deferred_world.trigger_batched_on_remove(
+                    &[(entity, location.table_row)],
+                    bundle_info.iter_components(),
+                );
             }
         }
 
+        record_remove(world, bundle_info, location.archetype_id, new_archetype_id);
+
         let old_archetype = &world.archetypes[location.archetype_id];
+        world.removed_components.begin_operation();
         for component_id in bundle_info.iter_components() {
             if old_archetype.contains(component_id) {
                 world.removed_components.send(component_id, entity);
@@ -1178,6 +1200,291 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Applies several insertions and removals to the entity at once.
+    ///
+    /// Calling [`insert`](Self::insert) and [`remove`](Self::remove) repeatedly moves the entity
+    /// to a new archetype, and its components to a new table, on each call. Operations queued on
+    /// the [`EntityEditor`] are coalesced instead, and the entity is moved once, straight to the
+    /// archetype with all the removals and insertions applied.
+    ///
+    /// Operations are coalesced in the order they are queued: a component inserted twice keeps
+    /// its last value, and a component inserted after being removed is only overwritten.
+    /// The [`Bundle::on_spawn`] effects of the inserted bundles run once all operations have been
+    /// applied.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # #[derive(Component)] struct Health(u32);
+    /// # #[derive(Component)] struct Speed(f32);
+    /// # #[derive(Component)] struct Stunned;
+    /// # let mut world = World::new();
+    /// let mut entity = world.spawn(Stunned);
+    /// entity.edit(|editor| {
+    ///     editor
+    ///         .insert(Health(100))
+    ///         .insert(Speed(2.0))
+    ///         .remove::<Stunned>();
+    /// });
+    /// assert!(entity.contains::<Health>() && entity.contains::<Speed>());
+    /// assert!(!entity.contains::<Stunned>());
+    /// ```
+    pub fn edit(&mut self, f: impl FnOnce(&mut EntityEditor)) -> &mut Self {
+        let mut editor = EntityEditor {
+            world: self.world,
+            inserted: Vec::new(),
+            removed: Vec::new(),
+            on_spawn: Vec::new(),
+        };
+        f(&mut editor);
+        let EntityEditor {
+            mut inserted,
+            removed,
+            on_spawn,
+            ..
+        } = editor;
+
+        inserted.sort_unstable_by_key(|component| component.id);
+        if !removed.is_empty() || !inserted.is_empty() {
+            // SAFETY: the components were initialized by the editor, in this world, and it keeps
+            // the removed and inserted components disjoint
+            self.location = unsafe { self.apply_edit(&removed, &mut inserted) };
+        }
+
+        for on_spawn in on_spawn {
+            on_spawn(self);
+        }
+        self
+    }
+
+    /// Moves the entity to the archetype of its current components minus `removed` plus
+    /// `inserted` in a single move, and writes the values of `inserted`.
+    ///
+    /// The `on_remove` hooks of the removed components run before the move, and the `on_add` and
+    /// `on_insert` hooks of the inserted components after it.
+    ///
+    /// # Safety
+    /// - the components must have been initialized in this world
+    /// - `removed` and `inserted` must be disjoint, and `inserted` sorted by component id
+    unsafe fn apply_edit(
+        &mut self,
+        removed: &[ComponentId],
+        inserted: &mut [EditedComponent],
+    ) -> EntityLocation {
+        let entity = self.entity;
+        let location = self.location;
+        let world = &mut *self.world;
+        let change_tick = world.change_tick();
+
+        let old_archetype = &world.archetypes[location.archetype_id];
+        let old_table_id = old_archetype.table_id();
+        let removed: Vec<_> = removed
+            .iter()
+            .copied()
+            .filter(|&id| old_archetype.contains(id))
+            .collect();
+        let added: Vec<_> = inserted
+            .iter()
+            .map(|component| component.id)
+            .filter(|&id| !old_archetype.contains(id))
+            .collect();
+
+        let new_archetype_id = if removed.is_empty() && added.is_empty() {
+            location.archetype_id
+        } else {
+            let mut table_components: Vec<_> = old_archetype
+                .table_components()
+                .filter(|id| !removed.contains(id))
+                .collect();
+            let mut sparse_set_components: Vec<_> = old_archetype
+                .sparse_set_components()
+                .filter(|id| !removed.contains(id))
+                .collect();
+            let mut new_table = removed
+                .iter()
+                .any(|&id| old_archetype.get_storage_type(id) == Some(StorageType::Table));
+            for &id in &added {
+                // SAFETY: the caller guarantees that the components were initialized
+                match unsafe { world.components.get_info_unchecked(id) }.storage_type() {
+                    StorageType::Table => {
+                        table_components.push(id);
+                        new_table = true;
+                    }
+                    StorageType::SparseSet => sparse_set_components.push(id),
+                }
+            }
+            table_components.sort_unstable();
+            sparse_set_components.sort_unstable();
+            let table_id = if new_table {
+                // SAFETY: all the components exist
+                unsafe {
+                    world
+                        .storages
+                        .tables
+                        .get_id_or_insert(&table_components, &world.components)
+                }
+            } else {
+                old_table_id
+            };
+            // SAFETY: all the components exist, and `table_id` is the table of the table components
+            unsafe {
+                world.archetypes.get_id_or_insert(
+                    &world.components,
+                    table_id,
+                    table_components,
+                    sparse_set_components,
+                )
+            }
+        };
+
+        if world.archetypes[new_archetype_id].has_on_add() {
+            assert_singletons_unique(
+                world.as_unsafe_world_cell_readonly(),
+                entity,
+                added.iter().copied(),
+            );
+        }
+
+        if !removed.is_empty() {
+            // SAFETY: Archetypes cannot be mutably aliased through DeferredWorld
+            let (old_archetype, mut deferred_world) = unsafe {
+                let world = world.as_unsafe_world_cell();
+                (
+                    &world.archetypes()[location.archetype_id],
+                    world.into_deferred(),
+                )
+            };
+            if old_archetype.has_on_remove() {
+                // SAFETY: All the removed components are in the archetype, so they exist in world
+                unsafe {
+                    deferred_world.trigger_on_remove(entity, removed.iter().copied());
+                    deferred_world.trigger_batched_on_remove(
+                        &[(entity, location.table_row)],
+                        removed.iter().copied(),
+                    );
+                }
+            }
+
+            let old_archetype = &world.archetypes[location.archetype_id];
+            world.removed_components.begin_operation();
+            for &component_id in &removed {
+                world.removed_components.send(component_id, entity);
+                // Make sure to drop components stored in sparse sets.
+                // Dense components are dropped later in `move_to_and_drop_missing_unchecked`.
+                if let Some(StorageType::SparseSet) = old_archetype.get_storage_type(component_id) {
+                    world
+                        .storages
+                        .sparse_sets
+                        .get_mut(component_id)
+                        .unwrap()
+                        .remove(entity);
+                }
+            }
+        }
+
+        let mut new_location = location;
+        if new_archetype_id != location.archetype_id {
+            // SAFETY: the table components of the new archetype that are not in the old one are
+            // the added ones, which are initialized below before anything can access them
+            unsafe {
+                Self::move_entity_from_remove::<true>(
+                    entity,
+                    &mut new_location,
+                    location.archetype_id,
+                    location,
+                    &mut world.entities,
+                    &mut world.archetypes,
+                    &mut world.storages,
+                    new_archetype_id,
+                );
+            }
+        }
+
+        if !inserted.is_empty() {
+            let table = &mut world.storages.tables[new_location.table_id];
+            for component in inserted.iter_mut() {
+                // The value is moved into the entity: leaking it if a hook panics is safe,
+                // dropping it twice is not.
+                component.drop = None;
+                // SAFETY: the pointer holds an initialized value of the component
+                let ptr = unsafe { OwningPtr::new(component.ptr) };
+                // SAFETY: the caller guarantees that the components were initialized
+                match unsafe { world.components.get_info_unchecked(component.id) }.storage_type() {
+                    StorageType::Table => {
+                        // SAFETY: the table of the new archetype has a column for each of its
+                        // table components
+                        let column =
+                            unsafe { table.get_column_mut(component.id).debug_checked_unwrap() };
+                        if added.contains(&component.id) {
+                            column.initialize(new_location.table_row, ptr, change_tick);
+                        } else {
+                            column.replace(new_location.table_row, ptr, change_tick);
+                        }
+                    }
+                    StorageType::SparseSet => {
+                        // SAFETY: a sparse set exists for each initialized sparse set component
+                        let sparse_set = unsafe {
+                            world
+                                .storages
+                                .sparse_sets
+                                .get_mut(component.id)
+                                .debug_checked_unwrap()
+                        };
+                        sparse_set.insert(entity, ptr, change_tick);
+                    }
+                }
+            }
+            // SAFETY: the entity is at `table_row` in the table
+            unsafe { table.set_entity_changed_tick_unchecked(new_location.table_row, change_tick) };
+        }
+
+        if world.lifecycle_stats.is_enabled() && new_archetype_id != location.archetype_id {
+            let old_archetype = &world.archetypes[location.archetype_id];
+            let new_archetype = &world.archetypes[new_archetype_id];
+            world.lifecycle_stats.record_edit(
+                &world.components,
+                added.iter().copied(),
+                removed.iter().copied(),
+                old_archetype
+                    .table_components()
+                    .filter(|id| !removed.contains(id)),
+                old_table_id != new_archetype.table_id(),
+            );
+        }
+
+        // SAFETY: Archetypes cannot be mutably aliased through DeferredWorld
+        let (old_archetype, new_archetype, mut deferred_world) = unsafe {
+            let world = world.as_unsafe_world_cell();
+            (
+                &world.archetypes()[location.archetype_id],
+                &world.archetypes()[new_archetype_id],
+                world.into_deferred(),
+            )
+        };
+        if new_archetype.has_on_add() {
+            // SAFETY: All the added components are in the archetype, so they exist in world
+            unsafe {
+                deferred_world.trigger_on_add(entity, added.iter().copied());
+                deferred_world.trigger_batched_on_add(
+                    &[(entity, new_location.table_row)],
+                    added.iter().copied(),
+                );
+            }
+        }
+        if new_archetype.has_on_insert() {
+            // SAFETY: All the inserted components are in the archetype, so they exist in world
+            unsafe {
+                deferred_world
+                    .trigger_on_insert(entity, inserted.iter().map(|component| component.id));
+            }
+        }
+        if !deferred_world.complete_hooks().is_empty() && new_archetype_id != location.archetype_id
+        {
+            deferred_world.trigger_on_complete(entity, Some(old_archetype), new_archetype);
+        }
+
+        new_location
+    }
+
     /// Despawns the current entity.
     ///
     /// See [`World::despawn`] for more details.
@@ -1197,12 +1504,20 @@ impl<'w> EntityWorldMut<'w> {
             // SAFETY: All components in the archetype exist in world
             unsafe {
                 deferred_world.trigger_on_remove(self.entity, archetype.components());
+                deferred_world.trigger_batched_on_remove(
+                    &[(self.entity, self.location.table_row)],
+                    archetype.components(),
+                );
             }
         }
 
+        world.removed_components.begin_operation();
         for component_id in archetype.components() {
             world.removed_components.send(component_id, self.entity);
         }
+        if world.lifecycle_stats.is_enabled() {
+            world.lifecycle_stats.record_despawn(archetype.components());
+        }
 
         let location = world
             .entities
@@ -1383,6 +1698,119 @@ impl<'w> EntityWorldMut<'w> {
     }
 }
 
+/// Queues insertions and removals of components on an entity, to apply them with fewer archetype
+/// moves.
+///
+/// This is created by [`EntityWorldMut::edit`].
+pub struct EntityEditor<'a> {
+    world: &'a mut World,
+    inserted: Vec<EditedComponent>,
+    removed: Vec<ComponentId>,
+    on_spawn: Vec<fn(&mut EntityWorldMut)>,
+}
+
+impl<'a> EntityEditor<'a> {
+    /// Queues the insertion of a [`Bundle`] of components.
+    ///
+    /// This will overwrite any previous value(s) of the same component type, including values
+    /// queued on this editor.
+    pub fn insert<T: Bundle>(&mut self, bundle: T) -> &mut Self {
+        let world = &mut *self.world;
+        let bundle_id = world
+            .bundles
+            .init_info::<T>(&mut world.components, &mut world.storages);
+        // SAFETY: the `BundleInfo` is initialized above
+        let bundle_info = unsafe { world.bundles.get_unchecked(bundle_id) };
+        let components = &world.components;
+        let mut component_ids = bundle_info.iter_components();
+        bundle.get_components(&mut |_, ptr| {
+            let id = component_ids.next().unwrap();
+            // SAFETY: the components of the bundle were initialized with the `BundleInfo`
+            let info = unsafe { components.get_info_unchecked(id) };
+            // SAFETY: bundle components are iterated in order, so `ptr` holds a value of `id`
+            let component = unsafe { EditedComponent::new(id, info, ptr) };
+            self.removed.retain(|&removed| removed != id);
+            match self.inserted.iter_mut().find(|inserted| inserted.id == id) {
+                Some(inserted) => *inserted = component,
+                None => self.inserted.push(component),
+            }
+        });
+        drop(component_ids);
+        if T::HAS_ON_SPAWN {
+            self.on_spawn.push(T::on_spawn);
+        }
+        self
+    }
+
+    /// Queues the removal of any components in the [`Bundle`].
+    ///
+    /// Values of these components queued on this editor are dropped.
+    pub fn remove<T: Bundle>(&mut self) -> &mut Self {
+        let world = &mut *self.world;
+        let bundle_id = world
+            .bundles
+            .init_info::<T>(&mut world.components, &mut world.storages);
+        // SAFETY: the `BundleInfo` is initialized above
+        let bundle_info = unsafe { world.bundles.get_unchecked(bundle_id) };
+        for id in bundle_info.iter_components() {
+            self.inserted.retain(|inserted| inserted.id != id);
+            if !self.removed.contains(&id) {
+                self.removed.push(id);
+            }
+        }
+        self
+    }
+}
+
+/// A component value owned by an [`EntityEditor`].
+struct EditedComponent {
+    id: ComponentId,
+    ptr: NonNull<u8>,
+    layout: Layout,
+    // `None` once the value has been moved out.
+    drop: Option<unsafe fn(OwningPtr<'_>)>,
+}
+
+impl EditedComponent {
+    /// Moves the value behind `ptr` to a new allocation.
+    ///
+    /// # Safety
+    /// `ptr` must hold a value of the component described by `info`.
+    unsafe fn new(id: ComponentId, info: &ComponentInfo, ptr: OwningPtr<'_>) -> Self {
+        let layout = info.layout();
+        let dst = if layout.size() == 0 {
+            // SAFETY: alignments are never zero
+            unsafe { NonNull::new_unchecked(layout.align() as *mut u8) }
+        } else {
+            // SAFETY: the layout has a non-zero size
+            NonNull::new(unsafe { std::alloc::alloc(layout) })
+                .unwrap_or_else(|| std::alloc::handle_alloc_error(layout))
+        };
+        // SAFETY: `dst` is a new allocation for a value of this layout, and `ptr` is owned so its
+        // value can be moved out.
+        unsafe { std::ptr::copy_nonoverlapping(ptr.as_ptr(), dst.as_ptr(), layout.size()) };
+        Self {
+            id,
+            ptr: dst,
+            layout,
+            drop: info.drop(),
+        }
+    }
+}
+
+impl Drop for EditedComponent {
+    fn drop(&mut self) {
+        if let Some(drop) = self.drop {
+            // SAFETY: the value is initialized and owned until it is moved out
+            unsafe { drop(OwningPtr::new(self.ptr)) };
+        }
+        if self.layout.size() != 0 {
+            // SAFETY: `ptr` was allocated with `layout` in `EditedComponent::new`
+            unsafe { std::alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+        }
+    }
+}
+
 /// A view into a single entity and component in a world, which may either be vacant or occupied.
 ///
 /// This `enum` can only be constructed from the [`entry`] method on [`EntityWorldMut`].
@@ -2247,6 +2675,30 @@ unsafe fn insert_dynamic_bundle<
     unsafe { bundle_inserter.insert(entity, location, bundle) }
 }
 
+/// Records the removal of `bundle_info` in the [`ComponentLifecycleStats`](crate::world::ComponentLifecycleStats)
+/// of `world`, if they are enabled.
+fn record_remove(
+    world: &mut World,
+    bundle_info: &BundleInfo,
+    old_archetype_id: ArchetypeId,
+    new_archetype_id: ArchetypeId,
+) {
+    if !world.lifecycle_stats.is_enabled() {
+        return;
+    }
+    let old_archetype = &world.archetypes[old_archetype_id];
+    let new_archetype = &world.archetypes[new_archetype_id];
+    world.lifecycle_stats.record_move(
+        &world.components,
+        bundle_info
+            .iter_components()
+            .filter(|&id| old_archetype.contains(id)),
+        new_archetype.table_components(),
+        old_archetype.table_id() != new_archetype.table_id(),
+        false,
+    );
+}
+
 /// Removes a bundle from the given archetype and returns the resulting archetype (or None if the
 /// removal was invalid). in the event that adding the given bundle does not result in an Archetype
 /// change. Results are cached in the Archetype Graph to avoid redundant work.
@@ -2256,7 +2708,7 @@ unsafe fn insert_dynamic_bundle<
 ///
 /// # Safety
 /// `archetype_id` must exist and components in `bundle_info` must exist
-unsafe fn remove_bundle_from_archetype(
+pub(crate) unsafe fn remove_bundle_from_archetype(
     archetypes: &mut Archetypes,
     storages: &mut Storages,
     components: &Components,
@@ -2350,6 +2802,88 @@ unsafe fn remove_bundle_from_archetype(
     result
 }
 
+/// Caches the edges of the Archetype Graph from the given archetype for the given bundle, when
+/// their target archetypes already exist. Missing archetypes are not created.
+///
+/// # Safety
+/// `archetype_id` must exist and components in `bundle_info` must exist
+pub(crate) unsafe fn cache_existing_edges(
+    archetypes: &mut Archetypes,
+    storages: &mut Storages,
+    components: &Components,
+    archetype_id: ArchetypeId,
+    bundle_info: &BundleInfo,
+) {
+    let archetype = &archetypes[archetype_id];
+    let mut added_table_components: Vec<_> = archetype.table_components().collect();
+    let mut added_sparse_set_components: Vec<_> = archetype.sparse_set_components().collect();
+    let mut removed_table_components = added_table_components.clone();
+    let mut removed_sparse_set_components = added_sparse_set_components.clone();
+    let mut contains_bundle = true;
+    for component_id in bundle_info.components().iter().cloned() {
+        // SAFETY: the caller ensures the components in the bundle exist
+        let component_info = unsafe { components.get_info_unchecked(component_id) };
+        let (added, removed) = match component_info.storage_type() {
+            StorageType::Table => (&mut added_table_components, &mut removed_table_components),
+            StorageType::SparseSet => (
+                &mut added_sparse_set_components,
+                &mut removed_sparse_set_components,
+            ),
+        };
+        if archetype.contains(component_id) {
+            removed.retain(|id| *id != component_id);
+        } else {
+            added.push(component_id);
+            contains_bundle = false;
+        }
+    }
+    // sort to match the archetype identity
+    added_table_components.sort();
+    added_sparse_set_components.sort();
+
+    if archetypes
+        .get_id(&added_table_components, &added_sparse_set_components)
+        .is_some()
+    {
+        // SAFETY: `components` is the world the bundle was registered in, and the target
+        // archetype exists, so none is created
+        unsafe {
+            bundle_info.add_bundle_to_archetype(archetypes, storages, components, archetype_id);
+        }
+    }
+
+    let removed_exists = archetypes
+        .get_id(&removed_table_components, &removed_sparse_set_components)
+        .is_some();
+    if removed_exists {
+        // SAFETY: forwarded from the caller, and the target archetype exists
+        unsafe {
+            remove_bundle_from_archetype(
+                archetypes,
+                storages,
+                components,
+                archetype_id,
+                bundle_info,
+                true,
+            );
+        }
+    }
+    // taking a bundle the archetype doesn't contain fails without creating an archetype
+    if removed_exists || !contains_bundle {
+        // SAFETY: forwarded from the caller, and no archetype is created
+        unsafe {
+            remove_bundle_from_archetype(
+                archetypes,
+                storages,
+                components,
+                archetype_id,
+                bundle_info,
+                false,
+            );
+        }
+    }
+}
+
 fn sorted_remove<T: Eq + Ord + Copy>(source: &mut Vec<T>, remove: &[T]) {
     let mut remove_index = 0;
     source.retain(|value| {
@@ -2918,4 +3452,59 @@ mod tests {
 
         assert_is_system(incompatible_system);
     }
+
+    #[test]
+    fn edit_moves_entity_once() {
+        let mut world = World::new();
+        let entity = world.spawn(TestComponent2(0)).id();
+        let archetypes = world.archetypes().len();
+        world.lifecycle_stats_mut().set_enabled(true);
+
+        world.entity_mut(entity).edit(|editor| {
+            editor
+                .insert(TestComponent(1))
+                .insert(A)
+                .remove::<TestComponent2>();
+        });
+
+        // Only the final archetype was created: the entity didn't go through the empty archetype
+        // or any other intermediate one.
+        assert_eq!(world.archetypes().len(), archetypes + 1);
+        assert_eq!(world.lifecycle_stats().archetype_moves(), 1);
+        let entity = world.entity(entity);
+        assert_eq!(entity.get::<TestComponent>(), Some(&TestComponent(1)));
+        assert!(entity.contains::<A>());
+        assert!(!entity.contains::<TestComponent2>());
+    }
+
+    #[test]
+    fn edit_coalesces_operations() {
+        #[derive(Component)]
+        struct Counted(std::sync::Arc<()>);
+
+        let counter = std::sync::Arc::new(());
+        let mut world = World::new();
+        let entity = world.spawn(TestComponent(0)).id();
+
+        world.entity_mut(entity).edit(|editor| {
+            editor
+                .insert(Counted(counter.clone()))
+                .insert(Counted(counter.clone()))
+                .remove::<TestComponent>()
+                .insert(TestComponent(2))
+                .insert(TestComponent2(3))
+                .remove::<TestComponent2>();
+        });
+
+        // The first `Counted` was dropped when replaced.
+        assert_eq!(std::sync::Arc::strong_count(&counter), 2);
+        let entity_ref = world.entity(entity);
+        assert_eq!(entity_ref.get::<TestComponent>(), Some(&TestComponent(2)));
+        let counted = entity_ref.get::<Counted>().unwrap();
+        assert!(std::sync::Arc::ptr_eq(&counted.0, &counter));
+        assert!(!entity_ref.contains::<TestComponent2>());
+
+        world.despawn(entity);
+        assert_eq!(std::sync::Arc::strong_count(&counter), 1);
+    }
 }
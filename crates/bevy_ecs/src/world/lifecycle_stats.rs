@@ -0,0 +1,253 @@
+use crate::{
+    component::{ComponentId, Components},
+    storage::SparseSet,
+};
+
+/// Counts how often each component is inserted, removed and moved between tables, to find the
+/// components responsible for archetype churn.
+///
+/// Statistics are opt-in: nothing is recorded until they are enabled with
+/// [`set_enabled`](Self::set_enabled), and recording only costs a branch while disabled.
+/// Counts accumulate until [`clear`](Self::clear) is called, which the
+/// `ComponentChurnDiagnosticsPlugin` of `bevy_diagnostic` does once per frame.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Position(f32);
+///
+/// #[derive(Component)]
+/// struct Stunned;
+///
+/// let mut world = World::new();
+/// world.lifecycle_stats_mut().set_enabled(true);
+///
+/// let entity = world.spawn(Position(0.0)).id();
+/// world.entity_mut(entity).insert(Stunned);
+/// world.entity_mut(entity).remove::<Stunned>();
+///
+/// let stunned = world.init_component::<Stunned>();
+/// let stats = world.lifecycle_stats();
+/// assert_eq!(stats.get(stunned).unwrap().caused_moves, 2);
+/// assert_eq!(stats.archetype_moves(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct ComponentLifecycleStats {
+    enabled: bool,
+    components: SparseSet<ComponentId, ComponentLifecycleCounts>,
+    archetype_moves: u64,
+    table_moves: u64,
+    bytes_moved: u64,
+}
+
+/// The lifecycle events of a single component, recorded by [`ComponentLifecycleStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ComponentLifecycleCounts {
+    /// The number of times the component was added to an entity, including spawns.
+    ///
+    /// Overwriting the value of a component an entity already has isn't counted.
+    pub inserted: u64,
+    /// The number of times the component was removed from an entity, including despawns.
+    pub removed: u64,
+    /// The number of times a value of the component was moved to another table, because other
+    /// components were inserted or removed.
+    pub moved: u64,
+    /// The number of archetype moves caused by inserting or removing the component.
+    ///
+    /// When a bundle is inserted or removed, the move is counted for each of its components.
+    pub caused_moves: u64,
+    /// The number of bytes moved between tables by the archetype moves caused by the component.
+    pub caused_bytes_moved: u64,
+}
+
+impl ComponentLifecycleStats {
+    /// Returns `true` if lifecycle events are being recorded.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Starts or stops recording lifecycle events.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Returns the counts of the component `id`, if any of its lifecycle events were recorded.
+    pub fn get(&self, id: ComponentId) -> Option<&ComponentLifecycleCounts> {
+        self.components.get(id)
+    }
+
+    /// Iterates over the counts of every component with recorded lifecycle events.
+    pub fn iter(&self) -> impl Iterator<Item = (ComponentId, &ComponentLifecycleCounts)> {
+        self.components.iter().map(|(&id, counts)| (id, counts))
+    }
+
+    /// Returns the number of times an entity changed archetype, excluding spawns and despawns.
+    pub fn archetype_moves(&self) -> u64 {
+        self.archetype_moves
+    }
+
+    /// Returns the number of archetype moves that also moved the entity to another table.
+    pub fn table_moves(&self) -> u64 {
+        self.table_moves
+    }
+
+    /// Returns the number of component bytes copied between tables by archetype moves.
+    pub fn bytes_moved(&self) -> u64 {
+        self.bytes_moved
+    }
+
+    /// Resets every count to zero, without changing whether recording is enabled.
+    pub fn clear(&mut self) {
+        self.components.clear();
+        self.archetype_moves = 0;
+        self.table_moves = 0;
+        self.bytes_moved = 0;
+    }
+
+    fn counts_mut(&mut self, id: ComponentId) -> &mut ComponentLifecycleCounts {
+        self.components.get_or_insert_with(id, Default::default)
+    }
+
+    /// Records the components of a newly spawned entity.
+    pub(crate) fn record_spawn(&mut self, components: impl Iterator<Item = ComponentId>) {
+        for id in components {
+            self.counts_mut(id).inserted += 1;
+        }
+    }
+
+    /// Records the components of a despawned entity.
+    pub(crate) fn record_despawn(&mut self, components: impl Iterator<Item = ComponentId>) {
+        for id in components {
+            self.counts_mut(id).removed += 1;
+        }
+    }
+
+    /// Records an archetype move caused by inserting or removing the `changed` components.
+    ///
+    /// `moved` are the table components carried over to the new archetype, whose values were
+    /// copied if the move changed table.
+    pub(crate) fn record_move(
+        &mut self,
+        components: &Components,
+        changed: impl Iterator<Item = ComponentId>,
+        moved: impl Iterator<Item = ComponentId>,
+        new_table: bool,
+        inserted: bool,
+    ) {
+        if inserted {
+            self.record_edit(components, changed, std::iter::empty(), moved, new_table);
+        } else {
+            self.record_edit(components, std::iter::empty(), changed, moved, new_table);
+        }
+    }
+
+    /// Records a single archetype move caused by inserting the `inserted` components and removing
+    /// the `removed` components.
+    pub(crate) fn record_edit(
+        &mut self,
+        components: &Components,
+        inserted: impl Iterator<Item = ComponentId>,
+        removed: impl Iterator<Item = ComponentId>,
+        moved: impl Iterator<Item = ComponentId>,
+        new_table: bool,
+    ) {
+        let mut bytes = 0;
+        if new_table {
+            for id in moved {
+                self.counts_mut(id).moved += 1;
+                bytes += components
+                    .get_info(id)
+                    .map_or(0, |info| info.layout().size() as u64);
+            }
+            self.table_moves += 1;
+            self.bytes_moved += bytes;
+        }
+        self.archetype_moves += 1;
+
+        for (id, inserted) in inserted
+            .map(|id| (id, true))
+            .chain(removed.map(|id| (id, false)))
+        {
+            let counts = self.counts_mut(id);
+            if inserted {
+                counts.inserted += 1;
+            } else {
+                counts.removed += 1;
+            }
+            counts.caused_moves += 1;
+            counts.caused_bytes_moved += bytes;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ComponentLifecycleCounts;
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct Payload(u64);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct S;
+
+    #[test]
+    fn records_lifecycle_events() {
+        let mut world = World::new();
+        world.spawn(A);
+        assert!(world.lifecycle_stats().iter().next().is_none());
+
+        world.lifecycle_stats_mut().set_enabled(true);
+        let entity = world.spawn((A, Payload(7))).id();
+        world.entity_mut(entity).insert(B);
+        world.entity_mut(entity).insert(B);
+        world.entity_mut(entity).insert(S);
+        world.entity_mut(entity).remove::<(B, S)>();
+        assert_eq!(world.get::<Payload>(entity).unwrap().0, 7);
+        world.despawn(entity);
+
+        let [a, b, s] = [
+            world.init_component::<A>(),
+            world.init_component::<B>(),
+            world.init_component::<S>(),
+        ];
+        let stats = world.lifecycle_stats();
+        assert_eq!(
+            *stats.get(a).unwrap(),
+            ComponentLifecycleCounts {
+                inserted: 1,
+                removed: 1,
+                moved: 2,
+                caused_moves: 0,
+                caused_bytes_moved: 0,
+            }
+        );
+        assert_eq!(
+            *stats.get(b).unwrap(),
+            ComponentLifecycleCounts {
+                inserted: 1,
+                removed: 1,
+                moved: 0,
+                caused_moves: 2,
+                caused_bytes_moved: 8 + 8,
+            }
+        );
+        assert_eq!(stats.get(s).unwrap().caused_moves, 2);
+        assert_eq!(stats.archetype_moves(), 3);
+        assert_eq!(stats.table_moves(), 2);
+        assert_eq!(stats.bytes_moved(), 16);
+
+        world.lifecycle_stats_mut().clear();
+        assert!(world.lifecycle_stats().iter().next().is_none());
+        assert!(world.lifecycle_stats().is_enabled());
+    }
+}
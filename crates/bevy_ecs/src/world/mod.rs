@@ -1,40 +1,57 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
 mod command_queue;
+mod compaction;
+mod complete_hooks;
 mod deferred_world;
 mod entity_ref;
 pub mod error;
+mod lifecycle_stats;
+mod query_cache;
+mod resource_transaction;
 mod spawn_batch;
+mod structural_commands;
 pub mod unsafe_world_cell;
 
 pub use crate::change_detection::{Mut, Ref, CHECK_TICK_THRESHOLD};
-pub use crate::world::command_queue::CommandQueue;
+pub use crate::world::command_queue::{
+    CommandQueue, CommandQueueBudget, CommandQueueOverflow, CommandQueueStats, SystemCommandQueue,
+};
+pub use compaction::CompactionReport;
+pub use complete_hooks::{BundleCompleteHook, BundleCompleteHooks};
 pub use deferred_world::DeferredWorld;
 pub use entity_ref::{
-    EntityMut, EntityRef, EntityWorldMut, Entry, FilteredEntityMut, FilteredEntityRef,
-    OccupiedEntry, VacantEntry,
+    EntityEditor, EntityMut, EntityRef, EntityWorldMut, Entry, FilteredEntityMut,
+    FilteredEntityRef, OccupiedEntry, VacantEntry,
 };
+pub use lifecycle_stats::{ComponentLifecycleCounts, ComponentLifecycleStats};
+pub use query_cache::QueryCache;
+pub use resource_transaction::{ResourceConflict, ResourceTransaction};
 pub use spawn_batch::*;
+use structural_commands::StructuralCommandQueue;
+pub use structural_commands::StructuralCommands;
 
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeId, ArchetypeRow, Archetypes},
     bundle::{Bundle, BundleInfo, BundleInserter, BundleSpawner, Bundles},
-    change_detection::{MutUntyped, TicksMut},
+    change_detection::{FieldTicksMut, MutUntyped, TicksMut},
     component::{
         Component, ComponentDescriptor, ComponentHooks, ComponentId, ComponentInfo, ComponentTicks,
-        Components, Tick,
+        Components, StorageType, Tick,
     },
     entity::{AllocAtWithoutReplacement, Entities, Entity, EntityLocation},
+    entity_disabling::{DefaultQueryFilters, Disabled},
     event::{Event, EventId, Events, SendBatchIds},
     query::{DebugCheckedUnwrap, QueryData, QueryEntityError, QueryFilter, QueryState},
     removal_detection::RemovedComponentEvents,
-    schedule::{Schedule, ScheduleLabel, Schedules},
-    storage::{ResourceData, Storages},
-    system::{Commands, Res, Resource},
+    schedule::{AmbiguitySet, Schedule, ScheduleLabel, ScheduleProgress, Schedules},
+    storage::{ComponentSparseSet, ResourceData, Storages, TableId, TableView},
+    system::{Commands, Query, Res, Resource},
+    tag::TagIndex,
     world::error::TryRunScheduleError,
 };
 use bevy_ptr::{OwningPtr, Ptr};
-use bevy_utils::tracing::warn;
+use bevy_utils::{tracing::warn, Duration};
 use std::{
     any::TypeId,
     fmt,
@@ -113,11 +130,23 @@ pub struct World {
     pub(crate) last_change_tick: Tick,
     pub(crate) last_check_tick: Tick,
     pub(crate) command_queue: CommandQueue,
+    pub(crate) lifecycle_stats: ComponentLifecycleStats,
+    /// The number of schedule runs started in this world, used to memoize shared run conditions.
+    pub(crate) schedule_runs: u64,
+    pub(crate) complete_hooks: BundleCompleteHooks,
+    pub(crate) query_cache: QueryCache,
+    pub(crate) structural_commands: StructuralCommandQueue,
+    pub(crate) default_query_filters: DefaultQueryFilters,
+    pub(crate) tag_index: TagIndex,
+    /// Whether the standard commands record their mutations in the
+    /// [`CommandJournal`](crate::reflect::CommandJournal).
+    #[cfg(feature = "bevy_reflect")]
+    pub(crate) journaling: bool,
 }
 
 impl Default for World {
     fn default() -> Self {
-        Self {
+        let mut world = Self {
             id: WorldId::new().expect("More `bevy` `World`s have been created than is supported"),
             entities: Entities::new(),
             components: Default::default(),
@@ -131,7 +160,20 @@ impl Default for World {
             last_change_tick: Tick::new(0),
             last_check_tick: Tick::new(0),
             command_queue: CommandQueue::default(),
-        }
+            lifecycle_stats: ComponentLifecycleStats::default(),
+            schedule_runs: 0,
+            complete_hooks: BundleCompleteHooks::default(),
+            query_cache: QueryCache::default(),
+            structural_commands: StructuralCommandQueue::default(),
+            default_query_filters: DefaultQueryFilters::empty(),
+            tag_index: TagIndex::default(),
+            #[cfg(feature = "bevy_reflect")]
+            journaling: false,
+        };
+        world.default_query_filters = DefaultQueryFilters::builder(&mut world)
+            .disabling::<Disabled>()
+            .build();
+        world
     }
 }
 
@@ -200,6 +242,23 @@ impl World {
         &self.storages
     }
 
+    /// Returns a [`TableView`] of the raw memory of the table `table_id`, or `None` if it doesn't
+    /// exist.
+    ///
+    /// The table of an archetype is given by [`Archetype::table_id`]. Archetypes that only differ
+    /// in their [sparse set](crate::component::StorageType::SparseSet) components share a table,
+    /// and those components aren't part of it.
+    ///
+    /// [`Archetype::table_id`]: crate::archetype::Archetype::table_id
+    pub fn table_view(&self, table_id: TableId) -> Option<TableView<'_>> {
+        let table = self.storages.tables.get(table_id)?;
+        Some(TableView::new(
+            table,
+            &self.components,
+            self.read_change_tick(),
+        ))
+    }
+
     /// Retrieves this world's [`Bundles`] collection.
     #[inline]
     pub fn bundles(&self) -> &Bundles {
@@ -212,6 +271,33 @@ impl World {
         &self.removed_components
     }
 
+    /// Retrieves this world's [`ComponentLifecycleStats`].
+    #[inline]
+    pub fn lifecycle_stats(&self) -> &ComponentLifecycleStats {
+        &self.lifecycle_stats
+    }
+
+    /// Retrieves a mutable reference to this world's [`ComponentLifecycleStats`], to enable or
+    /// clear them.
+    #[inline]
+    pub fn lifecycle_stats_mut(&mut self) -> &mut ComponentLifecycleStats {
+        &mut self.lifecycle_stats
+    }
+
+    /// Retrieves this world's [`DefaultQueryFilters`], the disabling components hiding entities
+    /// from the queries that don't mention them.
+    #[inline]
+    pub fn default_query_filters(&self) -> &DefaultQueryFilters {
+        &self.default_query_filters
+    }
+
+    /// Sets this world's [`DefaultQueryFilters`], built with [`DefaultQueryFilters::builder`].
+    ///
+    /// Only the queries created afterwards use the new filters.
+    pub fn set_default_query_filters(&mut self, filters: DefaultQueryFilters) {
+        self.default_query_filters = filters;
+    }
+
     /// Creates a new [`Commands`] instance that writes to the world's command queue
     /// Use [`World::flush_commands`] to apply all queued commands
     #[inline]
@@ -220,13 +306,86 @@ impl World {
     }
 
     /// Initializes a new [`Component`] type and returns the [`ComponentId`] created for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't registered yet and the component registry is
+    /// [sealed](World::seal_component_registry).
     pub fn init_component<T: Component>(&mut self) -> ComponentId {
         self.components.init_component::<T>(&mut self.storages)
     }
 
+    /// Forbids registering new [`Component`] types, and precomputes the archetype graph between
+    /// the existing archetypes.
+    ///
+    /// The first time a bundle is inserted into or removed from an entity of some archetype, the
+    /// resulting archetype is computed and cached in the [`Edges`] of that archetype. Sealing
+    /// fills this cache ahead of time for every registered bundle and every existing archetype,
+    /// so later structural changes between those archetypes are a single indexed lookup.
+    /// Edges are stored in arrays indexed by [`BundleId`], whether sealed or not: sealing only
+    /// makes sure the lookup never misses.
+    ///
+    /// Only edges to archetypes that already exist are precomputed: sealing never creates
+    /// archetypes for combinations of components that may never be used. Other transitions are
+    /// still computed and cached the first time they happen. Games with a stable set of
+    /// components would typically spawn their prefabs while loading, then seal the registry.
+    ///
+    /// # Panics
+    ///
+    /// Registering a new component after sealing panics, whether through
+    /// [`World::init_component`], spawning or inserting a bundle with an unregistered
+    /// component, or initializing a query over one. Resources can still be registered.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position(f32);
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity(f32);
+    ///
+    /// let mut world = World::new();
+    /// let entity = world.spawn(Position(0.0)).id();
+    /// world.spawn((Position(0.0), Velocity(1.0)));
+    /// world.seal_component_registry();
+    ///
+    /// // this transition was precomputed
+    /// world.entity_mut(entity).insert(Velocity(1.0));
+    /// ```
+    ///
+    /// [`Edges`]: crate::archetype::Edges
+    /// [`BundleId`]: crate::bundle::BundleId
+    pub fn seal_component_registry(&mut self) {
+        self.components.seal();
+
+        let World {
+            archetypes,
+            storages,
+            components,
+            bundles,
+            ..
+        } = self;
+        let archetype_count = archetypes.len();
+        for bundle_info in bundles.iter() {
+            for index in 0..archetype_count {
+                // SAFETY: the archetype and the components of the bundle exist in this world
+                unsafe {
+                    entity_ref::cache_existing_edges(
+                        archetypes,
+                        storages,
+                        components,
+                        ArchetypeId::new(index),
+                        bundle_info,
+                    );
+                }
+            }
+        }
+    }
+
     /// Returns a mutable reference to the [`ComponentHooks`] for a [`Component`] type.
     ///
-    /// Will panic if `T` exists in any archetypes.
+    /// Will panic if `T` exists in any archetypes, or if `T` isn't registered yet and the
+    /// component registry is [sealed](World::seal_component_registry).
     pub fn register_component_hooks<T: Component>(&mut self) -> &mut ComponentHooks {
         let index = self.init_component::<T>();
         assert!(!self.archetypes.archetypes.iter().any(|a| a.contains(index)), "Components hooks cannot be modified if the component already exists in an archetype, use init_component if {} may already be in use", std::any::type_name::<T>());
@@ -234,6 +393,50 @@ impl World {
         unsafe { self.components.get_hooks_mut(index).debug_checked_unwrap() }
     }
 
+    /// Registers a `hook` run when an entity gets every component of the bundle `B`, whatever the
+    /// order in which they were inserted.
+    ///
+    /// The hook runs after the `on_add` and `on_insert` hooks of the insertion that completed the
+    /// bundle, and again if one of the components is removed and inserted back. Entities that
+    /// already have every component when the hook is registered are not affected.
+    ///
+    /// ```
+    /// # use bevy_ecs::{prelude::*, world::DeferredWorld};
+    /// #[derive(Component)]
+    /// struct Transform;
+    ///
+    /// #[derive(Component)]
+    /// struct Mesh;
+    ///
+    /// #[derive(Component)]
+    /// struct Ready;
+    ///
+    /// let mut world = World::new();
+    /// world.register_complete_hook::<(Transform, Mesh)>(|mut world: DeferredWorld, entity| {
+    ///     world.commands().entity(entity).insert(Ready);
+    /// });
+    ///
+    /// let entity = world.spawn(Mesh).id();
+    /// world.entity_mut(entity).insert(Transform);
+    /// world.flush_commands();
+    /// assert!(world.entity(entity).contains::<Ready>());
+    /// ```
+    pub fn register_complete_hook<B: Bundle>(&mut self, hook: BundleCompleteHook) -> &mut Self {
+        let bundle_id = self
+            .bundles
+            .init_info::<B>(&mut self.components, &mut self.storages);
+        // SAFETY: we just initialized this bundle
+        let components = unsafe { self.bundles.get_unchecked(bundle_id) }.components();
+        self.complete_hooks.push(components, hook);
+        self
+    }
+
+    /// Returns the hooks registered with [`World::register_complete_hook`].
+    #[inline]
+    pub fn complete_hooks(&self) -> &BundleCompleteHooks {
+        &self.complete_hooks
+    }
+
     /// Returns a mutable reference to the [`ComponentHooks`] for a [`Component`] with the given id if it exists.
     ///
     /// Will panic if `id` exists in any archetypes.
@@ -245,6 +448,35 @@ impl World {
         self.components.get_hooks_mut(id)
     }
 
+    /// Tracks the changes of each of the `fields` of the component `id` separately, in addition
+    /// to the changes of the whole component.
+    ///
+    /// This stores a change tick for each field of each value of the component, which is updated
+    /// by `Mut::map_field_reflect` for the field it maps to, and for every field when the
+    /// component is mutably dereferenced. The [`FieldChanged`](crate::query::FieldChanged) filter
+    /// then finds the values whose given field changed.
+    ///
+    /// The fields must be listed in the order they're declared in, like
+    /// `StructInfo::field_names`. Prefer `World::track_field_changes` for reflected structs.
+    ///
+    /// Will panic if `id` exists in any archetypes.
+    pub fn track_field_changes_by_id(
+        &mut self,
+        id: ComponentId,
+        fields: impl Into<Box<[&'static str]>>,
+    ) {
+        assert!(!self.archetypes.archetypes.iter().any(|a| a.contains(id)), "Field changes cannot be tracked if the component already exists in an archetype, track them before spawning the component with id {:?}", id);
+        self.components.set_tracked_fields(id, fields.into());
+        let info = self.components.get_info(id).unwrap();
+        if info.storage_type() == StorageType::SparseSet {
+            // The sparse set is created with the component, so it's replaced by one storing the
+            // ticks of the fields. It's empty since the component isn't in any archetype.
+            if let Some(sparse_set) = self.storages.sparse_sets.get_mut(id) {
+                *sparse_set = ComponentSparseSet::new(info, 64);
+            }
+        }
+    }
+
     /// Initializes a new [`Component`] type and returns the [`ComponentId`] created for it.
     ///
     /// This method differs from [`World::init_component`] in that it uses a [`ComponentDescriptor`]
@@ -254,6 +486,10 @@ impl World {
     /// While the option to initialize a component from a descriptor is useful in type-erased
     /// contexts, the standard `World::init_component` function should always be used instead
     /// when type information is available at compile time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the component registry is [sealed](World::seal_component_registry).
     pub fn init_component_with_descriptor(
         &mut self,
         descriptor: ComponentDescriptor,
@@ -801,15 +1037,21 @@ impl World {
     pub fn spawn<B: Bundle>(&mut self, bundle: B) -> EntityWorldMut {
         self.flush_entities();
         let change_tick = self.change_tick();
-        let entity = self.entities.alloc();
-        let entity_location = {
+        let (entity, entity_location) = {
             let mut bundle_spawner = BundleSpawner::new::<B>(self, change_tick);
-            // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent
-            unsafe { bundle_spawner.spawn_non_existent(entity, bundle) }
+            // checked before allocating the entity, so that it isn't leaked on panic
+            bundle_spawner.assert_singletons_unique(Entity::PLACEHOLDER);
+            let entity = bundle_spawner.entities().alloc();
+            // SAFETY: bundle's type matches `bundle_info`, entity is allocated but non-existent,
+            // and the singleton components were checked
+            let location = unsafe { bundle_spawner.spawn_non_existent_unchecked(entity, bundle) };
+            (entity, location)
         };
 
         // SAFETY: entity and location are valid, as they were just created above
-        unsafe { EntityWorldMut::new(self, entity, entity_location) }
+        let mut entity = unsafe { EntityWorldMut::new(self, entity, entity_location) };
+        B::on_spawn(&mut entity);
+        entity
     }
 
     /// # Safety
@@ -904,6 +1146,63 @@ impl World {
         unsafe { self.as_unsafe_world_cell().get_entity(entity)?.get_mut() }
     }
 
+    /// Returns the entity that has the singleton component `T`, declared with
+    /// `#[component(singleton)]`, without iterating over the entities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't a [singleton](Component::SINGLETON) component.
+    #[inline]
+    pub fn singleton_entity<T: Component>(&self) -> Option<Entity> {
+        assert!(
+            T::SINGLETON,
+            "`{}` is not a singleton component.",
+            std::any::type_name::<T>()
+        );
+        let id = self.component_id::<T>()?;
+        let entity = self.components.get_info(id)?.singleton_entity()?;
+        // cleared entities don't trigger hooks, so the slot may refer to a despawned entity
+        self.get_entity(entity)
+            .is_some_and(|entity| entity.contains_id(id))
+            .then_some(entity)
+    }
+
+    /// Returns the singleton component `T`, declared with `#[component(singleton)]`, without
+    /// iterating over the entities.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[component(singleton)]
+    /// struct Score(u32);
+    ///
+    /// let mut world = World::new();
+    /// assert!(world.singleton::<Score>().is_none());
+    /// world.spawn(Score(3));
+    /// assert_eq!(world.singleton::<Score>().unwrap().0, 3);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't a [singleton](Component::SINGLETON) component.
+    #[inline]
+    pub fn singleton<T: Component>(&self) -> Option<&T> {
+        let entity = self.singleton_entity::<T>()?;
+        self.get(entity)
+    }
+
+    /// Returns a mutable reference to the singleton component `T`, declared with
+    /// `#[component(singleton)]`, without iterating over the entities.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` isn't a [singleton](Component::SINGLETON) component.
+    #[inline]
+    pub fn singleton_mut<T: Component>(&mut self) -> Option<Mut<T>> {
+        let entity = self.singleton_entity::<T>()?;
+        self.get_mut(entity)
+    }
+
     /// Despawns the given `entity`, if it exists. This will also remove all of the entity's
     /// [`Component`]s. Returns `true` if the `entity` is successfully despawned and `false` if
     /// the `entity` does not exist.
@@ -1074,6 +1373,67 @@ impl World {
         QueryState::new(self)
     }
 
+    /// Returns a [`Query`] for the given [`QueryData`] and [`QueryFilter`], reusing a
+    /// [`QueryState`] cached in the world.
+    ///
+    /// Unlike [`World::query_filtered`], which builds a new [`QueryState`] matching every
+    /// archetype of the world, this keeps the state in the world's [`QueryCache`] and only
+    /// matches the archetypes added since the last call. This suits exclusive systems and tools
+    /// running the same ad-hoc queries over and over, without storing their states themselves.
+    ///
+    /// ```
+    /// use bevy_ecs::{component::Component, query::With, world::World};
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    /// #[derive(Component)]
+    /// struct Poisoned;
+    ///
+    /// let mut world = World::new();
+    /// world.spawn((Health(10), Poisoned));
+    /// world.spawn(Health(10));
+    ///
+    /// for _ in 0..3 {
+    ///     for mut health in &mut world.cached_query::<&mut Health, With<Poisoned>>() {
+    ///         health.0 -= 1;
+    ///     }
+    /// }
+    ///
+    /// let total: u32 = world.cached_query::<&Health, ()>().iter().map(|h| h.0).sum();
+    /// assert_eq!(total, 17);
+    /// ```
+    pub fn cached_query<D: QueryData + 'static, F: QueryFilter + 'static>(
+        &mut self,
+    ) -> Query<'_, '_, D, F> {
+        if !self.query_cache.contains::<D, F>() {
+            let state = QueryState::<D, F>::new(self);
+            self.query_cache.insert(state);
+        }
+        let last_run = self.last_change_tick();
+        let this_run = self.change_tick();
+        let world = self.as_unsafe_world_cell();
+        // SAFETY: we have exclusive access to the world, and the query cache is only accessed here.
+        let state = unsafe { world.query_cache_mut() }
+            .get_mut::<D, F>()
+            .expect("the query state was cached above");
+        state.update_archetypes_unsafe_world_cell(world);
+        // SAFETY: we have exclusive access to the world, and the state was created for it.
+        unsafe { Query::new(world, state, last_run, this_run) }
+    }
+
+    /// Retrieves this world's [`QueryCache`], holding the states of [`World::cached_query`].
+    #[inline]
+    pub fn query_cache(&self) -> &QueryCache {
+        &self.query_cache
+    }
+
+    /// Retrieves a mutable reference to this world's [`QueryCache`], to change its capacity or
+    /// evict states.
+    #[inline]
+    pub fn query_cache_mut(&mut self) -> &mut QueryCache {
+        &mut self.query_cache
+    }
+
     /// Returns an iterator of entities that had components of type `T` removed
     /// since the last call to [`World::clear_trackers`].
     pub fn removed<T: Component>(&self) -> impl Iterator<Item = Entity> + '_ {
@@ -1607,6 +1967,7 @@ impl World {
         });
 
         let mut invalid_entities = Vec::new();
+        let mut spawned = Vec::new();
         for (entity, bundle) in iter {
             match spawn_or_insert
                 .entities()
@@ -1652,8 +2013,18 @@ impl World {
                 }
                 AllocAtWithoutReplacement::ExistsWithWrongGeneration => {
                     invalid_entities.push(entity);
+                    continue;
                 }
             }
+            if B::HAS_ON_SPAWN {
+                spawned.push(entity);
+            }
+        }
+
+        for entity in spawned {
+            if let Some(mut entity) = self.get_entity_mut(entity) {
+                B::on_spawn(&mut entity);
+            }
         }
 
         if invalid_entities.is_empty() {
@@ -1708,6 +2079,8 @@ impl World {
             ticks: TicksMut {
                 added: &mut ticks.added,
                 changed: &mut ticks.changed,
+                entity_changed: None,
+                fields_changed: FieldTicksMut::default(),
                 last_run: last_change_tick,
                 this_run: change_tick,
             },
@@ -1867,10 +2240,12 @@ impl World {
         }
     }
 
-    /// Applies any commands in the world's internal [`CommandQueue`].
+    /// Applies any commands in the world's internal [`CommandQueue`], after the changes queued
+    /// through [`StructuralCommands`].
     /// This does not apply commands from any systems, only those stored in the world.
     #[inline]
     pub fn flush_commands(&mut self) {
+        self.flush_structural_commands();
         if !self.command_queue.is_empty() {
             // `CommandQueue` application always applies commands from the world queue first so this will apply all stored commands
             CommandQueue::default().apply(self);
@@ -2072,6 +2447,7 @@ impl World {
         self.storages.sparse_sets.clear_entities();
         self.archetypes.clear_entities();
         self.entities.clear();
+        self.tag_index.clear();
     }
 
     /// Clears all resources in this [`World`].
@@ -2568,6 +2944,22 @@ impl World {
         self.schedule_scope(label, |world, sched| sched.run(world));
     }
 
+    /// Runs the [`Schedule`] associated with the `label` until `budget` has elapsed, resuming
+    /// where it stopped on the next call.
+    ///
+    /// See [`Schedule::run_budgeted`] for how a time-sliced pass differs from a regular run.
+    ///
+    /// # Panics
+    ///
+    /// If the requested schedule does not exist.
+    pub fn run_schedule_budgeted(
+        &mut self,
+        label: impl ScheduleLabel,
+        budget: Duration,
+    ) -> ScheduleProgress {
+        self.schedule_scope(label, |world, sched| sched.run_budgeted(world, budget))
+    }
+
     /// Ignore system order ambiguities caused by conflicts on [`Component`]s of type `T`.
     pub fn allow_ambiguous_component<T: Component>(&mut self) {
         let mut schedules = self.remove_resource::<Schedules>().unwrap_or_default();
@@ -2581,6 +2973,14 @@ impl World {
         schedules.allow_ambiguous_resource::<T>(self);
         self.insert_resource(schedules);
     }
+
+    /// Ignore the system order ambiguities in `ambiguities`.
+    ///
+    /// See [`Schedules::suppress_ambiguities`].
+    pub fn suppress_ambiguities(&mut self, ambiguities: AmbiguitySet) {
+        self.get_resource_or_insert_with(Schedules::default)
+            .suppress_ambiguities(ambiguities);
+    }
 }
 
 impl fmt::Debug for World {
@@ -3124,4 +3524,125 @@ mod tests {
         let mut world = World::new();
         world.spawn(());
     }
+
+    #[derive(Component, Debug, PartialEq)]
+    #[component(singleton)]
+    struct Player(u32);
+
+    #[test]
+    fn singleton_tracks_its_entity() {
+        let mut world = World::new();
+        assert_eq!(world.singleton_entity::<Player>(), None);
+
+        let a = world.spawn(Player(1)).id();
+        assert_eq!(world.singleton_entity::<Player>(), Some(a));
+        assert_eq!(world.singleton::<Player>(), Some(&Player(1)));
+        world.singleton_mut::<Player>().unwrap().0 = 2;
+        assert_eq!(world.get::<Player>(a), Some(&Player(2)));
+
+        world.entity_mut(a).remove::<Player>();
+        assert_eq!(world.singleton_entity::<Player>(), None);
+        let b = world.spawn(Player(3)).id();
+        assert_eq!(world.singleton_entity::<Player>(), Some(b));
+
+        world.despawn(b);
+        assert_eq!(world.singleton::<Player>(), None);
+        let c = world.spawn(Player(4)).id();
+        assert_eq!(world.singleton_entity::<Player>(), Some(c));
+
+        world.clear_entities();
+        assert_eq!(world.singleton_entity::<Player>(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "already has it")]
+    fn second_singleton_panics() {
+        let mut world = World::new();
+        world.spawn(Player(1));
+        world.spawn(Player(2));
+    }
+
+    #[test]
+    fn second_singleton_leaves_world_intact() {
+        #[derive(Component)]
+        struct Marker;
+
+        let mut world = World::new();
+        let a = world.spawn(Player(1)).id();
+        let b = world.spawn(Marker).id();
+        let entity_count = world.entities().len();
+
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            world.entity_mut(b).insert(Player(2));
+        }));
+        assert!(res.is_err());
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            world.spawn((Marker, Player(3)));
+        }));
+        assert!(res.is_err());
+
+        assert_eq!(world.entities().len(), entity_count);
+        assert_eq!(world.singleton_entity::<Player>(), Some(a));
+        assert_eq!(world.get::<Player>(a), Some(&Player(1)));
+        assert!(world.entity(b).contains::<Marker>());
+        assert!(!world.entity(b).contains::<Player>());
+        assert_eq!(world.query::<&Player>().iter(&world).count(), 1);
+        assert_eq!(world.query::<&Marker>().iter(&world).count(), 1);
+
+        world.despawn(a);
+        world.entity_mut(b).insert(Player(2));
+        assert_eq!(world.singleton_entity::<Player>(), Some(b));
+    }
+
+    #[test]
+    fn seal_component_registry_precomputes_edges() {
+        #[derive(Component)]
+        struct A;
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct B;
+        #[derive(Component)]
+        struct C;
+
+        let mut world = World::new();
+        let a = world.spawn(A).id();
+        let ab = world.spawn((A, B)).id();
+        world.spawn(B);
+        world.spawn(C);
+        let archetype_count = world.archetypes().len();
+        world.seal_component_registry();
+        assert!(world.components().is_sealed());
+        assert_eq!(world.archetypes().len(), archetype_count);
+
+        let a_archetype = world.entity(a).archetype().id();
+        let ab_archetype = world.entity(ab).archetype().id();
+        let b_bundle = world.bundles().get_id(TypeId::of::<B>()).unwrap();
+        let c_bundle = world.bundles().get_id(TypeId::of::<C>()).unwrap();
+        let a_edges = world.archetypes()[a_archetype].edges();
+        let ab_edges = world.archetypes()[ab_archetype].edges();
+        assert_eq!(a_edges.get_add_bundle(b_bundle), Some(ab_archetype));
+        assert_eq!(
+            ab_edges.get_remove_bundle(b_bundle),
+            Some(Some(a_archetype))
+        );
+        assert_eq!(ab_edges.get_take_bundle(b_bundle), Some(Some(a_archetype)));
+        assert_eq!(a_edges.get_take_bundle(b_bundle), Some(None));
+        // no entity has both A and C, so that archetype isn't created
+        assert_eq!(a_edges.get_add_bundle(c_bundle), None);
+
+        world.entity_mut(a).insert(C);
+        assert_eq!(world.archetypes().len(), archetype_count + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "the component registry is sealed")]
+    fn sealed_component_registry() {
+        #[derive(Component)]
+        struct A;
+
+        let mut world = World::new();
+        world.seal_component_registry();
+        world.insert_resource(TestResource(0));
+        world.spawn(A);
+    }
 }
@@ -0,0 +1,184 @@
+use std::{any::TypeId, fmt};
+
+use bevy_utils::HashMap;
+
+use crate::query::{QueryData, QueryFilter, QueryState};
+
+/// The [`QueryState`]s built by [`World::cached_query`](crate::world::World::cached_query),
+/// reused between calls.
+///
+/// Cached states aren't rebuilt when archetypes are added to the world: like the states of
+/// systems, they match the new archetypes incrementally the next time they are used.
+///
+/// The cache holds at most [`capacity`](Self::capacity) states. When it is full, the least
+/// recently used state is evicted to make room for a new one.
+pub struct QueryCache {
+    entries: HashMap<TypeId, CachedQuery>,
+    capacity: usize,
+    /// Incremented on every use of the cache, to find the least recently used entry.
+    clock: u64,
+}
+
+struct CachedQuery {
+    state: Box<dyn std::any::Any + Send + Sync>,
+    last_used: u64,
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::default(),
+            capacity: Self::DEFAULT_CAPACITY,
+            clock: 0,
+        }
+    }
+}
+
+impl fmt::Debug for QueryCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryCache")
+            .field("len", &self.entries.len())
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+impl QueryCache {
+    /// The number of states a cache holds by default.
+    pub const DEFAULT_CAPACITY: usize = 64;
+
+    /// The maximum number of states held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Sets the maximum number of states held by the cache, evicting the least recently used
+    /// states if it holds more.
+    ///
+    /// The cache always holds the state of the last query, even with a capacity of zero.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity.max(1) {
+            self.evict_least_recently_used();
+        }
+    }
+
+    /// The number of cached states.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no state is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the state of the query with data `D` and filter `F` is cached.
+    pub fn contains<D: QueryData + 'static, F: QueryFilter + 'static>(&self) -> bool {
+        self.entries.contains_key(&TypeId::of::<QueryState<D, F>>())
+    }
+
+    /// Evicts the state of the query with data `D` and filter `F`, returning `true` if it was
+    /// cached.
+    pub fn remove<D: QueryData + 'static, F: QueryFilter + 'static>(&mut self) -> bool {
+        self.entries
+            .remove(&TypeId::of::<QueryState<D, F>>())
+            .is_some()
+    }
+
+    /// Evicts every cached state.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Caches `state`, evicting the least recently used state if the cache is full.
+    pub(crate) fn insert<D: QueryData + 'static, F: QueryFilter + 'static>(
+        &mut self,
+        state: QueryState<D, F>,
+    ) {
+        while self.entries.len() >= self.capacity.max(1) {
+            self.evict_least_recently_used();
+        }
+        self.entries.insert(
+            TypeId::of::<QueryState<D, F>>(),
+            CachedQuery {
+                state: Box::new(state),
+                last_used: self.clock,
+            },
+        );
+    }
+
+    /// Returns the cached state of the query with data `D` and filter `F`, marking it as used.
+    pub(crate) fn get_mut<D: QueryData + 'static, F: QueryFilter + 'static>(
+        &mut self,
+    ) -> Option<&mut QueryState<D, F>> {
+        self.clock += 1;
+        let entry = self.entries.get_mut(&TypeId::of::<QueryState<D, F>>())?;
+        entry.last_used = self.clock;
+        entry.state.downcast_mut()
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        let least_recently_used = self
+            .entries
+            .iter()
+            .min_by_key(|(_, entry)| entry.last_used)
+            .map(|(type_id, _)| *type_id);
+        if let Some(type_id) = least_recently_used {
+            self.entries.remove(&type_id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{self as bevy_ecs, prelude::*};
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[derive(Component)]
+    struct C;
+
+    #[test]
+    fn cached_queries_match_new_archetypes() {
+        let mut world = World::new();
+        world.spawn(A(1));
+        assert_eq!(world.cached_query::<&A, ()>().iter().count(), 1);
+        assert!(world.query_cache().contains::<&A, ()>());
+
+        // A new archetype is matched by the cached state.
+        world.spawn((A(2), B));
+        let mut query = world.cached_query::<&mut A, With<B>>();
+        for mut a in &mut query {
+            a.0 *= 10;
+        }
+        let mut values: Vec<u32> = world.cached_query::<&A, ()>().iter().map(|a| a.0).collect();
+        values.sort_unstable();
+        assert_eq!(values, vec![1, 20]);
+        assert_eq!(world.query_cache().len(), 2);
+    }
+
+    #[test]
+    fn least_recently_used_queries_are_evicted() {
+        let mut world = World::new();
+        world.query_cache_mut().set_capacity(2);
+        world.cached_query::<&A, ()>();
+        world.cached_query::<&B, ()>();
+        world.cached_query::<&A, ()>();
+        world.cached_query::<&C, ()>();
+
+        let cache = world.query_cache();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains::<&A, ()>());
+        assert!(!cache.contains::<&B, ()>());
+        assert!(cache.contains::<&C, ()>());
+
+        world.query_cache_mut().set_capacity(0);
+        assert_eq!(world.query_cache().len(), 1);
+        assert!(world.query_cache().contains::<&C, ()>());
+    }
+}
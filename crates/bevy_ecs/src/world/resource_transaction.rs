@@ -0,0 +1,255 @@
+use std::{
+    any::TypeId,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use thiserror::Error;
+
+use crate::{system::Resource, world::World};
+
+/// A copy of a [`Resource`] being modified apart from the [`World`], to be written back with
+/// [`World::commit_resource_transaction`] if the resource didn't change in the meantime.
+///
+/// This allows optimistic concurrent modification, for instance from an async task holding the
+/// transaction while the schedule keeps running. Created with
+/// [`World::begin_resource_transaction`].
+pub struct ResourceTransaction<R: Resource> {
+    value: R,
+    version: u32,
+}
+
+impl<R: Resource> ResourceTransaction<R> {
+    /// Returns the version of the resource when the transaction began, see
+    /// [`World::resource_version`].
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Abandons the transaction, returning the modified copy of the resource.
+    pub fn into_inner(self) -> R {
+        self.value
+    }
+}
+
+impl<R: Resource> Deref for ResourceTransaction<R> {
+    type Target = R;
+
+    fn deref(&self) -> &R {
+        &self.value
+    }
+}
+
+impl<R: Resource> DerefMut for ResourceTransaction<R> {
+    fn deref_mut(&mut self) -> &mut R {
+        &mut self.value
+    }
+}
+
+/// The error returned by [`World::commit_resource_transaction`] when the resource changed or was
+/// removed after the transaction began.
+///
+/// The transaction is returned, so that it can be merged with the current value of the resource
+/// or retried.
+#[derive(Error)]
+#[error("The resource {} changed during a transaction begun at version {}.", std::any::type_name::<R>(), .transaction.version)]
+pub struct ResourceConflict<R: Resource> {
+    /// The transaction that couldn't be committed.
+    pub transaction: ResourceTransaction<R>,
+    /// The current version of the resource, or `None` if it was removed.
+    pub current_version: Option<u32>,
+}
+
+impl<R: Resource> fmt::Debug for ResourceConflict<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourceConflict")
+            .field("resource", &std::any::type_name::<R>())
+            .field("transaction_version", &self.transaction.version)
+            .field("current_version", &self.current_version)
+            .finish()
+    }
+}
+
+impl World {
+    /// Returns the version of the resource of type `R`, or `None` if it doesn't exist.
+    ///
+    /// The version is a wrapping counter increased when the resource is found to have changed since
+    /// its version was last read, so two reads return the same version only if the resource didn't
+    /// change in between. Several changes between two reads increase the version only once.
+    ///
+    /// Reading the version increases the world's change tick, so that changes made afterwards
+    /// increase the version even if they happen before the tick is next increased.
+    ///
+    /// Systems can read it with the [`ResourceVersion`](crate::system::ResourceVersion) parameter.
+    pub fn resource_version<R: Resource>(&self) -> Option<u32> {
+        let component_id = self.components.get_resource_id(TypeId::of::<R>())?;
+        let version = self
+            .storages
+            .resources
+            .get(component_id)
+            .filter(|data| data.is_present())
+            .map(|data| data.version())?;
+        // Changes made from now on get a newer tick than the current one.
+        self.increment_change_tick();
+        Some(version)
+    }
+
+    /// Copies the resource of type `R` into a [`ResourceTransaction`], to be modified apart from
+    /// the world and written back with [`World::commit_resource_transaction`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    pub fn begin_resource_transaction<R: Resource + Clone>(&self) -> ResourceTransaction<R> {
+        ResourceTransaction {
+            value: self.resource::<R>().clone(),
+            version: self.resource_version::<R>().unwrap(),
+        }
+    }
+
+    /// Writes the modified copy of a resource back into the world, unless the resource changed or
+    /// was removed since the `transaction` began. Returns the new version of the resource.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource, Clone)]
+    /// struct Score(u32);
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(Score(0));
+    ///
+    /// let mut first = world.begin_resource_transaction::<Score>();
+    /// let mut second = world.begin_resource_transaction::<Score>();
+    /// first.0 += 1;
+    /// second.0 += 2;
+    ///
+    /// assert!(world.commit_resource_transaction(first).is_ok());
+    /// // The second transaction began before the first one was committed.
+    /// let conflict = world.commit_resource_transaction(second).unwrap_err();
+    /// assert_eq!(conflict.transaction.0, 2);
+    /// assert_eq!(world.resource::<Score>().0, 1);
+    /// ```
+    pub fn commit_resource_transaction<R: Resource>(
+        &mut self,
+        transaction: ResourceTransaction<R>,
+    ) -> Result<u32, ResourceConflict<R>> {
+        let current_version = self.resource_version::<R>();
+        if current_version != Some(transaction.version) {
+            return Err(ResourceConflict {
+                transaction,
+                current_version,
+            });
+        }
+        *self.resource_mut::<R>() = transaction.value;
+        Ok(self.resource_version::<R>().unwrap())
+    }
+
+    /// Applies `f` to a copy of the resource of type `R`, and then replaces the resource with it.
+    ///
+    /// If `f` panics, the resource is left untouched. To modify the resource apart from the world,
+    /// with conflict detection, use [`World::begin_resource_transaction`] instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource doesn't exist.
+    pub fn resource_transaction<R: Resource + Clone, T>(
+        &mut self,
+        f: impl FnOnce(&mut R) -> T,
+    ) -> T {
+        let mut transaction = self.begin_resource_transaction::<R>();
+        let result = f(&mut transaction);
+        // The world is borrowed mutably for the whole transaction, so it can't conflict.
+        assert!(self.commit_resource_transaction(transaction).is_ok());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::system::{ResourceVersion, RunSystemOnce};
+
+    #[derive(Resource, Clone)]
+    struct Counter(u32);
+
+    #[test]
+    fn version_follows_changes() {
+        let mut world = World::new();
+        assert_eq!(world.resource_version::<Counter>(), None);
+
+        world.insert_resource(Counter(0));
+        let version = world.resource_version::<Counter>().unwrap();
+        assert_eq!(world.resource_version::<Counter>(), Some(version));
+
+        world.resource_mut::<Counter>().0 += 1;
+        let changed = world.resource_version::<Counter>().unwrap();
+        assert_ne!(changed, version);
+
+        fn read_version(version: ResourceVersion<Counter>) -> u32 {
+            version.get()
+        }
+        assert_eq!(world.run_system_once(read_version), changed);
+    }
+
+    #[test]
+    fn version_follows_same_tick_changes() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+
+        let version = world.resource_version::<Counter>().unwrap();
+        world.resource_mut::<Counter>().0 += 1;
+        world.resource_mut::<Counter>().0 += 1;
+        let changed = world.resource_version::<Counter>().unwrap();
+        assert_ne!(changed, version);
+        assert_eq!(world.resource_version::<Counter>(), Some(changed));
+
+        fn read_version(version: ResourceVersion<Counter>) -> u32 {
+            version.get()
+        }
+        let version = world.run_system_once(read_version);
+        world.resource_mut::<Counter>().0 += 1;
+        assert_ne!(world.run_system_once(read_version), version);
+    }
+
+    #[test]
+    fn transaction_conflicts_with_system_writes() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+
+        let mut transaction = world.begin_resource_transaction::<Counter>();
+        transaction.0 = 10;
+
+        world.run_system_once(|mut counter: ResMut<Counter>| counter.0 += 1);
+        let conflict = world.commit_resource_transaction(transaction).unwrap_err();
+        assert_eq!(conflict.transaction.0, 10);
+        assert_eq!(world.resource::<Counter>().0, 1);
+
+        let mut transaction = world.begin_resource_transaction::<Counter>();
+        transaction.0 += 10;
+        world.run_system_once(|counter: Res<Counter>| assert_eq!(counter.0, 1));
+        assert!(world.commit_resource_transaction(transaction).is_ok());
+        assert_eq!(world.resource::<Counter>().0, 11);
+    }
+
+    #[test]
+    fn panicking_transaction_leaves_resource() {
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+
+        let doubled = world.resource_transaction(|counter: &mut Counter| {
+            counter.0 += 1;
+            counter.0 * 2
+        });
+        assert_eq!(doubled, 2);
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.resource_transaction(|counter: &mut Counter| {
+                counter.0 += 1;
+                panic!("transaction failed");
+            });
+        }));
+        assert!(result.is_err());
+        assert_eq!(world.resource::<Counter>().0, 1);
+    }
+}
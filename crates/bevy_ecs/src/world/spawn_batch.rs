@@ -16,6 +16,8 @@ where
 {
     inner: I,
     spawner: BundleSpawner<'w>,
+    // Entities whose `Bundle::on_spawn` runs once the batch is spawned.
+    spawned: Vec<Entity>,
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -37,10 +39,12 @@ where
 
         let mut spawner = BundleSpawner::new::<I::Item>(world, change_tick);
         spawner.reserve_storage(length);
+        spawner.start_batch();
 
         Self {
             inner: iter,
             spawner,
+            spawned: Vec::new(),
         }
     }
 }
@@ -53,9 +57,19 @@ where
     fn drop(&mut self) {
         // Iterate through self in order to spawn remaining bundles.
         for _ in &mut *self {}
+        self.spawner.trigger_batched_hooks();
         // Apply any commands from those operations.
         // SAFETY: `self.spawner` will be dropped immediately after this call.
         unsafe { self.spawner.flush_commands() };
+        if !self.spawned.is_empty() {
+            // SAFETY: `self.spawner` will be dropped immediately after using the world.
+            let world = unsafe { self.spawner.world_mut() };
+            for entity in std::mem::take(&mut self.spawned) {
+                if let Some(mut entity) = world.get_entity_mut(entity) {
+                    I::Item::on_spawn(&mut entity);
+                }
+            }
+        }
     }
 }
 
@@ -69,7 +83,11 @@ where
     fn next(&mut self) -> Option<Entity> {
         let bundle = self.inner.next()?;
         // SAFETY: bundle matches spawner type
-        unsafe { Some(self.spawner.spawn(bundle)) }
+        let entity = unsafe { self.spawner.spawn(bundle) };
+        if I::Item::HAS_ON_SPAWN {
+            self.spawned.push(entity);
+        }
+        Some(entity)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -0,0 +1,239 @@
+use std::fmt;
+
+use bevy_utils::tracing::error;
+use concurrent_queue::ConcurrentQueue;
+
+use crate::{
+    bundle::Bundle,
+    component::Tick,
+    entity::{Entities, Entity},
+    system::{ReadOnlySystemParam, SystemMeta, SystemParam},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// The lock-free buffer of structural changes queued through [`StructuralCommands`],
+/// stored in the [`World`].
+pub(crate) struct StructuralCommandQueue {
+    queue: ConcurrentQueue<StructuralCommand>,
+}
+
+enum StructuralCommand {
+    Spawn(Box<dyn FnOnce(&mut World) + Send>),
+    Despawn(Entity),
+}
+
+impl Default for StructuralCommandQueue {
+    fn default() -> Self {
+        Self {
+            queue: ConcurrentQueue::unbounded(),
+        }
+    }
+}
+
+impl fmt::Debug for StructuralCommandQueue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StructuralCommandQueue")
+            .field("len", &self.queue.len())
+            .finish()
+    }
+}
+
+impl StructuralCommandQueue {
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    fn push(&self, command: StructuralCommand) {
+        // The queue is unbounded and never closed.
+        let _ = self.queue.push(command);
+    }
+}
+
+/// Queues entity spawns and despawns from shared access to the [`World`], without a
+/// [`Commands`](crate::system::Commands) queue per system.
+///
+/// Every [`StructuralCommands`] of a world pushes to the same lock-free buffer, so systems using
+/// them declare no access and run in parallel with any other system. The buffer is applied in a
+/// single pass at the next sync point, before the commands of the systems, or by
+/// [`World::flush_commands`].
+///
+/// Spawned entities are reserved immediately, so their ids can be used right away, for instance
+/// in components or events, but they don't hold any component until the buffer is applied.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::StructuralCommands};
+/// #[derive(Component)]
+/// struct Bullet;
+///
+/// fn fire(structural: StructuralCommands) {
+///     structural.spawn_batch((0..8).map(|_| Bullet));
+/// }
+///
+/// # let mut world = World::new();
+/// # let mut schedule = Schedule::default();
+/// # schedule.add_systems(fire);
+/// schedule.run(&mut world);
+/// assert_eq!(world.query::<&Bullet>().iter(&world).count(), 8);
+/// ```
+#[derive(Clone, Copy)]
+pub struct StructuralCommands<'w> {
+    queue: &'w StructuralCommandQueue,
+    entities: &'w Entities,
+}
+
+impl<'w> StructuralCommands<'w> {
+    pub(crate) fn new(queue: &'w StructuralCommandQueue, entities: &'w Entities) -> Self {
+        Self { queue, entities }
+    }
+
+    /// Queues the spawn of an entity for each bundle of `iter`, returning the reserved entities
+    /// in the same order.
+    pub fn spawn_batch<I>(&self, iter: I) -> Vec<Entity>
+    where
+        I: IntoIterator,
+        I::Item: Bundle,
+    {
+        let bundles: Vec<I::Item> = iter.into_iter().collect();
+        let entities: Vec<Entity> = self
+            .entities
+            .reserve_entities(bundles.len() as u32)
+            .collect();
+        let batch: Vec<_> = entities.iter().copied().zip(bundles).collect();
+        self.queue
+            .push(StructuralCommand::Spawn(Box::new(move |world: &mut World| {
+                if let Err(invalid_entities) = world.insert_or_spawn_batch(batch) {
+                    error!(
+                        "Failed to spawn bundle of type {} into the following invalid entities: {:?}",
+                        std::any::type_name::<I::Item>(),
+                        invalid_entities
+                    );
+                }
+            })));
+        entities
+    }
+
+    /// Queues the despawn of `entity`.
+    ///
+    /// A warning is logged if the entity no longer exists when the buffer is applied.
+    pub fn despawn(&self, entity: Entity) {
+        self.queue.push(StructuralCommand::Despawn(entity));
+    }
+}
+
+impl World {
+    /// Returns a [`StructuralCommands`] queuing spawns and despawns from shared access to the
+    /// world.
+    #[inline]
+    pub fn structural_commands(&self) -> StructuralCommands<'_> {
+        StructuralCommands::new(&self.structural_commands, &self.entities)
+    }
+
+    /// Applies the spawns and despawns queued through [`StructuralCommands`], in the order they
+    /// were queued.
+    ///
+    /// This is called by [`World::flush_commands`] and whenever a
+    /// [`CommandQueue`](crate::world::CommandQueue) is applied, so it rarely needs to be called
+    /// directly.
+    pub fn flush_structural_commands(&mut self) {
+        if self.structural_commands.is_empty() {
+            return;
+        }
+        // Reserved entities must exist before bundles can be inserted into them.
+        self.flush_entities();
+        while let Ok(command) = self.structural_commands.queue.pop() {
+            match command {
+                StructuralCommand::Spawn(spawn) => spawn(self),
+                StructuralCommand::Despawn(entity) => {
+                    self.despawn(entity);
+                }
+            }
+        }
+    }
+}
+
+// SAFETY: Only the lock-free queue and entity reservation are accessed.
+unsafe impl ReadOnlySystemParam for StructuralCommands<'_> {}
+
+// SAFETY: Only the lock-free queue and entity reservation are accessed.
+unsafe impl SystemParam for StructuralCommands<'_> {
+    type State = ();
+    type Item<'w, 's> = StructuralCommands<'w>;
+
+    fn init_state(_world: &mut World, system_meta: &mut SystemMeta) -> Self::State {
+        system_meta.set_has_deferred();
+    }
+
+    fn apply(_state: &mut Self::State, _system_meta: &SystemMeta, world: &mut World) {
+        world.flush_structural_commands();
+    }
+
+    #[inline]
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        _system_meta: &SystemMeta,
+        world: UnsafeWorldCell<'w>,
+        _change_tick: Tick,
+    ) -> Self::Item<'w, 's> {
+        world.structural_commands()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::prelude::*;
+    use crate::world::{DeferredWorld, StructuralCommands};
+
+    #[derive(Component)]
+    struct A(u32);
+
+    #[test]
+    fn deferred_world_spawn_and_despawn() {
+        let mut world = World::new();
+        let kept = world.spawn(A(0)).id();
+        let despawned = world.spawn(A(1)).id();
+
+        let deferred = DeferredWorld::from(&mut world);
+        let spawned = deferred.spawn_batch_deferred((2..5).map(A));
+        deferred.despawn_deferred(despawned);
+        assert_eq!(spawned.len(), 3);
+        assert!(deferred.get::<A>(spawned[0]).is_none());
+        assert!(deferred.get::<A>(despawned).is_some());
+
+        world.flush_commands();
+        assert!(world.get::<A>(kept).is_some());
+        assert!(world.get_entity(despawned).is_none());
+        let values: Vec<u32> = spawned
+            .iter()
+            .map(|&e| world.get::<A>(e).unwrap().0)
+            .collect();
+        assert_eq!(values, [2, 3, 4]);
+    }
+
+    #[test]
+    fn parallel_systems_flush_at_sync_point() {
+        fn spawn(structural: StructuralCommands) {
+            structural.spawn_batch((0..4).map(A));
+        }
+
+        fn despawn(structural: StructuralCommands, query: Query<(Entity, &A)>) {
+            for (entity, a) in &query {
+                if a.0 % 2 == 0 {
+                    structural.despawn(entity);
+                }
+            }
+        }
+
+        let mut world = World::new();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((spawn, spawn));
+        schedule.run(&mut world);
+        assert_eq!(world.query::<&A>().iter(&world).count(), 8);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(despawn);
+        schedule.run(&mut world);
+        assert_eq!(world.query::<&A>().iter(&world).count(), 4);
+    }
+}
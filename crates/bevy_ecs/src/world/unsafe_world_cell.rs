@@ -2,7 +2,10 @@
 
 #![warn(unsafe_op_in_unsafe_fn)]
 
-use super::{command_queue::CommandQueue, Mut, Ref, World, WorldId};
+use super::{
+    command_queue::CommandQueue, ComponentLifecycleStats, Mut, QueryCache, Ref, StructuralCommands,
+    World, WorldId,
+};
 use crate::{
     archetype::{Archetype, Archetypes},
     bundle::Bundles,
@@ -10,12 +13,22 @@ use crate::{
     component::{ComponentId, ComponentTicks, Components, StorageType, Tick, TickCells},
     entity::{Entities, Entity, EntityLocation},
     prelude::Component,
+    query::Access,
     removal_detection::RemovedComponentEvents,
     storage::{Column, ComponentSparseSet, Storages},
     system::{Res, Resource},
+    tag::TagIndex,
 };
 use bevy_ptr::Ptr;
-use std::{any::TypeId, cell::UnsafeCell, fmt::Debug, marker::PhantomData, ptr, ptr::addr_of_mut};
+use std::{
+    any::TypeId,
+    cell::UnsafeCell,
+    fmt::Debug,
+    marker::PhantomData,
+    ptr,
+    ptr::addr_of_mut,
+    sync::atomic::{AtomicU32, Ordering},
+};
 
 /// Variant of the [`World`] where resource and component accesses take `&self`, and the responsibility to avoid
 /// aliasing violations are given to the caller instead of being checked at compile-time by rust's unique XOR shared rule.
@@ -71,6 +84,9 @@ use std::{any::TypeId, cell::UnsafeCell, fmt::Debug, marker::PhantomData, ptr, p
 ///     (resource_access, component_access)
 /// }
 /// ```
+///
+/// [`UnsafeWorldCell::split_access`] implements this pattern for any set of components and resources,
+/// returning two [`WorldCellView`]s that check their [`Access`] at runtime.
 #[derive(Copy, Clone)]
 pub struct UnsafeWorldCell<'w>(*mut World, PhantomData<(&'w World, &'w UnsafeCell<World>)>);
 
@@ -207,6 +223,17 @@ impl<'w> UnsafeWorldCell<'w> {
         &unsafe { self.world_metadata() }.entities
     }
 
+    /// Returns a [`StructuralCommands`] queuing spawns and despawns in this world's lock-free
+    /// structural buffer.
+    #[inline]
+    pub fn structural_commands(self) -> StructuralCommands<'w> {
+        // SAFETY:
+        // - we only access world metadata and the lock-free structural buffer, which is only
+        //   accessed mutably through `&mut World`
+        let world = unsafe { self.world_metadata() };
+        StructuralCommands::new(&world.structural_commands, &world.entities)
+    }
+
     /// Retrieves this world's [`Archetypes`] collection.
     #[inline]
     pub fn archetypes(self) -> &'w Archetypes {
@@ -570,6 +597,248 @@ impl<'w> UnsafeWorldCell<'w> {
         // - caller ensures that we have permission to access the queue
         unsafe { &mut *addr_of_mut!((*self.0).command_queue) }
     }
+
+    /// Returns a mutable reference to the underlying world's [`ComponentLifecycleStats`].
+    /// # Safety
+    /// It is the callers responsibility to ensure that
+    /// - the [`UnsafeWorldCell`] has permission to access the stats mutably
+    /// - no other references to the stats exist at the same time
+    pub(crate) unsafe fn lifecycle_stats_mut(self) -> &'w mut ComponentLifecycleStats {
+        // SAFETY:
+        // - caller ensures there are no existing references
+        // - caller ensures that we have permission to access the stats
+        unsafe { &mut *addr_of_mut!((*self.0).lifecycle_stats) }
+    }
+
+    /// Returns a mutable reference to the underlying world's [`TagIndex`].
+    /// # Safety
+    /// It is the callers responsibility to ensure that
+    /// - the [`UnsafeWorldCell`] has permission to access the index mutably
+    /// - no other references to the index exist at the same time
+    pub(crate) unsafe fn tag_index_mut(self) -> &'w mut TagIndex {
+        // SAFETY:
+        // - caller ensures there are no existing references
+        // - caller ensures that we have permission to access the index
+        unsafe { &mut *addr_of_mut!((*self.0).tag_index) }
+    }
+
+    /// Returns a mutable reference to the underlying world's [`QueryCache`].
+    /// # Safety
+    /// It is the callers responsibility to ensure that
+    /// - the [`UnsafeWorldCell`] has permission to access the cache mutably
+    /// - no other references to the cache exist at the same time
+    pub(crate) unsafe fn query_cache_mut(self) -> &'w mut QueryCache {
+        // SAFETY:
+        // - caller ensures there are no existing references
+        // - caller ensures that we have permission to access the cache
+        unsafe { &mut *addr_of_mut!((*self.0).query_cache) }
+    }
+}
+
+impl<'w> UnsafeWorldCell<'w> {
+    /// Splits the world into a [`WorldCellView`] that can safely access the components and
+    /// resources granted by `access`, and a view of the remaining accesses.
+    ///
+    /// The remaining view keeps read access to what `access` only reads, and has no access to
+    /// what `access` writes. Like [`WorldCellView::split`], the remaining accesses are resolved
+    /// against the components and resources registered at the time of the split.
+    ///
+    /// This formalizes the pattern shown in the [`UnsafeWorldCell`] documentation: once the
+    /// preconditions below have been checked, both views can be handed to safe code.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_ecs::query::Access;
+    /// # #[derive(Resource)] struct Score(u32);
+    /// # #[derive(Component)] struct Health(u32);
+    /// let mut world = World::new();
+    /// world.insert_resource(Score(0));
+    /// let entity = world.spawn(Health(10)).id();
+    ///
+    /// let mut access = Access::default();
+    /// access.add_write(world.components().resource_id::<Score>().unwrap());
+    ///
+    /// let cell = world.as_unsafe_world_cell();
+    /// // SAFETY: `cell` has access to the whole world, and isn't used while the views are alive.
+    /// let (mut view, mut rest) = unsafe { cell.split_access(access) };
+    ///
+    /// view.get_resource_mut::<Score>().unwrap().0 += 1;
+    /// // The view only has access to `Score`.
+    /// assert!(view.get::<Health>(entity).is_none());
+    /// // The remaining view has access to everything else.
+    /// assert!(rest.get_resource::<Score>().is_none());
+    /// rest.get_mut::<Health>(entity).unwrap().0 -= 1;
+    /// # assert_eq!(world.resource::<Score>().0, 1);
+    /// ```
+    ///
+    /// # Safety
+    /// - `self` must have permission to access the whole world mutably.
+    /// - While the returned views are alive, no other copy of `self` may be used to access the
+    ///   components and resources of the world.
+    pub unsafe fn split_access(
+        self,
+        access: Access<ComponentId>,
+    ) -> (WorldCellView<'w>, WorldCellView<'w>) {
+        let mut all = Access::default();
+        all.write_all();
+        let remaining = remaining_access(self.components(), &all, &access);
+        // Both views satisfy the invariant: the caller ensures `self` can access the whole world
+        // exclusively, and the accesses the views share are reads.
+        (
+            WorldCellView {
+                world: self,
+                access,
+            },
+            WorldCellView {
+                world: self,
+                access: remaining,
+            },
+        )
+    }
+}
+
+/// A view of a [`World`] that can only access the components and resources in its [`Access`].
+///
+/// Unlike [`UnsafeWorldCell`], the view checks its access at runtime, so its methods are safe.
+/// Accessing a component or resource outside of the view's access returns `None`.
+///
+/// Views are created with [`UnsafeWorldCell::split_access`], and can be split further with
+/// [`WorldCellView::split`].
+pub struct WorldCellView<'w> {
+    world: UnsafeWorldCell<'w>,
+    // INVARIANT: the view has permission to perform every access in `access`, and no other
+    // access to the world conflicts with it while the view is alive.
+    access: Access<ComponentId>,
+}
+
+impl<'w> WorldCellView<'w> {
+    /// Returns the components and resources this view can access.
+    #[inline]
+    pub fn access(&self) -> &Access<ComponentId> {
+        &self.access
+    }
+
+    /// Retrieves this view's [`Components`] collection.
+    #[inline]
+    pub fn components(&self) -> &'w Components {
+        self.world.components()
+    }
+
+    /// Retrieves this view's [`Entities`] collection.
+    #[inline]
+    pub fn entities(&self) -> &'w Entities {
+        self.world.entities()
+    }
+
+    /// Gets a reference to the resource of type `R`, if it exists and this view can read it.
+    #[inline]
+    pub fn get_resource<R: Resource>(&self) -> Option<&R> {
+        let id = self.components().get_resource_id(TypeId::of::<R>())?;
+        if !self.access.has_read(id) {
+            return None;
+        }
+        // SAFETY: the view has read access to the resource, and no mutable reference to it can
+        // exist while `self` is borrowed immutably.
+        unsafe { self.world.get_resource::<R>() }
+    }
+
+    /// Gets a mutable reference to the resource of type `R`, if it exists and this view can write
+    /// it.
+    #[inline]
+    pub fn get_resource_mut<R: Resource>(&mut self) -> Option<Mut<'_, R>> {
+        let id = self.components().get_resource_id(TypeId::of::<R>())?;
+        if !self.access.has_write(id) {
+            return None;
+        }
+        // SAFETY: the view has write access to the resource, and `self` is borrowed mutably so no
+        // other reference to it can be obtained from the view.
+        unsafe { self.world.get_resource_mut::<R>() }
+    }
+
+    /// Gets a reference to the component `T` of `entity`, if it exists and this view can read it.
+    #[inline]
+    pub fn get<T: Component>(&self, entity: Entity) -> Option<&T> {
+        let id = self.components().component_id::<T>()?;
+        if !self.access.has_read(id) {
+            return None;
+        }
+        // SAFETY: the view has read access to `T`, and no mutable reference to it can exist while
+        // `self` is borrowed immutably.
+        unsafe { self.world.get_entity(entity)?.get::<T>() }
+    }
+
+    /// Gets a mutable reference to the component `T` of `entity`, if it exists and this view can
+    /// write it.
+    #[inline]
+    pub fn get_mut<T: Component>(&mut self, entity: Entity) -> Option<Mut<'_, T>> {
+        let id = self.components().component_id::<T>()?;
+        if !self.access.has_write(id) {
+            return None;
+        }
+        // SAFETY: the view has write access to `T`, and `self` is borrowed mutably so no other
+        // reference to it can be obtained from the view.
+        unsafe { self.world.get_entity(entity)?.get_mut::<T>() }
+    }
+
+    /// Splits this view in two: a view with the given `access`, and a view with the remaining
+    /// accesses.
+    ///
+    /// The remaining view keeps read access to what both views only read, and loses access to
+    /// anything the new view writes.
+    /// Accesses to all components are resolved against the components registered at the time of
+    /// the split.
+    ///
+    /// Returns `Err(self)` if `access` isn't a subset of this view's access.
+    pub fn split(
+        self,
+        access: Access<ComponentId>,
+    ) -> Result<(WorldCellView<'w>, WorldCellView<'w>), WorldCellView<'w>> {
+        if !access.is_subset(&self.access) {
+            return Err(self);
+        }
+        let remaining = remaining_access(self.components(), &self.access, &access);
+        // Both views satisfy the invariant: they only hold accesses of `self`, and the accesses
+        // they share are reads.
+        Ok((
+            WorldCellView {
+                world: self.world,
+                access,
+            },
+            WorldCellView {
+                world: self.world,
+                access: remaining,
+            },
+        ))
+    }
+}
+
+/// Returns the accesses of `access` left after splitting off `split`, resolved against the
+/// registered `components`: writes that `split` doesn't read, and reads that `split` doesn't
+/// write.
+fn remaining_access(
+    components: &Components,
+    access: &Access<ComponentId>,
+    split: &Access<ComponentId>,
+) -> Access<ComponentId> {
+    let mut remaining = Access::default();
+    for index in 0..components.len() {
+        let id = ComponentId::new(index);
+        if access.has_write(id) && !split.has_read(id) {
+            remaining.add_write(id);
+        } else if access.has_read(id) && !split.has_write(id) {
+            remaining.add_read(id);
+        }
+    }
+    remaining
+}
+
+impl Debug for WorldCellView<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("WorldCellView")
+            .field("world", &self.world)
+            .field("access", &self.access)
+            .finish()
+    }
 }
 
 impl Debug for UnsafeWorldCell<'_> {
@@ -814,10 +1083,34 @@ impl<'w> UnsafeEntityCell<'w> {
             .map(|(value, cells)| Mut {
                 // SAFETY: returned component is of type T
                 value: value.assert_unique().deref_mut::<T>(),
-                ticks: TicksMut::from_tick_cells(cells, last_change_tick, change_tick),
+                ticks: TicksMut::from_tick_cells(cells, last_change_tick, change_tick)
+                    .with_entity_changed(Some(self.entity_changed_tick())),
             })
         }
     }
+
+    /// Returns `true` if any component of the entity was added or changed after `tick`.
+    ///
+    /// This checks a single tick stored alongside the entity's table row, updated on every
+    /// component write, rather than the ticks of each component.
+    #[inline]
+    pub fn any_changed_since(self, tick: Tick) -> bool {
+        let entity_changed = Tick::new(self.entity_changed_tick().load(Ordering::Relaxed));
+        entity_changed.is_newer_than(tick, self.world.change_tick())
+    }
+
+    /// The tick tracking the last change of any component of the entity.
+    #[inline]
+    fn entity_changed_tick(self) -> &'w AtomicU32 {
+        // SAFETY: The tick is only accessed atomically, so it never conflicts with other accesses.
+        let table = &unsafe { self.world.storages() }.tables[self.location.table_id];
+        // SAFETY: `location` is valid, so its row is in range of its table.
+        unsafe {
+            table
+                .get_entity_changed_ticks_slice()
+                .get_unchecked(self.location.table_row.as_usize())
+        }
+    }
 }
 
 impl<'w> UnsafeEntityCell<'w> {
@@ -878,7 +1171,8 @@ impl<'w> UnsafeEntityCell<'w> {
                     cells,
                     self.world.last_change_tick(),
                     self.world.change_tick(),
-                ),
+                )
+                .with_entity_changed(Some(self.entity_changed_tick())),
             })
         }
     }
@@ -966,6 +1260,7 @@ unsafe fn get_component_and_ticks(
                 TickCells {
                     added: components.get_added_tick_unchecked(location.table_row),
                     changed: components.get_changed_tick_unchecked(location.table_row),
+                    fields: components.get_field_ticks_unchecked(location.table_row),
                 },
             ))
         }
@@ -999,3 +1294,61 @@ unsafe fn get_ticks(
         StorageType::SparseSet => world.fetch_sparse_set(component_id)?.get_ticks(entity),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate as bevy_ecs;
+    use crate::{prelude::*, query::Access};
+
+    #[derive(Resource)]
+    struct Score(u32);
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    struct Armor(u32);
+
+    #[test]
+    fn split_view_access() {
+        let mut world = World::new();
+        world.insert_resource(Score(0));
+        let entity = world.spawn((Health(10), Armor(5))).id();
+        let score = world.components().resource_id::<Score>().unwrap();
+        let health = world.component_id::<Health>().unwrap();
+        let armor = world.component_id::<Armor>().unwrap();
+
+        let mut all = Access::default();
+        all.add_write(score);
+        all.add_write(health);
+        all.add_read(armor);
+        // SAFETY: the cell has access to the whole world and isn't used afterwards.
+        let (view, mut outside) = unsafe { world.as_unsafe_world_cell().split_access(all) };
+        assert!(outside.get::<Health>(entity).is_none());
+        assert!(outside.get_resource_mut::<Score>().is_none());
+        // `Armor` is only read by the split view.
+        assert_eq!(outside.get::<Armor>(entity).unwrap().0, 5);
+        assert!(outside.get_mut::<Armor>(entity).is_none());
+
+        let mut health_access = Access::default();
+        health_access.add_write(health);
+        health_access.add_read(armor);
+        let (mut health_view, mut rest) = view.split(health_access).unwrap();
+
+        health_view.get_mut::<Health>(entity).unwrap().0 -= 1;
+        assert_eq!(health_view.get::<Armor>(entity).unwrap().0, 5);
+        assert!(health_view.get_resource::<Score>().is_none());
+
+        assert!(rest.get_mut::<Health>(entity).is_none());
+        assert!(rest.get::<Health>(entity).is_none());
+        assert_eq!(rest.get::<Armor>(entity).unwrap().0, 5);
+        rest.get_resource_mut::<Score>().unwrap().0 += 1;
+
+        let mut too_much = Access::default();
+        too_much.add_write(armor);
+        assert!(rest.split(too_much).is_err());
+
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 9);
+        assert_eq!(world.resource::<Score>().0, 1);
+    }
+}
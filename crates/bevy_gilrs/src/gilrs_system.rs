@@ -11,6 +11,7 @@ use bevy_input::gamepad::{
     GamepadSettings,
 };
 use bevy_input::gamepad::{GamepadEvent, GamepadInfo};
+use bevy_input::gamepad_calibration::{GamepadCalibrationWizard, GamepadCalibrations};
 use bevy_input::prelude::{GamepadAxis, GamepadButton};
 use bevy_input::Axis;
 use gilrs::{ev::filter::axis_dpad_to_button, EventType, Filter};
@@ -42,6 +43,8 @@ pub fn gilrs_event_system(
     mut gamepad_buttons: ResMut<Axis<GamepadButton>>,
     gamepad_axis: Res<Axis<GamepadAxis>>,
     gamepad_settings: Res<GamepadSettings>,
+    gamepad_calibrations: Res<GamepadCalibrations>,
+    mut calibration_wizard: Option<ResMut<GamepadCalibrationWizard>>,
 ) {
     let gilrs = gilrs.0.get();
     while let Some(gilrs_event) = gilrs.next_event().filter_ev(&axis_dpad_to_button, gilrs) {
@@ -84,6 +87,15 @@ pub fn gilrs_event_system(
             }
             EventType::AxisChanged(gilrs_axis, raw_value, _) => {
                 if let Some(axis_type) = convert_axis(gilrs_axis) {
+                    if let Some(wizard) = calibration_wizard.as_deref_mut() {
+                        wizard.sample(gamepad, axis_type, raw_value);
+                    }
+                    // Calibrations are keyed by device name, which is stable across sessions.
+                    let raw_value = gamepad_calibrations.apply(
+                        gilrs.gamepad(gilrs_event.id).name(),
+                        axis_type,
+                        raw_value,
+                    );
                     let axis = GamepadAxis::new(gamepad, axis_type);
                     let old_value = gamepad_axis.get(axis);
                     let axis_settings = gamepad_settings.get_axis_settings(axis);
@@ -10,6 +10,7 @@ use bevy_color::{
 };
 use bevy_math::{Quat, Vec2, Vec3};
 use bevy_transform::TransformPoint;
+use std::f32::consts::TAU;
 
 /// A builder returned by [`Gizmos::arrow`] and [`Gizmos::arrow_2d`]
 pub struct ArrowBuilder<'a, 'w, 's, Config, Clear>
@@ -23,6 +24,7 @@ where
     color: Color,
     double_ended: bool,
     tip_length: f32,
+    cone_segments: Option<usize>,
 }
 
 impl<Config, Clear> ArrowBuilder<'_, '_, '_, Config, Clear>
@@ -57,6 +59,27 @@ where
         self.double_ended = true;
         self
     }
+
+    /// Draws the tips as cones made of `segments` lines joined by a ring, instead of four lines.
+    ///
+    /// Cone tips read better than the default tips when the arrow is viewed from its end.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_math::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// fn system(mut gizmos: Gizmos) {
+    ///     gizmos.arrow(Vec3::ZERO, Vec3::ONE, GREEN)
+    ///         .with_cone_tips(12);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn with_cone_tips(mut self, segments: usize) -> Self {
+        self.cone_segments = Some(segments);
+        self
+    }
 }
 
 impl<Config, Clear> Drop for ArrowBuilder<'_, '_, '_, Config, Clear>
@@ -72,33 +95,52 @@ where
         // first, draw the body of the arrow
         self.gizmos.line(self.start, self.end, self.color);
         // now the hard part is to draw the head in a sensible way
+        self.draw_tip(self.end, (self.end - self.start).normalize());
+        if self.double_ended {
+            self.draw_tip(self.start, (self.start - self.end).normalize());
+        }
+    }
+}
+
+impl<Config, Clear> ArrowBuilder<'_, '_, '_, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draws a tip at `position`, pointing towards `direction`.
+    fn draw_tip(&mut self, position: Vec3, direction: Vec3) {
         // put us in a coordinate system where the arrow is pointing towards +x and ends at the origin
-        let pointing_end = (self.end - self.start).normalize();
-        let rotation_end = Quat::from_rotation_arc(Vec3::X, pointing_end);
-        let tips = [
-            Vec3::new(-1., 1., 0.),
-            Vec3::new(-1., 0., 1.),
-            Vec3::new(-1., -1., 0.),
-            Vec3::new(-1., 0., -1.),
-        ];
+        let rotation = Quat::from_rotation_arc(Vec3::X, direction);
         // - extend the vectors so their length is `tip_length`
         // - rotate the world so +x is facing in the same direction as the arrow
         // - translate over to the tip of the arrow
-        let tips_end = tips.map(|v| rotation_end * (v.normalize() * self.tip_length) + self.end);
-        for v in tips_end {
-            // then actually draw the tips
-            self.gizmos.line(self.end, v, self.color);
-        }
-        if self.double_ended {
-            let pointing_start = (self.start - self.end).normalize();
-            let rotation_start = Quat::from_rotation_arc(Vec3::X, pointing_start);
-            let tips_start =
-                tips.map(|v| rotation_start * (v.normalize() * self.tip_length) + self.start);
-            for v in tips_start {
-                // draw the start points tips
-                self.gizmos.line(self.start, v, self.color);
+        let to_world = |v: Vec3| rotation * (v.normalize() * self.tip_length) + position;
+
+        let Some(segments) = self.cone_segments.filter(|&segments| segments >= 3) else {
+            let tips = [
+                Vec3::new(-1., 1., 0.),
+                Vec3::new(-1., 0., 1.),
+                Vec3::new(-1., -1., 0.),
+                Vec3::new(-1., 0., -1.),
+            ];
+            for v in tips.map(to_world) {
+                // then actually draw the tips
+                self.gizmos.line(position, v, self.color);
             }
+            return;
+        };
+
+        // the base of the cone goes through the ends of the four line tips
+        let base: Vec<Vec3> = (0..=segments)
+            .map(|i| {
+                let angle = i as f32 * TAU / segments as f32;
+                to_world(Vec3::new(-1., angle.cos(), angle.sin()))
+            })
+            .collect();
+        for &v in &base[..segments] {
+            self.gizmos.line(position, v, self.color);
         }
+        self.gizmos.linestrip(base, self.color);
     }
 }
 
@@ -136,6 +178,7 @@ where
             color: color.into(),
             double_ended: false,
             tip_length: length / 10.,
+            cone_segments: None,
         }
     }
 
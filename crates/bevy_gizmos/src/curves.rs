@@ -0,0 +1,253 @@
+//! Additional [`Gizmos`] Functions -- Curves
+//!
+//! Includes the implementation of [`Gizmos::curve_2d`] and [`Gizmos::curve_3d`],
+//! and assorted support items.
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::Color;
+use bevy_math::{cubic_splines::CubicCurve, NormedVectorSpace, Vec2, Vec3};
+
+/// The default maximum distance between a curve and the lines drawn for it.
+pub const DEFAULT_CURVE_TOLERANCE: f32 = 0.01;
+
+/// The maximum number of times each segment of a curve is halved while sampling it.
+const MAX_SUBDIVISION_DEPTH: u32 = 10;
+
+/// A point type of a curve that can be drawn with [`Gizmos`].
+pub trait CurvePoint: NormedVectorSpace {
+    /// Converts the point to the 3D position used for drawing.
+    fn to_gizmo_position(self) -> Vec3;
+}
+
+impl CurvePoint for Vec2 {
+    fn to_gizmo_position(self) -> Vec3 {
+        self.extend(0.)
+    }
+}
+
+impl CurvePoint for Vec3 {
+    fn to_gizmo_position(self) -> Vec3 {
+        self
+    }
+}
+
+/// A builder returned by [`Gizmos::curve_2d`] and [`Gizmos::curve_3d`]
+pub struct CurveBuilder<'a, 'w, 's, Config, Clear, P>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+    P: CurvePoint,
+{
+    gizmos: &'a mut Gizmos<'w, 's, Config, Clear>,
+    curve: &'a CubicCurve<P>,
+    color: Color,
+    tolerance: f32,
+}
+
+impl<Config, Clear, P> CurveBuilder<'_, '_, '_, Config, Clear, P>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+    P: CurvePoint,
+{
+    /// Set the maximum distance between the curve and the lines drawn for it.
+    ///
+    /// Lower values draw a smoother curve with more lines.
+    /// The default tolerance is [`DEFAULT_CURVE_TOLERANCE`].
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl<Config, Clear, P> Drop for CurveBuilder<'_, '_, '_, Config, Clear, P>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+    P: CurvePoint,
+{
+    fn drop(&mut self) {
+        if !self.gizmos.enabled {
+            return;
+        }
+        let positions = sample_curve(self.curve, self.tolerance);
+        self.gizmos.linestrip(
+            positions.into_iter().map(CurvePoint::to_gizmo_position),
+            self.color,
+        );
+    }
+}
+
+/// Samples `curve` so that the polyline through the samples is at most about `tolerance` away
+/// from it.
+///
+/// Each segment of the curve is split in four, and the parts are halved again until the midpoint
+/// of the curve is within `tolerance` of the midpoint of the chord, so straight parts use few
+/// lines and tight bends use many.
+pub fn sample_curve<P: CurvePoint>(curve: &CubicCurve<P>, tolerance: f32) -> Vec<P> {
+    let mut samples = Vec::new();
+    if curve.segments().is_empty() {
+        return samples;
+    }
+    let tolerance_squared = tolerance.max(f32::EPSILON).powi(2);
+
+    for (index, segment) in curve.segments().iter().enumerate() {
+        if index == 0 {
+            samples.push(segment.position(0.));
+        }
+        let quarters = [0., 0.25, 0.5, 0.75, 1.].map(|t| (t, segment.position(t)));
+        for window in quarters.windows(2) {
+            subdivide(
+                &|t| segment.position(t),
+                window[0],
+                window[1],
+                tolerance_squared,
+                MAX_SUBDIVISION_DEPTH,
+                &mut samples,
+            );
+        }
+    }
+    samples
+}
+
+/// Pushes the samples after `start`, up to and including `end`.
+fn subdivide<P: CurvePoint>(
+    position: &impl Fn(f32) -> P,
+    start: (f32, P),
+    end: (f32, P),
+    tolerance_squared: f32,
+    depth: u32,
+    samples: &mut Vec<P>,
+) {
+    let t = (start.0 + end.0) / 2.;
+    let middle = position(t);
+    let chord_middle = (start.1 + end.1) / 2.;
+    if depth > 0 && middle.distance_squared(chord_middle) > tolerance_squared {
+        let middle = (t, middle);
+        subdivide(
+            position,
+            start,
+            middle,
+            tolerance_squared,
+            depth - 1,
+            samples,
+        );
+        subdivide(position, middle, end, tolerance_squared, depth - 1, samples);
+    } else {
+        samples.push(end.1);
+    }
+}
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw a curve in 3D, such as a spline, with lines.
+    ///
+    /// The curve is sampled adaptively: more lines are used where the curve bends, see
+    /// [`CurveBuilder::tolerance`].
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::{cubic_splines::CubicCurve, prelude::*};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// #[derive(Resource)]
+    /// struct Path(CubicCurve<Vec3>);
+    ///
+    /// fn system(mut gizmos: Gizmos, path: Res<Path>) {
+    ///     gizmos.curve_3d(&path.0, GREEN).tolerance(0.001);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn curve_3d<'a>(
+        &'a mut self,
+        curve: &'a CubicCurve<Vec3>,
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'a, 'w, 's, Config, Clear, Vec3> {
+        CurveBuilder {
+            gizmos: self,
+            curve,
+            color: color.into(),
+            tolerance: DEFAULT_CURVE_TOLERANCE,
+        }
+    }
+
+    /// Draw a curve in 2D (on the xy plane), such as a spline, with lines.
+    ///
+    /// The curve is sampled adaptively: more lines are used where the curve bends, see
+    /// [`CurveBuilder::tolerance`].
+    ///
+    /// This should be called for each frame the curve needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_math::{cubic_splines::CubicCurve, prelude::*};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_color::palettes::basic::GREEN;
+    /// #[derive(Resource)]
+    /// struct Path(CubicCurve<Vec2>);
+    ///
+    /// fn system(mut gizmos: Gizmos, path: Res<Path>) {
+    ///     gizmos.curve_2d(&path.0, GREEN);
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn curve_2d<'a>(
+        &'a mut self,
+        curve: &'a CubicCurve<Vec2>,
+        color: impl Into<Color>,
+    ) -> CurveBuilder<'a, 'w, 's, Config, Clear, Vec2> {
+        CurveBuilder {
+            gizmos: self,
+            curve,
+            color: color.into(),
+            tolerance: DEFAULT_CURVE_TOLERANCE,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::cubic_splines::{CubicBezier, CubicGenerator};
+
+    #[test]
+    fn straight_curves_use_few_samples() {
+        let line = CubicBezier::new([[Vec2::ZERO, Vec2::X, Vec2::X * 2., Vec2::X * 3.]]).to_curve();
+        let samples = sample_curve(&line, 0.01);
+        assert_eq!(samples.len(), 5);
+        assert_eq!(samples.first(), Some(&Vec2::ZERO));
+        assert_eq!(samples.last(), Some(&(Vec2::X * 3.)));
+    }
+
+    #[test]
+    fn bends_are_within_tolerance() {
+        let bend = CubicBezier::new([[Vec2::ZERO, Vec2::Y * 4., Vec2::new(4., 4.), Vec2::X * 4.]])
+            .to_curve();
+        let coarse = sample_curve(&bend, 0.1);
+        let fine = sample_curve(&bend, 0.001);
+        assert!(fine.len() > coarse.len());
+        assert_eq!(fine.last(), Some(&(Vec2::X * 4.)));
+
+        // every point of the curve is close to the polyline
+        for i in 0..=100 {
+            let point = bend.position(i as f32 / 100.);
+            let distance = fine
+                .windows(2)
+                .map(|line| {
+                    let direction = line[1] - line[0];
+                    let t = ((point - line[0]).dot(direction) / direction.length_squared())
+                        .clamp(0., 1.);
+                    point.distance(line[0] + direction * t)
+                })
+                .fold(f32::INFINITY, f32::min);
+            assert!(distance < 0.01, "{point} is {distance} from the polyline");
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Additional [`Gizmos`] Functions -- Frusta
+//!
+//! Includes the implementation of [`Gizmos::frustum`] and [`Gizmos::frustum_corners`].
+
+use crate::prelude::{GizmoConfigGroup, Gizmos};
+use bevy_color::Color;
+use bevy_math::Vec3;
+use bevy_render::camera::{CameraProjection, Projection};
+use bevy_transform::TransformPoint;
+
+impl<'w, 's, Config, Clear> Gizmos<'w, 's, Config, Clear>
+where
+    Config: GizmoConfigGroup,
+    Clear: 'static + Send + Sync,
+{
+    /// Draw the view frustum of a camera with the given `projection`, placed at `transform`.
+    ///
+    /// The frustum spans from the near plane to the far plane of the projection. Perspective
+    /// projections usually have a distant far plane: use [`Gizmos::frustum_corners`] with
+    /// [`CameraProjection::get_frustum_corners`] to draw a shorter frustum.
+    ///
+    /// This should be called for each frame the frustum needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::prelude::*;
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_transform::components::GlobalTransform;
+    /// # use bevy_color::palettes::basic::YELLOW;
+    /// fn system(mut gizmos: Gizmos, cameras: Query<(&Projection, &GlobalTransform)>) {
+    ///     for (projection, transform) in &cameras {
+    ///         gizmos.frustum(projection, *transform, YELLOW);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn frustum(
+        &mut self,
+        projection: &Projection,
+        transform: impl TransformPoint,
+        color: impl Into<Color>,
+    ) {
+        let near = match projection {
+            Projection::Perspective(projection) => projection.near,
+            Projection::Orthographic(projection) => projection.near,
+        };
+        // cameras look towards -z
+        let corners = projection
+            .get_frustum_corners(-near, -projection.far())
+            .map(|corner| transform.transform_point(corner));
+        self.frustum_corners(corners, color);
+    }
+
+    /// Draw a frustum from its eight `corners`.
+    ///
+    /// The first four corners are the corners of the near plane, and the last four the corners of
+    /// the far plane, both in the same winding order, as returned by
+    /// [`CameraProjection::get_frustum_corners`].
+    ///
+    /// This should be called for each frame the frustum needs to be rendered.
+    ///
+    /// # Example
+    /// ```
+    /// # use bevy_gizmos::prelude::*;
+    /// # use bevy_render::{camera::CameraProjection, prelude::*};
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_transform::components::GlobalTransform;
+    /// # use bevy_color::palettes::basic::YELLOW;
+    /// fn system(mut gizmos: Gizmos, cameras: Query<(&Projection, &GlobalTransform)>) {
+    ///     for (projection, transform) in &cameras {
+    ///         // only draw the first ten units of the frustum
+    ///         let corners = projection
+    ///             .get_frustum_corners(-0.1, -10.0)
+    ///             .map(|corner| transform.transform_point(corner));
+    ///         gizmos.frustum_corners(corners, YELLOW);
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    pub fn frustum_corners(&mut self, corners: [Vec3; 8], color: impl Into<Color>) {
+        if !self.enabled {
+            return;
+        }
+        let color = color.into();
+        let [near, far] = [0, 4].map(|offset| [0, 1, 2, 3, 0].map(|index| corners[offset + index]));
+        self.linestrip(near, color);
+        self.linestrip(far, color);
+        for index in 0..4 {
+            self.line(near[index], far[index], color);
+        }
+    }
+}
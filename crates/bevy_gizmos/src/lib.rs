@@ -36,10 +36,13 @@ pub mod arcs;
 pub mod arrows;
 pub mod circles;
 pub mod config;
+pub mod curves;
+pub mod frustum;
 pub mod gizmos;
 pub mod grid;
 pub mod primitives;
 pub mod rounded_box;
+pub mod skeleton;
 
 #[cfg(feature = "bevy_pbr")]
 pub mod light;
@@ -60,6 +63,7 @@ pub mod prelude {
         },
         gizmos::Gizmos,
         primitives::{dim2::GizmoPrimitive2d, dim3::GizmoPrimitive3d},
+        skeleton::{ShowSkeletonGizmo, SkeletonGizmoConfigGroup},
         AppGizmoBuilder,
     };
 
@@ -104,6 +108,7 @@ use config::{
 use gizmos::{GizmoStorage, Swap};
 #[cfg(feature = "bevy_pbr")]
 use light::LightGizmoPlugin;
+use skeleton::SkeletonGizmoPlugin;
 use std::{any::TypeId, mem};
 
 const LINE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(7414812689238026784);
@@ -138,7 +143,7 @@ impl Plugin for GizmoPlugin {
             .init_resource::<LineGizmoHandles>()
             // We insert the Resource GizmoConfigStore into the world implicitly here if it does not exist.
             .init_gizmo_group::<DefaultGizmoConfigGroup>()
-            .add_plugins(AabbGizmoPlugin);
+            .add_plugins((AabbGizmoPlugin, SkeletonGizmoPlugin));
 
         #[cfg(feature = "bevy_pbr")]
         app.add_plugins(LightGizmoPlugin);
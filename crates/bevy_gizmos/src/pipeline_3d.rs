@@ -8,7 +8,7 @@ use crate::{
 use bevy_app::{App, Plugin};
 use bevy_asset::Handle;
 use bevy_core_pipeline::{
-    core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT},
+    core_3d::{CameraStencil, Transparent3d},
     prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
 };
 
@@ -145,7 +145,7 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             layout,
             primitive: PrimitiveState::default(),
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: key.view_key.depth_format(),
                 depth_write_enabled: true,
                 depth_compare: CompareFunction::Greater,
                 stencil: StencilState::default(),
@@ -243,7 +243,7 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             layout,
             primitive: PrimitiveState::default(),
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: key.view_key.depth_format(),
                 depth_write_enabled: true,
                 depth_compare: CompareFunction::Greater,
                 stencil: StencilState::default(),
@@ -291,6 +291,7 @@ fn queue_line_gizmos_3d(
             Has<DepthPrepass>,
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
+            Has<CameraStencil>,
         ),
     )>,
 ) {
@@ -300,7 +301,7 @@ fn queue_line_gizmos_3d(
         view,
         mut transparent_phase,
         render_layers,
-        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass, stencil),
     ) in &mut views
     {
         let render_layers = render_layers.unwrap_or_default();
@@ -324,6 +325,10 @@ fn queue_line_gizmos_3d(
             view_key |= MeshPipelineKey::DEFERRED_PREPASS;
         }
 
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
         for (entity, handle, config) in &line_gizmos {
             if !config.render_layers.intersects(render_layers) {
                 continue;
@@ -374,6 +379,7 @@ fn queue_line_joint_gizmos_3d(
             Has<DepthPrepass>,
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
+            Has<CameraStencil>,
         ),
     )>,
 ) {
@@ -386,7 +392,7 @@ fn queue_line_joint_gizmos_3d(
         view,
         mut transparent_phase,
         render_layers,
-        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass, stencil),
     ) in &mut views
     {
         let render_layers = render_layers.unwrap_or_default();
@@ -410,6 +416,10 @@ fn queue_line_joint_gizmos_3d(
             view_key |= MeshPipelineKey::DEFERRED_PREPASS;
         }
 
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
         for (entity, handle, config) in &line_gizmos {
             if !config.render_layers.intersects(render_layers) {
                 continue;
@@ -0,0 +1,128 @@
+//! A module adding debug visualization of the skeletons of [`SkinnedMesh`]es.
+
+use crate as bevy_gizmos;
+
+use bevy_app::{Plugin, PostUpdate};
+use bevy_color::{Color, Oklcha};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::Without,
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Query, Res},
+};
+use bevy_hierarchy::Parent;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::mesh::skinning::SkinnedMesh;
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+
+use crate::{
+    config::{GizmoConfigGroup, GizmoConfigStore},
+    gizmos::Gizmos,
+    AppGizmoBuilder,
+};
+
+/// A [`Plugin`] that provides visualization of the skeletons of [`SkinnedMesh`]es for debugging.
+pub struct SkeletonGizmoPlugin;
+
+impl Plugin for SkeletonGizmoPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.register_type::<SkeletonGizmoConfigGroup>()
+            .init_gizmo_group::<SkeletonGizmoConfigGroup>()
+            .add_systems(
+                PostUpdate,
+                (
+                    draw_skeletons,
+                    draw_all_skeletons.run_if(|config: Res<GizmoConfigStore>| {
+                        config.config::<SkeletonGizmoConfigGroup>().1.draw_all
+                    }),
+                )
+                    .after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// The [`GizmoConfigGroup`] used for debug visualizations of the skeletons of [`SkinnedMesh`]es.
+///
+/// Each joint is linked by a line to its parent, if that parent is a joint of the same skeleton.
+#[derive(Clone, Default, Reflect, GizmoConfigGroup)]
+pub struct SkeletonGizmoConfigGroup {
+    /// Draws all skeletons in the scene when set to `true`.
+    ///
+    /// To draw a specific entity's skeleton, you can add the [`ShowSkeletonGizmo`] component.
+    ///
+    /// Defaults to `false`.
+    pub draw_all: bool,
+    /// The default color for skeleton gizmos.
+    ///
+    /// A random color is chosen per skeleton if `None`.
+    ///
+    /// Defaults to `None`.
+    pub default_color: Option<Color>,
+}
+
+/// Add this [`Component`] to an entity to draw the skeleton of its [`SkinnedMesh`] component.
+#[derive(Component, Reflect, Default, Debug)]
+#[reflect(Component, Default)]
+pub struct ShowSkeletonGizmo {
+    /// The color of the skeleton.
+    ///
+    /// The default color from the [`SkeletonGizmoConfigGroup`] config is used if `None`,
+    pub color: Option<Color>,
+}
+
+fn draw_skeletons(
+    query: Query<(Entity, &SkinnedMesh, &ShowSkeletonGizmo)>,
+    joints: Query<(&GlobalTransform, Option<&Parent>)>,
+    mut gizmos: Gizmos<SkeletonGizmoConfigGroup>,
+) {
+    for (entity, skinned_mesh, gizmo) in &query {
+        let color = gizmo
+            .color
+            .or(gizmos.config_ext.default_color)
+            .unwrap_or_else(|| color_from_entity(entity));
+        draw_skeleton(skinned_mesh, &joints, &mut gizmos, color);
+    }
+}
+
+fn draw_all_skeletons(
+    query: Query<(Entity, &SkinnedMesh), Without<ShowSkeletonGizmo>>,
+    joints: Query<(&GlobalTransform, Option<&Parent>)>,
+    mut gizmos: Gizmos<SkeletonGizmoConfigGroup>,
+) {
+    for (entity, skinned_mesh) in &query {
+        let color = gizmos
+            .config_ext
+            .default_color
+            .unwrap_or_else(|| color_from_entity(entity));
+        draw_skeleton(skinned_mesh, &joints, &mut gizmos, color);
+    }
+}
+
+fn draw_skeleton(
+    skinned_mesh: &SkinnedMesh,
+    joints: &Query<(&GlobalTransform, Option<&Parent>)>,
+    gizmos: &mut Gizmos<SkeletonGizmoConfigGroup>,
+    color: Color,
+) {
+    for &joint in &skinned_mesh.joints {
+        let Ok((transform, Some(parent))) = joints.get(joint) else {
+            continue;
+        };
+        if !skinned_mesh.joints.contains(&parent.get()) {
+            continue;
+        }
+        if let Ok((parent_transform, _)) = joints.get(parent.get()) {
+            gizmos.line(
+                parent_transform.translation(),
+                transform.translation(),
+                color,
+            );
+        }
+    }
+}
+
+fn color_from_entity(entity: Entity) -> Color {
+    Oklcha::sequential_dispersed(entity.index()).into()
+}
@@ -4,6 +4,7 @@ use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityMapper, MapEntities},
     prelude::FromWorld,
+    relationship::Relationship,
     world::World,
 };
 use core::slice;
@@ -36,6 +37,14 @@ impl MapEntities for Children {
     }
 }
 
+/// Walked by [`Related<Children>`](bevy_ecs::relationship::Related) to visit the descendants of
+/// an entity, in depth-first order.
+impl Relationship for Children {
+    fn related(&self) -> &[Entity] {
+        &self.0
+    }
+}
+
 // TODO: We need to impl either FromWorld or Default so Children can be registered as Reflect.
 // This is because Reflect deserialize by creating an instance and apply a patch on top.
 // However Children should only ever be set with a real user-defined entities. Its worth looking
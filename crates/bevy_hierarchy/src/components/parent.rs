@@ -3,6 +3,7 @@ use bevy_ecs::reflect::{ReflectComponent, ReflectMapEntities};
 use bevy_ecs::{
     component::Component,
     entity::{Entity, EntityMapper, MapEntities},
+    relationship::Relationship,
     world::{FromWorld, World},
 };
 use std::ops::Deref;
@@ -55,6 +56,14 @@ impl FromWorld for Parent {
     }
 }
 
+/// Walked by [`Related<Parent>`](bevy_ecs::relationship::Related) to visit the ancestors of an
+/// entity, from its parent to the root.
+impl Relationship for Parent {
+    fn related(&self) -> &[Entity] {
+        self.as_slice()
+    }
+}
+
 impl MapEntities for Parent {
     fn map_entities<M: EntityMapper>(&mut self, entity_mapper: &mut M) {
         self.0 = entity_mapper.map_entity(self.0);
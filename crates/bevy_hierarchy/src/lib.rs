@@ -70,6 +70,11 @@ pub use valid_parent_check_plugin::*;
 mod query_extension;
 pub use query_extension::*;
 
+#[cfg(feature = "bevy_app")]
+mod world_integrity_plugin;
+#[cfg(feature = "bevy_app")]
+pub use world_integrity_plugin::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
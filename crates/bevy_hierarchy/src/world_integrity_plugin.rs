@@ -0,0 +1,417 @@
+use std::any::TypeId;
+
+use crate::{Children, Parent};
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    prelude::*,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
+};
+use bevy_reflect::{Reflect, ReflectRef, TypeRegistry};
+use bevy_utils::{tracing::warn, HashSet};
+
+/// Periodically scans the world for references to despawned entities, and reports them with
+/// [`WorldIntegrityIssue`] events.
+///
+/// The scan checks:
+/// - the [`Parent`] and [`Children`] of every entity, for despawned or inconsistent relatives,
+/// - every [`Entity`] found in the fields of the components registered with
+///   [`with_component`](Self::with_component), through their [`ReflectComponent`] type data,
+/// - every [`Entity`] found in the fields of resources registered with [`ReflectResource`] type
+///   data, if [`check_resources`](Self::check_resources) is enabled.
+///
+/// The number of issues found by the last scan is kept in the [`WorldIntegrityReport`] resource.
+///
+/// This is a debugging aid: scanning reflects every checked value, which is slow in large worlds.
+pub struct WorldIntegrityPlugin {
+    /// The number of frames between two scans.
+    pub interval: u32,
+    /// Whether to scan resources for references to despawned entities.
+    pub check_resources: bool,
+    /// Whether to log a warning for each issue found.
+    pub log_issues: bool,
+    components: HashSet<TypeId>,
+}
+
+impl Default for WorldIntegrityPlugin {
+    fn default() -> Self {
+        Self {
+            interval: 60,
+            check_resources: true,
+            log_issues: true,
+            components: HashSet::default(),
+        }
+    }
+}
+
+impl WorldIntegrityPlugin {
+    /// Adds the component `C` to the components scanned for references to despawned entities.
+    ///
+    /// `C` must be registered with [`ReflectComponent`] type data.
+    #[must_use]
+    pub fn with_component<C: Component>(mut self) -> Self {
+        self.components.insert(TypeId::of::<C>());
+        self
+    }
+}
+
+impl Plugin for WorldIntegrityPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WorldIntegrityChecks {
+            interval: self.interval.max(1),
+            check_resources: self.check_resources,
+            log_issues: self.log_issues,
+            components: self.components.clone(),
+            frames_until_scan: 0,
+        })
+        .init_resource::<WorldIntegrityReport>()
+        .add_event::<WorldIntegrityIssue>()
+        .add_systems(Last, check_world_integrity);
+    }
+}
+
+/// The configuration of the [`WorldIntegrityPlugin`].
+#[derive(Resource, Debug)]
+pub struct WorldIntegrityChecks {
+    /// The number of frames between two scans.
+    pub interval: u32,
+    /// Whether to scan resources for references to despawned entities.
+    pub check_resources: bool,
+    /// Whether to log a warning for each issue found.
+    pub log_issues: bool,
+    components: HashSet<TypeId>,
+    frames_until_scan: u32,
+}
+
+impl WorldIntegrityChecks {
+    /// Adds the component `C` to the components scanned for references to despawned entities.
+    ///
+    /// `C` must be registered with [`ReflectComponent`] type data.
+    pub fn check_component<C: Component>(&mut self) -> &mut Self {
+        self.components.insert(TypeId::of::<C>());
+        self
+    }
+
+    /// Requests a scan at the end of the current frame.
+    pub fn scan_now(&mut self) {
+        self.frames_until_scan = 0;
+    }
+}
+
+/// A problem found by the [`WorldIntegrityPlugin`].
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub enum WorldIntegrityIssue {
+    /// `child` has a [`Parent`] that was despawned.
+    OrphanedChild {
+        /// The entity with the [`Parent`] component.
+        child: Entity,
+        /// The despawned parent.
+        parent: Entity,
+    },
+    /// `parent` has a despawned entity in its [`Children`].
+    DanglingChild {
+        /// The entity with the [`Children`] component.
+        parent: Entity,
+        /// The despawned child.
+        child: Entity,
+    },
+    /// `child` has `parent` as its [`Parent`], but isn't part of its [`Children`].
+    MismatchedParent {
+        /// The entity with the [`Parent`] component.
+        child: Entity,
+        /// The parent not listing `child`.
+        parent: Entity,
+    },
+    /// A component of `entity` references the despawned entity `target`.
+    DanglingComponentReference {
+        /// The entity holding the component.
+        entity: Entity,
+        /// The type path of the component.
+        component: &'static str,
+        /// The despawned entity.
+        target: Entity,
+    },
+    /// A resource references the despawned entity `target`.
+    DanglingResourceReference {
+        /// The type path of the resource.
+        resource: &'static str,
+        /// The despawned entity.
+        target: Entity,
+    },
+}
+
+/// The number of issues of each kind found by the last scan of the [`WorldIntegrityPlugin`].
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorldIntegrityReport {
+    /// The number of scans run so far.
+    pub scans: u32,
+    /// See [`WorldIntegrityIssue::OrphanedChild`].
+    pub orphaned_children: usize,
+    /// See [`WorldIntegrityIssue::DanglingChild`].
+    pub dangling_children: usize,
+    /// See [`WorldIntegrityIssue::MismatchedParent`].
+    pub mismatched_parents: usize,
+    /// See [`WorldIntegrityIssue::DanglingComponentReference`].
+    pub dangling_component_references: usize,
+    /// See [`WorldIntegrityIssue::DanglingResourceReference`].
+    pub dangling_resource_references: usize,
+}
+
+impl WorldIntegrityReport {
+    /// Returns the total number of issues found by the last scan.
+    pub fn total(&self) -> usize {
+        self.orphaned_children
+            + self.dangling_children
+            + self.mismatched_parents
+            + self.dangling_component_references
+            + self.dangling_resource_references
+    }
+
+    fn count(&mut self, issue: &WorldIntegrityIssue) {
+        match issue {
+            WorldIntegrityIssue::OrphanedChild { .. } => self.orphaned_children += 1,
+            WorldIntegrityIssue::DanglingChild { .. } => self.dangling_children += 1,
+            WorldIntegrityIssue::MismatchedParent { .. } => self.mismatched_parents += 1,
+            WorldIntegrityIssue::DanglingComponentReference { .. } => {
+                self.dangling_component_references += 1;
+            }
+            WorldIntegrityIssue::DanglingResourceReference { .. } => {
+                self.dangling_resource_references += 1;
+            }
+        }
+    }
+}
+
+/// Scans the world when the interval configured in [`WorldIntegrityChecks`] has elapsed.
+pub fn check_world_integrity(world: &mut World) {
+    let mut checks = world.resource_mut::<WorldIntegrityChecks>();
+    if checks.frames_until_scan > 0 {
+        checks.frames_until_scan -= 1;
+        return;
+    }
+    checks.frames_until_scan = checks.interval.saturating_sub(1);
+    let check_resources = checks.check_resources;
+    let log_issues = checks.log_issues;
+    let components: Vec<TypeId> = checks.components.iter().copied().collect();
+
+    let mut issues = check_hierarchy(world);
+    if let Some(registry) = world.get_resource::<AppTypeRegistry>() {
+        let registry = registry.read();
+        check_components(world, &registry, &components, &mut issues);
+        if check_resources {
+            check_resources_references(world, &registry, &mut issues);
+        }
+    }
+
+    let mut report = WorldIntegrityReport {
+        scans: world.resource::<WorldIntegrityReport>().scans + 1,
+        ..Default::default()
+    };
+    for issue in &issues {
+        report.count(issue);
+        if log_issues {
+            warn!("World integrity issue: {issue:?}");
+        }
+    }
+    world.insert_resource(report);
+    world.send_event_batch(issues);
+}
+
+fn check_hierarchy(world: &mut World) -> Vec<WorldIntegrityIssue> {
+    let mut issues = Vec::new();
+    let mut parents = world.query::<(Entity, &Parent)>();
+    for (child, parent) in parents.iter(world) {
+        let parent = parent.get();
+        match world.get_entity(parent) {
+            None => issues.push(WorldIntegrityIssue::OrphanedChild { child, parent }),
+            Some(parent_ref) => {
+                let listed = parent_ref
+                    .get::<Children>()
+                    .is_some_and(|children| children.contains(&child));
+                if !listed {
+                    issues.push(WorldIntegrityIssue::MismatchedParent { child, parent });
+                }
+            }
+        }
+    }
+    let mut children = world.query::<(Entity, &Children)>();
+    for (parent, children) in children.iter(world) {
+        for &child in children {
+            if world.get_entity(child).is_none() {
+                issues.push(WorldIntegrityIssue::DanglingChild { parent, child });
+            }
+        }
+    }
+    issues
+}
+
+fn check_components(
+    world: &World,
+    registry: &TypeRegistry,
+    components: &[TypeId],
+    issues: &mut Vec<WorldIntegrityIssue>,
+) {
+    for &type_id in components {
+        let (Some(registration), Some(component_id)) =
+            (registry.get(type_id), world.components().get_id(type_id))
+        else {
+            continue;
+        };
+        let Some(reflect_component) = registration.data::<ReflectComponent>() else {
+            warn!(
+                "{} is checked by the WorldIntegrityPlugin but isn't registered with ReflectComponent",
+                registration.type_info().type_path()
+            );
+            continue;
+        };
+        let component = registration.type_info().type_path();
+        for archetype in world
+            .archetypes()
+            .iter()
+            .filter(|archetype| archetype.contains(component_id))
+        {
+            for archetype_entity in archetype.entities() {
+                let entity = archetype_entity.id();
+                let Some(value) = reflect_component.reflect(world.entity(entity)) else {
+                    continue;
+                };
+                visit_entities(value, &mut |target| {
+                    if target != Entity::PLACEHOLDER && world.get_entity(target).is_none() {
+                        issues.push(WorldIntegrityIssue::DanglingComponentReference {
+                            entity,
+                            component,
+                            target,
+                        });
+                    }
+                });
+            }
+        }
+    }
+}
+
+fn check_resources_references(
+    world: &World,
+    registry: &TypeRegistry,
+    issues: &mut Vec<WorldIntegrityIssue>,
+) {
+    for (component_id, _) in world.storages().resources.iter() {
+        let Some(registration) = world
+            .components()
+            .get_info(component_id)
+            .and_then(|info| info.type_id())
+            .and_then(|type_id| registry.get(type_id))
+        else {
+            continue;
+        };
+        let Some(value) = registration
+            .data::<ReflectResource>()
+            .and_then(|reflect_resource| reflect_resource.reflect(world))
+        else {
+            continue;
+        };
+        let resource = registration.type_info().type_path();
+        visit_entities(value, &mut |target| {
+            if target != Entity::PLACEHOLDER && world.get_entity(target).is_none() {
+                issues.push(WorldIntegrityIssue::DanglingResourceReference { resource, target });
+            }
+        });
+    }
+}
+
+/// Calls `f` with every [`Entity`] found in `value` and its fields, recursively.
+fn visit_entities(value: &dyn Reflect, f: &mut impl FnMut(Entity)) {
+    if let Some(entity) = value.downcast_ref::<Entity>() {
+        f(*entity);
+        return;
+    }
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value
+            .iter_fields()
+            .for_each(|field| visit_entities(field, f)),
+        ReflectRef::TupleStruct(value) => {
+            value
+                .iter_fields()
+                .for_each(|field| visit_entities(field, f));
+        }
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .for_each(|field| visit_entities(field, f)),
+        ReflectRef::List(value) => value.iter().for_each(|item| visit_entities(item, f)),
+        ReflectRef::Array(value) => value.iter().for_each(|item| visit_entities(item, f)),
+        ReflectRef::Map(value) => value.iter().for_each(|(key, item)| {
+            visit_entities(key, f);
+            visit_entities(item, f);
+        }),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .for_each(|field| visit_entities(field.value(), f)),
+        ReflectRef::Value(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BuildWorldChildren;
+    use bevy_reflect::TypePath;
+
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Target(Option<Entity>);
+
+    #[derive(Resource, Reflect)]
+    #[reflect(Resource)]
+    struct Focus {
+        entities: Vec<Entity>,
+    }
+
+    #[test]
+    fn reports_dangling_references() {
+        let mut app = App::new();
+        app.register_type::<Target>()
+            .register_type::<Focus>()
+            .add_plugins(
+                WorldIntegrityPlugin {
+                    interval: 1,
+                    log_issues: false,
+                    ..Default::default()
+                }
+                .with_component::<Target>(),
+            );
+
+        let world = app.world_mut();
+        let gone = world.spawn_empty().id();
+        let alive = world.spawn_empty().id();
+        let parent = world.spawn_empty().id();
+        let child = world.spawn_empty().set_parent(parent).id();
+        let holder = world.spawn(Target(Some(gone))).id();
+        world.spawn(Target(Some(alive)));
+        world.insert_resource(Focus {
+            entities: vec![alive, gone],
+        });
+        world.despawn(gone);
+        world.despawn(parent);
+
+        app.update();
+
+        let events = app.world().resource::<Events<WorldIntegrityIssue>>();
+        let issues: Vec<_> = events.iter_current_update_events().cloned().collect();
+        assert!(issues.contains(&WorldIntegrityIssue::OrphanedChild { child, parent }));
+        assert!(
+            issues.contains(&WorldIntegrityIssue::DanglingComponentReference {
+                entity: holder,
+                component: Target::type_path(),
+                target: gone,
+            })
+        );
+        assert!(
+            issues.contains(&WorldIntegrityIssue::DanglingResourceReference {
+                resource: Focus::type_path(),
+                target: gone,
+            })
+        );
+
+        let report = app.world().resource::<WorldIntegrityReport>();
+        assert_eq!(report.scans, 1);
+        assert_eq!(report.total(), 3);
+    }
+}
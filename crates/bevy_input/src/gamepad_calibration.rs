@@ -0,0 +1,376 @@
+//! Per-device calibration of gamepad axes.
+//!
+//! Worn or cheap gamepads often report sticks that don't rest at zero or don't reach the ends
+//! of their range. A [`GamepadCalibration`] maps the raw values of such a device to the `[-1, 1]`
+//! range, before the [`AxisSettings`](crate::gamepad::AxisSettings) filter them. The calibrations
+//! of every known device are stored in the [`GamepadCalibrations`] resource, keyed by device
+//! name so that they survive reconnections and restarts, and are applied by the gamepad backend.
+//!
+//! Calibrations can be measured with a [`GamepadCalibrationWizard`].
+
+use crate::gamepad::{Gamepad, GamepadAxisType};
+use bevy_ecs::system::Resource;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::HashMap;
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The calibration of a single gamepad axis.
+///
+/// Raw values between `min` and `center` are mapped to `[-1, 0]`, and values between `center`
+/// and `max` to `[0, 1]`. Mapped values closer to zero than `deadzone` are then rounded to zero,
+/// with the rest of the range stretched to still reach `-1` and `1`.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct AxisCalibration {
+    /// The raw value of the axis pushed to its lowest.
+    pub min: f32,
+    /// The raw value of the axis at rest.
+    pub center: f32,
+    /// The raw value of the axis pushed to its highest.
+    pub max: f32,
+    /// The distance from the center under which the axis is considered at rest, between `0.0`
+    /// and `1.0`.
+    pub deadzone: f32,
+    /// Whether the direction of the axis is flipped, such as for an inverted look axis.
+    pub inverted: bool,
+}
+
+impl Default for AxisCalibration {
+    fn default() -> Self {
+        Self {
+            min: -1.0,
+            center: 0.0,
+            max: 1.0,
+            deadzone: 0.0,
+            inverted: false,
+        }
+    }
+}
+
+impl AxisCalibration {
+    /// Maps a raw value of the axis to the `[-1, 1]` range.
+    pub fn apply(&self, raw_value: f32) -> f32 {
+        let offset = raw_value - self.center;
+        let extent = if offset >= 0.0 {
+            self.max - self.center
+        } else {
+            self.center - self.min
+        };
+        let value = if extent > 0.0 {
+            (offset / extent).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        };
+
+        let deadzone = self.deadzone.clamp(0.0, 1.0);
+        let value = if value.abs() <= deadzone {
+            0.0
+        } else {
+            value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+        };
+
+        if self.inverted {
+            -value
+        } else {
+            value
+        }
+    }
+}
+
+/// The calibration of the axes of a gamepad.
+///
+/// Axes without a calibration report their raw values.
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadCalibration {
+    /// The calibration of each calibrated axis.
+    pub axes: HashMap<GamepadAxisType, AxisCalibration>,
+}
+
+impl GamepadCalibration {
+    /// Maps a raw value of the `axis_type` to the `[-1, 1]` range.
+    pub fn apply(&self, axis_type: GamepadAxisType, raw_value: f32) -> f32 {
+        self.axes
+            .get(&axis_type)
+            .map_or(raw_value, |calibration| calibration.apply(raw_value))
+    }
+}
+
+/// The [`GamepadCalibration`]s of every known gamepad, keyed by the name of the device.
+///
+/// Gamepad IDs change across reconnections and restarts, while the name reported by the OS
+/// doesn't, see [`Gamepads::name`](crate::gamepad::Gamepads::name). This means that identical
+/// devices share their calibration.
+///
+/// With the `serialize` feature, this resource can be saved and loaded along with the other
+/// settings of the app, to keep calibrations across sessions.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct GamepadCalibrations {
+    /// The calibration of each device, keyed by name.
+    pub devices: HashMap<String, GamepadCalibration>,
+}
+
+impl GamepadCalibrations {
+    /// Returns the calibration of the device named `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&GamepadCalibration> {
+        self.devices.get(name)
+    }
+
+    /// Sets the calibration of the device named `name`, returning its previous calibration.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        calibration: GamepadCalibration,
+    ) -> Option<GamepadCalibration> {
+        self.devices.insert(name.into(), calibration)
+    }
+
+    /// Removes the calibration of the device named `name`, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<GamepadCalibration> {
+        self.devices.remove(name)
+    }
+
+    /// Maps a raw value of the `axis_type` of the device named `name` to the `[-1, 1]` range.
+    pub fn apply(&self, name: &str, axis_type: GamepadAxisType, raw_value: f32) -> f32 {
+        self.get(name).map_or(raw_value, |calibration| {
+            calibration.apply(axis_type, raw_value)
+        })
+    }
+}
+
+/// A step of a [`GamepadCalibrationWizard`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, Default, PartialEq, Hash)]
+pub enum CalibrationStep {
+    /// The player leaves every stick and trigger at rest, to measure their center and noise.
+    #[default]
+    Center,
+    /// The player moves every stick in circles and presses the triggers fully, to measure their
+    /// range.
+    Range,
+}
+
+/// The samples of an axis taken by a [`GamepadCalibrationWizard`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+struct AxisSamples {
+    center_sum: f32,
+    center_count: u32,
+    center_min: f32,
+    center_max: f32,
+    min: f32,
+    max: f32,
+}
+
+impl Default for AxisSamples {
+    fn default() -> Self {
+        Self {
+            center_sum: 0.0,
+            center_count: 0,
+            center_min: f32::INFINITY,
+            center_max: f32::NEG_INFINITY,
+            min: f32::INFINITY,
+            max: f32::NEG_INFINITY,
+        }
+    }
+}
+
+/// Measures the [`GamepadCalibration`] of a gamepad from its raw values.
+///
+/// Insert this resource to start calibrating a gamepad: the gamepad backend then samples its raw
+/// axis values. Guide the player through each [`CalibrationStep`], calling
+/// [`next_step`](Self::next_step) when they are done with one, then store the result of
+/// [`finish`](Self::finish) in the [`GamepadCalibrations`] and remove this resource.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_input::{
+/// #     gamepad::Gamepads,
+/// #     gamepad_calibration::{CalibrationStep, GamepadCalibrationWizard, GamepadCalibrations},
+/// # };
+/// fn calibration_wizard(
+///     mut commands: Commands,
+///     mut wizard: ResMut<GamepadCalibrationWizard>,
+///     mut calibrations: ResMut<GamepadCalibrations>,
+///     gamepads: Res<Gamepads>,
+/// ) {
+///     // Called when the player presses "Continue" in the wizard UI.
+///     match wizard.step() {
+///         CalibrationStep::Center => wizard.next_step(),
+///         CalibrationStep::Range => {
+///             if let Some(name) = gamepads.name(wizard.gamepad()) {
+///                 calibrations.insert(name, wizard.finish());
+///             }
+///             commands.remove_resource::<GamepadCalibrationWizard>();
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(calibration_wizard);
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Reflect)]
+pub struct GamepadCalibrationWizard {
+    gamepad: Gamepad,
+    step: CalibrationStep,
+    samples: HashMap<GamepadAxisType, AxisSamples>,
+}
+
+impl GamepadCalibrationWizard {
+    /// Starts calibrating the `gamepad`.
+    pub fn new(gamepad: Gamepad) -> Self {
+        Self {
+            gamepad,
+            step: CalibrationStep::Center,
+            samples: HashMap::default(),
+        }
+    }
+
+    /// Returns the gamepad being calibrated.
+    pub fn gamepad(&self) -> Gamepad {
+        self.gamepad
+    }
+
+    /// Returns the current step of the calibration.
+    pub fn step(&self) -> CalibrationStep {
+        self.step
+    }
+
+    /// Moves on to the next step of the calibration.
+    pub fn next_step(&mut self) {
+        self.step = CalibrationStep::Range;
+    }
+
+    /// Records a raw value of an axis, called by the gamepad backend.
+    ///
+    /// Values of other gamepads than the one being calibrated are ignored.
+    pub fn sample(&mut self, gamepad: Gamepad, axis_type: GamepadAxisType, raw_value: f32) {
+        if gamepad != self.gamepad {
+            return;
+        }
+        let samples = self.samples.entry(axis_type).or_default();
+        match self.step {
+            CalibrationStep::Center => {
+                samples.center_sum += raw_value;
+                samples.center_count += 1;
+                samples.center_min = samples.center_min.min(raw_value);
+                samples.center_max = samples.center_max.max(raw_value);
+            }
+            CalibrationStep::Range => {
+                samples.min = samples.min.min(raw_value);
+                samples.max = samples.max.max(raw_value);
+            }
+        }
+    }
+
+    /// Returns the calibration measured so far.
+    ///
+    /// Axes that weren't moved during the [`CalibrationStep::Range`] keep the default range,
+    /// and axes that didn't report any value aren't calibrated.
+    pub fn finish(&self) -> GamepadCalibration {
+        let axes = self
+            .samples
+            .iter()
+            .map(|(&axis_type, samples)| {
+                let default = AxisCalibration::default();
+                let center = if samples.center_count > 0 {
+                    samples.center_sum / samples.center_count as f32
+                } else {
+                    default.center
+                };
+                let min = if samples.min < center {
+                    samples.min
+                } else {
+                    default.min.min(center)
+                };
+                let max = if samples.max > center {
+                    samples.max
+                } else {
+                    default.max.max(center)
+                };
+                // The noise at rest, relative to the range on each side of the center.
+                let deadzone = if samples.center_count > 0 {
+                    ((samples.center_max - center) / (max - center))
+                        .max((center - samples.center_min) / (center - min))
+                        .clamp(0.0, 1.0)
+                } else {
+                    default.deadzone
+                };
+                let calibration = AxisCalibration {
+                    min,
+                    center,
+                    max,
+                    deadzone,
+                    inverted: false,
+                };
+                (axis_type, calibration)
+            })
+            .collect();
+        GamepadCalibration { axes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AxisCalibration, GamepadCalibrationWizard};
+    use crate::gamepad::{Gamepad, GamepadAxisType};
+
+    #[test]
+    fn axis_calibration_apply() {
+        let calibration = AxisCalibration {
+            min: -0.8,
+            center: 0.1,
+            max: 0.9,
+            deadzone: 0.5,
+            inverted: false,
+        };
+        assert_eq!(calibration.apply(0.1), 0.0);
+        assert_eq!(calibration.apply(0.4), 0.0);
+        assert_eq!(calibration.apply(0.9), 1.0);
+        assert_eq!(calibration.apply(-1.0), -1.0);
+        assert!((calibration.apply(0.7) - 0.5).abs() < 1e-5);
+
+        let inverted = AxisCalibration {
+            inverted: true,
+            ..Default::default()
+        };
+        assert_eq!(inverted.apply(0.25), -0.25);
+    }
+
+    #[test]
+    fn calibration_wizard() {
+        let gamepad = Gamepad::new(0);
+        let mut wizard = GamepadCalibrationWizard::new(gamepad);
+        for raw_value in [0.08, 0.12, 0.1] {
+            wizard.sample(gamepad, GamepadAxisType::LeftStickX, raw_value);
+        }
+        wizard.sample(Gamepad::new(1), GamepadAxisType::LeftStickX, 0.5);
+        wizard.next_step();
+        for raw_value in [-0.9, 0.3, 0.9] {
+            wizard.sample(gamepad, GamepadAxisType::LeftStickX, raw_value);
+        }
+
+        let calibration = wizard.finish().axes[&GamepadAxisType::LeftStickX];
+        assert!((calibration.center - 0.1).abs() < 1e-5);
+        assert_eq!(calibration.min, -0.9);
+        assert_eq!(calibration.max, 0.9);
+        assert!((calibration.deadzone - 0.025).abs() < 1e-5);
+        assert_eq!(calibration.apply(0.11), 0.0);
+        assert_eq!(calibration.apply(0.9), 1.0);
+    }
+}
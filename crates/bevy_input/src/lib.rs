@@ -9,15 +9,18 @@
 //!
 //! # Supported input devices
 //!
-//! `bevy` currently supports keyboard, mouse, gamepad, and touch inputs.
+//! `bevy` currently supports keyboard, mouse, gamepad, touch and device motion sensor inputs.
 
 mod axis;
 mod button_input;
 /// Common run conditions
 pub mod common_conditions;
 pub mod gamepad;
+pub mod gamepad_calibration;
 pub mod keyboard;
 pub mod mouse;
+pub mod recording;
+pub mod sensors;
 pub mod touch;
 pub mod touchpad;
 
@@ -41,8 +44,13 @@ pub mod prelude {
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 use bevy_reflect::Reflect;
+use gamepad_calibration::{GamepadCalibrationWizard, GamepadCalibrations};
 use keyboard::{keyboard_input_system, KeyCode, KeyboardInput};
 use mouse::{mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseWheel};
+use sensors::{
+    sensor_event_system, Accelerometer, Gyroscope, HapticFeedback, Magnetometer, SensorEvent,
+    SensorSettings,
+};
 use touch::{touch_screen_input_system, TouchInput, Touches};
 use touchpad::{TouchpadMagnify, TouchpadRotate};
 
@@ -87,6 +95,7 @@ impl Plugin for InputPlugin {
             .add_event::<GamepadEvent>()
             .add_event::<GamepadRumbleRequest>()
             .init_resource::<GamepadSettings>()
+            .init_resource::<GamepadCalibrations>()
             .init_resource::<Gamepads>()
             .init_resource::<ButtonInput<GamepadButton>>()
             .init_resource::<Axis<GamepadAxis>>()
@@ -108,7 +117,15 @@ impl Plugin for InputPlugin {
             // touch
             .add_event::<TouchInput>()
             .init_resource::<Touches>()
-            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem));
+            .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystem))
+            // sensors
+            .add_event::<SensorEvent>()
+            .add_event::<HapticFeedback>()
+            .init_resource::<SensorSettings>()
+            .init_resource::<Accelerometer>()
+            .init_resource::<Gyroscope>()
+            .init_resource::<Magnetometer>()
+            .add_systems(PreUpdate, sensor_event_system.in_set(InputSystem));
 
         // Register common types
         app.register_type::<ButtonState>()
@@ -119,7 +136,15 @@ impl Plugin for InputPlugin {
             .register_type::<TouchInput>()
             .register_type::<GamepadEvent>()
             .register_type::<GamepadButtonInput>()
-            .register_type::<GamepadSettings>();
+            .register_type::<GamepadSettings>()
+            .register_type::<GamepadCalibrations>()
+            .register_type::<GamepadCalibrationWizard>()
+            .register_type::<SensorEvent>()
+            .register_type::<HapticFeedback>()
+            .register_type::<SensorSettings>()
+            .register_type::<Accelerometer>()
+            .register_type::<Gyroscope>()
+            .register_type::<Magnetometer>();
     }
 }
 
@@ -0,0 +1,420 @@
+//! Recording and playback of input events.
+//!
+//! The [`InputRecordingPlugin`] captures the raw keyboard, mouse, touchpad, touch and gamepad
+//! events of every frame into an [`InputRecording`] while the [`InputRecorder`] is active,
+//! and sends the events of an [`InputRecording`] again, frame by frame, while the [`InputPlayer`]
+//! plays it back.
+//!
+//! Replayed events are sent before the [`InputSystem`] set runs, so they update the input resources
+//! such as [`ButtonInput<KeyCode>`](crate::ButtonInput) exactly as the original events did.
+//! This makes recordings useful for automated UI tests and demo playback.
+//!
+//! With the `serialize` feature, an [`InputRecording`] can be saved to and loaded from disk with any
+//! `serde` format.
+//!
+//! Events are replayed with the same [`Entity`](bevy_ecs::entity::Entity) values they were
+//! recorded with (for example [`KeyboardInput::window`]), so recordings are best replayed in an app
+//! spawning the same windows in the same order.
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    event::{EventReader, EventWriter},
+    schedule::IntoSystemConfigs,
+    system::{ResMut, Resource, SystemParam},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+use crate::{
+    gamepad::GamepadEvent,
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    touchpad::{TouchpadMagnify, TouchpadRotate},
+    InputSystem,
+};
+
+/// Adds the [`InputRecorder`] and [`InputPlayer`] resources, used to record and replay input events.
+///
+/// Requires the [`InputPlugin`](crate::InputPlugin).
+#[derive(Default)]
+pub struct InputRecordingPlugin;
+
+impl Plugin for InputRecordingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputRecorder>()
+            .init_resource::<InputPlayer>()
+            .register_type::<RecordedInput>()
+            .register_type::<RecordedFrame>()
+            .register_type::<InputRecording>()
+            .add_systems(
+                PreUpdate,
+                (input_playback_system, input_recording_system)
+                    .chain()
+                    .before(InputSystem),
+            );
+    }
+}
+
+/// An input event captured by the [`InputRecorder`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum RecordedInput {
+    /// A [`KeyboardInput`] event.
+    Keyboard(KeyboardInput),
+    /// A [`MouseButtonInput`] event.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] event.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] event.
+    MouseWheel(MouseWheel),
+    /// A [`TouchpadMagnify`] event.
+    TouchpadMagnify(TouchpadMagnify),
+    /// A [`TouchpadRotate`] event.
+    TouchpadRotate(TouchpadRotate),
+    /// A [`TouchInput`] event.
+    Touch(TouchInput),
+    /// A [`GamepadEvent`].
+    Gamepad(GamepadEvent),
+}
+
+/// The events received during a single frame of an [`InputRecording`].
+#[derive(Debug, Clone, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct RecordedFrame {
+    /// The index of the frame, counted from the start of the recording.
+    pub frame: u32,
+    /// The events received during the frame.
+    ///
+    /// Events of the same type are stored in the order they were sent.
+    pub events: Vec<RecordedInput>,
+}
+
+/// A sequence of input events, timestamped by frame.
+#[derive(Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct InputRecording {
+    /// The frames during which events were received, in order.
+    ///
+    /// Frames without any event are omitted.
+    pub frames: Vec<RecordedFrame>,
+    /// The total number of frames covered by the recording.
+    pub frame_count: u32,
+}
+
+impl InputRecording {
+    /// Iterates over all recorded events, in the order they will be replayed.
+    pub fn events(&self) -> impl Iterator<Item = &RecordedInput> {
+        self.frames.iter().flat_map(|frame| &frame.events)
+    }
+
+    /// Returns `true` if the recording contains no events.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// A [`Resource`] controlling the recording of input events.
+///
+/// ```
+/// # use bevy_ecs::system::ResMut;
+/// # use bevy_input::{ButtonInput, keyboard::KeyCode, recording::InputRecorder};
+/// # use bevy_ecs::system::Res;
+/// fn toggle_recording(keys: Res<ButtonInput<KeyCode>>, mut recorder: ResMut<InputRecorder>) {
+///     if keys.just_pressed(KeyCode::F9) {
+///         if recorder.is_recording() {
+///             let recording = recorder.stop();
+///             // Save the recording...
+///         } else {
+///             recorder.start();
+///         }
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Default)]
+pub struct InputRecorder {
+    recording: Option<InputRecording>,
+}
+
+impl InputRecorder {
+    /// Starts a new recording, discarding the current one if any.
+    pub fn start(&mut self) {
+        self.recording = Some(InputRecording::default());
+    }
+
+    /// Stops recording and returns the recorded events, or `None` if nothing was being recorded.
+    pub fn stop(&mut self) -> Option<InputRecording> {
+        self.recording.take()
+    }
+
+    /// Returns `true` if input events are being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Returns the recording in progress.
+    pub fn recording(&self) -> Option<&InputRecording> {
+        self.recording.as_ref()
+    }
+}
+
+/// A recording being replayed by the [`InputPlayer`].
+#[derive(Debug)]
+struct Playback {
+    recording: InputRecording,
+    /// The frame of the recording that will be replayed next.
+    frame: u32,
+    /// The index of the next [`RecordedFrame`] to replay.
+    next: usize,
+}
+
+/// A [`Resource`] controlling the playback of an [`InputRecording`].
+///
+/// While playing, the events of one recorded frame are sent every frame.
+/// Input from the actual devices keeps being processed alongside replayed events.
+#[derive(Resource, Debug, Default)]
+pub struct InputPlayer {
+    playback: Option<Playback>,
+}
+
+impl InputPlayer {
+    /// Starts replaying `recording` from its first frame, replacing the current playback if any.
+    pub fn play(&mut self, recording: InputRecording) {
+        self.playback = Some(Playback {
+            recording,
+            frame: 0,
+            next: 0,
+        });
+    }
+
+    /// Stops the playback and returns the recording that was being replayed.
+    pub fn stop(&mut self) -> Option<InputRecording> {
+        self.playback.take().map(|playback| playback.recording)
+    }
+
+    /// Returns `true` if a recording is being replayed.
+    pub fn is_playing(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Returns the frame of the recording that will be replayed next.
+    pub fn current_frame(&self) -> Option<u32> {
+        self.playback.as_ref().map(|playback| playback.frame)
+    }
+}
+
+#[derive(SystemParam)]
+struct InputEventReaders<'w, 's> {
+    keyboard: EventReader<'w, 's, KeyboardInput>,
+    mouse_button: EventReader<'w, 's, MouseButtonInput>,
+    mouse_motion: EventReader<'w, 's, MouseMotion>,
+    mouse_wheel: EventReader<'w, 's, MouseWheel>,
+    touchpad_magnify: EventReader<'w, 's, TouchpadMagnify>,
+    touchpad_rotate: EventReader<'w, 's, TouchpadRotate>,
+    touch: EventReader<'w, 's, TouchInput>,
+    gamepad: EventReader<'w, 's, GamepadEvent>,
+}
+
+impl InputEventReaders<'_, '_> {
+    /// Reads all the events sent since the last call.
+    fn read(&mut self) -> Vec<RecordedInput> {
+        let mut events = Vec::new();
+        events.extend(self.keyboard.read().cloned().map(RecordedInput::Keyboard));
+        events.extend(
+            self.mouse_button
+                .read()
+                .copied()
+                .map(RecordedInput::MouseButton),
+        );
+        events.extend(
+            self.mouse_motion
+                .read()
+                .copied()
+                .map(RecordedInput::MouseMotion),
+        );
+        events.extend(
+            self.mouse_wheel
+                .read()
+                .copied()
+                .map(RecordedInput::MouseWheel),
+        );
+        events.extend(
+            self.touchpad_magnify
+                .read()
+                .copied()
+                .map(RecordedInput::TouchpadMagnify),
+        );
+        events.extend(
+            self.touchpad_rotate
+                .read()
+                .copied()
+                .map(RecordedInput::TouchpadRotate),
+        );
+        events.extend(self.touch.read().copied().map(RecordedInput::Touch));
+        events.extend(self.gamepad.read().cloned().map(RecordedInput::Gamepad));
+        events
+    }
+}
+
+#[derive(SystemParam)]
+struct InputEventWriters<'w> {
+    keyboard: EventWriter<'w, KeyboardInput>,
+    mouse_button: EventWriter<'w, MouseButtonInput>,
+    mouse_motion: EventWriter<'w, MouseMotion>,
+    mouse_wheel: EventWriter<'w, MouseWheel>,
+    touchpad_magnify: EventWriter<'w, TouchpadMagnify>,
+    touchpad_rotate: EventWriter<'w, TouchpadRotate>,
+    touch: EventWriter<'w, TouchInput>,
+    gamepad: EventWriter<'w, GamepadEvent>,
+}
+
+impl InputEventWriters<'_> {
+    fn send(&mut self, event: RecordedInput) {
+        match event {
+            RecordedInput::Keyboard(event) => {
+                self.keyboard.send(event);
+            }
+            RecordedInput::MouseButton(event) => {
+                self.mouse_button.send(event);
+            }
+            RecordedInput::MouseMotion(event) => {
+                self.mouse_motion.send(event);
+            }
+            RecordedInput::MouseWheel(event) => {
+                self.mouse_wheel.send(event);
+            }
+            RecordedInput::TouchpadMagnify(event) => {
+                self.touchpad_magnify.send(event);
+            }
+            RecordedInput::TouchpadRotate(event) => {
+                self.touchpad_rotate.send(event);
+            }
+            RecordedInput::Touch(event) => {
+                self.touch.send(event);
+            }
+            RecordedInput::Gamepad(event) => {
+                self.gamepad.send(event);
+            }
+        }
+    }
+}
+
+/// Captures the input events of the current frame while the [`InputRecorder`] is recording.
+fn input_recording_system(mut recorder: ResMut<InputRecorder>, mut readers: InputEventReaders) {
+    // Always read the events, so a recording never contains events older than its start.
+    let events = readers.read();
+    let Some(recording) = recorder.recording.as_mut() else {
+        return;
+    };
+    if !events.is_empty() {
+        recording.frames.push(RecordedFrame {
+            frame: recording.frame_count,
+            events,
+        });
+    }
+    recording.frame_count += 1;
+}
+
+/// Sends the recorded events of the current frame while the [`InputPlayer`] is playing.
+fn input_playback_system(mut player: ResMut<InputPlayer>, mut writers: InputEventWriters) {
+    let Some(playback) = player.playback.as_mut() else {
+        return;
+    };
+    while let Some(frame) = playback.recording.frames.get(playback.next) {
+        if frame.frame > playback.frame {
+            break;
+        }
+        for event in &frame.events {
+            writers.send(event.clone());
+        }
+        playback.next += 1;
+    }
+    playback.frame += 1;
+    if playback.frame >= playback.recording.frame_count {
+        player.playback = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputPlayer, InputRecorder, InputRecordingPlugin, RecordedInput};
+    use crate::{
+        keyboard::{Key, KeyCode, KeyboardInput},
+        ButtonInput, ButtonState, InputPlugin,
+    };
+    use bevy_app::App;
+    use bevy_ecs::entity::Entity;
+
+    fn key_event(state: ButtonState) -> KeyboardInput {
+        KeyboardInput {
+            key_code: KeyCode::Space,
+            logical_key: Key::Space,
+            state,
+            window: Entity::PLACEHOLDER,
+        }
+    }
+
+    fn input_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, InputRecordingPlugin));
+        app
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut app = input_app();
+        app.world_mut().resource_mut::<InputRecorder>().start();
+        app.update();
+        app.world_mut().send_event(key_event(ButtonState::Pressed));
+        app.update();
+        app.update();
+        app.world_mut().send_event(key_event(ButtonState::Released));
+        app.update();
+        let recording = app
+            .world_mut()
+            .resource_mut::<InputRecorder>()
+            .stop()
+            .unwrap();
+
+        assert_eq!(recording.frame_count, 4);
+        let frames: Vec<u32> = recording.frames.iter().map(|frame| frame.frame).collect();
+        assert_eq!(frames, [1, 3]);
+        assert_eq!(
+            recording.frames[0].events,
+            [RecordedInput::Keyboard(key_event(ButtonState::Pressed))]
+        );
+
+        let mut app = input_app();
+        app.world_mut()
+            .resource_mut::<InputPlayer>()
+            .play(recording);
+        let mut pressed = Vec::new();
+        for _ in 0..4 {
+            app.update();
+            pressed.push(
+                app.world()
+                    .resource::<ButtonInput<KeyCode>>()
+                    .pressed(KeyCode::Space),
+            );
+        }
+        assert_eq!(pressed, [false, true, true, false]);
+        assert!(!app.world().resource::<InputPlayer>().is_playing());
+    }
+}
@@ -0,0 +1,262 @@
+//! The motion sensors of mobile devices, and their haptic feedback.
+//!
+//! Platform backends send a [`SensorEvent`] for each reading of the sensors enabled in the
+//! [`SensorSettings`], which [`sensor_event_system`] stores in the [`Accelerometer`],
+//! [`Gyroscope`] and [`Magnetometer`] resources. They also play the [`HapticFeedback`] events
+//! sent by the app.
+//!
+//! Sensor readings use the coordinates of the device in its natural orientation: X points to the
+//! right of the screen, Y to its top and Z out of the screen, towards the user.
+
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    event::{Event, EventReader},
+    system::{ResMut, Resource},
+};
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_utils::Duration;
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// How often a sensor is read.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum SamplingRate {
+    /// The sensor is turned off.
+    #[default]
+    Disabled,
+    /// The sensor is read about this many times per second.
+    ///
+    /// Platforms may deliver readings faster or slower than requested.
+    Hz(f32),
+}
+
+impl SamplingRate {
+    /// Returns the time between two readings, or `None` if the sensor is disabled.
+    pub fn interval(&self) -> Option<Duration> {
+        match *self {
+            SamplingRate::Hz(hz) if hz > 0.0 => Some(Duration::from_secs_f32(hz.recip())),
+            SamplingRate::Disabled | SamplingRate::Hz(_) => None,
+        }
+    }
+}
+
+/// The sampling rates of the device sensors.
+///
+/// Sensors drain the battery, so they are all disabled by default.
+///
+/// ```
+/// # use bevy_ecs::system::ResMut;
+/// # use bevy_input::sensors::{SamplingRate, SensorSettings};
+/// fn enable_tilt_controls(mut settings: ResMut<SensorSettings>) {
+///     settings.accelerometer = SamplingRate::Hz(60.0);
+/// }
+/// # bevy_ecs::system::assert_is_system(enable_tilt_controls);
+/// ```
+#[derive(Resource, Debug, Clone, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SensorSettings {
+    /// The sampling rate of the [`Accelerometer`].
+    pub accelerometer: SamplingRate,
+    /// The sampling rate of the [`Gyroscope`].
+    pub gyroscope: SamplingRate,
+    /// The sampling rate of the [`Magnetometer`].
+    pub magnetometer: SamplingRate,
+}
+
+/// A reading of a device sensor, sent by the platform backend.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum SensorEvent {
+    /// The acceleration of the device in m/s², including gravity.
+    Accelerometer(Vec3),
+    /// The angular velocity of the device in rad/s around each axis.
+    Gyroscope(Vec3),
+    /// The ambient magnetic field in µT.
+    Magnetometer(Vec3),
+}
+
+/// The latest reading of the accelerometer.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub struct Accelerometer {
+    /// The acceleration of the device in m/s², including gravity, or `None` if the sensor wasn't
+    /// read yet.
+    ///
+    /// At rest, this points up, away from the ground.
+    pub acceleration: Option<Vec3>,
+}
+
+/// The latest reading of the gyroscope.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub struct Gyroscope {
+    /// The angular velocity of the device in rad/s around each axis, counterclockwise, or `None`
+    /// if the sensor wasn't read yet.
+    pub angular_velocity: Option<Vec3>,
+}
+
+/// The latest reading of the magnetometer, used as a compass.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub struct Magnetometer {
+    /// The ambient magnetic field in µT, or `None` if the sensor wasn't read yet.
+    pub magnetic_field: Option<Vec3>,
+}
+
+impl Magnetometer {
+    /// Returns the compass heading of the device in radians, from magnetic north to the top of
+    /// the device, clockwise.
+    ///
+    /// The `acceleration` of the device at rest, usually from the [`Accelerometer`], compensates
+    /// for the tilt of the device. Returns `None` if the magnetic field wasn't read yet, or if
+    /// the device is in free fall or close to a strong magnet.
+    pub fn heading(&self, acceleration: Vec3) -> Option<f32> {
+        let magnetic_field = self.magnetic_field?;
+        // East is perpendicular to both the magnetic field and the vertical, and north to both
+        // east and the vertical.
+        let east = magnetic_field.cross(acceleration).try_normalize()?;
+        let north = acceleration.cross(east).try_normalize()?;
+        Some(east.y.atan2(north.y).rem_euclid(std::f32::consts::TAU))
+    }
+}
+
+/// Stores the [`SensorEvent`]s in the [`Accelerometer`], [`Gyroscope`] and [`Magnetometer`]
+/// resources.
+pub fn sensor_event_system(
+    mut events: EventReader<SensorEvent>,
+    mut accelerometer: ResMut<Accelerometer>,
+    mut gyroscope: ResMut<Gyroscope>,
+    mut magnetometer: ResMut<Magnetometer>,
+) {
+    for event in events.read() {
+        match *event {
+            SensorEvent::Accelerometer(acceleration) => {
+                accelerometer.set_if_neq(Accelerometer {
+                    acceleration: Some(acceleration),
+                });
+            }
+            SensorEvent::Gyroscope(angular_velocity) => {
+                gyroscope.set_if_neq(Gyroscope {
+                    angular_velocity: Some(angular_velocity),
+                });
+            }
+            SensorEvent::Magnetometer(magnetic_field) => {
+                magnetometer.set_if_neq(Magnetometer {
+                    magnetic_field: Some(magnetic_field),
+                });
+            }
+        }
+    }
+}
+
+/// The outcome reported by a [`HapticFeedback::Notification`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Debug, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum HapticNotification {
+    /// A task succeeded.
+    Success,
+    /// A task produced a warning.
+    Warning,
+    /// A task failed.
+    Error,
+}
+
+/// A request to play haptic feedback on the device, sent by the app and played by the platform
+/// backend.
+///
+/// The patterns follow the feedback generators of iOS, and are approximated with vibrations on
+/// platforms that don't have them. Devices without haptics ignore these requests.
+///
+/// ```
+/// # use bevy_ecs::event::EventWriter;
+/// # use bevy_input::sensors::HapticFeedback;
+/// fn on_collision(mut haptics: EventWriter<HapticFeedback>) {
+///     haptics.send(HapticFeedback::Impact { intensity: 0.8 });
+/// }
+/// # bevy_ecs::system::assert_is_system(on_collision);
+/// ```
+#[derive(Event, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub enum HapticFeedback {
+    /// A short tap, like a collision, with an intensity between `0.0` and `1.0`.
+    Impact {
+        /// The intensity of the tap, between `0.0` and `1.0`.
+        intensity: f32,
+    },
+    /// A light tick when a selection changes, like when scrolling through a picker.
+    Selection,
+    /// A pattern reporting the outcome of a task.
+    Notification(HapticNotification),
+    /// A continuous vibration.
+    Vibrate {
+        /// How long the device vibrates.
+        duration: Duration,
+        /// The intensity of the vibration, between `0.0` and `1.0`.
+        intensity: f32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Magnetometer, SamplingRate};
+    use bevy_math::Vec3;
+    use bevy_utils::Duration;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn sampling_rate_interval() {
+        assert_eq!(SamplingRate::Disabled.interval(), None);
+        assert_eq!(SamplingRate::Hz(0.0).interval(), None);
+        assert_eq!(
+            SamplingRate::Hz(4.0).interval(),
+            Some(Duration::from_millis(250))
+        );
+    }
+
+    #[test]
+    fn compass_heading() {
+        // Lying flat, screen up: gravity reads along +Z, and the field points north and down.
+        let acceleration = Vec3::new(0.0, 0.0, 9.81);
+        let heading = |field: Vec3| {
+            Magnetometer {
+                magnetic_field: Some(field),
+            }
+            .heading(acceleration)
+            .unwrap()
+        };
+        assert!(heading(Vec3::new(0.0, 20.0, -40.0)).abs() < 1e-5);
+        // North is to the left of the device, so the device faces east.
+        assert!((heading(Vec3::new(-20.0, 0.0, -40.0)) - FRAC_PI_2).abs() < 1e-5);
+        assert!((heading(Vec3::new(0.0, -20.0, -40.0)) - PI).abs() < 1e-5);
+
+        assert_eq!(Magnetometer::default().heading(acceleration), None);
+    }
+}
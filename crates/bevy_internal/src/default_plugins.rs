@@ -14,6 +14,7 @@ use bevy_app::{Plugin, PluginGroup, PluginGroupBuilder};
 /// * [`WindowPlugin`](crate::window::WindowPlugin)
 /// * [`AccessibilityPlugin`](crate::a11y::AccessibilityPlugin)
 /// * [`AssetPlugin`](crate::asset::AssetPlugin) - with feature `bevy_asset`
+/// * [`ColorPalettePlugin`](crate::color::ColorPalettePlugin) - with features `bevy_asset` and `bevy_color`
 /// * [`ScenePlugin`](crate::scene::ScenePlugin) - with feature `bevy_scene`
 /// * [`WinitPlugin`](crate::winit::WinitPlugin) - with feature `bevy_winit`
 /// * [`RenderPlugin`](crate::render::RenderPlugin) - with feature `bevy_render`
@@ -61,6 +62,11 @@ impl PluginGroup for DefaultPlugins {
             group = group.add(bevy_asset::AssetPlugin::default());
         }
 
+        #[cfg(all(feature = "bevy_asset", feature = "bevy_color"))]
+        {
+            group = group.add(bevy_color::ColorPalettePlugin);
+        }
+
         #[cfg(feature = "bevy_scene")]
         {
             group = group.add(bevy_scene::ScenePlugin);
@@ -14,6 +14,8 @@ mod default_plugins;
 pub use default_plugins::*;
 
 pub use bevy_a11y as a11y;
+#[cfg(feature = "bevy_ai")]
+pub use bevy_ai as ai;
 #[cfg(feature = "bevy_animation")]
 pub use bevy_animation as animation;
 pub use bevy_app as app;
@@ -44,12 +46,16 @@ pub use bevy_log as log;
 pub use bevy_math as math;
 #[cfg(feature = "bevy_pbr")]
 pub use bevy_pbr as pbr;
+#[cfg(feature = "bevy_physics")]
+pub use bevy_physics as physics;
 pub use bevy_ptr as ptr;
 pub use bevy_reflect as reflect;
 #[cfg(feature = "bevy_render")]
 pub use bevy_render as render;
 #[cfg(feature = "bevy_scene")]
 pub use bevy_scene as scene;
+#[cfg(feature = "bevy_scripting")]
+pub use bevy_scripting as scripting;
 #[cfg(feature = "bevy_sprite")]
 pub use bevy_sprite as sprite;
 #[cfg(feature = "bevy_state")]
@@ -7,6 +7,10 @@ pub use crate::{
 
 pub use bevy_derive::{bevy_main, Deref, DerefMut};
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_ai")]
+pub use crate::ai::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_asset")]
 pub use crate::asset::prelude::*;
@@ -66,3 +70,11 @@ pub use crate::gilrs::*;
 #[doc(hidden)]
 #[cfg(feature = "bevy_state")]
 pub use crate::state::prelude::*;
+
+#[doc(hidden)]
+#[cfg(feature = "bevy_physics")]
+pub use crate::physics::prelude::*;
+
+#[doc(hidden)]
+#[cfg(feature = "bevy_scripting")]
+pub use crate::scripting::prelude::*;
@@ -18,6 +18,7 @@ mod common_traits;
 pub mod cubic_splines;
 mod direction;
 mod float_ord;
+pub mod noise;
 pub mod primitives;
 mod ray;
 mod rects;
@@ -0,0 +1,170 @@
+use super::{Noise, PerlinNoise, SimplexNoise, WorleyNoise};
+use crate::VectorSpace;
+
+/// Methods combining the noise functions of this module.
+pub trait NoiseExt: Sized {
+    /// Scales the points sampled by the noise by `frequency`.
+    fn scaled(self, frequency: f32) -> Scaled<Self> {
+        Scaled {
+            noise: self,
+            frequency,
+        }
+    }
+
+    /// Sums `octaves` layers of the noise into fractal Brownian motion.
+    fn fbm(self, octaves: u32) -> Fbm<Self> {
+        Fbm::new(self, octaves)
+    }
+
+    /// Sums `octaves` layers of the noise into ridged multifractal noise.
+    fn ridged(self, octaves: u32) -> Ridged<Self> {
+        Ridged::new(self, octaves)
+    }
+}
+
+impl NoiseExt for PerlinNoise {}
+impl NoiseExt for SimplexNoise {}
+impl NoiseExt for WorleyNoise {}
+impl<N> NoiseExt for Scaled<N> {}
+impl<N> NoiseExt for Fbm<N> {}
+impl<N> NoiseExt for Ridged<N> {}
+
+/// Samples a noise at points scaled by a frequency, created with [`NoiseExt::scaled`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scaled<N> {
+    /// The scaled noise.
+    pub noise: N,
+    /// The factor applied to the sampled points. Higher frequencies give smaller features.
+    pub frequency: f32,
+}
+
+impl<P: VectorSpace, N: Noise<P>> Noise<P> for Scaled<N> {
+    fn sample(&self, point: P) -> f32 {
+        self.noise.sample(point * self.frequency)
+    }
+}
+
+/// Fractal Brownian motion: the sum of layers, or octaves, of a noise with increasing frequency
+/// and decreasing amplitude, in the same range as the noise.
+///
+/// This adds detail to the noise, for example small bumps on the hills of a terrain.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Fbm<N> {
+    /// The layered noise.
+    pub noise: N,
+    /// The number of layers.
+    pub octaves: u32,
+    /// How much the frequency is multiplied by from one octave to the next.
+    pub lacunarity: f32,
+    /// How much the amplitude is multiplied by from one octave to the next.
+    pub gain: f32,
+}
+
+impl<N> Fbm<N> {
+    /// Layers `octaves` of `noise`, doubling the frequency and halving the amplitude with each
+    /// octave.
+    pub const fn new(noise: N, octaves: u32) -> Self {
+        Self {
+            noise,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    /// Returns this noise with the given `lacunarity`.
+    pub const fn with_lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Returns this noise with the given `gain`.
+    pub const fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+}
+
+impl<P: VectorSpace, N: Noise<P>> Noise<P> for Fbm<N> {
+    fn sample(&self, point: P) -> f32 {
+        let (mut sum, mut total_amplitude) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        for _ in 0..self.octaves {
+            sum += self.noise.sample(point * frequency) * amplitude;
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        if total_amplitude > 0.0 {
+            sum / total_amplitude
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Ridged multifractal noise: layers of a noise folded into sharp ridges, in `[0, 1]`.
+///
+/// Each octave is weighted by the previous one, so detail accumulates on the ridges, which looks
+/// like mountain ranges. The layered noise should be in `[-1, 1]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ridged<N> {
+    /// The layered noise.
+    pub noise: N,
+    /// The number of layers.
+    pub octaves: u32,
+    /// How much the frequency is multiplied by from one octave to the next.
+    pub lacunarity: f32,
+    /// How much the amplitude is multiplied by from one octave to the next.
+    pub gain: f32,
+}
+
+impl<N> Ridged<N> {
+    /// Layers `octaves` of `noise`, doubling the frequency and halving the amplitude with each
+    /// octave.
+    pub const fn new(noise: N, octaves: u32) -> Self {
+        Self {
+            noise,
+            octaves,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+
+    /// Returns this noise with the given `lacunarity`.
+    pub const fn with_lacunarity(mut self, lacunarity: f32) -> Self {
+        self.lacunarity = lacunarity;
+        self
+    }
+
+    /// Returns this noise with the given `gain`.
+    pub const fn with_gain(mut self, gain: f32) -> Self {
+        self.gain = gain;
+        self
+    }
+}
+
+impl<P: VectorSpace, N: Noise<P>> Noise<P> for Ridged<N> {
+    fn sample(&self, point: P) -> f32 {
+        let (mut sum, mut total_amplitude) = (0.0, 0.0);
+        let (mut frequency, mut amplitude) = (1.0, 1.0);
+        let mut weight = 1.0;
+        for _ in 0..self.octaves {
+            let ridge = 1.0 - self.noise.sample(point * frequency).abs().min(1.0);
+            let ridge = ridge * ridge * weight;
+            weight = ridge;
+            sum += ridge * amplitude;
+            total_amplitude += amplitude;
+            frequency *= self.lacunarity;
+            amplitude *= self.gain;
+        }
+        if total_amplitude > 0.0 {
+            sum / total_amplitude
+        } else {
+            0.0
+        }
+    }
+}
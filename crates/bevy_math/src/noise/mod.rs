@@ -0,0 +1,253 @@
+//! This module contains seeded procedural noise functions, for terrain, textures and effects.
+//!
+//! The noise functions are [`PerlinNoise`], [`SimplexNoise`] and [`WorleyNoise`], sampled in 2D
+//! or 3D through the [`Noise`] trait. They can be layered into fractal noise with [`Fbm`] and
+//! [`Ridged`]:
+//!
+//! ```
+//! # use bevy_math::{noise::*, Vec2};
+//! let terrain = PerlinNoise::new(42).scaled(0.01).fbm(5);
+//! let height = terrain.sample(Vec2::new(120.0, -40.0)) * 50.0;
+//! ```
+//!
+//! The lattice hashing only uses 32-bit integer operations, so that the `bevy_render::noise`
+//! shader import produces the same noise on the GPU for the same seed.
+
+mod fractal;
+mod perlin;
+mod simplex;
+mod worley;
+
+pub use fractal::*;
+pub use perlin::*;
+pub use simplex::*;
+pub use worley::*;
+
+use crate::{Vec2, Vec3};
+
+/// A noise function, sampled at points of type `P`.
+///
+/// Noise functions can be combined with the methods of [`NoiseExt`].
+pub trait Noise<P: Copy> {
+    /// Samples the noise at `point`.
+    fn sample(&self, point: P) -> f32;
+
+    /// Samples the noise at each of `points`, writing the values to `output`.
+    ///
+    /// Sampling many points at once keeps the loop free of dynamic dispatch, which allows the
+    /// compiler to vectorize it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` and `output` don't have the same length.
+    fn sample_batch(&self, points: &[P], output: &mut [f32]) {
+        assert_eq!(
+            points.len(),
+            output.len(),
+            "there must be as many output values as sampled points"
+        );
+        for (point, value) in points.iter().zip(output) {
+            *value = self.sample(*point);
+        }
+    }
+}
+
+/// Mixes the bits of `x`, with the "lowbias32" integer hash.
+#[inline]
+pub(crate) fn hash(mut x: u32) -> u32 {
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+/// Hashes the 2D lattice point `(x, y)`.
+#[inline]
+pub(crate) fn hash_2d(x: i32, y: i32, seed: u32) -> u32 {
+    hash(x as u32 ^ hash(y as u32 ^ hash(seed)))
+}
+
+/// Hashes the 3D lattice point `(x, y, z)`.
+#[inline]
+pub(crate) fn hash_3d(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    hash(x as u32 ^ hash_2d(y, z, seed))
+}
+
+/// Maps a hash to `[0, 1)`.
+#[inline]
+pub(crate) fn hash_to_unit(hash: u32) -> f32 {
+    (hash >> 8) as f32 / (1 << 24) as f32
+}
+
+/// The quintic smoothstep used to interpolate between lattice points.
+#[inline]
+pub(crate) fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Picks one of eight unit gradients in 2D.
+#[inline]
+pub(crate) fn gradient_2d(hash: u32) -> Vec2 {
+    use std::f32::consts::FRAC_1_SQRT_2;
+    const GRADIENTS: [Vec2; 8] = [
+        Vec2::new(1.0, 0.0),
+        Vec2::new(FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        Vec2::new(0.0, 1.0),
+        Vec2::new(-FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        Vec2::new(-1.0, 0.0),
+        Vec2::new(-FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+        Vec2::new(0.0, -1.0),
+        Vec2::new(FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+    ];
+    GRADIENTS[(hash & 7) as usize]
+}
+
+/// Picks one of the twelve gradients towards the edges of a cube in 3D.
+#[inline]
+pub(crate) fn gradient_3d(hash: u32) -> Vec3 {
+    const GRADIENTS: [Vec3; 12] = [
+        Vec3::new(1.0, 1.0, 0.0),
+        Vec3::new(-1.0, 1.0, 0.0),
+        Vec3::new(1.0, -1.0, 0.0),
+        Vec3::new(-1.0, -1.0, 0.0),
+        Vec3::new(1.0, 0.0, 1.0),
+        Vec3::new(-1.0, 0.0, 1.0),
+        Vec3::new(1.0, 0.0, -1.0),
+        Vec3::new(-1.0, 0.0, -1.0),
+        Vec3::new(0.0, 1.0, 1.0),
+        Vec3::new(0.0, -1.0, 1.0),
+        Vec3::new(0.0, 1.0, -1.0),
+        Vec3::new(0.0, -1.0, -1.0),
+    ];
+    GRADIENTS[(hash % 12) as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points_2d() -> impl Iterator<Item = Vec2> {
+        (0..1000).map(|i| {
+            let x = hash_to_unit(hash(i * 2)) * 200.0 - 100.0;
+            let y = hash_to_unit(hash(i * 2 + 1)) * 200.0 - 100.0;
+            Vec2::new(x, y)
+        })
+    }
+
+    fn points_3d() -> impl Iterator<Item = Vec3> {
+        points_2d()
+            .zip(points_2d().skip(500))
+            .map(|(a, b)| a.extend(b.x))
+    }
+
+    fn assert_noise_properties<P: Copy>(
+        noise: impl Fn(u32) -> Box<dyn Noise<P>>,
+        points: impl Iterator<Item = P>,
+        range: (f32, f32),
+        step: impl Fn(P) -> P,
+    ) {
+        let (a, b) = (noise(1), noise(2));
+        let mut differs = false;
+        for point in points {
+            let value = a.sample(point);
+            assert!(
+                (range.0..=range.1).contains(&value),
+                "{value} out of {range:?}"
+            );
+            assert_eq!(value, noise(1).sample(point), "noise must be deterministic");
+            assert!(
+                (a.sample(step(point)) - value).abs() < 0.05,
+                "noise must be continuous"
+            );
+            differs |= (b.sample(point) - value).abs() > 1e-3;
+        }
+        assert!(differs, "different seeds must give different noise");
+    }
+
+    #[test]
+    fn noise_is_seeded_continuous_and_bounded() {
+        let step_2d = |p: Vec2| p + Vec2::splat(1e-3);
+        let step_3d = |p: Vec3| p + Vec3::splat(1e-3);
+        let range = (-1.0, 1.0);
+        assert_noise_properties(
+            |s| Box::new(PerlinNoise::new(s)),
+            points_2d(),
+            range,
+            step_2d,
+        );
+        assert_noise_properties(
+            |s| Box::new(PerlinNoise::new(s)),
+            points_3d(),
+            range,
+            step_3d,
+        );
+        assert_noise_properties(
+            |s| Box::new(SimplexNoise::new(s)),
+            points_2d(),
+            range,
+            step_2d,
+        );
+        assert_noise_properties(
+            |s| Box::new(SimplexNoise::new(s)),
+            points_3d(),
+            range,
+            step_3d,
+        );
+        let range = (0.0, 1.5);
+        assert_noise_properties(
+            |s| Box::new(WorleyNoise::new(s)),
+            points_2d(),
+            range,
+            step_2d,
+        );
+        assert_noise_properties(
+            |s| Box::new(WorleyNoise::new(s)),
+            points_3d(),
+            range,
+            step_3d,
+        );
+        let range = (-1.0, 1.0);
+        assert_noise_properties(
+            |s| Box::new(PerlinNoise::new(s).fbm(4)),
+            points_2d(),
+            range,
+            step_2d,
+        );
+        let range = (0.0, 1.0);
+        assert_noise_properties(
+            |s| Box::new(SimplexNoise::new(s).ridged(4)),
+            points_3d(),
+            range,
+            step_3d,
+        );
+    }
+
+    #[test]
+    fn perlin_noise_is_zero_on_the_lattice() {
+        let noise = PerlinNoise::new(7);
+        assert_eq!(noise.sample(Vec2::new(3.0, -8.0)), 0.0);
+        assert_eq!(noise.sample(Vec3::new(-1.0, 0.0, 12.0)), 0.0);
+    }
+
+    #[test]
+    fn regular_worley_noise_measures_distance_to_cell_centers() {
+        let noise = WorleyNoise::new(3).with_jitter(0.0);
+        assert!((noise.sample(Vec2::new(0.5, 0.5))).abs() < 1e-6);
+        assert!((noise.sample(Vec2::new(1.0, 0.5)) - 0.5).abs() < 1e-6);
+        let cracks = noise.with_output(WorleyOutput::F2MinusF1);
+        assert!(cracks.sample(Vec3::new(1.0, 0.5, 0.5)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn batch_sampling_matches_single_samples() {
+        let noise = SimplexNoise::new(5).scaled(0.1).fbm(3);
+        let points: Vec<Vec2> = points_2d().take(64).collect();
+        let mut values = vec![0.0; points.len()];
+        noise.sample_batch(&points, &mut values);
+        for (point, value) in points.iter().zip(values) {
+            assert_eq!(noise.sample(*point), value);
+        }
+    }
+}
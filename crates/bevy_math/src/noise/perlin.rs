@@ -0,0 +1,61 @@
+use super::{fade, gradient_2d, gradient_3d, hash_2d, hash_3d, Noise};
+use crate::{FloatExt, Vec2, Vec3};
+
+/// Perlin gradient noise, in approximately `[-1, 1]`.
+///
+/// The noise is zero at integer coordinates, and has features about one unit wide.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct PerlinNoise {
+    /// The seed of the noise. Different seeds give unrelated noise.
+    pub seed: u32,
+}
+
+impl PerlinNoise {
+    /// Creates Perlin noise with the given `seed`.
+    pub const fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl Noise<Vec2> for PerlinNoise {
+    fn sample(&self, point: Vec2) -> f32 {
+        let cell = point.floor();
+        let offset = point - cell;
+        let (x, y) = (cell.x as i32, cell.y as i32);
+        let corner = |dx: i32, dy: i32| {
+            let hash = hash_2d(x.wrapping_add(dx), y.wrapping_add(dy), self.seed);
+            gradient_2d(hash).dot(offset - Vec2::new(dx as f32, dy as f32))
+        };
+        let (u, v) = (fade(offset.x), fade(offset.y));
+        let bottom = corner(0, 0).lerp(corner(1, 0), u);
+        let top = corner(0, 1).lerp(corner(1, 1), u);
+        // Unit gradients reach at most `sqrt(2) / 2` in 2D.
+        bottom.lerp(top, v) * std::f32::consts::SQRT_2
+    }
+}
+
+impl Noise<Vec3> for PerlinNoise {
+    fn sample(&self, point: Vec3) -> f32 {
+        let cell = point.floor();
+        let offset = point - cell;
+        let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+        let corner = |dx: i32, dy: i32, dz: i32| {
+            let hash = hash_3d(
+                x.wrapping_add(dx),
+                y.wrapping_add(dy),
+                z.wrapping_add(dz),
+                self.seed,
+            );
+            gradient_3d(hash).dot(offset - Vec3::new(dx as f32, dy as f32, dz as f32))
+        };
+        let (u, v, w) = (fade(offset.x), fade(offset.y), fade(offset.z));
+        let near = corner(0, 0, 0)
+            .lerp(corner(1, 0, 0), u)
+            .lerp(corner(0, 1, 0).lerp(corner(1, 1, 0), u), v);
+        let far = corner(0, 0, 1)
+            .lerp(corner(1, 0, 1), u)
+            .lerp(corner(0, 1, 1).lerp(corner(1, 1, 1), u), v);
+        near.lerp(far, w)
+    }
+}
@@ -0,0 +1,101 @@
+use super::{gradient_2d, gradient_3d, hash_2d, hash_3d, Noise};
+use crate::{Vec2, Vec3};
+
+/// Simplex noise, in approximately `[-1, 1]`.
+///
+/// Simplex noise looks like [`PerlinNoise`](super::PerlinNoise) with fewer directional artifacts,
+/// and is cheaper to sample in 3D.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimplexNoise {
+    /// The seed of the noise. Different seeds give unrelated noise.
+    pub seed: u32,
+}
+
+impl SimplexNoise {
+    /// Creates simplex noise with the given `seed`.
+    pub const fn new(seed: u32) -> Self {
+        Self { seed }
+    }
+}
+
+impl Noise<Vec2> for SimplexNoise {
+    fn sample(&self, point: Vec2) -> f32 {
+        // (sqrt(3) - 1) / 2 and (3 - sqrt(3)) / 6: the factors skewing the triangles of the
+        // simplex grid into squares, and back.
+        const SKEW: f32 = 0.366_025_4;
+        const UNSKEW: f32 = 0.211_324_87;
+
+        let cell = (point + Vec2::splat(point.element_sum() * SKEW)).floor();
+        let first = point - (cell - Vec2::splat(cell.element_sum() * UNSKEW));
+        let step = if first.x > first.y { Vec2::X } else { Vec2::Y };
+        let second = first - step + Vec2::splat(UNSKEW);
+        let third = first - Vec2::ONE + Vec2::splat(2.0 * UNSKEW);
+
+        let (x, y) = (cell.x as i32, cell.y as i32);
+        let corner = |offset: Vec2, corner: Vec2| {
+            let falloff = 0.5 - offset.length_squared();
+            if falloff <= 0.0 {
+                return 0.0;
+            }
+            let hash = hash_2d(
+                x.wrapping_add(corner.x as i32),
+                y.wrapping_add(corner.y as i32),
+                self.seed,
+            );
+            falloff.powi(4) * gradient_2d(hash).dot(offset)
+        };
+        (corner(first, Vec2::ZERO) + corner(second, step) + corner(third, Vec2::ONE)) * 99.2
+    }
+}
+
+impl Noise<Vec3> for SimplexNoise {
+    fn sample(&self, point: Vec3) -> f32 {
+        const SKEW: f32 = 1.0 / 3.0;
+        const UNSKEW: f32 = 1.0 / 6.0;
+
+        let cell = (point + Vec3::splat(point.element_sum() * SKEW)).floor();
+        let first = point - (cell - Vec3::splat(cell.element_sum() * UNSKEW));
+
+        // The two intermediate corners of the tetrahedron containing the point step along the
+        // largest, then the second largest, offset axes.
+        let (step_1, step_2) = if first.x >= first.y {
+            if first.y >= first.z {
+                (Vec3::X, Vec3::new(1.0, 1.0, 0.0))
+            } else if first.x >= first.z {
+                (Vec3::X, Vec3::new(1.0, 0.0, 1.0))
+            } else {
+                (Vec3::Z, Vec3::new(1.0, 0.0, 1.0))
+            }
+        } else if first.y < first.z {
+            (Vec3::Z, Vec3::new(0.0, 1.0, 1.0))
+        } else if first.x < first.z {
+            (Vec3::Y, Vec3::new(0.0, 1.0, 1.0))
+        } else {
+            (Vec3::Y, Vec3::new(1.0, 1.0, 0.0))
+        };
+        let second = first - step_1 + Vec3::splat(UNSKEW);
+        let third = first - step_2 + Vec3::splat(2.0 * UNSKEW);
+        let fourth = first - Vec3::ONE + Vec3::splat(3.0 * UNSKEW);
+
+        let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+        let corner = |offset: Vec3, corner: Vec3| {
+            let falloff = 0.6 - offset.length_squared();
+            if falloff <= 0.0 {
+                return 0.0;
+            }
+            let hash = hash_3d(
+                x.wrapping_add(corner.x as i32),
+                y.wrapping_add(corner.y as i32),
+                z.wrapping_add(corner.z as i32),
+                self.seed,
+            );
+            falloff.powi(4) * gradient_3d(hash).dot(offset)
+        };
+        (corner(first, Vec3::ZERO)
+            + corner(second, step_1)
+            + corner(third, step_2)
+            + corner(fourth, Vec3::ONE))
+            * 32.0
+    }
+}
@@ -0,0 +1,138 @@
+use super::{hash, hash_2d, hash_3d, hash_to_unit, Noise};
+use crate::{Vec2, Vec3};
+
+/// Which distance [`WorleyNoise`] returns.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum WorleyOutput {
+    /// The distance to the closest feature point, which looks like rounded cells.
+    #[default]
+    F1,
+    /// The distance to the second closest feature point.
+    F2,
+    /// The difference between the distances to the second closest and the closest feature
+    /// points, which is zero on the borders between cells and looks like cracks or veins.
+    F2MinusF1,
+}
+
+/// Worley noise, also called cellular or Voronoi noise, in approximately `[0, 1]`.
+///
+/// Each unit cell of the lattice contains one randomly placed feature point, and the noise is
+/// the distance from the sampled point to the nearby feature points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorleyNoise {
+    /// The seed of the noise. Different seeds give unrelated noise.
+    pub seed: u32,
+    /// How far feature points stray from the center of their cell, from `0.0` for a regular grid
+    /// to `1.0` anywhere in the cell.
+    pub jitter: f32,
+    /// Which distance the noise returns.
+    pub output: WorleyOutput,
+}
+
+impl Default for WorleyNoise {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl WorleyNoise {
+    /// Creates Worley noise with the given `seed`, returning the distance to the closest feature
+    /// point.
+    pub const fn new(seed: u32) -> Self {
+        Self {
+            seed,
+            jitter: 1.0,
+            output: WorleyOutput::F1,
+        }
+    }
+
+    /// Returns this noise with the given `jitter`.
+    pub const fn with_jitter(mut self, jitter: f32) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Returns this noise, returning the given distance.
+    pub const fn with_output(mut self, output: WorleyOutput) -> Self {
+        self.output = output;
+        self
+    }
+
+    fn output(&self, closest: f32, second_closest: f32) -> f32 {
+        match self.output {
+            WorleyOutput::F1 => closest,
+            WorleyOutput::F2 => second_closest,
+            WorleyOutput::F2MinusF1 => second_closest - closest,
+        }
+    }
+}
+
+/// Keeps the two smallest of the distances passed to it.
+#[derive(Clone, Copy)]
+struct ClosestTwo(f32, f32);
+
+impl ClosestTwo {
+    fn push(&mut self, distance: f32) {
+        if distance < self.0 {
+            self.1 = self.0;
+            self.0 = distance;
+        } else if distance < self.1 {
+            self.1 = distance;
+        }
+    }
+}
+
+impl Noise<Vec2> for WorleyNoise {
+    fn sample(&self, point: Vec2) -> f32 {
+        let cell = point.floor();
+        let offset = point - cell;
+        let (x, y) = (cell.x as i32, cell.y as i32);
+        let mut closest = ClosestTwo(f32::INFINITY, f32::INFINITY);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let hash = hash_2d(x.wrapping_add(dx), y.wrapping_add(dy), self.seed);
+                let feature = Vec2::new(hash_to_unit(hash), hash_to_unit(super::hash(hash)));
+                let feature = Vec2::new(dx as f32, dy as f32)
+                    + Vec2::splat(0.5)
+                    + (feature - Vec2::splat(0.5)) * self.jitter;
+                closest.push(feature.distance_squared(offset));
+            }
+        }
+        self.output(closest.0.sqrt(), closest.1.sqrt())
+    }
+}
+
+impl Noise<Vec3> for WorleyNoise {
+    fn sample(&self, point: Vec3) -> f32 {
+        let cell = point.floor();
+        let offset = point - cell;
+        let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+        let mut closest = ClosestTwo(f32::INFINITY, f32::INFINITY);
+        for dz in -1..=1 {
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let hash_x = hash_3d(
+                        x.wrapping_add(dx),
+                        y.wrapping_add(dy),
+                        z.wrapping_add(dz),
+                        self.seed,
+                    );
+                    let hash_y = hash(hash_x);
+                    let hash_z = hash(hash_y);
+                    let feature = Vec3::new(
+                        hash_to_unit(hash_x),
+                        hash_to_unit(hash_y),
+                        hash_to_unit(hash_z),
+                    );
+                    let feature = Vec3::new(dx as f32, dy as f32, dz as f32)
+                        + Vec3::splat(0.5)
+                        + (feature - Vec3::splat(0.5)) * self.jitter;
+                    closest.push(feature.distance_squared(offset));
+                }
+            }
+        }
+        self.output(closest.0.sqrt(), closest.1.sqrt())
+    }
+}
@@ -0,0 +1,115 @@
+#import bevy_pbr::{
+    mesh_view_bindings::{globals, view},
+    mesh_types::MESH_FLAGS_SHADOW_RECEIVER_BIT,
+    pbr_functions,
+    pbr_types,
+}
+
+// Three texels per joint, holding the rows of its affine skinning matrix, and a row per frame.
+@group(2) @binding(100) var animation_texture: texture_2d<f32>;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) joint_indices: vec4<u32>,
+    @location(3) joint_weights: vec4<f32>,
+    // The rows of the affine transform of the instance.
+    @location(4) model_x: vec4<f32>,
+    @location(5) model_y: vec4<f32>,
+    @location(6) model_z: vec4<f32>,
+    // The first frame, frame count and frame rate of the clip, and the time offset in frames.
+    @location(7) animation: vec4<f32>,
+    @location(8) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+struct Skinning {
+    x: vec4<f32>,
+    y: vec4<f32>,
+    z: vec4<f32>,
+};
+
+fn joint_skinning(frame: u32, joint: u32) -> Skinning {
+    let texel = i32(joint * 3u);
+    return Skinning(
+        textureLoad(animation_texture, vec2<i32>(texel, i32(frame)), 0),
+        textureLoad(animation_texture, vec2<i32>(texel + 1, i32(frame)), 0),
+        textureLoad(animation_texture, vec2<i32>(texel + 2, i32(frame)), 0),
+    );
+}
+
+fn frame_skinning(frame: u32, joints: vec4<u32>, weights: vec4<f32>) -> Skinning {
+    var skinning = Skinning(vec4(0.0), vec4(0.0), vec4(0.0));
+    for (var i = 0; i < 4; i += 1) {
+        let joint = joint_skinning(frame, joints[i]);
+        skinning.x += joint.x * weights[i];
+        skinning.y += joint.y * weights[i];
+        skinning.z += joint.z * weights[i];
+    }
+    return skinning;
+}
+
+fn transform_point(rows: Skinning, point: vec3<f32>) -> vec3<f32> {
+    let p = vec4(point, 1.0);
+    return vec3(dot(rows.x, p), dot(rows.y, p), dot(rows.z, p));
+}
+
+fn transform_vector(rows: Skinning, vector: vec3<f32>) -> vec3<f32> {
+    return vec3(dot(rows.x.xyz, vector), dot(rows.y.xyz, vector), dot(rows.z.xyz, vector));
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    // Loop over the frames of the clip, blending between the two frames around the current time.
+    let first_frame = vertex.animation.x;
+    let frame_count = max(vertex.animation.y, 1.0);
+    let frame_position = globals.time * vertex.animation.z + vertex.animation.w;
+    let clip_position = fract(frame_position / frame_count) * frame_count;
+    let frame_a = floor(clip_position);
+    let frame_b = (frame_a + 1.0) % frame_count;
+    let blend = clip_position - frame_a;
+
+    let skinning_a = frame_skinning(u32(first_frame + frame_a), vertex.joint_indices, vertex.joint_weights);
+    let skinning_b = frame_skinning(u32(first_frame + frame_b), vertex.joint_indices, vertex.joint_weights);
+    let skinning = Skinning(
+        mix(skinning_a.x, skinning_b.x, blend),
+        mix(skinning_a.y, skinning_b.y, blend),
+        mix(skinning_a.z, skinning_b.z, blend),
+    );
+    let model = Skinning(vertex.model_x, vertex.model_y, vertex.model_z);
+
+    let world_position = transform_point(model, transform_point(skinning, vertex.position));
+    let world_normal = normalize(transform_vector(model, transform_vector(skinning, vertex.normal)));
+
+    var out: VertexOutput;
+    out.world_position = vec4(world_position, 1.0);
+    out.position = view.view_proj * out.world_position;
+    out.world_normal = world_normal;
+    out.color = vertex.color;
+    return out;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> @location(0) vec4<f32> {
+    var pbr_input = pbr_types::pbr_input_new();
+    pbr_input.material.base_color = in.color;
+    pbr_input.frag_coord = in.position;
+    pbr_input.world_position = in.world_position;
+    pbr_input.world_normal = pbr_functions::prepare_world_normal(in.world_normal, false, is_front);
+    pbr_input.N = normalize(pbr_input.world_normal);
+    pbr_input.is_orthographic = view.projection[3].w == 1.0;
+    pbr_input.V = pbr_functions::calculate_view(in.world_position, pbr_input.is_orthographic);
+    pbr_input.flags = MESH_FLAGS_SHADOW_RECEIVER_BIT;
+
+    var color = pbr_functions::apply_pbr_lighting(pbr_input);
+    return pbr_functions::main_pass_post_lighting_processing(pbr_input, color);
+}
@@ -0,0 +1,472 @@
+//! Rendering crowds of animated characters in a single draw call.
+//!
+//! A [`Crowd`] draws its skinned mesh once per [`CrowdInstance`], with one instanced draw call.
+//! Instances aren't skinned from joint entities like a
+//! [`SkinnedMesh`](bevy_render::mesh::skinning::SkinnedMesh), but from an animation texture
+//! holding the baked skinning matrices of their clips, as described by [`AnimationTextureClip`].
+//! Each instance plays its own clip from its own time offset, entirely on the GPU, so that the
+//! per-frame cost of a crowd on the CPU is the upload of its instance transforms.
+//!
+//! Animation textures are typically baked from the clips of a reference character by the
+//! `AnimationBaker` of `bevy_animation`.
+//!
+//! Crowds are shaded with a uniform base color, and don't cast shadows.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, AssetId, Handle};
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_core_pipeline::core_3d::{CameraStencil, Transparent3d};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{Has, QueryItem, With},
+    schedule::IntoSystemConfigs as _,
+    system::{
+        lifetimeless::{Read, SRes},
+        Commands, Query, Res, ResMut, Resource, SystemParamItem,
+    },
+    world::{FromWorld, World},
+};
+use bevy_math::{Mat4, Vec4};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    mesh::{
+        skinning::AnimationTextureClip, GpuBufferInfo, GpuMesh, Mesh, MeshVertexBufferLayoutRef,
+    },
+    render_asset::RenderAssets,
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+        RenderCommandResult, SetItemPipeline, SortedRenderPhase, TrackedRenderPass,
+    },
+    render_resource::{
+        binding_types::texture_2d, BindGroup, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, Buffer, BufferInitDescriptor, BufferUsages, PipelineCache,
+        RenderPipelineDescriptor, Shader, ShaderDefVal, ShaderStages, SpecializedMeshPipeline,
+        SpecializedMeshPipelineError, SpecializedMeshPipelines, TextureSampleType, VertexAttribute,
+        VertexBufferLayout, VertexFormat, VertexStepMode,
+    },
+    renderer::RenderDevice,
+    texture::{GpuImage, Image},
+    view::{ExtractedView, Msaa},
+    Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::tracing::warn;
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    MeshPipeline, MeshPipelineKey, RenderMeshInstances, SetMeshBindGroup, SetMeshViewBindGroup,
+};
+
+/// The crowd shader.
+pub const CROWD_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(9263107511406532716);
+
+/// A plugin rendering [`Crowd`]s.
+pub struct CrowdPlugin;
+
+/// Many instances of the skinned mesh of the entity, animated from an animation texture.
+///
+/// The entity needs a [`Handle<Mesh>`] with joint indices and weights, and the components of a
+/// [`SpatialBundle`](bevy_render::prelude::SpatialBundle). The instances are culled as a whole,
+/// from the bounding box of the mesh at the transform of the entity, so crowds spreading beyond
+/// their mesh need a [`NoFrustumCulling`](bevy_render::view::NoFrustumCulling) component.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Crowd {
+    /// The baked skinning matrices of the clips played by the instances.
+    pub animation_texture: Handle<Image>,
+    /// The color of the instances.
+    pub base_color: Color,
+    /// The instances, relative to the entity.
+    pub instances: Vec<CrowdInstance>,
+}
+
+/// A character of a [`Crowd`].
+#[derive(Clone, Copy, Debug)]
+pub struct CrowdInstance {
+    /// The transform of the instance, relative to the crowd.
+    pub transform: Transform,
+    /// The clip of the animation texture played by the instance.
+    pub clip: AnimationTextureClip,
+    /// How far into the clip the instance is when the elapsed time is zero, in seconds.
+    ///
+    /// Spreading the offsets of instances playing the same clip keeps them out of step.
+    pub time_offset: f32,
+    /// The playback speed of the clip.
+    pub speed: f32,
+}
+
+impl CrowdInstance {
+    /// Creates an instance at `transform` playing `clip` at normal speed.
+    pub fn new(transform: Transform, clip: AnimationTextureClip) -> Self {
+        Self {
+            transform,
+            clip,
+            time_offset: 0.0,
+            speed: 1.0,
+        }
+    }
+
+    /// Returns the instance starting `time_offset` seconds into its clip.
+    pub fn with_time_offset(mut self, time_offset: f32) -> Self {
+        self.time_offset = time_offset;
+        self
+    }
+
+    /// Returns the instance playing its clip at `speed`.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+}
+
+/// The instances of a [`Crowd`], extracted to the render world.
+#[derive(Component)]
+pub struct ExtractedCrowd {
+    animation_texture: AssetId<Image>,
+    instances: Vec<CrowdInstanceData>,
+}
+
+/// The per-instance vertex data of a crowd, see `crowd.wgsl`.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct CrowdInstanceData {
+    /// The rows of the affine transform of the instance.
+    model: [Vec4; 3],
+    /// The first frame, frame count and frame rate of the clip, and the time offset in frames.
+    animation: Vec4,
+    color: Vec4,
+}
+
+impl ExtractComponent for ExtractedCrowd {
+    type QueryData = (Read<Crowd>, Read<GlobalTransform>);
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component((crowd, transform): QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        if crowd.instances.is_empty() {
+            return None;
+        }
+        let color = Vec4::from_array(LinearRgba::from(crowd.base_color).to_f32_array());
+        let instances = crowd
+            .instances
+            .iter()
+            .map(|instance| {
+                let model = Mat4::from(transform.affine() * instance.transform.compute_affine());
+                let clip = instance.clip;
+                let frames_per_second = clip.frame_rate * instance.speed;
+                CrowdInstanceData {
+                    model: [model.row(0), model.row(1), model.row(2)],
+                    animation: Vec4::new(
+                        clip.first_frame as f32,
+                        clip.frame_count as f32,
+                        frames_per_second,
+                        instance.time_offset * clip.frame_rate,
+                    ),
+                    color,
+                }
+            })
+            .collect();
+        Some(ExtractedCrowd {
+            animation_texture: crowd.animation_texture.id(),
+            instances,
+        })
+    }
+}
+
+impl Plugin for CrowdPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, CROWD_SHADER_HANDLE, "crowd.wgsl", Shader::from_wgsl);
+
+        app.add_plugins(ExtractComponentPlugin::<ExtractedCrowd>::extract_visible());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .add_render_command::<Transparent3d, DrawCrowd>()
+            .init_resource::<SpecializedMeshPipelines<CrowdPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    queue_crowds.in_set(RenderSet::QueueMeshes),
+                    prepare_crowd_buffers.in_set(RenderSet::PrepareResources),
+                    prepare_crowd_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<CrowdPipeline>();
+    }
+}
+
+/// The render pipeline of crowds, specializing the [`MeshPipeline`].
+#[derive(Resource)]
+pub struct CrowdPipeline {
+    mesh_pipeline: MeshPipeline,
+    animation_texture_layout: BindGroupLayout,
+}
+
+impl FromWorld for CrowdPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let animation_texture_layout = render_device.create_bind_group_layout(
+            "crowd_animation_texture_layout",
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::VERTEX,
+                // Material bindings are declared in group 2 by the PBR shader imports, so the
+                // texture uses the binding indices left to extended materials.
+                ((
+                    100,
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                ),),
+            ),
+        );
+
+        CrowdPipeline {
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+            animation_texture_layout,
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for CrowdPipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+
+        // Instances are skinned from the animation texture rather than from the joint matrices
+        // of a `SkinnedMesh`, and only the model-only mesh bind group is set.
+        let vertex_layout = layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_NORMAL.at_shader_location(1),
+            Mesh::ATTRIBUTE_JOINT_INDEX.at_shader_location(2),
+            Mesh::ATTRIBUTE_JOINT_WEIGHT.at_shader_location(3),
+        ])?;
+        let instance_attributes = (0..5)
+            .map(|index| VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: index * VertexFormat::Float32x4.size(),
+                shader_location: 4 + index as u32,
+            })
+            .collect();
+        descriptor.vertex.buffers = vec![
+            vertex_layout,
+            VertexBufferLayout {
+                array_stride: std::mem::size_of::<CrowdInstanceData>() as u64,
+                step_mode: VertexStepMode::Instance,
+                attributes: instance_attributes,
+            },
+        ];
+        descriptor.layout.truncate(1);
+        descriptor
+            .layout
+            .push(self.mesh_pipeline.mesh_layouts.model_only.clone());
+        descriptor
+            .layout
+            .push(self.animation_texture_layout.clone());
+
+        let skinned = ShaderDefVal::from("SKINNED");
+        descriptor.vertex.shader = CROWD_SHADER_HANDLE;
+        descriptor.vertex.shader_defs.retain(|def| *def != skinned);
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.shader = CROWD_SHADER_HANDLE;
+        fragment.shader_defs.retain(|def| *def != skinned);
+        Ok(descriptor)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_crowds(
+    transparent_3d_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    crowd_pipeline: Res<CrowdPipeline>,
+    msaa: Res<Msaa>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<CrowdPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<GpuMesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    crowds: Query<Entity, With<ExtractedCrowd>>,
+    mut views: Query<(
+        &ExtractedView,
+        Has<CameraStencil>,
+        &mut SortedRenderPhase<Transparent3d>,
+    )>,
+) {
+    let draw_crowd = transparent_3d_draw_functions.read().id::<DrawCrowd>();
+
+    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
+
+    for (view, stencil, mut transparent_phase) in &mut views {
+        let mut view_key = msaa_key | MeshPipelineKey::from_hdr(view.hdr);
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+        let rangefinder = view.rangefinder3d();
+        for entity in &crowds {
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(entity) else {
+                continue;
+            };
+            let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+            let key =
+                view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology());
+            let pipeline =
+                match pipelines.specialize(&pipeline_cache, &crowd_pipeline, key, &mesh.layout) {
+                    Ok(pipeline) => pipeline,
+                    Err(err) => {
+                        warn!("Failed to specialize the pipeline of crowd {entity:?}: {err}");
+                        continue;
+                    }
+                };
+            transparent_phase.add(Transparent3d {
+                entity,
+                pipeline,
+                draw_function: draw_crowd,
+                distance: rangefinder.distance_translation(&mesh_instance.translation),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+/// The instance buffer of a crowd.
+#[derive(Component)]
+pub struct CrowdInstanceBuffer {
+    buffer: Buffer,
+    length: usize,
+}
+
+fn prepare_crowd_buffers(
+    mut commands: Commands,
+    crowds: Query<(Entity, &ExtractedCrowd)>,
+    render_device: Res<RenderDevice>,
+) {
+    for (entity, crowd) in &crowds {
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("crowd_instance_buffer"),
+            contents: bytemuck::cast_slice(crowd.instances.as_slice()),
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+        });
+        commands.entity(entity).insert(CrowdInstanceBuffer {
+            buffer,
+            length: crowd.instances.len(),
+        });
+    }
+}
+
+/// The bind group of the animation texture of a crowd.
+#[derive(Component)]
+pub struct CrowdBindGroup(BindGroup);
+
+fn prepare_crowd_bind_groups(
+    mut commands: Commands,
+    crowds: Query<(Entity, &ExtractedCrowd)>,
+    crowd_pipeline: Res<CrowdPipeline>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+) {
+    for (entity, crowd) in &crowds {
+        let Some(animation_texture) = images.get(crowd.animation_texture) else {
+            continue;
+        };
+        let bind_group = render_device.create_bind_group(
+            "crowd_bind_group",
+            &crowd_pipeline.animation_texture_layout,
+            &BindGroupEntries::with_indices(((100, &animation_texture.texture_view),)),
+        );
+        commands.entity(entity).insert(CrowdBindGroup(bind_group));
+    }
+}
+
+type DrawCrowd = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    SetCrowdBindGroup<2>,
+    DrawCrowdInstanced,
+);
+
+/// Sets the bind group of the animation texture of a crowd.
+pub struct SetCrowdBindGroup<const I: usize>;
+
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetCrowdBindGroup<I> {
+    type Param = ();
+    type ViewQuery = ();
+    type ItemQuery = Read<CrowdBindGroup>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        bind_group: Option<&'w CrowdBindGroup>,
+        _param: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        // The animation texture may not be loaded yet.
+        let Some(bind_group) = bind_group else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, &bind_group.0, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+/// Draws all the instances of a crowd.
+pub struct DrawCrowdInstanced;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawCrowdInstanced {
+    type Param = (SRes<RenderAssets<GpuMesh>>, SRes<RenderMeshInstances>);
+    type ViewQuery = ();
+    type ItemQuery = Read<CrowdInstanceBuffer>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        instance_buffer: Option<&'w CrowdInstanceBuffer>,
+        (meshes, render_mesh_instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(item.entity())
+        else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(instance_buffer) = instance_buffer else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, instance_buffer.buffer.slice(..));
+
+        let instances = 0..instance_buffer.length as u32;
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                count,
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, instances);
+            }
+            GpuBufferInfo::NonIndexed => {
+                pass.draw(0..gpu_mesh.vertex_count, instances);
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
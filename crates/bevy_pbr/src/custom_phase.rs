@@ -0,0 +1,228 @@
+use std::{hash::Hash, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_core_pipeline::{
+    core_3d::CameraStencil,
+    custom_phase::{CustomPhase, CustomPhaseItem, CustomPhasePlugin, InCustomPhase},
+    prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    camera::{Projection, TemporalJitter},
+    mesh::GpuMesh,
+    render_asset::{prepare_assets, RenderAssets},
+    render_phase::{AddRenderCommand, DrawFunctions, SortedRenderPhase},
+    render_resource::{PipelineCache, SpecializedMeshPipelines},
+    view::{ExtractedView, Msaa, VisibleEntities, WithMesh},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::tracing::error;
+
+use crate::{
+    tonemapping_pipeline_key, DrawMaterial, Material, MaterialPipeline, MaterialPipelineKey,
+    MeshPipeline, MeshPipelineKey, PreparedMaterial, RenderMaterialInstances, RenderMeshInstances,
+};
+
+/// Draws the meshes with the [`Material`] `M` in the custom phase `P`, with the pipeline of the
+/// material.
+///
+/// By default, only the entities tagged with [`InCustomPhase<P>`] are drawn in the phase. Setting
+/// [`MaterialCustomPhasePlugin::all_entities`] tags the material itself instead, so that every
+/// mesh using it is drawn in the phase.
+///
+/// The entities are still drawn in the main phases by the [`MaterialPlugin`](crate::MaterialPlugin)
+/// of `M`, which must be added too.
+pub struct MaterialCustomPhasePlugin<M: Material, P: CustomPhase> {
+    /// Whether every mesh with the material is drawn in the phase, rather than only the entities
+    /// tagged with [`InCustomPhase<P>`].
+    pub all_entities: bool,
+    pub _marker: PhantomData<fn() -> (M, P)>,
+}
+
+impl<M: Material, P: CustomPhase> Default for MaterialCustomPhasePlugin<M, P> {
+    fn default() -> Self {
+        Self {
+            all_entities: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Material, P: CustomPhase> Plugin for MaterialCustomPhasePlugin<M, P>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<CustomPhasePlugin<P, MeshPipeline>>() {
+            app.add_plugins(CustomPhasePlugin::<P, MeshPipeline>::default());
+        }
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .insert_resource(MaterialCustomPhaseFilter::<M, P> {
+                all_entities: self.all_entities,
+                _marker: PhantomData,
+            })
+            .add_render_command::<CustomPhaseItem<P>, DrawMaterial<M>>()
+            .add_systems(
+                Render,
+                queue_material_custom_phase::<M, P>
+                    .in_set(RenderSet::QueueMeshes)
+                    .after(prepare_assets::<PreparedMaterial<M>>),
+            );
+    }
+}
+
+/// Which meshes with the [`Material`] `M` are drawn in the custom phase `P`, as configured by
+/// the [`MaterialCustomPhasePlugin`].
+#[derive(Resource)]
+pub struct MaterialCustomPhaseFilter<M: Material, P: CustomPhase> {
+    pub all_entities: bool,
+    _marker: PhantomData<fn() -> (M, P)>,
+}
+
+/// Queues the visible meshes with the [`Material`] `M` into the custom phase `P` of each view.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_material_custom_phase<M: Material, P: CustomPhase>(
+    draw_functions: Res<DrawFunctions<CustomPhaseItem<P>>>,
+    filter: Res<MaterialCustomPhaseFilter<M, P>>,
+    material_pipeline: Res<MaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<MaterialPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    render_meshes: Res<RenderAssets<GpuMesh>>,
+    render_materials: Res<RenderAssets<PreparedMaterial<M>>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    render_material_instances: Res<RenderMaterialInstances<M>>,
+    tagged: Query<(), With<InCustomPhase<P>>>,
+    mut views: Query<(
+        &ExtractedView,
+        &VisibleEntities,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+        (
+            Has<NormalPrepass>,
+            Has<DepthPrepass>,
+            Has<MotionVectorPrepass>,
+            Has<DeferredPrepass>,
+        ),
+        Has<TemporalJitter>,
+        Has<CameraStencil>,
+        Option<&Projection>,
+        &mut SortedRenderPhase<CustomPhaseItem<P>>,
+    )>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    let draw_function = draw_functions.read().id::<DrawMaterial<M>>();
+
+    for (
+        view,
+        visible_entities,
+        tonemapping,
+        dither,
+        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        temporal_jitter,
+        stencil,
+        projection,
+        mut phase,
+    ) in &mut views
+    {
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
+            | MeshPipelineKey::from_hdr(view.hdr);
+
+        // The prepass bits select the layout of the view bind group, so they must match the view.
+        if normal_prepass {
+            view_key |= MeshPipelineKey::NORMAL_PREPASS;
+        }
+        if depth_prepass {
+            view_key |= MeshPipelineKey::DEPTH_PREPASS;
+        }
+        if motion_vector_prepass {
+            view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+        }
+        if deferred_prepass {
+            view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+        }
+        if temporal_jitter {
+            view_key |= MeshPipelineKey::TEMPORAL_JITTER;
+        }
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
+        if let Some(projection) = projection {
+            view_key |= match projection {
+                Projection::Perspective(_) => MeshPipelineKey::VIEW_PROJECTION_PERSPECTIVE,
+                Projection::Orthographic(_) => MeshPipelineKey::VIEW_PROJECTION_ORTHOGRAPHIC,
+            };
+        }
+
+        if !view.hdr {
+            if let Some(tonemapping) = tonemapping {
+                view_key |= MeshPipelineKey::TONEMAP_IN_SHADER;
+                view_key |= tonemapping_pipeline_key(*tonemapping);
+            }
+            if let Some(DebandDither::Enabled) = dither {
+                view_key |= MeshPipelineKey::DEBAND_DITHER;
+            }
+        }
+
+        let rangefinder = view.rangefinder3d();
+        for visible_entity in visible_entities.iter::<WithMesh>() {
+            if !filter.all_entities && !tagged.contains(*visible_entity) {
+                continue;
+            }
+            let Some(material_asset_id) = render_material_instances.get(visible_entity) else {
+                continue;
+            };
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(*visible_entity)
+            else {
+                continue;
+            };
+            let Some(mesh) = render_meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+            let Some(material) = render_materials.get(*material_asset_id) else {
+                continue;
+            };
+
+            let mesh_key = view_key
+                | MeshPipelineKey::from_bits_retain(mesh.key_bits.bits())
+                | material.properties.mesh_pipeline_key_bits;
+
+            let pipeline_id = match pipelines.specialize(
+                &pipeline_cache,
+                &material_pipeline,
+                MaterialPipelineKey {
+                    mesh_key,
+                    bind_group_data: material.key.clone(),
+                },
+                &mesh.layout,
+            ) {
+                Ok(id) => id,
+                Err(err) => {
+                    error!("{}", err);
+                    continue;
+                }
+            };
+
+            mesh_instance
+                .material_bind_group_id
+                .set(material.get_bind_group_id());
+
+            let distance = rangefinder.distance_translation(&mesh_instance.translation)
+                + material.properties.depth_bias;
+            phase.add(CustomPhaseItem::new(
+                *visible_entity,
+                distance,
+                pipeline_id,
+                draw_function,
+            ));
+        }
+    }
+}
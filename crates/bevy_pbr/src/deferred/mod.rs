@@ -1,7 +1,7 @@
 use crate::{
-    graph::NodePbr, irradiance_volume::IrradianceVolume, prelude::EnvironmentMapLight,
-    MeshPipeline, MeshViewBindGroup, RenderViewLightProbes, ScreenSpaceAmbientOcclusionSettings,
-    ViewLightProbesUniformOffset,
+    cluster_debug_pipeline_key, graph::NodePbr, irradiance_volume::IrradianceVolume,
+    prelude::EnvironmentMapLight, ClusterDebugOverlay, MeshPipeline, MeshViewBindGroup,
+    RenderViewLightProbes, ScreenSpaceAmbientOcclusionSettings, ViewLightProbesUniformOffset,
 };
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, Handle};
@@ -314,6 +314,15 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
             shader_defs.push("SHADOW_FILTER_METHOD_TEMPORAL".into());
         }
 
+        let cluster_debug = key.intersection(MeshPipelineKey::CLUSTER_DEBUG_RESERVED_BITS);
+        if cluster_debug == MeshPipelineKey::CLUSTER_DEBUG_Z_SLICES {
+            shader_defs.push("CLUSTERED_FORWARD_DEBUG_Z_SLICES".into());
+        } else if cluster_debug == MeshPipelineKey::CLUSTER_DEBUG_LIGHT_COMPLEXITY {
+            shader_defs.push("CLUSTERED_FORWARD_DEBUG_CLUSTER_LIGHT_COMPLEXITY".into());
+        } else if cluster_debug == MeshPipelineKey::CLUSTER_DEBUG_COHERENCY {
+            shader_defs.push("CLUSTERED_FORWARD_DEBUG_CLUSTER_COHERENCY".into());
+        }
+
         #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
         shader_defs.push("SIXTEEN_BYTE_ALIGNMENT".into());
 
@@ -414,6 +423,7 @@ pub fn prepare_deferred_lighting_pipelines(
             ),
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
+            Option<&ClusterDebugOverlay>,
         ),
         With<DeferredPrepass>,
     >,
@@ -428,6 +438,7 @@ pub fn prepare_deferred_lighting_pipelines(
         (normal_prepass, depth_prepass, motion_vector_prepass),
         has_environment_maps,
         has_irradiance_volumes,
+        cluster_debug_overlay,
     ) in &views
     {
         let mut view_key = MeshPipelineKey::from_hdr(view.hdr);
@@ -485,6 +496,8 @@ pub fn prepare_deferred_lighting_pipelines(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        view_key |= cluster_debug_pipeline_key(cluster_debug_overlay);
+
         match shadow_filter_method.unwrap_or(&ShadowFilteringMethod::default()) {
             ShadowFilteringMethod::Hardware2x2 => {
                 view_key |= MeshPipelineKey::SHADOW_FILTER_METHOD_HARDWARE_2X2;
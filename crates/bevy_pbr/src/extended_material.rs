@@ -34,6 +34,18 @@ pub trait MaterialExtension: Asset + AsBindGroup + Clone + Sized {
         ShaderRef::Default
     }
 
+    /// Returns this material's vertex displacement shader, used as the vertex shader of every pass, including the
+    /// prepass and shadows, unless the pass has its own vertex shader. If [`ShaderRef::Default`] is returned, the
+    /// vertices aren't displaced.
+    ///
+    /// This is the extension point for simple vertex animations, such as foliage swaying in the wind, that keep
+    /// all the features of the base material. The shader moves the vertices of the mesh before they are skinned and
+    /// transformed as usual, using the functions of the `bevy_pbr::vertex_displacement` shader module, the time from
+    /// its `time` function and the uniforms of the extension, which are visible to vertex shaders.
+    fn vertex_displacement_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
     /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the base material mesh fragment shader
     /// will be used.
     #[allow(unused_variables)]
@@ -113,7 +125,8 @@ pub trait MaterialExtension: Asset + AsBindGroup + Clone + Sized {
 /// bindings) will work as expected, and custom alterations based on custom data can also be used.
 ///
 /// If the extension `E` returns a non-default result from `vertex_shader()` it will be used in place of the base
-/// material's vertex shader.
+/// material's vertex shader. Otherwise, a non-default result from `vertex_displacement_shader()` is used in place of
+/// the vertex shaders of all the passes.
 ///
 /// If the extension `E` returns a non-default result from `fragment_shader()` it will be used in place of the base
 /// fragment shader.
@@ -191,9 +204,10 @@ impl<B: Material, E: MaterialExtension> AsBindGroup for ExtendedMaterial<B, E> {
 
 impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
     fn vertex_shader() -> ShaderRef {
-        match E::vertex_shader() {
-            ShaderRef::Default => B::vertex_shader(),
-            specified => specified,
+        match (E::vertex_shader(), E::vertex_displacement_shader()) {
+            (ShaderRef::Default, ShaderRef::Default) => B::vertex_shader(),
+            (ShaderRef::Default, displacement) => displacement,
+            (specified, _) => specified,
         }
     }
 
@@ -220,10 +234,15 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
         B::reads_view_transmission_texture(&self.base)
     }
 
+    fn stencil(&self) -> crate::MaterialStencil {
+        B::stencil(&self.base)
+    }
+
     fn prepass_vertex_shader() -> ShaderRef {
-        match E::prepass_vertex_shader() {
-            ShaderRef::Default => B::prepass_vertex_shader(),
-            specified => specified,
+        match (E::prepass_vertex_shader(), E::vertex_displacement_shader()) {
+            (ShaderRef::Default, ShaderRef::Default) => B::prepass_vertex_shader(),
+            (ShaderRef::Default, displacement) => displacement,
+            (specified, _) => specified,
         }
     }
 
@@ -235,9 +254,10 @@ impl<B: Material, E: MaterialExtension> Material for ExtendedMaterial<B, E> {
     }
 
     fn deferred_vertex_shader() -> ShaderRef {
-        match E::deferred_vertex_shader() {
-            ShaderRef::Default => B::deferred_vertex_shader(),
-            specified => specified,
+        match (E::deferred_vertex_shader(), E::vertex_displacement_shader()) {
+            (ShaderRef::Default, ShaderRef::Default) => B::deferred_vertex_shader(),
+            (ShaderRef::Default, displacement) => displacement,
+            (specified, _) => specified,
         }
     }
 
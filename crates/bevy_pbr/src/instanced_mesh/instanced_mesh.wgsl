@@ -0,0 +1,90 @@
+#import bevy_pbr::{
+    mesh_view_bindings::view,
+    mesh_types::MESH_FLAGS_SHADOW_RECEIVER_BIT,
+    pbr_functions,
+    pbr_types,
+}
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    // The rows of the affine transform of the instance.
+    @location(2) model_x: vec4<f32>,
+    @location(3) model_y: vec4<f32>,
+    @location(4) model_z: vec4<f32>,
+    @location(5) color: vec4<f32>,
+    @location(6) data: vec4<f32>,
+    // The center and radius of the bounding sphere of the instance, in world space.
+    @location(7) bounds: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_position: vec4<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) color: vec4<f32>,
+};
+
+#ifdef INSTANCE_GPU_CULLING
+fn is_culled(bounds: vec4<f32>) -> bool {
+    // The far plane is left out, as it is for meshes.
+    for (var i = 0; i < 5; i += 1) {
+        if dot(view.frustum[i], vec4(bounds.xyz, 1.0)) + bounds.w <= 0.0 {
+            return true;
+        }
+    }
+    return false;
+}
+#endif
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+#ifdef INSTANCE_GPU_CULLING
+    if is_culled(vertex.bounds) {
+        // Collapse the instance outside of the clip volume, so that it isn't rasterized.
+        out.position = vec4(2.0, 2.0, 2.0, 1.0);
+        return out;
+    }
+#endif
+
+    let position = vec4(vertex.position, 1.0);
+    let world_position = vec3(
+        dot(vertex.model_x, position),
+        dot(vertex.model_y, position),
+        dot(vertex.model_z, position),
+    );
+    // Correct for non-uniform scale, up to a factor.
+    let model = transpose(mat3x3(vertex.model_x.xyz, vertex.model_y.xyz, vertex.model_z.xyz));
+    let inverse_transpose = mat3x3(
+        cross(model[1], model[2]),
+        cross(model[2], model[0]),
+        cross(model[0], model[1]),
+    );
+
+    out.world_position = vec4(world_position, 1.0);
+    out.position = view.view_proj * out.world_position;
+    out.world_normal = normalize(inverse_transpose * vertex.normal);
+    out.color = vertex.color;
+    return out;
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> @location(0) vec4<f32> {
+    var pbr_input = pbr_types::pbr_input_new();
+    pbr_input.material.base_color = in.color;
+    pbr_input.frag_coord = in.position;
+    pbr_input.world_position = in.world_position;
+    pbr_input.world_normal = pbr_functions::prepare_world_normal(in.world_normal, false, is_front);
+    pbr_input.N = normalize(pbr_input.world_normal);
+    pbr_input.is_orthographic = view.projection[3].w == 1.0;
+    pbr_input.V = pbr_functions::calculate_view(in.world_position, pbr_input.is_orthographic);
+    pbr_input.flags = MESH_FLAGS_SHADOW_RECEIVER_BIT;
+
+    var color = pbr_functions::apply_pbr_lighting(pbr_input);
+    return pbr_functions::main_pass_post_lighting_processing(pbr_input, color);
+}
@@ -0,0 +1,646 @@
+//! Rendering many copies of a mesh in a single draw call.
+//!
+//! An [`InstancedMesh`] draws the mesh of its entity once per [`MeshInstance`], with one
+//! instanced draw call per view, and culls the instances individually, on the CPU or on the GPU
+//! depending on its [`InstanceCulling`]. The instances are shaded with their color by default,
+//! and can be shaded by a custom shader receiving their custom data.
+
+use std::ops::Range;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetId, Assets, Handle};
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_core_pipeline::core_3d::{CameraStencil, Transparent3d};
+use bevy_ecs::{
+    component::Component,
+    entity::{Entity, EntityHashMap},
+    query::{Changed, Has, Or, QueryItem, With, Without},
+    schedule::IntoSystemConfigs as _,
+    system::{
+        lifetimeless::{Read, SRes},
+        Commands, Query, Res, ResMut, Resource, SystemParamItem,
+    },
+    world::{FromWorld, World},
+};
+use bevy_math::{Affine3A, Mat4, Vec3A, Vec4};
+use bevy_render::{
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    mesh::{GpuBufferInfo, GpuMesh, Mesh, MeshVertexBufferLayoutRef},
+    primitives::{Aabb, Frustum},
+    render_asset::RenderAssets,
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand,
+        RenderCommandResult, SetItemPipeline, SortedRenderPhase, TrackedRenderPass,
+    },
+    render_resource::{
+        Buffer, BufferInitDescriptor, BufferUsages, PipelineCache, RenderPipelineDescriptor,
+        Shader, SpecializedMeshPipeline, SpecializedMeshPipelineError, SpecializedMeshPipelines,
+        VertexAttribute, VertexBufferLayout, VertexFormat, VertexStepMode,
+    },
+    renderer::RenderDevice,
+    view::{calculate_bounds, ExtractedView, Msaa, VisibilitySystems},
+    Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::tracing::warn;
+use bytemuck::{Pod, Zeroable};
+
+use crate::{MeshPipeline, MeshPipelineKey, RenderMeshInstances, SetMeshViewBindGroup};
+
+/// The default shader of instanced meshes.
+pub const INSTANCED_MESH_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(3471659207518849323);
+
+/// A plugin rendering [`InstancedMesh`]es.
+pub struct InstancedMeshPlugin;
+
+/// Many instances of the mesh of the entity, drawn in a single draw call.
+///
+/// The entity needs a [`Handle<Mesh>`] with positions and normals, and the components of a
+/// [`SpatialBundle`](bevy_render::prelude::SpatialBundle). The [`Aabb`] of the entity is kept
+/// up to date with the bounds of all its instances, so that the entity is culled as a whole when
+/// none of them is visible.
+///
+/// The mesh is drawn after the opaque meshes, and doesn't cast shadows.
+#[derive(Component, Clone, Debug, Default)]
+pub struct InstancedMesh {
+    /// The instances, relative to the entity.
+    pub instances: Vec<MeshInstance>,
+    /// How the instances are culled.
+    pub culling: InstanceCulling,
+    /// The shader drawing the instances, instead of the default [`INSTANCED_MESH_SHADER_HANDLE`].
+    ///
+    /// Its `vertex` entry point receives the position and normal of the mesh at locations 0 and
+    /// 1, and the rows of the affine transform, the color, the custom data and the world space
+    /// bounding sphere of the instance at locations 2 to 7, and its `fragment` entry point
+    /// renders to the main pass.
+    pub shader: Option<Handle<Shader>>,
+}
+
+impl InstancedMesh {
+    /// Creates an instanced mesh drawing `instances`, culled on the CPU.
+    pub fn new(instances: impl IntoIterator<Item = MeshInstance>) -> Self {
+        Self {
+            instances: instances.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the instanced mesh culled with `culling`.
+    pub fn with_culling(mut self, culling: InstanceCulling) -> Self {
+        self.culling = culling;
+        self
+    }
+
+    /// Returns the instanced mesh drawn with `shader`.
+    pub fn with_shader(mut self, shader: Handle<Shader>) -> Self {
+        self.shader = Some(shader);
+        self
+    }
+}
+
+/// A copy of the mesh of an [`InstancedMesh`].
+#[derive(Clone, Copy, Debug)]
+pub struct MeshInstance {
+    /// The transform of the instance, relative to the entity.
+    pub transform: Transform,
+    /// The color of the instance.
+    pub color: Color,
+    /// Custom data, ignored by the default shader and passed to the
+    /// [`shader`](InstancedMesh::shader) of the instanced mesh.
+    pub data: Vec4,
+}
+
+impl MeshInstance {
+    /// Creates a white instance at `transform`.
+    pub fn new(transform: Transform) -> Self {
+        Self {
+            transform,
+            color: Color::WHITE,
+            data: Vec4::ZERO,
+        }
+    }
+
+    /// Returns the instance with `color`.
+    pub fn with_color(mut self, color: impl Into<Color>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Returns the instance with custom `data`.
+    pub fn with_data(mut self, data: Vec4) -> Self {
+        self.data = data;
+        self
+    }
+}
+
+/// How the instances of an [`InstancedMesh`] are culled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum InstanceCulling {
+    /// All the instances are drawn when the entity is visible.
+    None,
+    /// The instances outside of the frustum of a view are left out of its instance buffer.
+    ///
+    /// This saves the most GPU time, at the cost of testing every instance against every view
+    /// on the CPU.
+    #[default]
+    Cpu,
+    /// The vertex shader collapses the instances outside of the frustum of the view, so that
+    /// they aren't rasterized.
+    ///
+    /// This is cheaper on the CPU than [`InstanceCulling::Cpu`] for large numbers of instances,
+    /// but still runs the vertex shader on the vertices of culled instances.
+    Gpu,
+}
+
+/// The bounds of the mesh of an [`InstancedMesh`], in the space of the mesh.
+///
+/// This is inserted and kept up to date by [`calculate_instanced_mesh_bounds`], along with the
+/// [`Aabb`] of the entity, which bounds all the instances.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct InstancedMeshAabb(pub Aabb);
+
+/// A system computing the [`Aabb`] and [`InstancedMeshAabb`] of [`InstancedMesh`]es.
+pub fn calculate_instanced_mesh_bounds(
+    mut commands: Commands,
+    meshes: Res<Assets<Mesh>>,
+    instanced_meshes: Query<
+        (Entity, &Handle<Mesh>, &InstancedMesh),
+        Or<(
+            Changed<InstancedMesh>,
+            Changed<Handle<Mesh>>,
+            Without<InstancedMeshAabb>,
+        )>,
+    >,
+) {
+    for (entity, mesh_handle, instanced_mesh) in &instanced_meshes {
+        let Some(mesh_aabb) = meshes.get(mesh_handle).and_then(Mesh::compute_aabb) else {
+            continue;
+        };
+        let (min, max) = instanced_mesh.instances.iter().fold(
+            (Vec3A::splat(f32::MAX), Vec3A::splat(f32::MIN)),
+            |(min, max), instance| {
+                let affine = instance.transform.compute_affine();
+                let center = affine.transform_point3a(mesh_aabb.center);
+                let half_extents = affine.matrix3.abs() * mesh_aabb.half_extents;
+                (
+                    min.min(center - half_extents),
+                    max.max(center + half_extents),
+                )
+            },
+        );
+        let aabb = if instanced_mesh.instances.is_empty() {
+            Aabb::default()
+        } else {
+            Aabb::from_min_max(min.into(), max.into())
+        };
+        commands
+            .entity(entity)
+            .try_insert((aabb, InstancedMeshAabb(mesh_aabb)));
+    }
+}
+
+/// The instances of an [`InstancedMesh`], extracted to the render world.
+#[derive(Component)]
+pub struct ExtractedInstancedMesh {
+    culling: InstanceCulling,
+    shader: AssetId<Shader>,
+    mesh_aabb: Option<Aabb>,
+    instances: Vec<InstanceData>,
+    transforms: Vec<Affine3A>,
+}
+
+/// The per-instance vertex data of an instanced mesh, see `instanced_mesh.wgsl`.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct InstanceData {
+    /// The rows of the affine transform of the instance.
+    model: [Vec4; 3],
+    color: Vec4,
+    data: Vec4,
+    /// The center and radius of the bounding sphere of the instance, in world space.
+    bounds: Vec4,
+}
+
+impl ExtractComponent for ExtractedInstancedMesh {
+    type QueryData = (
+        Read<InstancedMesh>,
+        Read<GlobalTransform>,
+        Option<Read<InstancedMeshAabb>>,
+    );
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        (instanced_mesh, transform, mesh_aabb): QueryItem<'_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        if instanced_mesh.instances.is_empty() {
+            return None;
+        }
+        let mesh_aabb = mesh_aabb.map(|mesh_aabb| mesh_aabb.0);
+        let transforms: Vec<_> = instanced_mesh
+            .instances
+            .iter()
+            .map(|instance| transform.affine() * instance.transform.compute_affine())
+            .collect();
+        let instances = instanced_mesh
+            .instances
+            .iter()
+            .zip(&transforms)
+            .map(|(instance, affine)| {
+                let bounds = match mesh_aabb {
+                    Some(aabb) => {
+                        let scale = affine
+                            .matrix3
+                            .x_axis
+                            .length()
+                            .max(affine.matrix3.y_axis.length())
+                            .max(affine.matrix3.z_axis.length());
+                        affine
+                            .transform_point3a(aabb.center)
+                            .extend(aabb.half_extents.length() * scale)
+                    }
+                    None => Vec4::new(0.0, 0.0, 0.0, f32::INFINITY),
+                };
+                let model = Mat4::from(*affine);
+                InstanceData {
+                    model: [model.row(0), model.row(1), model.row(2)],
+                    color: Vec4::from_array(LinearRgba::from(instance.color).to_f32_array()),
+                    data: instance.data,
+                    bounds,
+                }
+            })
+            .collect();
+        Some(ExtractedInstancedMesh {
+            culling: instanced_mesh.culling,
+            shader: instanced_mesh
+                .shader
+                .as_ref()
+                .map_or(INSTANCED_MESH_SHADER_HANDLE.id(), Handle::id),
+            mesh_aabb,
+            instances,
+            transforms,
+        })
+    }
+}
+
+impl Plugin for InstancedMeshPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            INSTANCED_MESH_SHADER_HANDLE,
+            "instanced_mesh.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.add_plugins(ExtractComponentPlugin::<ExtractedInstancedMesh>::extract_visible())
+            .add_systems(
+                PostUpdate,
+                calculate_instanced_mesh_bounds
+                    .in_set(VisibilitySystems::CalculateBounds)
+                    .after(calculate_bounds),
+            );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .add_render_command::<Transparent3d, DrawInstancedMesh>()
+            .init_resource::<SpecializedMeshPipelines<InstancedMeshPipeline>>()
+            .add_systems(
+                Render,
+                (
+                    queue_instanced_meshes.in_set(RenderSet::QueueMeshes),
+                    prepare_instance_buffers.in_set(RenderSet::PrepareResources),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<InstancedMeshPipeline>();
+    }
+}
+
+/// The render pipeline of instanced meshes, specializing the [`MeshPipeline`].
+#[derive(Resource)]
+pub struct InstancedMeshPipeline {
+    mesh_pipeline: MeshPipeline,
+}
+
+impl FromWorld for InstancedMeshPipeline {
+    fn from_world(world: &mut World) -> Self {
+        InstancedMeshPipeline {
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+        }
+    }
+}
+
+/// The key specializing the [`InstancedMeshPipeline`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InstancedMeshPipelineKey {
+    pub mesh_key: MeshPipelineKey,
+    pub gpu_culling: bool,
+    pub shader: AssetId<Shader>,
+}
+
+impl SpecializedMeshPipeline for InstancedMeshPipeline {
+    type Key = InstancedMeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key.mesh_key, layout)?;
+
+        // The transforms of the instances are read from the instance buffer, so only the view
+        // bind group is set.
+        let vertex_layout = layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_NORMAL.at_shader_location(1),
+        ])?;
+        let instance_attributes = (0..6)
+            .map(|index| VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: index * VertexFormat::Float32x4.size(),
+                shader_location: 2 + index as u32,
+            })
+            .collect();
+        descriptor.vertex.buffers = vec![
+            vertex_layout,
+            VertexBufferLayout {
+                array_stride: std::mem::size_of::<InstanceData>() as u64,
+                step_mode: VertexStepMode::Instance,
+                attributes: instance_attributes,
+            },
+        ];
+        descriptor.layout.truncate(1);
+
+        let shader = Handle::Weak(key.shader);
+        if key.gpu_culling {
+            descriptor
+                .vertex
+                .shader_defs
+                .push("INSTANCE_GPU_CULLING".into());
+        }
+        descriptor.vertex.shader = shader.clone();
+        descriptor.fragment.as_mut().unwrap().shader = shader;
+        Ok(descriptor)
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn queue_instanced_meshes(
+    transparent_3d_draw_functions: Res<DrawFunctions<Transparent3d>>,
+    instanced_mesh_pipeline: Res<InstancedMeshPipeline>,
+    msaa: Res<Msaa>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<InstancedMeshPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<GpuMesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    instanced_meshes: Query<(Entity, &ExtractedInstancedMesh)>,
+    mut views: Query<(
+        &ExtractedView,
+        Has<CameraStencil>,
+        &mut SortedRenderPhase<Transparent3d>,
+    )>,
+) {
+    let draw_instanced_mesh = transparent_3d_draw_functions
+        .read()
+        .id::<DrawInstancedMesh>();
+
+    let msaa_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
+
+    for (view, stencil, mut transparent_phase) in &mut views {
+        let mut view_key = msaa_key | MeshPipelineKey::from_hdr(view.hdr);
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+        let rangefinder = view.rangefinder3d();
+        for (entity, instanced_mesh) in &instanced_meshes {
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(entity) else {
+                continue;
+            };
+            let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else {
+                continue;
+            };
+            let key = InstancedMeshPipelineKey {
+                mesh_key: view_key
+                    | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology()),
+                gpu_culling: instanced_mesh.culling == InstanceCulling::Gpu,
+                shader: instanced_mesh.shader,
+            };
+            let pipeline = match pipelines.specialize(
+                &pipeline_cache,
+                &instanced_mesh_pipeline,
+                key,
+                &mesh.layout,
+            ) {
+                Ok(pipeline) => pipeline,
+                Err(err) => {
+                    warn!("Failed to specialize the pipeline of instanced mesh {entity:?}: {err}");
+                    continue;
+                }
+            };
+            transparent_phase.add(Transparent3d {
+                entity,
+                pipeline,
+                draw_function: draw_instanced_mesh,
+                distance: rangefinder.distance_translation(&mesh_instance.translation),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+/// The instances of the instanced meshes drawn in a view.
+#[derive(Component)]
+pub struct ViewInstanceBuffer {
+    buffer: Option<Buffer>,
+    /// The range of the instances of each instanced mesh in the buffer.
+    ranges: EntityHashMap<Range<u32>>,
+}
+
+fn prepare_instance_buffers(
+    mut commands: Commands,
+    views: Query<(Entity, Option<&Frustum>), With<ExtractedView>>,
+    instanced_meshes: Query<(Entity, &ExtractedInstancedMesh)>,
+    render_device: Res<RenderDevice>,
+) {
+    let mut instances = Vec::new();
+    for (view, frustum) in &views {
+        instances.clear();
+        let mut ranges = EntityHashMap::default();
+        for (entity, instanced_mesh) in &instanced_meshes {
+            let range = push_view_instances(&mut instances, instanced_mesh, frustum);
+            ranges.insert(entity, range);
+        }
+
+        let buffer = (!instances.is_empty()).then(|| {
+            render_device.create_buffer_with_data(&BufferInitDescriptor {
+                label: Some("view_instance_buffer"),
+                contents: bytemuck::cast_slice(instances.as_slice()),
+                usage: BufferUsages::VERTEX,
+            })
+        });
+        commands
+            .entity(view)
+            .insert(ViewInstanceBuffer { buffer, ranges });
+    }
+}
+
+/// Appends the instances of `instanced_mesh` drawn in a view with `frustum` to `instances`,
+/// returning their range.
+fn push_view_instances(
+    instances: &mut Vec<InstanceData>,
+    instanced_mesh: &ExtractedInstancedMesh,
+    frustum: Option<&Frustum>,
+) -> Range<u32> {
+    let start = instances.len() as u32;
+    match (instanced_mesh.culling, frustum, instanced_mesh.mesh_aabb) {
+        (InstanceCulling::Cpu, Some(frustum), Some(mesh_aabb)) => {
+            instances.extend(
+                instanced_mesh
+                    .instances
+                    .iter()
+                    .zip(&instanced_mesh.transforms)
+                    .filter(|(_, transform)| {
+                        frustum.intersects_obb(&mesh_aabb, transform, true, false)
+                    })
+                    .map(|(instance, _)| *instance),
+            );
+        }
+        _ => instances.extend_from_slice(&instanced_mesh.instances),
+    }
+    start..instances.len() as u32
+}
+
+type DrawInstancedMesh = (SetItemPipeline, SetMeshViewBindGroup<0>, DrawMeshInstances);
+
+/// Draws the instances of an instanced mesh that are visible from the view.
+pub struct DrawMeshInstances;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawMeshInstances {
+    type Param = (SRes<RenderAssets<GpuMesh>>, SRes<RenderMeshInstances>);
+    type ViewQuery = Read<ViewInstanceBuffer>;
+    type ItemQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        instance_buffer: &'w ViewInstanceBuffer,
+        _entity: Option<()>,
+        (meshes, render_mesh_instances): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(item.entity())
+        else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Failure;
+        };
+        let (Some(buffer), Some(instances)) = (
+            &instance_buffer.buffer,
+            instance_buffer.ranges.get(&item.entity()),
+        ) else {
+            return RenderCommandResult::Failure;
+        };
+        // All the instances may have been culled.
+        if instances.is_empty() {
+            return RenderCommandResult::Success;
+        }
+
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_vertex_buffer(1, buffer.slice(..));
+
+        match &gpu_mesh.buffer_info {
+            GpuBufferInfo::Indexed {
+                buffer,
+                index_format,
+                count,
+            } => {
+                pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(0..*count, 0, instances.clone());
+            }
+            GpuBufferInfo::NonIndexed => {
+                pass.draw(0..gpu_mesh.vertex_count, instances.clone());
+            }
+        }
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{Mat4, Vec3};
+    use bevy_render::{extract_component::ExtractComponent, primitives::Frustum};
+    use bevy_transform::components::{GlobalTransform, Transform};
+
+    use super::{
+        push_view_instances, Aabb, ExtractedInstancedMesh, InstanceCulling, InstancedMesh,
+        InstancedMeshAabb, MeshInstance,
+    };
+
+    /// An instanced mesh of unit cubes at x = -10, 0 and 10, with custom data holding their index.
+    fn extract(culling: InstanceCulling, transform: GlobalTransform) -> ExtractedInstancedMesh {
+        let instanced_mesh = InstancedMesh::new((0..3).map(|index| {
+            MeshInstance::new(Transform::from_xyz(index as f32 * 10.0 - 10.0, 0.0, 0.0))
+                .with_data(Vec3::ZERO.extend(index as f32))
+        }))
+        .with_culling(culling);
+        let mesh_aabb = InstancedMeshAabb(Aabb::from_min_max(Vec3::splat(-0.5), Vec3::splat(0.5)));
+        ExtractedInstancedMesh::extract_component((&instanced_mesh, &transform, Some(&mesh_aabb)))
+            .unwrap()
+    }
+
+    /// A frustum seeing the space between x = -5 and x = 5.
+    fn frustum() -> Frustum {
+        Frustum::from_view_projection(&Mat4::orthographic_rh(-5.0, 5.0, -5.0, 5.0, -5.0, 5.0))
+    }
+
+    #[test]
+    fn cpu_culling_keeps_the_visible_instances() {
+        let instanced_mesh = extract(InstanceCulling::Cpu, GlobalTransform::IDENTITY);
+        let mut instances = Vec::new();
+        let range = push_view_instances(&mut instances, &instanced_mesh, Some(&frustum()));
+        assert_eq!(range, 0..1);
+        assert_eq!(instances[0].data.w, 1.0);
+        // The instance is at the origin, with a bounding sphere around the cube.
+        assert_eq!(instances[0].bounds.truncate(), Vec3::ZERO);
+        assert!((instances[0].bounds.w - 0.75_f32.sqrt()).abs() < 1e-6);
+
+        // The instances are culled in world space, after the transform of the entity.
+        let instanced_mesh = extract(
+            InstanceCulling::Cpu,
+            GlobalTransform::from_xyz(10.0, 0.0, 0.0),
+        );
+        let range = push_view_instances(&mut instances, &instanced_mesh, Some(&frustum()));
+        assert_eq!(range, 1..2);
+        assert_eq!(instances[1].data.w, 0.0);
+        assert_eq!(instances[1].model[0].w, 0.0);
+    }
+
+    #[test]
+    fn instances_are_kept_without_cpu_culling() {
+        let mut instances = Vec::new();
+        for (culling, frustum) in [
+            (InstanceCulling::None, Some(frustum())),
+            (InstanceCulling::Gpu, Some(frustum())),
+            (InstanceCulling::Cpu, None),
+        ] {
+            let instanced_mesh = extract(culling, GlobalTransform::IDENTITY);
+            let start = instances.len() as u32;
+            let range = push_view_instances(&mut instances, &instanced_mesh, frustum.as_ref());
+            assert_eq!(range, start..start + 3);
+        }
+        let indices: Vec<_> = instances.iter().map(|instance| instance.data.w).collect();
+        assert_eq!(indices, [0.0, 1.0, 2.0].repeat(3));
+    }
+}
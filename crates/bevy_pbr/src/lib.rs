@@ -22,36 +22,50 @@ pub mod experimental {
 }
 
 mod bundle;
+mod crowd;
+mod custom_phase;
 pub mod deferred;
 mod extended_material;
 mod fog;
+mod instanced_mesh;
 mod light;
 mod light_probe;
 mod lightmap;
 mod material;
+mod outline;
 mod parallax;
 mod pbr_material;
 mod prepass;
 mod render;
 mod ssao;
+mod stencil;
+mod time_of_day;
 mod volumetric_fog;
+mod water;
 
 use bevy_color::{Color, LinearRgba};
 use std::marker::PhantomData;
 
 pub use bundle::*;
+pub use crowd::*;
+pub use custom_phase::*;
 pub use extended_material::*;
 pub use fog::*;
+pub use instanced_mesh::*;
 pub use light::*;
 pub use light_probe::*;
 pub use lightmap::*;
 pub use material::*;
+pub use outline::*;
 pub use parallax::*;
 pub use pbr_material::*;
 pub use prepass::*;
 pub use render::*;
 pub use ssao::*;
+pub use stencil::*;
+pub use time_of_day::*;
 pub use volumetric_fog::*;
+pub use water::*;
 
 pub mod prelude {
     #[doc(hidden)]
@@ -87,6 +101,11 @@ pub mod graph {
         VolumetricFog,
         /// Label for the compute shader instance data building pass.
         GpuPreprocess,
+        /// Label for the pass blending the captures of dynamic reflection probes into their
+        /// cubemaps.
+        ReflectionProbeBlend,
+        /// Label for the pass drawing the outlines of [`Outlined`](crate::Outlined) meshes.
+        Outline,
     }
 }
 
@@ -281,6 +300,8 @@ impl Plugin for PbrPlugin {
             .register_type::<Cascades>()
             .register_type::<CascadesVisibleEntities>()
             .register_type::<ClusterConfig>()
+            .register_type::<ClusterDebugOverlay>()
+            .register_type::<ClusterLightStats>()
             .register_type::<CubemapVisibleEntities>()
             .register_type::<DirectionalLight>()
             .register_type::<DirectionalLightShadowMap>()
@@ -291,6 +312,8 @@ impl Plugin for PbrPlugin {
             .register_type::<SpotLight>()
             .register_type::<FogSettings>()
             .register_type::<ShadowFilteringMethod>()
+            .register_type::<MaterialStencil>()
+            .register_type::<StencilRef>()
             .init_resource::<AmbientLight>()
             .init_resource::<GlobalVisiblePointLights>()
             .init_resource::<DirectionalLightShadowMap>()
@@ -310,6 +333,7 @@ impl Plugin for PbrPlugin {
                 FogPlugin,
                 ExtractResourcePlugin::<DefaultOpaqueRendererMethod>::default(),
                 ExtractComponentPlugin::<ShadowFilteringMethod>::default(),
+                ExtractComponentPlugin::<ClusterDebugOverlay>::default(),
                 LightmapPlugin,
                 LightProbePlugin,
                 PbrProjectionPlugin::<Projection>::default(),
@@ -320,6 +344,7 @@ impl Plugin for PbrPlugin {
                 },
                 VolumetricFogPlugin,
             ))
+            .add_plugins((CrowdPlugin, InstancedMeshPlugin, OutlinePlugin, WaterPlugin))
             .configure_sets(
                 PostUpdate,
                 (
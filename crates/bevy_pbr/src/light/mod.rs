@@ -532,6 +532,22 @@ pub enum ClusterFarZMode {
     Constant(f32),
 }
 
+/// Configure how the depth range between the first depth slice and the far `Z` plane is divided
+/// into slices for clustered forward rendering with a perspective projection.
+///
+/// Orthographic projections always use evenly spaced slices.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Reflect)]
+#[reflect(Default)]
+pub enum ClusterZSlicing {
+    /// Slices grow exponentially with depth, so that clusters stay roughly cubic in view space.
+    /// This suits scenes spanning a large depth range.
+    #[default]
+    Exponential,
+    /// Slices have the same depth. This spends more clusters far from the camera, which suits
+    /// views where lights are spread evenly over a bounded depth range, such as top-down cameras.
+    Linear,
+}
+
 /// Configure the depth-slicing strategy for clustered forward rendering
 #[derive(Debug, Copy, Clone, Reflect)]
 #[reflect(Default)]
@@ -540,6 +556,8 @@ pub struct ClusterZConfig {
     pub first_slice_depth: f32,
     /// Strategy for how to evaluate the far `Z` plane of the furthest depth slice
     pub far_z_mode: ClusterFarZMode,
+    /// How the depth slices after the first one are distributed up to the far `Z` plane
+    pub z_slicing: ClusterZSlicing,
 }
 
 impl Default for ClusterZConfig {
@@ -547,6 +565,7 @@ impl Default for ClusterZConfig {
         Self {
             first_slice_depth: 5.0,
             far_z_mode: ClusterFarZMode::MaxLightRange,
+            z_slicing: ClusterZSlicing::Exponential,
         }
     }
 }
@@ -653,6 +672,15 @@ impl ClusterConfig {
         }
     }
 
+    fn z_slicing(&self) -> ClusterZSlicing {
+        match self {
+            ClusterConfig::None | ClusterConfig::Single => ClusterZSlicing::Exponential,
+            ClusterConfig::XYZ { z_config, .. } | ClusterConfig::FixedZ { z_config, .. } => {
+                z_config.z_slicing
+            }
+        }
+    }
+
     fn dynamic_resizing(&self) -> bool {
         match self {
             ClusterConfig::None | ClusterConfig::Single => false,
@@ -666,6 +694,85 @@ impl ClusterConfig {
     }
 }
 
+/// Statistics about how many lights affect the clusters of a view, updated every frame while
+/// lights are assigned to clusters.
+///
+/// This is added to every camera that uses clustered forward rendering. Insert it yourself to
+/// change the `warn_threshold`.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct ClusterLightStats {
+    /// A warning is logged when clusters start being affected by more lights than this. Defaults
+    /// to 64, the light count at which [`ClusterDebugOverlay::LightComplexity`] saturates.
+    pub warn_threshold: usize,
+    /// The number of lights affecting the busiest cluster
+    pub max_lights: usize,
+    /// The mean number of lights affecting a cluster
+    pub mean_lights: f32,
+    /// The total number of light indices of all clusters. When storage buffers are not
+    /// supported, indices beyond [`ViewClusterBindings::MAX_INDICES`] are dropped.
+    pub light_indices: usize,
+    /// The number of clusters affected by more than `warn_threshold` lights
+    pub overloaded_clusters: usize,
+}
+
+impl Default for ClusterLightStats {
+    fn default() -> Self {
+        Self {
+            warn_threshold: 64,
+            max_lights: 0,
+            mean_lights: 0.0,
+            light_indices: 0,
+            overloaded_clusters: 0,
+        }
+    }
+}
+
+impl ClusterLightStats {
+    fn update(&mut self, view_entity: Entity, clusters: &Clusters) {
+        let was_overloaded = self.overloaded_clusters > 0;
+
+        self.max_lights = 0;
+        self.light_indices = 0;
+        self.overloaded_clusters = 0;
+        for cluster_lights in &clusters.lights {
+            let count = cluster_lights.len();
+            self.max_lights = self.max_lights.max(count);
+            self.light_indices += count;
+            if count > self.warn_threshold {
+                self.overloaded_clusters += 1;
+            }
+        }
+        self.mean_lights = if clusters.lights.is_empty() {
+            0.0
+        } else {
+            self.light_indices as f32 / clusters.lights.len() as f32
+        };
+
+        if self.overloaded_clusters > 0 && !was_overloaded {
+            warn!(
+                "{} clusters of view {:?} are affected by more than {} lights (at most {}). \
+                Consider using more clusters or reducing the range of the lights.",
+                self.overloaded_clusters, view_entity, self.warn_threshold, self.max_lights
+            );
+        }
+    }
+}
+
+/// Add this to a camera to overlay a visualization of its light clusters on the meshes it renders,
+/// which helps tuning its [`ClusterConfig`].
+#[derive(Component, ExtractComponent, Reflect, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub enum ClusterDebugOverlay {
+    /// Tints each depth slice with a different color
+    ZSlices,
+    /// Shows a heatmap of the number of lights affecting each cluster, going from green for no
+    /// lights to red for 64 or more lights
+    LightComplexity,
+    /// Tints each cluster with a random color
+    Coherency,
+}
+
 #[derive(Component, Debug, Default)]
 pub struct Clusters {
     /// Tile size
@@ -676,6 +783,8 @@ pub struct Clusters {
     /// and explicitly-configured to avoid having unnecessarily many slices close to the camera.
     pub(crate) near: f32,
     pub(crate) far: f32,
+    /// How the depth slices after the first one are distributed between `near` and `far`
+    pub(crate) z_slicing: ClusterZSlicing,
     pub(crate) lights: Vec<VisiblePointLights>,
 }
 
@@ -754,16 +863,20 @@ impl VisiblePointLights {
     }
 }
 
-// NOTE: Keep in sync with bevy_pbr/src/render/pbr.wgsl
+// NOTE: Keep in sync with bevy_pbr/src/render/clustered_forward.wgsl
 fn view_z_to_z_slice(
     cluster_factors: Vec2,
     z_slices: u32,
     view_z: f32,
     is_orthographic: bool,
+    z_slicing: ClusterZSlicing,
 ) -> u32 {
     let z_slice = if is_orthographic {
         // NOTE: view_z is correct in the orthographic case
         ((view_z - cluster_factors.x) * cluster_factors.y).floor() as u32
+    } else if z_slicing == ClusterZSlicing::Linear {
+        // NOTE: Depths closer than the first slice depth give negative values, which saturate to 0
+        ((-view_z - cluster_factors.x) * cluster_factors.y + 1.0) as u32
     } else {
         // NOTE: had to use -view_z to make it positive else log(negative) is nan
         ((-view_z).ln() * cluster_factors.x - cluster_factors.y + 1.0) as u32
@@ -780,6 +893,7 @@ fn z_slice_to_view_z(
     z_slices: u32,
     z_slice: u32,
     is_orthographic: bool,
+    z_slicing: ClusterZSlicing,
 ) -> f32 {
     if is_orthographic {
         return -near - (far - near) * z_slice as f32 / z_slices as f32;
@@ -788,6 +902,8 @@ fn z_slice_to_view_z(
     // Perspective
     if z_slice == 0 {
         0.0
+    } else if z_slicing == ClusterZSlicing::Linear {
+        -near - (far - near) * (z_slice - 1) as f32 / (z_slices - 1).max(1) as f32
     } else {
         -near * (far / near).powf((z_slice - 1) as f32 / (z_slices - 1) as f32)
     }
@@ -797,6 +913,7 @@ fn ndc_position_to_cluster(
     cluster_dimensions: UVec3,
     cluster_factors: Vec2,
     is_orthographic: bool,
+    z_slicing: ClusterZSlicing,
     ndc_p: Vec3,
     view_z: f32,
 ) -> UVec3 {
@@ -808,6 +925,7 @@ fn ndc_position_to_cluster(
         cluster_dimensions.z,
         view_z,
         is_orthographic,
+        z_slicing,
     );
     xy.as_uvec2()
         .extend(z_slice)
@@ -933,6 +1051,7 @@ fn compute_aabb_for_cluster(
     screen_size: Vec2,
     inverse_projection: Mat4,
     is_orthographic: bool,
+    z_slicing: ClusterZSlicing,
     cluster_dimensions: UVec3,
     ijk: UVec3,
 ) -> Aabb {
@@ -964,18 +1083,41 @@ fn compute_aabb_for_cluster(
         let p_min = screen_to_view(screen_size, inverse_projection, p_min, 1.0);
         let p_max = screen_to_view(screen_size, inverse_projection, p_max, 1.0);
 
-        let z_far_over_z_near = -z_far / -z_near;
-        let cluster_near = if ijk.z == 0.0 {
-            0.0
-        } else {
-            -z_near * z_far_over_z_near.powf((ijk.z - 1.0) / (cluster_dimensions.z - 1) as f32)
-        };
-        // NOTE: This could be simplified to:
-        // cluster_far = cluster_near * z_far_over_z_near;
-        let cluster_far = if cluster_dimensions.z == 1 {
-            -z_far
+        let (cluster_near, cluster_far) = if z_slicing == ClusterZSlicing::Linear {
+            let slice_index = ijk.z as u32;
+            (
+                z_slice_to_view_z(
+                    z_near,
+                    z_far,
+                    cluster_dimensions.z,
+                    slice_index,
+                    false,
+                    z_slicing,
+                ),
+                z_slice_to_view_z(
+                    z_near,
+                    z_far,
+                    cluster_dimensions.z,
+                    slice_index + 1,
+                    false,
+                    z_slicing,
+                ),
+            )
         } else {
-            -z_near * z_far_over_z_near.powf(ijk.z / (cluster_dimensions.z - 1) as f32)
+            let z_far_over_z_near = -z_far / -z_near;
+            let cluster_near = if ijk.z == 0.0 {
+                0.0
+            } else {
+                -z_near * z_far_over_z_near.powf((ijk.z - 1.0) / (cluster_dimensions.z - 1) as f32)
+            };
+            // NOTE: This could be simplified to:
+            // cluster_far = cluster_near * z_far_over_z_near;
+            let cluster_far = if cluster_dimensions.z == 1 {
+                -z_far
+            } else {
+                -z_near * z_far_over_z_near.powf(ijk.z / (cluster_dimensions.z - 1) as f32)
+            };
+            (cluster_near, cluster_far)
         };
 
         // Calculate the four intersection points of the min and max points with the cluster near and far planes
@@ -1075,6 +1217,7 @@ pub(crate) fn assign_lights_to_clusters(
         &mut Clusters,
         Option<&RenderLayers>,
         Option<&mut VisiblePointLights>,
+        Option<&mut ClusterLightStats>,
     )>,
     point_lights_query: Query<(
         Entity,
@@ -1162,7 +1305,7 @@ pub(crate) fn assign_lights_to_clusters(
         // check each light against each view's frustum, keep only those that affect at least one of our views
         let frusta: Vec<_> = views
             .iter()
-            .map(|(_, _, _, frustum, _, _, _, _)| *frustum)
+            .map(|(_, _, _, frustum, _, _, _, _, _)| *frustum)
             .collect();
         let mut lights_in_view_count = 0;
         lights.retain(|light| {
@@ -1203,6 +1346,7 @@ pub(crate) fn assign_lights_to_clusters(
         clusters,
         maybe_layers,
         mut visible_lights,
+        light_stats,
     ) in &mut views
     {
         let view_layers = maybe_layers.unwrap_or_default();
@@ -1228,6 +1372,7 @@ pub(crate) fn assign_lights_to_clusters(
         let view_inv_scale_max = view_inv_scale.abs().max_element();
         let inverse_view_transform = view_transform.inverse();
         let is_orthographic = camera.projection_matrix().w_axis.w == 1.0;
+        let z_slicing = config.z_slicing();
 
         let far_z = match config.far_z_mode() {
             ClusterFarZMode::MaxLightRange => {
@@ -1267,6 +1412,7 @@ pub(crate) fn assign_lights_to_clusters(
             far_z,
             requested_cluster_dimensions.z as f32,
             is_orthographic,
+            z_slicing,
         );
 
         if config.dynamic_resizing() {
@@ -1295,12 +1441,14 @@ pub(crate) fn assign_lights_to_clusters(
                     requested_cluster_dimensions.z,
                     light_aabb_min.z,
                     is_orthographic,
+                    z_slicing,
                 );
                 let z_cluster_max = view_z_to_z_slice(
                     cluster_factors,
                     requested_cluster_dimensions.z,
                     light_aabb_max.z,
                     is_orthographic,
+                    z_slicing,
                 );
                 let z_count =
                     z_cluster_min.max(z_cluster_max) - z_cluster_min.min(z_cluster_max) + 1;
@@ -1345,6 +1493,7 @@ pub(crate) fn assign_lights_to_clusters(
         clusters.update(screen_size, requested_cluster_dimensions);
         clusters.near = first_slice_depth;
         clusters.far = far_z;
+        clusters.z_slicing = z_slicing;
 
         // NOTE: Maximum 4096 clusters due to uniform buffer size constraints
         debug_assert!(
@@ -1419,7 +1568,14 @@ pub(crate) fn assign_lights_to_clusters(
 
         let z_slices = clusters.dimensions.z;
         for z in 0..=z_slices {
-            let view_z = z_slice_to_view_z(first_slice_depth, far_z, z_slices, z, is_orthographic);
+            let view_z = z_slice_to_view_z(
+                first_slice_depth,
+                far_z,
+                z_slices,
+                z,
+                is_orthographic,
+                z_slicing,
+            );
             let normal = -Vec3::Z;
             let d = view_z * normal.z;
             z_planes.push(HalfSpace::new(normal.extend(d)));
@@ -1456,6 +1612,7 @@ pub(crate) fn assign_lights_to_clusters(
                     clusters.dimensions,
                     cluster_factors,
                     is_orthographic,
+                    z_slicing,
                     light_aabb_xy_ndc_z_view_min,
                     light_aabb_xy_ndc_z_view_min.z,
                 );
@@ -1463,6 +1620,7 @@ pub(crate) fn assign_lights_to_clusters(
                     clusters.dimensions,
                     cluster_factors,
                     is_orthographic,
+                    z_slicing,
                     light_aabb_xy_ndc_z_view_max,
                     light_aabb_xy_ndc_z_view_max.z,
                 );
@@ -1497,6 +1655,7 @@ pub(crate) fn assign_lights_to_clusters(
                     clusters.dimensions,
                     cluster_factors,
                     is_orthographic,
+                    z_slicing,
                     light_center_ndc,
                     view_light_sphere.center.z,
                 );
@@ -1602,6 +1761,7 @@ pub(crate) fn assign_lights_to_clusters(
                                         screen_size.as_vec2(),
                                         inverse_projection,
                                         is_orthographic,
+                                        z_slicing,
                                         clusters.dimensions,
                                         UVec3::new(x, y, z),
                                     );
@@ -1662,6 +1822,14 @@ pub(crate) fn assign_lights_to_clusters(
                 ..Default::default()
             });
         }
+
+        if let Some(mut light_stats) = light_stats {
+            light_stats.update(view_entity, clusters);
+        } else {
+            let mut light_stats = ClusterLightStats::default();
+            light_stats.update(view_entity, clusters);
+            commands.entity(view_entity).insert(light_stats);
+        }
     }
 }
 
@@ -0,0 +1,624 @@
+//! Reflection probes captured at runtime.
+//!
+//! A [`DynamicReflectionProbe`] renders the scene around a reflection probe into the cubemaps of
+//! its [`EnvironmentMapLight`], so that reflections follow changes of the lighting, such as a
+//! light turned on in a room, without baking environment maps offline.
+//!
+//! Each probe is captured by six cameras, one per face of its cubemap, which are only active for
+//! the frame of a capture. Captures are scheduled by priority, then round-robin among the probes
+//! of a same priority, within the [`ReflectionProbeCaptureBudget`] of each frame. A new capture
+//! then blends into the cubemaps over a few frames, so that reflections don't pop.
+//!
+//! The cubemaps aren't prefiltered like baked environment maps: the mips of the specular cubemap
+//! are box-filtered from the capture, and the diffuse cubemap is a small mip of the specular one.
+//! This is a cheap approximation of rough reflections and of irradiance, suited to interiors.
+
+use std::{cmp::Reverse, f32::consts::FRAC_PI_2, time::Duration};
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AssetId, Assets, Handle};
+use bevy_color::LinearRgba;
+use bevy_core_pipeline::{
+    core_3d::Camera3dBundle,
+    fullscreen_vertex_shader::fullscreen_shader_vertex_state,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::prelude::*;
+use bevy_math::Vec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, Exposure, PerspectiveProjection, Projection, RenderTarget},
+    graph::CameraDriverLabel,
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_resource::{
+        binding_types::{sampler, texture_2d},
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, BlendComponent, BlendFactor,
+        BlendOperation, BlendState, CachedRenderPipelineId, ColorTargetState, ColorWrites,
+        Extent3d, FilterMode, FragmentState, ImageCopyTexture, LoadOp, MultisampleState,
+        Operations, Origin3d, PipelineCache, PrimitiveState, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipeline, RenderPipelineDescriptor, Sampler,
+        SamplerBindingType, SamplerDescriptor, Shader, ShaderStages, StoreOp, TextureAspect,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
+        TextureView, TextureViewDescriptor, TextureViewDimension,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{GpuImage, Image, TextureFormatPixelInfo},
+    Extract, ExtractSchedule, RenderApp,
+};
+use bevy_time::Time;
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+use crate::{graph::NodePbr, light_probe::environment_map::EnvironmentMapLight};
+
+pub const REFLECTION_PROBE_BLEND_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(4218950714663102458);
+
+/// The format of the captures and cubemaps of dynamic reflection probes.
+const CAPTURE_FORMAT: TextureFormat = TextureFormat::Rgba16Float;
+
+/// The mip of the specular cubemap copied to the diffuse cubemap, as a number of halvings of the
+/// resolution of the probe.
+const DIFFUSE_MIP: u32 = 4;
+
+/// The direction and up vector of the camera rendering each face of a cubemap, in the order of
+/// the layers of the cubemap.
+///
+/// Environment maps are sampled with the `Z` axis flipped, so the faces are those of a cubemap
+/// in a left-handed space.
+const CUBEMAP_FACES: [(Vec3, Vec3); 6] = [
+    (Vec3::X, Vec3::Y),
+    (Vec3::NEG_X, Vec3::Y),
+    (Vec3::Y, Vec3::Z),
+    (Vec3::NEG_Y, Vec3::NEG_Z),
+    (Vec3::NEG_Z, Vec3::Y),
+    (Vec3::Z, Vec3::Y),
+];
+
+/// Adds support for [`DynamicReflectionProbe`]s.
+pub struct DynamicReflectionProbePlugin;
+
+impl Plugin for DynamicReflectionProbePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            REFLECTION_PROBE_BLEND_SHADER_HANDLE,
+            "reflection_probe_blend.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<DynamicReflectionProbe>()
+            .register_type::<ReflectionProbeCaptureBudget>()
+            .init_resource::<ReflectionProbeCaptureBudget>()
+            .add_systems(
+                PostUpdate,
+                (
+                    setup_dynamic_reflection_probes,
+                    schedule_reflection_probe_captures,
+                )
+                    .chain()
+                    .before(TransformSystem::TransformPropagate),
+            );
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<ReflectionProbeBlends>()
+            .add_systems(ExtractSchedule, extract_reflection_probe_blends);
+
+        let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        render_graph.add_node(NodePbr::ReflectionProbeBlend, ReflectionProbeBlendNode);
+        // Blend before the cameras render, so that they see the updated cubemaps.
+        render_graph.add_node_edge(NodePbr::ReflectionProbeBlend, CameraDriverLabel);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<ReflectionProbeBlendPipeline>();
+    }
+}
+
+/// Captures the [`EnvironmentMapLight`] of a reflection probe at runtime.
+///
+/// Add this component to an entity with a [`LightProbe`](crate::LightProbe) and a
+/// [`Transform`]. When it is added, the cubemaps of the probe are created and set as its
+/// [`EnvironmentMapLight`], replacing any other, and the cameras capturing them are spawned.
+/// Later changes of the resolution are ignored.
+///
+/// The probe is captured from its center, as often as its [`DynamicReflectionProbe::interval`]
+/// allows within the [`ReflectionProbeCaptureBudget`].
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct DynamicReflectionProbe {
+    /// The size in pixels of the faces of the specular cubemap, rounded up to a power of two.
+    pub resolution: u32,
+    /// The minimum time between two captures of the probe, measured with [`Time`].
+    ///
+    /// A probe with a zero interval is captured as often as the budget allows.
+    pub interval: Duration,
+    /// The priority of the probe. When more probes are due for a capture than the budget allows,
+    /// probes of a higher priority are captured first.
+    pub priority: i32,
+    /// The number of frames over which a new capture replaces the previous one.
+    pub blend_frames: u32,
+}
+
+impl Default for DynamicReflectionProbe {
+    fn default() -> Self {
+        Self {
+            resolution: 256,
+            interval: Duration::from_secs(1),
+            priority: 0,
+            blend_frames: 30,
+        }
+    }
+}
+
+/// The number of [`DynamicReflectionProbe`]s captured per frame.
+///
+/// Each capture renders the scene six times, once per face of the cubemap of the probe.
+#[derive(Resource, Clone, Copy, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct ReflectionProbeCaptureBudget {
+    /// The maximum number of probes captured in a frame.
+    pub probes_per_frame: usize,
+}
+
+impl Default for ReflectionProbeCaptureBudget {
+    fn default() -> Self {
+        Self {
+            probes_per_frame: 1,
+        }
+    }
+}
+
+/// The capture state of a [`DynamicReflectionProbe`], added along with its cubemaps.
+#[derive(Component, Debug)]
+pub struct ReflectionProbeCapture {
+    cameras: [Entity; 6],
+    faces: [Handle<Image>; 6],
+    specular: Handle<Image>,
+    diffuse: Handle<Image>,
+    diffuse_mip: u32,
+    captures: u32,
+    last_capture: Option<Duration>,
+    capturing: bool,
+    blend_remaining: u32,
+    blend_factor: Option<f32>,
+}
+
+impl ReflectionProbeCapture {
+    /// Returns the number of times the probe was captured.
+    pub fn captures(&self) -> u32 {
+        self.captures
+    }
+
+    /// Returns the [`Time::elapsed`] of the last capture of the probe, if any.
+    pub fn last_capture(&self) -> Option<Duration> {
+        self.last_capture
+    }
+
+    /// Returns the cameras capturing each face of the cubemap of the probe.
+    pub fn cameras(&self) -> [Entity; 6] {
+        self.cameras
+    }
+}
+
+/// Creates the cubemaps and the capture cameras of new [`DynamicReflectionProbe`]s.
+pub fn setup_dynamic_reflection_probes(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    probes: Query<(Entity, &DynamicReflectionProbe), Without<ReflectionProbeCapture>>,
+) {
+    for (entity, probe) in &probes {
+        let resolution = probe.resolution.max(1).next_power_of_two();
+        let mip_level_count = resolution.ilog2() + 1;
+        let diffuse_mip = DIFFUSE_MIP.min(mip_level_count - 1);
+
+        let specular = images.add(new_capture_image(
+            resolution,
+            6,
+            mip_level_count,
+            TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC
+                | TextureUsages::COPY_DST,
+        ));
+        let diffuse = images.add(new_capture_image(
+            resolution >> diffuse_mip,
+            6,
+            1,
+            TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+        ));
+
+        let exposure = Exposure::default();
+        let faces = CUBEMAP_FACES.map(|_| {
+            images.add(new_capture_image(
+                resolution,
+                1,
+                1,
+                TextureUsages::RENDER_ATTACHMENT
+                    | TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_DST,
+            ))
+        });
+        let cameras = std::array::from_fn(|i| {
+            commands
+                .spawn(Camera3dBundle {
+                    camera: Camera {
+                        target: RenderTarget::Image(faces[i].clone()),
+                        is_active: false,
+                        hdr: true,
+                        ..Default::default()
+                    },
+                    projection: Projection::Perspective(PerspectiveProjection {
+                        fov: FRAC_PI_2,
+                        aspect_ratio: 1.0,
+                        ..Default::default()
+                    }),
+                    // Keep the linear radiance of the scene.
+                    tonemapping: Tonemapping::None,
+                    deband_dither: DebandDither::Disabled,
+                    exposure,
+                    ..Default::default()
+                })
+                .id()
+        });
+
+        commands.entity(entity).insert((
+            EnvironmentMapLight {
+                diffuse_map: diffuse.clone(),
+                specular_map: specular.clone(),
+                // Undo the exposure of the capture.
+                intensity: 1.0 / exposure.exposure(),
+            },
+            ReflectionProbeCapture {
+                cameras,
+                faces,
+                specular,
+                diffuse,
+                diffuse_mip,
+                captures: 0,
+                last_capture: None,
+                capturing: false,
+                blend_remaining: 0,
+                blend_factor: None,
+            },
+        ));
+    }
+}
+
+/// Schedules the captures of the [`DynamicReflectionProbe`]s, and advances the blending of the
+/// previous captures.
+pub fn schedule_reflection_probe_captures(
+    time: Option<Res<Time>>,
+    budget: Res<ReflectionProbeCaptureBudget>,
+    mut probes: Query<(
+        &GlobalTransform,
+        &DynamicReflectionProbe,
+        &mut ReflectionProbeCapture,
+    )>,
+    mut cameras: Query<(&mut Camera, &mut Transform)>,
+) {
+    let now = time.map(|time| time.elapsed()).unwrap_or_default();
+
+    for (_, probe, mut capture) in &mut probes {
+        // The capture of the previous frame was rendered, blend it in from this frame on.
+        if capture.capturing {
+            capture.capturing = false;
+            capture.captures += 1;
+            for entity in capture.cameras {
+                if let Ok((mut camera, _)) = cameras.get_mut(entity) {
+                    camera.is_active = false;
+                }
+            }
+            // There is nothing to blend with before the first capture.
+            capture.blend_remaining = if capture.captures == 1 {
+                1
+            } else {
+                probe.blend_frames.max(1)
+            };
+        }
+
+        // Blending by `1 / n` with `n` frames remaining moves linearly to the new capture.
+        capture.blend_factor =
+            (capture.blend_remaining > 0).then(|| 1.0 / capture.blend_remaining as f32);
+        capture.blend_remaining = capture.blend_remaining.saturating_sub(1);
+    }
+
+    let mut due: Vec<_> = probes
+        .iter_mut()
+        .filter(|(_, probe, capture)| {
+            capture
+                .last_capture
+                .map_or(true, |last| now.saturating_sub(last) >= probe.interval)
+        })
+        .collect();
+    // Probes never captured come first, then the ones captured the longest ago.
+    due.sort_by_key(|(_, probe, capture)| (Reverse(probe.priority), capture.last_capture));
+
+    for (transform, _, mut capture) in due.into_iter().take(budget.probes_per_frame) {
+        capture.capturing = true;
+        capture.last_capture = Some(now);
+        for (entity, (direction, up)) in capture.cameras.into_iter().zip(CUBEMAP_FACES) {
+            if let Ok((mut camera, mut camera_transform)) = cameras.get_mut(entity) {
+                camera.is_active = true;
+                *camera_transform =
+                    Transform::from_translation(transform.translation()).looking_to(direction, up);
+            }
+        }
+    }
+}
+
+fn new_capture_image(size: u32, layers: u32, mip_level_count: u32, usage: TextureUsages) -> Image {
+    let texel_count: u32 = (0..mip_level_count)
+        .map(|mip| (size >> mip).max(1).pow(2))
+        .sum();
+    Image {
+        data: vec![0; (texel_count * layers) as usize * CAPTURE_FORMAT.pixel_size()],
+        texture_descriptor: TextureDescriptor {
+            label: Some("dynamic_reflection_probe_texture"),
+            size: Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: layers,
+            },
+            mip_level_count,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: CAPTURE_FORMAT,
+            usage,
+            view_formats: &[],
+        },
+        texture_view_descriptor: (layers == 6).then(|| TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            ..Default::default()
+        }),
+        asset_usage: RenderAssetUsages::RENDER_WORLD,
+        ..Default::default()
+    }
+}
+
+/// A capture of a dynamic reflection probe to blend into its cubemaps this frame.
+struct ReflectionProbeBlend {
+    faces: [AssetId<Image>; 6],
+    specular: AssetId<Image>,
+    diffuse: AssetId<Image>,
+    diffuse_mip: u32,
+    factor: f32,
+}
+
+/// The captures of dynamic reflection probes to blend into their cubemaps this frame.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct ReflectionProbeBlends(Vec<ReflectionProbeBlend>);
+
+fn extract_reflection_probe_blends(
+    mut blends: ResMut<ReflectionProbeBlends>,
+    probes: Extract<Query<&ReflectionProbeCapture>>,
+) {
+    blends.clear();
+    blends.extend(probes.iter().filter_map(|capture| {
+        Some(ReflectionProbeBlend {
+            faces: capture.faces.each_ref().map(Handle::id),
+            specular: capture.specular.id(),
+            diffuse: capture.diffuse.id(),
+            diffuse_mip: capture.diffuse_mip,
+            factor: capture.blend_factor?,
+        })
+    }));
+}
+
+#[derive(Resource)]
+struct ReflectionProbeBlendPipeline {
+    bind_group_layout: BindGroupLayout,
+    sampler: Sampler,
+    pipeline_id: CachedRenderPipelineId,
+}
+
+impl FromWorld for ReflectionProbeBlendPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let bind_group_layout = render_device.create_bind_group_layout(
+            "reflection_probe_blend_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    sampler(SamplerBindingType::Filtering),
+                ),
+            ),
+        );
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let blend = BlendComponent {
+            src_factor: BlendFactor::Constant,
+            dst_factor: BlendFactor::OneMinusConstant,
+            operation: BlendOperation::Add,
+        };
+        let pipeline_id =
+            world
+                .resource_mut::<PipelineCache>()
+                .queue_render_pipeline(RenderPipelineDescriptor {
+                    label: Some("reflection_probe_blend_pipeline".into()),
+                    layout: vec![bind_group_layout.clone()],
+                    vertex: fullscreen_shader_vertex_state(),
+                    fragment: Some(FragmentState {
+                        shader: REFLECTION_PROBE_BLEND_SHADER_HANDLE,
+                        shader_defs: vec![],
+                        entry_point: "fragment".into(),
+                        targets: vec![Some(ColorTargetState {
+                            format: CAPTURE_FORMAT,
+                            blend: Some(BlendState {
+                                color: blend,
+                                alpha: blend,
+                            }),
+                            write_mask: ColorWrites::ALL,
+                        })],
+                    }),
+                    primitive: PrimitiveState::default(),
+                    depth_stencil: None,
+                    multisample: MultisampleState::default(),
+                    push_constant_ranges: vec![],
+                });
+
+        Self {
+            bind_group_layout,
+            sampler,
+            pipeline_id,
+        }
+    }
+}
+
+/// Blends the captures of dynamic reflection probes into their cubemaps, then regenerates their
+/// mips and diffuse cubemaps.
+struct ReflectionProbeBlendNode;
+
+impl Node for ReflectionProbeBlendNode {
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let blends = world.resource::<ReflectionProbeBlends>();
+        if blends.is_empty() {
+            return Ok(());
+        }
+        let pipeline = world.resource::<ReflectionProbeBlendPipeline>();
+        let Some(render_pipeline) = world
+            .resource::<PipelineCache>()
+            .get_render_pipeline(pipeline.pipeline_id)
+        else {
+            return Ok(());
+        };
+        let images = world.resource::<RenderAssets<GpuImage>>();
+
+        for blend in blends.iter() {
+            let (Some(specular), Some(diffuse)) =
+                (images.get(blend.specular), images.get(blend.diffuse))
+            else {
+                continue;
+            };
+            let Some(faces) = blend
+                .faces
+                .iter()
+                .map(|face| images.get(*face))
+                .collect::<Option<Vec<_>>>()
+            else {
+                continue;
+            };
+
+            for (layer, face) in (0..).zip(faces) {
+                let target = layer_view(specular, layer, 0);
+                blend_pass(
+                    render_context,
+                    pipeline,
+                    render_pipeline,
+                    &face.texture_view,
+                    &target,
+                    blend.factor,
+                );
+            }
+
+            for mip in 1..specular.mip_level_count {
+                for layer in 0..6 {
+                    let source = layer_view(specular, layer, mip - 1);
+                    let target = layer_view(specular, layer, mip);
+                    blend_pass(
+                        render_context,
+                        pipeline,
+                        render_pipeline,
+                        &source,
+                        &target,
+                        1.0,
+                    );
+                }
+            }
+
+            render_context.command_encoder().copy_texture_to_texture(
+                ImageCopyTexture {
+                    texture: &specular.texture,
+                    mip_level: blend.diffuse_mip,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                ImageCopyTexture {
+                    texture: &diffuse.texture,
+                    mip_level: 0,
+                    origin: Origin3d::ZERO,
+                    aspect: TextureAspect::All,
+                },
+                Extent3d {
+                    width: diffuse.size.x,
+                    height: diffuse.size.y,
+                    depth_or_array_layers: 6,
+                },
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a view of a single mip of a single face of a cubemap.
+fn layer_view(cubemap: &GpuImage, layer: u32, mip: u32) -> TextureView {
+    cubemap.texture.create_view(&TextureViewDescriptor {
+        label: Some("reflection_probe_face_view"),
+        dimension: Some(TextureViewDimension::D2),
+        base_mip_level: mip,
+        mip_level_count: Some(1),
+        base_array_layer: layer,
+        array_layer_count: Some(1),
+        ..Default::default()
+    })
+}
+
+/// Draws `source` over `target`, weighted by `factor`.
+fn blend_pass(
+    render_context: &mut RenderContext,
+    pipeline: &ReflectionProbeBlendPipeline,
+    render_pipeline: &RenderPipeline,
+    source: &TextureView,
+    target: &TextureView,
+    factor: f32,
+) {
+    let bind_group = render_context.render_device().create_bind_group(
+        "reflection_probe_blend_bind_group",
+        &pipeline.bind_group_layout,
+        &BindGroupEntries::sequential((source, &pipeline.sampler)),
+    );
+
+    let mut pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some("reflection_probe_blend_pass"),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            // Keep the previous capture to blend with.
+            ops: Operations {
+                load: LoadOp::Load,
+                store: StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+    pass.set_render_pipeline(render_pipeline);
+    pass.set_bind_group(0, &bind_group, &[]);
+    pass.set_blend_constant(LinearRgba::gray(factor));
+    pass.draw(0..3, 0..1);
+}
@@ -38,10 +38,13 @@ use crate::{
     },
 };
 
-use self::irradiance_volume::IrradianceVolume;
+use self::{
+    dynamic_reflection_probe::DynamicReflectionProbePlugin, irradiance_volume::IrradianceVolume,
+};
 
 pub const LIGHT_PROBE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(8954249792581071582);
 
+pub mod dynamic_reflection_probe;
 pub mod environment_map;
 pub mod irradiance_volume;
 
@@ -319,7 +322,8 @@ impl Plugin for LightProbePlugin {
 
         app.register_type::<LightProbe>()
             .register_type::<EnvironmentMapLight>()
-            .register_type::<IrradianceVolume>();
+            .register_type::<IrradianceVolume>()
+            .add_plugins(DynamicReflectionProbePlugin);
     }
 
     fn finish(&self, app: &mut App) {
@@ -0,0 +1,14 @@
+// Blends a captured face of a dynamic reflection probe into its cubemap, and downsamples the mips
+// of the cubemap. The weight of the source is the blend constant of the render pass.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+
+@fragment
+fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    // When downsampling, the sample falls between 2x2 texels of the larger mip, which the linear
+    // filter averages.
+    return textureSampleLevel(source, source_sampler, in.uv, 0.0);
+}
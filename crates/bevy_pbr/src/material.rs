@@ -7,8 +7,8 @@ use crate::*;
 use bevy_asset::{Asset, AssetId, AssetServer};
 use bevy_core_pipeline::{
     core_3d::{
-        AlphaMask3d, Camera3d, Opaque3d, Opaque3dBinKey, ScreenSpaceTransmissionQuality,
-        Transmissive3d, Transparent3d,
+        AlphaMask3d, Camera3d, CameraStencil, Opaque3d, Opaque3dBinKey,
+        ScreenSpaceTransmissionQuality, Transmissive3d, Transparent3d,
     },
     prepass::{
         DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, OpaqueNoLightmap3dBinKey,
@@ -144,6 +144,13 @@ pub trait Material: Asset + AsBindGroup + Clone + Sized {
         false
     }
 
+    #[inline]
+    /// Returns how the material uses the stencil buffer of the cameras with a
+    /// [`CameraStencil`](bevy_core_pipeline::core_3d::CameraStencil), see [`MaterialStencil`].
+    fn stencil(&self) -> MaterialStencil {
+        MaterialStencil::Disabled
+    }
+
     /// Returns this material's prepass vertex shader. If [`ShaderRef::Default`] is returned, the default prepass vertex shader
     /// will be used.
     ///
@@ -420,11 +427,12 @@ impl<M: Material> FromWorld for MaterialPipeline<M> {
     }
 }
 
-type DrawMaterial<M> = (
+pub(crate) type DrawMaterial<M> = (
     SetItemPipeline,
     SetMeshViewBindGroup<0>,
     SetMeshBindGroup<1>,
     SetMaterialBindGroup<M, 2>,
+    SetMeshStencilReference,
     DrawMesh,
 );
 
@@ -493,6 +501,17 @@ pub const fn tonemapping_pipeline_key(tonemapping: Tonemapping) -> MeshPipelineK
     }
 }
 
+pub const fn cluster_debug_pipeline_key(overlay: Option<&ClusterDebugOverlay>) -> MeshPipelineKey {
+    match overlay {
+        None => MeshPipelineKey::CLUSTER_DEBUG_NONE,
+        Some(ClusterDebugOverlay::ZSlices) => MeshPipelineKey::CLUSTER_DEBUG_Z_SLICES,
+        Some(ClusterDebugOverlay::LightComplexity) => {
+            MeshPipelineKey::CLUSTER_DEBUG_LIGHT_COMPLEXITY
+        }
+        Some(ClusterDebugOverlay::Coherency) => MeshPipelineKey::CLUSTER_DEBUG_COHERENCY,
+    }
+}
+
 pub const fn screen_space_specular_transmission_pipeline_key(
     screen_space_transmissive_blur_quality: ScreenSpaceTransmissionQuality,
 ) -> MeshPipelineKey {
@@ -553,6 +572,8 @@ pub fn queue_material_meshes<M: Material>(
         (
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
+            Option<&ClusterDebugOverlay>,
+            Has<CameraStencil>,
         ),
     )>,
 ) where
@@ -573,7 +594,7 @@ pub fn queue_material_meshes<M: Material>(
         mut alpha_mask_phase,
         mut transmissive_phase,
         mut transparent_phase,
-        (has_environment_maps, has_irradiance_volumes),
+        (has_environment_maps, has_irradiance_volumes, cluster_debug_overlay, stencil),
     ) in &mut views
     {
         let draw_opaque_pbr = opaque_draw_functions.read().id::<DrawMaterial<M>>();
@@ -612,6 +633,12 @@ pub fn queue_material_meshes<M: Material>(
             view_key |= MeshPipelineKey::IRRADIANCE_VOLUME;
         }
 
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
+        view_key |= cluster_debug_pipeline_key(cluster_debug_overlay);
+
         if let Some(projection) = projection {
             view_key |= match projection {
                 Projection::Perspective(_) => MeshPipelineKey::VIEW_PROJECTION_PERSPECTIVE,
@@ -885,6 +912,7 @@ impl<M: Material> RenderAsset for PreparedMaterial<M> {
                     material.reads_view_transmission_texture(),
                 );
                 mesh_pipeline_key_bits.insert(alpha_mode_pipeline_key(material.alpha_mode(), msaa));
+                mesh_pipeline_key_bits.insert(material_stencil_pipeline_key(material.stencil()));
 
                 Ok(PreparedMaterial {
                     bindings: prepared.bindings,
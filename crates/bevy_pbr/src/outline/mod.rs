@@ -0,0 +1,667 @@
+//! Highlighting meshes with an outline, such as the selection of an editor.
+//!
+//! An [`Outlined`] mesh is drawn a second time after the transparent pass, with its vertices
+//! extruded along their normals by the width of the outline, and only its back faces kept, so
+//! that the outline shows around its silhouette. The outlines sharing a mesh are drawn with a
+//! single instanced draw call per view.
+//!
+//! The outlined meshes need positions and normals, and their normals must be smooth for the
+//! outline to be continuous around sharp edges.
+
+use std::ops::Range;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, AssetId, Handle};
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_core_pipeline::core_3d::{
+    graph::{Core3d, Node3d},
+    Camera3d, CameraStencil,
+};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{Has, QueryItem, With},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs as _,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::{Mat4, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::ExtractedCamera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    mesh::{GpuBufferInfo, GpuMesh, Mesh, MeshVertexBufferLayoutRef},
+    render_asset::RenderAssets,
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_phase::TrackedRenderPass,
+    render_resource::{
+        binding_types::uniform_buffer, BindGroup, BindGroupEntries, BindGroupLayout,
+        BindGroupLayoutEntries, BlendState, Buffer, BufferInitDescriptor, BufferUsages,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, CompareFunction, DepthBiasState,
+        DepthStencilState, Extent3d, Face, FragmentState, LoadOp, MultisampleState, Operations,
+        PipelineCache, PrimitiveState, RenderPassDepthStencilAttachment, RenderPassDescriptor,
+        RenderPipelineDescriptor, Shader, ShaderStages, SpecializedMeshPipeline,
+        SpecializedMeshPipelineError, SpecializedMeshPipelines, StencilFaceState, StencilOperation,
+        StencilState, StoreOp, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        VertexAttribute, VertexBufferLayout, VertexFormat, VertexState, VertexStepMode,
+    },
+    renderer::{RenderContext, RenderDevice},
+    texture::{BevyDefault, CachedTexture, TextureCache},
+    view::{
+        ExtractedView, Msaa, ViewDepthTexture, ViewTarget, ViewUniform, ViewUniformOffset,
+        ViewUniforms, VisibleEntities, WithMesh,
+    },
+    Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{tracing::warn, HashMap};
+use bytemuck::{Pod, Zeroable};
+
+use crate::{graph::NodePbr, MeshPipelineKey, RenderMeshInstances};
+
+/// The shader drawing the outlines.
+pub const OUTLINE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(3717391359965817770);
+
+/// The format of the stencil texture masking the [`OutlineDepthMode::AlwaysVisible`] outlines.
+pub const OUTLINE_STENCIL_FORMAT: TextureFormat = TextureFormat::Stencil8;
+
+/// A plugin drawing the outlines of [`Outlined`] meshes.
+pub struct OutlinePlugin;
+
+/// Draws an outline around the mesh of the entity, after the transparent pass.
+///
+/// The mesh is drawn a second time with its vertices extruded along their normals, so it needs
+/// positions and smooth normals. The outlines sharing a mesh are batched into a single draw call
+/// per view.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default, Debug)]
+pub struct Outlined {
+    /// The color of the outline. Translucent outlines are blended with what's behind them.
+    pub color: Color,
+    /// The width of the outline, in physical pixels.
+    pub width: f32,
+    /// Whether the outline is hidden by the meshes in front of it.
+    pub depth_mode: OutlineDepthMode,
+}
+
+impl Outlined {
+    /// Creates an outline of the given color and width, hidden by the meshes in front of it.
+    pub fn new(color: impl Into<Color>, width: f32) -> Self {
+        Self {
+            color: color.into(),
+            width,
+            depth_mode: OutlineDepthMode::DepthTested,
+        }
+    }
+
+    /// Returns the outline with the given `depth_mode`.
+    pub fn with_depth_mode(mut self, depth_mode: OutlineDepthMode) -> Self {
+        self.depth_mode = depth_mode;
+        self
+    }
+}
+
+impl Default for Outlined {
+    fn default() -> Self {
+        Self::new(Color::srgb(1.0, 0.5, 0.0), 2.0)
+    }
+}
+
+/// How an [`Outlined`] mesh is drawn relative to the other meshes.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum OutlineDepthMode {
+    /// The outline is hidden by the meshes in front of it.
+    #[default]
+    DepthTested,
+    /// The outline is visible through the other meshes, such as the selection of an editor.
+    ///
+    /// The outline is still kept out of the silhouettes of all the outlined meshes.
+    AlwaysVisible,
+}
+
+/// The outline of an [`Outlined`] mesh, extracted to the render world.
+#[derive(Component)]
+pub struct ExtractedOutline {
+    depth_mode: OutlineDepthMode,
+    instance: OutlineInstance,
+}
+
+/// The per-instance vertex data of an outline, see `outline.wgsl`.
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+struct OutlineInstance {
+    /// The rows of the affine transform of the mesh.
+    model: [Vec4; 3],
+    color: Vec4,
+    /// The width of the outline, in `x`.
+    params: Vec4,
+}
+
+impl ExtractComponent for ExtractedOutline {
+    type QueryData = (Read<Outlined>, Read<GlobalTransform>);
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(
+        (outlined, transform): QueryItem<'_, Self::QueryData>,
+    ) -> Option<Self::Out> {
+        if outlined.width <= 0.0 {
+            return None;
+        }
+        let model = Mat4::from(transform.affine());
+        Some(ExtractedOutline {
+            depth_mode: outlined.depth_mode,
+            instance: OutlineInstance {
+                model: [model.row(0), model.row(1), model.row(2)],
+                color: Vec4::from_array(LinearRgba::from(outlined.color).to_f32_array()),
+                params: Vec4::new(outlined.width, 0.0, 0.0, 0.0),
+            },
+        })
+    }
+}
+
+impl Plugin for OutlinePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OUTLINE_SHADER_HANDLE,
+            "outline.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Outlined>()
+            .register_type::<OutlineDepthMode>()
+            .add_plugins(ExtractComponentPlugin::<ExtractedOutline>::extract_visible());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedMeshPipelines<OutlinePipeline>>()
+            .add_systems(
+                Render,
+                (
+                    prepare_outlines.in_set(RenderSet::PrepareResources),
+                    prepare_outline_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<OutlinePipeline>()
+            .add_render_graph_node::<ViewNodeRunner<OutlineNode>>(Core3d, NodePbr::Outline)
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    Node3d::MainTransparentPass,
+                    NodePbr::Outline,
+                    Node3d::EndMainPass,
+                ),
+            );
+    }
+}
+
+/// The render pipeline of outlines.
+#[derive(Resource)]
+pub struct OutlinePipeline {
+    view_layout: BindGroupLayout,
+}
+
+impl FromWorld for OutlinePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let view_layout = render_device.create_bind_group_layout(
+            "outline_view_layout",
+            &BindGroupLayoutEntries::single(
+                ShaderStages::VERTEX,
+                uniform_buffer::<ViewUniform>(true),
+            ),
+        );
+        OutlinePipeline { view_layout }
+    }
+}
+
+/// The key specializing the [`OutlinePipeline`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OutlinePipelineKey {
+    /// The MSAA, HDR, depth format and primitive topology bits of the view and mesh.
+    pub mesh_key: MeshPipelineKey,
+    pub depth_mode: OutlineDepthMode,
+    /// Whether the pipeline writes the silhouette of the mesh to the stencil texture of the
+    /// [`OutlineDepthMode::AlwaysVisible`] outlines, rather than drawing the outline.
+    pub mask: bool,
+}
+
+impl SpecializedMeshPipeline for OutlinePipeline {
+    type Key = OutlinePipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let vertex_layout = layout.0.get_layout(&[
+            Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
+            Mesh::ATTRIBUTE_NORMAL.at_shader_location(1),
+        ])?;
+        let instance_attributes = (0..5)
+            .map(|index| VertexAttribute {
+                format: VertexFormat::Float32x4,
+                offset: index * VertexFormat::Float32x4.size(),
+                shader_location: 2 + index as u32,
+            })
+            .collect();
+
+        let mut shader_defs = Vec::new();
+        if key.mask {
+            shader_defs.push("OUTLINE_MASK".into());
+        }
+
+        let format = if key.mesh_key.contains(MeshPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let depth_stencil = match key.depth_mode {
+            // Only the back faces are drawn, so the outline is hidden by the front faces of the
+            // mesh everywhere but around its silhouette.
+            OutlineDepthMode::DepthTested => DepthStencilState {
+                format: key.mesh_key.depth_format(),
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            },
+            OutlineDepthMode::AlwaysVisible => {
+                let face = if key.mask {
+                    StencilFaceState {
+                        pass_op: StencilOperation::Replace,
+                        ..StencilFaceState::IGNORE
+                    }
+                } else {
+                    StencilFaceState {
+                        compare: CompareFunction::NotEqual,
+                        ..StencilFaceState::IGNORE
+                    }
+                };
+                DepthStencilState {
+                    format: OUTLINE_STENCIL_FORMAT,
+                    depth_write_enabled: false,
+                    depth_compare: CompareFunction::Always,
+                    stencil: StencilState {
+                        front: face,
+                        back: face,
+                        read_mask: !0,
+                        write_mask: if key.mask { !0 } else { 0 },
+                    },
+                    bias: DepthBiasState::default(),
+                }
+            }
+        };
+
+        Ok(RenderPipelineDescriptor {
+            label: Some("outline_pipeline".into()),
+            layout: vec![self.view_layout.clone()],
+            push_constant_ranges: vec![],
+            vertex: VertexState {
+                shader: OUTLINE_SHADER_HANDLE,
+                shader_defs: shader_defs.clone(),
+                entry_point: "vertex".into(),
+                buffers: vec![
+                    vertex_layout,
+                    VertexBufferLayout {
+                        array_stride: std::mem::size_of::<OutlineInstance>() as u64,
+                        step_mode: VertexStepMode::Instance,
+                        attributes: instance_attributes,
+                    },
+                ],
+            },
+            fragment: Some(FragmentState {
+                shader: OUTLINE_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: if key.mask {
+                        ColorWrites::empty()
+                    } else {
+                        ColorWrites::ALL
+                    },
+                })],
+            }),
+            primitive: PrimitiveState {
+                topology: key.mesh_key.primitive_topology(),
+                cull_mode: Some(if key.mask { Face::Back } else { Face::Front }),
+                ..Default::default()
+            },
+            depth_stencil: Some(depth_stencil),
+            multisample: MultisampleState {
+                count: key.mesh_key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+        })
+    }
+}
+
+/// The outlines drawn in a view, batched by mesh.
+#[derive(Component)]
+pub struct ViewOutlines {
+    batches: Vec<OutlineBatch>,
+    buffer: Buffer,
+    /// The stencil texture of the [`OutlineDepthMode::AlwaysVisible`] outlines, if any.
+    stencil: Option<CachedTexture>,
+}
+
+struct OutlineBatch {
+    mesh: AssetId<Mesh>,
+    depth_mode: OutlineDepthMode,
+    instances: Range<u32>,
+    pipeline: CachedRenderPipelineId,
+    /// The pipeline writing the silhouettes of the [`OutlineDepthMode::AlwaysVisible`] outlines.
+    mask_pipeline: Option<CachedRenderPipelineId>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare_outlines(
+    mut commands: Commands,
+    outline_pipeline: Res<OutlinePipeline>,
+    mut pipelines: ResMut<SpecializedMeshPipelines<OutlinePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    msaa: Res<Msaa>,
+    meshes: Res<RenderAssets<GpuMesh>>,
+    render_mesh_instances: Res<RenderMeshInstances>,
+    outlines: Query<&ExtractedOutline>,
+    views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            &ExtractedCamera,
+            &VisibleEntities,
+            Has<CameraStencil>,
+        ),
+        With<Camera3d>,
+    >,
+) {
+    let mut groups: HashMap<(AssetId<Mesh>, OutlineDepthMode), Vec<OutlineInstance>> =
+        HashMap::default();
+    for (view_entity, view, camera, visible_entities, stencil) in &views {
+        for entity in visible_entities.iter::<WithMesh>() {
+            let Ok(outline) = outlines.get(*entity) else {
+                continue;
+            };
+            let Some(mesh_instance) = render_mesh_instances.render_mesh_queue_data(*entity) else {
+                continue;
+            };
+            groups
+                .entry((mesh_instance.mesh_asset_id, outline.depth_mode))
+                .or_default()
+                .push(outline.instance);
+        }
+        if groups.is_empty() {
+            continue;
+        }
+
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
+            | MeshPipelineKey::from_hdr(view.hdr);
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
+
+        let mut instances = Vec::new();
+        let mut batches = Vec::new();
+        for ((mesh_id, depth_mode), group) in groups.drain() {
+            let Some(mesh) = meshes.get(mesh_id) else {
+                continue;
+            };
+            let mut specialize = |mask| {
+                let key = OutlinePipelineKey {
+                    mesh_key: view_key
+                        | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology()),
+                    depth_mode,
+                    mask,
+                };
+                pipelines
+                    .specialize(&pipeline_cache, &outline_pipeline, key, &mesh.layout)
+                    .map_err(|err| warn!("Failed to specialize the pipeline of an outline: {err}"))
+                    .ok()
+            };
+            let Some(pipeline) = specialize(false) else {
+                continue;
+            };
+            let mask_pipeline = match depth_mode {
+                OutlineDepthMode::DepthTested => None,
+                OutlineDepthMode::AlwaysVisible => match specialize(true) {
+                    Some(mask_pipeline) => Some(mask_pipeline),
+                    None => continue,
+                },
+            };
+
+            let start = instances.len() as u32;
+            instances.extend(group);
+            batches.push(OutlineBatch {
+                mesh: mesh_id,
+                depth_mode,
+                instances: start..instances.len() as u32,
+                pipeline,
+                mask_pipeline,
+            });
+        }
+        if batches.is_empty() {
+            continue;
+        }
+
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("view_outline_buffer"),
+            contents: bytemuck::cast_slice(instances.as_slice()),
+            usage: BufferUsages::VERTEX,
+        });
+
+        let stencil = match camera.physical_target_size {
+            Some(size) if batches.iter().any(|batch| batch.mask_pipeline.is_some()) => {
+                Some(texture_cache.get(
+                    &render_device,
+                    TextureDescriptor {
+                        label: Some("outline_stencil_texture"),
+                        size: Extent3d {
+                            width: size.x,
+                            height: size.y,
+                            depth_or_array_layers: 1,
+                        },
+                        mip_level_count: 1,
+                        sample_count: msaa.samples(),
+                        dimension: TextureDimension::D2,
+                        format: OUTLINE_STENCIL_FORMAT,
+                        usage: TextureUsages::RENDER_ATTACHMENT,
+                        view_formats: &[],
+                    },
+                ))
+            }
+            _ => None,
+        };
+
+        commands.entity(view_entity).insert(ViewOutlines {
+            batches,
+            buffer,
+            stencil,
+        });
+    }
+}
+
+/// The bind group of the view uniforms of a view with [`ViewOutlines`].
+#[derive(Component)]
+pub struct OutlineViewBindGroup(BindGroup);
+
+fn prepare_outline_bind_groups(
+    mut commands: Commands,
+    outline_pipeline: Res<OutlinePipeline>,
+    render_device: Res<RenderDevice>,
+    view_uniforms: Res<ViewUniforms>,
+    views: Query<Entity, With<ViewOutlines>>,
+) {
+    let Some(view_binding) = view_uniforms.uniforms.binding() else {
+        return;
+    };
+    if views.is_empty() {
+        return;
+    }
+
+    // The views share the buffer of the view uniforms, at different dynamic offsets.
+    let bind_group = render_device.create_bind_group(
+        "outline_view_bind_group",
+        &outline_pipeline.view_layout,
+        &BindGroupEntries::single(view_binding),
+    );
+    for view_entity in &views {
+        commands
+            .entity(view_entity)
+            .insert(OutlineViewBindGroup(bind_group.clone()));
+    }
+}
+
+/// A [`bevy_render::render_graph::Node`] drawing the [`ViewOutlines`] of a view, on top of its
+/// main pass.
+#[derive(Default)]
+pub struct OutlineNode;
+
+impl ViewNode for OutlineNode {
+    type ViewQuery = (
+        Read<ExtractedCamera>,
+        Read<ViewTarget>,
+        Read<ViewDepthTexture>,
+        Read<ViewUniformOffset>,
+        Read<ViewOutlines>,
+        Read<OutlineViewBindGroup>,
+    );
+
+    fn run<'w>(
+        &self,
+        _: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (camera, target, depth, view_uniform_offset, outlines, view_bind_group): QueryItem<
+            'w,
+            Self::ViewQuery,
+        >,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let meshes = world.resource::<RenderAssets<GpuMesh>>();
+
+        let is_depth_tested =
+            |batch: &&OutlineBatch| batch.depth_mode == OutlineDepthMode::DepthTested;
+        if outlines.batches.iter().any(|batch| is_depth_tested(&batch)) {
+            let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+                label: Some("outline_pass"),
+                color_attachments: &[Some(target.get_color_attachment())],
+                depth_stencil_attachment: Some(depth.get_attachment(StoreOp::Store)),
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            if let Some(viewport) = camera.viewport.as_ref() {
+                render_pass.set_camera_viewport(viewport);
+            }
+            render_pass.set_bind_group(0, &view_bind_group.0, &[view_uniform_offset.offset]);
+            render_pass.set_vertex_buffer(1, outlines.buffer.slice(..));
+            for batch in outlines.batches.iter().filter(is_depth_tested) {
+                draw_outline_batch(
+                    &mut render_pass,
+                    pipeline_cache,
+                    meshes,
+                    batch,
+                    batch.pipeline,
+                );
+            }
+        }
+
+        let Some(stencil) = &outlines.stencil else {
+            return Ok(());
+        };
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("outline_always_visible_pass"),
+            color_attachments: &[Some(target.get_color_attachment())],
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                view: &stencil.default_view,
+                depth_ops: None,
+                stencil_ops: Some(Operations {
+                    load: LoadOp::Clear(0),
+                    store: StoreOp::Discard,
+                }),
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        if let Some(viewport) = camera.viewport.as_ref() {
+            render_pass.set_camera_viewport(viewport);
+        }
+        render_pass.set_bind_group(0, &view_bind_group.0, &[view_uniform_offset.offset]);
+        render_pass.set_vertex_buffer(1, outlines.buffer.slice(..));
+        render_pass.set_stencil_reference(1);
+        // All the silhouettes are masked first, so that no outline covers an outlined mesh.
+        for batch in &outlines.batches {
+            if let Some(mask_pipeline) = batch.mask_pipeline {
+                draw_outline_batch(
+                    &mut render_pass,
+                    pipeline_cache,
+                    meshes,
+                    batch,
+                    mask_pipeline,
+                );
+            }
+        }
+        for batch in &outlines.batches {
+            if batch.mask_pipeline.is_some() {
+                draw_outline_batch(
+                    &mut render_pass,
+                    pipeline_cache,
+                    meshes,
+                    batch,
+                    batch.pipeline,
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn draw_outline_batch<'w>(
+    render_pass: &mut TrackedRenderPass<'w>,
+    pipeline_cache: &'w PipelineCache,
+    meshes: &'w RenderAssets<GpuMesh>,
+    batch: &OutlineBatch,
+    pipeline: CachedRenderPipelineId,
+) {
+    let (Some(pipeline), Some(gpu_mesh)) = (
+        pipeline_cache.get_render_pipeline(pipeline),
+        meshes.get(batch.mesh),
+    ) else {
+        return;
+    };
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+    match &gpu_mesh.buffer_info {
+        GpuBufferInfo::Indexed {
+            buffer,
+            index_format,
+            count,
+        } => {
+            render_pass.set_index_buffer(buffer.slice(..), 0, *index_format);
+            render_pass.draw_indexed(0..*count, 0, batch.instances.clone());
+        }
+        GpuBufferInfo::NonIndexed => {
+            render_pass.draw(0..gpu_mesh.vertex_count, batch.instances.clone());
+        }
+    }
+}
@@ -0,0 +1,54 @@
+#import bevy_render::view::View
+
+@group(0) @binding(0) var<uniform> view: View;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    // The rows of the affine transform of the outlined mesh.
+    @location(2) model_x: vec4<f32>,
+    @location(3) model_y: vec4<f32>,
+    @location(4) model_z: vec4<f32>,
+    @location(5) color: vec4<f32>,
+    // The width of the outline in pixels, in `x`.
+    @location(6) params: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+    let position = vec4(vertex.position, 1.0);
+    let world_position = vec4(
+        dot(vertex.model_x, position),
+        dot(vertex.model_y, position),
+        dot(vertex.model_z, position),
+        1.0,
+    );
+    out.position = view.view_proj * world_position;
+    out.color = vertex.color;
+
+#ifndef OUTLINE_MASK
+    // Extrude the vertex along its normal by the width of the outline, in screen space, so that
+    // the outline has the same width at any distance.
+    let model = transpose(mat3x3(vertex.model_x.xyz, vertex.model_y.xyz, vertex.model_z.xyz));
+    let world_normal = model * vertex.normal;
+    let clip_normal = (view.view_proj * vec4(world_normal, 0.0)).xy;
+    if length(clip_normal) > 0.0 {
+        let offset = normalize(clip_normal) * vertex.params.x * 2.0 / view.viewport.zw;
+        out.position = vec4(out.position.xy + offset * out.position.w, out.position.zw);
+    }
+#endif
+
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
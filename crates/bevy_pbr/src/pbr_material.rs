@@ -527,6 +527,13 @@ pub struct StandardMaterial {
     /// [z-fighting]: https://en.wikipedia.org/wiki/Z-fighting
     pub depth_bias: f32,
 
+    /// How the material tests or writes the stencil buffer of the cameras with a
+    /// [`CameraStencil`](bevy_core_pipeline::core_3d::CameraStencil), against the
+    /// [`StencilRef`](crate::StencilRef) of each mesh.
+    ///
+    /// Defaults to [`MaterialStencil::Disabled`].
+    pub stencil: MaterialStencil,
+
     /// The depth map used for [parallax mapping].
     ///
     /// It is a greyscale image where white represents bottom and black the top.
@@ -746,6 +753,7 @@ impl Default for StandardMaterial {
             fog_enabled: true,
             alpha_mode: AlphaMode::Opaque,
             depth_bias: 0.0,
+            stencil: MaterialStencil::Disabled,
             depth_map: None,
             parallax_depth_scale: 0.1,
             max_parallax_layer_count: 16.0,
@@ -1167,6 +1175,11 @@ impl Material for StandardMaterial {
         self.specular_transmission > 0.0
     }
 
+    #[inline]
+    fn stencil(&self) -> MaterialStencil {
+        self.stencil
+    }
+
     fn prepass_fragment_shader() -> ShaderRef {
         PBR_PREPASS_SHADER_HANDLE.into()
     }
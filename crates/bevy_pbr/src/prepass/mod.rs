@@ -6,7 +6,7 @@ use bevy_render::view::WithMesh;
 pub use prepass_bindings::*;
 
 use bevy_asset::{load_internal_asset, AssetServer};
-use bevy_core_pipeline::{core_3d::CORE_3D_DEPTH_FORMAT, prelude::Camera3d};
+use bevy_core_pipeline::{core_3d::CameraStencil, prelude::Camera3d};
 use bevy_core_pipeline::{deferred::*, prepass::*};
 use bevy_ecs::{
     prelude::*,
@@ -47,6 +47,9 @@ pub const PREPASS_UTILS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(4
 
 pub const PREPASS_IO_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(81212356509530944);
 
+pub const PREPASS_VERTEX_SHADER_HANDLE: Handle<Shader> =
+    Handle::weak_from_u128(6213980945236014728);
+
 /// Sets up everything required to use the prepass pipeline.
 ///
 /// This does not add the actual prepasses, see [`PrepassPlugin`] for that.
@@ -70,6 +73,13 @@ where
             Shader::from_wgsl
         );
 
+        load_internal_asset!(
+            app,
+            PREPASS_VERTEX_SHADER_HANDLE,
+            "prepass_vertex.wgsl",
+            Shader::from_wgsl
+        );
+
         load_internal_asset!(
             app,
             PREPASS_BINDINGS_SHADER_HANDLE,
@@ -566,7 +576,7 @@ where
                 conservative: false,
             },
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: key.mesh_key.depth_format(),
                 depth_write_enabled: true,
                 depth_compare: CompareFunction::GreaterEqual,
                 stencil: StencilState {
@@ -725,6 +735,7 @@ pub fn queue_prepass_material_meshes<M: Material>(
             Option<&NormalPrepass>,
             Option<&MotionVectorPrepass>,
             Option<&DeferredPrepass>,
+            Has<CameraStencil>,
         ),
         Or<(
             With<BinnedRenderPhase<Opaque3dPrepass>>,
@@ -763,9 +774,13 @@ pub fn queue_prepass_material_meshes<M: Material>(
         normal_prepass,
         motion_vector_prepass,
         deferred_prepass,
+        stencil,
     ) in &mut views
     {
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
+        if stencil {
+            view_key |= MeshPipelineKey::DEPTH_STENCIL;
+        }
         if depth_prepass.is_some() {
             view_key |= MeshPipelineKey::DEPTH_PREPASS;
         }
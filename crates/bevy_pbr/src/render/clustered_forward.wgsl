@@ -10,12 +10,15 @@
    maths::PI_2,
 }
 
-// NOTE: Keep in sync with bevy_pbr/src/light.rs
+// NOTE: Keep in sync with bevy_pbr/src/light/mod.rs
 fn view_z_to_z_slice(view_z: f32, is_orthographic: bool) -> u32 {
     var z_slice: u32 = 0u;
     if is_orthographic {
         // NOTE: view_z is correct in the orthographic case
         z_slice = u32(floor((view_z - bindings::lights.cluster_factors.z) * bindings::lights.cluster_factors.w));
+    } else if bindings::lights.cluster_z_slicing == 1u {
+        // NOTE: Depths closer than the first slice depth belong to the first slice
+        z_slice = u32(max((-view_z - bindings::lights.cluster_factors.z) * bindings::lights.cluster_factors.w + 1.0, 0.0));
     } else {
         // NOTE: had to use -view_z to make it positive else log(negative) is nan
         z_slice = u32(log(-view_z) * bindings::lights.cluster_factors.z - bindings::lights.cluster_factors.w + 1.0);
@@ -201,6 +201,8 @@ pub struct GpuLights {
     n_directional_lights: u32,
     // offset from spot light's light index to spot light's shadow map index
     spot_light_shadowmap_offset: i32,
+    // 1 if the depth slices of a perspective view are evenly spaced, 0 if they are exponential
+    cluster_z_slicing: u32,
 }
 
 // NOTE: this must be kept in sync with the same constants in pbr.frag
@@ -268,6 +270,7 @@ pub struct ExtractedClusterConfig {
     far: f32,
     /// Number of clusters in `X` / `Y` / `Z` in the view frustum
     dimensions: UVec3,
+    z_slicing: ClusterZSlicing,
 }
 
 enum ExtractedClustersPointLightsElement {
@@ -307,6 +310,7 @@ pub fn extract_clusters(
                 near: clusters.near,
                 far: clusters.far,
                 dimensions: clusters.dimensions,
+                z_slicing: clusters.z_slicing,
             },
         ));
     }
@@ -636,9 +640,18 @@ pub fn calculate_cluster_factors(
     far: f32,
     z_slices: f32,
     is_orthographic: bool,
+    z_slicing: ClusterZSlicing,
 ) -> Vec2 {
     if is_orthographic {
         Vec2::new(-near, z_slices / (-far - -near))
+    } else if z_slicing == ClusterZSlicing::Linear {
+        // The first slice ends at `near`, the remaining slices evenly divide `near..far`
+        let depth_range = far - near;
+        if z_slices <= 1.0 || depth_range <= 0.0 {
+            Vec2::new(near, 0.0)
+        } else {
+            Vec2::new(near, (z_slices - 1.0) / depth_range)
+        }
     } else {
         let z_slices_of_ln_zfar_over_znear = (z_slices - 1.0) / (far / near).ln();
         Vec2::new(
@@ -1006,6 +1019,7 @@ pub fn prepare_lights(
             clusters.far,
             clusters.dimensions.z as f32,
             is_orthographic,
+            clusters.z_slicing,
         );
 
         let n_clusters = clusters.dimensions.x * clusters.dimensions.y * clusters.dimensions.z;
@@ -1027,6 +1041,7 @@ pub fn prepare_lights(
             // index to shadow map index, we need to subtract point light count and add directional shadowmap count.
             spot_light_shadowmap_offset: num_directional_cascades_enabled as i32
                 - point_light_count as i32,
+            cluster_z_slicing: u32::from(clusters.z_slicing == ClusterZSlicing::Linear),
         };
 
         // TODO: this should select lights based on relevance to the view instead of the first ones that show up in a query
@@ -2,7 +2,10 @@ use std::mem;
 
 use bevy_asset::{load_internal_asset, AssetId};
 use bevy_core_pipeline::{
-    core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
+    core_3d::{
+        AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT,
+        CORE_3D_DEPTH_STENCIL_FORMAT,
+    },
     deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
 };
 use bevy_derive::{Deref, DerefMut};
@@ -21,6 +24,7 @@ use bevy_render::{
         no_gpu_preprocessing, GetBatchData, GetFullBatchData, NoAutomaticBatching,
     },
     camera::Camera,
+    extract_component::ExtractComponentPlugin,
     mesh::*,
     primitives::Aabb,
     render_asset::RenderAssets,
@@ -73,6 +77,8 @@ pub const MESH_TYPES_HANDLE: Handle<Shader> = Handle::weak_from_u128(25060241019
 pub const MESH_BINDINGS_HANDLE: Handle<Shader> = Handle::weak_from_u128(16831548636314682308);
 pub const MESH_FUNCTIONS_HANDLE: Handle<Shader> = Handle::weak_from_u128(6300874327833745635);
 pub const MESH_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(3252377289100772450);
+pub const MESH_VERTEX_HANDLE: Handle<Shader> = Handle::weak_from_u128(9154312278364410551);
+pub const VERTEX_DISPLACEMENT_HANDLE: Handle<Shader> = Handle::weak_from_u128(4716049732986512093);
 pub const SKINNING_HANDLE: Handle<Shader> = Handle::weak_from_u128(13215291596265391738);
 pub const MORPH_HANDLE: Handle<Shader> = Handle::weak_from_u128(970982813587607345);
 
@@ -120,14 +126,31 @@ impl Plugin for MeshRenderPlugin {
             Shader::from_wgsl
         );
         load_internal_asset!(app, MESH_SHADER_HANDLE, "mesh.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            MESH_VERTEX_HANDLE,
+            "mesh_vertex.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VERTEX_DISPLACEMENT_HANDLE,
+            "vertex_displacement.wgsl",
+            Shader::from_wgsl
+        );
         load_internal_asset!(app, SKINNING_HANDLE, "skinning.wgsl", Shader::from_wgsl);
         load_internal_asset!(app, MORPH_HANDLE, "morph.wgsl", Shader::from_wgsl);
 
         app.add_systems(
             PostUpdate,
-            (no_automatic_skin_batching, no_automatic_morph_batching),
+            (
+                no_automatic_skin_batching,
+                no_automatic_morph_batching,
+                no_automatic_stencil_batching,
+            ),
         )
         .add_plugins((
+            ExtractComponentPlugin::<StencilRef>::default(),
             BinnedRenderPhasePlugin::<Opaque3d, MeshPipeline>::default(),
             BinnedRenderPhasePlugin::<AlphaMask3d, MeshPipeline>::default(),
             BinnedRenderPhasePlugin::<Shadow, MeshPipeline>::default(),
@@ -1342,7 +1365,8 @@ bitflags::bitflags! {
         const LIGHTMAPPED                       = 1 << 13;
         const IRRADIANCE_VOLUME                 = 1 << 14;
         const VISIBILITY_RANGE_DITHER           = 1 << 15;
-        const LAST_FLAG                         = Self::VISIBILITY_RANGE_DITHER.bits();
+        const DEPTH_STENCIL                     = 1 << 16; // The view depth texture has a stencil aspect, see `CameraStencil`
+        const LAST_FLAG                         = Self::DEPTH_STENCIL.bits();
 
         // Bitfields
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
@@ -1375,13 +1399,25 @@ bitflags::bitflags! {
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_MEDIUM = 1 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_HIGH = 2 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA = 3 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+        const CLUSTER_DEBUG_RESERVED_BITS       = Self::CLUSTER_DEBUG_MASK_BITS << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_NONE                = 0 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_Z_SLICES            = 1 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_LIGHT_COMPLEXITY    = 2 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const CLUSTER_DEBUG_COHERENCY           = 3 << Self::CLUSTER_DEBUG_SHIFT_BITS;
+        const STENCIL_RESERVED_BITS             = Self::STENCIL_MASK_BITS << Self::STENCIL_SHIFT_BITS;
+        const STENCIL_NONE                      = 0 << Self::STENCIL_SHIFT_BITS;
+        const STENCIL_WRITE                     = 1 << Self::STENCIL_SHIFT_BITS;
+        const STENCIL_EQUAL                     = 2 << Self::STENCIL_SHIFT_BITS;
+        const STENCIL_NOT_EQUAL                 = 3 << Self::STENCIL_SHIFT_BITS;
         const ALL_RESERVED_BITS =
             Self::BLEND_RESERVED_BITS.bits() |
             Self::MSAA_RESERVED_BITS.bits() |
             Self::TONEMAP_METHOD_RESERVED_BITS.bits() |
             Self::SHADOW_FILTER_METHOD_RESERVED_BITS.bits() |
             Self::VIEW_PROJECTION_RESERVED_BITS.bits() |
-            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits();
+            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits() |
+            Self::CLUSTER_DEBUG_RESERVED_BITS.bits() |
+            Self::STENCIL_RESERVED_BITS.bits();
     }
 }
 
@@ -1409,6 +1445,15 @@ impl MeshPipelineKey {
     const SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS: u64 =
         Self::VIEW_PROJECTION_MASK_BITS.count_ones() as u64 + Self::VIEW_PROJECTION_SHIFT_BITS;
 
+    const CLUSTER_DEBUG_MASK_BITS: u64 = 0b11;
+    const CLUSTER_DEBUG_SHIFT_BITS: u64 = Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS
+        .count_ones() as u64
+        + Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+
+    const STENCIL_MASK_BITS: u64 = 0b11;
+    const STENCIL_SHIFT_BITS: u64 =
+        Self::CLUSTER_DEBUG_MASK_BITS.count_ones() as u64 + Self::CLUSTER_DEBUG_SHIFT_BITS;
+
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
             (msaa_samples.trailing_zeros() as u64 & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
@@ -1427,6 +1472,51 @@ impl MeshPipelineKey {
         1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
     }
 
+    /// The format of the depth texture of the view, see [`MeshPipelineKey::DEPTH_STENCIL`].
+    pub fn depth_format(&self) -> TextureFormat {
+        if self.contains(MeshPipelineKey::DEPTH_STENCIL) {
+            CORE_3D_DEPTH_STENCIL_FORMAT
+        } else {
+            CORE_3D_DEPTH_FORMAT
+        }
+    }
+
+    /// The stencil test of the pipeline, from its `STENCIL_*` bits.
+    ///
+    /// The stencil is only used if the view depth texture has a stencil aspect.
+    pub fn stencil_state(&self) -> StencilState {
+        let stencil_bits = *self & MeshPipelineKey::STENCIL_RESERVED_BITS;
+        if !self.contains(MeshPipelineKey::DEPTH_STENCIL)
+            || stencil_bits == MeshPipelineKey::STENCIL_NONE
+        {
+            return StencilState::default();
+        }
+        let writes = stencil_bits == MeshPipelineKey::STENCIL_WRITE;
+        let face = if writes {
+            // Fragments passing the depth test write the stencil reference of the entity.
+            StencilFaceState {
+                pass_op: StencilOperation::Replace,
+                ..StencilFaceState::IGNORE
+            }
+        } else if stencil_bits == MeshPipelineKey::STENCIL_EQUAL {
+            StencilFaceState {
+                compare: CompareFunction::Equal,
+                ..StencilFaceState::IGNORE
+            }
+        } else {
+            StencilFaceState {
+                compare: CompareFunction::NotEqual,
+                ..StencilFaceState::IGNORE
+            }
+        };
+        StencilState {
+            front: face,
+            back: face,
+            read_mask: !0,
+            write_mask: if writes { !0 } else { 0 },
+        }
+    }
+
     pub fn from_primitive_topology(primitive_topology: PrimitiveTopology) -> Self {
         let primitive_topology_bits = ((primitive_topology as u64)
             & BaseMeshPipelineKey::PRIMITIVE_TOPOLOGY_MASK_BITS)
@@ -1737,6 +1827,15 @@ impl SpecializedMeshPipeline for MeshPipeline {
             },
         ));
 
+        let cluster_debug = key.intersection(MeshPipelineKey::CLUSTER_DEBUG_RESERVED_BITS);
+        if cluster_debug == MeshPipelineKey::CLUSTER_DEBUG_Z_SLICES {
+            shader_defs.push("CLUSTERED_FORWARD_DEBUG_Z_SLICES".into());
+        } else if cluster_debug == MeshPipelineKey::CLUSTER_DEBUG_LIGHT_COMPLEXITY {
+            shader_defs.push("CLUSTERED_FORWARD_DEBUG_CLUSTER_LIGHT_COMPLEXITY".into());
+        } else if cluster_debug == MeshPipelineKey::CLUSTER_DEBUG_COHERENCY {
+            shader_defs.push("CLUSTERED_FORWARD_DEBUG_CLUSTER_COHERENCY".into());
+        }
+
         if key.contains(MeshPipelineKey::VISIBILITY_RANGE_DITHER) {
             shader_defs.push("VISIBILITY_RANGE_DITHER".into());
         }
@@ -1794,15 +1893,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
                 strip_index_format: None,
             },
             depth_stencil: Some(DepthStencilState {
-                format: CORE_3D_DEPTH_FORMAT,
+                format: key.depth_format(),
                 depth_write_enabled,
                 depth_compare: CompareFunction::GreaterEqual,
-                stencil: StencilState {
-                    front: StencilFaceState::IGNORE,
-                    back: StencilFaceState::IGNORE,
-                    read_mask: 0,
-                    write_mask: 0,
-                },
+                stencil: key.stencil_state(),
                 bias: DepthBiasState {
                     constant: 0,
                     slope_scale: 0.0,
@@ -0,0 +1,84 @@
+#define_import_path bevy_pbr::mesh_vertex
+
+#import bevy_pbr::{
+    mesh_functions,
+    skinning,
+    morph::morph,
+    forward_io::{Vertex, VertexOutput},
+    view_transformations::position_world_to_clip,
+}
+
+// Applies the morph targets of the mesh to the vertex, if it has any.
+fn morph_vertex(vertex_in: Vertex) -> Vertex {
+    var vertex = vertex_in;
+#ifdef MORPH_TARGETS
+    let weight_count = bevy_pbr::morph::layer_count();
+    for (var i: u32 = 0u; i < weight_count; i ++) {
+        let weight = bevy_pbr::morph::weight_at(i);
+        if weight == 0.0 {
+            continue;
+        }
+        vertex.position += weight * morph(vertex.index, bevy_pbr::morph::position_offset, i);
+#ifdef VERTEX_NORMALS
+        vertex.normal += weight * morph(vertex.index, bevy_pbr::morph::normal_offset, i);
+#endif
+#ifdef VERTEX_TANGENTS
+        vertex.tangent += vec4(weight * morph(vertex.index, bevy_pbr::morph::tangent_offset, i), 0.0);
+#endif
+    }
+#endif
+    return vertex;
+}
+
+// Skins the vertex, if the mesh is skinned, and transforms it to world and clip space.
+//
+// `instance_index` must be the instance index of the vertex given to the entry point, to work around a
+// wgpu dx12 bug. See https://github.com/gfx-rs/naga/issues/2416 .
+fn vertex_output(vertex: Vertex, instance_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+#ifdef SKINNED
+    var model = skinning::skin_model(vertex.joint_indices, vertex.joint_weights);
+#else
+    var model = mesh_functions::get_model_matrix(instance_index);
+#endif
+
+#ifdef VERTEX_NORMALS
+#ifdef SKINNED
+    out.world_normal = skinning::skin_normals(model, vertex.normal);
+#else
+    out.world_normal = mesh_functions::mesh_normal_local_to_world(vertex.normal, instance_index);
+#endif
+#endif
+
+#ifdef VERTEX_POSITIONS
+    out.world_position = mesh_functions::mesh_position_local_to_world(model, vec4<f32>(vertex.position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+#endif
+
+#ifdef VERTEX_UVS_A
+    out.uv = vertex.uv;
+#endif
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+#ifdef VERTEX_TANGENTS
+    out.world_tangent = mesh_functions::mesh_tangent_local_to_world(model, vertex.tangent, instance_index);
+#endif
+
+#ifdef VERTEX_COLORS
+    out.color = vertex.color;
+#endif
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = instance_index;
+#endif
+
+#ifdef VISIBILITY_RANGE_DITHER
+    out.visibility_range_dither = mesh_functions::get_visibility_range_dither_level(
+        instance_index, model[3]);
+#endif
+
+    return out;
+}
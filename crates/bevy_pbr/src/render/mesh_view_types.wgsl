@@ -51,6 +51,10 @@ struct Lights {
     // z is cluster_dimensions.z / log(far / near)
     // w is cluster_dimensions.z * log(near) / log(far / near)
     //
+    // For perspective projections with linear z-slicing:
+    // z is near
+    // w is (cluster_dimensions.z - 1) / (far - near)
+    //
     // For orthographic projections:
     // NOTE: near and far are +ve but -z is infront of the camera
     // z is -near
@@ -58,8 +62,8 @@ struct Lights {
     cluster_factors: vec4<f32>,
     n_directional_lights: u32,
     spot_light_shadowmap_offset: i32,
-    environment_map_smallest_specular_mip_level: u32,
-    environment_map_intensity: f32,
+    // 1 if the depth slices of a perspective view are evenly spaced, 0 if they are exponential
+    cluster_z_slicing: u32,
 };
 
 struct Fog {
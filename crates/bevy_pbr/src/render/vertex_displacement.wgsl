@@ -0,0 +1,60 @@
+#define_import_path bevy_pbr::vertex_displacement
+
+// The building blocks of the vertex displacement shaders of material extensions, see
+// `MaterialExtension::vertex_displacement_shader`. The same shader is used in the main pass and in the
+// prepass, including shadows, so that the displaced mesh casts matching shadows:
+//
+// #import bevy_pbr::vertex_displacement::{morph_vertex, vertex_output, time}
+// #ifdef PREPASS_PIPELINE
+// #import bevy_pbr::prepass_io::{Vertex, VertexOutput}
+// #else
+// #import bevy_pbr::forward_io::{Vertex, VertexOutput}
+// #endif
+//
+// @vertex
+// fn vertex(vertex_in: Vertex) -> VertexOutput {
+//     var vertex = morph_vertex(vertex_in);
+//     vertex.position.x += sin(time() + vertex.position.y) * 0.1;
+//     return vertex_output(vertex, vertex_in.instance_index);
+// }
+
+// The view bind group of the prepass has a different layout: its globals are at another binding.
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::{
+    prepass_bindings::globals,
+    prepass_io::{Vertex, VertexOutput},
+    prepass_vertex,
+}
+#else
+#import bevy_pbr::{
+    forward_io::{Vertex, VertexOutput},
+    mesh_view_bindings::globals,
+    mesh_vertex,
+}
+#endif
+
+// Applies the morph targets of the mesh to the vertex, if it has any.
+fn morph_vertex(vertex: Vertex) -> Vertex {
+#ifdef PREPASS_PIPELINE
+    return prepass_vertex::morph_vertex(vertex);
+#else
+    return mesh_vertex::morph_vertex(vertex);
+#endif
+}
+
+// Skins the vertex, if the mesh is skinned, and transforms it to world and clip space, the way the
+// default vertex shader of the pass does.
+//
+// `instance_index` must be the instance index of the vertex given to the entry point.
+fn vertex_output(vertex: Vertex, instance_index: u32) -> VertexOutput {
+#ifdef PREPASS_PIPELINE
+    return prepass_vertex::vertex_output(vertex, instance_index);
+#else
+    return mesh_vertex::vertex_output(vertex, instance_index);
+#endif
+}
+
+// The time since startup in seconds, wrapping to 0 after 1 hour.
+fn time() -> f32 {
+    return globals.time;
+}
@@ -0,0 +1,89 @@
+use bevy_ecs::{
+    prelude::*,
+    query::ROQueryItem,
+    system::{lifetimeless::Read, SystemParamItem},
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    batching::NoAutomaticBatching,
+    extract_component::ExtractComponent,
+    render_phase::{PhaseItem, RenderCommand, RenderCommandResult, TrackedRenderPass},
+};
+
+use crate::MeshPipelineKey;
+
+/// How a [`Material`](crate::Material) uses the stencil buffer of the cameras with a
+/// [`CameraStencil`](bevy_core_pipeline::core_3d::CameraStencil).
+///
+/// The stencil reference of each mesh is its [`StencilRef`], or `0` without one. Materials drawn
+/// by cameras without a stencil buffer ignore this setting.
+///
+/// This is only supported by the forward passes: the deferred and depth prepasses don't use the
+/// stencil buffer.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum MaterialStencil {
+    /// The stencil buffer is neither tested nor written.
+    #[default]
+    Disabled,
+    /// The stencil reference of the mesh is written wherever it passes the depth test, to mask
+    /// the areas other materials are drawn in, such as a portal.
+    Write,
+    /// The mesh is only drawn where the stencil buffer is equal to its stencil reference.
+    Equal,
+    /// The mesh is only drawn where the stencil buffer differs from its stencil reference, such as
+    /// an outline drawn around a mesh writing the stencil.
+    NotEqual,
+}
+
+pub const fn material_stencil_pipeline_key(stencil: MaterialStencil) -> MeshPipelineKey {
+    match stencil {
+        MaterialStencil::Disabled => MeshPipelineKey::STENCIL_NONE,
+        MaterialStencil::Write => MeshPipelineKey::STENCIL_WRITE,
+        MaterialStencil::Equal => MeshPipelineKey::STENCIL_EQUAL,
+        MaterialStencil::NotEqual => MeshPipelineKey::STENCIL_NOT_EQUAL,
+    }
+}
+
+/// The stencil reference a mesh writes or is tested against, depending on the
+/// [`MaterialStencil`] of its material. Defaults to `0`.
+///
+/// Meshes with this component aren't batched automatically, since each draw sets its own
+/// reference.
+#[derive(Component, ExtractComponent, Reflect, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[reflect(Component, Default)]
+pub struct StencilRef(pub u32);
+
+/// Sets the stencil reference of the render pass to the [`StencilRef`] of the drawn entity.
+pub struct SetMeshStencilReference;
+
+impl<P: PhaseItem> RenderCommand<P> for SetMeshStencilReference {
+    type Param = ();
+    type ViewQuery = ();
+    type ItemQuery = Option<Read<StencilRef>>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        stencil_ref: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        _param: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        // The reference is always set, since the previous draw of the pass may have changed it.
+        let reference = stencil_ref.flatten().map_or(0, |stencil_ref| stencil_ref.0);
+        pass.set_stencil_reference(reference);
+        RenderCommandResult::Success
+    }
+}
+
+/// Opts the meshes with a [`StencilRef`] out of automatic batching, so that each of them is drawn
+/// with its own reference.
+pub fn no_automatic_stencil_batching(
+    mut commands: Commands,
+    query: Query<Entity, (With<StencilRef>, Without<NoAutomaticBatching>)>,
+) {
+    for entity in &query {
+        commands.entity(entity).try_insert(NoAutomaticBatching);
+    }
+}
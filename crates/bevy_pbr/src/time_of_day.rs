@@ -0,0 +1,371 @@
+//! A day-night cycle, moving the sun and the moon across the sky and driving the sky and the
+//! atmosphere of cameras with them.
+//!
+//! The [`TimeOfDay`] resource holds the hour of the day, advanced with the virtual [`Time`]. From
+//! it:
+//!
+//! - the [`DirectionalLight`]s of [`CelestialBody`] entities follow the sun or the moon, fading
+//!   out below the horizon and turning warmer close to it;
+//! - the fog, skybox and environment map lighting of cameras with a [`SkyCycle`] blend between
+//!   their day, twilight and night settings;
+//! - [`DaylightEvent`]s are sent at dawn and dusk, for gameplay.
+
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_color::{Color, LinearRgba, Mix};
+use bevy_core_pipeline::Skybox;
+use bevy_ecs::prelude::*;
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::{components::Transform, TransformSystem};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use crate::{environment_map::EnvironmentMapLight, light_consts, DirectionalLight, FogSettings};
+
+/// The range of elevations of the sun, in radians above [`TimeOfDay::twilight_elevation`], over
+/// which the sky turns from night to day.
+const TWILIGHT_RANGE: f32 = 12.0 * (TAU / 360.0);
+
+/// Adds the [`TimeOfDay`] day-night cycle.
+///
+/// This plugin isn't added by the [`PbrPlugin`](crate::PbrPlugin), and requires the
+/// [`Time`] resource.
+pub struct TimeOfDayPlugin;
+
+impl Plugin for TimeOfDayPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<TimeOfDay>()
+            .register_type::<CelestialBody>()
+            .register_type::<SkyCycle>()
+            .init_resource::<TimeOfDay>()
+            .add_event::<DaylightEvent>()
+            .add_systems(PreUpdate, advance_time_of_day)
+            .add_systems(
+                PostUpdate,
+                (update_celestial_bodies, update_sky_cycles)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// The time of day of the [`TimeOfDayPlugin`], positioning the sun and the moon.
+///
+/// The sun rises in the east, along `+X`, at 6 o'clock, and sets in the west, along `-X`, at 18
+/// o'clock. North is along `-Z`, so that the sun passes to the south, along `+Z`, at noon in the
+/// northern hemisphere. The moon is always opposite the sun.
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource, Default, Debug)]
+pub struct TimeOfDay {
+    /// The hour of the day, from `0.0` at midnight up to `24.0`.
+    pub hour: f32,
+    /// The number of days elapsed, incremented at midnight.
+    pub day: u32,
+    /// The number of hours of the day elapsing per second of virtual time.
+    pub hours_per_second: f32,
+    /// Whether the time of day stands still.
+    pub paused: bool,
+    /// The latitude, in radians, tilting the path of the sun away from the zenith.
+    pub latitude: f32,
+    /// The elevation of the sun, in radians, above which it is day.
+    ///
+    /// Defaults to -6°, the end of civil twilight.
+    pub twilight_elevation: f32,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            hour: 9.0,
+            day: 0,
+            // A whole day in 20 minutes.
+            hours_per_second: 24.0 / (20.0 * 60.0),
+            paused: false,
+            latitude: 40.0_f32.to_radians(),
+            twilight_elevation: -6.0_f32.to_radians(),
+        }
+    }
+}
+
+impl TimeOfDay {
+    /// Advances the time of day by `hours`, counting the days passing midnight.
+    pub fn advance(&mut self, hours: f32) {
+        let hour = self.hour + hours;
+        let days = (hour / 24.0).floor();
+        self.hour = hour - days * 24.0;
+        self.day = self.day.saturating_add_signed(days as i32);
+    }
+
+    /// Returns the direction towards the sun.
+    pub fn sun_direction(&self) -> Vec3 {
+        let angle = (self.hour - 6.0) / 24.0 * TAU;
+        self.tilt() * Vec3::new(angle.cos(), angle.sin(), 0.0)
+    }
+
+    /// Returns the direction towards the moon.
+    pub fn moon_direction(&self) -> Vec3 {
+        -self.sun_direction()
+    }
+
+    /// Returns the elevation of the sun above the horizon, in radians.
+    pub fn sun_elevation(&self) -> f32 {
+        self.sun_direction().y.clamp(-1.0, 1.0).asin()
+    }
+
+    /// Returns `true` if the sun is above [`TimeOfDay::twilight_elevation`].
+    pub fn is_day(&self) -> bool {
+        self.sun_elevation() > self.twilight_elevation
+    }
+
+    /// Returns how much of the day it is, from `0.0` at night to `1.0` during the day, going
+    /// through twilight as the sun rises over [`TimeOfDay::twilight_elevation`].
+    pub fn daylight(&self) -> f32 {
+        let t = (self.sun_elevation() - self.twilight_elevation + TWILIGHT_RANGE * 0.5)
+            / TWILIGHT_RANGE;
+        smoothstep(t)
+    }
+
+    /// The normal of the plane the sun and the moon move in.
+    fn orbit_normal(&self) -> Vec3 {
+        self.tilt() * Vec3::Z
+    }
+
+    fn tilt(&self) -> Quat {
+        Quat::from_rotation_x(self.latitude.clamp(-FRAC_PI_2, FRAC_PI_2))
+    }
+}
+
+/// An event sent by the [`TimeOfDayPlugin`] when the sun crosses
+/// [`TimeOfDay::twilight_elevation`].
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DaylightEvent {
+    /// The sun rose above the twilight elevation.
+    Dawn,
+    /// The sun set below the twilight elevation.
+    Dusk,
+}
+
+/// Which body of the sky a [`CelestialBody`] follows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum CelestialBodyKind {
+    #[default]
+    Sun,
+    Moon,
+}
+
+/// Makes the [`DirectionalLight`] of an entity follow the sun or the moon of the [`TimeOfDay`].
+///
+/// The rotation of the [`Transform`] of the entity, its illuminance and its color are set every
+/// frame.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct CelestialBody {
+    /// The body the light follows.
+    pub kind: CelestialBodyKind,
+    /// The illuminance of the light, in lux, when the body is high in the sky.
+    pub illuminance: f32,
+    /// The color of the light when the body is high in the sky.
+    pub color: Color,
+    /// The color of the light when the body is close to the horizon.
+    pub horizon_color: Color,
+}
+
+impl Default for CelestialBody {
+    fn default() -> Self {
+        Self::sun()
+    }
+}
+
+impl CelestialBody {
+    /// A sun, turning orange at sunrise and sunset.
+    pub fn sun() -> Self {
+        Self {
+            kind: CelestialBodyKind::Sun,
+            illuminance: light_consts::lux::FULL_DAYLIGHT,
+            color: Color::WHITE,
+            horizon_color: Color::srgb(1.0, 0.5, 0.2),
+        }
+    }
+
+    /// A full moon, with a cold light.
+    pub fn moon() -> Self {
+        Self {
+            kind: CelestialBodyKind::Moon,
+            illuminance: light_consts::lux::FULL_MOON_NIGHT,
+            color: Color::srgb(0.75, 0.8, 1.0),
+            horizon_color: Color::srgb(0.6, 0.6, 0.8),
+        }
+    }
+}
+
+/// The day, twilight and night settings of the sky and the atmosphere of a camera, blended with
+/// the [`TimeOfDay`].
+///
+/// When the camera has them, this sets:
+///
+/// - the color of its [`FogSettings`], inscattered by atmospheric fog, and the color of the light
+///   of the sun scattered by the fog;
+/// - the brightness of its [`Skybox`];
+/// - the intensity of its [`EnvironmentMapLight`].
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct SkyCycle {
+    /// The color of the fog during the day.
+    pub day_fog_color: Color,
+    /// The color of the fog at sunrise and sunset.
+    pub twilight_fog_color: Color,
+    /// The color of the fog at night.
+    pub night_fog_color: Color,
+    /// The color of the light of the sun scattered by the fog when it is high in the sky, faded
+    /// out at night and turned to the twilight fog color close to the horizon.
+    pub sun_glow_color: Color,
+    /// The brightness of the skybox during the day.
+    pub day_skybox_brightness: f32,
+    /// The brightness of the skybox at night.
+    pub night_skybox_brightness: f32,
+    /// The intensity of the environment map light during the day.
+    pub day_environment_intensity: f32,
+    /// The intensity of the environment map light at night.
+    pub night_environment_intensity: f32,
+}
+
+impl Default for SkyCycle {
+    fn default() -> Self {
+        Self {
+            day_fog_color: Color::srgb(0.35, 0.48, 0.66),
+            twilight_fog_color: Color::srgb(0.8, 0.45, 0.3),
+            night_fog_color: Color::srgb(0.01, 0.015, 0.03),
+            sun_glow_color: Color::srgb(1.0, 0.9, 0.7),
+            day_skybox_brightness: 1000.0,
+            night_skybox_brightness: 5.0,
+            day_environment_intensity: 1000.0,
+            night_environment_intensity: 5.0,
+        }
+    }
+}
+
+impl SkyCycle {
+    /// Returns the color of the fog for the given [`TimeOfDay::daylight`].
+    pub fn fog_color(&self, daylight: f32) -> Color {
+        if daylight < 0.5 {
+            mix_linear(
+                self.night_fog_color,
+                self.twilight_fog_color,
+                daylight * 2.0,
+            )
+        } else {
+            mix_linear(
+                self.twilight_fog_color,
+                self.day_fog_color,
+                daylight * 2.0 - 1.0,
+            )
+        }
+    }
+}
+
+/// Advances the [`TimeOfDay`] with the virtual [`Time`], and sends the [`DaylightEvent`]s.
+pub fn advance_time_of_day(
+    time: Res<Time>,
+    mut time_of_day: ResMut<TimeOfDay>,
+    mut events: EventWriter<DaylightEvent>,
+    mut was_day: Local<Option<bool>>,
+) {
+    if !time_of_day.paused {
+        let hours = time.delta_seconds() * time_of_day.hours_per_second;
+        time_of_day.advance(hours);
+    }
+
+    // Compare with the previous frame rather than the time advanced, to also catch changes of
+    // the time of day made by the app.
+    let is_day = time_of_day.is_day();
+    match *was_day {
+        Some(false) if is_day => {
+            events.send(DaylightEvent::Dawn);
+        }
+        Some(true) if !is_day => {
+            events.send(DaylightEvent::Dusk);
+        }
+        _ => {}
+    }
+    *was_day = Some(is_day);
+}
+
+/// Points the [`DirectionalLight`]s of the [`CelestialBody`] entities towards the sun or the
+/// moon, and sets their illuminance and color.
+pub fn update_celestial_bodies(
+    time_of_day: Res<TimeOfDay>,
+    mut bodies: Query<(&CelestialBody, &mut DirectionalLight, &mut Transform)>,
+) {
+    let up = time_of_day.orbit_normal();
+    for (body, mut light, mut transform) in &mut bodies {
+        let direction = match body.kind {
+            CelestialBodyKind::Sun => time_of_day.sun_direction(),
+            CelestialBodyKind::Moon => time_of_day.moon_direction(),
+        };
+        // The light shines from the body, along the forward direction of the transform.
+        transform.look_to(-direction, up);
+
+        // Fade out as the body sets, a little after it crosses the horizon, and redden close to
+        // the horizon, where the light crosses more of the atmosphere.
+        let height = direction.y;
+        let fade = smoothstep((height + 0.05) / 0.15);
+        let zenith = smoothstep(height / 0.5);
+        light.illuminance = body.illuminance * fade;
+        light.color = mix_linear(body.horizon_color, body.color, zenith);
+    }
+}
+
+/// Blends the fog, skybox and environment map light of the cameras with a [`SkyCycle`].
+pub fn update_sky_cycles(
+    time_of_day: Res<TimeOfDay>,
+    mut cameras: Query<(
+        &SkyCycle,
+        Option<&mut FogSettings>,
+        Option<&mut Skybox>,
+        Option<&mut EnvironmentMapLight>,
+    )>,
+) {
+    let daylight = time_of_day.daylight();
+    let sun_height = time_of_day.sun_direction().y;
+    for (cycle, fog, skybox, environment_map) in &mut cameras {
+        if let Some(mut fog) = fog {
+            fog.color = cycle.fog_color(daylight);
+            let glow = mix_linear(
+                cycle.twilight_fog_color,
+                cycle.sun_glow_color,
+                smoothstep(sun_height / 0.5),
+            );
+            fog.directional_light_color = mix_linear(Color::NONE, glow, daylight);
+        }
+        if let Some(mut skybox) = skybox {
+            skybox.brightness = lerp(
+                cycle.night_skybox_brightness,
+                cycle.day_skybox_brightness,
+                daylight,
+            );
+        }
+        if let Some(mut environment_map) = environment_map {
+            environment_map.intensity = lerp(
+                cycle.night_environment_intensity,
+                cycle.day_environment_intensity,
+                daylight,
+            );
+        }
+    }
+}
+
+fn mix_linear(from: Color, to: Color, factor: f32) -> Color {
+    LinearRgba::from(from)
+        .mix(&LinearRgba::from(to), factor)
+        .into()
+}
+
+fn lerp(from: f32, to: f32, factor: f32) -> f32 {
+    from + (to - from) * factor
+}
+
+/// A smooth step from `0.0` to `1.0` as `t` goes from `0.0` to `1.0`.
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
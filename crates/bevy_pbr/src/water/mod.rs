@@ -0,0 +1,305 @@
+//! A starter module for rendering water surfaces.
+//!
+//! A [`WaterMaterial`] extends the [`StandardMaterial`] of a flat, subdivided plane mesh lying in
+//! its local `XZ` plane, such as a [`Plane3d`](bevy_math::primitives::Plane3d) mesh, with:
+//!
+//! - waves moving its vertices, described by [`WaterWaves`];
+//! - a color absorbed with the depth of the water below the surface;
+//! - foam along the shores, where the surface is close to the ground below it.
+//!
+//! The surface is lit, reflects its environment and refracts the scene below it through the usual
+//! PBR lighting of the [`StandardMaterial`], with the specular transmission set by
+//! [`WaterExtension::base_material`].
+//!
+//! The depth of the water is read from the depth prepass, so that cameras need a
+//! [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass) for the absorption and the foam.
+//! Without it, the surface has the shallow color everywhere.
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Asset, Handle};
+use bevy_color::{Color, ColorToComponents, LinearRgba};
+use bevy_math::{Vec2, Vec3, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_resource::{AsBindGroup, AsBindGroupShaderType, Shader, ShaderRef, ShaderType},
+    texture::GpuImage,
+};
+use std::f32::consts::{PI, TAU};
+
+use crate::{
+    ExtendedMaterial, MaterialExtension, MaterialPlugin, OpaqueRendererMethod, StandardMaterial,
+};
+
+pub const WATER_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(1718436259052373801);
+
+/// The maximum number of waves of a [`WaterWaves`], the others being ignored.
+pub const MAX_WATER_WAVES: usize = 8;
+
+/// The gravity, in meters per second squared, that gives waves their speed.
+const GRAVITY: f32 = 9.81;
+
+/// Adds support for [`WaterMaterial`].
+pub struct WaterPlugin;
+
+impl Plugin for WaterPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, WATER_SHADER_HANDLE, "water.wgsl", Shader::from_wgsl);
+
+        app.register_type::<WaterExtension>()
+            .add_plugins(MaterialPlugin::<WaterMaterial>::default());
+    }
+}
+
+/// A material for water surfaces, with waves, a color absorbed with depth and foam along the
+/// shores.
+///
+/// The base material is typically created with [`WaterExtension::base_material`].
+pub type WaterMaterial = ExtendedMaterial<StandardMaterial, WaterExtension>;
+
+/// A single Gerstner wave, moving the points of the surface along circles, so that the crests are
+/// sharper than the troughs.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Default, Debug)]
+pub struct GerstnerWave {
+    /// The direction the wave travels in, in the `XZ` plane of the mesh.
+    pub direction: Vec2,
+    /// The distance between two crests, in meters.
+    pub wavelength: f32,
+    /// The sharpness of the crests, from `0.0` for a flat surface to `1.0` for crests about to
+    /// break. The steepness of all the waves of a surface should add up to at most `1.0`, or
+    /// the surface loops over itself.
+    pub steepness: f32,
+}
+
+impl Default for GerstnerWave {
+    fn default() -> Self {
+        Self {
+            direction: Vec2::X,
+            wavelength: 10.0,
+            steepness: 0.25,
+        }
+    }
+}
+
+impl GerstnerWave {
+    /// Creates a wave traveling in `direction`.
+    pub fn new(direction: Vec2, wavelength: f32, steepness: f32) -> Self {
+        Self {
+            direction,
+            wavelength,
+            steepness,
+        }
+    }
+
+    /// The number of radians per meter of the wave.
+    #[inline]
+    pub fn wave_number(&self) -> f32 {
+        TAU / self.wavelength.max(f32::EPSILON)
+    }
+
+    /// The speed of the wave in meters per second, the one of waves in deep water.
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        (GRAVITY / self.wave_number()).sqrt()
+    }
+
+    /// The height of the crests above the rest level of the surface.
+    #[inline]
+    pub fn amplitude(&self) -> f32 {
+        self.steepness / self.wave_number()
+    }
+
+    /// Returns the offset of the point of the surface at rest at `position` at `time`.
+    pub fn displacement(&self, position: Vec2, time: f32) -> Vec3 {
+        let direction = self.direction.normalize_or_zero();
+        let k = self.wave_number();
+        let phase = k * (direction.dot(position) - self.speed() * time);
+        let amplitude = self.amplitude();
+        let horizontal = direction * amplitude * phase.cos();
+        Vec3::new(horizontal.x, amplitude * phase.sin(), horizontal.y)
+    }
+}
+
+/// The waves of a [`WaterExtension`].
+#[derive(Clone, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, Debug)]
+pub struct WaterWaves {
+    /// The waves adding up to the surface. Only the first [`MAX_WATER_WAVES`] are rendered.
+    pub waves: Vec<GerstnerWave>,
+}
+
+impl WaterWaves {
+    /// A calm surface, without any wave.
+    pub const CALM: Self = Self { waves: Vec::new() };
+
+    /// Creates a surface from the given Gerstner waves.
+    pub fn gerstner(waves: impl IntoIterator<Item = GerstnerWave>) -> Self {
+        Self {
+            waves: waves.into_iter().collect(),
+        }
+    }
+
+    /// Creates a surface of [`MAX_WATER_WAVES`] waves sampled from the spectrum of a sea raised
+    /// by `wind`, in meters per second in the `XZ` plane of the mesh.
+    ///
+    /// The waves are spread around the direction of the wind, with wavelengths decreasing from
+    /// the peak of the Pierson-Moskowitz spectrum of the wind speed, the distribution that FFT
+    /// ocean simulations sample from. `choppiness`, from `0.0` to `1.0`, is the sum of the
+    /// steepness of the waves.
+    pub fn from_wind(wind: Vec2, choppiness: f32) -> Self {
+        let speed = wind.length();
+        if speed <= f32::EPSILON {
+            return Self::CALM;
+        }
+        let wind_angle = wind.y.atan2(wind.x);
+        // The angular frequency of the peak of the spectrum is `0.855 * g / U`.
+        let peak_frequency = 0.855 * GRAVITY / speed;
+        let peak_wavelength = TAU * GRAVITY / (peak_frequency * peak_frequency);
+
+        let count = MAX_WATER_WAVES;
+        // The energy of the spectrum falls off with the wavelength, so shorter waves are
+        // steeper relative to their size, but carry less of the total steepness.
+        let weights = (0..count).map(|i| 0.75_f32.powi(i as i32));
+        let total: f32 = weights.clone().sum();
+        let waves = weights
+            .enumerate()
+            .map(|(i, weight)| {
+                // Spread the directions with the golden ratio, over half a circle facing the
+                // wind.
+                let spread = ((i as f32 * 0.618_034).fract() - 0.5) * PI * 0.75;
+                let angle = wind_angle + spread;
+                GerstnerWave {
+                    direction: Vec2::from_angle(angle),
+                    wavelength: peak_wavelength * 0.7_f32.powi(i as i32),
+                    steepness: choppiness.clamp(0.0, 1.0) * weight / total,
+                }
+            })
+            .collect();
+        Self { waves }
+    }
+
+    /// Returns the offset of the point of the surface at rest at `position` at `time`, in the
+    /// local space of the mesh.
+    ///
+    /// This matches the rendered surface, for gameplay such as buoyancy.
+    pub fn displacement(&self, position: Vec2, time: f32) -> Vec3 {
+        self.waves
+            .iter()
+            .take(MAX_WATER_WAVES)
+            .map(|wave| wave.displacement(position, time))
+            .sum()
+    }
+
+    /// Returns the approximate height of the surface above `position` at `time`, in the local
+    /// space of the mesh.
+    ///
+    /// Waves move points horizontally as well, so the height of the point at rest at `position`
+    /// is refined over a few iterations to find the point ending up above `position`.
+    pub fn height_at(&self, position: Vec2, time: f32) -> f32 {
+        let mut rest = position;
+        for _ in 0..4 {
+            let offset = self.displacement(rest, time);
+            rest = position - Vec2::new(offset.x, offset.z);
+        }
+        self.displacement(rest, time).y
+    }
+}
+
+/// The [`MaterialExtension`] of a [`WaterMaterial`].
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[uniform(100, WaterUniform)]
+#[reflect(Default, Debug)]
+pub struct WaterExtension {
+    /// The waves of the surface.
+    pub waves: WaterWaves,
+    /// The color of the water where it is shallow, replacing the base color of the material.
+    pub shallow_color: Color,
+    /// The color of the water where it is deep.
+    pub deep_color: Color,
+    /// The depth, in meters, at which the color of the water is mostly the deep color.
+    pub absorption_depth: f32,
+    /// The color of the foam along the shores.
+    pub foam_color: Color,
+    /// The depth, in meters, under which the surface is covered with foam.
+    pub foam_depth: f32,
+}
+
+impl Default for WaterExtension {
+    fn default() -> Self {
+        Self {
+            waves: WaterWaves::from_wind(Vec2::new(6.0, 2.0), 0.6),
+            shallow_color: Color::srgb(0.1, 0.45, 0.45),
+            deep_color: Color::srgb(0.01, 0.05, 0.12),
+            absorption_depth: 4.0,
+            foam_color: Color::WHITE,
+            foam_depth: 0.3,
+        }
+    }
+}
+
+impl WaterExtension {
+    /// Returns a [`StandardMaterial`] suited to water, to use as the base of a [`WaterMaterial`].
+    ///
+    /// The material is smooth and transmits the light of the scene below it, refracted with the
+    /// index of refraction of water. It is rendered forward, since the deferred renderer doesn't
+    /// support transmission.
+    pub fn base_material() -> StandardMaterial {
+        StandardMaterial {
+            perceptual_roughness: 0.05,
+            reflectance: 0.25,
+            specular_transmission: 0.8,
+            ior: 1.33,
+            thickness: 0.5,
+            opaque_render_method: OpaqueRendererMethod::Forward,
+            ..Default::default()
+        }
+    }
+}
+
+impl MaterialExtension for WaterExtension {
+    fn vertex_displacement_shader() -> ShaderRef {
+        WATER_SHADER_HANDLE.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        WATER_SHADER_HANDLE.into()
+    }
+}
+
+/// The GPU representation of the uniform data of a [`WaterExtension`].
+#[derive(Clone, Default, ShaderType)]
+pub struct WaterUniform {
+    /// The direction, steepness and wavelength of each wave.
+    pub waves: [Vec4; MAX_WATER_WAVES],
+    pub shallow_color: Vec4,
+    pub deep_color: Vec4,
+    pub foam_color: Vec4,
+    pub wave_count: u32,
+    pub absorption_depth: f32,
+    pub foam_depth: f32,
+}
+
+impl AsBindGroupShaderType<WaterUniform> for WaterExtension {
+    fn as_bind_group_shader_type(&self, _images: &RenderAssets<GpuImage>) -> WaterUniform {
+        let mut waves = [Vec4::ZERO; MAX_WATER_WAVES];
+        for (wave, gerstner) in waves.iter_mut().zip(&self.waves.waves) {
+            let direction = gerstner.direction.normalize_or_zero();
+            *wave = Vec4::new(
+                direction.x,
+                direction.y,
+                gerstner.steepness,
+                gerstner.wavelength,
+            );
+        }
+        WaterUniform {
+            waves,
+            shallow_color: LinearRgba::from(self.shallow_color).to_vec4(),
+            deep_color: LinearRgba::from(self.deep_color).to_vec4(),
+            foam_color: LinearRgba::from(self.foam_color).to_vec4(),
+            wave_count: self.waves.waves.len().min(MAX_WATER_WAVES) as u32,
+            absorption_depth: self.absorption_depth.max(f32::EPSILON),
+            foam_depth: self.foam_depth,
+        }
+    }
+}
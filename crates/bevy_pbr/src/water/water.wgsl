@@ -0,0 +1,115 @@
+#import bevy_pbr::vertex_displacement::{morph_vertex, vertex_output, time}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::prepass_io::{Vertex, VertexOutput}
+#else
+#import bevy_pbr::{
+    forward_io::{Vertex, VertexOutput, FragmentOutput},
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{alpha_discard, apply_pbr_lighting, main_pass_post_lighting_processing},
+    prepass_utils,
+    view_transformations::{depth_ndc_to_view_z, position_world_to_view},
+}
+#endif
+
+struct Water {
+    // The direction, steepness and wavelength of each wave.
+    waves: array<vec4<f32>, 8>,
+    shallow_color: vec4<f32>,
+    deep_color: vec4<f32>,
+    foam_color: vec4<f32>,
+    wave_count: u32,
+    absorption_depth: f32,
+    foam_depth: f32,
+};
+
+@group(2) @binding(100) var<uniform> water: Water;
+
+const GRAVITY: f32 = 9.81;
+const TAU: f32 = 6.28318530718;
+
+// Moves the vertices of the plane with the sum of the Gerstner waves, see `GerstnerWave::displacement`.
+@vertex
+fn vertex(vertex_in: Vertex) -> VertexOutput {
+    var vertex = morph_vertex(vertex_in);
+    let rest = vertex.position.xz;
+
+    var offset = vec3(0.0);
+    // The derivatives of the surface along X and Z.
+    var tangent = vec3(1.0, 0.0, 0.0);
+    var bitangent = vec3(0.0, 0.0, 1.0);
+    for (var i = 0u; i < water.wave_count; i += 1u) {
+        let wave = water.waves[i];
+        let direction = wave.xy;
+        let steepness = wave.z;
+        let k = TAU / max(wave.w, 0.0001);
+        let speed = sqrt(GRAVITY / k);
+        let phase = k * (dot(direction, rest) - speed * time());
+        let amplitude = steepness / k;
+        let c = cos(phase);
+        let s = sin(phase);
+
+        offset += vec3(direction.x * amplitude * c, amplitude * s, direction.y * amplitude * c);
+        tangent += vec3(
+            -direction.x * direction.x * steepness * s,
+            direction.x * steepness * c,
+            -direction.x * direction.y * steepness * s,
+        );
+        bitangent += vec3(
+            -direction.x * direction.y * steepness * s,
+            direction.y * steepness * c,
+            -direction.y * direction.y * steepness * s,
+        );
+    }
+
+    vertex.position += offset;
+#ifdef PREPASS_PIPELINE
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    vertex.normal = normalize(cross(bitangent, tangent));
+#endif
+#else
+#ifdef VERTEX_NORMALS
+    vertex.normal = normalize(cross(bitangent, tangent));
+#endif
+#endif
+
+    return vertex_output(vertex, vertex_in.instance_index);
+}
+
+#ifndef PREPASS_PIPELINE
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    // Without a depth prepass, the water is shallow and without foam everywhere.
+    var absorption = 0.0;
+    var foam = 0.0;
+#ifdef DEPTH_PREPASS
+    // The water isn't in the depth prepass, as it reads the view transmission texture, so the
+    // prepass holds the depth of the ground below it.
+    let ground_view_z = depth_ndc_to_view_z(prepass_utils::prepass_depth(in.position, 0u));
+    let surface_view_z = position_world_to_view(in.world_position.xyz).z;
+    // The view looks along -Z, so the ground is further along -Z than the surface.
+    let water_depth = max(surface_view_z - ground_view_z, 0.0);
+    absorption = 1.0 - exp(-water_depth / water.absorption_depth);
+    foam = 1.0 - smoothstep(0.0, max(water.foam_depth, 0.0001), water_depth);
+#endif
+
+    let water_color = mix(water.shallow_color, water.deep_color, absorption);
+    let color = mix(water_color, water.foam_color, foam);
+    pbr_input.material.base_color = vec4(color.rgb, pbr_input.material.base_color.a);
+    // Foam is rough and lets no light through.
+    pbr_input.material.perceptual_roughness = mix(pbr_input.material.perceptual_roughness, 1.0, foam);
+    pbr_input.material.specular_transmission = mix(pbr_input.material.specular_transmission, 0.0, foam);
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
+}
+#endif
@@ -0,0 +1,138 @@
+use bevy_ecs::{
+    entity::{Entity, EntityHashSet},
+    system::Resource,
+};
+use bevy_math::{Dir3, Quat, Ray3d, Vec3};
+use downcast_rs::{impl_downcast, Downcast};
+
+use crate::Collider;
+
+/// The spatial queries a physics engine integration provides to the rest of the engine.
+///
+/// Physics engine integrations implement this trait and insert their implementation with
+/// [`SpatialQueryBackend::new`], so that engine features and third-party crates can cast rays
+/// and shapes with [`SpatialQuery`](crate::SpatialQuery) without depending on a specific engine.
+///
+/// Backends usually keep an acceleration structure of the colliders of the world. They update it
+/// in their own systems, getting mutable access with [`SpatialQueryBackend::get_mut`].
+pub trait PhysicsBackend: Downcast + Send + Sync {
+    /// Returns the closest collider hit by `ray` within `max_distance`.
+    fn cast_ray(
+        &self,
+        ray: Ray3d,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<RayHit>;
+
+    /// Returns the first collider hit by `shape`, moving from `origin` towards `direction` with
+    /// the given `rotation` for at most `max_distance`.
+    fn cast_shape(
+        &self,
+        shape: &Collider,
+        origin: Vec3,
+        rotation: Quat,
+        direction: Dir3,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<ShapeHit>;
+
+    /// Calls `callback` with every collider intersecting `shape` placed at `position` with
+    /// `rotation`, until it returns `false`.
+    fn overlaps(
+        &self,
+        shape: &Collider,
+        position: Vec3,
+        rotation: Quat,
+        filter: &SpatialQueryFilter,
+        callback: &mut dyn FnMut(Entity) -> bool,
+    );
+
+    /// The name of the backend, for diagnostics.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+impl_downcast!(PhysicsBackend);
+
+/// The [`PhysicsBackend`] used by [`SpatialQuery`](crate::SpatialQuery).
+#[derive(Resource)]
+pub struct SpatialQueryBackend(Box<dyn PhysicsBackend>);
+
+impl SpatialQueryBackend {
+    /// Wraps `backend` so that it can be inserted as a resource.
+    pub fn new(backend: impl PhysicsBackend) -> Self {
+        Self(Box::new(backend))
+    }
+
+    /// Returns the backend, if it is a `B`.
+    pub fn get<B: PhysicsBackend>(&self) -> Option<&B> {
+        self.0.downcast_ref()
+    }
+
+    /// Returns the backend mutably, if it is a `B`.
+    pub fn get_mut<B: PhysicsBackend>(&mut self) -> Option<&mut B> {
+        self.0.downcast_mut()
+    }
+
+    /// Returns the backend.
+    pub fn backend(&self) -> &dyn PhysicsBackend {
+        &*self.0
+    }
+}
+
+/// Which colliders a spatial query considers.
+#[derive(Clone, Debug, Default)]
+pub struct SpatialQueryFilter {
+    /// Colliders of these entities are ignored, for example the collider of the entity casting
+    /// the query.
+    pub excluded_entities: EntityHashSet,
+    /// Whether colliders marked as [`Sensor`](crate::Sensor) are considered.
+    pub include_sensors: bool,
+}
+
+impl SpatialQueryFilter {
+    /// Returns this filter, ignoring the colliders of `entities` as well.
+    pub fn excluding(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        self.excluded_entities.extend(entities);
+        self
+    }
+
+    /// Returns this filter, considering sensors as well.
+    pub fn with_sensors(mut self) -> Self {
+        self.include_sensors = true;
+        self
+    }
+
+    /// Returns `true` if the collider of `entity`, which is a sensor if `is_sensor` is `true`,
+    /// should be considered.
+    pub fn accepts(&self, entity: Entity, is_sensor: bool) -> bool {
+        (self.include_sensors || !is_sensor) && !self.excluded_entities.contains(&entity)
+    }
+}
+
+/// A collider hit by a ray, returned by [`PhysicsBackend::cast_ray`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RayHit {
+    /// The entity of the collider.
+    pub entity: Entity,
+    /// The distance from the origin of the ray to the hit point.
+    pub distance: f32,
+    /// The point where the ray hit the collider.
+    pub point: Vec3,
+    /// The normal of the collider surface at the hit point.
+    pub normal: Vec3,
+}
+
+/// A collider hit by a moving shape, returned by [`PhysicsBackend::cast_shape`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapeHit {
+    /// The entity of the collider.
+    pub entity: Entity,
+    /// The distance travelled by the shape before touching the collider.
+    pub distance: f32,
+    /// The point where the shape touched the collider.
+    pub point: Vec3,
+    /// The normal of the collider surface at the contact point.
+    pub normal: Vec3,
+}
@@ -0,0 +1,655 @@
+//! Minimal built-in 2D collision detection, for games that don't need a physics engine.
+//!
+//! [`Collision2dPlugin`] detects intersections between the 2D [`Collider`]s of the world and
+//! reports them with events and the [`Collisions2d`] resource. It doesn't move anything: games
+//! decide how to react, for example by pushing the player out of a wall along
+//! [`Contact2d::normal`]. Colliders marked with [`Sensor`] are trigger volumes, reported with
+//! [`TriggerEntered`] and [`TriggerExited`] instead.
+//!
+//! [`Collider::Circle`]s are tested exactly, and every other 2D collider is tested as its
+//! axis-aligned bounding box. 3D colliders are ignored.
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    entity::{Entity, EntityHashSet},
+    prelude::*,
+};
+use bevy_math::{
+    bounding::{Aabb2d, AabbCast2d, BoundingCircle, BoundingCircleCast, BoundingVolume, RayCast2d},
+    Dir2, Dir3, Quat, Ray3d, Vec2, Vec3, Vec3Swizzles,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+use crate::{
+    Collider, PhysicsBackend, PhysicsPlugin, PhysicsSet, RayHit, Sensor, ShapeHit,
+    SpatialQueryBackend, SpatialQueryFilter,
+};
+
+/// Detects collisions between the 2D [`Collider`]s of the world.
+///
+/// Detection runs in [`PhysicsSet::Step`], placing colliders with their [`GlobalTransform`].
+/// The plugin also inserts a [`SpatialQueryBackend`] answering spatial queries on the xy plane,
+/// unless another backend has already been inserted.
+#[derive(Default)]
+pub struct Collision2dPlugin;
+
+impl Plugin for Collision2dPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PhysicsPlugin>() {
+            app.add_plugins(PhysicsPlugin);
+        }
+        if !app.world().contains_resource::<SpatialQueryBackend>() {
+            app.insert_resource(SpatialQueryBackend::new(Collision2dBackend::default()));
+        }
+        app.init_resource::<Collisions2d>()
+            .add_event::<CollisionStarted>()
+            .add_event::<CollisionEnded>()
+            .add_event::<TriggerEntered>()
+            .add_event::<TriggerExited>()
+            .add_systems(
+                FixedPostUpdate,
+                detect_collisions_2d.in_set(PhysicsSet::Step),
+            );
+    }
+}
+
+/// Sent when two solid colliders start intersecting.
+///
+/// The entities are ordered so that the first is the lowest.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionStarted(pub Entity, pub Entity);
+
+/// Sent when two solid colliders stop intersecting, or when one of them is removed.
+///
+/// The entities are ordered so that the first is the lowest.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionEnded(pub Entity, pub Entity);
+
+/// Sent when a collider enters a trigger volume, a collider marked with [`Sensor`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerEntered {
+    /// The trigger volume.
+    pub trigger: Entity,
+    /// The collider that entered it.
+    pub entity: Entity,
+}
+
+/// Sent when a collider leaves a trigger volume, or when one of them is removed.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerExited {
+    /// The trigger volume.
+    pub trigger: Entity,
+    /// The collider that left it.
+    pub entity: Entity,
+}
+
+/// How two colliders intersect.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact2d {
+    /// The direction in which the second collider should move to stop intersecting the first.
+    pub normal: Vec2,
+    /// How far the second collider should move along [`normal`](Self::normal) to stop
+    /// intersecting the first.
+    pub depth: f32,
+}
+
+impl Contact2d {
+    fn flipped(self) -> Self {
+        Self {
+            normal: -self.normal,
+            depth: self.depth,
+        }
+    }
+}
+
+/// The intersections found by the last run of [`Collision2dPlugin`]'s detection.
+#[derive(Resource, Debug, Default)]
+pub struct Collisions2d {
+    /// Intersecting solid colliders, the lowest entity first.
+    contacts: HashMap<(Entity, Entity), Contact2d>,
+    /// Trigger volumes and the colliders inside them.
+    triggers: HashMap<Entity, EntityHashSet>,
+}
+
+impl Collisions2d {
+    /// Returns how the solid colliders of `a` and `b` intersect, with the normal pointing from
+    /// `a` to `b`.
+    pub fn get(&self, a: Entity, b: Entity) -> Option<Contact2d> {
+        if a < b {
+            self.contacts.get(&(a, b)).copied()
+        } else {
+            self.contacts.get(&(b, a)).map(|contact| contact.flipped())
+        }
+    }
+
+    /// Returns `true` if the solid colliders of `a` and `b` intersect.
+    pub fn contains(&self, a: Entity, b: Entity) -> bool {
+        self.get(a, b).is_some()
+    }
+
+    /// Iterates over the intersecting pairs of solid colliders.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity, Contact2d)> + '_ {
+        self.contacts
+            .iter()
+            .map(|(&(a, b), &contact)| (a, b, contact))
+    }
+
+    /// Iterates over the solid colliders intersecting `entity`, with normals pointing from
+    /// `entity` to them.
+    pub fn collisions_with(
+        &self,
+        entity: Entity,
+    ) -> impl Iterator<Item = (Entity, Contact2d)> + '_ {
+        self.contacts.iter().filter_map(move |(&(a, b), &contact)| {
+            if a == entity {
+                Some((b, contact))
+            } else if b == entity {
+                Some((a, contact.flipped()))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Iterates over the colliders inside the trigger volume `trigger`.
+    pub fn inside_trigger(&self, trigger: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.triggers.get(&trigger).into_iter().flatten().copied()
+    }
+}
+
+/// The shape a 2D collider is tested as.
+#[derive(Debug, Clone, Copy)]
+enum Volume2d {
+    Aabb(Aabb2d),
+    Circle(BoundingCircle),
+}
+
+impl Volume2d {
+    fn new(collider: &Collider, translation: Vec3, rotation: Quat) -> Option<Self> {
+        match *collider {
+            Collider::Circle { radius } => Some(Volume2d::Circle(BoundingCircle::new(
+                translation.xy(),
+                radius,
+            ))),
+            collider if collider.is_2d() => {
+                let aabb = collider.aabb(translation, rotation);
+                Some(Volume2d::Aabb(Aabb2d {
+                    min: aabb.min.xy(),
+                    max: aabb.max.xy(),
+                }))
+            }
+            _ => None,
+        }
+    }
+
+    fn aabb(&self) -> Aabb2d {
+        match self {
+            Volume2d::Aabb(aabb) => *aabb,
+            Volume2d::Circle(circle) => circle.aabb_2d(),
+        }
+    }
+
+    fn center(&self) -> Vec2 {
+        match self {
+            Volume2d::Aabb(aabb) => aabb.center(),
+            Volume2d::Circle(circle) => circle.center,
+        }
+    }
+
+    fn closest_point(&self, point: Vec2) -> Vec2 {
+        match self {
+            Volume2d::Aabb(aabb) => aabb.closest_point(point),
+            Volume2d::Circle(circle) => circle.closest_point(point),
+        }
+    }
+
+    /// Returns how `self` and `other` intersect, with the normal pointing from `self` to
+    /// `other`. Touching volumes don't intersect.
+    fn contact(&self, other: &Volume2d) -> Option<Contact2d> {
+        match (self, other) {
+            (Volume2d::Circle(a), Volume2d::Circle(b)) => {
+                let offset = b.center - a.center;
+                let distance = offset.length();
+                let depth = a.radius() + b.radius() - distance;
+                (depth > 0.).then(|| Contact2d {
+                    normal: offset.try_normalize().unwrap_or(Vec2::X),
+                    depth,
+                })
+            }
+            (Volume2d::Aabb(a), Volume2d::Aabb(b)) => aabb_contact(a, b),
+            (Volume2d::Aabb(aabb), Volume2d::Circle(circle)) => {
+                let closest = aabb.closest_point(circle.center);
+                let offset = circle.center - closest;
+                let distance_squared = offset.length_squared();
+                if distance_squared >= circle.radius().powi(2) {
+                    return None;
+                }
+                if distance_squared > 0. {
+                    let distance = distance_squared.sqrt();
+                    Some(Contact2d {
+                        normal: offset / distance,
+                        depth: circle.radius() - distance,
+                    })
+                } else {
+                    // the center of the circle is inside the box
+                    aabb_contact(aabb, &circle.aabb_2d())
+                }
+            }
+            (Volume2d::Circle(_), Volume2d::Aabb(_)) => other.contact(self).map(Contact2d::flipped),
+        }
+    }
+}
+
+fn aabb_contact(a: &Aabb2d, b: &Aabb2d) -> Option<Contact2d> {
+    let overlap = a.max.min(b.max) - a.min.max(b.min);
+    if overlap.x <= 0. || overlap.y <= 0. {
+        return None;
+    }
+    let offset = b.center() - a.center();
+    Some(if overlap.x < overlap.y {
+        Contact2d {
+            normal: Vec2::new(if offset.x < 0. { -1. } else { 1. }, 0.),
+            depth: overlap.x,
+        }
+    } else {
+        Contact2d {
+            normal: Vec2::new(0., if offset.y < 0. { -1. } else { 1. }),
+            depth: overlap.y,
+        }
+    })
+}
+
+/// A 2D collider placed in the world.
+#[derive(Debug, Clone, Copy)]
+struct Placed2d {
+    entity: Entity,
+    volume: Volume2d,
+    aabb: Aabb2d,
+    is_sensor: bool,
+}
+
+/// Calls `on_pair` with every pair of `colliders` whose bounding boxes overlap.
+///
+/// This is a sweep and prune broadphase: the colliders are sorted along the x axis, so that only
+/// colliders overlapping along that axis are compared.
+fn sweep_and_prune(colliders: &mut [Placed2d], mut on_pair: impl FnMut(&Placed2d, &Placed2d)) {
+    colliders.sort_unstable_by(|a, b| a.aabb.min.x.total_cmp(&b.aabb.min.x));
+    for (index, a) in colliders.iter().enumerate() {
+        for b in &colliders[index + 1..] {
+            if b.aabb.min.x > a.aabb.max.x {
+                break;
+            }
+            if b.aabb.min.y <= a.aabb.max.y && a.aabb.min.y <= b.aabb.max.y {
+                on_pair(a, b);
+            }
+        }
+    }
+}
+
+/// Detects the intersections of the 2D colliders, updates [`Collisions2d`] and sends the
+/// collision and trigger events.
+pub fn detect_collisions_2d(
+    colliders: Query<(Entity, &Collider, &GlobalTransform, Has<Sensor>)>,
+    mut collisions: ResMut<Collisions2d>,
+    mut backend: Option<ResMut<SpatialQueryBackend>>,
+    mut started: EventWriter<CollisionStarted>,
+    mut ended: EventWriter<CollisionEnded>,
+    mut entered: EventWriter<TriggerEntered>,
+    mut exited: EventWriter<TriggerExited>,
+) {
+    let mut placed: Vec<Placed2d> = colliders
+        .iter()
+        .filter_map(|(entity, collider, transform, is_sensor)| {
+            let (_, rotation, translation) = transform.to_scale_rotation_translation();
+            let volume = Volume2d::new(collider, translation, rotation)?;
+            Some(Placed2d {
+                entity,
+                volume,
+                aabb: volume.aabb(),
+                is_sensor,
+            })
+        })
+        .collect();
+
+    let mut contacts = HashMap::default();
+    let mut triggers: HashMap<Entity, EntityHashSet> = HashMap::default();
+    sweep_and_prune(&mut placed, |a, b| {
+        if a.is_sensor && b.is_sensor {
+            return;
+        }
+        let (a, b) = if a.entity < b.entity { (a, b) } else { (b, a) };
+        let Some(contact) = a.volume.contact(&b.volume) else {
+            return;
+        };
+        if a.is_sensor {
+            triggers.entry(a.entity).or_default().insert(b.entity);
+        } else if b.is_sensor {
+            triggers.entry(b.entity).or_default().insert(a.entity);
+        } else {
+            contacts.insert((a.entity, b.entity), contact);
+        }
+    });
+
+    for &(a, b) in collisions.contacts.keys() {
+        if !contacts.contains_key(&(a, b)) {
+            ended.send(CollisionEnded(a, b));
+        }
+    }
+    for &(a, b) in contacts.keys() {
+        if !collisions.contacts.contains_key(&(a, b)) {
+            started.send(CollisionStarted(a, b));
+        }
+    }
+    for (&trigger, inside) in &collisions.triggers {
+        for &entity in inside {
+            if !triggers
+                .get(&trigger)
+                .is_some_and(|now| now.contains(&entity))
+            {
+                exited.send(TriggerExited { trigger, entity });
+            }
+        }
+    }
+    for (&trigger, inside) in &triggers {
+        for &entity in inside {
+            let previous = collisions.triggers.get(&trigger);
+            if !previous.is_some_and(|previous| previous.contains(&entity)) {
+                entered.send(TriggerEntered { trigger, entity });
+            }
+        }
+    }
+    collisions.contacts = contacts;
+    collisions.triggers = triggers;
+
+    if let Some(backend) = backend
+        .as_mut()
+        .and_then(|backend| backend.get_mut::<Collision2dBackend>())
+    {
+        backend.colliders = placed;
+    }
+}
+
+/// The [`PhysicsBackend`] inserted by [`Collision2dPlugin`], answering spatial queries on the
+/// xy plane with the colliders found by the last detection.
+///
+/// The z coordinates of queries are ignored. Shape casts between a circle and another kind of
+/// collider are approximated by casting bounding boxes.
+#[derive(Debug, Default)]
+pub struct Collision2dBackend {
+    colliders: Vec<Placed2d>,
+}
+
+impl PhysicsBackend for Collision2dBackend {
+    fn cast_ray(
+        &self,
+        ray: Ray3d,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<RayHit> {
+        // distances along the ray are scaled by the projection on the xy plane
+        let planar_length = ray.direction.xy().length();
+        let direction = Dir2::new(ray.direction.xy()).ok()?;
+        let cast = RayCast2d::new(ray.origin.xy(), direction, max_distance * planar_length);
+        self.colliders
+            .iter()
+            .filter(|collider| filter.accepts(collider.entity, collider.is_sensor))
+            .filter_map(|collider| {
+                let distance = match &collider.volume {
+                    Volume2d::Aabb(aabb) => cast.aabb_intersection_at(aabb),
+                    Volume2d::Circle(circle) => cast.circle_intersection_at(circle),
+                }?;
+                Some((collider, distance / planar_length))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(collider, distance)| {
+                let point = ray.get_point(distance);
+                RayHit {
+                    entity: collider.entity,
+                    distance,
+                    point,
+                    normal: surface_normal(&collider.volume, point.xy(), -direction.as_vec2())
+                        .extend(0.),
+                }
+            })
+    }
+
+    fn cast_shape(
+        &self,
+        shape: &Collider,
+        origin: Vec3,
+        rotation: Quat,
+        direction: Dir3,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<ShapeHit> {
+        let volume = Volume2d::new(shape, origin, rotation)?;
+        let planar_length = direction.xy().length();
+        let direction_2d = Dir2::new(direction.xy()).ok()?;
+        let max = max_distance * planar_length;
+        self.colliders
+            .iter()
+            .filter(|collider| filter.accepts(collider.entity, collider.is_sensor))
+            .filter_map(|collider| {
+                let distance = match (&volume, &collider.volume) {
+                    // the cast volumes are relative to the origin of the ray
+                    (Volume2d::Circle(circle), Volume2d::Circle(other)) => {
+                        let local = BoundingCircle::new(Vec2::ZERO, circle.radius());
+                        BoundingCircleCast::new(local, circle.center, direction_2d, max)
+                            .circle_collision_at(*other)
+                    }
+                    _ => {
+                        let aabb = volume.aabb();
+                        let local = Aabb2d::new(Vec2::ZERO, aabb.half_size());
+                        AabbCast2d::new(local, aabb.center(), direction_2d, max)
+                            .aabb_collision_at(collider.aabb)
+                    }
+                }?;
+                Some((collider, distance / planar_length))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(collider, distance)| {
+                let center = volume.center() + direction.xy() * distance;
+                let point = collider.volume.closest_point(center);
+                ShapeHit {
+                    entity: collider.entity,
+                    distance,
+                    point: point.extend(origin.z),
+                    normal: surface_normal(&collider.volume, point, -direction_2d.as_vec2())
+                        .extend(0.),
+                }
+            })
+    }
+
+    fn overlaps(
+        &self,
+        shape: &Collider,
+        position: Vec3,
+        rotation: Quat,
+        filter: &SpatialQueryFilter,
+        callback: &mut dyn FnMut(Entity) -> bool,
+    ) {
+        let Some(volume) = Volume2d::new(shape, position, rotation) else {
+            return;
+        };
+        for collider in &self.colliders {
+            if filter.accepts(collider.entity, collider.is_sensor)
+                && volume.contact(&collider.volume).is_some()
+                && !callback(collider.entity)
+            {
+                return;
+            }
+        }
+    }
+}
+
+/// Returns the outward normal of `volume` at `point`, a point on its surface, or `fallback` if
+/// it can't be determined.
+fn surface_normal(volume: &Volume2d, point: Vec2, fallback: Vec2) -> Vec2 {
+    match volume {
+        Volume2d::Circle(circle) => (point - circle.center).try_normalize().unwrap_or(fallback),
+        Volume2d::Aabb(aabb) => {
+            // the normal of the closest side
+            let sides = [
+                (point.x - aabb.min.x, Vec2::NEG_X),
+                (aabb.max.x - point.x, Vec2::X),
+                (point.y - aabb.min.y, Vec2::NEG_Y),
+                (aabb.max.y - point.y, Vec2::Y),
+            ];
+            sides
+                .into_iter()
+                .min_by(|(a, _), (b, _)| a.abs().total_cmp(&b.abs()))
+                .map_or(fallback, |(_, normal)| normal)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpatialQuery;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_transform::components::Transform;
+
+    fn run_detection(world: &mut World) {
+        world.run_system_once(detect_collisions_2d);
+    }
+
+    fn setup() -> World {
+        let mut world = World::new();
+        world.init_resource::<Collisions2d>();
+        world.init_resource::<Events<CollisionStarted>>();
+        world.init_resource::<Events<CollisionEnded>>();
+        world.init_resource::<Events<TriggerEntered>>();
+        world.init_resource::<Events<TriggerExited>>();
+        world.insert_resource(SpatialQueryBackend::new(Collision2dBackend::default()));
+        world
+    }
+
+    fn at(x: f32, y: f32) -> GlobalTransform {
+        Transform::from_xyz(x, y, 0.).into()
+    }
+
+    fn drain<E: Event>(world: &mut World) -> Vec<E> {
+        world.resource_mut::<Events<E>>().drain().collect()
+    }
+
+    #[test]
+    fn contacts() {
+        let circle = Volume2d::Circle(BoundingCircle::new(Vec2::ZERO, 1.));
+        let other_circle = Volume2d::Circle(BoundingCircle::new(Vec2::new(1.5, 0.), 1.));
+        let contact = circle.contact(&other_circle).unwrap();
+        assert_eq!(contact.normal, Vec2::X);
+        assert!((contact.depth - 0.5).abs() < 1e-5);
+
+        let aabb = Volume2d::Aabb(Aabb2d::new(Vec2::new(0., 1.8), Vec2::splat(1.)));
+        let contact = circle.contact(&aabb).unwrap();
+        assert_eq!(contact.normal, Vec2::Y);
+        assert!((contact.depth - 0.2).abs() < 1e-5);
+        assert_eq!(aabb.contact(&circle).unwrap().normal, Vec2::NEG_Y);
+
+        // corners are rounded
+        let corner = Volume2d::Aabb(Aabb2d::new(Vec2::splat(1.75), Vec2::splat(1.)));
+        assert!(circle.contact(&corner).is_none());
+        let touching = Volume2d::Aabb(Aabb2d::new(Vec2::new(2., 0.), Vec2::splat(1.)));
+        assert!(circle.contact(&touching).is_none());
+    }
+
+    #[test]
+    fn collision_events() {
+        let mut world = setup();
+        let a = world
+            .spawn((Collider::Circle { radius: 1. }, at(0., 0.)))
+            .id();
+        let b = world
+            .spawn((
+                Collider::Rectangle {
+                    half_size: Vec2::splat(1.),
+                },
+                at(5., 0.),
+            ))
+            .id();
+        let trigger = world
+            .spawn((Collider::Circle { radius: 1. }, Sensor, at(-1., 5.)))
+            .id();
+
+        run_detection(&mut world);
+        assert!(drain::<CollisionStarted>(&mut world).is_empty());
+
+        *world.get_mut::<GlobalTransform>(b).unwrap() = at(1.5, 4.);
+        *world.get_mut::<GlobalTransform>(a).unwrap() = at(0., 4.);
+        run_detection(&mut world);
+        assert_eq!(
+            drain::<CollisionStarted>(&mut world),
+            [CollisionStarted(a, b)]
+        );
+        assert_eq!(
+            drain::<TriggerEntered>(&mut world),
+            [TriggerEntered { trigger, entity: a }]
+        );
+        let collisions = world.resource::<Collisions2d>();
+        assert!(collisions.contains(b, a));
+        assert_eq!(collisions.inside_trigger(trigger).collect::<Vec<_>>(), [a]);
+
+        // no new events while the colliders keep intersecting
+        run_detection(&mut world);
+        assert!(drain::<CollisionStarted>(&mut world).is_empty());
+        assert!(drain::<TriggerEntered>(&mut world).is_empty());
+
+        world.despawn(b);
+        *world.get_mut::<GlobalTransform>(a).unwrap() = at(0., 0.);
+        run_detection(&mut world);
+        assert_eq!(drain::<CollisionEnded>(&mut world), [CollisionEnded(a, b)]);
+        assert_eq!(
+            drain::<TriggerExited>(&mut world),
+            [TriggerExited { trigger, entity: a }]
+        );
+    }
+
+    #[test]
+    fn spatial_queries() {
+        let mut world = setup();
+        let wall = world
+            .spawn((
+                Collider::Rectangle {
+                    half_size: Vec2::new(1., 5.),
+                },
+                at(10., 0.),
+            ))
+            .id();
+        world.spawn((Collider::Circle { radius: 1. }, Sensor, at(5., 0.)));
+        run_detection(&mut world);
+
+        let (ray_hit, shape_hit, overlaps) = world.run_system_once(|query: SpatialQuery| {
+            let filter = SpatialQueryFilter::default();
+            (
+                query.cast_ray(Ray3d::new(Vec3::ZERO, Vec3::X), 100., &filter),
+                query.cast_shape(
+                    &Collider::Circle { radius: 1. },
+                    Vec3::ZERO,
+                    Quat::IDENTITY,
+                    Dir3::X,
+                    100.,
+                    &filter,
+                ),
+                query.overlaps(
+                    &Collider::Circle { radius: 1. },
+                    Vec3::new(5., 0., 0.),
+                    Quat::IDENTITY,
+                    &filter.with_sensors(),
+                ),
+            )
+        });
+
+        let ray_hit = ray_hit.unwrap();
+        assert_eq!(ray_hit.entity, wall);
+        assert!((ray_hit.distance - 9.).abs() < 1e-4);
+        assert_eq!(ray_hit.normal, Vec3::NEG_X);
+
+        let shape_hit = shape_hit.unwrap();
+        assert_eq!(shape_hit.entity, wall);
+        assert!((shape_hit.distance - 8.).abs() < 1e-4);
+
+        assert_eq!(overlaps.len(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+use bevy_ecs::{component::Component, reflect::ReflectComponent};
+use bevy_math::{
+    bounding::{Aabb3d, Bounded2d, Bounded3d},
+    primitives::{Capsule2d, Capsule3d, Circle, Cuboid, Cylinder, Rectangle, Sphere},
+    EulerRot, Quat, Rotation2d, Vec2, Vec3,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+/// How an entity with a [`Collider`] is moved by the physics simulation.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum RigidBody {
+    /// The body is moved by forces, gravity and collisions.
+    #[default]
+    Dynamic,
+    /// The body is moved only by its [`Velocity`], or by setting its transform. It pushes dynamic
+    /// bodies, but isn't pushed by them.
+    Kinematic,
+    /// The body never moves.
+    Static,
+}
+
+impl RigidBody {
+    /// Returns `true` if the body is moved by forces, gravity and collisions.
+    pub fn is_dynamic(self) -> bool {
+        self == RigidBody::Dynamic
+    }
+}
+
+/// The velocity of a [`RigidBody`], in world space.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Velocity {
+    /// The linear velocity, in units per second.
+    pub linear: Vec3,
+    /// The angular velocity, as an axis scaled by the rotation speed in radians per second.
+    pub angular: Vec3,
+}
+
+impl Velocity {
+    /// No velocity.
+    pub const ZERO: Self = Self {
+        linear: Vec3::ZERO,
+        angular: Vec3::ZERO,
+    };
+
+    /// Creates a velocity with the given linear velocity and no angular velocity.
+    pub const fn linear(linear: Vec3) -> Self {
+        Self {
+            linear,
+            angular: Vec3::ZERO,
+        }
+    }
+
+    /// Creates a velocity with the given angular velocity and no linear velocity.
+    pub const fn angular(angular: Vec3) -> Self {
+        Self {
+            linear: Vec3::ZERO,
+            angular,
+        }
+    }
+}
+
+/// The shape of an entity for collisions and spatial queries, centered on its transform.
+///
+/// The 2D shapes lie on the xy plane, and are rotated around the z axis only. Physics backends
+/// convert colliders into their own shapes, and may not support every kind of collider.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub enum Collider {
+    /// A sphere.
+    Sphere {
+        /// The radius of the sphere.
+        radius: f32,
+    },
+    /// A box.
+    Cuboid {
+        /// Half of the width, height and depth of the box.
+        half_size: Vec3,
+    },
+    /// A capsule along the y axis.
+    Capsule {
+        /// The radius of the capsule.
+        radius: f32,
+        /// Half the height of the capsule, excluding the hemispheres.
+        half_length: f32,
+    },
+    /// A cylinder along the y axis.
+    Cylinder {
+        /// The radius of the cylinder.
+        radius: f32,
+        /// Half the height of the cylinder.
+        half_height: f32,
+    },
+    /// A circle.
+    Circle {
+        /// The radius of the circle.
+        radius: f32,
+    },
+    /// A rectangle.
+    Rectangle {
+        /// Half of the width and height of the rectangle.
+        half_size: Vec2,
+    },
+    /// A 2D capsule along the y axis.
+    Capsule2d {
+        /// The radius of the capsule.
+        radius: f32,
+        /// Half the height of the capsule, excluding the hemicircles.
+        half_length: f32,
+    },
+}
+
+impl Default for Collider {
+    fn default() -> Self {
+        Collider::Cuboid {
+            half_size: Vec3::splat(0.5),
+        }
+    }
+}
+
+impl Collider {
+    /// Returns `true` if the collider is one of the 2D shapes.
+    pub fn is_2d(&self) -> bool {
+        matches!(
+            self,
+            Collider::Circle { .. } | Collider::Rectangle { .. } | Collider::Capsule2d { .. }
+        )
+    }
+
+    /// Computes the axis-aligned bounding box of the collider placed at `translation` with
+    /// `rotation`.
+    ///
+    /// The boxes of 2D colliders have no depth.
+    pub fn aabb(&self, translation: Vec3, rotation: Quat) -> Aabb3d {
+        let aabb_2d = |shape: &dyn Fn(Vec2, Rotation2d) -> bevy_math::bounding::Aabb2d| {
+            let rotation = Rotation2d::radians(rotation.to_euler(EulerRot::ZYX).0);
+            let aabb = shape(translation.truncate(), rotation);
+            Aabb3d {
+                min: aabb.min.extend(translation.z).into(),
+                max: aabb.max.extend(translation.z).into(),
+            }
+        };
+        match *self {
+            Collider::Sphere { radius } => Sphere::new(radius).aabb_3d(translation, rotation),
+            Collider::Cuboid { half_size } => Cuboid { half_size }.aabb_3d(translation, rotation),
+            Collider::Capsule {
+                radius,
+                half_length,
+            } => Capsule3d {
+                radius,
+                half_length,
+            }
+            .aabb_3d(translation, rotation),
+            Collider::Cylinder {
+                radius,
+                half_height,
+            } => Cylinder {
+                radius,
+                half_height,
+            }
+            .aabb_3d(translation, rotation),
+            Collider::Circle { radius } => {
+                aabb_2d(&|position, rotation| Circle::new(radius).aabb_2d(position, rotation))
+            }
+            Collider::Rectangle { half_size } => {
+                aabb_2d(&|position, rotation| Rectangle { half_size }.aabb_2d(position, rotation))
+            }
+            Collider::Capsule2d {
+                radius,
+                half_length,
+            } => aabb_2d(&|position, rotation| {
+                Capsule2d {
+                    radius,
+                    half_length,
+                }
+                .aabb_2d(position, rotation)
+            }),
+        }
+    }
+}
+
+impl From<Sphere> for Collider {
+    fn from(Sphere { radius }: Sphere) -> Self {
+        Collider::Sphere { radius }
+    }
+}
+
+impl From<Cuboid> for Collider {
+    fn from(Cuboid { half_size }: Cuboid) -> Self {
+        Collider::Cuboid { half_size }
+    }
+}
+
+impl From<Capsule3d> for Collider {
+    fn from(
+        Capsule3d {
+            radius,
+            half_length,
+        }: Capsule3d,
+    ) -> Self {
+        Collider::Capsule {
+            radius,
+            half_length,
+        }
+    }
+}
+
+impl From<Cylinder> for Collider {
+    fn from(
+        Cylinder {
+            radius,
+            half_height,
+        }: Cylinder,
+    ) -> Self {
+        Collider::Cylinder {
+            radius,
+            half_height,
+        }
+    }
+}
+
+impl From<Circle> for Collider {
+    fn from(Circle { radius }: Circle) -> Self {
+        Collider::Circle { radius }
+    }
+}
+
+impl From<Rectangle> for Collider {
+    fn from(Rectangle { half_size }: Rectangle) -> Self {
+        Collider::Rectangle { half_size }
+    }
+}
+
+impl From<Capsule2d> for Collider {
+    fn from(
+        Capsule2d {
+            radius,
+            half_length,
+        }: Capsule2d,
+    ) -> Self {
+        Collider::Capsule2d {
+            radius,
+            half_length,
+        }
+    }
+}
+
+/// Marks a [`Collider`] as a sensor: it detects intersections, but doesn't cause collision
+/// responses.
+///
+/// Sensors are skipped by spatial queries unless
+/// [`SpatialQueryFilter::include_sensors`](crate::SpatialQueryFilter::include_sensors) is set.
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct Sensor;
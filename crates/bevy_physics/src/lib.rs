@@ -0,0 +1,89 @@
+#![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! A physics integration interface for Bevy.
+//!
+//! This crate doesn't simulate physics itself. It defines the components shared by physics
+//! engine integrations, [`RigidBody`], [`Collider`], [`Velocity`] and [`Sensor`], and the
+//! [`PhysicsBackend`] trait through which integrations answer spatial queries. Engine features
+//! and third-party crates that need physics, such as picking or character controllers, use
+//! [`SpatialQuery`] and these components instead of depending on a specific physics engine.
+//!
+//! Integrations should:
+//! - read the components to build their own bodies and colliders, and write the simulated
+//!   transforms and [`Velocity`] back,
+//! - run their systems in the [`PhysicsSet`]s,
+//! - insert a [`SpatialQueryBackend`] wrapping their [`PhysicsBackend`].
+//!
+//! Games that only need to know when 2D shapes touch can use the built-in
+//! [`Collision2dPlugin`](collision_2d::Collision2dPlugin) instead of a physics engine.
+//!
+//! [`SteeringPlugin`](steering::SteeringPlugin) moves agents with steering behaviors, such as
+//! seeking a target or flocking, on top of the spatial queries.
+
+mod backend;
+pub mod collision_2d;
+mod components;
+mod spatial_query;
+pub mod steering;
+
+pub use backend::*;
+pub use components::*;
+pub use spatial_query::*;
+
+/// The physics prelude.
+///
+/// This includes the most common types in this crate, re-exported for your convenience.
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        collision_2d::{
+            Collision2dPlugin, CollisionEnded, CollisionStarted, Collisions2d, TriggerEntered,
+            TriggerExited,
+        },
+        steering::{SteeringAgent, SteeringBehavior, SteeringPlugin},
+        Collider, PhysicsPlugin, PhysicsSet, RigidBody, Sensor, SpatialQuery, SpatialQueryFilter,
+        Velocity,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_ecs::schedule::{IntoSystemSetConfigs, SystemSet};
+
+/// Registers the physics components and configures the [`PhysicsSet`]s.
+///
+/// Physics engine integrations should add this plugin if it hasn't been added yet.
+#[derive(Default)]
+pub struct PhysicsPlugin;
+
+impl Plugin for PhysicsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<RigidBody>()
+            .register_type::<Velocity>()
+            .register_type::<Collider>()
+            .register_type::<Sensor>()
+            .configure_sets(
+                FixedPostUpdate,
+                (PhysicsSet::Prepare, PhysicsSet::Step, PhysicsSet::Writeback).chain(),
+            );
+    }
+}
+
+/// The system sets physics engine integrations run their systems in, in order, in
+/// [`FixedPostUpdate`].
+///
+/// Systems changing bodies during the fixed timestep should run before [`PhysicsSet::Prepare`],
+/// and systems reading the simulation results after [`PhysicsSet::Writeback`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhysicsSet {
+    /// Copies the components of the world to the physics engine.
+    Prepare,
+    /// Steps the simulation.
+    Step,
+    /// Copies the simulation results back to the components of the world.
+    Writeback,
+}
@@ -0,0 +1,207 @@
+use bevy_ecs::{
+    entity::Entity,
+    system::{Res, SystemParam},
+};
+use bevy_math::{Dir3, Quat, Ray3d, Vec3};
+
+use crate::{Collider, PhysicsBackend, RayHit, ShapeHit, SpatialQueryBackend, SpatialQueryFilter};
+
+/// A [`SystemParam`] casting rays and shapes against the colliders of the world, using the
+/// [`PhysicsBackend`] of the app.
+///
+/// Every query returns no hits when no backend has been inserted, so features built on spatial
+/// queries degrade gracefully in apps without physics.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_physics::{SpatialQuery, SpatialQueryFilter};
+/// #[derive(Component)]
+/// struct Player;
+///
+/// fn ground_check(
+///     spatial_query: SpatialQuery,
+///     players: Query<(Entity, &Transform), With<Player>>,
+/// ) {
+///     for (player, transform) in &players {
+///         let ray = Ray3d::new(transform.translation, Vec3::NEG_Y);
+///         let filter = SpatialQueryFilter::default().excluding([player]);
+///         if let Some(hit) = spatial_query.cast_ray(ray, 0.1, &filter) {
+///             println!("standing on {:?}", hit.entity);
+///         }
+///     }
+/// }
+/// # use bevy_transform::components::Transform;
+/// # bevy_ecs::system::assert_is_system(ground_check);
+/// ```
+#[derive(SystemParam)]
+pub struct SpatialQuery<'w> {
+    backend: Option<Res<'w, SpatialQueryBackend>>,
+}
+
+impl SpatialQuery<'_> {
+    /// Returns the backend answering the queries, if one has been inserted.
+    pub fn backend(&self) -> Option<&dyn PhysicsBackend> {
+        self.backend.as_deref().map(SpatialQueryBackend::backend)
+    }
+
+    /// Returns the closest collider hit by `ray` within `max_distance`.
+    pub fn cast_ray(
+        &self,
+        ray: Ray3d,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<RayHit> {
+        self.backend()?.cast_ray(ray, max_distance, filter)
+    }
+
+    /// Returns the first collider hit by `shape`, moving from `origin` towards `direction` with
+    /// the given `rotation` for at most `max_distance`.
+    pub fn cast_shape(
+        &self,
+        shape: &Collider,
+        origin: Vec3,
+        rotation: Quat,
+        direction: Dir3,
+        max_distance: f32,
+        filter: &SpatialQueryFilter,
+    ) -> Option<ShapeHit> {
+        self.backend()?
+            .cast_shape(shape, origin, rotation, direction, max_distance, filter)
+    }
+
+    /// Returns every collider intersecting `shape` placed at `position` with `rotation`.
+    pub fn overlaps(
+        &self,
+        shape: &Collider,
+        position: Vec3,
+        rotation: Quat,
+        filter: &SpatialQueryFilter,
+    ) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        if let Some(backend) = self.backend() {
+            backend.overlaps(shape, position, rotation, filter, &mut |entity| {
+                entities.push(entity);
+                true
+            });
+        }
+        entities
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    /// A backend of spheres, tested by brute force.
+    #[derive(Default)]
+    struct Spheres(Vec<(Entity, Vec3, f32)>);
+
+    impl PhysicsBackend for Spheres {
+        fn cast_ray(
+            &self,
+            ray: Ray3d,
+            max_distance: f32,
+            filter: &SpatialQueryFilter,
+        ) -> Option<RayHit> {
+            self.0
+                .iter()
+                .filter(|(entity, ..)| filter.accepts(*entity, false))
+                .filter_map(|&(entity, center, radius)| {
+                    let along = (center - ray.origin).dot(*ray.direction);
+                    let closest = ray.get_point(along);
+                    let offset = (radius * radius - closest.distance_squared(center)).sqrt();
+                    let distance = along - offset;
+                    (distance >= 0.0 && distance <= max_distance).then(|| {
+                        let point = ray.get_point(distance);
+                        RayHit {
+                            entity,
+                            distance,
+                            point,
+                            normal: (point - center).normalize(),
+                        }
+                    })
+                })
+                .min_by(|a, b| a.distance.total_cmp(&b.distance))
+        }
+
+        fn cast_shape(
+            &self,
+            _shape: &Collider,
+            _origin: Vec3,
+            _rotation: Quat,
+            _direction: Dir3,
+            _max_distance: f32,
+            _filter: &SpatialQueryFilter,
+        ) -> Option<ShapeHit> {
+            None
+        }
+
+        fn overlaps(
+            &self,
+            shape: &Collider,
+            position: Vec3,
+            _rotation: Quat,
+            filter: &SpatialQueryFilter,
+            callback: &mut dyn FnMut(Entity) -> bool,
+        ) {
+            let Collider::Sphere {
+                radius: shape_radius,
+            } = *shape
+            else {
+                return;
+            };
+            for &(entity, center, radius) in &self.0 {
+                if filter.accepts(entity, false)
+                    && center.distance(position) <= radius + shape_radius
+                    && !callback(entity)
+                {
+                    return;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn spatial_query_uses_backend() {
+        let mut world = World::new();
+        let hits = world.run_system_once(|query: SpatialQuery| {
+            query.cast_ray(
+                Ray3d::new(Vec3::ZERO, Vec3::X),
+                10.0,
+                &SpatialQueryFilter::default(),
+            )
+        });
+        assert_eq!(hits, None);
+
+        let near = world.spawn_empty().id();
+        let far = world.spawn_empty().id();
+        world.insert_resource(SpatialQueryBackend::new(Spheres::default()));
+        world
+            .resource_mut::<SpatialQueryBackend>()
+            .get_mut::<Spheres>()
+            .unwrap()
+            .0
+            .extend([(near, Vec3::X * 3.0, 1.0), (far, Vec3::X * 6.0, 1.0)]);
+
+        let (hit, filtered_hit, overlaps) = world.run_system_once(move |query: SpatialQuery| {
+            let ray = Ray3d::new(Vec3::ZERO, Vec3::X);
+            (
+                query.cast_ray(ray, 10.0, &SpatialQueryFilter::default()),
+                query.cast_ray(ray, 10.0, &SpatialQueryFilter::default().excluding([near])),
+                query.overlaps(
+                    &Collider::Sphere { radius: 2.5 },
+                    Vec3::ZERO,
+                    Quat::IDENTITY,
+                    &SpatialQueryFilter::default(),
+                ),
+            )
+        });
+        let hit = hit.unwrap();
+        assert_eq!(hit.entity, near);
+        assert!((hit.distance - 2.0).abs() < 1e-5);
+        assert_eq!(filtered_hit.map(|hit| hit.entity), Some(far));
+        assert_eq!(overlaps, vec![near]);
+    }
+}
@@ -0,0 +1,491 @@
+//! Steering behaviors, the building blocks of crowd and flocking AI.
+//!
+//! A [`SteeringAgent`] combines weighted [`SteeringBehavior`]s into a steering force every fixed
+//! timestep, and accelerates its [`Velocity`] with it. The group behaviors,
+//! [`SteeringBehavior::Separation`], [`SteeringBehavior::Alignment`] and
+//! [`SteeringBehavior::Cohesion`], react to the neighbors of the agent, found with
+//! [`SpatialQuery::overlaps`]: the neighbors need a [`Collider`] known to the
+//! [`SpatialQueryBackend`](crate::SpatialQueryBackend) of the app.
+//!
+//! The force calculators behind each behavior, such as [`seek`] and [`separation`], are also
+//! available to build custom steering systems.
+
+use std::f32::consts::PI;
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_math::{
+    noise::{Noise, PerlinNoise},
+    Dir3, Quat, Vec2, Vec3,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{
+    Collider, PhysicsPlugin, PhysicsSet, RigidBody, SpatialQuery, SpatialQueryFilter, Velocity,
+};
+
+/// Steers the entities with a [`SteeringAgent`].
+///
+/// Steering runs in [`FixedPostUpdate`], before [`PhysicsSet::Prepare`], so that physics engine
+/// integrations see the steered [`Velocity`].
+#[derive(Default)]
+pub struct SteeringPlugin;
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PhysicsPlugin>() {
+            app.add_plugins(PhysicsPlugin);
+        }
+        app.register_type::<SteeringAgent>().add_systems(
+            FixedPostUpdate,
+            (compute_steering, apply_steering)
+                .chain()
+                .before(PhysicsSet::Prepare),
+        );
+    }
+}
+
+/// A behavior contributing to the steering force of a [`SteeringAgent`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+pub enum SteeringBehavior {
+    /// Moves towards a point at full speed. See [`seek`].
+    Seek(Vec3),
+    /// Moves away from a point at full speed while it is closer than `panic_distance`. See
+    /// [`flee`].
+    Flee {
+        /// The point to flee from.
+        threat: Vec3,
+        /// The distance from which the agent starts fleeing.
+        panic_distance: f32,
+    },
+    /// Moves towards a point, slowing down to stop on it. See [`arrive`].
+    Arrive {
+        /// The point to stop on.
+        target: Vec3,
+        /// The distance from the target at which the agent starts slowing down.
+        slowing_radius: f32,
+    },
+    /// Wanders around smoothly. See [`wander`].
+    Wander {
+        /// The distance ahead of the agent of the circle it steers towards.
+        distance: f32,
+        /// The radius of the circle the agent steers towards.
+        radius: f32,
+        /// How fast the wandering direction changes.
+        rate: f32,
+        /// The axis the agent turns around: [`Dir3::Y`] for agents walking on the ground, and
+        /// [`Dir3::Z`] for 2D agents.
+        axis: Dir3,
+    },
+    /// Keeps away from all the neighbors of the agent. See [`separation`].
+    Separation,
+    /// Moves in the same direction as the neighboring agents. See [`alignment`].
+    Alignment,
+    /// Moves towards the center of the neighboring agents. See [`cohesion`].
+    Cohesion,
+}
+
+/// Moves an entity by combining weighted [`SteeringBehavior`]s.
+///
+/// The agent needs a [`Velocity`], which steering accelerates. Entities with a [`RigidBody`] are
+/// then moved by the physics engine, and the [`Transform`] of entities without one is moved by
+/// steering directly.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::prelude::*;
+/// # use bevy_physics::{prelude::*, steering::*};
+/// # use bevy_transform::components::Transform;
+/// fn spawn_boid(mut commands: Commands) {
+///     commands.spawn((
+///         SteeringAgent::new(4.0, 8.0, 2.0)
+///             .with(SteeringBehavior::Separation, 1.5)
+///             .with(SteeringBehavior::Alignment, 1.0)
+///             .with(SteeringBehavior::Cohesion, 1.0),
+///         Velocity::default(),
+///         Collider::Circle { radius: 0.25 },
+///         Transform::default(),
+///     ));
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_boid);
+/// ```
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub struct SteeringAgent {
+    /// The maximum speed of the agent.
+    pub max_speed: f32,
+    /// The maximum length of the steering force, which limits how fast the agent turns and
+    /// accelerates.
+    pub max_force: f32,
+    /// The distance within which other colliders are neighbors of the agent.
+    ///
+    /// The neighborhood is a circle on the xy plane for agents with a 2D [`Collider`], and a
+    /// sphere otherwise.
+    pub neighbor_radius: f32,
+    /// The behaviors of the agent, with their weights in the steering force.
+    pub behaviors: Vec<(SteeringBehavior, f32)>,
+    force: Vec3,
+}
+
+impl Default for SteeringAgent {
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+}
+
+impl SteeringAgent {
+    /// Creates an agent without behaviors.
+    pub fn new(max_speed: f32, max_force: f32, neighbor_radius: f32) -> Self {
+        Self {
+            max_speed,
+            max_force,
+            neighbor_radius,
+            behaviors: Vec::new(),
+            force: Vec3::ZERO,
+        }
+    }
+
+    /// Returns this agent with `behavior` added, weighted by `weight`.
+    pub fn with(mut self, behavior: SteeringBehavior, weight: f32) -> Self {
+        self.behaviors.push((behavior, weight));
+        self
+    }
+
+    /// The steering force computed during the last fixed timestep.
+    pub fn force(&self) -> Vec3 {
+        self.force
+    }
+}
+
+/// Returns the steering force moving an agent towards `target` at `max_speed`.
+pub fn seek(position: Vec3, velocity: Vec3, target: Vec3, max_speed: f32) -> Vec3 {
+    (target - position).normalize_or_zero() * max_speed - velocity
+}
+
+/// Returns the steering force moving an agent away from `threat` at `max_speed`.
+pub fn flee(position: Vec3, velocity: Vec3, threat: Vec3, max_speed: f32) -> Vec3 {
+    (position - threat).normalize_or_zero() * max_speed - velocity
+}
+
+/// Returns the steering force moving an agent towards `target`, slowing down within
+/// `slowing_radius` of it to stop on it.
+pub fn arrive(
+    position: Vec3,
+    velocity: Vec3,
+    target: Vec3,
+    max_speed: f32,
+    slowing_radius: f32,
+) -> Vec3 {
+    let offset = target - position;
+    let distance = offset.length();
+    if distance <= f32::EPSILON {
+        return -velocity;
+    }
+    let speed = if distance < slowing_radius {
+        max_speed * distance / slowing_radius
+    } else {
+        max_speed
+    };
+    offset / distance * speed - velocity
+}
+
+/// Returns the steering force moving an agent towards a point of a circle `distance` ahead of
+/// it, on the plane perpendicular to `axis`.
+///
+/// The point moves around the circle by `angle`, which should change smoothly over time.
+pub fn wander(
+    position: Vec3,
+    velocity: Vec3,
+    max_speed: f32,
+    distance: f32,
+    radius: f32,
+    angle: f32,
+    axis: Dir3,
+) -> Vec3 {
+    let heading = velocity.reject_from_normalized(*axis).normalize_or_zero();
+    let heading = if heading == Vec3::ZERO {
+        axis.any_orthonormal_vector()
+    } else {
+        heading
+    };
+    let target =
+        position + heading * distance + Quat::from_axis_angle(*axis, angle) * heading * radius;
+    seek(position, velocity, target, max_speed)
+}
+
+/// Returns the steering force moving an agent away from `neighbors`, the closest the most.
+pub fn separation(
+    position: Vec3,
+    velocity: Vec3,
+    neighbors: impl IntoIterator<Item = Vec3>,
+    max_speed: f32,
+) -> Vec3 {
+    let away: Vec3 = neighbors
+        .into_iter()
+        .map(|neighbor| {
+            let offset = position - neighbor;
+            offset / offset.length_squared().max(f32::EPSILON)
+        })
+        .sum();
+    if away == Vec3::ZERO {
+        return Vec3::ZERO;
+    }
+    away.normalize_or_zero() * max_speed - velocity
+}
+
+/// Returns the steering force matching the velocity of an agent with the average velocity of
+/// its `neighbors`.
+pub fn alignment(velocity: Vec3, neighbor_velocities: impl IntoIterator<Item = Vec3>) -> Vec3 {
+    let (sum, count) = neighbor_velocities
+        .into_iter()
+        .fold((Vec3::ZERO, 0), |(sum, count), velocity| {
+            (sum + velocity, count + 1)
+        });
+    if count == 0 {
+        return Vec3::ZERO;
+    }
+    sum / count as f32 - velocity
+}
+
+/// Returns the steering force moving an agent towards the center of its `neighbors`.
+pub fn cohesion(
+    position: Vec3,
+    velocity: Vec3,
+    neighbors: impl IntoIterator<Item = Vec3>,
+    max_speed: f32,
+) -> Vec3 {
+    let (sum, count) = neighbors
+        .into_iter()
+        .fold((Vec3::ZERO, 0), |(sum, count), neighbor| {
+            (sum + neighbor, count + 1)
+        });
+    if count == 0 {
+        return Vec3::ZERO;
+    }
+    seek(position, velocity, sum / count as f32, max_speed)
+}
+
+/// A collider near a [`SteeringAgent`].
+struct Neighbor {
+    position: Vec3,
+    velocity: Vec3,
+    is_agent: bool,
+}
+
+/// Computes the steering force of every [`SteeringAgent`], in parallel.
+pub fn compute_steering(
+    time: Res<Time>,
+    spatial_query: SpatialQuery,
+    mut agents: Query<(
+        Entity,
+        &mut SteeringAgent,
+        &GlobalTransform,
+        &Velocity,
+        Option<&Collider>,
+    )>,
+    bodies: Query<(&GlobalTransform, Option<&Velocity>, Has<SteeringAgent>)>,
+) {
+    let wander_noise = PerlinNoise::new(0);
+    let elapsed = time.elapsed_seconds_wrapped();
+    agents
+        .par_iter_mut()
+        .for_each(|(entity, mut agent, transform, velocity, collider)| {
+            let position = transform.translation();
+            let velocity = velocity.linear;
+            let needs_neighbors = agent.behaviors.iter().any(|(behavior, _)| {
+                matches!(
+                    behavior,
+                    SteeringBehavior::Separation
+                        | SteeringBehavior::Alignment
+                        | SteeringBehavior::Cohesion
+                )
+            });
+            let neighbors: Vec<Neighbor> = if needs_neighbors {
+                let radius = agent.neighbor_radius;
+                let neighborhood = if collider.is_some_and(Collider::is_2d) {
+                    Collider::Circle { radius }
+                } else {
+                    Collider::Sphere { radius }
+                };
+                spatial_query
+                    .overlaps(
+                        &neighborhood,
+                        position,
+                        Quat::IDENTITY,
+                        &SpatialQueryFilter::default().excluding([entity]),
+                    )
+                    .into_iter()
+                    .filter_map(|neighbor| {
+                        let (transform, velocity, is_agent) = bodies.get(neighbor).ok()?;
+                        Some(Neighbor {
+                            position: transform.translation(),
+                            velocity: velocity.map_or(Vec3::ZERO, |velocity| velocity.linear),
+                            is_agent,
+                        })
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            let agents = || neighbors.iter().filter(|neighbor| neighbor.is_agent);
+
+            let max_speed = agent.max_speed;
+            let force: Vec3 = agent
+                .behaviors
+                .iter()
+                .map(|&(behavior, weight)| {
+                    let force = match behavior {
+                        SteeringBehavior::Seek(target) => {
+                            seek(position, velocity, target, max_speed)
+                        }
+                        SteeringBehavior::Flee {
+                            threat,
+                            panic_distance,
+                        } => {
+                            if position.distance_squared(threat) < panic_distance * panic_distance {
+                                flee(position, velocity, threat, max_speed)
+                            } else {
+                                Vec3::ZERO
+                            }
+                        }
+                        SteeringBehavior::Arrive {
+                            target,
+                            slowing_radius,
+                        } => arrive(position, velocity, target, max_speed, slowing_radius),
+                        SteeringBehavior::Wander {
+                            distance,
+                            radius,
+                            rate,
+                            axis,
+                        } => {
+                            // Each agent follows its own smooth path through the noise.
+                            let angle = wander_noise
+                                .sample(Vec2::new(elapsed * rate, entity.index() as f32 * 7.31))
+                                * PI;
+                            wander(position, velocity, max_speed, distance, radius, angle, axis)
+                        }
+                        SteeringBehavior::Separation => separation(
+                            position,
+                            velocity,
+                            neighbors.iter().map(|neighbor| neighbor.position),
+                            max_speed,
+                        ),
+                        SteeringBehavior::Alignment => {
+                            alignment(velocity, agents().map(|neighbor| neighbor.velocity))
+                        }
+                        SteeringBehavior::Cohesion => cohesion(
+                            position,
+                            velocity,
+                            agents().map(|neighbor| neighbor.position),
+                            max_speed,
+                        ),
+                    };
+                    force * weight
+                })
+                .sum();
+            agent.force = force.clamp_length_max(agent.max_force);
+        });
+}
+
+/// Accelerates the [`Velocity`] of every [`SteeringAgent`] with its steering force, and moves
+/// the agents without a [`RigidBody`].
+pub fn apply_steering(
+    time: Res<Time>,
+    mut agents: Query<(
+        &SteeringAgent,
+        &mut Velocity,
+        Option<&mut Transform>,
+        Has<RigidBody>,
+    )>,
+) {
+    let delta = time.delta_seconds();
+    agents
+        .par_iter_mut()
+        .for_each(|(agent, mut velocity, transform, has_body)| {
+            velocity.linear =
+                (velocity.linear + agent.force * delta).clamp_length_max(agent.max_speed);
+            if let (Some(mut transform), false) = (transform, has_body) {
+                transform.translation += velocity.linear * delta;
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        collision_2d::{detect_collisions_2d, Collision2dBackend, Collisions2d},
+        SpatialQueryBackend,
+    };
+    use bevy_ecs::system::RunSystemOnce;
+
+    #[test]
+    fn force_calculators() {
+        let force = seek(Vec3::ZERO, Vec3::ZERO, Vec3::X * 10.0, 2.0);
+        assert_eq!(force, Vec3::X * 2.0);
+        let force = flee(Vec3::ZERO, Vec3::X, Vec3::X, 2.0);
+        assert_eq!(force, Vec3::NEG_X * 3.0);
+
+        // Arriving slows down within the slowing radius, and stops on the target.
+        let force = arrive(Vec3::ZERO, Vec3::ZERO, Vec3::X, 4.0, 2.0);
+        assert_eq!(force, Vec3::X * 2.0);
+        assert_eq!(arrive(Vec3::X, Vec3::Y, Vec3::X, 4.0, 2.0), Vec3::NEG_Y);
+
+        // The closest neighbor pushes the most.
+        let force = separation(
+            Vec3::ZERO,
+            Vec3::ZERO,
+            [Vec3::X * 0.5, Vec3::NEG_Y * 4.0],
+            1.0,
+        );
+        assert!(force.x < 0.0 && force.y > 0.0 && -force.x > force.y);
+
+        let force = alignment(Vec3::ZERO, [Vec3::X, Vec3::Y]);
+        assert_eq!(force, Vec3::new(0.5, 0.5, 0.0));
+        let force = cohesion(Vec3::ZERO, Vec3::ZERO, [Vec3::X, Vec3::Y * 2.0], 1.0);
+        assert!((force - Vec3::new(1.0, 2.0, 0.0).normalize()).length() < 1e-6);
+        assert_eq!(cohesion(Vec3::ZERO, Vec3::X, [], 1.0), Vec3::ZERO);
+
+        // Wandering steers ahead of the agent, on the plane perpendicular to the axis.
+        let force = wander(Vec3::ZERO, Vec3::X, 1.0, 2.0, 1.0, PI / 2.0, Dir3::Z);
+        assert!(force.y > 0.0 && force.z.abs() < 1e-6);
+    }
+
+    #[test]
+    fn flocking_agents_react_to_neighbors() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<Collisions2d>();
+        world.init_resource::<Events<crate::collision_2d::CollisionStarted>>();
+        world.init_resource::<Events<crate::collision_2d::CollisionEnded>>();
+        world.init_resource::<Events<crate::collision_2d::TriggerEntered>>();
+        world.init_resource::<Events<crate::collision_2d::TriggerExited>>();
+        world.insert_resource(SpatialQueryBackend::new(Collision2dBackend::default()));
+
+        let boid = |x: f32, velocity: Vec3| {
+            (
+                SteeringAgent::new(1.0, 10.0, 2.0)
+                    .with(SteeringBehavior::Separation, 1.0)
+                    .with(SteeringBehavior::Alignment, 1.0),
+                Velocity::linear(velocity),
+                Collider::Circle { radius: 0.1 },
+                GlobalTransform::from_xyz(x, 0.0, 0.0),
+            )
+        };
+        let left = world.spawn(boid(0.0, Vec3::Y)).id();
+        let right = world.spawn(boid(1.0, Vec3::Y)).id();
+        let far = world.spawn(boid(10.0, Vec3::NEG_Y)).id();
+
+        world.run_system_once(detect_collisions_2d);
+        world.run_system_once(compute_steering);
+
+        let force = |entity| world.get::<SteeringAgent>(entity).unwrap().force();
+        // The close boids push each other apart, and already move in the same direction.
+        assert_eq!(force(left), Vec3::NEG_X - Vec3::Y);
+        assert_eq!(force(right), Vec3::X - Vec3::Y);
+        // The far boid has no neighbors.
+        assert_eq!(force(far), Vec3::ZERO);
+    }
+}
@@ -8,6 +8,9 @@ mod parse;
 pub use parse::ParseError;
 use parse::PathParser;
 
+mod query;
+pub use query::*;
+
 use crate::Reflect;
 use std::fmt;
 use thiserror::Error;
@@ -224,6 +227,38 @@ impl<'a> ReflectPath<'a> for &'a str {
 /// );
 /// ```
 ///
+/// # Wildcards and filters
+///
+/// [`reflect_query`](GetPath::reflect_query) and [`reflect_query_mut`](GetPath::reflect_query_mut)
+/// accept paths matching any number of elements, with wildcards (`children[*].stats.hp`) and
+/// filters (`inventory[?(@.kind == "potion")]`).
+/// See [`PathQuery`] for their syntax.
+///
+/// ## Example
+/// ```
+/// # use bevy_reflect::{GetPath, Reflect};
+/// #[derive(Reflect)]
+/// struct Child {
+///   hp: u32,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Unit {
+///   children: Vec<Child>,
+/// }
+///
+/// let mut unit = Unit {
+///   children: vec![Child { hp: 5 }, Child { hp: 0 }],
+/// };
+/// let hps: Vec<_> = unit.reflect_query("children[*].hp").unwrap().collect();
+/// assert_eq!(hps.len(), 2);
+///
+/// // Heal the children that are still alive
+/// let healed = unit.reflect_query_mut("children[?(@.hp > 0)].hp", |hp| hp.apply(&20u32));
+/// assert_eq!(healed.unwrap(), 1);
+/// assert_eq!(unit.children[0].hp, 20);
+/// ```
+///
 /// [`Struct`]: crate::Struct
 /// [`Tuple`]: crate::Tuple
 /// [`TupleStruct`]: crate::TupleStruct
@@ -271,6 +306,26 @@ pub trait GetPath: Reflect {
     fn path_mut<'p, T: Reflect>(&mut self, path: impl ReflectPath<'p>) -> PathResult<'p, &mut T> {
         path.element_mut(self.as_reflect_mut())
     }
+
+    /// Returns an iterator over the values matched by `query`, which may contain wildcards and
+    /// filters.
+    ///
+    /// To avoid parsing the query at every call, use [`PathQuery::matches`] instead.
+    fn reflect_query<'p>(&self, query: &'p str) -> PathResult<'p, PathMatches<'_>> {
+        Ok(PathQuery::parse(query)?.matches(self.as_reflect()))
+    }
+
+    /// Calls `f` on each value matched by `query`, which may contain wildcards and filters, and
+    /// returns the number of matches.
+    ///
+    /// To avoid parsing the query at every call, use [`PathQuery::for_each_mut`] instead.
+    fn reflect_query_mut<'p>(
+        &mut self,
+        query: &'p str,
+        f: impl FnMut(&mut dyn Reflect),
+    ) -> PathResult<'p, usize> {
+        Ok(PathQuery::parse(query)?.for_each_mut(self.as_reflect_mut(), f))
+    }
 }
 
 // Implement `GetPath` for `dyn Reflect`
@@ -15,7 +15,7 @@ pub struct ParseError<'a>(Error<'a>);
 
 /// A parse error for a path string.
 #[derive(Debug, PartialEq, Eq, Error)]
-enum Error<'a> {
+pub(super) enum Error<'a> {
     #[error("expected an identifier, but reached end of path string")]
     NoIdent,
 
@@ -33,6 +33,22 @@ enum Error<'a> {
 
     #[error("a ']' was found before an opening '['")]
     CloseBeforeOpen,
+
+    #[error("a '[?(' wasn't closed, reached end of path string before finding a ')]'")]
+    UnclosedFilter,
+
+    #[error("invalid filter `{0}`, expected `@` followed by a path and optionally a comparison with a string, number or boolean")]
+    InvalidFilter(&'a str),
+}
+
+impl<'a> Error<'a> {
+    pub(super) fn at(self, path: &'a str, offset: usize) -> ReflectPathError<'a> {
+        ReflectPathError::ParseError {
+            offset,
+            path,
+            error: ParseError(self),
+        }
+    }
 }
 
 pub(super) struct PathParser<'a> {
@@ -109,11 +125,7 @@ impl<'a> Iterator for PathParser<'a> {
         let offset = self.offset();
         Some((
             self.access_following(token)
-                .map_err(|error| ReflectPathError::ParseError {
-                    offset,
-                    path: self.path,
-                    error: ParseError(error),
-                }),
+                .map_err(|error| error.at(self.path, offset)),
             offset,
         ))
     }
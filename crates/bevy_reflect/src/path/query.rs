@@ -0,0 +1,591 @@
+//! Paths matching any number of elements, with wildcards and filters.
+
+use std::{cmp::Ordering, fmt, vec};
+
+use super::{
+    parse::{Error, PathParser},
+    OffsetAccess, ParsedPath, PathResult, ReflectPath, ReflectPathError,
+};
+use crate::{Reflect, ReflectMut, ReflectRef};
+
+/// A segment of a [`PathQuery`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum QuerySegment {
+    /// A single element access, as in a [`ParsedPath`].
+    Access(OffsetAccess),
+    /// Every child element: `[*]` or `.*`.
+    Wildcard,
+    /// The child elements for which a [`PathFilter`] holds: `[?(@.kind == "potion")]`.
+    Filter(PathFilter),
+}
+
+/// A predicate on the child elements matched by a [`QuerySegment::Filter`].
+///
+/// A filter without comparison holds if the element at [`path`](Self::path) exists, and is
+/// `true` if it is a [`bool`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathFilter {
+    /// The path of the compared value, relative to the child element (`@`).
+    pub path: ParsedPath,
+    /// The comparison the value must satisfy.
+    pub comparison: Option<(FilterOp, FilterValue)>,
+}
+
+/// A comparison operator of a [`PathFilter`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FilterOp {
+    /// `==`
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+}
+
+/// A literal compared by a [`PathFilter`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterValue {
+    /// A quoted string, compared with [`String`]s, `&'static str`s and the variant names of enums.
+    String(String),
+    /// A number, compared with every primitive numeric type.
+    Number(f64),
+    /// `true` or `false`.
+    Bool(bool),
+}
+
+/// A pre-parsed path matching any number of elements within a type.
+///
+/// On top of the syntax of [`GetPath`](super::GetPath), a query may contain:
+/// - Wildcards (`[*]` or `.*`), matching every element of a list, array or map and every field
+///   of a struct, tuple or enum.
+/// - Filters (`[?(@.path == value)]`), matching the elements a wildcard would match for which the
+///   comparison holds. The left-hand side is a path relative to the element (`@`), and the
+///   right-hand side a quoted string, a number or a boolean. The supported operators are `==`,
+///   `!=`, `<`, `<=`, `>` and `>=`. Without a comparison (`[?(@.path)]`), the filter holds if the
+///   path exists and isn't a `false` boolean.
+///
+/// Elements that don't have the accessed fields, or that can't be compared with the literal of a
+/// filter, are skipped instead of causing an error.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_reflect::{GetPath, PathQuery, Reflect};
+/// #[derive(Reflect)]
+/// struct Item {
+///     kind: String,
+///     charges: u32,
+/// }
+///
+/// #[derive(Reflect)]
+/// struct Inventory {
+///     items: Vec<Item>,
+/// }
+///
+/// let mut inventory = Inventory {
+///     items: vec![
+///         Item { kind: "potion".into(), charges: 3 },
+///         Item { kind: "sword".into(), charges: 0 },
+///         Item { kind: "potion".into(), charges: 1 },
+///     ],
+/// };
+///
+/// let charges: Vec<_> = inventory
+///     .reflect_query(r#"items[?(@.kind == "potion")].charges"#)
+///     .unwrap()
+///     .filter_map(|charges| charges.downcast_ref::<u32>())
+///     .collect();
+/// assert_eq!(charges, [&3, &1]);
+///
+/// let low = PathQuery::parse("items[?(@.charges < 2)].charges").unwrap();
+/// low.for_each_mut(&mut inventory, |charges| charges.apply(&5u32));
+/// assert_eq!(inventory.items[1].charges, 5);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathQuery(
+    /// The segments of the query, applied one after the other.
+    pub Vec<QuerySegment>,
+);
+
+impl PathQuery {
+    /// Parses a [`PathQuery`] from a string.
+    ///
+    /// See the type documentation for the syntax.
+    pub fn parse(string: &str) -> PathResult<Self> {
+        let bytes = string.as_bytes();
+        let mut segments = Vec::new();
+        let mut start = 0;
+        let mut index = 0;
+        while index < bytes.len() {
+            let rest = &bytes[index..];
+            let (segment, len) = if rest.starts_with(b"[*]") {
+                (QuerySegment::Wildcard, 3)
+            } else if rest.starts_with(b".*") {
+                (QuerySegment::Wildcard, 2)
+            } else if rest.starts_with(b"[?(") {
+                let len = filter_len(rest).ok_or(Error::UnclosedFilter.at(string, index))?;
+                let filter = parse_filter(string, index + 3, index + len - 2)?;
+                (QuerySegment::Filter(filter), len)
+            } else {
+                index += 1;
+                continue;
+            };
+            parse_accesses(string, start, index, &mut segments)?;
+            segments.push(segment);
+            index += len;
+            start = index;
+        }
+        parse_accesses(string, start, bytes.len(), &mut segments)?;
+        Ok(Self(segments))
+    }
+
+    /// Returns an iterator over the elements of `root` matched by this query.
+    pub fn matches<'r>(&self, root: &'r dyn Reflect) -> PathMatches<'r> {
+        let mut matches = vec![root];
+        for segment in &self.0 {
+            let mut next = Vec::new();
+            for value in matches {
+                match segment {
+                    QuerySegment::Access(OffsetAccess { access, offset }) => {
+                        next.extend(access.element(value, *offset).ok());
+                    }
+                    QuerySegment::Wildcard => for_each_child(value, |child| next.push(child)),
+                    QuerySegment::Filter(filter) => for_each_child(value, |child| {
+                        if filter.matches(child) {
+                            next.push(child);
+                        }
+                    }),
+                }
+            }
+            matches = next;
+        }
+        PathMatches(matches.into_iter())
+    }
+
+    /// Calls `f` on each element of `root` matched by this query, and returns the number of
+    /// matches.
+    pub fn for_each_mut(
+        &self,
+        root: &mut dyn Reflect,
+        mut f: impl FnMut(&mut dyn Reflect),
+    ) -> usize {
+        let mut count = 0;
+        visit_mut(&self.0, root, &mut f, &mut count);
+        count
+    }
+}
+
+impl fmt::Display for PathQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for segment in &self.0 {
+            match segment {
+                QuerySegment::Access(OffsetAccess { access, .. }) => write!(f, "{access}")?,
+                QuerySegment::Wildcard => f.write_str("[*]")?,
+                QuerySegment::Filter(filter) => write!(f, "[?({filter})]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PathFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.path)?;
+        if let Some((op, value)) = &self.comparison {
+            let op = match op {
+                FilterOp::Eq => "==",
+                FilterOp::Ne => "!=",
+                FilterOp::Lt => "<",
+                FilterOp::Le => "<=",
+                FilterOp::Gt => ">",
+                FilterOp::Ge => ">=",
+            };
+            write!(f, " {op} ")?;
+            match value {
+                FilterValue::String(string) => write!(f, "{string:?}")?,
+                FilterValue::Number(number) => write!(f, "{number}")?,
+                FilterValue::Bool(boolean) => write!(f, "{boolean}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl PathFilter {
+    /// Returns `true` if the filter holds for `element`.
+    pub fn matches(&self, element: &dyn Reflect) -> bool {
+        let Ok(value) = (&self.path).reflect_element(element) else {
+            return false;
+        };
+        let Some((op, literal)) = &self.comparison else {
+            return value.downcast_ref::<bool>().copied().unwrap_or(true);
+        };
+        let Some(ordering) = literal.compare(value) else {
+            return false;
+        };
+        match op {
+            FilterOp::Eq => ordering.is_eq(),
+            FilterOp::Ne => ordering.is_ne(),
+            FilterOp::Lt => ordering.is_lt(),
+            FilterOp::Le => ordering.is_le(),
+            FilterOp::Gt => ordering.is_gt(),
+            FilterOp::Ge => ordering.is_ge(),
+        }
+    }
+}
+
+impl FilterValue {
+    /// Compares `value` to this literal, if they are comparable.
+    pub fn compare(&self, value: &dyn Reflect) -> Option<Ordering> {
+        match self {
+            FilterValue::String(literal) => {
+                let string = if let Some(string) = value.downcast_ref::<String>() {
+                    string.as_str()
+                } else if let Some(string) = value.downcast_ref::<&'static str>() {
+                    string
+                } else if let ReflectRef::Enum(enum_ref) = value.reflect_ref() {
+                    enum_ref.variant_name()
+                } else {
+                    return None;
+                };
+                Some(string.cmp(literal.as_str()))
+            }
+            FilterValue::Number(literal) => as_f64(value)?.partial_cmp(literal),
+            FilterValue::Bool(literal) => Some(value.downcast_ref::<bool>()?.cmp(literal)),
+        }
+    }
+}
+
+/// An iterator over the elements matched by a [`PathQuery`].
+pub struct PathMatches<'r>(vec::IntoIter<&'r dyn Reflect>);
+
+impl<'r> Iterator for PathMatches<'r> {
+    type Item = &'r dyn Reflect;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for PathMatches<'_> {}
+
+fn as_f64(value: &dyn Reflect) -> Option<f64> {
+    macro_rules! downcast {
+        ($($ty:ty),*) => {
+            $(if let Some(value) = value.downcast_ref::<$ty>() {
+                return Some(*value as f64);
+            })*
+        };
+    }
+    downcast!(f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+    None
+}
+
+fn for_each_child<'r>(value: &'r dyn Reflect, mut f: impl FnMut(&'r dyn Reflect)) {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value.iter_fields().for_each(f),
+        ReflectRef::TupleStruct(value) => value.iter_fields().for_each(f),
+        ReflectRef::Tuple(value) => value.iter_fields().for_each(f),
+        ReflectRef::List(value) => value.iter().for_each(f),
+        ReflectRef::Array(value) => value.iter().for_each(f),
+        ReflectRef::Map(value) => value.iter().for_each(|(_, value)| f(value)),
+        ReflectRef::Enum(value) => value.iter_fields().for_each(|field| f(field.value())),
+        ReflectRef::Value(_) => {}
+    }
+}
+
+fn for_each_child_mut(value: &mut dyn Reflect, f: &mut dyn FnMut(&mut dyn Reflect)) {
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => (0..value.field_len())
+            .for_each(|index| value.field_at_mut(index).into_iter().for_each(&mut *f)),
+        ReflectMut::TupleStruct(value) => (0..value.field_len())
+            .for_each(|index| value.field_mut(index).into_iter().for_each(&mut *f)),
+        ReflectMut::Tuple(value) => (0..value.field_len())
+            .for_each(|index| value.field_mut(index).into_iter().for_each(&mut *f)),
+        ReflectMut::List(value) => {
+            (0..value.len()).for_each(|index| value.get_mut(index).into_iter().for_each(&mut *f));
+        }
+        ReflectMut::Array(value) => {
+            (0..value.len()).for_each(|index| value.get_mut(index).into_iter().for_each(&mut *f));
+        }
+        ReflectMut::Map(value) => (0..value.len()).for_each(|index| {
+            if let Some((_, value)) = value.get_at_mut(index) {
+                f(value);
+            }
+        }),
+        ReflectMut::Enum(value) => (0..value.field_len())
+            .for_each(|index| value.field_at_mut(index).into_iter().for_each(&mut *f)),
+        ReflectMut::Value(_) => {}
+    }
+}
+
+fn visit_mut(
+    segments: &[QuerySegment],
+    value: &mut dyn Reflect,
+    f: &mut dyn FnMut(&mut dyn Reflect),
+    count: &mut usize,
+) {
+    let Some((segment, rest)) = segments.split_first() else {
+        f(value);
+        *count += 1;
+        return;
+    };
+    match segment {
+        QuerySegment::Access(OffsetAccess { access, offset }) => {
+            if let Ok(child) = access.element_mut(value, *offset) {
+                visit_mut(rest, child, f, count);
+            }
+        }
+        QuerySegment::Wildcard => {
+            for_each_child_mut(value, &mut |child| visit_mut(rest, child, f, count));
+        }
+        QuerySegment::Filter(filter) => for_each_child_mut(value, &mut |child| {
+            if filter.matches(child) {
+                visit_mut(rest, child, f, count);
+            }
+        }),
+    }
+}
+
+/// Parses the plain accesses of `string[start..end]` into `segments`.
+fn parse_accesses<'a>(
+    string: &'a str,
+    start: usize,
+    end: usize,
+    segments: &mut Vec<QuerySegment>,
+) -> PathResult<'a, ()> {
+    for (access, offset) in PathParser::new(&string[start..end]) {
+        let access = access.map_err(|error| relocate(error, string, start))?;
+        segments.push(QuerySegment::Access(OffsetAccess {
+            access: access.into_owned(),
+            offset: Some(start + offset),
+        }));
+    }
+    Ok(())
+}
+
+/// Makes the offset of a parse error of `string[start..]` relative to `string`.
+fn relocate<'a>(
+    error: ReflectPathError<'a>,
+    string: &'a str,
+    start: usize,
+) -> ReflectPathError<'a> {
+    match error {
+        ReflectPathError::ParseError { offset, error, .. } => ReflectPathError::ParseError {
+            offset: start + offset,
+            path: string,
+            error,
+        },
+        error => error,
+    }
+}
+
+/// Returns the length of the filter at the start of `bytes`, up to and including its `)]`.
+fn filter_len(bytes: &[u8]) -> Option<usize> {
+    let mut quote = None;
+    for (index, &byte) in bytes.iter().enumerate() {
+        match quote {
+            Some(q) if byte == q => quote = None,
+            None if byte == b'"' || byte == b'\'' => quote = Some(byte),
+            None if bytes[index..].starts_with(b")]") => return Some(index + 2),
+            Some(_) | None => {}
+        }
+    }
+    None
+}
+
+/// Parses the filter expression `string[start..end]`.
+fn parse_filter(string: &str, start: usize, end: usize) -> PathResult<PathFilter> {
+    let expression = &string[start..end];
+    let invalid = || Error::InvalidFilter(expression).at(string, start);
+    let path_start = expression
+        .find(|c: char| !c.is_whitespace())
+        .filter(|&at| expression[at..].starts_with('@'))
+        .ok_or_else(invalid)?
+        + 1;
+    let path_end = expression
+        .find(['=', '!', '<', '>'])
+        .unwrap_or(expression.len());
+
+    let path_text = expression[path_start..path_end].trim_end();
+    if path_text.contains(char::is_whitespace) {
+        return Err(invalid());
+    }
+    let mut segments = Vec::new();
+    let path_start = start + path_start;
+    parse_accesses(
+        string,
+        path_start,
+        path_start + path_text.len(),
+        &mut segments,
+    )?;
+    let path = segments
+        .into_iter()
+        .filter_map(|segment| match segment {
+            QuerySegment::Access(access) => Some(access),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .into();
+
+    let comparison = &expression[path_end..];
+    if comparison.trim().is_empty() {
+        return Ok(PathFilter {
+            path,
+            comparison: None,
+        });
+    }
+    let (op, literal) = [
+        ("==", FilterOp::Eq),
+        ("!=", FilterOp::Ne),
+        ("<=", FilterOp::Le),
+        (">=", FilterOp::Ge),
+        ("<", FilterOp::Lt),
+        (">", FilterOp::Gt),
+    ]
+    .into_iter()
+    .find_map(|(token, op)| comparison.strip_prefix(token).map(|literal| (op, literal)))
+    .ok_or_else(invalid)?;
+
+    let literal = literal.trim();
+    let value = match literal {
+        "true" => FilterValue::Bool(true),
+        "false" => FilterValue::Bool(false),
+        _ => {
+            let quoted = literal
+                .strip_prefix('"')
+                .and_then(|literal| literal.strip_suffix('"'))
+                .or_else(|| {
+                    literal
+                        .strip_prefix('\'')
+                        .and_then(|literal| literal.strip_suffix('\''))
+                });
+            match quoted {
+                Some(string) => FilterValue::String(string.to_owned()),
+                None => FilterValue::Number(literal.parse().map_err(|_| invalid())?),
+            }
+        }
+    };
+    Ok(PathFilter {
+        path,
+        comparison: Some((op, value)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as bevy_reflect, GetPath};
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect)]
+    struct Stats {
+        hp: f32,
+        alive: bool,
+    }
+
+    #[derive(Reflect)]
+    enum Kind {
+        Potion,
+        Weapon { damage: u32 },
+    }
+
+    #[derive(Reflect)]
+    struct Pet {
+        stats: Stats,
+    }
+
+    #[derive(Reflect)]
+    struct Unit {
+        stats: Stats,
+        children: Vec<Pet>,
+        inventory: Vec<Kind>,
+        tags: HashMap<String, u8>,
+    }
+
+    fn stats(hp: f32) -> Stats {
+        Stats {
+            hp,
+            alive: hp > 0.0,
+        }
+    }
+
+    fn hps(root: &Unit, query: &str) -> Vec<f32> {
+        root.reflect_query(query)
+            .unwrap()
+            .map(|hp| *hp.downcast_ref::<f32>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn wildcards_and_filters() {
+        let mut root = Unit {
+            stats: stats(10.0),
+            children: vec![Pet { stats: stats(5.0) }, Pet { stats: stats(0.0) }],
+            inventory: vec![Kind::Potion, Kind::Weapon { damage: 4 }, Kind::Potion],
+            tags: HashMap::from([("level".to_owned(), 3)]),
+        };
+
+        assert_eq!(hps(&root, "children[*].stats.hp"), [5.0, 0.0]);
+        assert_eq!(hps(&root, "children.*.*.hp"), [5.0, 0.0]);
+        assert_eq!(hps(&root, "children[?(@.stats.alive)].stats.hp"), [5.0]);
+        assert_eq!(
+            hps(&root, "children[?(@.stats.hp >= 0)].stats.hp"),
+            [5.0, 0.0]
+        );
+        assert_eq!(hps(&root, "children[?( @.stats.hp < 1 )].stats.hp"), [0.0]);
+        assert_eq!(hps(&root, "children[*].missing.hp"), Vec::<f32>::new());
+        assert_eq!(root.reflect_query("stats.*").unwrap().len(), 2);
+
+        let potions = root
+            .reflect_query(r#"inventory[?(@ == "Potion")]"#)
+            .unwrap();
+        assert_eq!(potions.len(), 2);
+        let damage = root
+            .reflect_query("inventory[?(@.damage > 2)].damage")
+            .unwrap()
+            .next()
+            .unwrap();
+        assert_eq!(damage.downcast_ref::<u32>(), Some(&4));
+        let levels = root.reflect_query("tags[*]").unwrap();
+        assert_eq!(levels.len(), 1);
+
+        let query = PathQuery::parse("children[?(@.stats.hp > 0)]..stats.hp");
+        assert!(query.is_err());
+        let query = PathQuery::parse("children[?(@.stats.hp != 0)].stats.hp").unwrap();
+        assert_eq!(query.to_string(), ".children[?(@.stats.hp != 0)].stats.hp");
+        let count = query.for_each_mut(&mut root, |hp| hp.apply(&20.0f32));
+        assert_eq!(count, 1);
+        assert_eq!(hps(&root, "children[*].stats.hp"), [20.0, 0.0]);
+    }
+
+    #[test]
+    fn parse_invalid_filters() {
+        assert_eq!(
+            PathQuery::parse("a[?(@.b == 1]"),
+            Err(Error::UnclosedFilter.at("a[?(@.b == 1]", 1)),
+        );
+        assert_eq!(
+            PathQuery::parse("a[?(b == 1)]"),
+            Err(Error::InvalidFilter("b == 1").at("a[?(b == 1)]", 4)),
+        );
+        assert_eq!(
+            PathQuery::parse("a[?(@.b ~ 1)]"),
+            Err(Error::InvalidFilter("@.b ~ 1").at("a[?(@.b ~ 1)]", 4)),
+        );
+        assert_eq!(
+            PathQuery::parse(r#"a[?(@.b == "x)]")]"#).unwrap().0.len(),
+            2
+        );
+    }
+}
@@ -238,6 +238,7 @@ pub const INSTANCE_INDEX_SHADER_HANDLE: Handle<Shader> =
 pub const MATHS_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(10665356303104593376);
 pub const COLOR_OPERATIONS_SHADER_HANDLE: Handle<Shader> =
     Handle::weak_from_u128(1844674407370955161);
+pub const NOISE_SHADER_HANDLE: Handle<Shader> = Handle::weak_from_u128(5846130472193306824);
 
 impl Plugin for RenderPlugin {
     /// Initializes the renderer, sets up the [`RenderSet`] and creates the rendering sub-app.
@@ -273,22 +274,24 @@ impl Plugin for RenderPlugin {
                     let primary_window = system_state.get(app.world()).get_single().ok().cloned();
 
                     let settings = render_creation.clone();
-                    let async_renderer = async move {
-                        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-                            backends,
-                            dx12_shader_compiler: settings.dx12_shader_compiler.clone(),
-                            flags: settings.instance_flags,
-                            gles_minor_version: settings.gles3_minor_version,
-                        });
-
-                        // SAFETY: Plugins should be set up on the main thread.
-                        let surface = primary_window.map(|wrapper| unsafe {
-                            let handle = wrapper.get_handle();
-                            instance
-                                .create_surface(handle)
-                                .expect("Failed to create wgpu surface")
-                        });
+                    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+                        backends,
+                        dx12_shader_compiler: settings.dx12_shader_compiler.clone(),
+                        flags: settings.instance_flags,
+                        gles_minor_version: settings.gles3_minor_version,
+                    });
+
+                    // The surface is created here, as some platforms require it to be created on
+                    // the main thread.
+                    // SAFETY: Plugins should be set up on the main thread.
+                    let surface = primary_window.map(|wrapper| unsafe {
+                        let handle = wrapper.get_handle();
+                        instance
+                            .create_surface(handle)
+                            .expect("Failed to create wgpu surface")
+                    });
 
+                    let async_renderer = async move {
                         let request_adapter_options = wgpu::RequestAdapterOptions {
                             power_preference: settings.power_preference,
                             compatible_surface: surface.as_ref(),
@@ -319,9 +322,14 @@ impl Plugin for RenderPlugin {
                     bevy_tasks::IoTaskPool::get()
                         .spawn_local(async_renderer)
                         .detach();
-                    // Otherwise, just block for it to complete
+                    // Otherwise, request the device on the task pool while the other plugins
+                    // build
                     #[cfg(not(target_arch = "wasm32"))]
-                    futures_lite::future::block_on(async_renderer);
+                    app.add_finish_task("render device", async move {
+                        async_renderer.await;
+                        // the resources are inserted by `RenderPlugin::finish`
+                        |_: &mut App| {}
+                    });
 
                     // SAFETY: Plugins should be set up on the main thread.
                     unsafe { initialize_render_app(app) };
@@ -367,6 +375,7 @@ impl Plugin for RenderPlugin {
             "color_operations.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(app, NOISE_SHADER_HANDLE, "noise.wgsl", Shader::from_wgsl);
         if let Some(future_renderer_resources) =
             app.world_mut().remove_resource::<FutureRendererResources>()
         {
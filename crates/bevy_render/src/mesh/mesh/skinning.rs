@@ -39,3 +39,33 @@ impl Deref for SkinnedMeshInverseBindposes {
         &self.0
     }
 }
+
+/// The frames of an animation clip baked into an animation texture, for skinning many instances
+/// of a mesh without a [`SkinnedMesh`] and its joint entities per instance.
+///
+/// Each row of an animation texture holds the joint matrices of one frame: three consecutive
+/// `Rgba32Float` texels per joint, holding the rows of the affine matrix transforming a vertex
+/// from the bind pose to the animated pose, in the space of the mesh. The frames of a clip are
+/// consecutive rows, sampled at `frame_rate` frames per second, and loop from the last frame back
+/// to the first.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct AnimationTextureClip {
+    /// The row of the first frame of the clip.
+    pub first_frame: u32,
+    /// The number of frames of the clip.
+    pub frame_count: u32,
+    /// The number of frames per second the clip was sampled at.
+    pub frame_rate: f32,
+}
+
+impl AnimationTextureClip {
+    /// The duration of a loop of the clip, in seconds.
+    pub fn duration(&self) -> f32 {
+        if self.frame_rate > 0.0 {
+            self.frame_count as f32 / self.frame_rate
+        } else {
+            0.0
+        }
+    }
+}
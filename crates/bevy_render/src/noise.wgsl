@@ -0,0 +1,288 @@
+#define_import_path bevy_render::noise
+
+// Seeded noise functions matching the ones of `bevy_math::noise`: for the same seed, a shader
+// sampling this noise agrees with the CPU, up to floating point precision.
+
+fn noise_hash(value: u32) -> u32 {
+    var x = value;
+    x ^= x >> 16u;
+    x *= 0x7feb352du;
+    x ^= x >> 15u;
+    x *= 0x846ca68bu;
+    x ^= x >> 16u;
+    return x;
+}
+
+fn noise_hash_2d(cell: vec2<i32>, seed: u32) -> u32 {
+    return noise_hash(bitcast<u32>(cell.x) ^ noise_hash(bitcast<u32>(cell.y) ^ noise_hash(seed)));
+}
+
+fn noise_hash_3d(cell: vec3<i32>, seed: u32) -> u32 {
+    return noise_hash(bitcast<u32>(cell.x) ^ noise_hash_2d(cell.yz, seed));
+}
+
+fn noise_hash_to_unit(hash: u32) -> f32 {
+    return f32(hash >> 8u) / 16777216.0;
+}
+
+fn noise_fade_2d(t: vec2<f32>) -> vec2<f32> {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
+
+fn noise_fade_3d(t: vec3<f32>) -> vec3<f32> {
+    return t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+}
+
+fn noise_gradient_2d(hash: u32) -> vec2<f32> {
+    let d = 0.70710678;
+    var gradients = array<vec2<f32>, 8>(
+        vec2(1.0, 0.0), vec2(d, d), vec2(0.0, 1.0), vec2(-d, d),
+        vec2(-1.0, 0.0), vec2(-d, -d), vec2(0.0, -1.0), vec2(d, -d),
+    );
+    return gradients[hash & 7u];
+}
+
+fn noise_gradient_3d(hash: u32) -> vec3<f32> {
+    var gradients = array<vec3<f32>, 12>(
+        vec3(1.0, 1.0, 0.0), vec3(-1.0, 1.0, 0.0), vec3(1.0, -1.0, 0.0), vec3(-1.0, -1.0, 0.0),
+        vec3(1.0, 0.0, 1.0), vec3(-1.0, 0.0, 1.0), vec3(1.0, 0.0, -1.0), vec3(-1.0, 0.0, -1.0),
+        vec3(0.0, 1.0, 1.0), vec3(0.0, -1.0, 1.0), vec3(0.0, 1.0, -1.0), vec3(0.0, -1.0, -1.0),
+    );
+    return gradients[hash % 12u];
+}
+
+fn perlin_corner_2d(cell: vec2<i32>, offset: vec2<f32>, corner: vec2<i32>, seed: u32) -> f32 {
+    let gradient = noise_gradient_2d(noise_hash_2d(cell + corner, seed));
+    return dot(gradient, offset - vec2<f32>(corner));
+}
+
+// Perlin gradient noise, in approximately [-1, 1].
+fn perlin_noise_2d(point: vec2<f32>, seed: u32) -> f32 {
+    let cell = floor(point);
+    let offset = point - cell;
+    let i = vec2<i32>(cell);
+    let u = noise_fade_2d(offset);
+    let bottom = mix(
+        perlin_corner_2d(i, offset, vec2(0, 0), seed),
+        perlin_corner_2d(i, offset, vec2(1, 0), seed),
+        u.x
+    );
+    let top = mix(
+        perlin_corner_2d(i, offset, vec2(0, 1), seed),
+        perlin_corner_2d(i, offset, vec2(1, 1), seed),
+        u.x
+    );
+    return mix(bottom, top, u.y) * 1.41421356;
+}
+
+fn perlin_corner_3d(cell: vec3<i32>, offset: vec3<f32>, corner: vec3<i32>, seed: u32) -> f32 {
+    let gradient = noise_gradient_3d(noise_hash_3d(cell + corner, seed));
+    return dot(gradient, offset - vec3<f32>(corner));
+}
+
+// Perlin gradient noise, in approximately [-1, 1].
+fn perlin_noise_3d(point: vec3<f32>, seed: u32) -> f32 {
+    let cell = floor(point);
+    let offset = point - cell;
+    let i = vec3<i32>(cell);
+    let u = noise_fade_3d(offset);
+    let near = mix(
+        mix(
+            perlin_corner_3d(i, offset, vec3(0, 0, 0), seed),
+            perlin_corner_3d(i, offset, vec3(1, 0, 0), seed),
+            u.x
+        ),
+        mix(
+            perlin_corner_3d(i, offset, vec3(0, 1, 0), seed),
+            perlin_corner_3d(i, offset, vec3(1, 1, 0), seed),
+            u.x
+        ),
+        u.y
+    );
+    let far = mix(
+        mix(
+            perlin_corner_3d(i, offset, vec3(0, 0, 1), seed),
+            perlin_corner_3d(i, offset, vec3(1, 0, 1), seed),
+            u.x
+        ),
+        mix(
+            perlin_corner_3d(i, offset, vec3(0, 1, 1), seed),
+            perlin_corner_3d(i, offset, vec3(1, 1, 1), seed),
+            u.x
+        ),
+        u.y
+    );
+    return mix(near, far, u.z);
+}
+
+fn simplex_corner_2d(cell: vec2<i32>, offset: vec2<f32>, corner: vec2<i32>, seed: u32) -> f32 {
+    let falloff = 0.5 - dot(offset, offset);
+    if falloff <= 0.0 {
+        return 0.0;
+    }
+    let gradient = noise_gradient_2d(noise_hash_2d(cell + corner, seed));
+    return falloff * falloff * falloff * falloff * dot(gradient, offset);
+}
+
+// Simplex noise, in approximately [-1, 1].
+fn simplex_noise_2d(point: vec2<f32>, seed: u32) -> f32 {
+    let skew = 0.3660254;
+    let unskew = 0.21132487;
+
+    let cell = floor(point + (point.x + point.y) * skew);
+    let first = point - (cell - (cell.x + cell.y) * unskew);
+    var corner_step = vec2(0, 1);
+    if first.x > first.y {
+        corner_step = vec2(1, 0);
+    }
+    let second = first - vec2<f32>(corner_step) + unskew;
+    let third = first - 1.0 + 2.0 * unskew;
+
+    let i = vec2<i32>(cell);
+    return (simplex_corner_2d(i, first, vec2(0, 0), seed)
+        + simplex_corner_2d(i, second, corner_step, seed)
+        + simplex_corner_2d(i, third, vec2(1, 1), seed)) * 99.2;
+}
+
+fn simplex_corner_3d(cell: vec3<i32>, offset: vec3<f32>, corner: vec3<i32>, seed: u32) -> f32 {
+    let falloff = 0.6 - dot(offset, offset);
+    if falloff <= 0.0 {
+        return 0.0;
+    }
+    let gradient = noise_gradient_3d(noise_hash_3d(cell + corner, seed));
+    return falloff * falloff * falloff * falloff * dot(gradient, offset);
+}
+
+// Simplex noise, in approximately [-1, 1].
+fn simplex_noise_3d(point: vec3<f32>, seed: u32) -> f32 {
+    let skew = 1.0 / 3.0;
+    let unskew = 1.0 / 6.0;
+
+    let cell = floor(point + (point.x + point.y + point.z) * skew);
+    let first = point - (cell - (cell.x + cell.y + cell.z) * unskew);
+
+    // The two intermediate corners of the tetrahedron containing the point step along the
+    // largest, then the second largest, offset axes.
+    var step_1: vec3<i32>;
+    var step_2: vec3<i32>;
+    if first.x >= first.y {
+        if first.y >= first.z {
+            step_1 = vec3(1, 0, 0);
+            step_2 = vec3(1, 1, 0);
+        } else if first.x >= first.z {
+            step_1 = vec3(1, 0, 0);
+            step_2 = vec3(1, 0, 1);
+        } else {
+            step_1 = vec3(0, 0, 1);
+            step_2 = vec3(1, 0, 1);
+        }
+    } else if first.y < first.z {
+        step_1 = vec3(0, 0, 1);
+        step_2 = vec3(0, 1, 1);
+    } else if first.x < first.z {
+        step_1 = vec3(0, 1, 0);
+        step_2 = vec3(0, 1, 1);
+    } else {
+        step_1 = vec3(0, 1, 0);
+        step_2 = vec3(1, 1, 0);
+    }
+    let second = first - vec3<f32>(step_1) + unskew;
+    let third = first - vec3<f32>(step_2) + 2.0 * unskew;
+    let fourth = first - 1.0 + 3.0 * unskew;
+
+    let i = vec3<i32>(cell);
+    return (simplex_corner_3d(i, first, vec3(0, 0, 0), seed)
+        + simplex_corner_3d(i, second, step_1, seed)
+        + simplex_corner_3d(i, third, step_2, seed)
+        + simplex_corner_3d(i, fourth, vec3(1, 1, 1), seed)) * 32.0;
+}
+
+// Worley noise, returning the distances to the closest and second closest feature points.
+//
+// `jitter` is how far feature points stray from the center of their cell, from 0.0 for a regular
+// grid to 1.0 anywhere in the cell.
+fn worley_noise_2d(point: vec2<f32>, seed: u32, jitter: f32) -> vec2<f32> {
+    let cell = floor(point);
+    let offset = point - cell;
+    let i = vec2<i32>(cell);
+    var closest = vec2(1e30, 1e30);
+    for (var dy = -1; dy <= 1; dy += 1) {
+        for (var dx = -1; dx <= 1; dx += 1) {
+            let hash = noise_hash_2d(i + vec2(dx, dy), seed);
+            let random = vec2(noise_hash_to_unit(hash), noise_hash_to_unit(noise_hash(hash)));
+            let feature = vec2(f32(dx), f32(dy)) + 0.5 + (random - 0.5) * jitter;
+            let distance = dot(feature - offset, feature - offset);
+            if distance < closest.x {
+                closest = vec2(distance, closest.x);
+            } else if distance < closest.y {
+                closest.y = distance;
+            }
+        }
+    }
+    return sqrt(closest);
+}
+
+// Worley noise, returning the distances to the closest and second closest feature points.
+//
+// `jitter` is how far feature points stray from the center of their cell, from 0.0 for a regular
+// grid to 1.0 anywhere in the cell.
+fn worley_noise_3d(point: vec3<f32>, seed: u32, jitter: f32) -> vec2<f32> {
+    let cell = floor(point);
+    let offset = point - cell;
+    let i = vec3<i32>(cell);
+    var closest = vec2(1e30, 1e30);
+    for (var dz = -1; dz <= 1; dz += 1) {
+        for (var dy = -1; dy <= 1; dy += 1) {
+            for (var dx = -1; dx <= 1; dx += 1) {
+                let hash_x = noise_hash_3d(i + vec3(dx, dy, dz), seed);
+                let hash_y = noise_hash(hash_x);
+                let hash_z = noise_hash(hash_y);
+                let random = vec3(
+                    noise_hash_to_unit(hash_x),
+                    noise_hash_to_unit(hash_y),
+                    noise_hash_to_unit(hash_z),
+                );
+                let feature = vec3(f32(dx), f32(dy), f32(dz)) + 0.5 + (random - 0.5) * jitter;
+                let distance = dot(feature - offset, feature - offset);
+                if distance < closest.x {
+                    closest = vec2(distance, closest.x);
+                } else if distance < closest.y {
+                    closest.y = distance;
+                }
+            }
+        }
+    }
+    return sqrt(closest);
+}
+
+// Fractal Brownian motion of Perlin noise, matching `PerlinNoise::new(seed).fbm(octaves)` with the
+// given lacunarity and gain.
+fn fbm_perlin_noise_3d(point: vec3<f32>, seed: u32, octaves: u32, lacunarity: f32, gain: f32) -> f32 {
+    var sum = 0.0;
+    var total_amplitude = 0.0;
+    var frequency = 1.0;
+    var amplitude = 1.0;
+    for (var octave = 0u; octave < octaves; octave += 1u) {
+        sum += perlin_noise_3d(point * frequency, seed) * amplitude;
+        total_amplitude += amplitude;
+        frequency *= lacunarity;
+        amplitude *= gain;
+    }
+    return select(0.0, sum / total_amplitude, total_amplitude > 0.0);
+}
+
+// Fractal Brownian motion of simplex noise, matching `SimplexNoise::new(seed).fbm(octaves)` with
+// the given lacunarity and gain.
+fn fbm_simplex_noise_3d(point: vec3<f32>, seed: u32, octaves: u32, lacunarity: f32, gain: f32) -> f32 {
+    var sum = 0.0;
+    var total_amplitude = 0.0;
+    var frequency = 1.0;
+    var amplitude = 1.0;
+    for (var octave = 0u; octave < octaves; octave += 1u) {
+        sum += simplex_noise_3d(point * frequency, seed) * amplitude;
+        total_amplitude += amplitude;
+        frequency *= lacunarity;
+        amplitude *= gain;
+    }
+    return select(0.0, sum / total_amplitude, total_amplitude > 0.0);
+}
@@ -81,10 +81,13 @@ impl ColorAttachment {
     }
 }
 
-/// A wrapper for a [`TextureView`] that is used as a depth-only [`RenderPassDepthStencilAttachment`].
+/// A wrapper for a [`TextureView`] that is used as a [`RenderPassDepthStencilAttachment`].
+///
+/// The stencil aspect is only used if enabled with [`DepthAttachment::with_stencil`].
 pub struct DepthAttachment {
     pub view: TextureView,
     clear_value: Option<f32>,
+    stencil_clear_value: Option<u32>,
     is_first_call: Arc<AtomicBool>,
 }
 
@@ -93,10 +96,18 @@ impl DepthAttachment {
         Self {
             view,
             clear_value,
+            stencil_clear_value: None,
             is_first_call: Arc::new(AtomicBool::new(clear_value.is_some())),
         }
     }
 
+    /// Uses the stencil aspect of the texture too, cleared with `clear_value` whenever the depth
+    /// aspect is cleared. The texture must have a depth-stencil format.
+    pub fn with_stencil(mut self, clear_value: u32) -> Self {
+        self.stencil_clear_value = Some(clear_value);
+        self
+    }
+
     /// Get this texture view as an attachment. The attachment will be cleared with a value of
     /// `clear_value` if this is the first time calling this function with `store` == [`StoreOp::Store`],
     /// and a clear value was provided, otherwise it will be loaded.
@@ -116,7 +127,14 @@ impl DepthAttachment {
                 },
                 store,
             }),
-            stencil_ops: None,
+            stencil_ops: self.stencil_clear_value.map(|clear_value| Operations {
+                load: if first_call {
+                    LoadOp::Clear(clear_value)
+                } else {
+                    LoadOp::Load
+                },
+                store,
+            }),
         }
     }
 }
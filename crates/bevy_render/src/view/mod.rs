@@ -37,7 +37,8 @@ use std::{
 };
 use wgpu::{
     BufferUsages, Extent3d, RenderPassColorAttachment, RenderPassDepthStencilAttachment, StoreOp,
-    TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    TextureAspect, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    TextureViewDescriptor,
 };
 
 pub const VIEW_TYPE_HANDLE: Handle<Shader> = Handle::weak_from_u128(15421373904451797197);
@@ -683,6 +684,7 @@ impl ViewTarget {
 pub struct ViewDepthTexture {
     pub texture: Texture,
     attachment: DepthAttachment,
+    depth_view: Option<TextureView>,
 }
 
 impl ViewDepthTexture {
@@ -690,15 +692,30 @@ impl ViewDepthTexture {
         Self {
             texture: texture.texture,
             attachment: DepthAttachment::new(texture.default_view, clear_value),
+            depth_view: None,
         }
     }
 
+    /// Uses the stencil aspect of the texture too, which must have a depth-stencil format. The
+    /// stencil is cleared with `clear_value` whenever the depth is cleared.
+    pub fn with_stencil(mut self, clear_value: u32) -> Self {
+        self.attachment = self.attachment.with_stencil(clear_value);
+        // Depth-stencil textures can only be sampled through a view of one of their aspects.
+        self.depth_view = Some(self.texture.create_view(&TextureViewDescriptor {
+            label: Some("view_depth_texture_depth_view"),
+            aspect: TextureAspect::DepthOnly,
+            ..Default::default()
+        }));
+        self
+    }
+
     pub fn get_attachment(&self, store: StoreOp) -> RenderPassDepthStencilAttachment {
         self.attachment.get_attachment(store)
     }
 
+    /// Returns a view of the depth aspect of the texture, to sample it.
     pub fn view(&self) -> &TextureView {
-        &self.attachment.view
+        self.depth_view.as_ref().unwrap_or(&self.attachment.view)
     }
 }
 
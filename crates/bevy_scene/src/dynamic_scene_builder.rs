@@ -6,10 +6,33 @@ use bevy_ecs::{
     reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
     world::World,
 };
-use bevy_reflect::Reflect;
-use bevy_utils::default;
+use bevy_reflect::{Reflect, TypeRegistry};
+use bevy_utils::{default, HashMap};
+use std::any::TypeId;
 use std::collections::BTreeMap;
 
+/// Extracts a resource from a [`World`] into the value stored in a [`DynamicScene`].
+pub type ResourceExtractor<'w> = Box<dyn Fn(&World) -> Option<Box<dyn Reflect>> + 'w>;
+
+/// How a single resource is handled by [`DynamicSceneBuilder::extract_resources`].
+///
+/// A policy set for a resource takes precedence over the builder's resource [`SceneFilter`].
+pub enum ResourcePolicy<'w> {
+    /// Never extract the resource.
+    Skip,
+    /// Extract the resource through its [`ReflectResource`] type data, even if the resource
+    /// filter denies it.
+    Serialize,
+    /// Extract the value returned by the given closure instead of the resource itself.
+    ///
+    /// This allows storing resources that don't implement [`Reflect`], or storing a smaller
+    /// representation of them. Returning `None` skips the resource.
+    ///
+    /// The returned value is stored in the scene as is: to be written back into a world, its type
+    /// must be registered with [`ReflectResource`] type data.
+    Custom(ResourceExtractor<'w>),
+}
+
 /// A [`DynamicScene`] builder, used to build a scene from a [`World`] by extracting some entities and resources.
 ///
 /// # Component Extraction
@@ -28,6 +51,11 @@ use std::collections::BTreeMap;
 /// This can be changed by [specifying a filter](DynamicSceneBuilder::with_resource_filter) or by explicitly
 /// [allowing](DynamicSceneBuilder::allow_resource)/[denying](DynamicSceneBuilder::deny_resource) certain resources.
 ///
+/// Individual resources can also be given a [`ResourcePolicy`], which takes precedence over the filter
+/// and allows extracting resources that don't implement [`Reflect`] through a custom closure.
+/// See [`with_resource_policy`](DynamicSceneBuilder::with_resource_policy) and
+/// [`with_resource_extractor`](DynamicSceneBuilder::with_resource_extractor).
+///
 /// Extraction happens immediately and uses the filter as it exists during the time of extraction.
 ///
 /// # Entity Order
@@ -57,6 +85,7 @@ pub struct DynamicSceneBuilder<'w> {
     extracted_scene: BTreeMap<Entity, DynamicEntity>,
     component_filter: SceneFilter,
     resource_filter: SceneFilter,
+    resource_policies: HashMap<TypeId, ResourcePolicy<'w>>,
     original_world: &'w World,
 }
 
@@ -68,6 +97,7 @@ impl<'w> DynamicSceneBuilder<'w> {
             extracted_scene: default(),
             component_filter: SceneFilter::default(),
             resource_filter: SceneFilter::default(),
+            resource_policies: default(),
             original_world: world,
         }
     }
@@ -156,6 +186,60 @@ impl<'w> DynamicSceneBuilder<'w> {
         self
     }
 
+    /// Sets the [`ResourcePolicy`] used to extract the resource type `T`, overriding the resource
+    /// filter for it.
+    #[must_use]
+    pub fn with_resource_policy<T: Resource>(mut self, policy: ResourcePolicy<'w>) -> Self {
+        self.resource_policies.insert(TypeId::of::<T>(), policy);
+        self
+    }
+
+    /// Extracts the resource type `T` as the value returned by `extractor`, overriding the
+    /// resource filter for it.
+    ///
+    /// `T` doesn't need to implement [`Reflect`]. See [`ResourcePolicy::Custom`] for details.
+    ///
+    /// ```
+    /// # use bevy_scene::DynamicSceneBuilder;
+    /// # use bevy_ecs::reflect::AppTypeRegistry;
+    /// # use bevy_ecs::prelude::{ReflectResource, Resource, World};
+    /// # use bevy_reflect::Reflect;
+    /// #[derive(Resource)]
+    /// struct Connection {
+    ///     address: String,
+    /// }
+    ///
+    /// #[derive(Resource, Default, Reflect)]
+    /// #[reflect(Resource)]
+    /// struct SavedConnection {
+    ///     address: String,
+    /// }
+    ///
+    /// # let mut world = World::default();
+    /// # world.init_resource::<AppTypeRegistry>();
+    /// world.insert_resource(Connection {
+    ///     address: "localhost".to_string(),
+    /// });
+    ///
+    /// let scene = DynamicSceneBuilder::from_world(&world)
+    ///     .with_resource_extractor(|connection: &Connection| SavedConnection {
+    ///         address: connection.address.clone(),
+    ///     })
+    ///     .extract_resources()
+    ///     .build();
+    /// assert_eq!(scene.resources.len(), 1);
+    /// ```
+    #[must_use]
+    pub fn with_resource_extractor<T: Resource, R: Reflect>(
+        self,
+        extractor: impl Fn(&T) -> R + 'w,
+    ) -> Self {
+        self.with_resource_policy::<T>(ResourcePolicy::Custom(Box::new(move |world| {
+            let resource = world.get_resource::<T>()?;
+            Some(Box::new(extractor(resource)))
+        })))
+    }
+
     /// Updates the filter to allow all resource types.
     ///
     /// This is useful for resetting the filter so that types may be selectively [denied].
@@ -325,19 +409,21 @@ impl<'w> DynamicSceneBuilder<'w> {
                     .get_info(component_id)?
                     .type_id()?;
 
-                let is_denied = self.resource_filter.is_denied_by_id(type_id);
-
-                if is_denied {
-                    // Resource is either in the denylist or _not_ in the allowlist
-                    return None;
-                }
-
-                let resource = type_registry
-                    .get(type_id)?
-                    .data::<ReflectResource>()?
-                    .reflect(self.original_world)?;
-                self.extracted_resources
-                    .insert(component_id, resource.clone_value());
+                let resource = match self.resource_policies.get(&type_id) {
+                    Some(ResourcePolicy::Skip) => return None,
+                    Some(ResourcePolicy::Custom(extractor)) => extractor(self.original_world)?,
+                    Some(ResourcePolicy::Serialize) => {
+                        reflect_resource(self.original_world, &type_registry, type_id)?
+                    }
+                    None => {
+                        if self.resource_filter.is_denied_by_id(type_id) {
+                            // Resource is either in the denylist or _not_ in the allowlist
+                            return None;
+                        }
+                        reflect_resource(self.original_world, &type_registry, type_id)?
+                    }
+                };
+                self.extracted_resources.insert(component_id, resource);
                 Some(())
             };
             extract_and_push();
@@ -348,6 +434,19 @@ impl<'w> DynamicSceneBuilder<'w> {
     }
 }
 
+/// Clones the resource with the given [`TypeId`] through its [`ReflectResource`] type data.
+fn reflect_resource(
+    world: &World,
+    type_registry: &TypeRegistry,
+    type_id: TypeId,
+) -> Option<Box<dyn Reflect>> {
+    let resource = type_registry
+        .get(type_id)?
+        .data::<ReflectResource>()?
+        .reflect(world)?;
+    Some(resource.clone_value())
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
@@ -358,7 +457,7 @@ mod tests {
 
     use bevy_reflect::Reflect;
 
-    use super::DynamicSceneBuilder;
+    use super::{DynamicSceneBuilder, ResourcePolicy};
 
     #[derive(Component, Reflect, Default, Eq, PartialEq, Debug)]
     #[reflect(Component)]
@@ -552,6 +651,55 @@ mod tests {
         assert!(scene.resources[0].represents::<ResourceA>());
     }
 
+    #[test]
+    fn resource_policies_override_filter() {
+        let mut world = World::default();
+
+        let atr = AppTypeRegistry::default();
+        {
+            let mut register = atr.write();
+            register.register::<ResourceA>();
+            register.register::<ResourceB>();
+        }
+        world.insert_resource(atr);
+
+        world.insert_resource(ResourceA);
+        world.insert_resource(ResourceB);
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .deny_all_resources()
+            .with_resource_policy::<ResourceA>(ResourcePolicy::Serialize)
+            .extract_resources()
+            .build();
+        assert_eq!(scene.resources.len(), 1);
+        assert!(scene.resources[0].represents::<ResourceA>());
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .with_resource_policy::<ResourceA>(ResourcePolicy::Skip)
+            .extract_resources()
+            .build();
+        assert_eq!(scene.resources.len(), 1);
+        assert!(scene.resources[0].represents::<ResourceB>());
+    }
+
+    #[test]
+    fn extract_non_reflect_resource_with_extractor() {
+        #[derive(Resource)]
+        struct Opaque(u32);
+
+        let mut world = World::default();
+        world.init_resource::<AppTypeRegistry>();
+        world.insert_resource(Opaque(7));
+
+        let scene = DynamicSceneBuilder::from_world(&world)
+            .with_resource_extractor(|opaque: &Opaque| opaque.0)
+            .extract_resources()
+            .build();
+
+        assert_eq!(scene.resources.len(), 1);
+        assert_eq!(scene.resources[0].downcast_ref::<u32>(), Some(&7));
+    }
+
     #[test]
     fn should_extract_allowed_components() {
         let mut world = World::default();
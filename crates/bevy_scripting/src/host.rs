@@ -0,0 +1,320 @@
+//! The host functions exposed to scripts in the `bevy` import module.
+//!
+//! Entities are passed as `i64` values obtained from [`Entity::to_bits`], and strings as a pointer
+//! and a length into the script's exported `memory`.
+//!
+//! | Function | Signature | Description |
+//! |-|-|-|
+//! | `log` | `(ptr: i32, len: i32)` | Logs the string at `info` level. |
+//! | `spawn` | `() -> i64` | Spawns an empty entity and returns it. |
+//! | `despawn` | `(entity: i64) -> i32` | Despawns the entity, returning `1` if it existed. |
+//! | `query` | `(names_ptr: i32, names_len: i32, out_ptr: i32, out_len: i32) -> i32` | Finds the entities with all the comma-separated components, writes up to `out_len` of them at `out_ptr` as little-endian `i64`s, and returns how many matched, or `-1` if a component is unknown. |
+//! | `get_f64` | `(entity: i64, path_ptr: i32, path_len: i32) -> f64` | Reads a numeric or boolean field, or returns `NaN` if it doesn't exist. |
+//! | `set_f64` | `(entity: i64, path_ptr: i32, path_len: i32, value: f64) -> i32` | Writes a numeric or boolean field, returning `1` on success. |
+//! | `send_event` | `(entity: i64, name_ptr: i32, name_len: i32, value: f64)` | Sends a [`ScriptEvent`]. |
+//! | `blackboard_get_f64` | `(entity: i64, key_ptr: i32, key_len: i32) -> f64` | Reads a numeric or boolean value of the entity's `Blackboard`, or returns `NaN` if it doesn't exist. Requires the `bevy_ai` feature. |
+//! | `blackboard_set_f64` | `(entity: i64, key_ptr: i32, key_len: i32, value: f64) -> i32` | Writes a numeric or boolean value of the entity's `Blackboard`, inserting an `f64` if the key isn't set, and returns `1` on success. Requires the `bevy_ai` feature. |
+//!
+//! Field paths start with the name of the component, followed by a [reflection path]
+//! into it, for example `Transform.translation.x` or `Health.0`.
+//! Components can be named either by their full or their short type path.
+//!
+//! [reflection path]: bevy_reflect::GetPath
+
+use bevy_ecs::{
+    component::ComponentId,
+    entity::Entity,
+    query::QueryBuilder,
+    reflect::{AppTypeRegistry, ReflectComponent},
+    world::World,
+};
+use bevy_reflect::{GetPath, Reflect, TypeRegistration, TypeRegistry};
+use bevy_utils::tracing::info;
+use wasmi::{Caller, Extern, Linker};
+
+use crate::ScriptEvent;
+
+/// The data available to host functions while a script runs.
+#[derive(Default)]
+pub(crate) struct ScriptHost {
+    /// The world the script operates on.
+    ///
+    /// This is only set for the duration of a call, see [`LentWorld`](crate::runtime::LentWorld).
+    pub(crate) world: Option<World>,
+}
+
+impl ScriptHost {
+    /// Returns the world lent to the running call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no world is lent, which can't happen from within a host function.
+    fn world(&self) -> &World {
+        self.world.as_ref().expect("no world is lent to the script")
+    }
+
+    /// Returns the world lent to the running call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no world is lent, which can't happen from within a host function.
+    fn world_mut(&mut self) -> &mut World {
+        self.world.as_mut().expect("no world is lent to the script")
+    }
+}
+
+/// Reads the string at `ptr..ptr + len` from the calling script's memory.
+fn read_str(caller: &Caller<'_, ScriptHost>, ptr: i32, len: i32) -> Option<String> {
+    let memory = caller.get_export("memory").and_then(Extern::into_memory)?;
+    let start = usize::try_from(ptr).ok()?;
+    let end = start.checked_add(usize::try_from(len).ok()?)?;
+    let bytes = memory.data(caller).get(start..end)?;
+    std::str::from_utf8(bytes).ok().map(ToString::to_string)
+}
+
+fn registration_by_name<'a>(
+    registry: &'a TypeRegistry,
+    name: &str,
+) -> Option<&'a TypeRegistration> {
+    registry
+        .get_with_type_path(name)
+        .or_else(|| registry.get_with_short_type_path(name))
+}
+
+fn component_id_by_name(world: &World, name: &str) -> Option<ComponentId> {
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    let registration = registration_by_name(&registry, name.trim())?;
+    world.components().get_id(registration.type_id())
+}
+
+/// Splits a field path into the [`ReflectComponent`] of its component and the remaining path.
+fn resolve_path<'p>(world: &World, path: &'p str) -> Option<(ReflectComponent, &'p str)> {
+    let (name, field) = match path.find(['.', '[']) {
+        Some(index) => path.split_at(index),
+        None => (path, ""),
+    };
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    let reflect_component = registration_by_name(&registry, name)?
+        .data::<ReflectComponent>()?
+        .clone();
+    Some((reflect_component, field))
+}
+
+fn as_f64(value: &dyn Reflect) -> Option<f64> {
+    let any = value.as_any();
+    Some(if let Some(v) = any.downcast_ref::<f64>() {
+        *v
+    } else if let Some(v) = any.downcast_ref::<f32>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<i32>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<u32>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<i64>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<u64>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<usize>() {
+        *v as f64
+    } else if let Some(v) = any.downcast_ref::<bool>() {
+        if *v {
+            1.0
+        } else {
+            0.0
+        }
+    } else {
+        return None;
+    })
+}
+
+fn set_from_f64(value: &mut dyn Reflect, new_value: f64) -> bool {
+    let any = value.as_any_mut();
+    if let Some(v) = any.downcast_mut::<f64>() {
+        *v = new_value;
+    } else if let Some(v) = any.downcast_mut::<f32>() {
+        *v = new_value as f32;
+    } else if let Some(v) = any.downcast_mut::<i32>() {
+        *v = new_value as i32;
+    } else if let Some(v) = any.downcast_mut::<u32>() {
+        *v = new_value as u32;
+    } else if let Some(v) = any.downcast_mut::<i64>() {
+        *v = new_value as i64;
+    } else if let Some(v) = any.downcast_mut::<u64>() {
+        *v = new_value as u64;
+    } else if let Some(v) = any.downcast_mut::<usize>() {
+        *v = new_value as usize;
+    } else if let Some(v) = any.downcast_mut::<bool>() {
+        *v = new_value != 0.0;
+    } else {
+        return false;
+    }
+    true
+}
+
+fn get_f64(world: &World, entity: Entity, path: &str) -> Option<f64> {
+    let (reflect_component, field) = resolve_path(world, path)?;
+    let component = reflect_component.reflect(world.get_entity(entity)?)?;
+    as_f64(component.reflect_path(field).ok()?)
+}
+
+fn set_f64(world: &mut World, entity: Entity, path: &str, value: f64) -> Option<()> {
+    let (reflect_component, field) = resolve_path(world, path)?;
+    let mut component = reflect_component.reflect_mut(world.get_entity_mut(entity)?)?;
+    let field = component.reflect_path_mut(field).ok()?;
+    set_from_f64(field, value).then_some(())
+}
+
+#[cfg(feature = "bevy_ai")]
+fn blackboard_get_f64(world: &World, entity: Entity, key: &str) -> Option<f64> {
+    as_f64(world.get::<bevy_ai::Blackboard>(entity)?.get_reflect(key)?)
+}
+
+#[cfg(feature = "bevy_ai")]
+fn blackboard_set_f64(world: &mut World, entity: Entity, key: &str, value: f64) -> Option<()> {
+    let mut blackboard = world.get_mut::<bevy_ai::Blackboard>(entity)?;
+    if let Some(field) = blackboard.get_reflect_mut(key) {
+        return set_from_f64(field, value).then_some(());
+    }
+    blackboard.insert(key, value);
+    Some(())
+}
+
+fn query(world: &mut World, names: &str) -> Option<Vec<Entity>> {
+    let ids = names
+        .split(',')
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| component_id_by_name(world, name))
+        .collect::<Option<Vec<_>>>()?;
+    let mut builder = QueryBuilder::<Entity>::new(world);
+    for id in ids {
+        builder.with_id(id);
+    }
+    let mut query = builder.build();
+    Some(query.iter(world).collect())
+}
+
+/// Defines all host functions in the `linker`.
+pub(crate) fn define(linker: &mut Linker<ScriptHost>) -> Result<(), wasmi::errors::LinkerError> {
+    linker.func_wrap(
+        "bevy",
+        "log",
+        |caller: Caller<'_, ScriptHost>, ptr: i32, len: i32| {
+            if let Some(message) = read_str(&caller, ptr, len) {
+                info!("{message}");
+            }
+        },
+    )?;
+    linker.func_wrap("bevy", "spawn", |mut caller: Caller<'_, ScriptHost>| {
+        caller.data_mut().world_mut().spawn_empty().id().to_bits() as i64
+    })?;
+    linker.func_wrap(
+        "bevy",
+        "despawn",
+        |mut caller: Caller<'_, ScriptHost>, entity: i64| {
+            Entity::try_from_bits(entity as u64)
+                .is_ok_and(|entity| caller.data_mut().world_mut().despawn(entity))
+                as i32
+        },
+    )?;
+    linker.func_wrap(
+        "bevy",
+        "query",
+        |mut caller: Caller<'_, ScriptHost>,
+         names_ptr: i32,
+         names_len: i32,
+         out_ptr: i32,
+         out_len: i32| {
+            let Some(names) = read_str(&caller, names_ptr, names_len) else {
+                return -1;
+            };
+            let Some(entities) = query(caller.data_mut().world_mut(), &names) else {
+                return -1;
+            };
+            let Some(memory) = caller.get_export("memory").and_then(Extern::into_memory) else {
+                return -1;
+            };
+            let bytes: Vec<u8> = entities
+                .iter()
+                .take(usize::try_from(out_len).unwrap_or(0))
+                .flat_map(|entity| entity.to_bits().to_le_bytes())
+                .collect();
+            if memory
+                .write(&mut caller, usize::try_from(out_ptr).unwrap_or(0), &bytes)
+                .is_err()
+            {
+                return -1;
+            }
+            entities.len() as i32
+        },
+    )?;
+    linker.func_wrap(
+        "bevy",
+        "get_f64",
+        |caller: Caller<'_, ScriptHost>, entity: i64, ptr: i32, len: i32| {
+            let value = read_str(&caller, ptr, len).and_then(|path| {
+                let entity = Entity::try_from_bits(entity as u64).ok()?;
+                get_f64(caller.data().world(), entity, &path)
+            });
+            value.unwrap_or(f64::NAN)
+        },
+    )?;
+    linker.func_wrap(
+        "bevy",
+        "set_f64",
+        |mut caller: Caller<'_, ScriptHost>, entity: i64, ptr: i32, len: i32, value: f64| {
+            let Some(path) = read_str(&caller, ptr, len) else {
+                return 0;
+            };
+            Entity::try_from_bits(entity as u64)
+                .ok()
+                .and_then(|entity| set_f64(caller.data_mut().world_mut(), entity, &path, value))
+                .is_some() as i32
+        },
+    )?;
+    linker.func_wrap(
+        "bevy",
+        "send_event",
+        |mut caller: Caller<'_, ScriptHost>, entity: i64, ptr: i32, len: i32, value: f64| {
+            let (Some(name), Ok(entity)) = (
+                read_str(&caller, ptr, len),
+                Entity::try_from_bits(entity as u64),
+            ) else {
+                return;
+            };
+            caller.data_mut().world_mut().send_event(ScriptEvent {
+                entity,
+                name,
+                value,
+            });
+        },
+    )?;
+    #[cfg(feature = "bevy_ai")]
+    linker.func_wrap(
+        "bevy",
+        "blackboard_get_f64",
+        |caller: Caller<'_, ScriptHost>, entity: i64, ptr: i32, len: i32| {
+            let value = read_str(&caller, ptr, len).and_then(|key| {
+                let entity = Entity::try_from_bits(entity as u64).ok()?;
+                blackboard_get_f64(caller.data().world(), entity, &key)
+            });
+            value.unwrap_or(f64::NAN)
+        },
+    )?;
+    #[cfg(feature = "bevy_ai")]
+    linker.func_wrap(
+        "bevy",
+        "blackboard_set_f64",
+        |mut caller: Caller<'_, ScriptHost>, entity: i64, ptr: i32, len: i32, value: f64| {
+            let Some(key) = read_str(&caller, ptr, len) else {
+                return 0;
+            };
+            Entity::try_from_bits(entity as u64)
+                .ok()
+                .and_then(|entity| {
+                    blackboard_set_f64(caller.data_mut().world_mut(), entity, &key, value)
+                })
+                .is_some() as i32
+        },
+    )?;
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![forbid(unsafe_code)]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+#![doc(
+    html_logo_url = "https://bevyengine.org/assets/icon.png",
+    html_favicon_url = "https://bevyengine.org/assets/icon.png"
+)]
+
+//! WebAssembly scripting for Bevy.
+//!
+//! `.wasm` files are loaded as [`WasmScript`] assets. Adding a [`Script`] component to an entity
+//! instantiates the script for that entity, and the script's exported callbacks are then called
+//! by systems created with [`run_script_callback`]. The [`ScriptingPlugin`] runs the `update`
+//! callback every frame in [`Update`].
+//!
+//! Every callback is an exported function taking the entity the script is attached to (as
+//! [`Entity::to_bits`](bevy_ecs::entity::Entity::to_bits)) and returning nothing. An `init`
+//! callback, if exported, is called once when the script is instantiated.
+//!
+//! Scripts interact with the [`World`](bevy_ecs::world::World) through the host functions of
+//! the `bevy` import module, see [`host`] for the full list. Components are addressed by the type
+//! path they are registered under in the [`AppTypeRegistry`](bevy_ecs::reflect::AppTypeRegistry),
+//! and their fields are read and written through reflection.
+
+pub mod host;
+mod runtime;
+mod script;
+
+pub use runtime::*;
+pub use script::*;
+
+#[allow(missing_docs)]
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{run_script_callback, Script, ScriptEvent, ScriptingPlugin, WasmScript};
+}
+
+use bevy_app::prelude::*;
+use bevy_asset::AssetApp;
+use wasmi::Engine;
+
+/// Adds support for WebAssembly scripts to an [`App`].
+#[derive(Default)]
+pub struct ScriptingPlugin;
+
+impl Plugin for ScriptingPlugin {
+    fn build(&self, app: &mut App) {
+        let engine = Engine::default();
+        app.insert_resource(ScriptEngine(engine.clone()))
+            .init_resource::<ScriptInstances>()
+            .add_event::<ScriptEvent>()
+            .init_asset::<WasmScript>()
+            .register_asset_loader(WasmScriptLoader { engine })
+            .add_systems(Update, run_script_callback("update"));
+    }
+}
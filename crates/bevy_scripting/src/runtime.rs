@@ -0,0 +1,350 @@
+use bevy_asset::{AssetId, Assets};
+use bevy_ecs::{
+    entity::{Entity, EntityHashMap},
+    prelude::Mut,
+    system::Resource,
+    world::World,
+};
+use bevy_utils::tracing::error;
+use wasmi::{Engine, Instance, Linker, Module, Store};
+
+use crate::{
+    host::{self, ScriptHost},
+    Script, WasmScript,
+};
+
+/// The [`Engine`] used to compile and run [`WasmScript`]s.
+#[derive(Resource, Clone)]
+pub struct ScriptEngine(pub Engine);
+
+/// An instance of a [`WasmScript`] attached to an entity.
+struct ScriptInstance {
+    script: AssetId<WasmScript>,
+    store: Store<ScriptHost>,
+    instance: Instance,
+}
+
+impl ScriptInstance {
+    fn new(
+        engine: &Engine,
+        script: AssetId<WasmScript>,
+        module: &Module,
+    ) -> Result<Self, wasmi::Error> {
+        let mut linker = Linker::new(engine);
+        host::define(&mut linker)?;
+        let mut store = Store::new(engine, ScriptHost::default());
+        let instance = linker.instantiate(&mut store, module)?.start(&mut store)?;
+        Ok(Self {
+            script,
+            store,
+            instance,
+        })
+    }
+
+    /// Calls the exported `callback` with the given `entity`, if the script exports it.
+    ///
+    /// `placeholder` is left in place of `world` during the call.
+    fn call(
+        &mut self,
+        world: &mut World,
+        placeholder: &mut Option<World>,
+        entity: Entity,
+        callback: &str,
+    ) {
+        let Ok(func) = self
+            .instance
+            .get_typed_func::<i64, ()>(&self.store, callback)
+        else {
+            return;
+        };
+        let result = {
+            let lent = LentWorld::new(&mut self.store, world, placeholder);
+            func.call(&mut *lent.store, entity.to_bits() as i64)
+        };
+        if let Err(err) = result {
+            error!("Script callback `{callback}` failed for entity {entity:?}: {err}");
+        }
+    }
+}
+
+/// Lends a [`World`] to the host functions of a [`Store`] until dropped.
+///
+/// The world is moved into the store and a placeholder is left in its place. It is moved back when
+/// the guard is dropped, even if a host function panics, so the caller never keeps the
+/// placeholder.
+pub(crate) struct LentWorld<'a> {
+    store: &'a mut Store<ScriptHost>,
+    world: &'a mut World,
+    placeholder: &'a mut Option<World>,
+}
+
+impl<'a> LentWorld<'a> {
+    /// Lends `world` to `store`, leaving `placeholder` in its place, or a new empty world if there
+    /// is none yet.
+    fn new(
+        store: &'a mut Store<ScriptHost>,
+        world: &'a mut World,
+        placeholder: &'a mut Option<World>,
+    ) -> Self {
+        let lent = std::mem::replace(world, placeholder.take().unwrap_or_default());
+        store.data_mut().world = Some(lent);
+        Self {
+            store,
+            world,
+            placeholder,
+        }
+    }
+}
+
+impl Drop for LentWorld<'_> {
+    fn drop(&mut self) {
+        if let Some(lent) = self.store.data_mut().world.take() {
+            *self.placeholder = Some(std::mem::replace(self.world, lent));
+        }
+    }
+}
+
+/// A [`Resource`] holding the running instance of every [`Script`].
+#[derive(Resource, Default)]
+pub struct ScriptInstances {
+    instances: EntityHashMap<ScriptInstance>,
+    /// Scripts that failed to instantiate, so they aren't retried every frame.
+    failed: EntityHashMap<AssetId<WasmScript>>,
+    /// The empty world left in place of the app's world while it is lent to a script, reused
+    /// across calls.
+    placeholder: Option<World>,
+}
+
+impl ScriptInstances {
+    /// Returns the number of running script instances.
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    /// Returns `true` if no script instance is running.
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+}
+
+/// Creates instances for new [`Script`]s and drops the instances of removed ones.
+///
+/// Scripts whose asset hasn't loaded yet are instantiated once it is available.
+/// Newly created instances have their `init` callback called.
+pub fn sync_script_instances(world: &mut World) {
+    let Some(ScriptEngine(engine)) = world.get_resource::<ScriptEngine>().cloned() else {
+        return;
+    };
+    let scripts: EntityHashMap<AssetId<WasmScript>> = world
+        .query::<(Entity, &Script)>()
+        .iter(world)
+        .map(|(entity, script)| (entity, script.0.id()))
+        .collect();
+
+    world.resource_scope(|world, mut instances: Mut<ScriptInstances>| {
+        let ScriptInstances {
+            instances,
+            failed,
+            placeholder,
+        } = &mut *instances;
+        instances.retain(|entity, instance| scripts.get(entity) == Some(&instance.script));
+        failed.retain(|entity, script| scripts.get(entity) == Some(script));
+
+        for (&entity, &script) in &scripts {
+            if instances.contains_key(&entity) || failed.contains_key(&entity) {
+                continue;
+            }
+            let Some(module) = world
+                .get_resource::<Assets<WasmScript>>()
+                .and_then(|assets| assets.get(script))
+                .map(WasmScript::module)
+            else {
+                continue;
+            };
+            match ScriptInstance::new(&engine, script, module) {
+                Ok(mut instance) => {
+                    instance.call(world, placeholder, entity, "init");
+                    instances.insert(entity, instance);
+                }
+                Err(err) => {
+                    error!("Failed to instantiate script for entity {entity:?}: {err}");
+                    failed.insert(entity, script);
+                }
+            }
+        }
+    });
+}
+
+/// Returns an exclusive system that calls the exported `callback` of every [`Script`], so that
+/// script callbacks can be scheduled like any other system.
+///
+/// ```
+/// # use bevy_app::{App, FixedUpdate};
+/// # use bevy_scripting::run_script_callback;
+/// # let mut app = App::new();
+/// app.add_systems(FixedUpdate, run_script_callback("fixed_update"));
+/// ```
+pub fn run_script_callback(callback: &'static str) -> impl FnMut(&mut World) {
+    move |world: &mut World| {
+        if !world.contains_resource::<ScriptInstances>() {
+            return;
+        }
+        sync_script_instances(world);
+        world.resource_scope(|world, mut instances: Mut<ScriptInstances>| {
+            let ScriptInstances {
+                instances,
+                placeholder,
+                ..
+            } = &mut *instances;
+            for (&entity, instance) in instances.iter_mut() {
+                instance.call(world, placeholder, entity, callback);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{run_script_callback, ScriptEngine, ScriptInstances};
+    use crate::{Script, ScriptEvent, WasmScript};
+    use bevy_asset::Assets;
+    use bevy_ecs::{
+        event::Events,
+        prelude::*,
+        reflect::{AppTypeRegistry, ReflectComponent},
+        system::RunSystemOnce,
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Default)]
+    #[reflect(Component)]
+    struct Counter(u32);
+
+    const SCRIPT: &str = r#"
+        (module
+            (import "bevy" "get_f64" (func $get (param i64 i32 i32) (result f64)))
+            (import "bevy" "set_f64" (func $set (param i64 i32 i32 f64) (result i32)))
+            (import "bevy" "send_event" (func $send (param i64 i32 i32 f64)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "Counter.0")
+            (data (i32.const 16) "counted")
+            (func (export "init") (param $entity i64)
+                (drop (call $set (local.get $entity) (i32.const 0) (i32.const 9) (f64.const 10))))
+            (func (export "update") (param $entity i64)
+                (local $value f64)
+                (local.set $value
+                    (f64.add (call $get (local.get $entity) (i32.const 0) (i32.const 9)) (f64.const 1)))
+                (drop (call $set (local.get $entity) (i32.const 0) (i32.const 9) (local.get $value)))
+                (call $send (local.get $entity) (i32.const 16) (i32.const 7) (local.get $value))))
+    "#;
+
+    #[test]
+    fn script_updates_component() {
+        let mut world = World::new();
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<Counter>();
+        world.insert_resource(type_registry);
+        world.init_resource::<Events<ScriptEvent>>();
+        world.init_resource::<ScriptInstances>();
+
+        let engine = wasmi::Engine::default();
+        let script = WasmScript::new(&engine, &wat::parse_str(SCRIPT).unwrap()).unwrap();
+        world.insert_resource(ScriptEngine(engine));
+        let mut assets = Assets::<WasmScript>::default();
+        let handle = assets.add(script);
+        world.insert_resource(assets);
+
+        let entity = world.spawn((Counter(0), Script(handle))).id();
+        world.run_system_once(run_script_callback("update"));
+        world.run_system_once(run_script_callback("update"));
+
+        assert_eq!(world.get::<Counter>(entity).unwrap().0, 12);
+        assert_eq!(world.resource::<ScriptInstances>().len(), 1);
+        let events: Vec<_> = world
+            .resource_mut::<Events<ScriptEvent>>()
+            .drain()
+            .map(|event| event.value)
+            .collect();
+        assert_eq!(events, [11.0, 12.0]);
+
+        world.entity_mut(entity).remove::<Script>();
+        world.run_system_once(run_script_callback("update"));
+        assert!(world.resource::<ScriptInstances>().is_empty());
+    }
+
+    #[test]
+    fn panicking_host_function_returns_world() {
+        #[derive(Component)]
+        struct Fragile;
+
+        const SCRIPT: &str = r#"
+            (module
+                (import "bevy" "despawn" (func $despawn (param i64) (result i32)))
+                (func (export "update") (param $entity i64)
+                    (drop (call $despawn (local.get $entity)))))
+        "#;
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_resource::<Events<ScriptEvent>>();
+        world.init_resource::<ScriptInstances>();
+        world
+            .register_component_hooks::<Fragile>()
+            .on_remove(|_, _, _| panic!("despawned a fragile entity"));
+
+        let engine = wasmi::Engine::default();
+        let script = WasmScript::new(&engine, &wat::parse_str(SCRIPT).unwrap()).unwrap();
+        world.insert_resource(ScriptEngine(engine));
+        let mut assets = Assets::<WasmScript>::default();
+        let handle = assets.add(script);
+        world.insert_resource(assets);
+
+        let entity = world.spawn((Fragile, Script(handle))).id();
+        let mut update = run_script_callback("update");
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| update(&mut world)));
+        assert!(res.is_err());
+
+        // The app's world was taken back from the script, not left in the store.
+        assert!(world.get_entity(entity).is_some());
+        assert!(world.contains_resource::<ScriptEngine>());
+    }
+
+    #[cfg(feature = "bevy_ai")]
+    #[test]
+    fn script_updates_blackboard() {
+        use bevy_ai::Blackboard;
+
+        const SCRIPT: &str = r#"
+            (module
+                (import "bevy" "blackboard_get_f64" (func $get (param i64 i32 i32) (result f64)))
+                (import "bevy" "blackboard_set_f64" (func $set (param i64 i32 i32 f64) (result i32)))
+                (memory (export "memory") 1)
+                (data (i32.const 0) "hunger")
+                (data (i32.const 16) "fed")
+                (func (export "update") (param $entity i64)
+                    (drop (call $set (local.get $entity) (i32.const 0) (i32.const 6)
+                        (f64.sub (call $get (local.get $entity) (i32.const 0) (i32.const 6)) (f64.const 0.25))))
+                    (drop (call $set (local.get $entity) (i32.const 16) (i32.const 3) (f64.const 1)))))
+        "#;
+
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world.init_resource::<Events<ScriptEvent>>();
+        world.init_resource::<ScriptInstances>();
+
+        let engine = wasmi::Engine::default();
+        let script = WasmScript::new(&engine, &wat::parse_str(SCRIPT).unwrap()).unwrap();
+        world.insert_resource(ScriptEngine(engine));
+        let mut assets = Assets::<WasmScript>::default();
+        let handle = assets.add(script);
+        world.insert_resource(assets);
+
+        let blackboard = Blackboard::new().with("hunger", 1.0_f32).with("fed", false);
+        let entity = world.spawn((blackboard, Script(handle))).id();
+        world.run_system_once(run_script_callback("update"));
+
+        let blackboard = world.get::<Blackboard>(entity).unwrap();
+        assert_eq!(blackboard.get::<f32>("hunger"), Some(&0.75));
+        assert_eq!(blackboard.get::<bool>("fed"), Some(&true));
+    }
+}
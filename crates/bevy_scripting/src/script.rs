@@ -0,0 +1,80 @@
+use bevy_asset::{io::Reader, Asset, AssetLoader, AsyncReadExt, Handle, LoadContext};
+use bevy_ecs::{component::Component, entity::Entity, event::Event};
+use bevy_reflect::TypePath;
+use thiserror::Error;
+use wasmi::{Engine, Module};
+
+/// A compiled WebAssembly module that can be attached to entities with [`Script`].
+#[derive(Asset, TypePath)]
+pub struct WasmScript {
+    module: Module,
+}
+
+impl WasmScript {
+    /// Compiles the given WebAssembly binary.
+    pub fn new(engine: &Engine, bytes: &[u8]) -> Result<Self, wasmi::Error> {
+        Ok(Self {
+            module: Module::new(engine, bytes)?,
+        })
+    }
+
+    /// The compiled module.
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+}
+
+/// Runs the referenced [`WasmScript`] for this entity.
+///
+/// Each entity gets its own instance of the script, with its own memory.
+/// Changing the handle replaces the instance.
+#[derive(Component, Clone, Debug)]
+pub struct Script(pub Handle<WasmScript>);
+
+/// An event sent by a script through the `send_event` host function.
+#[derive(Event, Clone, Debug, PartialEq)]
+pub struct ScriptEvent {
+    /// The entity of the script that sent the event.
+    pub entity: Entity,
+    /// The name of the event, as chosen by the script.
+    pub name: String,
+    /// The value attached to the event.
+    pub value: f64,
+}
+
+/// Loads `.wasm` files as [`WasmScript`] assets.
+pub struct WasmScriptLoader {
+    pub(crate) engine: Engine,
+}
+
+/// Possible errors that can be produced by [`WasmScriptLoader`]
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum WasmScriptLoaderError {
+    /// An [IO](std::io) Error
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// The module failed to compile
+    #[error("invalid WebAssembly module: {0}")]
+    Wasm(#[from] wasmi::Error),
+}
+
+impl AssetLoader for WasmScriptLoader {
+    type Asset = WasmScript;
+    type Settings = ();
+    type Error = WasmScriptLoaderError;
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<WasmScript, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(WasmScript::new(&self.engine, &bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["wasm"]
+    }
+}
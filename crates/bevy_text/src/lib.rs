@@ -13,6 +13,7 @@ mod font_atlas;
 mod font_atlas_set;
 mod font_loader;
 mod glyph_brush;
+mod measure;
 mod pipeline;
 mod text;
 mod text2d;
@@ -23,6 +24,7 @@ pub use font_atlas::*;
 pub use font_atlas_set::*;
 pub use font_loader::*;
 pub use glyph_brush::*;
+pub use measure::*;
 pub use pipeline::*;
 pub use text::*;
 pub use text2d::*;
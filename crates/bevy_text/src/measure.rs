@@ -0,0 +1,248 @@
+use crate::{
+    compute_text_bounds, BreakLineOn, Font, JustifyText, Text, TextError, TextSection, TextStyle,
+};
+use ab_glyph::{PxScale, ScaleFont};
+use bevy_asset::Assets;
+use bevy_ecs::system::{Res, SystemParam};
+use bevy_math::{Rect, Vec2};
+use glyph_brush_layout::{
+    BuiltInLineBreaker, FontId, GlyphPositioner, SectionGeometry, SectionText,
+};
+use std::ops::Range;
+
+/// [`SystemParam`] that lays out text without spawning text entities.
+///
+/// This is useful to size widgets around a string, wrap text in a custom renderer, or place the
+/// caret of a text input, before or without displaying the text. No glyph is rasterized: only
+/// the loaded [`Font`] assets are needed.
+///
+/// Font sizes and bounds are in logical pixels, and so is the returned [`TextMeasurement`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec2;
+/// # use bevy_text::{TextMeasurer, TextStyle};
+/// fn measure_label(measurer: TextMeasurer) {
+///     let style = TextStyle {
+///         font_size: 24.0,
+///         ..Default::default()
+///     };
+///     let bounds = Vec2::new(200.0, f32::INFINITY);
+///     if let Ok(measurement) = measurer.measure_str("Hello, world!", &style, bounds) {
+///         let _size = measurement.size;
+///         let _caret = measurement.caret_rect(0, 5);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(measure_label);
+/// ```
+#[derive(SystemParam)]
+pub struct TextMeasurer<'w> {
+    fonts: Res<'w, Assets<Font>>,
+}
+
+impl<'w> TextMeasurer<'w> {
+    /// Lays out the sections of `text` within `bounds`.
+    ///
+    /// Returns [`TextError::NoSuchFont`] if the font of a section isn't loaded.
+    pub fn measure(&self, text: &Text, bounds: Vec2) -> Result<TextMeasurement, TextError> {
+        TextMeasurement::new(
+            &self.fonts,
+            &text.sections,
+            text.justify,
+            text.linebreak_behavior,
+            bounds,
+        )
+    }
+
+    /// Lays out `value` with `style` within `bounds`, left-justified and wrapped at word
+    /// boundaries.
+    ///
+    /// Returns [`TextError::NoSuchFont`] if the font of `style` isn't loaded.
+    pub fn measure_str(
+        &self,
+        value: &str,
+        style: &TextStyle,
+        bounds: Vec2,
+    ) -> Result<TextMeasurement, TextError> {
+        TextMeasurement::new(
+            &self.fonts,
+            &[TextSection::new(value, style.clone())],
+            JustifyText::Left,
+            BreakLineOn::WordBoundary,
+            bounds,
+        )
+    }
+}
+
+/// The layout of a text, computed by a [`TextMeasurer`].
+///
+/// Positions are relative to the top-left corner of the text, with the y axis pointing down.
+#[derive(Clone, Debug, Default)]
+pub struct TextMeasurement {
+    /// The size of the laid-out text.
+    pub size: Vec2,
+    /// The glyphs of the text, in the order they are laid out.
+    pub glyphs: Vec<MeasuredGlyph>,
+    /// The lines of the text, from top to bottom.
+    ///
+    /// Lines without any glyph, like the ones between two consecutive line breaks, are skipped.
+    pub lines: Vec<MeasuredLine>,
+}
+
+/// A glyph of a [`TextMeasurement`].
+#[derive(Clone, Debug)]
+pub struct MeasuredGlyph {
+    /// The index of the section of the glyph.
+    pub section_index: usize,
+    /// The bytes of the character of the glyph, in the value of its section.
+    pub byte_range: Range<usize>,
+    /// The index of the line of the glyph in [`TextMeasurement::lines`].
+    pub line_index: usize,
+    /// The box of the glyph, spanning its horizontal advance and the ascent and descent of its
+    /// font.
+    pub rect: Rect,
+}
+
+/// A line of a [`TextMeasurement`].
+#[derive(Clone, Debug)]
+pub struct MeasuredLine {
+    /// The indices of the glyphs of the line in [`TextMeasurement::glyphs`].
+    pub glyphs: Range<usize>,
+    /// The y position of the baseline of the line.
+    pub baseline: f32,
+    /// The y position of the top of the line.
+    pub top: f32,
+    /// The y position of the bottom of the line.
+    pub bottom: f32,
+}
+
+impl TextMeasurement {
+    /// Lays out `sections` within `bounds`, using the fonts in `fonts`.
+    ///
+    /// Prefer [`TextMeasurer`] in systems.
+    pub fn new(
+        fonts: &Assets<Font>,
+        sections: &[TextSection],
+        justify: JustifyText,
+        linebreak_behavior: BreakLineOn,
+        bounds: Vec2,
+    ) -> Result<Self, TextError> {
+        let section_fonts = sections
+            .iter()
+            .map(|section| {
+                fonts
+                    .get(&section.style.font)
+                    .map(|font| font.font.clone())
+                    .ok_or(TextError::NoSuchFont)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let section_texts: Vec<_> = sections
+            .iter()
+            .enumerate()
+            .map(|(i, section)| SectionText {
+                text: &section.value,
+                scale: PxScale::from(section.style.font_size),
+                font_id: FontId(i),
+            })
+            .collect();
+
+        let geom = SectionGeometry {
+            bounds: (bounds.x, bounds.y),
+            ..Default::default()
+        };
+        let section_glyphs = glyph_brush_layout::Layout::default()
+            .h_align(justify.into())
+            .line_breaker(BuiltInLineBreaker::from(linebreak_behavior))
+            .calculate_glyphs(&section_fonts, &geom, &section_texts);
+        if section_glyphs.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let scaled_font = |index: usize| {
+            ab_glyph::Font::into_scaled(&section_fonts[index], sections[index].style.font_size)
+        };
+        let text_bounds = compute_text_bounds(&section_glyphs, scaled_font);
+
+        let mut glyphs = Vec::with_capacity(section_glyphs.len());
+        let mut lines: Vec<MeasuredLine> = Vec::new();
+        for sg in &section_glyphs {
+            let font = scaled_font(sg.section_index);
+            let baseline = sg.glyph.position.y;
+            let top = baseline - font.ascent();
+            let bottom = baseline - font.descent();
+            // the sections of a line share its baseline
+            let same_line = lines
+                .last()
+                .is_some_and(|line| (line.baseline - baseline).abs() < 0.5);
+            if !same_line {
+                lines.push(MeasuredLine {
+                    glyphs: glyphs.len()..glyphs.len(),
+                    baseline,
+                    top,
+                    bottom,
+                });
+            }
+            let line = lines.last_mut().unwrap();
+            line.glyphs.end += 1;
+            line.top = line.top.min(top);
+            line.bottom = line.bottom.max(bottom);
+
+            let value = &sections[sg.section_index].value;
+            let char_len = value[sg.byte_index..]
+                .chars()
+                .next()
+                .map_or(0, char::len_utf8);
+            let x = sg.glyph.position.x - text_bounds.min.x;
+            glyphs.push(MeasuredGlyph {
+                section_index: sg.section_index,
+                byte_range: sg.byte_index..sg.byte_index + char_len,
+                line_index: lines.len() - 1,
+                rect: Rect::new(x, top, x + font.h_advance(sg.glyph.id), bottom),
+            });
+        }
+
+        Ok(Self {
+            size: text_bounds.size(),
+            glyphs,
+            lines,
+        })
+    }
+
+    /// Returns the caret of the text input before the character at `byte_index` in the section
+    /// `section_index`, as a zero-width rectangle spanning the height of its line.
+    ///
+    /// A caret past the last character is placed after the last glyph.
+    pub fn caret_rect(&self, section_index: usize, byte_index: usize) -> Rect {
+        let next = self.glyphs.iter().find(|glyph| {
+            (glyph.section_index, glyph.byte_range.start) >= (section_index, byte_index)
+        });
+        let (x, glyph) = match next {
+            Some(glyph) => (glyph.rect.min.x, glyph),
+            None => match self.glyphs.last() {
+                Some(glyph) => (glyph.rect.max.x, glyph),
+                None => return Rect::default(),
+            },
+        };
+        let line = &self.lines[glyph.line_index];
+        Rect::new(x, line.top, x, line.bottom)
+    }
+
+    /// Returns the position of the caret closest to `point`, as the index of a section and a
+    /// byte index in its value, or `None` if the text is empty.
+    ///
+    /// This is the inverse of [`caret_rect`](Self::caret_rect), to place the caret where a text
+    /// input is clicked.
+    pub fn caret_at(&self, point: Vec2) -> Option<(usize, usize)> {
+        let line = self
+            .lines
+            .iter()
+            .find(|line| point.y < line.bottom)
+            .or(self.lines.last())?;
+        let glyphs = &self.glyphs[line.glyphs.clone()];
+        if let Some(glyph) = glyphs.iter().find(|glyph| point.x < glyph.rect.center().x) {
+            return Some((glyph.section_index, glyph.byte_range.start));
+        }
+        let last = glyphs.last()?;
+        Some((last.section_index, last.byte_range.end))
+    }
+}
@@ -278,10 +278,11 @@ mod tests {
 
     use bevy_app::{App, Update};
     use bevy_asset::{load_internal_binary_asset, Handle};
-    use bevy_ecs::{event::Events, schedule::IntoSystemConfigs};
+    use bevy_ecs::{event::Events, schedule::IntoSystemConfigs, system::SystemState};
     use bevy_utils::default;
 
     use super::*;
+    use crate::TextMeasurer;
 
     const FIRST_TEXT: &str = "Sample text.";
     const SECOND_TEXT: &str = "Another, longer sample text.";
@@ -387,4 +388,40 @@ mod tests {
         assert!(FIRST_TEXT.len() < SECOND_TEXT.len());
         assert!(first_aabb.half_extents.x < second_aabb.half_extents.x);
     }
+
+    #[test]
+    fn text_measurer_matches_text2d_layout() {
+        let (mut app, entity) = setup();
+
+        // Lays out the text of the entity.
+        app.update();
+
+        let layout_size = app
+            .world()
+            .get::<TextLayoutInfo>(entity)
+            .expect("Text should be laid out")
+            .logical_size;
+        assert!(layout_size.x > 0.0 && layout_size.y > 0.0);
+
+        let text = app
+            .world()
+            .get::<Text>(entity)
+            .expect("Missing Text on entity")
+            .clone();
+        let mut system_state: SystemState<TextMeasurer> = SystemState::new(app.world_mut());
+        let measurer = system_state.get(app.world());
+        let measurement = measurer
+            .measure(&text, Text2dBounds::UNBOUNDED.size)
+            .expect("The font should be loaded");
+        assert_eq!(measurement.size, layout_size);
+
+        let measurement = measurer
+            .measure_str(
+                FIRST_TEXT,
+                &text.sections[0].style,
+                Text2dBounds::UNBOUNDED.size,
+            )
+            .expect("The font should be loaded");
+        assert_eq!(measurement.size, layout_size);
+    }
 }
@@ -1,5 +1,10 @@
-use bevy_app::FixedMain;
-use bevy_ecs::world::World;
+use std::{fmt, marker::PhantomData};
+
+use bevy_app::{App, FixedMain, RunFixedMainLoop};
+use bevy_ecs::{
+    schedule::{IntoSystemConfigs, ScheduleLabel},
+    world::World,
+};
 #[cfg(feature = "bevy_reflect")]
 use bevy_reflect::Reflect;
 use bevy_utils::Duration;
@@ -249,6 +254,193 @@ pub fn run_fixed_main_schedule(world: &mut World) {
     *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
 }
 
+/// The fixed timestep clock of a schedule added with
+/// [`add_fixed_schedule`](AppExtFixedSchedule::add_fixed_schedule), labeled with the type `L`.
+///
+/// Each fixed schedule has its own accumulator, so several fixed-timestep loops can run at
+/// different rates, for example 60 Hz physics and 10 Hz AI. Like [`Time<Fixed>`] for
+/// [`FixedMain`], the clock of a fixed schedule follows [`Time<Virtual>`](Virtual), and is set as
+/// the generic [`Time`] resource while the schedule runs.
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::schedule::ScheduleLabel;
+/// # use bevy_time::{AppExtFixedSchedule, FixedRate, Time, TimePlugin};
+/// #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+/// struct AiTick;
+///
+/// fn think(time: Res<Time<FixedRate<AiTick>>>) {
+///     assert_eq!(time.delta_seconds(), 0.1);
+/// }
+///
+/// let mut app = App::new();
+/// app.add_plugins(TimePlugin)
+///     .add_fixed_schedule(10.0, AiTick)
+///     .add_systems(AiTick, think);
+/// ```
+pub struct FixedRate<L> {
+    timestep: Duration,
+    overstep: Duration,
+    marker: PhantomData<fn() -> L>,
+}
+
+impl<L> Clone for FixedRate<L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L> Copy for FixedRate<L> {}
+
+impl<L> fmt::Debug for FixedRate<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedRate")
+            .field("label", &std::any::type_name::<L>())
+            .field("timestep", &self.timestep)
+            .field("overstep", &self.overstep)
+            .finish()
+    }
+}
+
+impl<L> Default for FixedRate<L> {
+    fn default() -> Self {
+        Self {
+            timestep: Time::<Fixed>::DEFAULT_TIMESTEP,
+            overstep: Duration::ZERO,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<L: 'static> Time<FixedRate<L>> {
+    /// Return new fixed time clock with given timestep frequency in Hertz (1/seconds)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    pub fn from_hz(hz: f64) -> Self {
+        let mut ret = Self::default();
+        ret.set_timestep_hz(hz);
+        ret
+    }
+
+    /// Returns the amount of virtual time that must pass before the fixed
+    /// schedule is run again.
+    #[inline]
+    pub fn timestep(&self) -> Duration {
+        self.context().timestep
+    }
+
+    /// Sets the amount of virtual time that must pass before the fixed schedule
+    /// is run again, as [`Duration`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestep` is zero.
+    #[inline]
+    pub fn set_timestep(&mut self, timestep: Duration) {
+        assert_ne!(
+            timestep,
+            Duration::ZERO,
+            "attempted to set fixed timestep to zero"
+        );
+        self.context_mut().timestep = timestep;
+    }
+
+    /// Sets the amount of virtual time that must pass before the fixed schedule
+    /// is run again, as frequency.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hz` is zero, negative or not finite.
+    #[inline]
+    pub fn set_timestep_hz(&mut self, hz: f64) {
+        assert!(hz.is_sign_positive(), "Hz less than or equal to zero");
+        assert!(hz.is_finite(), "Hz is infinite");
+        self.set_timestep(Duration::from_secs_f64(1.0 / hz));
+    }
+
+    /// Returns the amount of overstep time accumulated toward new steps, as
+    /// [`Duration`].
+    #[inline]
+    pub fn overstep(&self) -> Duration {
+        self.context().overstep
+    }
+
+    /// Returns the amount of overstep time accumulated toward new steps, as an
+    /// [`f32`] fraction of the timestep.
+    #[inline]
+    pub fn overstep_fraction(&self) -> f32 {
+        self.context().overstep.as_secs_f32() / self.context().timestep.as_secs_f32()
+    }
+
+    fn accumulate(&mut self, delta: Duration) {
+        self.context_mut().overstep += delta;
+    }
+
+    fn expend(&mut self) -> bool {
+        let timestep = self.timestep();
+        if let Some(new_value) = self.context_mut().overstep.checked_sub(timestep) {
+            self.context_mut().overstep = new_value;
+            self.advance_by(timestep);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Runs the schedule `label` zero or more times based on delta of
+/// [`Time<Virtual>`](Virtual) and the overstep of its [`Time<FixedRate<L>>`](FixedRate).
+pub fn run_fixed_schedule<L: ScheduleLabel>(world: &mut World, label: L) {
+    let delta = world.resource::<Time<Virtual>>().delta();
+    world.resource_mut::<Time<FixedRate<L>>>().accumulate(delta);
+
+    // Run the schedule until we run out of accumulated time
+    let _ = world.try_schedule_scope(label, |world, schedule| {
+        while world.resource_mut::<Time<FixedRate<L>>>().expend() {
+            *world.resource_mut::<Time>() = world.resource::<Time<FixedRate<L>>>().as_generic();
+            schedule.run(world);
+        }
+    });
+
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
+
+/// Adds fixed-timestep schedules running independently of [`FixedMain`].
+pub trait AppExtFixedSchedule {
+    /// Adds the schedule `label`, run `hz` times per second of [`Time<Virtual>`](Virtual) with
+    /// its own [`Time<FixedRate<L>>`](FixedRate) clock.
+    ///
+    /// Fixed schedules run in [`RunFixedMainLoop`], after [`FixedMain`], in an unspecified order
+    /// between each other. Unlike [`FixedMain`], they don't delay the update of events, so a fixed schedule
+    /// running slower than the frame rate can miss events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a fixed schedule was already added for the label type `L`, or if `hz` is zero,
+    /// negative or not finite.
+    fn add_fixed_schedule<L: ScheduleLabel + Clone>(&mut self, hz: f64, label: L) -> &mut Self;
+}
+
+impl AppExtFixedSchedule for App {
+    fn add_fixed_schedule<L: ScheduleLabel + Clone>(&mut self, hz: f64, label: L) -> &mut Self {
+        assert!(
+            !self.world().contains_resource::<Time<FixedRate<L>>>(),
+            "a fixed schedule was already added for the label type `{}`",
+            std::any::type_name::<L>()
+        );
+        self.init_schedule(label.clone())
+            .insert_resource(Time::<FixedRate<L>>::from_hz(hz))
+            .add_systems(
+                RunFixedMainLoop,
+                (move |world: &mut World| run_fixed_schedule(world, label.clone()))
+                    .after(run_fixed_main_schedule),
+            )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -356,4 +548,53 @@ mod test {
         assert_eq!(time.elapsed(), Duration::from_secs(6));
         assert_eq!(time.overstep(), Duration::from_secs(1));
     }
+
+    #[test]
+    fn fixed_schedules_run_at_their_own_rate() {
+        use crate::{TimePlugin, TimeUpdateStrategy};
+        use bevy_ecs::prelude::*;
+
+        #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Physics;
+
+        #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+        struct Ai;
+
+        #[derive(Resource, Default)]
+        struct Runs {
+            physics: u32,
+            ai: u32,
+        }
+
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .init_resource::<Runs>()
+            .add_fixed_schedule(20.0, Physics)
+            .add_fixed_schedule(10.0, Ai)
+            .add_systems(Physics, |time: Res<Time>, mut runs: ResMut<Runs>| {
+                assert_eq!(time.delta(), Duration::from_millis(50));
+                runs.physics += 1;
+            })
+            .add_systems(Ai, |time: Res<Time>, mut runs: ResMut<Runs>| {
+                assert_eq!(time.delta(), Duration::from_millis(100));
+                runs.ai += 1;
+            });
+
+        for _ in 0..4 {
+            app.update();
+        }
+
+        let elapsed = app.world().resource::<Time<Virtual>>().elapsed();
+        let runs = app.world().resource::<Runs>();
+        assert_eq!(runs.ai as u128, elapsed.as_millis() / 100);
+        assert_eq!(runs.physics, 2 * runs.ai);
+        assert!(runs.ai > 0);
+        assert_eq!(
+            app.world().resource::<Time<FixedRate<Ai>>>().elapsed(),
+            elapsed
+        );
+    }
 }
@@ -181,8 +181,18 @@ pub fn ui_layout_system(
                     camera.scale_factor,
                     [camera.size.x as f32, camera.size.y as f32].into(),
                 );
+                let aspect_ratio = content_size.as_ref().and_then(|c| c.aspect_ratio);
                 let measure = content_size.and_then(|mut c| c.measure.take());
-                ui_surface.upsert_node(&layout_context, entity, &style, measure);
+                match aspect_ratio {
+                    Some(aspect_ratio) if style.aspect_ratio.is_none() => {
+                        let style = Style {
+                            aspect_ratio: Some(aspect_ratio),
+                            ..style.clone()
+                        };
+                        ui_surface.upsert_node(&layout_context, entity, &style, measure);
+                    }
+                    _ => ui_surface.upsert_node(&layout_context, entity, &style, measure),
+                }
             }
         } else {
             ui_surface.upsert_node(&LayoutContext::DEFAULT, entity, &Style::default(), None);
@@ -924,6 +934,44 @@ mod tests {
         assert_eq!(layout.size.height, content_size.y);
     }
 
+    #[test]
+    fn ui_node_should_keep_its_content_aspect_ratio() {
+        let (mut world, mut ui_schedule) = setup_ui_test_world();
+
+        let mut content_size = ContentSize::fixed_size(Vec2::new(50., 25.));
+        content_size.set_aspect_ratio(Some(4.));
+        let ui_entity = world
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        align_self: AlignSelf::Start,
+                        width: Val::Px(100.),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                content_size,
+            ))
+            .id();
+
+        ui_schedule.run(&mut world);
+
+        // the height follows the width with the aspect ratio of the content
+        let layout = world.resource::<UiSurface>().get_layout(ui_entity).unwrap();
+        assert_eq!(layout.size.width, 4. * layout.size.height);
+
+        // the aspect ratio of the style takes precedence, and is kept when only the style changes
+        world.get_mut::<Style>(ui_entity).unwrap().aspect_ratio = Some(2.);
+        ui_schedule.run(&mut world);
+        let layout = world.resource::<UiSurface>().get_layout(ui_entity).unwrap();
+        assert_eq!(layout.size.width, 2. * layout.size.height);
+
+        world.get_mut::<Style>(ui_entity).unwrap().aspect_ratio = None;
+        ui_schedule.run(&mut world);
+        let layout = world.resource::<UiSurface>().get_layout(ui_entity).unwrap();
+        assert_eq!(layout.size.width, 4. * layout.size.height);
+    }
+
     #[test]
     fn measure_funcs_should_be_removed_on_content_size_removal() {
         let (mut world, mut ui_schedule) = setup_ui_test_world();
@@ -24,16 +24,20 @@ mod accessibility;
 mod focus;
 mod geometry;
 mod layout;
+mod projection;
 mod render;
 mod stack;
 mod texture_slice;
+mod theme;
 mod ui_node;
 
 pub use focus::*;
 pub use geometry::*;
 pub use layout::*;
 pub use measurement::*;
+pub use projection::*;
 pub use render::*;
+pub use theme::*;
 pub use ui_material::*;
 pub use ui_node::*;
 use widget::UiImageSize;
@@ -127,11 +131,22 @@ impl Plugin for UiPlugin {
             .register_type::<BorderRadius>()
             .register_type::<widget::Button>()
             .register_type::<widget::Label>()
+            .register_type::<widget::Minimap>()
+            .register_type::<widget::MinimapCursor>()
             .register_type::<ZIndex>()
             .register_type::<Outline>()
+            .register_type::<Theme>()
+            .register_type::<ThemedBackgroundColor>()
+            .register_type::<ThemedBorderColor>()
+            .register_type::<ThemedBorderRadius>()
+            .register_type::<ThemedPadding>()
+            .register_type::<ThemedMargin>()
             .add_systems(
                 PreUpdate,
-                ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                (
+                    ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
+                    widget::update_minimap_cursor_system.after(UiSystem::Focus),
+                ),
             );
 
         app.add_systems(
@@ -139,6 +154,7 @@ impl Plugin for UiPlugin {
             (
                 check_visibility::<WithNode>.in_set(VisibilitySystems::CheckVisibility),
                 update_target_camera_system.before(UiSystem::Layout),
+                apply_theme_system.before(UiSystem::Layout),
                 apply_deferred
                     .after(update_target_camera_system)
                     .before(UiSystem::Layout),
@@ -197,7 +213,8 @@ fn build_text_interop(app: &mut App) {
     use bevy_text::TextLayoutInfo;
 
     app.register_type::<TextLayoutInfo>()
-        .register_type::<TextFlags>();
+        .register_type::<TextFlags>()
+        .register_type::<ThemedText>();
 
     app.add_systems(
         PostUpdate,
@@ -216,6 +233,7 @@ fn build_text_interop(app: &mut App) {
                 // We assume Text is on disjoint UI entities to UiImage and UiTextureAtlasImage
                 // FIXME: Add an archetype invariant for this https://github.com/bevyengine/bevy/issues/1481.
                 .ambiguous_with(widget::update_image_content_size_system),
+            apply_text_theme_system.before(widget::measure_text_system),
             widget::text_system
                 .after(UiSystem::Layout)
                 .after(bevy_text::remove_dropped_font_atlas_sets)
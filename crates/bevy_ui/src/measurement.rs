@@ -94,6 +94,8 @@ pub struct ContentSize {
     /// The `Measure` used to compute the intrinsic size
     #[reflect(ignore)]
     pub(crate) measure: Option<NodeMeasure>,
+    /// The aspect ratio of the content, used by the layout if the node's style doesn't set one
+    pub(crate) aspect_ratio: Option<f32>,
 }
 
 impl ContentSize {
@@ -102,6 +104,20 @@ impl ContentSize {
         self.measure = Some(measure);
     }
 
+    /// Returns the aspect ratio (width / height) the content keeps during layout, if any.
+    pub fn aspect_ratio(&self) -> Option<f32> {
+        self.aspect_ratio
+    }
+
+    /// Set the aspect ratio (width / height) the content keeps during layout, used when the node's
+    /// [`Style::aspect_ratio`](crate::Style::aspect_ratio) is `None`.
+    ///
+    /// The layout picks up a new aspect ratio along with a new `Measure` or a change of the node's
+    /// [`Style`](crate::Style).
+    pub fn set_aspect_ratio(&mut self, aspect_ratio: Option<f32>) {
+        self.aspect_ratio = aspect_ratio;
+    }
+
     /// Creates a `ContentSize` with a `Measure` that always returns given `size` argument, regardless of the UI layout's constraints.
     pub fn fixed_size(size: Vec2) -> ContentSize {
         let mut content_size = Self::default();
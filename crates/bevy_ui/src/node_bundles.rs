@@ -5,14 +5,17 @@
 #[cfg(feature = "bevy_text")]
 use crate::widget::TextFlags;
 use crate::{
-    widget::{Button, UiImageSize},
-    BackgroundColor, BorderColor, BorderRadius, ContentSize, FocusPolicy, Interaction, Node, Style,
-    UiImage, UiMaterial, ZIndex,
+    widget::{Button, Minimap, MinimapCursor, UiImageSize},
+    BackgroundColor, BorderColor, BorderRadius, ContentSize, FocusPolicy, Interaction, Node,
+    RelativeCursorPosition, Style, UiImage, UiMaterial, ZIndex,
 };
 use bevy_asset::Handle;
 use bevy_color::Color;
-use bevy_ecs::bundle::Bundle;
-use bevy_render::view::{InheritedVisibility, ViewVisibility, Visibility};
+use bevy_ecs::{bundle::Bundle, entity::Entity};
+use bevy_render::{
+    texture::Image,
+    view::{InheritedVisibility, ViewVisibility, Visibility},
+};
 use bevy_sprite::TextureAtlas;
 #[cfg(feature = "bevy_text")]
 use bevy_text::{BreakLineOn, JustifyText, Text, TextLayoutInfo, TextSection, TextStyle};
@@ -121,6 +124,40 @@ pub struct ImageBundle {
     pub z_index: ZIndex,
 }
 
+/// A UI node displaying the image rendered by a [`Minimap`] camera
+///
+/// The [`MinimapCursor`] of the node is updated with the position of the cursor in the world seen
+/// by the camera, to pick entities through the minimap.
+#[derive(Bundle, Debug, Default)]
+pub struct MinimapBundle {
+    /// The image node displaying the render target of the camera
+    pub image: ImageBundle,
+    /// The camera rendering to the image
+    pub minimap: Minimap,
+    /// The position of the cursor in the world seen by the camera
+    ///
+    /// This component is updated automatically
+    pub cursor: MinimapCursor,
+    /// The position of the cursor relative to the node
+    ///
+    /// This component is updated automatically
+    pub relative_cursor_position: RelativeCursorPosition,
+}
+
+impl MinimapBundle {
+    /// Creates a node displaying the `image` rendered by `camera`.
+    pub fn new(camera: Entity, image: Handle<Image>) -> Self {
+        Self {
+            image: ImageBundle {
+                image: UiImage::new(image),
+                ..Default::default()
+            },
+            minimap: Minimap { camera },
+            ..Default::default()
+        }
+    }
+}
+
 /// A UI node that is a texture atlas sprite
 ///
 /// # Extra behaviours
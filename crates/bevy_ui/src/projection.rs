@@ -0,0 +1,244 @@
+use crate::{DefaultUiCamera, TargetCamera, UiScale};
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{Ray3d, Rect, Vec2, Vec3};
+use bevy_render::camera::Camera;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::PrimaryWindow;
+use thiserror::Error;
+
+/// Errors that occur when mapping positions between the world and the UI
+#[derive(Debug, PartialEq, Clone, Copy, Error)]
+pub enum UiProjectionError {
+    /// The viewport of a camera isn't known, see [`Camera::logical_viewport_rect`].
+    #[error("The viewport of the camera isn't known")]
+    UnknownViewport,
+    /// The transform or the projection of the camera contains `NAN`.
+    #[error("The position can't be mapped by the projection of the camera")]
+    InvalidProjection,
+    /// The world position is beyond the near or far plane of the camera, such as behind it.
+    #[error("The position is beyond the near or far plane of the camera")]
+    OutsideClipPlanes,
+    /// The position is outside of the viewport of the world camera, such as in its letterbox.
+    ///
+    /// When mapping a world position, this holds the UI position it maps to, to clamp markers of
+    /// off-screen objects to the edges of the viewport for instance.
+    #[error("The position {0} is outside of the viewport of the camera")]
+    OffScreen(Vec2),
+    /// The camera doesn't exist or doesn't have a [`Camera`] and a [`GlobalTransform`].
+    #[error("The entity {0:?} isn't a camera")]
+    NoCamera(Entity),
+    /// There is no camera displaying the UI, see [`DefaultUiCamera`].
+    #[error("There is no camera displaying the UI")]
+    NoUiCamera,
+    /// The world camera and the UI camera render to different targets.
+    #[error("The world camera and the UI camera render to different targets")]
+    DifferentTargets,
+}
+
+/// Maps positions between the world seen by a [`Camera`] and the UI displayed by another camera
+/// rendering to the same target.
+///
+/// UI positions are in logical UI pixels from the top-left corner of the viewport of the UI
+/// camera, like the translation of the [`GlobalTransform`] of UI nodes and the `left` and `top`
+/// of absolutely positioned root nodes. They account for the [`UiScale`], the scale factor of the
+/// target, and the custom viewports of both cameras, such as letterboxing.
+pub trait CameraUiExt {
+    /// Returns the UI position of `world_position`, to place a marker over a 3D object for
+    /// instance.
+    ///
+    /// `ui_camera` is the camera displaying the UI, which may be this camera, and `ui_scale` the
+    /// value of the [`UiScale`] resource.
+    fn world_to_ui_node(
+        &self,
+        camera_transform: &GlobalTransform,
+        world_position: Vec3,
+        ui_camera: &Camera,
+        ui_scale: f32,
+    ) -> Result<Vec2, UiProjectionError>;
+
+    /// Returns the ray through the world at `ui_position`, to pick objects under a UI node for
+    /// instance.
+    ///
+    /// `ui_camera` is the camera displaying the UI, which may be this camera, and `ui_scale` the
+    /// value of the [`UiScale`] resource.
+    fn ui_node_to_world(
+        &self,
+        camera_transform: &GlobalTransform,
+        ui_position: Vec2,
+        ui_camera: &Camera,
+        ui_scale: f32,
+    ) -> Result<Ray3d, UiProjectionError>;
+}
+
+impl CameraUiExt for Camera {
+    fn world_to_ui_node(
+        &self,
+        camera_transform: &GlobalTransform,
+        world_position: Vec3,
+        ui_camera: &Camera,
+        ui_scale: f32,
+    ) -> Result<Vec2, UiProjectionError> {
+        let viewport = self
+            .logical_viewport_rect()
+            .ok_or(UiProjectionError::UnknownViewport)?;
+        let ui_viewport = ui_camera
+            .logical_viewport_rect()
+            .ok_or(UiProjectionError::UnknownViewport)?;
+        let ndc = self
+            .world_to_ndc(camera_transform, world_position)
+            .ok_or(UiProjectionError::InvalidProjection)?;
+        // NDC z-values outside of 0 < z < 1 are outside the (implicit) camera frustum.
+        if ndc.z < 0.0 || ndc.z > 1.0 {
+            return Err(UiProjectionError::OutsideClipPlanes);
+        }
+
+        let viewport_position = Vec2::new(ndc.x + 1.0, 1.0 - ndc.y) / 2.0 * viewport.size();
+        let target_position = viewport.min + viewport_position;
+        let ui_position = (target_position - ui_viewport.min) / ui_scale;
+        if !viewport.contains(target_position) {
+            return Err(UiProjectionError::OffScreen(ui_position));
+        }
+        Ok(ui_position)
+    }
+
+    fn ui_node_to_world(
+        &self,
+        camera_transform: &GlobalTransform,
+        ui_position: Vec2,
+        ui_camera: &Camera,
+        ui_scale: f32,
+    ) -> Result<Ray3d, UiProjectionError> {
+        let viewport = self
+            .logical_viewport_rect()
+            .ok_or(UiProjectionError::UnknownViewport)?;
+        let ui_viewport = ui_camera
+            .logical_viewport_rect()
+            .ok_or(UiProjectionError::UnknownViewport)?;
+        let target_position = ui_viewport.min + ui_position * ui_scale;
+        if !viewport.contains(target_position) {
+            return Err(UiProjectionError::OffScreen(ui_position));
+        }
+        self.viewport_to_world(camera_transform, target_position - viewport.min)
+            .ok_or(UiProjectionError::InvalidProjection)
+    }
+}
+
+/// A [`SystemParam`] mapping positions between the world seen by a camera and the UI, using the
+/// camera displaying the UI and the [`UiScale`], see [`CameraUiExt`].
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_render::camera::Camera;
+/// # use bevy_transform::components::GlobalTransform;
+/// # use bevy_ui::{Style, UiProjection, UiProjectionError, Val};
+/// #[derive(Component)]
+/// struct Marker {
+///     target: Entity,
+/// }
+///
+/// fn place_markers(
+///     projection: UiProjection,
+///     cameras: Query<Entity, With<Camera>>,
+///     targets: Query<&GlobalTransform>,
+///     mut markers: Query<(&Marker, &mut Style)>,
+/// ) {
+///     let Ok(camera) = cameras.get_single() else {
+///         return;
+///     };
+///     for (marker, mut style) in &mut markers {
+///         let Ok(target) = targets.get(marker.target) else {
+///             continue;
+///         };
+///         match projection.world_to_ui_node(camera, target.translation(), None) {
+///             // Clamp the markers of off-screen targets to the edges of the screen.
+///             Ok(position) | Err(UiProjectionError::OffScreen(position)) => {
+///                 style.left = Val::Px(position.x);
+///                 style.top = Val::Px(position.y);
+///             }
+///             Err(_) => {}
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(place_markers);
+/// ```
+#[derive(SystemParam)]
+pub struct UiProjection<'w, 's> {
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform)>,
+    default_ui_camera: DefaultUiCamera<'w, 's>,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    ui_scale: Res<'w, UiScale>,
+}
+
+impl<'w, 's> UiProjection<'w, 's> {
+    /// Returns the UI position of `world_position` seen by `camera`, see
+    /// [`CameraUiExt::world_to_ui_node`].
+    ///
+    /// The UI is displayed by the camera of `target_camera`, or by the [`DefaultUiCamera`].
+    pub fn world_to_ui_node(
+        &self,
+        camera: Entity,
+        world_position: Vec3,
+        target_camera: Option<&TargetCamera>,
+    ) -> Result<Vec2, UiProjectionError> {
+        let (camera, camera_transform, ui_camera) = self.cameras(camera, target_camera)?;
+        camera.world_to_ui_node(camera_transform, world_position, ui_camera, self.ui_scale.0)
+    }
+
+    /// Returns the ray through the world seen by `camera` at `ui_position`, see
+    /// [`CameraUiExt::ui_node_to_world`].
+    ///
+    /// The UI is displayed by the camera of `target_camera`, or by the [`DefaultUiCamera`].
+    pub fn ui_node_to_world(
+        &self,
+        camera: Entity,
+        ui_position: Vec2,
+        target_camera: Option<&TargetCamera>,
+    ) -> Result<Ray3d, UiProjectionError> {
+        let (camera, camera_transform, ui_camera) = self.cameras(camera, target_camera)?;
+        camera.ui_node_to_world(camera_transform, ui_position, ui_camera, self.ui_scale.0)
+    }
+
+    /// Returns the logical rect of the viewport of `camera` in the UI, to lay out a HUD around a
+    /// letterboxed viewport for instance.
+    pub fn viewport_rect(
+        &self,
+        camera: Entity,
+        target_camera: Option<&TargetCamera>,
+    ) -> Result<Rect, UiProjectionError> {
+        let (camera, _, ui_camera) = self.cameras(camera, target_camera)?;
+        let viewport = camera
+            .logical_viewport_rect()
+            .ok_or(UiProjectionError::UnknownViewport)?;
+        let ui_viewport = ui_camera
+            .logical_viewport_rect()
+            .ok_or(UiProjectionError::UnknownViewport)?;
+        Ok(Rect {
+            min: (viewport.min - ui_viewport.min) / self.ui_scale.0,
+            max: (viewport.max - ui_viewport.min) / self.ui_scale.0,
+        })
+    }
+
+    fn cameras(
+        &self,
+        camera: Entity,
+        target_camera: Option<&TargetCamera>,
+    ) -> Result<(&Camera, &GlobalTransform, &Camera), UiProjectionError> {
+        let ui_camera = target_camera
+            .map(TargetCamera::entity)
+            .or_else(|| self.default_ui_camera.get())
+            .ok_or(UiProjectionError::NoUiCamera)?;
+        let (camera, camera_transform) = self
+            .cameras
+            .get(camera)
+            .map_err(|_| UiProjectionError::NoCamera(camera))?;
+        let (ui_camera, _) = self
+            .cameras
+            .get(ui_camera)
+            .map_err(|_| UiProjectionError::NoCamera(ui_camera))?;
+        let primary_window = self.primary_window.get_single().ok();
+        if camera.target.normalize(primary_window) != ui_camera.target.normalize(primary_window) {
+            return Err(UiProjectionError::DifferentTargets);
+        }
+        Ok((camera, camera_transform, ui_camera))
+    }
+}
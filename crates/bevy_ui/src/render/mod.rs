@@ -20,9 +20,9 @@ pub use ui_material_pipeline::*;
 
 use crate::graph::{NodeUi, SubGraphUi};
 use crate::{
-    texture_slice::ComputedTextureSlices, BackgroundColor, BorderColor, BorderRadius,
-    CalculatedClip, ContentSize, DefaultUiCamera, Node, Outline, Style, TargetCamera, UiImage,
-    UiScale, Val,
+    texture_slice::ComputedTextureSlices, widget::UiImageSize, BackgroundColor, BorderColor,
+    BorderRadius, CalculatedClip, ContentSize, DefaultUiCamera, ImageFit, Node, Outline, Style,
+    TargetCamera, UiImage, UiScale, Val,
 };
 
 use bevy_app::prelude::*;
@@ -281,14 +281,25 @@ pub fn extract_uinode_images(
             Option<&CalculatedClip>,
             Option<&TargetCamera>,
             &UiImage,
+            Option<&UiImageSize>,
             Option<&TextureAtlas>,
             Option<&ComputedTextureSlices>,
             Option<&BorderRadius>,
         )>,
     >,
 ) {
-    for (uinode, transform, view_visibility, clip, camera, image, atlas, slices, border_radius) in
-        &uinode_query
+    for (
+        uinode,
+        transform,
+        view_visibility,
+        clip,
+        camera,
+        image,
+        image_size,
+        atlas,
+        slices,
+        border_radius,
+    ) in &uinode_query
     {
         let Some(camera_entity) = camera.map(TargetCamera::entity).or(default_ui_camera.get())
         else {
@@ -309,28 +320,33 @@ pub fn extract_uinode_images(
             continue;
         }
 
-        let (rect, atlas_size) = match atlas {
+        let (texture_rect, texture_size) = match atlas {
             Some(atlas) => {
                 let Some(layout) = texture_atlases.get(&atlas.layout) else {
                     // Atlas not present in assets resource (should this warn the user?)
                     continue;
                 };
-                let mut atlas_rect = layout.textures[atlas.index].as_rect();
-                let mut atlas_size = layout.size.as_vec2();
-                let scale = uinode.size() / atlas_rect.size();
-                atlas_rect.min *= scale;
-                atlas_rect.max *= scale;
-                atlas_size *= scale;
-                (atlas_rect, Some(atlas_size))
+                (
+                    layout.textures[atlas.index].as_rect(),
+                    layout.size.as_vec2(),
+                )
+            }
+            None => {
+                // Fill the node until the size of the image is known
+                let size = image_size
+                    .map(|image_size| image_size.size().as_vec2())
+                    .filter(|size| size.cmpgt(Vec2::ZERO).all())
+                    .unwrap_or(uinode.calculated_size);
+                (
+                    Rect {
+                        min: Vec2::ZERO,
+                        max: size,
+                    },
+                    size,
+                )
             }
-            None => (
-                Rect {
-                    min: Vec2::ZERO,
-                    max: uinode.calculated_size,
-                },
-                None,
-            ),
         };
+        let (rect, atlas_size) = fit_image(image.fit, texture_rect, texture_size, uinode.size());
 
         let ui_logical_viewport_size = camera_query
             .get(camera_entity)
@@ -361,7 +377,7 @@ pub fn extract_uinode_images(
                 rect,
                 clip: clip.map(|clip| clip.clip),
                 image: image.texture.id(),
-                atlas_size,
+                atlas_size: Some(atlas_size),
                 flip_x: image.flip_x,
                 flip_y: image.flip_y,
                 camera_entity,
@@ -373,6 +389,47 @@ pub fn extract_uinode_images(
     }
 }
 
+/// Returns the rect of the texture drawn for the region `texture_rect` of a texture of
+/// `texture_size` fitted into a node of `node_size`, and the size of the texture, both at the
+/// scale the region is drawn at.
+///
+/// The size of the returned rect is the size of the drawn quad, centered on the node.
+fn fit_image(
+    fit: ImageFit,
+    texture_rect: Rect,
+    texture_size: Vec2,
+    node_size: Vec2,
+) -> (Rect, Vec2) {
+    let region_size = texture_rect.size();
+    if region_size.cmple(Vec2::ZERO).any() {
+        return (
+            Rect {
+                min: Vec2::ZERO,
+                max: node_size,
+            },
+            node_size,
+        );
+    }
+    let scale = node_size / region_size;
+    let scale = match fit {
+        ImageFit::Fill => scale,
+        ImageFit::Contain => Vec2::splat(scale.min_element()),
+        ImageFit::Cover => Vec2::splat(scale.max_element()),
+        ImageFit::ScaleDown => Vec2::splat(scale.min_element().min(1.)),
+    };
+    let scaled_size = region_size * scale;
+    // `Cover` overflows the node, crop the overflow evenly on both sides
+    let visible_size = scaled_size.min(node_size);
+    let min = texture_rect.min * scale + (scaled_size - visible_size) / 2.;
+    (
+        Rect {
+            min,
+            max: min + visible_size,
+        },
+        texture_size * scale,
+    )
+}
+
 pub(crate) fn resolve_border_thickness(value: Val, parent_width: f32, viewport_size: Vec2) -> f32 {
     match value {
         Val::Auto => 0.,
@@ -0,0 +1,418 @@
+//! Design tokens shared by UI nodes, and the systems applying them.
+//!
+//! A [`Theme`] resource maps token names to colors, spacings, corner radii and fonts. Nodes
+//! reference tokens with the `Themed*` components instead of holding literal values, and
+//! [`apply_theme_system`] copies the current value of each token into the node's
+//! [`BackgroundColor`], [`BorderColor`], [`BorderRadius`] or [`Style`].
+//!
+//! Switching themes at runtime is done by mutating or replacing the [`Theme`] resource: change
+//! detection picks it up and every themed node is updated on the next frame.
+
+use std::borrow::Cow;
+
+use bevy_asset::UntypedHandle;
+use bevy_color::{Color, Oklcha};
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use bevy_utils::{tracing::warn, HashMap};
+
+use crate::{BackgroundColor, BorderColor, BorderRadius, Style, UiRect, Val};
+
+#[cfg(feature = "bevy_text")]
+use bevy_asset::Handle;
+#[cfg(feature = "bevy_text")]
+use bevy_text::{Font, Text};
+
+/// The name of a design token of a [`Theme`].
+pub type ThemeToken = Cow<'static, str>;
+
+/// A set of named design tokens used by themed UI nodes.
+///
+/// The tokens are referenced by the [`ThemedBackgroundColor`], [`ThemedBorderColor`],
+/// [`ThemedBorderRadius`], [`ThemedPadding`], [`ThemedMargin`] and `ThemedText` components.
+/// Changing the resource updates every themed node, see [`apply_theme_system`].
+///
+/// Colors are stored in the [`Oklcha`] color space, in which equal steps of lightness look
+/// equally different, so that variants of a color can be derived by adjusting its lightness and
+/// chroma. [`Theme::dark`] and [`Theme::light`] generate a complete theme from an accent color
+/// this way.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ui::prelude::*;
+/// # use bevy_ui::{Theme, ThemedBackgroundColor, ThemedPadding};
+/// # use bevy_color::palettes::tailwind;
+/// fn setup(mut commands: Commands) {
+///     commands.insert_resource(Theme::dark(tailwind::VIOLET_500.into()));
+///     commands.spawn((
+///         NodeBundle::default(),
+///         ThemedBackgroundColor::new("surface"),
+///         ThemedPadding::new("md"),
+///     ));
+/// }
+///
+/// fn toggle_theme(mut theme: ResMut<Theme>) {
+///     // every themed node is updated with the new values
+///     *theme = Theme::light(tailwind::VIOLET_600.into());
+/// }
+/// # bevy_ecs::system::assert_is_system(setup);
+/// # bevy_ecs::system::assert_is_system(toggle_theme);
+/// ```
+#[derive(Resource, Debug, Clone, Default, Reflect)]
+#[reflect(Resource, Default)]
+pub struct Theme {
+    /// The name of the theme, for display purposes.
+    pub name: Option<String>,
+    /// The color tokens.
+    pub colors: HashMap<String, Oklcha>,
+    /// The spacing tokens, used for padding and margins.
+    pub spacing: HashMap<String, Val>,
+    /// The corner radius tokens.
+    pub radii: HashMap<String, Val>,
+    /// The font tokens, holding handles to `Font` assets.
+    #[reflect(ignore)]
+    pub fonts: HashMap<String, UntypedHandle>,
+}
+
+impl Theme {
+    /// Creates a theme with dark surfaces and light text, derived from `accent`.
+    ///
+    /// The neutral colors are tinted with the hue of `accent`. The theme defines the colors
+    /// `background`, `surface`, `surface-hover`, `border`, `text`, `text-muted`, `primary`,
+    /// `primary-hover` and `on-primary`, the spacings and radii `xs`, `sm`, `md`, `lg` and `xl`
+    /// and the radius `full`.
+    pub fn dark(accent: Color) -> Self {
+        Self::from_accent(accent, [0.18, 0.23, 0.29, 0.38, 0.96, 0.74])
+    }
+
+    /// Creates a theme with light surfaces and dark text, derived from `accent`.
+    ///
+    /// It defines the same tokens as [`Theme::dark`].
+    pub fn light(accent: Color) -> Self {
+        Self::from_accent(accent, [0.98, 0.94, 0.90, 0.82, 0.22, 0.48])
+    }
+
+    /// `lightness` holds the lightness of the `background`, `surface`, `surface-hover`, `border`,
+    /// `text` and `text-muted` colors.
+    fn from_accent(accent: Color, lightness: [f32; 6]) -> Self {
+        let accent: Oklcha = accent.into();
+        let neutral = |lightness: f32| Oklcha::lch(lightness, 0.015, accent.hue);
+        let hover_lightness = if lightness[0] < 0.5 { 0.06 } else { -0.06 };
+        let on_primary = if accent.lightness < 0.65 { 0.98 } else { 0.18 };
+
+        let mut theme = Self::default()
+            .with_color("background", neutral(lightness[0]))
+            .with_color("surface", neutral(lightness[1]))
+            .with_color("surface-hover", neutral(lightness[2]))
+            .with_color("border", neutral(lightness[3]))
+            .with_color("text", neutral(lightness[4]))
+            .with_color("text-muted", neutral(lightness[5]))
+            .with_color("primary", accent)
+            .with_color(
+                "primary-hover",
+                accent.with_lightness((accent.lightness + hover_lightness).clamp(0.0, 1.0)),
+            )
+            .with_color("on-primary", Oklcha::lch(on_primary, 0.01, accent.hue))
+            .with_radius("full", Val::Percent(50.0));
+        for (name, spacing, radius) in [
+            ("xs", 4.0, 2.0),
+            ("sm", 8.0, 4.0),
+            ("md", 16.0, 8.0),
+            ("lg", 24.0, 12.0),
+            ("xl", 32.0, 16.0),
+        ] {
+            theme = theme
+                .with_spacing(name, Val::Px(spacing))
+                .with_radius(name, Val::Px(radius));
+        }
+        theme
+    }
+
+    /// Returns this theme with the given name.
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Returns this theme with the color token `name` set to `color`.
+    pub fn with_color(mut self, name: impl Into<String>, color: impl Into<Oklcha>) -> Self {
+        self.colors.insert(name.into(), color.into());
+        self
+    }
+
+    /// Returns this theme with the spacing token `name` set to `spacing`.
+    pub fn with_spacing(mut self, name: impl Into<String>, spacing: Val) -> Self {
+        self.spacing.insert(name.into(), spacing);
+        self
+    }
+
+    /// Returns this theme with the radius token `name` set to `radius`.
+    pub fn with_radius(mut self, name: impl Into<String>, radius: Val) -> Self {
+        self.radii.insert(name.into(), radius);
+        self
+    }
+
+    /// Returns this theme with the font token `name` set to `font`, a handle to a `Font` asset.
+    pub fn with_font(mut self, name: impl Into<String>, font: impl Into<UntypedHandle>) -> Self {
+        self.fonts.insert(name.into(), font.into());
+        self
+    }
+
+    /// Returns the color of the token `name`.
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.colors.get(name).map(|&color| color.into())
+    }
+
+    /// Returns the spacing of the token `name`.
+    pub fn spacing(&self, name: &str) -> Option<Val> {
+        self.spacing.get(name).copied()
+    }
+
+    /// Returns the radius of the token `name`.
+    pub fn radius(&self, name: &str) -> Option<Val> {
+        self.radii.get(name).copied()
+    }
+
+    /// Returns the font of the token `name`, if it is a handle to a [`Font`].
+    #[cfg(feature = "bevy_text")]
+    pub fn font(&self, name: &str) -> Option<Handle<Font>> {
+        self.fonts.get(name)?.clone().try_typed().ok()
+    }
+}
+
+macro_rules! impl_token_component {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Component, Debug, Clone, PartialEq, Eq, Reflect)]
+        #[reflect(Component, PartialEq)]
+        pub struct $name(pub ThemeToken);
+
+        impl $name {
+            /// Creates a reference to the token `token`.
+            pub fn new(token: impl Into<ThemeToken>) -> Self {
+                Self(token.into())
+            }
+        }
+    };
+}
+
+impl_token_component!(
+    /// Sets the [`BackgroundColor`] of a node to a color token of the [`Theme`].
+    ThemedBackgroundColor
+);
+impl_token_component!(
+    /// Sets the [`BorderColor`] of a node to a color token of the [`Theme`].
+    ThemedBorderColor
+);
+impl_token_component!(
+    /// Sets every corner of the [`BorderRadius`] of a node to a radius token of the [`Theme`].
+    ThemedBorderRadius
+);
+impl_token_component!(
+    /// Sets every side of the [`Style::padding`] of a node to a spacing token of the [`Theme`].
+    ThemedPadding
+);
+impl_token_component!(
+    /// Sets every side of the [`Style::margin`] of a node to a spacing token of the [`Theme`].
+    ThemedMargin
+);
+
+/// Sets the color and font of every section of a [`Text`] to tokens of the [`Theme`].
+#[cfg(feature = "bevy_text")]
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct ThemedText {
+    /// The color token, or `None` to keep the colors of the sections.
+    pub color: Option<ThemeToken>,
+    /// The font token, or `None` to keep the fonts of the sections.
+    pub font: Option<ThemeToken>,
+}
+
+#[cfg(feature = "bevy_text")]
+impl ThemedText {
+    /// Creates a reference to the color token `color`.
+    pub fn color(color: impl Into<ThemeToken>) -> Self {
+        Self {
+            color: Some(color.into()),
+            font: None,
+        }
+    }
+
+    /// Returns this reference with the font token `font`.
+    pub fn with_font(mut self, font: impl Into<ThemeToken>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+}
+
+/// Resolves `token` with `lookup`, warning about tokens missing from the theme.
+fn resolve<T>(token: &str, kind: &str, lookup: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+    let value = lookup(token);
+    if value.is_none() {
+        warn!("The theme has no {kind} token named `{token}`.");
+    }
+    value
+}
+
+/// Copies the values of the [`Theme`] tokens referenced by themed nodes into their components.
+///
+/// Every themed node is updated when the [`Theme`] changes, otherwise only the nodes whose token
+/// reference changed are. Components are only mutated if their value actually differs, so
+/// systems reacting to changes of [`Style`] are not triggered needlessly. Nodes referencing a
+/// missing token keep their current value.
+pub fn apply_theme_system(
+    theme: Option<Res<Theme>>,
+    mut backgrounds: Query<(Ref<ThemedBackgroundColor>, &mut BackgroundColor)>,
+    mut borders: Query<(Ref<ThemedBorderColor>, &mut BorderColor)>,
+    mut radii: Query<(Ref<ThemedBorderRadius>, &mut BorderRadius)>,
+    mut styles: Query<(
+        Option<Ref<ThemedPadding>>,
+        Option<Ref<ThemedMargin>>,
+        &mut Style,
+    )>,
+) {
+    let Some(theme) = theme else {
+        return;
+    };
+    let theme_changed = theme.is_changed();
+
+    for (token, mut background) in &mut backgrounds {
+        if theme_changed || token.is_changed() {
+            if let Some(color) = resolve(&token.0, "color", |name| theme.color(name)) {
+                background.set_if_neq(BackgroundColor(color));
+            }
+        }
+    }
+
+    for (token, mut border) in &mut borders {
+        if theme_changed || token.is_changed() {
+            if let Some(color) = resolve(&token.0, "color", |name| theme.color(name)) {
+                border.set_if_neq(BorderColor(color));
+            }
+        }
+    }
+
+    for (token, mut radius) in &mut radii {
+        if theme_changed || token.is_changed() {
+            if let Some(value) = resolve(&token.0, "radius", |name| theme.radius(name)) {
+                radius.set_if_neq(BorderRadius::all(value));
+            }
+        }
+    }
+
+    for (padding, margin, mut style) in &mut styles {
+        let padding = padding
+            .filter(|padding| theme_changed || padding.is_changed())
+            .and_then(|padding| resolve(&padding.0, "spacing", |name| theme.spacing(name)));
+        if let Some(padding) = padding {
+            if style.padding != UiRect::all(padding) {
+                style.padding = UiRect::all(padding);
+            }
+        }
+
+        let margin = margin
+            .filter(|margin| theme_changed || margin.is_changed())
+            .and_then(|margin| resolve(&margin.0, "spacing", |name| theme.spacing(name)));
+        if let Some(margin) = margin {
+            if style.margin != UiRect::all(margin) {
+                style.margin = UiRect::all(margin);
+            }
+        }
+    }
+}
+
+/// Copies the values of the [`Theme`] tokens referenced by [`ThemedText`] into the sections of
+/// their [`Text`].
+#[cfg(feature = "bevy_text")]
+pub fn apply_text_theme_system(
+    theme: Option<Res<Theme>>,
+    mut texts: Query<(Ref<ThemedText>, &mut Text)>,
+) {
+    let Some(theme) = theme else {
+        return;
+    };
+
+    for (tokens, mut text) in &mut texts {
+        if !theme.is_changed() && !tokens.is_changed() {
+            continue;
+        }
+        let color = tokens
+            .color
+            .as_deref()
+            .and_then(|token| resolve(token, "color", |name| theme.color(name)));
+        let font = tokens
+            .font
+            .as_deref()
+            .and_then(|token| resolve(token, "font", |name| theme.font(name)));
+
+        let needs_update = text.sections.iter().any(|section| {
+            color.is_some_and(|color| section.style.color != color)
+                || font
+                    .as_ref()
+                    .is_some_and(|font| section.style.font != *font)
+        });
+        if !needs_update {
+            continue;
+        }
+        for section in &mut text.sections {
+            if let Some(color) = color {
+                section.style.color = color;
+            }
+            if let Some(font) = &font {
+                section.style.font = font.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_color::palettes::basic;
+    use bevy_ecs::schedule::Schedule;
+
+    #[test]
+    fn theme_switch_updates_themed_nodes() {
+        let mut world = World::new();
+        world.insert_resource(Theme::dark(basic::BLUE.into()));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(apply_theme_system);
+
+        let node = world
+            .spawn((
+                BackgroundColor::DEFAULT,
+                ThemedBackgroundColor::new("surface"),
+                Style::default(),
+                ThemedPadding::new("md"),
+            ))
+            .id();
+        schedule.run(&mut world);
+
+        let dark_surface = world.resource::<Theme>().color("surface").unwrap();
+        assert_eq!(world.get::<BackgroundColor>(node).unwrap().0, dark_surface);
+        assert_eq!(
+            world.get::<Style>(node).unwrap().padding,
+            UiRect::all(Val::Px(16.0))
+        );
+
+        *world.resource_mut::<Theme>() =
+            Theme::light(basic::BLUE.into()).with_spacing("md", Val::Px(12.0));
+        schedule.run(&mut world);
+
+        let light_surface = world.resource::<Theme>().color("surface").unwrap();
+        assert_ne!(dark_surface, light_surface);
+        assert_eq!(world.get::<BackgroundColor>(node).unwrap().0, light_surface);
+        assert_eq!(
+            world.get::<Style>(node).unwrap().padding,
+            UiRect::all(Val::Px(12.0))
+        );
+
+        // retargeting a single node only needs the token reference to change
+        world.get_mut::<ThemedBackgroundColor>(node).unwrap().0 = "primary".into();
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<BackgroundColor>(node).unwrap().0,
+            world.resource::<Theme>().color("primary").unwrap()
+        );
+    }
+}
@@ -1830,6 +1830,15 @@ pub struct UiImage {
     pub flip_x: bool,
     /// Whether the image should be flipped along its y-axis
     pub flip_y: bool,
+    /// How the image is fitted into the node when their aspect ratios differ
+    pub fit: ImageFit,
+    /// Whether the node keeps the aspect ratio of the image during layout
+    ///
+    /// The image's aspect ratio is used as the node's [`Style::aspect_ratio`] if it doesn't set one,
+    /// so a node sized by a single dimension, or stretched by its flex or grid parent, gets the
+    /// other dimension from the image. An image sized by its content also shrinks to fit the space
+    /// available to it instead of overflowing.
+    pub lock_aspect_ratio: bool,
 }
 
 impl UiImage {
@@ -1860,6 +1869,40 @@ impl UiImage {
         self.flip_y = true;
         self
     }
+
+    /// Set how the image is fitted into the node
+    #[must_use]
+    pub const fn with_fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Keep the aspect ratio of the image during layout
+    #[must_use]
+    pub const fn with_locked_aspect_ratio(mut self) -> Self {
+        self.lock_aspect_ratio = true;
+        self
+    }
+}
+
+/// How the image of a [`UiImage`] is fitted into its node, like the CSS `object-fit` property
+///
+/// The image is centered in the node in every mode but [`ImageFit::Fill`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Default, PartialEq)]
+pub enum ImageFit {
+    /// The image is stretched to fill the node, which distorts it if their aspect ratios differ
+    #[default]
+    Fill,
+    /// The image is scaled to fit inside the node while keeping its aspect ratio, leaving empty
+    /// space on two sides if their aspect ratios differ
+    Contain,
+    /// The image is scaled to cover the whole node while keeping its aspect ratio, cropping it on
+    /// two sides if their aspect ratios differ
+    Cover,
+    /// The image is displayed at its size, or scaled down like [`ImageFit::Contain`] if it is
+    /// larger than the node
+    ScaleDown,
 }
 
 impl From<Handle<Image>> for UiImage {
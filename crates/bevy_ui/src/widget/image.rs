@@ -33,6 +33,9 @@ impl UiImageSize {
 pub struct ImageMeasure {
     /// The size of the image's texture
     pub size: Vec2,
+    /// Whether the image is scaled down to fit the available space while keeping its aspect ratio,
+    /// when it is sized by its content
+    pub lock_aspect_ratio: bool,
 }
 
 impl Measure for ImageMeasure {
@@ -40,12 +43,22 @@ impl Measure for ImageMeasure {
         &self,
         width: Option<f32>,
         height: Option<f32>,
-        _: AvailableSpace,
-        _: AvailableSpace,
+        available_width: AvailableSpace,
+        available_height: AvailableSpace,
     ) -> Vec2 {
         let mut size = self.size;
         match (width, height) {
-            (None, None) => {}
+            (None, None) => {
+                if self.lock_aspect_ratio {
+                    let definite = |space| match space {
+                        AvailableSpace::Definite(value) => value,
+                        AvailableSpace::MinContent | AvailableSpace::MaxContent => f32::INFINITY,
+                    };
+                    let available =
+                        Vec2::new(definite(available_width), definite(available_height));
+                    size *= (available / size).min_element().min(1.);
+                }
+            }
             (Some(width), None) => {
                 size.y = width * size.y / size.x;
                 size.x = width;
@@ -97,15 +110,20 @@ pub fn update_image_content_size_system(
             Some(atlas) => atlas.texture_rect(&atlases).map(|t| t.size()),
             None => textures.get(&image.texture).map(|t| t.size()),
         } {
-            // Update only if size or scale factor has changed to avoid needless layout calculations
+            let aspect_ratio = (image.lock_aspect_ratio && size.x > 0 && size.y > 0)
+                .then(|| size.x as f32 / size.y as f32);
+            // Update only if size, scale factor or aspect ratio has changed to avoid needless layout calculations
             if size != image_size.size
                 || combined_scale_factor != *previous_combined_scale_factor
                 || content_size.is_added()
+                || content_size.aspect_ratio() != aspect_ratio
             {
                 image_size.size = size;
+                content_size.set_aspect_ratio(aspect_ratio);
                 content_size.set(NodeMeasure::Image(ImageMeasure {
                     // multiply the image size by the scale factor to get the physical size
                     size: size.as_vec2() * combined_scale_factor,
+                    lock_aspect_ratio: image.lock_aspect_ratio,
                 }));
             }
         }
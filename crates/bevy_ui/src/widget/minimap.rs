@@ -0,0 +1,229 @@
+use crate::{Node, RelativeCursorPosition};
+use bevy_asset::Handle;
+use bevy_core_pipeline::core_3d::Camera3dBundle;
+use bevy_ecs::{prelude::*, system::SystemParam};
+use bevy_math::{Ray3d, Rect, UVec2, Vec2, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::{Camera, OrthographicProjection, Projection, ScalingMode},
+    render_resource::{
+        Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+    },
+    texture::Image,
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// A UI node displaying the image rendered by a capture camera, such as a top-down minimap.
+///
+/// The node maps positions between the world seen by its [`camera`](Self::camera) and the UI,
+/// through UV coordinates in the image: `(0, 0)` is its top-left corner and `(1, 1)` its
+/// bottom-right corner. See [`MinimapProjection`] to map positions between the world and the
+/// minimap nodes, and [`MinimapCursor`] for the world position under the cursor.
+///
+/// ```
+/// # use bevy_asset::Assets;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::{Rect, UVec2};
+/// # use bevy_render::texture::Image;
+/// # use bevy_ui::{node_bundles::MinimapBundle, widget::Minimap};
+/// fn spawn_minimap(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+///     let image = images.add(Minimap::create_image(UVec2::splat(256)));
+///     // Capture the area of the level from (-50, -50) to (50, 50) on the XZ plane.
+///     let area = Rect::new(-50.0, -50.0, 50.0, 50.0);
+///     let camera = commands
+///         .spawn(Minimap::camera_bundle(image.clone(), area, 100.0))
+///         .id();
+///     commands.spawn(MinimapBundle::new(camera, image));
+/// }
+/// # bevy_ecs::system::assert_is_system(spawn_minimap);
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct Minimap {
+    /// The camera rendering to the [`UiImage`](crate::UiImage) of the node.
+    pub camera: Entity,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self {
+            camera: Entity::PLACEHOLDER,
+        }
+    }
+}
+
+impl Minimap {
+    /// Creates an image of `size` pixels that a minimap camera can render to and a UI node can
+    /// display.
+    pub fn create_image(size: UVec2) -> Image {
+        let size = Extent3d {
+            width: size.x,
+            height: size.y,
+            ..Default::default()
+        };
+        let mut image = Image {
+            texture_descriptor: TextureDescriptor {
+                label: Some("minimap_image"),
+                size,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Bgra8UnormSrgb,
+                mip_level_count: 1,
+                sample_count: 1,
+                usage: TextureUsages::TEXTURE_BINDING
+                    | TextureUsages::COPY_DST
+                    | TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+            ..Default::default()
+        };
+        image.resize(size);
+        image
+    }
+
+    /// Returns a top-down orthographic camera rendering `area` of the XZ plane to `target`, from
+    /// `height` units above it.
+    ///
+    /// The top of the image is towards -Z. The camera renders before the cameras with the
+    /// default order, so that the image is up to date when they display it.
+    pub fn camera_bundle(target: Handle<Image>, area: Rect, height: f32) -> Camera3dBundle {
+        let center = area.center();
+        let size = area.size();
+        let center = Vec3::new(center.x, 0.0, center.y);
+        Camera3dBundle {
+            camera: Camera {
+                order: -1,
+                target: target.into(),
+                ..Default::default()
+            },
+            projection: Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::Fixed {
+                    width: size.x,
+                    height: size.y,
+                },
+                far: height * 2.0,
+                ..Default::default()
+            }),
+            transform: Transform::from_translation(center + Vec3::Y * height)
+                .looking_at(center, Vec3::NEG_Z),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the UV coordinates in the image of `camera` of the `world_position`, or `None` if
+    /// the projection of the camera isn't known yet.
+    ///
+    /// The coordinates are outside of `[0, 1]` for positions outside of the view of the camera.
+    pub fn world_to_uv(
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        world_position: Vec3,
+    ) -> Option<Vec2> {
+        let ndc = camera.world_to_ndc(camera_transform, world_position)?;
+        Some(Vec2::new(ndc.x + 1.0, 1.0 - ndc.y) / 2.0)
+    }
+
+    /// Returns the ray through the world seen by `camera` at the `uv` coordinates of its image,
+    /// or `None` if the projection of the camera isn't known yet.
+    ///
+    /// For a top-down camera, intersect the ray with the ground to find the position under `uv`.
+    pub fn uv_to_world_ray(
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        uv: Vec2,
+    ) -> Option<Ray3d> {
+        let viewport_size = camera.logical_viewport_size()?;
+        camera.viewport_to_world(camera_transform, uv * viewport_size)
+    }
+
+    /// Returns the logical UI position of the `uv` coordinates in the image of a minimap node.
+    pub fn uv_to_ui(node: &Node, node_transform: &GlobalTransform, uv: Vec2) -> Vec2 {
+        let rect = node.logical_rect(node_transform);
+        rect.min + uv * rect.size()
+    }
+
+    /// Returns the UV coordinates in the image of a minimap node of a logical UI position, or
+    /// `None` if the node has no area.
+    pub fn ui_to_uv(node: &Node, node_transform: &GlobalTransform, position: Vec2) -> Option<Vec2> {
+        let rect = node.logical_rect(node_transform);
+        let size = rect.size();
+        (size.cmpgt(Vec2::ZERO).all()).then(|| (position - rect.min) / size)
+    }
+}
+
+/// The cursor over a [`Minimap`] node, to pick entities in the world seen by its camera through
+/// the minimap.
+///
+/// This is updated in [`PreUpdate`](bevy_app::PreUpdate) from the [`RelativeCursorPosition`] of
+/// the node, which must be present too.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default, PartialEq)]
+pub struct MinimapCursor {
+    /// The UV coordinates of the cursor in the image of the minimap, or `None` if the cursor
+    /// isn't over the visible area of the node.
+    pub uv: Option<Vec2>,
+    /// The ray through the world seen by the camera of the minimap under the cursor, or `None` if
+    /// the cursor isn't over the node.
+    #[reflect(ignore)]
+    pub ray: Option<Ray3d>,
+}
+
+/// Updates the [`MinimapCursor`] of the [`Minimap`] nodes.
+pub fn update_minimap_cursor_system(
+    mut minimaps: Query<(&Minimap, &RelativeCursorPosition, &mut MinimapCursor)>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+) {
+    for (minimap, relative_cursor_position, mut cursor) in &mut minimaps {
+        let uv = relative_cursor_position
+            .normalized
+            .filter(|_| relative_cursor_position.mouse_over());
+        let ray = uv.and_then(|uv| {
+            let (camera, camera_transform) = cameras.get(minimap.camera).ok()?;
+            Minimap::uv_to_world_ray(camera, camera_transform, uv)
+        });
+        cursor.set_if_neq(MinimapCursor { uv, ray });
+    }
+}
+
+/// A [`SystemParam`] mapping positions between the world and the [`Minimap`] nodes.
+#[derive(SystemParam)]
+pub struct MinimapProjection<'w, 's> {
+    minimaps: Query<'w, 's, (&'static Minimap, &'static Node, &'static GlobalTransform)>,
+    cameras: Query<'w, 's, (&'static Camera, &'static GlobalTransform), Without<Node>>,
+}
+
+impl<'w, 's> MinimapProjection<'w, 's> {
+    /// Returns the UV coordinates of `world_position` in the image of the `minimap` node, see
+    /// [`Minimap::world_to_uv`].
+    pub fn world_to_uv(&self, minimap: Entity, world_position: Vec3) -> Option<Vec2> {
+        let (minimap, _, _) = self.minimaps.get(minimap).ok()?;
+        let (camera, camera_transform) = self.cameras.get(minimap.camera).ok()?;
+        Minimap::world_to_uv(camera, camera_transform, world_position)
+    }
+
+    /// Returns the logical UI position of `world_position` on the `minimap` node, to place a
+    /// marker over it for instance.
+    pub fn world_to_ui(&self, minimap: Entity, world_position: Vec3) -> Option<Vec2> {
+        let uv = self.world_to_uv(minimap, world_position)?;
+        let (_, node, node_transform) = self.minimaps.get(minimap).ok()?;
+        Some(Minimap::uv_to_ui(node, node_transform, uv))
+    }
+
+    /// Returns the ray through the world at the `uv` coordinates in the image of the `minimap`
+    /// node, see [`Minimap::uv_to_world_ray`].
+    pub fn uv_to_world_ray(&self, minimap: Entity, uv: Vec2) -> Option<Ray3d> {
+        let (minimap, _, _) = self.minimaps.get(minimap).ok()?;
+        let (camera, camera_transform) = self.cameras.get(minimap.camera).ok()?;
+        Minimap::uv_to_world_ray(camera, camera_transform, uv)
+    }
+
+    /// Returns the ray through the world at the logical UI `position` on the `minimap` node, or
+    /// `None` if the position isn't over the node.
+    pub fn ui_to_world_ray(&self, minimap: Entity, position: Vec2) -> Option<Ray3d> {
+        let (_, node, node_transform) = self.minimaps.get(minimap).ok()?;
+        let uv = Minimap::ui_to_uv(node, node_transform, position)?;
+        if !Rect::new(0.0, 0.0, 1.0, 1.0).contains(uv) {
+            return None;
+        }
+        self.uv_to_world_ray(minimap, uv)
+    }
+}
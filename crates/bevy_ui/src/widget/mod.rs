@@ -3,11 +3,13 @@
 mod button;
 mod image;
 mod label;
+mod minimap;
 #[cfg(feature = "bevy_text")]
 mod text;
 
 pub use button::*;
 pub use image::*;
 pub use label::*;
+pub use minimap::*;
 #[cfg(feature = "bevy_text")]
 pub use text::*;
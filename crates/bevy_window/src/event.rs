@@ -381,6 +381,9 @@ pub struct WindowThemeChanged {
 }
 
 /// Application lifetime events
+///
+/// On mobile platforms, these tell when the application goes to the background and comes back to
+/// the foreground, and when the system runs low on memory.
 #[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Reflect)]
 #[reflect(Debug, PartialEq)]
 #[cfg_attr(
@@ -394,7 +397,14 @@ pub enum ApplicationLifetime {
     /// The application was suspended.
     ///
     /// On Android, applications have one frame to react to this event before being paused in the background.
+    #[doc(alias = "background")]
     Suspended,
     /// The application was resumed.
+    #[doc(alias = "foreground")]
     Resumed,
+    /// The system is running low on memory, and may terminate the application if it doesn't free
+    /// some, such as by unloading cached assets.
+    ///
+    /// This is only sent on Android and iOS.
+    MemoryWarning,
 }
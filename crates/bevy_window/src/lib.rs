@@ -16,6 +16,7 @@ use bevy_a11y::Focus;
 mod cursor;
 mod event;
 mod raw_handle;
+mod safe_area;
 mod system;
 mod window;
 
@@ -23,6 +24,7 @@ pub use crate::raw_handle::*;
 
 pub use cursor::*;
 pub use event::*;
+pub use safe_area::*;
 pub use system::*;
 pub use window::*;
 
@@ -37,7 +39,7 @@ pub mod prelude {
     };
 }
 
-use bevy_app::prelude::*;
+use bevy_app::{prelude::*, Restart};
 
 impl Default for WindowPlugin {
     fn default() -> Self {
@@ -106,7 +108,8 @@ impl Plugin for WindowPlugin {
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
             .add_event::<WindowThemeChanged>()
-            .add_event::<ApplicationLifetime>();
+            .add_event::<ApplicationLifetime>()
+            .init_resource::<SafeArea>();
 
         if let Some(primary_window) = &self.primary_window {
             let initial_focus = app
@@ -118,6 +121,7 @@ impl Plugin for WindowPlugin {
                 **focus = Some(initial_focus);
             }
         }
+        app.add_systems(Restart, recreate_windows(self.primary_window.clone()));
 
         match self.exit_condition {
             ExitCondition::OnPrimaryClosed => {
@@ -157,7 +161,9 @@ impl Plugin for WindowPlugin {
 
         // Register window descriptor and related types
         app.register_type::<Window>()
-            .register_type::<PrimaryWindow>();
+            .register_type::<PrimaryWindow>()
+            .register_type::<EmbeddedWindow>()
+            .register_type::<SafeArea>();
     }
 }
 
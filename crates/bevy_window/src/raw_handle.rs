@@ -64,6 +64,29 @@ impl RawHandleWrapper {
         })
     }
 
+    /// Creates a `RawHandleWrapper` from the raw handles of a window owned outside of bevy, to
+    /// render into an [`EmbeddedWindow`](crate::EmbeddedWindow).
+    ///
+    /// `owner` is kept alive as long as this wrapper or any of its clones exists, like the window
+    /// of a [`WindowWrapper`]. It is typically the host's handle to its surface, or a guard
+    /// telling the host when bevy is done with it. The renderer keeps a clone while it has frames
+    /// in flight, so `owner` can outlive the entity holding this wrapper by a few updates.
+    ///
+    /// # Safety
+    ///
+    /// The handles must be valid, and stay valid as long as `owner` isn't dropped.
+    pub unsafe fn from_raw(
+        owner: Arc<dyn Any + Send + Sync>,
+        window_handle: RawWindowHandle,
+        display_handle: RawDisplayHandle,
+    ) -> RawHandleWrapper {
+        RawHandleWrapper {
+            _window: owner,
+            window_handle,
+            display_handle,
+        }
+    }
+
     /// Returns a [`HasWindowHandle`] + [`HasDisplayHandle`] impl, which exposes [`WindowHandle`] and [`DisplayHandle`].
     ///
     /// # Safety
@@ -116,3 +139,29 @@ impl HasDisplayHandle for ThreadLockedRawWindowHandleWrapper {
         Ok(unsafe { DisplayHandle::borrow_raw(self.0.display_handle) })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::RawHandleWrapper;
+    use raw_window_handle::{RawDisplayHandle, RawWindowHandle, WebDisplayHandle, WebWindowHandle};
+    use std::sync::Arc;
+
+    #[test]
+    fn from_raw_keeps_the_owner_alive() {
+        let owner = Arc::new(());
+        // SAFETY: The handles are never used.
+        let wrapper = unsafe {
+            RawHandleWrapper::from_raw(
+                owner.clone(),
+                RawWindowHandle::Web(WebWindowHandle::new(1)),
+                RawDisplayHandle::Web(WebDisplayHandle::new()),
+            )
+        };
+        let clone = wrapper.clone();
+
+        drop(wrapper);
+        assert_eq!(Arc::strong_count(&owner), 2);
+        drop(clone);
+        assert_eq!(Arc::strong_count(&owner), 1);
+    }
+}
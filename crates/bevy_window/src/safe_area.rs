@@ -0,0 +1,46 @@
+use bevy_ecs::{reflect::ReflectResource, system::Resource};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+
+#[cfg(feature = "serialize")]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+/// The insets of the primary window that may be covered by the platform, such as a notch, rounded
+/// corners or a home indicator.
+///
+/// UI should keep interactive and important content within the safe area. The insets are in
+/// logical pixels from each edge of the window, and are updated by the windowing backend when
+/// they change, such as when the device rotates. They are zero on platforms that don't report a
+/// safe area.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Resource, Debug, PartialEq, Default)]
+#[cfg_attr(
+    feature = "serialize",
+    derive(serde::Serialize, serde::Deserialize),
+    reflect(Serialize, Deserialize)
+)]
+pub struct SafeArea {
+    /// The inset from the top edge of the window.
+    pub top: f32,
+    /// The inset from the bottom edge of the window.
+    pub bottom: f32,
+    /// The inset from the left edge of the window.
+    pub left: f32,
+    /// The inset from the right edge of the window.
+    pub right: f32,
+}
+
+impl SafeArea {
+    /// Returns `true` if the whole window is safe.
+    pub fn is_empty(&self) -> bool {
+        self.top == 0.0 && self.bottom == 0.0 && self.left == 0.0 && self.right == 0.0
+    }
+
+    /// Returns the safe area of a window of the given logical size, with its origin at the
+    /// top-left corner of the window.
+    pub fn rect(&self, window_size: Vec2) -> Rect {
+        let min = Vec2::new(self.left, self.top);
+        let max = window_size - Vec2::new(self.right, self.bottom);
+        Rect::from_corners(min, max.max(min))
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{ClosingWindow, PrimaryWindow, Window, WindowCloseRequested};
 
-use bevy_app::AppExit;
+use bevy_app::{AppExit, AppRestart, KeepOnRestart};
 use bevy_ecs::prelude::*;
 
 /// Exit the application when there are no open windows.
@@ -53,3 +53,25 @@ pub fn close_when_requested(
         commands.entity(event.window).insert(ClosingWindow);
     }
 }
+
+/// Returns a system that closes all windows and spawns the `primary_window` again when the app
+/// handles an [`AppRestart`] with [`recreate_windows`](AppRestart::recreate_windows).
+///
+/// This system is added to the [`Restart`](bevy_app::Restart) schedule by the
+/// [`WindowPlugin`](crate::WindowPlugin). The other windows are spawned again by the startup
+/// systems that spawned them.
+pub fn recreate_windows(
+    primary_window: Option<Window>,
+) -> impl FnMut(Commands, EventReader<AppRestart>, Query<Entity, With<Window>>) {
+    move |mut commands, mut restarts, windows| {
+        if !restarts.read().any(|restart| restart.recreate_windows) {
+            return;
+        }
+        for window in &windows {
+            commands.entity(window).despawn();
+        }
+        if let Some(primary_window) = &primary_window {
+            commands.spawn((primary_window.clone(), PrimaryWindow, KeepOnRestart));
+        }
+    }
+}
@@ -25,6 +25,27 @@ use crate::CursorIcon;
 #[reflect(Component)]
 pub struct PrimaryWindow;
 
+/// Marker [`Component`] for a [`Window`] whose native surface is owned by an application
+/// embedding bevy, such as a widget of a GUI toolkit or an editor shell.
+///
+/// Window backends don't create a native window for it. It is rendered into like any other
+/// window once it has a [`RawHandleWrapper`](crate::RawHandleWrapper), usually made with
+/// [`RawHandleWrapper::from_raw`](crate::RawHandleWrapper::from_raw) from the handles of the
+/// host's surface. The host is responsible for the rest of its lifecycle:
+/// - keeping the [`resolution`](Window::resolution) of the window in sync with its surface,
+/// - sending the input events of the surface, with [`World::send_event`] or, from other
+///   threads, with the `ExternalInputPlugin` of `bevy_winit`,
+/// - destroying the surface only once the owner given to
+///   [`RawHandleWrapper::from_raw`](crate::RawHandleWrapper::from_raw) is dropped.
+///
+/// To render into the host's surface instead of opening a window, disable the primary window
+/// of the [`WindowPlugin`](crate::WindowPlugin) and spawn this along with [`PrimaryWindow`].
+///
+/// [`World::send_event`]: bevy_ecs::world::World::send_event
+#[derive(Default, Debug, Component, PartialEq, Eq, Copy, Clone, Reflect)]
+#[reflect(Component)]
+pub struct EmbeddedWindow;
+
 /// Reference to a [`Window`], whether it be a direct link to a specific entity or
 /// a more vague defaulting choice.
 #[repr(C)]
@@ -0,0 +1,280 @@
+use std::{
+    marker::PhantomData,
+    sync::{
+        mpsc::{channel, Receiver, SendError, Sender},
+        Mutex,
+    },
+};
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_input::{
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    InputSystem,
+};
+use bevy_window::{
+    CursorEntered, CursorLeft, CursorMoved, Ime, RequestRedraw, WindowCloseRequested, WindowFocused,
+};
+use winit::event_loop::EventLoop;
+
+use crate::{EventLoopProxy, UserEvent};
+
+/// A [`Plugin`] that adds an [`ExternalEvents<T>`] resource, to send events of type `T` into the
+/// app from other threads.
+///
+/// Editors, IPC servers or hardware device drivers running on their own threads can use it to
+/// drive the app. Sending an event wakes up the event loop and runs an update, even in a
+/// reactive [`UpdateMode`](crate::UpdateMode), so the app doesn't need to poll for them.
+///
+/// This plugin must be added after the [`WinitPlugin`](crate::WinitPlugin). Without it, events
+/// are still delivered, but only on the next update.
+///
+/// ```no_run
+/// # use bevy_app::{App, Startup, Update};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_winit::{ExternalEvents, ExternalEventsPlugin};
+/// #[derive(Event)]
+/// struct Reload(String);
+///
+/// fn spawn_watcher(events: Res<ExternalEvents<Reload>>) {
+///     let sender = events.sender();
+///     std::thread::spawn(move || {
+///         // ... wait for a file to change
+///         let _ = sender.send(Reload("level.scn.ron".into()));
+///     });
+/// }
+///
+/// fn reload(mut events: EventReader<Reload>) {
+///     for Reload(path) in events.read() {
+///         println!("reloading {path}");
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(ExternalEventsPlugin::<Reload>::default())
+///     .add_systems(Startup, spawn_watcher)
+///     .add_systems(Update, reload)
+///     .run();
+/// ```
+pub struct ExternalEventsPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for ExternalEventsPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Event> Plugin for ExternalEventsPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let proxy = app
+            .world()
+            .get_non_send_resource::<EventLoop<UserEvent>>()
+            .map(EventLoop::create_proxy);
+        let (sender, receiver) = channel::<T>();
+
+        app.add_event::<T>()
+            .insert_resource(ExternalEvents {
+                sender: Mutex::new(ExternalEventSender { sender, proxy }),
+                receiver: Mutex::new(receiver),
+            })
+            .add_systems(
+                PreUpdate,
+                forward_external_events::<T>.in_set(ExternalEventsSystem),
+            );
+    }
+}
+
+/// The [`SystemSet`] of the systems sending the events of [`ExternalEvents`] to [`Events`], in
+/// [`PreUpdate`].
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExternalEventsSystem;
+
+/// The events of type `T` sent from other threads, added by the [`ExternalEventsPlugin<T>`].
+///
+/// Use [`ExternalEvents::sender`] to get a handle that can be moved to another thread. The events
+/// are sent to [`Events<T>`] in [`PreUpdate`], in the [`ExternalEventsSystem`] set.
+#[derive(Resource)]
+pub struct ExternalEvents<T: Event> {
+    sender: Mutex<ExternalEventSender<T>>,
+    receiver: Mutex<Receiver<T>>,
+}
+
+impl<T: Event> ExternalEvents<T> {
+    /// Returns a handle to send events of type `T` into the app from any thread.
+    pub fn sender(&self) -> ExternalEventSender<T> {
+        self.sender.lock().unwrap().clone()
+    }
+}
+
+/// A handle to send events of type `T` into the app from any thread, returned by
+/// [`ExternalEvents::sender`].
+pub struct ExternalEventSender<T> {
+    sender: Sender<T>,
+    proxy: Option<EventLoopProxy>,
+}
+
+impl<T> Clone for ExternalEventSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            proxy: self.proxy.clone(),
+        }
+    }
+}
+
+impl<T: Event> ExternalEventSender<T> {
+    /// Sends an event into the app, and wakes up the event loop to run an update.
+    ///
+    /// Returns the event in an error if the app has been dropped.
+    pub fn send(&self, event: T) -> Result<(), SendError<T>> {
+        self.sender.send(event)?;
+        if let Some(proxy) = &self.proxy {
+            // fails only when the event loop is exiting, in which case there is nothing to wake
+            let _ = proxy.send_event(RequestRedraw);
+        }
+        Ok(())
+    }
+}
+
+/// A [`Plugin`] that adds an [`ExternalEventsPlugin`] for each window input event, for apps
+/// receiving their input from other threads.
+///
+/// This is typically used when bevy renders into an [`EmbeddedWindow`](bevy_window::EmbeddedWindow)
+/// of another application, which forwards the input of its surface with the senders of the
+/// [`ExternalEvents`] of [`KeyboardInput`], [`MouseButtonInput`], [`MouseMotion`],
+/// [`MouseWheel`], [`TouchInput`], [`CursorMoved`], [`CursorEntered`], [`CursorLeft`], [`Ime`],
+/// [`WindowFocused`] and [`WindowCloseRequested`].
+///
+/// The [`ExternalEventsSystem`] set runs before the [`InputSystem`] set, so the input sent before
+/// an update is visible to the input resources of that same update.
+#[derive(Default)]
+pub struct ExternalInputPlugin;
+
+impl Plugin for ExternalInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExternalEventsPlugin::<KeyboardInput>::default(),
+            ExternalEventsPlugin::<MouseButtonInput>::default(),
+            ExternalEventsPlugin::<MouseMotion>::default(),
+            ExternalEventsPlugin::<MouseWheel>::default(),
+            ExternalEventsPlugin::<TouchInput>::default(),
+            ExternalEventsPlugin::<CursorMoved>::default(),
+            ExternalEventsPlugin::<CursorEntered>::default(),
+            ExternalEventsPlugin::<CursorLeft>::default(),
+            ExternalEventsPlugin::<Ime>::default(),
+            ExternalEventsPlugin::<WindowFocused>::default(),
+            ExternalEventsPlugin::<WindowCloseRequested>::default(),
+        ))
+        .configure_sets(PreUpdate, ExternalEventsSystem.before(InputSystem));
+    }
+}
+
+/// Sends the events received by [`ExternalEvents<T>`] to [`Events<T>`].
+pub fn forward_external_events<T: Event>(
+    external_events: Res<ExternalEvents<T>>,
+    mut events: EventWriter<T>,
+) {
+    let receiver = external_events.receiver.lock().unwrap();
+    events.send_batch(receiver.try_iter());
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::{App, Update};
+    use bevy_ecs::prelude::*;
+
+    use bevy_input::{
+        keyboard::{Key, KeyCode, KeyboardInput},
+        ButtonInput, ButtonState, InputPlugin,
+    };
+    use bevy_window::{EmbeddedWindow, Window};
+
+    use super::{ExternalEvents, ExternalEventsPlugin, ExternalInputPlugin};
+
+    #[derive(Event, Debug, PartialEq)]
+    struct Message(u32);
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<u32>);
+
+    fn receive(mut events: EventReader<Message>, mut received: ResMut<Received>) {
+        received
+            .0
+            .extend(events.read().map(|Message(value)| *value));
+    }
+
+    #[test]
+    fn events_sent_from_other_threads_are_read_in_order() {
+        let mut app = App::new();
+        app.add_plugins(ExternalEventsPlugin::<Message>::default())
+            .init_resource::<Received>()
+            .add_systems(Update, receive);
+
+        let sender = app.world().resource::<ExternalEvents<Message>>().sender();
+        std::thread::spawn(move || {
+            for value in 0..10 {
+                sender.send(Message(value)).unwrap();
+            }
+        })
+        .join()
+        .unwrap();
+
+        app.update();
+        assert_eq!(
+            app.world().resource::<Received>().0,
+            (0..10).collect::<Vec<_>>()
+        );
+
+        // The events are only read once.
+        app.update();
+        assert_eq!(app.world().resource::<Received>().0.len(), 10);
+    }
+
+    #[test]
+    fn sending_after_the_app_is_dropped_returns_the_event() {
+        let mut app = App::new();
+        app.add_plugins(ExternalEventsPlugin::<Message>::default());
+        let sender = app.world().resource::<ExternalEvents<Message>>().sender();
+
+        drop(app);
+        let error = std::thread::spawn(move || sender.send(Message(1)))
+            .join()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(error.0, Message(1));
+    }
+
+    #[test]
+    fn embedded_window_input_is_visible_in_the_same_update() {
+        let mut app = App::new();
+        app.add_plugins((InputPlugin, ExternalInputPlugin));
+        let window = app
+            .world_mut()
+            .spawn((Window::default(), EmbeddedWindow))
+            .id();
+
+        // The host application forwards the input of its surface from its own thread.
+        let sender = app
+            .world()
+            .resource::<ExternalEvents<KeyboardInput>>()
+            .sender();
+        std::thread::spawn(move || {
+            sender
+                .send(KeyboardInput {
+                    key_code: KeyCode::Space,
+                    logical_key: Key::Space,
+                    state: ButtonState::Pressed,
+                    window,
+                })
+                .unwrap();
+        })
+        .join()
+        .unwrap();
+
+        app.update();
+        let keys = app.world().resource::<ButtonInput<KeyCode>>();
+        assert!(keys.just_pressed(KeyCode::Space));
+    }
+}
@@ -14,6 +14,7 @@
 
 pub mod accessibility;
 mod converters;
+mod external_events;
 mod system;
 mod winit_config;
 pub mod winit_event;
@@ -24,6 +25,7 @@ use std::sync::mpsc::{sync_channel, SyncSender};
 use approx::relative_eq;
 use bevy_a11y::AccessibilityRequested;
 use bevy_utils::Instant;
+pub use external_events::*;
 pub use system::create_windows;
 use system::{changed_windows, despawn_windows, CachedWindow};
 use winit::dpi::{LogicalSize, PhysicalSize};
@@ -46,7 +48,7 @@ use bevy_utils::tracing::{error, trace, warn};
 #[allow(deprecated)]
 use bevy_window::{
     exit_on_all_closed, ApplicationLifetime, CursorEntered, CursorLeft, CursorMoved,
-    FileDragAndDrop, Ime, ReceivedCharacter, RequestRedraw, Window,
+    EmbeddedWindow, FileDragAndDrop, Ime, ReceivedCharacter, RequestRedraw, Window,
     WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowDestroyed,
     WindowFocused, WindowMoved, WindowOccluded, WindowResized, WindowScaleFactorChanged,
     WindowThemeChanged,
@@ -144,6 +146,10 @@ impl Plugin for WinitPlugin {
                     .chain(),
             );
 
+        // Only iOS reports a safe area, see `update_safe_area`.
+        #[cfg(target_os = "ios")]
+        app.add_systems(bevy_app::PreUpdate, system::update_safe_area);
+
         app.add_plugins(AccessKitPlugin);
 
         let event_loop = event_loop_builder
@@ -192,6 +198,9 @@ struct WinitAppRunnerState {
     window_event_received: bool,
     /// Is `true` if a new [`DeviceEvent`] has been received since the last update.
     device_event_received: bool,
+    /// Is `true` if a new [`Event::UserEvent`] has been received since the last update, for
+    /// example from an [`ExternalEventSender`].
+    user_event_received: bool,
     /// Is `true` if the app has requested a redraw since the last update.
     redraw_requested: bool,
     /// Is `true` if enough time has elapsed since `last_update` to run another update.
@@ -204,6 +213,7 @@ impl WinitAppRunnerState {
     fn reset_on_update(&mut self) {
         self.window_event_received = false;
         self.device_event_received = false;
+        self.user_event_received = false;
     }
 }
 
@@ -214,6 +224,7 @@ impl Default for WinitAppRunnerState {
             update_mode: UpdateMode::Continuous,
             window_event_received: false,
             device_event_received: false,
+            user_event_received: false,
             redraw_requested: false,
             wait_elapsed: false,
             // 3 seems to be enough, 5 is a safe margin
@@ -244,7 +255,7 @@ impl UpdateState {
 /// The parameters of the [`create_windows`] system.
 pub type CreateWindowParams<'w, 's, F = ()> = (
     Commands<'w, 's>,
-    Query<'w, 's, (Entity, &'static mut Window), F>,
+    Query<'w, 's, (Entity, &'static mut Window), (F, Without<EmbeddedWindow>)>,
     EventWriter<'w, WindowCreated>,
     NonSendMut<'w, WinitWindows>,
     NonSendMut<'w, AccessKitAdapters>,
@@ -254,7 +265,8 @@ pub type CreateWindowParams<'w, 's, F = ()> = (
 
 /// The [`winit::event_loop::EventLoopProxy`] with the specific [`winit::event::Event::UserEvent`] used in the [`winit_runner`].
 ///
-/// The `EventLoopProxy` can be used to request a redraw from outside bevy.
+/// The `EventLoopProxy` can be used to request a redraw from outside bevy. To send your own
+/// events from other threads, see the [`ExternalEventsPlugin`].
 ///
 /// Use `NonSend<EventLoopProxy>` to receive this resource.
 pub type EventLoopProxy = winit::event_loop::EventLoopProxy<UserEvent>;
@@ -774,7 +786,11 @@ fn handle_winit_event(
             }
             runner_state.activity_state = UpdateState::WillResume;
         }
+        Event::MemoryWarning => {
+            winit_events.send(ApplicationLifetime::MemoryWarning);
+        }
         Event::UserEvent(RequestRedraw) => {
+            runner_state.user_event_received = true;
             runner_state.redraw_requested = true;
         }
         _ => (),
@@ -800,9 +816,12 @@ fn should_update(runner_state: &WinitAppRunnerState, update_mode: UpdateMode) ->
             runner_state.wait_elapsed
                 || runner_state.window_event_received
                 || runner_state.device_event_received
+                || runner_state.user_event_received
         }
         UpdateMode::ReactiveLowPower { .. } => {
-            runner_state.wait_elapsed || runner_state.window_event_received
+            runner_state.wait_elapsed
+                || runner_state.window_event_received
+                || runner_state.user_event_received
         }
     };
 
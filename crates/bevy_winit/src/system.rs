@@ -30,7 +30,7 @@ use crate::{
 };
 
 /// Creates new windows on the [`winit`] backend for each entity with a newly-added
-/// [`Window`] component.
+/// [`Window`] component, except [embedded windows](bevy_window::EmbeddedWindow).
 ///
 /// If any of these entities are missing required components, those will be added with their
 /// default values.
@@ -327,3 +327,43 @@ pub(crate) fn changed_windows(
         cache.window = window.clone();
     }
 }
+
+/// Updates the [`SafeArea`](bevy_window::SafeArea) from the primary window.
+///
+/// On iOS, winit reports the safe area as the inner rect of the window, while its outer rect
+/// covers the whole screen.
+#[cfg(target_os = "ios")]
+pub(crate) fn update_safe_area(
+    winit_windows: bevy_ecs::system::NonSend<WinitWindows>,
+    primary_window: Query<Entity, With<bevy_window::PrimaryWindow>>,
+    mut safe_area: bevy_ecs::system::ResMut<bevy_window::SafeArea>,
+) {
+    use bevy_ecs::change_detection::DetectChangesMut;
+
+    let Ok(entity) = primary_window.get_single() else {
+        return;
+    };
+    let Some(winit_window) = winit_windows.get_window(entity) else {
+        return;
+    };
+    let (Ok(inner_position), Ok(outer_position)) =
+        (winit_window.inner_position(), winit_window.outer_position())
+    else {
+        return;
+    };
+    let inner_size = winit_window.inner_size();
+    let outer_size = winit_window.outer_size();
+    let scale_factor = winit_window.scale_factor() as f32;
+
+    let left = inner_position.x - outer_position.x;
+    let top = inner_position.y - outer_position.y;
+    let right = outer_size.width as i32 - inner_size.width as i32 - left;
+    let bottom = outer_size.height as i32 - inner_size.height as i32 - top;
+    let inset = |physical: i32| physical.max(0) as f32 / scale_factor;
+    safe_area.set_if_neq(bevy_window::SafeArea {
+        top: inset(top),
+        bottom: inset(bottom),
+        left: inset(left),
+        right: inset(right),
+    });
+}
@@ -75,6 +75,7 @@ pub enum UpdateMode {
     /// - new [window](`winit::event::WindowEvent`) or [raw input](`winit::event::DeviceEvent`)
     /// events have appeared
     /// - a redraw has been requested with the [`EventLoopProxy`](crate::EventLoopProxy)
+    /// - an event has been sent with an [`ExternalEventSender`](crate::ExternalEventSender)
     Reactive {
         /// The approximate time from the start of one update to the next.
         ///
@@ -88,6 +89,7 @@ pub enum UpdateMode {
     /// - a redraw has been requested by [`RequestRedraw`](bevy_window::RequestRedraw)
     /// - new [window events](`winit::event::WindowEvent`) have appeared
     /// - a redraw has been requested with the [`EventLoopProxy`](crate::EventLoopProxy)
+    /// - an event has been sent with an [`ExternalEventSender`](crate::ExternalEventSender)
     ///
     /// **Note:** Unlike [`Reactive`](`UpdateMode::Reactive`), this mode will ignore events that
     /// don't come from interacting with a window, like [`MouseMotion`](winit::event::DeviceEvent::MouseMotion).
@@ -0,0 +1,93 @@
+//! Draws thousands of cubes with a single draw call using an [`InstancedMesh`].
+//!
+//! Press space to cycle how the instances outside of the view are culled.
+
+use bevy::{
+    color::palettes::css::WHITE,
+    pbr::{InstanceCulling, InstancedMesh, MeshInstance},
+    prelude::*,
+};
+
+const GRID_SIZE: i32 = 50;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (rotate, cycle_culling))
+        .run();
+}
+
+fn setup(mut commands: Commands, mut meshes: ResMut<Assets<Mesh>>) {
+    // A grid of cubes, colored by their position in the grid.
+    let instances = (0..GRID_SIZE * GRID_SIZE).map(|index| {
+        let (x, z) = (index % GRID_SIZE, index / GRID_SIZE);
+        let (u, v) = (
+            x as f32 / (GRID_SIZE - 1) as f32,
+            z as f32 / (GRID_SIZE - 1) as f32,
+        );
+        let offset = (GRID_SIZE - 1) as f32 / 2.0;
+        MeshInstance::new(
+            Transform::from_xyz(x as f32 - offset, 0.0, z as f32 - offset)
+                .with_scale(Vec3::splat(0.6)),
+        )
+        .with_color(Color::srgb(u, 0.5, v))
+    });
+
+    // The entity needs a mesh and the components of a `SpatialBundle`, but no material.
+    commands.spawn((
+        meshes.add(Cuboid::default()),
+        SpatialBundle::default(),
+        InstancedMesh::new(instances),
+    ));
+
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 12.0, 30.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    commands.spawn(
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                color: WHITE.into(),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn rotate(time: Res<Time>, mut instanced_meshes: Query<&mut Transform, With<InstancedMesh>>) {
+    for mut transform in &mut instanced_meshes {
+        transform.rotate_y(time.delta_seconds() * 0.2);
+    }
+}
+
+fn cycle_culling(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut instanced_meshes: Query<&mut InstancedMesh>,
+    mut texts: Query<&mut Text>,
+) {
+    for mut instanced_mesh in &mut instanced_meshes {
+        if keyboard.just_pressed(KeyCode::Space) {
+            instanced_mesh.culling = match instanced_mesh.culling {
+                InstanceCulling::Cpu => InstanceCulling::Gpu,
+                InstanceCulling::Gpu => InstanceCulling::None,
+                InstanceCulling::None => InstanceCulling::Cpu,
+            };
+        }
+        for mut text in &mut texts {
+            text.sections[0].value = format!(
+                "{} instances\nCulling: {:?} (space to cycle)",
+                instanced_mesh.instances.len(),
+                instanced_mesh.culling
+            );
+        }
+    }
+}
@@ -0,0 +1,110 @@
+//! Highlights meshes with outlines, such as the selection of an editor.
+//!
+//! Press space to toggle whether the outlines are visible through the meshes in front of them,
+//! like the wall in front of the torus.
+
+use bevy::{
+    pbr::{OutlineDepthMode, Outlined},
+    prelude::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, (rotate, toggle_depth_mode))
+        .run();
+}
+
+#[derive(Component)]
+struct Rotate;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Outlines extrude the mesh along its normals, so outlined meshes need smooth normals.
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Sphere::new(0.5)),
+            material: materials.add(Color::srgb(0.3, 0.5, 0.8)),
+            transform: Transform::from_xyz(-1.2, 0.5, 0.0),
+            ..default()
+        },
+        Outlined::new(Color::srgb(1.0, 0.5, 0.0), 4.0),
+    ));
+
+    // The outline of the torus is hidden by the wall in front of it, unless it's always visible.
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Torus::new(0.3, 0.6)),
+            material: materials.add(Color::srgb(0.8, 0.3, 0.3)),
+            transform: Transform::from_xyz(1.2, 0.5, -1.0)
+                .with_rotation(Quat::from_rotation_x(1.0)),
+            ..default()
+        },
+        Outlined::new(Color::srgb(0.2, 1.0, 1.0), 3.0),
+        Rotate,
+    ));
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Cuboid::new(1.5, 1.2, 0.1)),
+        material: materials.add(Color::srgb(0.7, 0.7, 0.7)),
+        transform: Transform::from_xyz(1.2, 0.6, 0.2),
+        ..default()
+    });
+
+    // ground plane
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(8.0, 8.0)),
+        material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+        ..default()
+    });
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(3.0, 6.0, 4.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 2.5, 5.0).looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::Y),
+        ..default()
+    });
+
+    commands.spawn(
+        TextBundle::from_section(
+            "Press space to toggle the outline depth mode",
+            TextStyle::default(),
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(12.0),
+            ..default()
+        }),
+    );
+}
+
+fn rotate(mut query: Query<&mut Transform, With<Rotate>>, time: Res<Time>) {
+    for mut transform in &mut query {
+        transform.rotate_y(time.delta_seconds());
+    }
+}
+
+fn toggle_depth_mode(keyboard: Res<ButtonInput<KeyCode>>, mut outlines: Query<&mut Outlined>) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut outlined in &mut outlines {
+        outlined.depth_mode = match outlined.depth_mode {
+            OutlineDepthMode::DepthTested => OutlineDepthMode::AlwaysVisible,
+            OutlineDepthMode::AlwaysVisible => OutlineDepthMode::DepthTested,
+        };
+    }
+}
@@ -173,7 +173,7 @@ fn handle_lifetime(
         match event {
             ApplicationLifetime::Suspended => music_controller.single().pause(),
             ApplicationLifetime::Resumed => music_controller.single().play(),
-            ApplicationLifetime::Started => (),
+            ApplicationLifetime::Started | ApplicationLifetime::MemoryWarning => (),
         }
     }
 }
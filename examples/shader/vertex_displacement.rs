@@ -0,0 +1,85 @@
+//! Demonstrates displacing the vertices of a mesh with a `MaterialExtension` of the `StandardMaterial`.
+//!
+//! The same displacement shader is used in the main pass and in the shadow pass, so the shadow of the mesh
+//! follows its animation.
+
+use bevy::{
+    color::palettes::css::{GOLD, SILVER},
+    pbr::{ExtendedMaterial, MaterialExtension},
+    prelude::*,
+    render::render_resource::*,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(MaterialPlugin::<
+            ExtendedMaterial<StandardMaterial, WaveExtension>,
+        >::default())
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut wave_materials: ResMut<Assets<ExtendedMaterial<StandardMaterial, WaveExtension>>>,
+) {
+    // displaced sphere, with enough vertices for the waves to be smooth
+    commands.spawn(MaterialMeshBundle {
+        mesh: meshes.add(Sphere::new(1.0).mesh().uv(64, 32)),
+        transform: Transform::from_xyz(0.0, 1.5, 0.0),
+        material: wave_materials.add(ExtendedMaterial {
+            base: StandardMaterial {
+                base_color: GOLD.into(),
+                ..default()
+            },
+            extension: WaveExtension {
+                amplitude: 0.15,
+                frequency: 6.0,
+            },
+        }),
+        ..default()
+    });
+
+    // ground plane, receiving the displaced shadow
+    commands.spawn(PbrBundle {
+        mesh: meshes.add(Plane3d::default().mesh().size(10.0, 10.0)),
+        material: materials.add(Color::from(SILVER)),
+        ..default()
+    });
+
+    // light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_xyz(2.0, 4.0, 1.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // camera
+    commands.spawn(Camera3dBundle {
+        transform: Transform::from_xyz(-3.0, 3.5, 6.0)
+            .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+}
+
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+struct WaveExtension {
+    // We need to ensure that the bindings of the base material and the extension do not conflict,
+    // so we start from binding slot 100, leaving slots 0-99 for the base material.
+    #[uniform(100)]
+    amplitude: f32,
+    #[uniform(100)]
+    frequency: f32,
+}
+
+impl MaterialExtension for WaveExtension {
+    fn vertex_displacement_shader() -> ShaderRef {
+        "shaders/vertex_displacement.wgsl".into()
+    }
+}